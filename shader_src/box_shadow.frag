@@ -0,0 +1,120 @@
+#version 330 core
+
+// Paints the shadow of a rounded rectangle blurred by a Gaussian, computing its coverage
+// analytically the same way `BoxShadow::alpha_at` does: along each row the shape is a single span,
+// whose blur along the row is a difference of error functions, and the blur along columns is
+// integrated from a few sampled rows.  Positions are in pixels, with the y-axis pointing down.
+// Rectangles are given as their left, top, right, and bottom edges, and the radii of their corners
+// in the order top-left, top-right, bottom-right, and bottom-left.
+
+const int BLUR_SAMPLES = 8;
+const float PI = 3.141592653589793;
+
+in vec2 PixelPositionOut;
+
+// The shape of the shadow before it's blurred.
+uniform vec4 ShapeRect;
+uniform vec4 ShapeHorizontalRadii;
+uniform vec4 ShapeVerticalRadii;
+// The box an outer shadow isn't painted within, or an inset shadow is only painted within.
+uniform vec4 ClipRect;
+uniform vec4 ClipHorizontalRadii;
+uniform vec4 ClipVerticalRadii;
+// The standard deviation of the Gaussian, which is 0 if the shadow isn't blurred.
+uniform float Sigma;
+// 1 for an inset shadow, 0 for an outer one.
+uniform int Inset;
+uniform vec4 ShadowColor;
+
+out vec4 Color;
+
+// The approximate signed distance from `p` to the edge of a rounded rectangle, which is negative
+// inside it, the same as in `rounded_rect.frag`.
+float signed_distance(vec2 p, vec4 rect, vec4 horizontal_radii, vec4 vertical_radii)
+{
+    vec2 center = (rect.xy + rect.zw) * 0.5;
+    int corner = p.y < center.y ? (p.x < center.x ? 0 : 1) : (p.x < center.x ? 3 : 2);
+    vec2 radii = vec2(horizontal_radii[corner], vertical_radii[corner]);
+    vec2 corner_point = vec2(corner == 0 || corner == 3 ? rect.x : rect.z,
+                             corner < 2 ? rect.y : rect.w);
+    vec2 outwards = sign(corner_point - center);
+    vec2 ellipse_center = corner_point - outwards * radii;
+    vec2 q = (p - ellipse_center) * outwards;
+    if (radii.x > 0.0 && radii.y > 0.0 && q.x > 0.0 && q.y > 0.0) {
+        float k0 = length(q / radii);
+        float k1 = length(q / (radii * radii));
+        return k0 * (k0 - 1.0) / k1;
+    }
+    vec2 outside = max(rect.xy - p, p - rect.zw);
+    return max(outside.x, outside.y);
+}
+
+float gaussian(float x, float sigma)
+{
+    return exp(-(x * x) / (2.0 * sigma * sigma)) / (sqrt(2.0 * PI) * sigma);
+}
+
+// An approximation of the error function, within 5e-4.
+vec2 erf(vec2 x)
+{
+    vec2 a = abs(x);
+    vec2 t = 1.0 + (0.278393 + (0.230389 + 0.078108 * a * a) * a) * a;
+    t *= t;
+    return sign(x) * (1.0 - 1.0 / (t * t));
+}
+
+// How far a rounded corner with the given radii curves inwards from the side of its rectangle,
+// `from_edge` from the top or bottom edge.
+float indent(float radius_x, float radius_y, float from_edge)
+{
+    if (radius_x <= 0.0 || radius_y <= 0.0 || from_edge >= radius_y) {
+        return 0.0;
+    }
+    float above_center = (radius_y - from_edge) / radius_y;
+    return radius_x * (1.0 - sqrt(max(1.0 - above_center * above_center, 0.0)));
+}
+
+// How much of the shape is covered at `p` once blurred.
+float blurred_coverage(vec2 p)
+{
+    if (ShapeRect.z <= ShapeRect.x || ShapeRect.w <= ShapeRect.y) {
+        return 0.0;
+    }
+    if (Sigma <= 0.0) {
+        return clamp(0.5 - signed_distance(p, ShapeRect, ShapeHorizontalRadii, ShapeVerticalRadii),
+                     0.0, 1.0);
+    }
+    float start = max(p.y - 3.0 * Sigma, ShapeRect.y);
+    float end = min(p.y + 3.0 * Sigma, ShapeRect.w);
+    if (start >= end) {
+        return 0.0;
+    }
+    float step = (end - start) / float(BLUR_SAMPLES);
+    float scale = 1.0 / (sqrt(2.0) * Sigma);
+    float middle = (ShapeRect.y + ShapeRect.w) * 0.5;
+    float coverage = 0.0;
+    for (int i = 0; i < BLUR_SAMPLES; i++) {
+        float y = start + step * (float(i) + 0.5);
+        bool top = y < middle;
+        float from_edge = top ? y - ShapeRect.y : ShapeRect.w - y;
+        int left_corner = top ? 0 : 3;
+        int right_corner = top ? 1 : 2;
+        float left = ShapeRect.x + indent(ShapeHorizontalRadii[left_corner],
+                                          ShapeVerticalRadii[left_corner], from_edge);
+        float right = ShapeRect.z - indent(ShapeHorizontalRadii[right_corner],
+                                           ShapeVerticalRadii[right_corner], from_edge);
+        vec2 edges = erf((p.x - vec2(left, right)) * scale);
+        coverage += 0.5 * (edges.x - edges.y) * gaussian(p.y - y, Sigma) * step;
+    }
+    return clamp(coverage, 0.0, 1.0);
+}
+
+void main()
+{
+    vec2 p = PixelPositionOut;
+    float shadow = blurred_coverage(p);
+    float clip = clamp(0.5 - signed_distance(p, ClipRect, ClipHorizontalRadii, ClipVerticalRadii),
+                       0.0, 1.0);
+    float alpha = Inset == 1 ? (1.0 - shadow) * clip : shadow * (1.0 - clip);
+    Color = vec4(ShadowColor.rgb, ShadowColor.a * alpha);
+}
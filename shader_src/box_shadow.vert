@@ -0,0 +1,12 @@
+#version 330 core
+
+layout (location = 0) in vec2 Position;
+layout (location = 1) in vec2 PixelPosition;
+
+out vec2 PixelPositionOut;
+
+void main()
+{
+    gl_Position = vec4(Position, 0.0, 1.0);
+    PixelPositionOut = PixelPosition;
+}
@@ -0,0 +1,59 @@
+#version 330 core
+
+// Fills tiles with a linear or radial gradient.  Positions are in pixels relative to the top-left
+// corner of each tile, with the y-axis pointing down.  A linear gradient is given as the start and
+// end of its gradient line, and a radial gradient as its center and horizontal and vertical radii.
+// Colors are interpolated in premultiplied alpha.
+
+const int MAX_STOPS = 16;
+
+in vec2 TilePositionOut;
+
+// 0 for a linear gradient, 1 for a radial one.
+uniform int Shape;
+uniform vec4 Geometry;
+uniform int StopCount;
+// How far along the gradient line or ray each stop is, in non-decreasing order.
+uniform float StopOffsets[MAX_STOPS];
+uniform vec4 StopColors[MAX_STOPS];
+// How far apart repetitions of the stops are, or 0 if they don't repeat.
+uniform float Period;
+
+out vec4 Color;
+
+float gradient_offset(vec2 p)
+{
+    if (Shape == 0) {
+        vec2 line = Geometry.zw - Geometry.xy;
+        float line_length = length(line);
+        return line_length == 0.0 ? 0.0 : dot(p - Geometry.xy, line) / line_length;
+    }
+    vec2 delta = p - Geometry.xy;
+    return length(vec2(delta.x, delta.y * Geometry.z / Geometry.w));
+}
+
+vec4 premultiplied(vec4 color)
+{
+    return vec4(color.rgb * color.a, color.a);
+}
+
+void main()
+{
+    float offset = gradient_offset(TilePositionOut);
+    if (Period > 0.0) {
+        offset = StopOffsets[0] + mod(offset - StopOffsets[0], Period);
+    }
+    vec4 color = premultiplied(StopColors[StopCount - 1]);
+    if (offset < StopOffsets[0]) {
+        color = premultiplied(StopColors[0]);
+    } else {
+        for (int i = 1; i < StopCount; i++) {
+            if (offset < StopOffsets[i]) {
+                float progress = (offset - StopOffsets[i - 1]) / (StopOffsets[i] - StopOffsets[i - 1]);
+                color = mix(premultiplied(StopColors[i - 1]), premultiplied(StopColors[i]), progress);
+                break;
+            }
+        }
+    }
+    Color = color.a > 0.0 ? vec4(color.rgb / color.a, color.a) : vec4(0.0);
+}
@@ -0,0 +1,12 @@
+#version 330 core
+
+layout (location = 0) in vec2 Position;
+layout (location = 1) in vec2 TilePosition;
+
+out vec2 TilePositionOut;
+
+void main()
+{
+    gl_Position = vec4(Position, 0.0, 1.0);
+    TilePositionOut = TilePosition;
+}
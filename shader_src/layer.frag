@@ -0,0 +1,13 @@
+#version 330 core
+in vec2 TexCoords;
+out vec4 color;
+
+uniform sampler2D layer;
+// The opacity of the group painted to the layer.
+uniform float Opacity;
+
+void main()
+{
+    // Layers hold colors premultiplied by their alpha, so the opacity applies to every channel.
+    color = texture(layer, TexCoords) * Opacity;
+}
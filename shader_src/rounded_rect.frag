@@ -0,0 +1,53 @@
+#version 330 core
+
+// Fills a rounded rectangle, with its edges anti-aliased by how far each fragment is from them.
+// Positions are in pixels, with the y-axis pointing down, and the rectangle is given as its left,
+// top, right, and bottom edges.  The radii of its corners are given in the order top-left,
+// top-right, bottom-right, and bottom-left.
+
+in VS_OUTPUT {
+    vec2 PixelPosition;
+    vec4 Rect;
+    vec4 HorizontalRadii;
+    vec4 VerticalRadii;
+    vec4 Color;
+} IN;
+
+// Fragments covered no more than this are discarded, which is how the rectangle is drawn into the
+// stencil buffer without anti-aliasing.
+uniform float MinCoverage;
+
+out vec4 Color;
+
+// The approximate signed distance from `p` to the edge of the rounded rectangle, which is negative
+// inside it.
+float signed_distance(vec2 p)
+{
+    vec2 center = (IN.Rect.xy + IN.Rect.zw) * 0.5;
+    int corner = p.y < center.y ? (p.x < center.x ? 0 : 1) : (p.x < center.x ? 3 : 2);
+    vec2 radii = vec2(IN.HorizontalRadii[corner], IN.VerticalRadii[corner]);
+    vec2 corner_point = vec2(corner == 0 || corner == 3 ? IN.Rect.x : IN.Rect.z,
+                             corner < 2 ? IN.Rect.y : IN.Rect.w);
+    // Where the ellipse of the corner is centered, and which way is outwards from it.
+    vec2 outwards = sign(corner_point - center);
+    vec2 ellipse_center = corner_point - outwards * radii;
+    vec2 q = (p - ellipse_center) * outwards;
+    if (radii.x > 0.0 && radii.y > 0.0 && q.x > 0.0 && q.y > 0.0) {
+        // The distance to an ellipse, approximated by its implicit function divided by the length
+        // of its gradient.
+        float k0 = length(q / radii);
+        float k1 = length(q / (radii * radii));
+        return k0 * (k0 - 1.0) / k1;
+    }
+    vec2 outside = max(IN.Rect.xy - p, p - IN.Rect.zw);
+    return max(outside.x, outside.y);
+}
+
+void main()
+{
+    float coverage = clamp(0.5 - signed_distance(IN.PixelPosition), 0.0, 1.0);
+    if (coverage <= MinCoverage) {
+        discard;
+    }
+    Color = vec4(IN.Color.rgb, IN.Color.a * coverage);
+}
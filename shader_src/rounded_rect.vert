@@ -0,0 +1,26 @@
+#version 330 core
+
+layout (location = 0) in vec2 Position;
+layout (location = 1) in vec2 PixelPosition;
+layout (location = 2) in vec4 Rect;
+layout (location = 3) in vec4 HorizontalRadii;
+layout (location = 4) in vec4 VerticalRadii;
+layout (location = 5) in vec4 Color;
+
+out VS_OUTPUT {
+    vec2 PixelPosition;
+    vec4 Rect;
+    vec4 HorizontalRadii;
+    vec4 VerticalRadii;
+    vec4 Color;
+} OUT;
+
+void main()
+{
+    gl_Position = vec4(Position, 0.0, 1.0);
+    OUT.PixelPosition = PixelPosition;
+    OUT.Rect = Rect;
+    OUT.HorizontalRadii = HorizontalRadii;
+    OUT.VerticalRadii = VerticalRadii;
+    OUT.Color = Color;
+}
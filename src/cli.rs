@@ -50,9 +50,60 @@ pub fn setup_and_get_cli_args<'a>() -> ArgMatches<'a> {
                 .validator(is_num_validator)
                 .global(true),
         )
-        .subcommand(SubCommand::with_name("dump-layout").about(
-            "Dumps layout-tree as text to stdout after first global layout, exiting afterwards.  Scale factor must be sup",
-        ))
+        .arg(
+            Arg::with_name("layout_stats")
+                .long("layout-stats")
+                .help("Prints how many boxes were laid out to stderr after every layout of the window."),
+        )
+        .subcommand(
+            SubCommand::with_name("dump-layout")
+                .about(
+                    "Dumps layout-tree as text to stdout after first global layout, exiting afterwards.  Scale factor must be sup",
+                )
+                .arg(
+                    Arg::with_name("relayout_from_width")
+                        .long("relayout-from-width")
+                        .value_name("WIDTH")
+                        .help("Lays out at this inner window width first, then lays out again incrementally at 'width', printing how many boxes were laid out to stderr.")
+                        .takes_value(true)
+                        .validator(is_num_validator),
+                )
+                .arg(
+                    Arg::with_name("invalidate")
+                        .long("invalidate")
+                        .value_name("SELECTORS")
+                        .help("Invalidates the boxes of the elements matching these selectors before laying out again.  Requires 'relayout-from-width'.")
+                        .takes_value(true)
+                        .requires("relayout_from_width"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("render-svg")
+                .about(
+                    "Renders the page as an SVG document to stdout after first global layout, exiting afterwards.  Scale factor must be supplied.",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("print-pdf")
+                .about(
+                    "Prints the page as a PDF document, with one page per fragment of the page's content, exiting afterwards.  Pages are sized and laid out by '@page' rules rather than the window.",
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("FILE")
+                        .help("The file to write the PDF document to.")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("dump-display-list")
+                .about(
+                    "Dumps the display list as text to stdout after first global layout, exiting afterwards.  Scale factor must be supplied.",
+                ),
+        )
         .get_matches()
 }
 
@@ -104,6 +155,43 @@ pub fn dump_layout_tree(arg_matches: &ArgMatches) -> bool {
     arg_matches.subcommand_matches("dump-layout").is_some()
 }
 
+pub fn render_svg(arg_matches: &ArgMatches) -> bool {
+    arg_matches.subcommand_matches("render-svg").is_some()
+}
+
+/// The file to write the PDF document to, if the page should be printed.
+pub fn print_pdf_output<'a>(arg_matches: &'a ArgMatches<'a>) -> Option<&'a str> {
+    arg_matches
+        .subcommand_matches("print-pdf")
+        .and_then(|print_pdf| print_pdf.value_of("output"))
+}
+
+pub fn dump_display_list(arg_matches: &ArgMatches) -> bool {
+    arg_matches
+        .subcommand_matches("dump-display-list")
+        .is_some()
+}
+
+/// The inner window width to lay out at before the dumped layout, which is then done
+/// incrementally.
+pub fn relayout_from_width(arg_matches: &ArgMatches) -> Option<f32> {
+    arg_matches
+        .subcommand_matches("dump-layout")
+        .and_then(|dump_layout| try_get::<f32>(dump_layout, "relayout_from_width"))
+}
+
+/// The selectors of the elements to invalidate before the dumped layout.
+pub fn invalidate_selectors<'a>(arg_matches: &'a ArgMatches<'a>) -> Option<&'a str> {
+    arg_matches
+        .subcommand_matches("dump-layout")
+        .and_then(|dump_layout| dump_layout.value_of("invalidate"))
+}
+
+/// Whether to print how many boxes each layout of the window laid out.
+pub fn layout_stats(arg_matches: &ArgMatches) -> bool {
+    arg_matches.is_present("layout_stats")
+}
+
 pub fn inner_window_width(arg_matches: &ArgMatches) -> Option<f32> {
     try_get::<f32>(arg_matches, "width")
 }
@@ -0,0 +1,107 @@
+use crate::dom::tree::NodeRef;
+use image::RgbaImage;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The image an `<img>` element refers to, which makes it a replaced element.
+///
+/// https://html.spec.whatwg.org/multipage/embedded-content.html#the-img-element
+/// https://www.w3.org/TR/CSS2/conform.html#replaced-element
+#[derive(Clone, Debug, PartialEq)]
+pub enum ElementImage {
+    /// The image was loaded and decoded into RGBA pixels.  Animated images are represented by
+    /// their first frame.
+    Decoded(ImageKey, Rc<RgbaImage>),
+    /// The element has no `src`, or the image couldn't be loaded or decoded.  The element is still
+    /// a replaced element, but has no intrinsic dimensions.
+    Broken,
+}
+
+impl ElementImage {
+    /// Gets the intrinsic width and height of this image, in CSS pixels.
+    pub fn intrinsic_size(&self) -> Option<(u32, u32)> {
+        match self {
+            ElementImage::Decoded(_, image) => Some(image.dimensions()),
+            ElementImage::Broken => None,
+        }
+    }
+}
+
+/// Identifies a decoded image for as long as Kosmonaut runs, so that display lists can refer to it
+/// and painting backends can keep whatever they make of it, such as textures, between frames.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ImageKey(usize);
+
+impl ImageKey {
+    /// Gets a key that no other image has.
+    fn next() -> ImageKey {
+        static NEXT_KEY: AtomicUsize = AtomicUsize::new(0);
+        ImageKey(NEXT_KEY.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Loads the image of each `<img>` element in the tree rooted at `node`, resolving relative `src`
+/// URLs against `base_dir`, the directory containing the document.
+///
+/// TODO: Only local files can be loaded, since Kosmonaut can't fetch resources over the network.
+pub fn load_images(node: &NodeRef, base_dir: &Path) {
+    for descendant in node.inclusive_descendants() {
+        let element = match descendant.as_element() {
+            Some(element) if element.name.expanded() == expanded_name!(html "img") => element,
+            _ => continue,
+        };
+        let src = element
+            .attributes
+            .borrow()
+            .get(local_name!("src"))
+            .map(str::to_owned);
+        let image = src
+            .and_then(|src| load_image(&src, base_dir))
+            .map_or(ElementImage::Broken, |image| {
+                ElementImage::Decoded(ImageKey::next(), Rc::new(image))
+            });
+        *element.image.borrow_mut() = Some(image);
+    }
+}
+
+/// A decoded image that's shared by every use of it, and the key it's painted by.
+pub type SharedImage = (ImageKey, Rc<RgbaImage>);
+
+thread_local! {
+    /// The images loaded by `load_shared_image` so far, by path, or `None` for those that couldn't
+    /// be loaded.
+    static SHARED_IMAGES: RefCell<HashMap<PathBuf, Option<SharedImage>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Loads the image at `src`, relative to `base_dir`, such as the image of a background layer.
+/// Each image is only loaded and decoded once, and shares its key with every other use of it.
+pub fn load_shared_image(src: &str, base_dir: &Path) -> Option<SharedImage> {
+    let path = base_dir.join(src.trim());
+    SHARED_IMAGES.with(|images| {
+        images
+            .borrow_mut()
+            .entry(path)
+            .or_insert_with(|| {
+                load_image(src, base_dir).map(|image| (ImageKey::next(), Rc::new(image)))
+            })
+            .clone()
+    })
+}
+
+/// Loads and decodes the image at `src`.  Any format the `image` crate can guess from the file's
+/// contents is supported, which includes PNG, JPEG, and GIF.
+fn load_image(src: &str, base_dir: &Path) -> Option<RgbaImage> {
+    let src = src.trim();
+    if src.is_empty() || (src.contains("://") && !src.starts_with("file://")) {
+        return None;
+    }
+    let path = base_dir.join(src.trim_start_matches("file://"));
+    let bytes = std::fs::read(path).ok()?;
+    image::load_from_memory(&bytes)
+        .ok()
+        .map(|image| image.to_rgba8())
+}
@@ -5,6 +5,7 @@
 /// Kuchiki where it makes sense.  Thanks to the authors of Kuchiki for their work.
 pub mod attributes;
 pub mod cell_extras;
+pub mod image;
 pub mod iter;
 pub mod node_data_ref;
 pub mod parser;
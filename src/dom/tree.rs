@@ -10,6 +10,7 @@ use html5ever::QualName;
 
 use crate::dom::attributes::{Attribute, Attributes, ExpandedName};
 use crate::dom::cell_extras::*;
+use crate::dom::image::ElementImage;
 use crate::dom::iter::NodeIterator;
 use crate::style::properties::{ContextualPropertyDeclaration, ContextualPropertyDeclarations};
 use crate::style::values::computed::ComputedValues;
@@ -68,6 +69,10 @@ pub struct ElementData {
     /// If the element is an HTML `<template>` element,
     /// the document fragment node that is the root of template contents.
     pub template_contents: Option<NodeRef>,
+
+    /// If the element is an HTML `<img>` element, the image it refers to, once images have been
+    /// loaded with `load_images`.
+    pub image: RefCell<Option<ElementImage>>,
 }
 
 /// Data specific to document nodes.
@@ -251,6 +256,7 @@ impl NodeRef {
             attributes: RefCell::new(Attributes {
                 map: attributes.into_iter().collect(),
             }),
+            image: RefCell::new(None),
         }))
     }
 
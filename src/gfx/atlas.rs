@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The number of texels left empty after each item in an atlas, in both axes, so that sampling
+/// an item with linear filtering never picks up the edge of its neighbours.
+pub const GUTTER: u32 = 1;
+
+/// A rectangle within an atlas, measured in texels from its top-left corner.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AtlasRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The result of adding an item to an atlas.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Allocation<K> {
+    /// Where the item was placed.
+    pub rect: AtlasRect,
+    /// The items that were evicted to make room for it, which have to be added again before
+    /// they're next used.
+    pub evicted: Vec<K>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AtlasError {
+    /// The item is larger than the atlas itself.
+    TooLarge,
+    /// There's no room for the item, and everything in the atlas has been used in the current
+    /// generation, so nothing can be evicted.
+    Full,
+}
+
+/// A row of items in an atlas, as tall as the item it was created for, filled from left to right.
+#[derive(Debug)]
+struct Shelf<K> {
+    y: u32,
+    height: u32,
+    used_width: u32,
+    keys: Vec<K>,
+    /// The latest generation any item on this shelf was used in.
+    last_used: u64,
+}
+
+/// Packs rectangular items, such as rasterized glyphs, into a texture atlas of a fixed size, so
+/// that they can be painted together from a single texture.
+///
+/// Items are packed onto shelves.  When there's no room left for an item, whole shelves are
+/// evicted in least recently used order, which frees them up for shelves of any height.  Items
+/// used in the current generation are never evicted, since anything painted in it may still refer
+/// to them, so callers should start a new generation with `next_generation` each time they start
+/// preparing a frame.
+///
+/// This only keeps track of where items go; what's stored at those places is up to the caller.
+#[derive(Debug)]
+pub struct ShelfAtlas<K> {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf<K>>,
+    /// The vertical ranges of the atlas no shelf takes up, as `(y, height)`, sorted by `y`.
+    free_bands: Vec<(u32, u32)>,
+    items: HashMap<K, AtlasRect>,
+    generation: u64,
+}
+
+impl<K: Clone + Eq + Hash> ShelfAtlas<K> {
+    pub fn new(width: u32, height: u32) -> ShelfAtlas<K> {
+        ShelfAtlas {
+            width,
+            height,
+            shelves: Vec::new(),
+            free_bands: vec![(0, height)],
+            items: HashMap::new(),
+            generation: 0,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Starts a new generation, after which the items used before it can be evicted.
+    pub fn next_generation(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Gets where `key` is in the atlas, if it's there, marking it as used in the current
+    /// generation.
+    pub fn get(&mut self, key: &K) -> Option<AtlasRect> {
+        let rect = *self.items.get(key)?;
+        let generation = self.generation;
+        if let Some(shelf) = self.shelves.iter_mut().find(|shelf| shelf.y == rect.y) {
+            shelf.last_used = generation;
+        }
+        Some(rect)
+    }
+
+    /// Adds `key` to the atlas as an item of the given size, evicting the least recently used
+    /// shelves if there's no room for it.
+    pub fn allocate(
+        &mut self,
+        key: K,
+        width: u32,
+        height: u32,
+    ) -> Result<Allocation<K>, AtlasError> {
+        let (padded_width, padded_height) = (width + GUTTER, height + GUTTER);
+        if padded_width > self.width || padded_height > self.height {
+            return Err(AtlasError::TooLarge);
+        }
+        let mut evicted = Vec::new();
+        let shelf_idx = loop {
+            // Prefer the shortest shelf the item fits on, unless it would waste too much of it and
+            // there's room for a shelf that fits better.
+            let best_fit = self
+                .shelves
+                .iter()
+                .enumerate()
+                .filter(|(_, shelf)| {
+                    shelf.height >= padded_height && self.width - shelf.used_width >= padded_width
+                })
+                .min_by_key(|(_, shelf)| shelf.height)
+                .map(|(idx, shelf)| (idx, shelf.height));
+            match best_fit {
+                Some((idx, shelf_height)) if shelf_height <= padded_height * 3 / 2 => break idx,
+                _ => {}
+            }
+            if let Some(idx) = self.add_shelf(padded_height) {
+                break idx;
+            }
+            if let Some((idx, _)) = best_fit {
+                break idx;
+            }
+            evicted.extend(self.evict_least_recently_used()?);
+        };
+
+        let generation = self.generation;
+        let shelf = &mut self.shelves[shelf_idx];
+        let rect = AtlasRect {
+            x: shelf.used_width,
+            y: shelf.y,
+            width,
+            height,
+        };
+        shelf.used_width += padded_width;
+        shelf.keys.push(key.clone());
+        shelf.last_used = generation;
+        self.items.insert(key, rect);
+        Ok(Allocation { rect, evicted })
+    }
+
+    /// Adds a shelf of the given height in the first free band it fits in, returning its index.
+    fn add_shelf(&mut self, height: u32) -> Option<usize> {
+        let band_idx = self
+            .free_bands
+            .iter()
+            .position(|&(_, band_height)| band_height >= height)?;
+        let (y, band_height) = self.free_bands[band_idx];
+        if band_height == height {
+            self.free_bands.remove(band_idx);
+        } else {
+            self.free_bands[band_idx] = (y + height, band_height - height);
+        }
+        self.shelves.push(Shelf {
+            y,
+            height,
+            used_width: 0,
+            keys: Vec::new(),
+            last_used: self.generation,
+        });
+        Some(self.shelves.len() - 1)
+    }
+
+    /// Removes the shelf that was used least recently before the current generation, returning the
+    /// items that were on it.
+    fn evict_least_recently_used(&mut self) -> Result<Vec<K>, AtlasError> {
+        let generation = self.generation;
+        let shelf_idx = self
+            .shelves
+            .iter()
+            .enumerate()
+            .filter(|(_, shelf)| shelf.last_used < generation)
+            .min_by_key(|(_, shelf)| shelf.last_used)
+            .map(|(idx, _)| idx)
+            .ok_or(AtlasError::Full)?;
+        let shelf = self.shelves.remove(shelf_idx);
+        for key in &shelf.keys {
+            self.items.remove(key);
+        }
+
+        // Give the shelf's band back, merging it with the free bands next to it.
+        let idx = self
+            .free_bands
+            .iter()
+            .position(|&(y, _)| y > shelf.y)
+            .unwrap_or(self.free_bands.len());
+        self.free_bands.insert(idx, (shelf.y, shelf.height));
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(self.free_bands.len());
+        for &(y, height) in &self.free_bands {
+            match merged.last_mut() {
+                Some((last_y, last_height)) if *last_y + *last_height == y => {
+                    *last_height += height
+                }
+                _ => merged.push((y, height)),
+            }
+        }
+        self.free_bands = merged;
+        Ok(shelf.keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> AtlasRect {
+        AtlasRect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn packs_items_onto_shelves() {
+        let mut atlas = ShelfAtlas::new(32, 32);
+        assert_eq!(atlas.allocate('a', 9, 9).unwrap().rect, rect(0, 0, 9, 9));
+        assert_eq!(atlas.allocate('b', 9, 8).unwrap().rect, rect(10, 0, 9, 8));
+        assert_eq!(atlas.allocate('c', 9, 9).unwrap().rect, rect(20, 0, 9, 9));
+        // There's no room left on the first shelf, so this starts a second one below it.
+        assert_eq!(atlas.allocate('d', 9, 9).unwrap().rect, rect(0, 10, 9, 9));
+        // Items much shorter than the existing shelves get a shelf of their own.
+        assert_eq!(atlas.allocate('e', 3, 3).unwrap().rect, rect(0, 20, 3, 3));
+        assert_eq!(atlas.get(&'b'), Some(rect(10, 0, 9, 8)));
+        assert_eq!(atlas.get(&'f'), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_shelves() {
+        let mut atlas = ShelfAtlas::new(16, 32);
+        atlas.allocate('a', 15, 9).unwrap();
+        atlas.next_generation();
+        atlas.allocate('b', 15, 9).unwrap();
+        atlas.next_generation();
+        atlas.allocate('c', 15, 9).unwrap();
+        atlas.get(&'a');
+        atlas.next_generation();
+        // The shelf of 'b' was used least recently, so it makes room for 'd'.
+        let allocation = atlas.allocate('d', 15, 9).unwrap();
+        assert_eq!(allocation.rect, rect(0, 10, 15, 9));
+        assert_eq!(allocation.evicted, vec!['b']);
+        assert_eq!(atlas.get(&'b'), None);
+        // Evicted shelves merge with the free bands next to them, making room for taller shelves.
+        atlas.next_generation();
+        let allocation = atlas.allocate('e', 15, 20).unwrap();
+        assert_eq!(allocation.rect, rect(0, 0, 15, 20));
+        assert_eq!(allocation.evicted, vec!['a', 'c', 'd']);
+    }
+
+    #[test]
+    fn never_evicts_items_used_in_current_generation() {
+        let mut atlas = ShelfAtlas::new(16, 16);
+        atlas.allocate('a', 15, 15).unwrap();
+        assert_eq!(atlas.allocate('b', 15, 15), Err(AtlasError::Full));
+        assert_eq!(atlas.allocate('c', 16, 1), Err(AtlasError::TooLarge));
+        atlas.next_generation();
+        assert_eq!(atlas.allocate('b', 15, 15).unwrap().evicted, vec!['a']);
+    }
+}
@@ -0,0 +1,483 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-backgrounds-3/#backgrounds
+//  * https://drafts.csswg.org/css-images-3/#sizing
+//  * https://drafts.csswg.org/css-images-3/#gradients
+use crate::dom::image::ImageKey;
+use crate::gfx::display::Tiles;
+use crate::gfx::gradient::{Gradient, GradientShape, GradientStop};
+use crate::layout::layout_box::LayoutBox;
+use crate::layout::rect::{CornerRadii, Rect, RoundedRect};
+use crate::layout::resolve_length_percentage;
+use crate::style::values::computed::background::{
+    BgPositionComponent, BgSize, ColorStop, EndingShape, GradientKind, Image,
+};
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::{background, BoxArea, RepeatStyle};
+use crate::style::values::specified::background::{LineDirection, ShapeExtent};
+use crate::Side;
+use app_units::Au;
+use image::RgbaImage;
+use pathfinder_geometry::vector::Vector2F;
+use std::rc::Rc;
+
+/// A background layer of a box, resolved against the size of the box.
+pub struct BackgroundLayer {
+    pub image: LayerImage,
+    /// Where the image is painted, and how it's repeated.
+    pub tiles: Tiles,
+    /// The background painting area, which the tiles are clipped to.
+    pub clip: RoundedRect,
+}
+
+/// The image of a background layer, ready to be tiled.
+pub enum LayerImage {
+    Raster(ImageKey, Rc<RgbaImage>),
+    /// A gradient, whose geometry is relative to the top-left corner of each tile.
+    Gradient(Rc<Gradient>),
+}
+
+/// The box of `layout_box` that background layers with `background-clip: area` are painted
+/// within, including its rounded corners.
+pub fn painting_area(layout_box: &LayoutBox, area: BoxArea) -> RoundedRect {
+    match area {
+        BoxArea::BorderBox => layout_box.rounded_border_box(),
+        BoxArea::PaddingBox => layout_box.rounded_padding_box(),
+        BoxArea::ContentBox => layout_box.rounded_content_box(),
+    }
+}
+
+/// The box of `layout_box` that background layers with `background-origin: area` are positioned
+/// and sized within.
+fn positioning_area(layout_box: &LayoutBox, area: BoxArea) -> Rect {
+    let d = layout_box.physical_dimensions();
+    match area {
+        BoxArea::BorderBox => d.border_box(),
+        BoxArea::PaddingBox => d.padding_box(),
+        BoxArea::ContentBox => d.content,
+    }
+}
+
+/// Resolves the background layers of `layout_box` that have an image, in the order they're
+/// painted in, which is the reverse of the order they're given in.  The images of the root element
+/// are painted over the whole `canvas` rather than its own painting area.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#layering
+pub fn background_layers(
+    layout_box: &LayoutBox,
+    scale_factor: f32,
+    canvas: Option<Rect>,
+) -> Vec<BackgroundLayer> {
+    let cvs = layout_box.computed_values();
+    let mut layers = Vec::new();
+    for (index, image) in cvs.background_image.0.iter().enumerate().rev() {
+        let intrinsic_size = match image {
+            Image::None => continue,
+            Image::Raster(_, image) => Some((
+                image.width() as f32 * scale_factor,
+                image.height() as f32 * scale_factor,
+            )),
+            Image::Gradient(_) => None,
+        };
+        let clip = match canvas {
+            Some(canvas) => RoundedRect::new(canvas, CornerRadii::default()),
+            None => painting_area(layout_box, cvs.background_clip.layer(index)),
+        }
+        .snapped_to_pixels();
+        let origin = positioning_area(layout_box, cvs.background_origin.layer(index));
+        let repeat = cvs.background_repeat.layer(index);
+        let size = cvs.background_size.layer(index);
+        if clip.rect.width <= Au(0) || clip.rect.height <= Au(0) {
+            continue;
+        }
+        let (width, height) = tile_size(
+            size,
+            intrinsic_size,
+            origin,
+            repeat.horizontal,
+            repeat.vertical,
+            scale_factor,
+        );
+        // Images with no size aren't painted at all.
+        if width < 0.5 || height < 0.5 {
+            continue;
+        }
+        let (width, height) = (
+            Au::from_px(width.round() as i32),
+            Au::from_px(height.round() as i32),
+        );
+        let (start_x, step_x) = place_tiles(
+            cvs.background_position_x.layer(index),
+            repeat.horizontal,
+            origin.start_x,
+            origin.width,
+            width,
+            scale_factor,
+        );
+        let (start_y, step_y) = place_tiles(
+            cvs.background_position_y.layer(index),
+            repeat.vertical,
+            origin.start_y,
+            origin.height,
+            height,
+            scale_factor,
+        );
+        let tiles = Tiles {
+            tile: Rect {
+                start_x,
+                start_y,
+                width,
+                height,
+            }
+            .snapped_to_pixels(),
+            step_x,
+            step_y,
+            area: clip.rect,
+        };
+        let image = match image {
+            Image::None => continue,
+            Image::Raster(key, image) => LayerImage::Raster(*key, image.clone()),
+            Image::Gradient(gradient) => LayerImage::Gradient(Rc::new(resolve_gradient(
+                gradient,
+                width.to_f32_px(),
+                height.to_f32_px(),
+                scale_factor,
+            ))),
+        };
+        layers.push(BackgroundLayer { image, tiles, clip });
+    }
+    layers
+}
+
+/// Resolves `length` against `basis`, both in device pixels.
+fn resolve(length: LengthPercentage, basis: f32, scale_factor: f32) -> f32 {
+    resolve_length_percentage(length, Au::from_f32_px(basis), scale_factor).to_f32_px()
+}
+
+/// Resolves the size of the image of a background layer within its positioning area `origin`, in
+/// device pixels.  Gradients have no `intrinsic_size`, so they fill as much of the area as they
+/// aren't given a size for.  If the image is repeated with `round`, it's then scaled so that it
+/// fits the area a whole number of times.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#the-background-size
+fn tile_size(
+    size: BgSize,
+    intrinsic_size: Option<(f32, f32)>,
+    origin: Rect,
+    repeat_x: RepeatStyle,
+    repeat_y: RepeatStyle,
+    scale_factor: f32,
+) -> (f32, f32) {
+    let (area_width, area_height) = (origin.width.to_f32_px(), origin.height.to_f32_px());
+    let ratio = intrinsic_size
+        .filter(|(width, height)| *width > 0. && *height > 0.)
+        .map(|(width, height)| width / height);
+    let (width, height, width_auto, height_auto) = match size {
+        BgSize::Cover | BgSize::Contain => {
+            let (width, height) = match intrinsic_size.filter(|_| ratio.is_some()) {
+                Some((width, height)) => {
+                    let (scale_x, scale_y) = (area_width / width, area_height / height);
+                    let scale = if size == BgSize::Cover {
+                        scale_x.max(scale_y)
+                    } else {
+                        scale_x.min(scale_y)
+                    };
+                    (width * scale, height * scale)
+                }
+                None => (area_width, area_height),
+            };
+            (width, height, false, false)
+        }
+        BgSize::Explicit { width, height } => {
+            let resolve_or_auto = |length: LengthPercentageOrAuto, basis: f32| match length {
+                LengthPercentageOrAuto::LengthPercentage(length) => {
+                    Some(resolve(length, basis, scale_factor).max(0.))
+                }
+                LengthPercentageOrAuto::Auto => None,
+            };
+            let (explicit_width, explicit_height) = (
+                resolve_or_auto(width, area_width),
+                resolve_or_auto(height, area_height),
+            );
+            let (width, height) = match (explicit_width, explicit_height, ratio) {
+                (Some(width), Some(height), _) => (width, height),
+                (Some(width), None, Some(ratio)) => (width, width / ratio),
+                (None, Some(height), Some(ratio)) => (height * ratio, height),
+                (Some(width), None, None) => (width, area_height),
+                (None, Some(height), None) => (area_width, height),
+                (None, None, _) => intrinsic_size.unwrap_or((area_width, area_height)),
+            };
+            (
+                width,
+                height,
+                explicit_width.is_none(),
+                explicit_height.is_none(),
+            )
+        }
+    };
+    // https://drafts.csswg.org/css-backgrounds-3/#valdef-background-repeat-round
+    let round = |size: f32, area: f32| {
+        if size <= 0. || area <= 0. {
+            return size;
+        }
+        area / (area / size).round().max(1.)
+    };
+    match (
+        repeat_x == RepeatStyle::Round,
+        repeat_y == RepeatStyle::Round,
+    ) {
+        (true, true) => (round(width, area_width), round(height, area_height)),
+        (true, false) => {
+            let rounded_width = round(width, area_width);
+            // The other dimension keeps the image's ratio if it was `auto`.
+            if height_auto && width > 0. {
+                (rounded_width, height * rounded_width / width)
+            } else {
+                (rounded_width, height)
+            }
+        }
+        (false, true) => {
+            let rounded_height = round(height, area_height);
+            if width_auto && height > 0. {
+                (width * rounded_height / height, rounded_height)
+            } else {
+                (width, rounded_height)
+            }
+        }
+        (false, false) => (width, height),
+    }
+}
+
+/// Places the tiles of a background layer along one axis of its positioning area, which starts
+/// at `area_start` and is `area_size` long, given the size of a tile.  Returns where one tile
+/// starts, and how far apart the tiles are if the image is repeated along this axis.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-position
+/// https://drafts.csswg.org/css-backgrounds-3/#background-repeat
+fn place_tiles(
+    position: BgPositionComponent,
+    repeat: RepeatStyle,
+    area_start: Au,
+    area_size: Au,
+    tile_size: Au,
+    scale_factor: f32,
+) -> (Au, Option<Au>) {
+    if repeat == RepeatStyle::Space {
+        let count = area_size.0 / tile_size.0;
+        if count > 1 {
+            // The first and last tiles touch the edges of the area, and the rest are spread out
+            // evenly between them.
+            let spacing = (area_size - tile_size * count) / (count - 1);
+            return (area_start, Some(tile_size + spacing));
+        }
+    }
+    // Percentages align the same point of the tile and the area, which is the same as resolving
+    // them against the space left over once the tile is placed.
+    let leftover = (area_size - tile_size).to_f32_px();
+    let offset = Au::from_f32_px(resolve(position.offset, leftover, scale_factor));
+    let start = if position.from_end {
+        area_start + area_size - tile_size - offset
+    } else {
+        area_start + offset
+    };
+    let step = match repeat {
+        RepeatStyle::Repeat | RepeatStyle::Round => Some(tile_size),
+        RepeatStyle::Space | RepeatStyle::NoRepeat => None,
+    };
+    (start, step)
+}
+
+/// Resolves the geometry of `gradient` against a tile `width` by `height` device pixels in size.
+fn resolve_gradient(
+    gradient: &background::Gradient,
+    width: f32,
+    height: f32,
+    scale_factor: f32,
+) -> Gradient {
+    let (shape, length) = match gradient.kind {
+        GradientKind::Linear(direction) => {
+            let direction = match direction {
+                LineDirection::Angle(degrees) => {
+                    let radians = degrees.to_radians();
+                    Vector2F::new(radians.sin(), -radians.cos())
+                }
+                // The line points such that the corners next to the one it points towards have
+                // the same color.
+                LineDirection::Corner(horizontal, vertical) => {
+                    let x = if horizontal == Side::Right { 1. } else { -1. };
+                    let y = if vertical == Side::Bottom { 1. } else { -1. };
+                    Vector2F::new(x * height, y * width).normalize()
+                }
+            };
+            // The gradient line is long enough that its ends are where the corners of the tile
+            // are projected onto it.
+            // https://drafts.csswg.org/css-images-3/#linear-gradient-syntax
+            let length = (width * direction.x()).abs() + (height * direction.y()).abs();
+            let center = Vector2F::new(width / 2., height / 2.);
+            let half = direction * (length / 2.);
+            (
+                GradientShape::Linear {
+                    start: center - half,
+                    end: center + half,
+                },
+                length,
+            )
+        }
+        GradientKind::Radial(shape, position) => {
+            let center = Vector2F::new(
+                resolve_position(position.horizontal, width, scale_factor),
+                resolve_position(position.vertical, height, scale_factor),
+            );
+            let (radius_x, radius_y) =
+                ending_shape_radii(shape, center, width, height, scale_factor);
+            (
+                GradientShape::Radial {
+                    center,
+                    radius_x,
+                    radius_y,
+                },
+                radius_x,
+            )
+        }
+    };
+    Gradient::new(
+        shape,
+        resolve_stops(&gradient.stops, length, scale_factor),
+        gradient.repeating,
+    )
+}
+
+/// Resolves the position of a point along an axis of a tile that's `size` long.
+fn resolve_position(component: BgPositionComponent, size: f32, scale_factor: f32) -> f32 {
+    let offset = resolve(component.offset, size, scale_factor);
+    if component.from_end {
+        size - offset
+    } else {
+        offset
+    }
+}
+
+/// Resolves the horizontal and vertical radii of the ending shape of a radial gradient centered
+/// on `center`, within a tile `width` by `height` in size.  Radii are kept from being zero, so
+/// that the gradient still has a direction to change in.
+///
+/// https://drafts.csswg.org/css-images-3/#radial-gradient-syntax
+fn ending_shape_radii(
+    shape: EndingShape,
+    center: Vector2F,
+    width: f32,
+    height: f32,
+    scale_factor: f32,
+) -> (f32, f32) {
+    const MIN_RADIUS: f32 = 0.01;
+    let sides_x = [center.x().abs(), (width - center.x()).abs()];
+    let sides_y = [center.y().abs(), (height - center.y()).abs()];
+    let closest = |sides: [f32; 2]| sides[0].min(sides[1]);
+    let farthest = |sides: [f32; 2]| sides[0].max(sides[1]);
+    // The distances to the corner closest to or farthest from the center along each axis.
+    let corner = |farthest_corner: bool| {
+        let mut corners = Vec::with_capacity(4);
+        for &x in &sides_x {
+            for &y in &sides_y {
+                corners.push((x, y));
+            }
+        }
+        let distance = |&(x, y): &(f32, f32)| x.hypot(y);
+        corners.sort_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap());
+        if farthest_corner {
+            corners[3]
+        } else {
+            corners[0]
+        }
+    };
+    let (radius_x, radius_y) = match shape {
+        EndingShape::Circle(radius) => {
+            let radius = radius.px() * scale_factor;
+            (radius, radius)
+        }
+        EndingShape::Ellipse(radius_x, radius_y) => (
+            resolve(radius_x, width, scale_factor),
+            resolve(radius_y, height, scale_factor),
+        ),
+        EndingShape::CircleExtent(extent) => {
+            let radius = match extent {
+                ShapeExtent::ClosestSide => closest(sides_x).min(closest(sides_y)),
+                ShapeExtent::FarthestSide => farthest(sides_x).max(farthest(sides_y)),
+                ShapeExtent::ClosestCorner | ShapeExtent::FarthestCorner => {
+                    let (x, y) = corner(extent == ShapeExtent::FarthestCorner);
+                    x.hypot(y)
+                }
+            };
+            (radius, radius)
+        }
+        EndingShape::EllipseExtent(extent) => match extent {
+            ShapeExtent::ClosestSide => (closest(sides_x), closest(sides_y)),
+            ShapeExtent::FarthestSide => (farthest(sides_x), farthest(sides_y)),
+            ShapeExtent::ClosestCorner | ShapeExtent::FarthestCorner => {
+                // The ellipse passes through the corner, with the ratio it would have if it
+                // touched the closest or farthest sides.
+                let farthest_corner = extent == ShapeExtent::FarthestCorner;
+                let (side_x, side_y) = if farthest_corner {
+                    (farthest(sides_x), farthest(sides_y))
+                } else {
+                    (closest(sides_x), closest(sides_y))
+                };
+                let ratio = side_x.max(MIN_RADIUS) / side_y.max(MIN_RADIUS);
+                let (x, y) = corner(farthest_corner);
+                let radius_y = (x / ratio).hypot(y);
+                (radius_y * ratio, radius_y)
+            }
+        },
+    };
+    (radius_x.max(MIN_RADIUS), radius_y.max(MIN_RADIUS))
+}
+
+/// Resolves the positions of `stops` along a gradient line or ray `length` device pixels long.
+/// The first and last stops default to its ends, no stop is placed before the one before it, and
+/// stops without a position are spread out evenly between the ones around them.
+///
+/// https://drafts.csswg.org/css-images-3/#color-stop-fixup
+fn resolve_stops(stops: &[ColorStop], length: f32, scale_factor: f32) -> Vec<GradientStop> {
+    let last = stops.len() - 1;
+    let mut positions = stops
+        .iter()
+        .enumerate()
+        .map(|(index, stop)| match stop.position {
+            Some(position) => Some(resolve(position, length, scale_factor)),
+            None if index == 0 => Some(0.),
+            None if index == last => Some(length),
+            None => None,
+        })
+        .collect::<Vec<_>>();
+    let mut max_so_far = f32::NEG_INFINITY;
+    for position in positions.iter_mut().flatten() {
+        max_so_far = max_so_far.max(*position);
+        *position = max_so_far;
+    }
+    let mut index = 0;
+    while index < positions.len() {
+        if positions[index].is_some() {
+            index += 1;
+            continue;
+        }
+        // The first and last stops always have positions, so every run of stops without one is
+        // between two with one.
+        let before = positions[index - 1].unwrap();
+        let run_end = (index..positions.len())
+            .find(|&end| positions[end].is_some())
+            .unwrap();
+        let after = positions[run_end].unwrap();
+        let count = (run_end - index + 1) as f32;
+        for (step, position) in positions[index..run_end].iter_mut().enumerate() {
+            *position = Some(before + (after - before) * (step + 1) as f32 / count);
+        }
+        index = run_end;
+    }
+    stops
+        .iter()
+        .zip(positions)
+        .map(|(stop, position)| GradientStop {
+            offset: position.unwrap(),
+            color: stop.color,
+        })
+        .collect()
+}
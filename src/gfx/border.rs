@@ -0,0 +1,680 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-backgrounds-3/#border-style
+//  * https://drafts.csswg.org/css-backgrounds-3/#corner-shaping
+use crate::gfx::display::BorderCommand;
+use crate::layout::rect::{CornerRadius, Rect, RoundedRect};
+use crate::style::values::computed::LineStyle;
+use crate::style::values::CSSFloat;
+use crate::Side;
+use cssparser::RGBA;
+use pathfinder_geometry::vector::Vector2F;
+use std::f32::consts::PI;
+
+/// The length of the dashes of `dashed` borders, and of the gaps between them, as multiples of the
+/// width of the border.  The gaps are stretched so that each side starts and ends with a dash.
+const DASH_LENGTH: CSSFloat = 3.;
+
+/// A cross-section of a border side, from a point on its outer edge to the point on its inner edge
+/// across from it, in device pixels.
+pub type Section = (Vector2F, Vector2F);
+
+/// A filled shape that's part of a painted border side, in device pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BorderShape {
+    /// A convex polygon, with its points in clockwise order.
+    Polygon(RGBA, Vec<Vector2F>),
+    /// The area swept by consecutive cross-sections of a side, in clockwise order along the side,
+    /// which is how sides curving around rounded corners are painted.
+    Strip(RGBA, Vec<Section>),
+    /// A dot of a `dotted` border.
+    Circle {
+        color: RGBA,
+        center: Vector2F,
+        radius: CSSFloat,
+    },
+}
+
+impl BorderShape {
+    pub fn color(&self) -> RGBA {
+        match self {
+            BorderShape::Polygon(color, _)
+            | BorderShape::Strip(color, _)
+            | BorderShape::Circle { color, .. } => *color,
+        }
+    }
+
+    /// The outline of this shape as a polygon in clockwise order, with circles approximated by
+    /// enough points that their edges look smooth.  It's convex unless this is a strip.
+    pub fn polygon(&self) -> Vec<Vector2F> {
+        match self {
+            BorderShape::Polygon(_, points) => points.clone(),
+            BorderShape::Strip(_, sections) => sections
+                .iter()
+                .map(|section| section.0)
+                .chain(sections.iter().rev().map(|section| section.1))
+                .collect(),
+            BorderShape::Circle { center, radius, .. } => {
+                let segments = (radius * 4.).ceil().max(8.) as usize;
+                (0..segments)
+                    .map(|i| {
+                        let angle = 2. * PI * i as CSSFloat / segments as CSSFloat;
+                        *center + Vector2F::new(angle.cos(), angle.sin()) * *radius
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// This shape split into convex polygons in clockwise order, for painters that can only fill
+    /// convex shapes.  Strips are split into the quads between consecutive sections.
+    pub fn convex_polygons(&self) -> Vec<Vec<Vector2F>> {
+        match self {
+            BorderShape::Strip(_, sections) => sections
+                .windows(2)
+                .map(|pair| vec![pair[0].0, pair[1].0, pair[1].1, pair[0].1])
+                .collect(),
+            _ => vec![self.polygon()],
+        }
+    }
+}
+
+/// The edges of a box, in device pixels.
+#[derive(Clone, Copy, Debug)]
+struct Edges {
+    left: CSSFloat,
+    top: CSSFloat,
+    right: CSSFloat,
+    bottom: CSSFloat,
+}
+
+impl Edges {
+    fn of(rect: &Rect) -> Edges {
+        Edges {
+            left: rect.start_x.to_f32_px(),
+            top: rect.start_y.to_f32_px(),
+            right: rect.end_x().to_f32_px(),
+            bottom: rect.end_y().to_f32_px(),
+        }
+    }
+
+    /// The edges `t` of the way from `self` to `other`, whose corners lie on the lines joining the
+    /// corners of both.
+    fn lerp(self, other: Edges, t: CSSFloat) -> Edges {
+        let lerp = |from: CSSFloat, to: CSSFloat| from + (to - from) * t;
+        Edges {
+            left: lerp(self.left, other.left),
+            top: lerp(self.top, other.top),
+            right: lerp(self.right, other.right),
+            bottom: lerp(self.bottom, other.bottom),
+        }
+    }
+}
+
+/// Decomposes `border` into the shapes that paint it.
+///
+/// Each side of a border is the part of the area between the border box and the padding box that
+/// is on its side of the lines joining their corners, so that the corners of adjacent sides are
+/// mitred.  Parts of the width of a side, such as the lines of `double` borders and the halves of
+/// `groove` and `ridge` borders, are bands between boxes part of the way from the border box to
+/// the padding box.
+///
+/// Sides that curve around rounded corners are painted as strips instead, whose cross-sections
+/// follow the curves of the outer and inner edges of the border.  See `strip`.
+pub fn border_shapes(border: &BorderCommand) -> Vec<BorderShape> {
+    let outer = Edges::of(&border.border_box().rect);
+    let inner = Edges::of(&border.padding_box().rect);
+    let side = border.side();
+    let color = border.color();
+    let width = match side {
+        Side::Bottom => outer.bottom - inner.bottom,
+        Side::Left => inner.left - outer.left,
+        Side::Right => outer.right - inner.right,
+        Side::Top => inner.top - outer.top,
+    };
+    if width <= 0. {
+        return Vec::new();
+    }
+    let rounded = !border.border_box().radii.is_zero();
+    let sections = if rounded {
+        strip(side, &border.border_box(), &border.padding_box())
+    } else {
+        Vec::new()
+    };
+    let shape = |color: RGBA, from: CSSFloat, to: CSSFloat| {
+        if rounded {
+            BorderShape::Strip(color, band_of_strip(&sections, from, to))
+        } else {
+            BorderShape::Polygon(
+                color,
+                band(side, outer.lerp(inner, from), outer.lerp(inner, to)),
+            )
+        }
+    };
+    let (dark, light) = shades(color);
+    // Sides on the top and left are darker than those on the bottom and right in `inset` borders,
+    // and in the outer halves of `groove` borders.
+    let (near, far) = if let Side::Top | Side::Left = side {
+        (dark, light)
+    } else {
+        (light, dark)
+    };
+    match border.style() {
+        LineStyle::None | LineStyle::Hidden => Vec::new(),
+        LineStyle::Dotted if rounded => dots_along_strip(&sections, width, color),
+        LineStyle::Dotted => dots(side, outer.lerp(inner, 0.5), width, color),
+        LineStyle::Dashed if rounded => dashes_along_strip(&sections, width, color),
+        LineStyle::Dashed => dashes(
+            side,
+            &band(side, outer, inner),
+            outer.lerp(inner, 0.5),
+            width,
+            color,
+        ),
+        // Lines that thin can't be separated by a gap.
+        LineStyle::Double if width < 3. => vec![shape(color, 0., 1.)],
+        LineStyle::Double => vec![shape(color, 0., 1. / 3.), shape(color, 2. / 3., 1.)],
+        LineStyle::Solid => vec![shape(color, 0., 1.)],
+        LineStyle::Groove => vec![shape(near, 0., 0.5), shape(far, 0.5, 1.)],
+        LineStyle::Ridge => vec![shape(far, 0., 0.5), shape(near, 0.5, 1.)],
+        LineStyle::Inset => vec![shape(near, 0., 1.)],
+        LineStyle::Outset => vec![shape(far, 0., 1.)],
+    }
+}
+
+/// The part of `side` between the edges `from` and `to`, in clockwise order.
+fn band(side: Side, from: Edges, to: Edges) -> Vec<Vector2F> {
+    let point = Vector2F::new;
+    match side {
+        Side::Bottom => vec![
+            point(from.right, from.bottom),
+            point(from.left, from.bottom),
+            point(to.left, to.bottom),
+            point(to.right, to.bottom),
+        ],
+        Side::Left => vec![
+            point(from.left, from.bottom),
+            point(from.left, from.top),
+            point(to.left, to.top),
+            point(to.left, to.bottom),
+        ],
+        Side::Right => vec![
+            point(from.right, from.top),
+            point(from.right, from.bottom),
+            point(to.right, to.bottom),
+            point(to.right, to.top),
+        ],
+        Side::Top => vec![
+            point(from.left, from.top),
+            point(from.right, from.top),
+            point(to.right, to.top),
+            point(to.left, to.top),
+        ],
+    }
+}
+
+/// Whether `side` runs along the x axis, and where its center line starts and ends on that axis.
+fn center_line(side: Side, center: Edges) -> (bool, CSSFloat, CSSFloat) {
+    match side {
+        Side::Bottom | Side::Top => (true, center.left, center.right),
+        Side::Left | Side::Right => (false, center.top, center.bottom),
+    }
+}
+
+/// Slices the polygon of `side` into dashes along the center line of the side, which is halfway
+/// between its edges.  The first and last dashes reach into the corners.
+fn dashes(
+    side: Side,
+    polygon: &[Vector2F],
+    center: Edges,
+    width: CSSFloat,
+    color: RGBA,
+) -> Vec<BorderShape> {
+    let (horizontal, start, end) = center_line(side, center);
+    let length = end - start;
+    let dash = width * DASH_LENGTH;
+    let count = ((length + dash) / (dash * 2.)).round().max(1.) as usize;
+    if count == 1 {
+        return vec![BorderShape::Polygon(color, polygon.to_vec())];
+    }
+    let gap = (length - dash * count as CSSFloat) / (count - 1) as CSSFloat;
+    let along = |point: Vector2F| if horizontal { point.x() } else { point.y() };
+    (0..count)
+        .filter_map(|i| {
+            let dash_start = start + (dash + gap) * i as CSSFloat;
+            let mut dash_polygon = polygon.to_vec();
+            if i > 0 {
+                dash_polygon = clip(&dash_polygon, |point| along(point) - dash_start);
+            }
+            if i < count - 1 {
+                dash_polygon = clip(&dash_polygon, |point| dash_start + dash - along(point));
+            }
+            if dash_polygon.len() < 3 {
+                None
+            } else {
+                Some(BorderShape::Polygon(color, dash_polygon))
+            }
+        })
+        .collect()
+}
+
+/// Places round dots as wide as the border along the center line of `side`, evenly spaced about
+/// one dot apart, with a dot at each end.
+fn dots(side: Side, center: Edges, width: CSSFloat, color: RGBA) -> Vec<BorderShape> {
+    let (horizontal, start, end) = center_line(side, center);
+    let (across_x, across_y) = (
+        if let Side::Right = side {
+            center.right
+        } else {
+            center.left
+        },
+        if let Side::Bottom = side {
+            center.bottom
+        } else {
+            center.top
+        },
+    );
+    let length = end - start;
+    let spaces = (length / (width * 2.)).round().max(1.) as usize;
+    (0..=spaces)
+        .map(|i| {
+            let position = start + length * i as CSSFloat / spaces as CSSFloat;
+            BorderShape::Circle {
+                color,
+                center: if horizontal {
+                    Vector2F::new(position, across_y)
+                } else {
+                    Vector2F::new(across_x, position)
+                },
+                radius: width / 2.,
+            }
+        })
+        .collect()
+}
+
+/// The cross-sections of `side` of a border whose outer edge is `outer` and inner edge `inner`,
+/// from where it meets the previous side to where it meets the next, clockwise.
+///
+/// Each corner is split between the two sides meeting there at a point on its curve that's
+/// further along it the wider the side that comes first is, so that the split follows the line
+/// the sides would be mitred along if the corner were square.  Sections across a curve join points
+/// at the same angle around the ellipses of its outer and inner edges.
+fn strip(side: Side, outer: &RoundedRect, inner: &RoundedRect) -> Vec<Section> {
+    let (outer_edges, inner_edges) = (Edges::of(&outer.rect), Edges::of(&inner.rect));
+    let widths = Edges {
+        left: inner_edges.left - outer_edges.left,
+        top: inner_edges.top - outer_edges.top,
+        right: outer_edges.right - inner_edges.right,
+        bottom: outer_edges.bottom - inner_edges.bottom,
+    };
+    let corners = [
+        Corner::new(outer, inner, Corner::TOP_LEFT),
+        Corner::new(outer, inner, Corner::TOP_RIGHT),
+        Corner::new(outer, inner, Corner::BOTTOM_RIGHT),
+        Corner::new(outer, inner, Corner::BOTTOM_LEFT),
+    ];
+    // Angles are measured clockwise from the positive x-axis, since the y-axis points down.
+    let top_left = PI + widths.left.atan2(widths.top);
+    let top_right = 1.5 * PI + widths.top.atan2(widths.right);
+    let bottom_right = widths.right.atan2(widths.bottom);
+    let bottom_left = 0.5 * PI + widths.bottom.atan2(widths.left);
+    let ((first, from, middle), (second, to)) = match side {
+        Side::Top => ((0, top_left, 1.5 * PI), (1, top_right)),
+        Side::Right => ((1, top_right, 2. * PI), (2, bottom_right)),
+        Side::Bottom => ((2, bottom_right, 0.5 * PI), (3, bottom_left)),
+        Side::Left => ((3, bottom_left, PI), (0, top_left)),
+    };
+    let mut sections = Vec::new();
+    corners[first].extend_arc(&mut sections, from, middle);
+    // The angles of the corner at the end of the right side start over from zero.
+    let middle = if let Side::Right = side { 0. } else { middle };
+    corners[second].extend_arc(&mut sections, middle, to);
+    sections
+}
+
+/// A corner of a border, with the ellipses its outer and inner edges curve along.  Square corners
+/// have ellipses with no radii.
+struct Corner {
+    outer_center: Vector2F,
+    outer_radii: Vector2F,
+    inner_center: Vector2F,
+    inner_radii: Vector2F,
+}
+
+impl Corner {
+    const TOP_LEFT: (bool, bool) = (false, false);
+    const TOP_RIGHT: (bool, bool) = (true, false);
+    const BOTTOM_RIGHT: (bool, bool) = (true, true);
+    const BOTTOM_LEFT: (bool, bool) = (false, true);
+
+    /// The corner of the border between `outer` and `inner` that's on the right if `right` is
+    /// true, and on the bottom if `bottom` is true.
+    fn new(outer: &RoundedRect, inner: &RoundedRect, (right, bottom): (bool, bool)) -> Corner {
+        let radius = |rrect: &RoundedRect| match (right, bottom) {
+            (false, false) => rrect.radii.top_left,
+            (true, false) => rrect.radii.top_right,
+            (true, true) => rrect.radii.bottom_right,
+            (false, true) => rrect.radii.bottom_left,
+        };
+        let ellipse = |rrect: &RoundedRect| {
+            let CornerRadius {
+                horizontal,
+                vertical,
+            } = radius(rrect);
+            let radii = Vector2F::new(horizontal.to_f32_px(), vertical.to_f32_px());
+            let edges = Edges::of(&rrect.rect);
+            let center = Vector2F::new(
+                if right {
+                    edges.right - radii.x()
+                } else {
+                    edges.left + radii.x()
+                },
+                if bottom {
+                    edges.bottom - radii.y()
+                } else {
+                    edges.top + radii.y()
+                },
+            );
+            (center, radii)
+        };
+        let ((outer_center, outer_radii), (inner_center, inner_radii)) =
+            (ellipse(outer), ellipse(inner));
+        Corner {
+            outer_center,
+            outer_radii,
+            inner_center,
+            inner_radii,
+        }
+    }
+
+    fn section(&self, angle: CSSFloat) -> Section {
+        let direction = Vector2F::new(angle.cos(), angle.sin());
+        (
+            self.outer_center + self.outer_radii * direction,
+            self.inner_center + self.inner_radii * direction,
+        )
+    }
+
+    /// Adds the sections of this corner from the angle `from` to the angle `to` to `sections`,
+    /// with enough in between that the curve looks smooth.  Square corners only add one.
+    fn extend_arc(&self, sections: &mut Vec<Section>, from: CSSFloat, to: CSSFloat) {
+        let radius = self.outer_radii.x().max(self.outer_radii.y());
+        let segments = ((to - from).abs() * radius).ceil() as usize;
+        for i in 0..=segments {
+            let t = if segments == 0 {
+                0.
+            } else {
+                i as CSSFloat / segments as CSSFloat
+            };
+            let section = self.section(from + (to - from) * t);
+            if sections.last() != Some(&section) {
+                sections.push(section);
+            }
+        }
+    }
+}
+
+/// The part of the strip made of `sections` between `from` and `to` of the way across it.
+fn band_of_strip(sections: &[Section], from: CSSFloat, to: CSSFloat) -> Vec<Section> {
+    sections
+        .iter()
+        .map(|(outer, inner)| (outer.lerp(*inner, from), outer.lerp(*inner, to)))
+        .collect()
+}
+
+/// The distance along the center line of the strip made of `sections` to each of them.
+fn distances_along_strip(sections: &[Section]) -> Vec<CSSFloat> {
+    let mut distance = 0.;
+    let mut distances = Vec::with_capacity(sections.len());
+    for (i, (outer, inner)) in sections.iter().enumerate() {
+        if i > 0 {
+            let (previous_outer, previous_inner) = sections[i - 1];
+            distance +=
+                (outer.lerp(*inner, 0.5) - previous_outer.lerp(previous_inner, 0.5)).length();
+        }
+        distances.push(distance);
+    }
+    distances
+}
+
+/// The section `distance` along the center line of the strip made of `sections`, where
+/// `distances` are those of each section.
+fn section_at(sections: &[Section], distances: &[CSSFloat], distance: CSSFloat) -> Section {
+    let next = distances
+        .iter()
+        .position(|&d| d >= distance)
+        .unwrap_or(sections.len() - 1)
+        .max(1);
+    let (start, end) = (distances[next - 1], distances[next]);
+    let t = if end > start {
+        (distance - start) / (end - start)
+    } else {
+        0.
+    };
+    let ((outer_from, inner_from), (outer_to, inner_to)) = (sections[next - 1], sections[next]);
+    (outer_from.lerp(outer_to, t), inner_from.lerp(inner_to, t))
+}
+
+/// Slices the strip made of `sections` into dashes along its center line, spaced as those of
+/// `dashes` are.
+fn dashes_along_strip(sections: &[Section], width: CSSFloat, color: RGBA) -> Vec<BorderShape> {
+    if sections.len() < 2 {
+        return Vec::new();
+    }
+    let distances = distances_along_strip(sections);
+    let length = distances[distances.len() - 1];
+    let dash = width * DASH_LENGTH;
+    let count = ((length + dash) / (dash * 2.)).round().max(1.) as usize;
+    if count == 1 {
+        return vec![BorderShape::Strip(color, sections.to_vec())];
+    }
+    let gap = (length - dash * count as CSSFloat) / (count - 1) as CSSFloat;
+    (0..count)
+        .map(|i| {
+            let dash_start = (dash + gap) * i as CSSFloat;
+            let dash_end = dash_start + dash;
+            let mut dash_sections = vec![section_at(sections, &distances, dash_start)];
+            dash_sections.extend(
+                sections
+                    .iter()
+                    .zip(&distances)
+                    .filter(|(_, &d)| d > dash_start && d < dash_end)
+                    .map(|(section, _)| *section),
+            );
+            dash_sections.push(section_at(sections, &distances, dash_end));
+            BorderShape::Strip(color, dash_sections)
+        })
+        .collect()
+}
+
+/// Places round dots as wide as the border along the center line of the strip made of
+/// `sections`, spaced as those of `dots` are.
+fn dots_along_strip(sections: &[Section], width: CSSFloat, color: RGBA) -> Vec<BorderShape> {
+    if sections.len() < 2 {
+        return Vec::new();
+    }
+    let distances = distances_along_strip(sections);
+    let length = distances[distances.len() - 1];
+    let spaces = (length / (width * 2.)).round().max(1.) as usize;
+    (0..=spaces)
+        .map(|i| {
+            let (outer, inner) = section_at(
+                sections,
+                &distances,
+                length * i as CSSFloat / spaces as CSSFloat,
+            );
+            BorderShape::Circle {
+                color,
+                center: outer.lerp(inner, 0.5),
+                radius: width / 2.,
+            }
+        })
+        .collect()
+}
+
+/// Clips the convex `polygon` to where `distance` isn't negative, where `distance` is the signed
+/// distance from the line to clip at, or any multiple of it.
+///
+/// https://en.wikipedia.org/wiki/Sutherland%E2%80%93Hodgman_algorithm
+fn clip<F: Fn(Vector2F) -> CSSFloat>(polygon: &[Vector2F], distance: F) -> Vec<Vector2F> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (current_distance, next_distance) = (distance(current), distance(next));
+        if current_distance >= 0. {
+            clipped.push(current);
+        }
+        if (current_distance >= 0.) != (next_distance >= 0.) {
+            let t = current_distance / (current_distance - next_distance);
+            clipped.push(current.lerp(next, t));
+        }
+    }
+    clipped
+}
+
+/// The dark and light colors that `groove`, `ridge`, `inset`, and `outset` borders of `color` are
+/// shaded with.  The exact colors are up to the user agent; the dark color is half as bright as
+/// `color`, which is also the light color unless it's black, in which case nothing would be
+/// darker.
+fn shades(color: RGBA) -> (RGBA, RGBA) {
+    if color.red == 0 && color.green == 0 && color.blue == 0 {
+        return (color, RGBA::new(128, 128, 128, color.alpha));
+    }
+    let dark = RGBA::new(color.red / 2, color.green / 2, color.blue / 2, color.alpha);
+    (dark, color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::rect::{CornerRadii, EdgeSizes};
+    use app_units::Au;
+
+    fn border(side: Side, style: LineStyle) -> BorderCommand {
+        rounded_border(side, style, 0)
+    }
+
+    /// A border whose outer corners are all rounded by `radius` pixels.
+    fn rounded_border(side: Side, style: LineStyle, radius: i32) -> BorderCommand {
+        let radius = CornerRadius {
+            horizontal: Au::from_px(radius),
+            vertical: Au::from_px(radius),
+        };
+        let radii = CornerRadii {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        };
+        let widths = EdgeSizes {
+            left: Au::from_px(10),
+            right: Au::from_px(6),
+            top: Au::from_px(6),
+            bottom: Au::from_px(6),
+        };
+        BorderCommand::new(
+            side,
+            style,
+            RGBA::new(200, 100, 50, 255),
+            RoundedRect::new(
+                Rect {
+                    start_x: Au::from_px(0),
+                    start_y: Au::from_px(0),
+                    width: Au::from_px(100),
+                    height: Au::from_px(50),
+                },
+                radii,
+            ),
+            RoundedRect::new(
+                Rect {
+                    start_x: Au::from_px(10),
+                    start_y: Au::from_px(6),
+                    width: Au::from_px(84),
+                    height: Au::from_px(38),
+                },
+                radii.shrunk_by(widths),
+            ),
+        )
+    }
+
+    #[test]
+    fn solid_sides_are_mitred_at_the_corners() {
+        let shapes = border_shapes(&border(Side::Top, LineStyle::Solid));
+        assert_eq!(
+            shapes,
+            vec![BorderShape::Polygon(
+                RGBA::new(200, 100, 50, 255),
+                vec![
+                    Vector2F::new(0., 0.),
+                    Vector2F::new(100., 0.),
+                    Vector2F::new(94., 6.),
+                    Vector2F::new(10., 6.),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn double_borders_paint_the_outer_thirds() {
+        let shapes = border_shapes(&border(Side::Left, LineStyle::Double));
+        assert_eq!(shapes.len(), 2);
+        let xs = |shape: &BorderShape| {
+            let polygon = shape.polygon();
+            let min = polygon
+                .iter()
+                .map(|p| p.x())
+                .fold(CSSFloat::MAX, CSSFloat::min);
+            let max = polygon
+                .iter()
+                .map(|p| p.x())
+                .fold(CSSFloat::MIN, CSSFloat::max);
+            (min.round(), max.round())
+        };
+        assert_eq!(xs(&shapes[0]), (0., 3.));
+        assert_eq!(xs(&shapes[1]), (7., 10.));
+    }
+
+    #[test]
+    fn dashes_start_and_end_in_the_corners() {
+        let shapes = border_shapes(&border(Side::Top, LineStyle::Dashed));
+        assert!(shapes.len() > 1);
+        assert_eq!(shapes[0].polygon()[0], Vector2F::new(0., 0.));
+        assert!(shapes
+            .last()
+            .unwrap()
+            .polygon()
+            .contains(&Vector2F::new(100., 0.)));
+        for shape in &shapes {
+            for point in shape.polygon() {
+                assert!(point.y() >= 0. && point.y() <= 6.);
+            }
+        }
+    }
+
+    #[test]
+    fn rounded_sides_curve_around_the_corners() {
+        let shapes = border_shapes(&rounded_border(Side::Top, LineStyle::Solid, 20));
+        assert_eq!(shapes.len(), 1);
+        let outline = shapes[0].polygon();
+        // The outer edge only reaches the top of the box between the curves of the corners.
+        let top = outline
+            .iter()
+            .filter(|point| point.y().abs() < 0.01)
+            .map(|point| point.x().round())
+            .collect::<Vec<_>>();
+        assert_eq!(top.first(), Some(&20.));
+        assert_eq!(top.last(), Some(&80.));
+        for point in outline {
+            assert!(point.x() > 0. && point.x() < 100.);
+            assert!(point.y() >= -0.01 && point.y() < 25.);
+        }
+    }
+
+    #[test]
+    fn inset_borders_are_darker_on_the_top_and_left() {
+        let color = |side| border_shapes(&border(side, LineStyle::Inset))[0].color();
+        assert_eq!(color(Side::Top), RGBA::new(100, 50, 25, 255));
+        assert_eq!(color(Side::Left), RGBA::new(100, 50, 25, 255));
+        assert_eq!(color(Side::Bottom), RGBA::new(200, 100, 50, 255));
+        assert_eq!(color(Side::Right), RGBA::new(200, 100, 50, 255));
+    }
+}
@@ -1,38 +1,95 @@
-use crate::gfx::char::CharHandle;
-use crate::gfx::font::FontHandle;
+use crate::dom::image::{ElementImage, ImageKey};
+use crate::gfx::background::{background_layers, painting_area, LayerImage};
+use crate::gfx::font::{FontHandle, FontKey};
+use crate::gfx::gradient::{Gradient, GradientShape};
+use crate::gfx::shadow::{box_shadows, BoxShadow};
+use crate::gfx::stacking_context::{
+    is_root_element, opacity, paint_kind, PaintKind, StackingContext,
+};
 use crate::layout::layout_box::{BoxType, LayoutBox};
-use crate::layout::rect::Rect;
+use crate::layout::rect::{Rect, RoundedRect};
+use crate::layout::DumpLayoutFormat;
+use crate::style::values::computed::shadow::Shadow;
 use crate::style::values::computed::LineStyle;
 use crate::Side;
 use app_units::Au;
 use cssparser::RGBA;
-use gl::texture::TextureId;
+use font_kit::error::GlyphLoadingError;
+use font_kit::font::Font;
+use image::RgbaImage;
 use pathfinder_geometry::vector::Vector2F;
+use std::collections::HashMap;
+use std::io::Write;
+use std::rc::Rc;
 
-/// Builds list of display commands that should be used to paint the output.
+/// Builds list of display commands that should be used to paint the output.  Text is only
+/// prepared if `font_handle` is given, since the glyphs it's made of depend on the fonts installed
+/// on the system.
 pub fn build_display_list(
     layout_box: &LayoutBox,
-    char_handle: &CharHandle,
+    font_handle: Option<&FontHandle>,
     scale_factor: f32,
 ) -> DisplayList {
-    let mut display_list = Vec::new();
-    // TODO: Remove the three preceeding statements once text rendering is fixed.
-    // let font_handle = FontHandle::new();
-    // let font = font_handle.get_font("Helvetica").unwrap();
-    // char_handle
-    //     .prepare_char(
-    //         &mut display_list,
-    //         'A',
-    //         RGBA::new(0, 0, 0, 1),
-    //         &font,
-    //         Au::from_f32_px(12.),
-    //         scale_factor,
-    //     )
-    //     .unwrap();
-    prepare_layout_box(&mut display_list, &layout_box);
+    let mut display_list = DisplayList::new();
+    let font = font_handle.and_then(|font_handle| match font_handle.get_default_font() {
+        Ok(font_key) => Some((font_key, font_handle.font(font_key))),
+        Err(err) => {
+            eprintln!("couldn't load a font to paint text with: {:?}", err);
+            None
+        }
+    });
+    let text_context = font.as_ref().map(|(font_key, font)| TextContext {
+        font_key: *font_key,
+        font: &**font,
+        scale_factor,
+    });
+    prepare_stacking_context(
+        &mut display_list,
+        &StackingContext::new(layout_box),
+        text_context.as_ref(),
+        scale_factor,
+    );
     display_list
 }
 
+/// What's needed to prepare text for display.
+struct TextContext<'a> {
+    /// The key of the font all text is painted with.
+    font_key: FontKey,
+    font: &'a dyn GlyphSource,
+    scale_factor: f32,
+}
+
+/// The parts of a font that text is laid out with, which are in font units.  This is implemented
+/// by the fonts font-kit loads, and lets text be prepared without any fonts installed.
+trait GlyphSource {
+    fn units_per_em(&self) -> u32;
+    /// The distance from the baseline up to the top of the em box.
+    fn ascent(&self) -> f32;
+    /// The ID of the glyph for `ch`, if the font has one.
+    fn glyph_id(&self, ch: char) -> Option<u32>;
+    /// How far the pen moves along the baseline past the glyph `glyph_id`.
+    fn advance_x(&self, glyph_id: u32) -> Result<f32, GlyphLoadingError>;
+}
+
+impl GlyphSource for Font {
+    fn units_per_em(&self) -> u32 {
+        self.metrics().units_per_em
+    }
+
+    fn ascent(&self) -> f32 {
+        self.metrics().ascent
+    }
+
+    fn glyph_id(&self, ch: char) -> Option<u32> {
+        self.glyph_for_char(ch)
+    }
+
+    fn advance_x(&self, glyph_id: u32) -> Result<f32, GlyphLoadingError> {
+        self.advance(glyph_id).map(|advance| advance.x())
+    }
+}
+
 /// Represents a display list, which is a list of graphics operations Kosmonaut should perform to
 /// paint output to the screen.
 ///
@@ -42,72 +99,595 @@ pub fn build_display_list(
 ///
 /// It also generalizes the input for various different types of
 /// output, such as making pixels for display on a screen or vector graphics for sending to a
-/// printer.
-///
-/// For now, our display list is OpenGL specific, but this could be made more dynamic should we
-/// implement other types of rendering backends.
+/// printer.  Display lists only describe what to paint, and refer to the fonts and images to paint
+/// with by key, leaving it to each `PaintBackend` to turn those into whatever it paints with, such
+/// as glyph atlases and textures.
 ///
 /// https://en.wikipedia.org/wiki/Display_list
-pub type DisplayList = Vec<DisplayCommand>;
+#[derive(Clone, Debug, Default)]
+pub struct DisplayList {
+    commands: Vec<DisplayCommand>,
+    /// The images the commands refer to.
+    images: HashMap<ImageKey, Rc<RgbaImage>>,
+}
+
+impl DisplayList {
+    pub fn new() -> DisplayList {
+        DisplayList::default()
+    }
+
+    pub fn commands(&self) -> &[DisplayCommand] {
+        &self.commands
+    }
+
+    /// Gets the image `key` refers to, if any command in this display list refers to it.
+    pub fn image(&self, key: ImageKey) -> Option<&Rc<RgbaImage>> {
+        self.images.get(&key)
+    }
+
+    /// Gets the part of this display list that paints within `area`, moved by `dx` and `dy` and
+    /// clipped to where `area` ends up, such as the slice of a page fragment.  Commands painting
+    /// entirely outside of `area` are left out, though the viewport background and every push and
+    /// pop are kept, so that the pushes still match the pops.
+    pub fn fragment(&self, area: Rect, dx: Au, dy: Au) -> DisplayList {
+        let overlaps = |rect: &Rect| rect.start_y < area.end_y() && rect.end_y() > area.start_y;
+        let mut fragment = DisplayList::new();
+        let mut background = None;
+        let mut commands = Vec::new();
+        for command in &self.commands {
+            let translated = match command {
+                DisplayCommand::Border(border) => {
+                    if !overlaps(&border.border_box.rect) {
+                        continue;
+                    }
+                    DisplayCommand::Border(BorderCommand {
+                        border_box: border.border_box.translated(dx, dy),
+                        padding_box: border.padding_box.translated(dx, dy),
+                        ..border.clone()
+                    })
+                }
+                DisplayCommand::BoxShadow(shadow) => {
+                    if !overlaps(&shadow.bounds()) {
+                        continue;
+                    }
+                    DisplayCommand::BoxShadow(shadow.translated(dx, dy))
+                }
+                DisplayCommand::Glyph(glyph) => {
+                    // Glyphs have no bounds until they're rasterized, but none reach further than
+                    // their font size from their baseline, plus the blur of a shadow.
+                    let (baseline, size) = (
+                        glyph.origin.y(),
+                        glyph.size.to_f32_px() + glyph.blur_radius.to_f32_px() * 1.5,
+                    );
+                    if baseline + size <= area.start_y.to_f32_px()
+                        || baseline - size >= area.end_y().to_f32_px()
+                    {
+                        continue;
+                    }
+                    DisplayCommand::Glyph(GlyphCommand {
+                        origin: glyph.origin + Vector2F::new(dx.to_f32_px(), dy.to_f32_px()),
+                        ..glyph.clone()
+                    })
+                }
+                DisplayCommand::Image(key, rect) => {
+                    if !overlaps(rect) {
+                        continue;
+                    }
+                    if let Some(image) = self.images.get(key) {
+                        fragment.images.entry(*key).or_insert_with(|| image.clone());
+                    }
+                    DisplayCommand::Image(*key, rect.translated(dx, dy))
+                }
+                DisplayCommand::ImageTiles(key, tiles) => {
+                    if !overlaps(&tiles.area) {
+                        continue;
+                    }
+                    if let Some(image) = self.images.get(key) {
+                        fragment.images.entry(*key).or_insert_with(|| image.clone());
+                    }
+                    DisplayCommand::ImageTiles(*key, tiles.translated(dx, dy))
+                }
+                DisplayCommand::GradientTiles(gradient, tiles) => {
+                    if !overlaps(&tiles.area) {
+                        continue;
+                    }
+                    DisplayCommand::GradientTiles(gradient.clone(), tiles.translated(dx, dy))
+                }
+                DisplayCommand::PushClip(rect) => DisplayCommand::PushClip(rect.translated(dx, dy)),
+                DisplayCommand::PushRoundedClip(rrect) => {
+                    DisplayCommand::PushRoundedClip(rrect.translated(dx, dy))
+                }
+                DisplayCommand::RectSolidColor(rgba, rect) => {
+                    if !overlaps(rect) {
+                        continue;
+                    }
+                    DisplayCommand::RectSolidColor(*rgba, rect.translated(dx, dy))
+                }
+                DisplayCommand::RoundedRectSolidColor(rgba, rrect) => {
+                    if !overlaps(&rrect.rect) {
+                        continue;
+                    }
+                    DisplayCommand::RoundedRectSolidColor(*rgba, rrect.translated(dx, dy))
+                }
+                DisplayCommand::ViewportBackground(rgba) => {
+                    background = Some(*rgba);
+                    continue;
+                }
+                DisplayCommand::PopClip
+                | DisplayCommand::PushOpacity(_)
+                | DisplayCommand::PopOpacity => command.clone(),
+            };
+            commands.push(translated);
+        }
+        if let Some(rgba) = background {
+            fragment.push(DisplayCommand::ViewportBackground(rgba));
+        }
+        fragment.push(DisplayCommand::PushClip(area.translated(dx, dy)));
+        fragment.commands.extend(commands);
+        fragment.push(DisplayCommand::PopClip);
+        fragment
+    }
+
+    fn push(&mut self, command: DisplayCommand) {
+        self.commands.push(command);
+    }
+
+    /// Adds a command clipping to `clip`, snapped to whole pixels, which is only a
+    /// `PushRoundedClip` if any of its corners are rounded.
+    fn push_clip(&mut self, clip: RoundedRect) {
+        let clip = clip.snapped_to_pixels();
+        self.commands.push(if clip.radii.is_zero() {
+            DisplayCommand::PushClip(clip.rect)
+        } else {
+            DisplayCommand::PushRoundedClip(clip)
+        });
+    }
+
+    /// Adds a command painting `image`, identified by `key`, stretched to fill `rect`.
+    fn push_image(&mut self, key: ImageKey, image: Rc<RgbaImage>, rect: Rect) {
+        self.images.entry(key).or_insert(image);
+        self.commands.push(DisplayCommand::Image(key, rect));
+    }
+}
 
-/// A command to perform a graphics operation.
+/// A command to perform a graphics operation.  The rectangles of commands have already been
+/// snapped to whole pixels.
 #[derive(Clone, Debug)]
 pub enum DisplayCommand {
-    Char(CharCommand),
+    Border(BorderCommand),
+    /// The blurred shadow of a box, painted outside its border box or inside its padding box.
+    BoxShadow(BoxShadow),
+    Glyph(GlyphCommand),
+    /// An image of the display list stretched to fill the given rectangle, such as the content box
+    /// of an `<img>`.
+    Image(ImageKey, Rect),
+    /// An image of the display list painted at its natural size in every one of the given tiles,
+    /// such as the image of a background layer.
+    ImageTiles(ImageKey, Tiles),
+    /// A gradient painted in every one of the given tiles, such as the `linear-gradient()` of a
+    /// background layer.  Its geometry is relative to the top-left corner of each tile.
+    GradientTiles(Rc<Gradient>, Tiles),
+    /// Clips everything painted until the matching `PopClip` to the given rectangle, on top of
+    /// any clip already in effect.
+    PushClip(Rect),
+    /// Clips everything painted until the matching `PopClip` to the given rounded rectangle, such
+    /// as the padding box of a box with `overflow: hidden` and a `border-radius`.
+    PushRoundedClip(RoundedRect),
+    /// Ends the clip started by the latest `PushClip` or `PushRoundedClip` that hasn't been ended
+    /// yet.
+    PopClip,
+    /// Paints everything until the matching `PopOpacity` with the given opacity, on top of any
+    /// opacity already in effect.
+    PushOpacity(f32),
+    /// Ends the opacity started by the latest `PushOpacity` that hasn't been ended yet.
+    PopOpacity,
     RectSolidColor(RGBA, Rect),
+    /// A rectangle with at least one rounded corner, filled with a solid color.  Its edges are
+    /// anti-aliased where they curve.
+    RoundedRectSolidColor(RGBA, RoundedRect),
     /// This _could_ be represented as [`RectSolidColor`], but graphics APIs sometimes have a
     /// special background painting capabilities that are more idiomatic, such as OpenGL's
     /// `Clear(COLOR_BUFFER_BIT)` and `ClearColor(r, g, b, a)` APIs.
     ViewportBackground(RGBA),
 }
 
+/// Writes `display_list` as text, one command per line, used in the `dump-display-list` tests and
+/// debugging.  The commands between a push and its matching pop are indented.
+pub fn dump_display_list<W: Write>(display_list: &DisplayList, write_to: &mut W) {
+    let mut indent_spaces: usize = 0;
+    for command in display_list.commands() {
+        if let DisplayCommand::PopClip | DisplayCommand::PopOpacity = command {
+            indent_spaces = indent_spaces.saturating_sub(2);
+        }
+        let dumped = match command {
+            DisplayCommand::Border(border) => format!(
+                "Border {:?} {:?} {} {} inside {}",
+                border.side(),
+                border.style(),
+                dump_rgba(border.color()),
+                dump_rounded_rect(&border.border_box()),
+                dump_rounded_rect(&border.padding_box()),
+            ),
+            DisplayCommand::BoxShadow(shadow) => format!(
+                "BoxShadow {} {} blur {} {} {} {}",
+                if shadow.is_inset() { "inset" } else { "outer" },
+                dump_rgba(shadow.color()),
+                shadow.blur_radius().dump_layout_format(),
+                dump_rounded_rect(&shadow.shape()),
+                if shadow.is_inset() {
+                    "inside"
+                } else {
+                    "outside"
+                },
+                dump_rounded_rect(&shadow.clip()),
+            ),
+            DisplayCommand::Glyph(glyph) => {
+                let blur = if glyph.blur_radius() > Au(0) {
+                    format!(" blur {}", glyph.blur_radius().dump_layout_format())
+                } else {
+                    String::new()
+                };
+                format!(
+                    "Glyph {} {:?} {} size {} at ({}, {}){}",
+                    glyph.glyph_id(),
+                    glyph.ch(),
+                    dump_rgba(glyph.color()),
+                    glyph.size().to_f32_px().dump_layout_format(),
+                    glyph.origin().x().dump_layout_format(),
+                    glyph.origin().y().dump_layout_format(),
+                    blur
+                )
+            }
+            DisplayCommand::Image(key, rect) => {
+                let dimensions = display_list
+                    .image(*key)
+                    .map_or((0, 0), |image| image.dimensions());
+                format!(
+                    "Image {}x{} {}",
+                    dimensions.0,
+                    dimensions.1,
+                    dump_rect(rect)
+                )
+            }
+            DisplayCommand::ImageTiles(key, tiles) => {
+                let dimensions = display_list
+                    .image(*key)
+                    .map_or((0, 0), |image| image.dimensions());
+                format!(
+                    "ImageTiles {}x{} {}",
+                    dimensions.0,
+                    dimensions.1,
+                    dump_tiles(tiles)
+                )
+            }
+            DisplayCommand::GradientTiles(gradient, tiles) => {
+                let shape = match gradient.shape() {
+                    GradientShape::Linear { start, end } => {
+                        format!("linear from {} to {}", dump_point(start), dump_point(end))
+                    }
+                    GradientShape::Radial {
+                        center,
+                        radius_x,
+                        radius_y,
+                    } => format!(
+                        "radial at {} radii {}x{}",
+                        dump_point(center),
+                        radius_x.dump_layout_format(),
+                        radius_y.dump_layout_format()
+                    ),
+                };
+                let stops = gradient
+                    .stops()
+                    .iter()
+                    .map(|stop| {
+                        format!(
+                            "{} {}",
+                            dump_rgba(stop.color),
+                            stop.offset.dump_layout_format()
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!(
+                    "GradientTiles {}{} stops {} {}",
+                    if gradient.is_repeating() {
+                        "repeating "
+                    } else {
+                        ""
+                    },
+                    shape,
+                    stops.join(", "),
+                    dump_tiles(tiles)
+                )
+            }
+            DisplayCommand::PushClip(rect) => format!("PushClip {}", dump_rect(rect)),
+            DisplayCommand::PushRoundedClip(rrect) => {
+                format!("PushRoundedClip {}", dump_rounded_rect(rrect))
+            }
+            DisplayCommand::PopClip => "PopClip".to_owned(),
+            DisplayCommand::PushOpacity(opacity) => {
+                format!("PushOpacity {}", opacity.dump_layout_format())
+            }
+            DisplayCommand::PopOpacity => "PopOpacity".to_owned(),
+            DisplayCommand::RectSolidColor(rgba, rect) => {
+                format!("RectSolidColor {} {}", dump_rgba(*rgba), dump_rect(rect))
+            }
+            DisplayCommand::RoundedRectSolidColor(rgba, rrect) => format!(
+                "RoundedRectSolidColor {} {}",
+                dump_rgba(*rgba),
+                dump_rounded_rect(rrect)
+            ),
+            DisplayCommand::ViewportBackground(rgba) => {
+                format!("ViewportBackground {}", dump_rgba(*rgba))
+            }
+        };
+        writeln!(write_to, "{:indent$}{}", "", dumped, indent = indent_spaces).unwrap();
+        if let DisplayCommand::PushClip(_)
+        | DisplayCommand::PushRoundedClip(_)
+        | DisplayCommand::PushOpacity(_) = command
+        {
+            indent_spaces += 2;
+        }
+    }
+}
+
+fn dump_point(point: Vector2F) -> String {
+    format!(
+        "({}, {})",
+        point.x().dump_layout_format(),
+        point.y().dump_layout_format()
+    )
+}
+
+/// Dumps `tiles` as the rectangle of one tile, how far apart the tiles are horizontally and
+/// vertically, and the area they cover.
+fn dump_tiles(tiles: &Tiles) -> String {
+    let dump_step =
+        |step: Option<Au>| step.map_or("none".to_owned(), |step| step.dump_layout_format());
+    format!(
+        "tile {} step ({}, {}) in {}",
+        dump_rect(&tiles.tile),
+        dump_step(tiles.step_x),
+        dump_step(tiles.step_y),
+        dump_rect(&tiles.area)
+    )
+}
+
+fn dump_rect(rect: &Rect) -> String {
+    format!(
+        "at ({}, {}) size {}x{}",
+        rect.start_x.dump_layout_format(),
+        rect.start_y.dump_layout_format(),
+        rect.width.dump_layout_format(),
+        rect.height.dump_layout_format()
+    )
+}
+
+/// Dumps `rrect` as its rectangle, followed by the horizontal and vertical radii of its top-left,
+/// top-right, bottom-right, and bottom-left corners if any of them are rounded.
+fn dump_rounded_rect(rrect: &RoundedRect) -> String {
+    if rrect.radii.is_zero() {
+        return dump_rect(&rrect.rect);
+    }
+    let radii = rrect
+        .radii
+        .corners()
+        .iter()
+        .map(|radius| {
+            format!(
+                "{}x{}",
+                radius.horizontal.dump_layout_format(),
+                radius.vertical.dump_layout_format()
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("{} radii {}", dump_rect(&rrect.rect), radii.join(" "))
+}
+
+fn dump_rgba(rgba: RGBA) -> String {
+    format!(
+        "rgba({}, {}, {}, {})",
+        rgba.red,
+        rgba.green,
+        rgba.blue,
+        rgba.alpha_f32().dump_layout_format()
+    )
+}
+
+/// Copies of an image repeated across an area, such as the image of a background layer repeated
+/// across its painting area.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-repeat
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tiles {
+    /// Where one of the copies is painted, which the others are placed relative to.
+    pub tile: Rect,
+    /// How far apart the starts of horizontally adjacent copies are, or `None` if the image isn't
+    /// repeated horizontally.
+    pub step_x: Option<Au>,
+    /// How far apart the starts of vertically adjacent copies are, or `None` if the image isn't
+    /// repeated vertically.
+    pub step_y: Option<Au>,
+    /// The area the copies are repeated across, which is the only part of them that's painted.
+    pub area: Rect,
+}
+
+impl Tiles {
+    /// The rectangles of the copies that overlap `area`, snapped to whole pixels, row by row.
+    pub fn rects(&self) -> Vec<Rect> {
+        let xs = tile_starts(
+            self.tile.start_x,
+            self.tile.width,
+            self.step_x,
+            self.area.start_x,
+            self.area.end_x(),
+        );
+        let ys = tile_starts(
+            self.tile.start_y,
+            self.tile.height,
+            self.step_y,
+            self.area.start_y,
+            self.area.end_y(),
+        );
+        let mut rects = Vec::with_capacity(xs.len() * ys.len());
+        for &start_y in &ys {
+            for &start_x in &xs {
+                rects.push(
+                    Rect {
+                        start_x,
+                        start_y,
+                        ..self.tile
+                    }
+                    .snapped_to_pixels(),
+                );
+            }
+        }
+        rects
+    }
+
+    /// The smallest rectangle containing every copy that overlaps `area`, or `None` if none do.
+    pub fn bounds(&self) -> Option<Rect> {
+        let rects = self.rects();
+        let first = *rects.first()?;
+        Some(rects.iter().fold(first, |bounds, rect| bounds.union(*rect)))
+    }
+
+    /// These tiles moved by `dx` and `dy`.
+    pub fn translated(&self, dx: Au, dy: Au) -> Tiles {
+        Tiles {
+            tile: self.tile.translated(dx, dy),
+            area: self.area.translated(dx, dy),
+            ..*self
+        }
+    }
+}
+
+/// Where the copies of a tile that starts at `start` and is `size` long start along one axis, if
+/// they're `step` apart, for those that overlap the area from `area_start` to `area_end`.
+fn tile_starts(start: Au, size: Au, step: Option<Au>, area_start: Au, area_end: Au) -> Vec<Au> {
+    let step = match step {
+        Some(step) if step > Au(0) => step,
+        _ if start < area_end && start + size > area_start => return vec![start],
+        _ => return Vec::new(),
+    };
+    // The number of steps back from `start` to the first copy that ends after the area starts,
+    // which is negative if that's a copy after `start`.
+    let steps_back = (start + size - area_start - Au(1)).0.div_euclid(step.0);
+    let mut tile_start = start - step * steps_back;
+    let mut starts = Vec::new();
+    while tile_start < area_end {
+        starts.push(tile_start);
+        tile_start += step;
+    }
+    starts
+}
+
+/// A command to paint one side of the border of a box, in its style.  The sides of a border are
+/// painted separately, since each may have its own style and color, but the widths of all of them
+/// are needed to join adjacent sides at the corners.
+#[derive(Clone, Debug)]
+pub struct BorderCommand {
+    side: Side,
+    style: LineStyle,
+    color: RGBA,
+    /// The outer edges of the border, and the radii of its outer corners.
+    border_box: RoundedRect,
+    /// The inner edges of the border, and the radii of its inner corners.
+    padding_box: RoundedRect,
+}
+
+impl BorderCommand {
+    pub fn new(
+        side: Side,
+        style: LineStyle,
+        color: RGBA,
+        border_box: RoundedRect,
+        padding_box: RoundedRect,
+    ) -> Self {
+        BorderCommand {
+            side,
+            style,
+            color,
+            border_box,
+            padding_box,
+        }
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn style(&self) -> LineStyle {
+        self.style
+    }
+
+    pub fn color(&self) -> RGBA {
+        self.color
+    }
+
+    pub fn border_box(&self) -> RoundedRect {
+        self.border_box
+    }
+
+    pub fn padding_box(&self) -> RoundedRect {
+        self.padding_box
+    }
+}
+
+/// A command to paint a glyph of a font.
 #[derive(Clone, Debug)]
-pub struct CharCommand {
-    /// The horizontal and vertical distance to the next glyph.
-    advance: Vector2F,
-    /// Offset from baseline to left/top of glyph.
-    bearing: Vector2F,
-    /// The char to be rendered.
+pub struct GlyphCommand {
+    /// The font the glyph is from.
+    font: FontKey,
+    /// The ID of the glyph within its font.
+    glyph_id: u32,
+    /// The char the glyph was chosen for.
     ch: char,
-    /// The color to render the char as.
+    /// The color to paint the glyph in.
     color: RGBA,
-    /// The size to render the char as.
-    size: Vector2F,
-    /// The x and y coordinates of where the glyph origin should be placed on the viewport.
-    start_coords: Vector2F,
-    // This is the only OpenGL-specific part of state associated with the command.  Kosmonaut is
-    // pretty hardcoded to OpenGL, but I _really_ wanted to keep the DisplayCommands renderer-agnostic...
-    /// The OpenGL texture associated with this character.
-    texture_id: TextureId,
+    /// The font size to paint the glyph at, in device pixels.
+    size: Au,
+    /// The x and y coordinates of where the glyph origin should be placed on the viewport, which
+    /// is on the baseline of the text.
+    origin: Vector2F,
+    /// How far the glyph is blurred, in device pixels, which is only ever the case for the glyphs
+    /// of text shadows.
+    blur_radius: Au,
 }
 
-impl CharCommand {
+impl GlyphCommand {
     pub fn new(
-        advance: Vector2F,
-        bearing: Vector2F,
+        font: FontKey,
+        glyph_id: u32,
         ch: char,
         color: RGBA,
-        size: Vector2F,
-        start_coords: Vector2F,
-        texture_id: TextureId,
+        size: Au,
+        origin: Vector2F,
     ) -> Self {
-        CharCommand {
-            advance,
-            bearing,
+        GlyphCommand {
+            font,
+            glyph_id,
             ch,
             color,
             size,
-            start_coords,
-            texture_id,
+            origin,
+            blur_radius: Au(0),
+        }
+    }
+
+    /// This glyph blurred by a Gaussian with a standard deviation of half of `blur_radius`.
+    pub fn blurred(self, blur_radius: Au) -> Self {
+        GlyphCommand {
+            blur_radius,
+            ..self
         }
     }
 
-    pub fn advance(&self) -> Vector2F {
-        self.advance
+    pub fn font(&self) -> FontKey {
+        self.font
     }
 
-    pub fn bearing(&self) -> Vector2F {
-        self.bearing
+    pub fn glyph_id(&self) -> u32 {
+        self.glyph_id
     }
 
     pub fn ch(&self) -> char {
@@ -118,63 +698,466 @@ impl CharCommand {
         self.color
     }
 
-    pub fn size(&self) -> Vector2F {
+    pub fn size(&self) -> Au {
         self.size
     }
 
-    pub fn start_coords(&self) -> Vector2F {
-        self.start_coords
+    pub fn origin(&self) -> Vector2F {
+        self.origin
     }
 
-    pub fn texture_id(&self) -> TextureId {
-        self.texture_id
+    pub fn blur_radius(&self) -> Au {
+        self.blur_radius
     }
 }
 
-/// Prepares a layout box for display in the correct order.  The order in which each part of a box
-/// is painted is defined here: https://www.w3.org/TR/CSS22/zindex.html
-fn prepare_layout_box(display_list: &mut DisplayList, layout_box: &LayoutBox) {
-    // Step 1 of painting order
-    if layout_box.is_root() {
-        // Step 1.1
+/// Prepares a stacking context for display, which is also how the layers painted as if they
+/// established one are prepared.  The order in which each part of a stacking context is painted is
+/// defined here: https://www.w3.org/TR/CSS22/zindex.html
+fn prepare_stacking_context(
+    display_list: &mut DisplayList,
+    stacking_context: &StackingContext,
+    text: Option<&TextContext>,
+    scale_factor: f32,
+) {
+    let layout_box = stacking_context.layout_box;
+    for clip in &stacking_context.clips {
+        display_list.push_clip(*clip);
+    }
+    let opacity = opacity(layout_box);
+    if !opacity.is_opaque() {
+        display_list.push(DisplayCommand::PushOpacity(opacity.0));
+    }
+
+    // Steps 1 and 2 of painting order
+    prepare_decorations(display_list, layout_box, scale_factor);
+    // Step 3
+    for layer in &stacking_context.negative_z {
+        prepare_stacking_context(display_list, layer, text, scale_factor);
+    }
+    // Steps 4 to 7
+    prepare_in_flow_content(display_list, layout_box, text, scale_factor);
+    // Step 8
+    for layer in &stacking_context.zero_z {
+        prepare_stacking_context(display_list, layer, text, scale_factor);
+    }
+    // Step 9
+    for layer in &stacking_context.positive_z {
+        prepare_stacking_context(display_list, layer, text, scale_factor);
+    }
+
+    if !opacity.is_opaque() {
+        display_list.push(DisplayCommand::PopOpacity);
+    }
+    for _ in &stacking_context.clips {
+        display_list.push(DisplayCommand::PopClip);
+    }
+}
+
+/// Prepares a float, inline-block, or other box painted atomically for display, as if it
+/// established a new stacking context, except that its positioned descendants and the stacking
+/// contexts within it are left to the stacking context it's part of.
+fn prepare_atomically(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    text: Option<&TextContext>,
+    scale_factor: f32,
+) {
+    prepare_decorations(display_list, layout_box, scale_factor);
+    prepare_in_flow_content(display_list, layout_box, text, scale_factor);
+}
+
+/// Prepares the content of `layout_box` that isn't painted in layers of its own for display.
+/// These are steps 4 to 7 of the painting order: first the backgrounds and borders of in-flow
+/// block-level descendants, then floats, and then inline-level content.
+///
+/// TODO: Inline boxes should be painted in step 7 too, and the outlines of everything in step 10.
+fn prepare_in_flow_content(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    text: Option<&TextContext>,
+    scale_factor: f32,
+) {
+    // Step 4 of painting order
+    prepare_descendants(
+        display_list,
+        layout_box,
+        &mut |display_list, descendant, kind| {
+            if kind == PaintKind::InFlow && descendant.box_type() != BoxType::InlineReplaced {
+                prepare_decorations(display_list, descendant, scale_factor);
+            }
+        },
+    );
+    // Step 5
+    prepare_descendants(
+        display_list,
+        layout_box,
+        &mut |display_list, descendant, kind| {
+            if kind == PaintKind::Float {
+                prepare_atomically(display_list, descendant, text, scale_factor);
+            }
+        },
+    );
+    // Step 7
+    prepare_replaced_content(display_list, layout_box, text);
+    prepare_descendants(
+        display_list,
+        layout_box,
+        &mut |display_list, descendant, kind| match kind {
+            PaintKind::Atomic => prepare_atomically(display_list, descendant, text, scale_factor),
+            PaintKind::InFlow if descendant.box_type() == BoxType::InlineReplaced => {
+                prepare_decorations(display_list, descendant, scale_factor);
+                prepare_replaced_content(display_list, descendant, text);
+            }
+            PaintKind::InFlow => {
+                prepare_replaced_content(display_list, descendant, text);
+                if let Some(text) = text {
+                    prepare_text(display_list, descendant, text);
+                }
+            }
+            PaintKind::Float | PaintKind::Layer => {}
+        },
+    );
+}
+
+/// Prepares the text of `layout_box` for display, if it was generated for a text node, in its
+/// computed `color` and `font-size`, over its `text-shadow`s.  Runs of white space are collapsed to
+/// a single space, as they are with `white-space: normal`.
+///
+/// TODO: Layout doesn't measure text yet, so text boxes have no size, and their text is painted
+/// on a single line from where the box starts, over whatever comes after it.
+fn prepare_text(display_list: &mut DisplayList, layout_box: &LayoutBox, text: &TextContext) {
+    let content = match layout_box.text() {
+        Some(content) => content.split_whitespace().collect::<Vec<_>>().join(" "),
+        None => return,
+    };
+    if content.is_empty() {
+        return;
+    }
+    let cvs = layout_box.computed_values();
+    let start = layout_box.physical_dimensions().content;
+    prepare_str(
+        display_list,
+        text,
+        cvs.color.rgba(),
+        &cvs.text_shadow.0,
+        &content,
+        Au::from(cvs.font_size.size),
+        Vector2F::new(start.start_x.to_f32_px(), start.start_y.to_f32_px()),
+    );
+}
+
+/// Prepares `str` for display as a single run of text whose line starts at `start`, measured in
+/// device pixels, with its baseline the ascent of the font below that.  The font size is scaled by
+/// the scale factor, and the baseline is snapped to whole device pixels, so that every glyph sits
+/// on the same one.
+///
+/// Each of `shadows` is painted beneath the text as a copy of its glyphs, offset, blurred, and in
+/// the color of the shadow, with the first shadow on top.
+///
+/// Characters the font has no glyph for are skipped.
+fn prepare_str(
+    display_list: &mut DisplayList,
+    text: &TextContext,
+    color: RGBA,
+    shadows: &[Shadow],
+    str: &str,
+    size: Au,
+    start: Vector2F,
+) {
+    let scaled_size = Au::from_f32_px(size.to_f32_px() * text.scale_factor);
+    // Font metrics are measured in font units, which are scaled to pixels by the number of units
+    // per em.
+    let units_to_px = scaled_size.to_f32_px() / text.font.units_per_em() as f32;
+    let baseline = (start.y() + text.font.ascent() * units_to_px).round();
+    let mut glyphs = Vec::new();
+    let mut pen_x = start.x();
+    for ch in str.chars() {
+        let glyph_id = match text.font.glyph_id(ch) {
+            Some(glyph_id) => glyph_id,
+            None => continue,
+        };
+        glyphs.push(GlyphCommand::new(
+            text.font_key,
+            glyph_id,
+            ch,
+            color,
+            scaled_size,
+            Vector2F::new(pen_x, baseline),
+        ));
+        match text.font.advance_x(glyph_id) {
+            Ok(advance) => pen_x += advance * units_to_px,
+            Err(err) => {
+                eprintln!("couldn't prepare text for display: {:?}", err);
+                break;
+            }
+        }
+    }
+    for shadow in shadows.iter().rev() {
+        if shadow.color.alpha == 0 {
+            continue;
+        }
+        // The offset is snapped to whole device pixels, like the baseline.
+        let offset = Vector2F::new(
+            (shadow.offset_x.px() * text.scale_factor).round(),
+            (shadow.offset_y.px() * text.scale_factor).round(),
+        );
+        let blur_radius = Au::from_f32_px(shadow.blur_radius.px() * text.scale_factor);
+        for glyph in &glyphs {
+            display_list.push(DisplayCommand::Glyph(
+                GlyphCommand {
+                    color: shadow.color,
+                    origin: glyph.origin + offset,
+                    ..glyph.clone()
+                }
+                .blurred(blur_radius),
+            ));
+        }
+    }
+    for glyph in glyphs {
+        display_list.push(DisplayCommand::Glyph(glyph));
+    }
+}
+
+/// Calls `prepare` on each descendant of `layout_box` that's part of its in-flow content, in tree
+/// order, along with how it's painted.  The descendants of floats and boxes painted atomically
+/// are left to them, and layers are skipped altogether.  The overflow clips of `layout_box` and of
+/// the in-flow descendants in between are applied around their descendants.
+fn prepare_descendants(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    prepare: &mut dyn FnMut(&mut DisplayList, &LayoutBox, PaintKind),
+) {
+    let clip = layout_box.overflow_clip();
+    if let Some(clip) = clip {
+        display_list.push_clip(clip);
+    }
+    let clip_index = display_list.commands.len();
+    for child in layout_box.children() {
+        let kind = paint_kind(child, layout_box);
+        match kind {
+            PaintKind::Layer => {}
+            PaintKind::InFlow => {
+                prepare(display_list, child, kind);
+                prepare_descendants(display_list, child, prepare);
+            }
+            PaintKind::Float | PaintKind::Atomic => prepare(display_list, child, kind),
+        }
+    }
+    if clip.is_some() {
+        // Leave out clips that nothing was painted within.
+        if display_list.commands.len() == clip_index {
+            display_list.commands.pop();
+        } else {
+            display_list.push(DisplayCommand::PopClip);
+        }
+    }
+}
+
+/// Prepares the background and borders of `layout_box` for display, along with the background of
+/// the viewport if it's the root element.
+fn prepare_decorations(display_list: &mut DisplayList, layout_box: &LayoutBox, scale_factor: f32) {
+    if is_root_element(layout_box) {
+        // Step 1.1 of painting order
         let cvs = layout_box.computed_values();
         display_list.push(DisplayCommand::ViewportBackground(
             cvs.background_color.rgba(),
         ));
-        // TODO: Step 1.2, painting background images
+        // Step 1.2, painting the background images of the root element over the canvas, is done
+        // by `prepare_background`, once its background color has been painted beneath them.
     }
 
     #[allow(clippy::single_match)]
     match layout_box.box_type() {
-        BoxType::Block => {
-            // Step 2 of painting order
-            prepare_block_listitem_block_equiv(display_list, layout_box)
+        // Anonymous table boxes have no backgrounds or borders to paint.
+        _ if layout_box.is_anonymous() => {}
+        // TODO: In the collapsing border model, the borders of table cells should be painted with
+        // the style of the border that won the conflict resolution.
+        BoxType::Block
+        | BoxType::Flex
+        | BoxType::Grid
+        | BoxType::InlineBlock
+        | BoxType::InlineFlex
+        | BoxType::InlineGrid
+        | BoxType::Table
+        | BoxType::TableRowGroup
+        | BoxType::TableRow
+        | BoxType::TableCell
+        | BoxType::TableColumnGroup
+        | BoxType::TableColumn
+        | BoxType::TableCaption
+        | BoxType::Replaced
+        | BoxType::InlineReplaced => {
+            prepare_block_listitem_block_equiv(display_list, layout_box, scale_factor)
         }
+        // The backgrounds and borders of a table element are painted by its table box, rather
+        // than the wrapper box around it.
+        BoxType::TableWrapper | BoxType::InlineTableWrapper => {}
         BoxType::Anonymous | BoxType::AnonymousInline | BoxType::Inline => {
-            // TODO: Implement other steps of painting order, 3 -> 10
-            // println!("skipping render of non-block box")
+            // TODO: Paint the backgrounds and borders of inline boxes in step 7.
         }
     }
-
-    for child in layout_box.children() {
-        prepare_layout_box(display_list, child);
-    }
 }
 
-fn prepare_block_listitem_block_equiv(display_list: &mut DisplayList, layout_box: &LayoutBox) {
-    prepare_background(display_list, layout_box);
+fn prepare_block_listitem_block_equiv(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    scale_factor: f32,
+) {
+    // Outer shadows are painted beneath the background, and inset shadows over it, but beneath
+    // the borders.
+    prepare_box_shadows(display_list, layout_box, scale_factor, false);
+    prepare_background(display_list, layout_box, scale_factor);
+    prepare_box_shadows(display_list, layout_box, scale_factor, true);
     prepare_borders(display_list, layout_box);
     // TODO: Render text - https://learnopengl.com/In-Practice/Text-Rendering
 }
 
+/// Prepares the image of `layout_box` for display over its content box, if it's a replaced box.
+fn prepare_replaced_content(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    text: Option<&TextContext>,
+) {
+    if !matches!(
+        layout_box.box_type(),
+        BoxType::Replaced | BoxType::InlineReplaced
+    ) {
+        return;
+    }
+    let content = layout_box.physical_dimensions().content;
+    if content.width == Au(0) || content.height == Au(0) {
+        return;
+    }
+    match layout_box.replaced_image() {
+        Some(ElementImage::Decoded(key, image)) => {
+            display_list.push_image(key, image, content.snapped_to_pixels())
+        }
+        // Broken images are painted as an outline of their content box, with their `alt` text
+        // inside it.
+        Some(ElementImage::Broken) => {
+            let color = RGBA::new(192, 192, 192, 255);
+            let thickness = Au::from_px(1).min(content.width).min(content.height);
+            let edges = [
+                Rect {
+                    height: thickness,
+                    ..content
+                },
+                Rect {
+                    start_y: content.end_y() - thickness,
+                    height: thickness,
+                    ..content
+                },
+                Rect {
+                    width: thickness,
+                    ..content
+                },
+                Rect {
+                    start_x: content.end_x() - thickness,
+                    width: thickness,
+                    ..content
+                },
+            ];
+            for edge in edges.iter() {
+                display_list.push(DisplayCommand::RectSolidColor(
+                    color,
+                    edge.snapped_to_pixels(),
+                ));
+            }
+            if let (Some(text), Some(alt)) = (text, layout_box.alt_text()) {
+                // The text isn't wrapped, so whatever doesn't fit on one line is clipped.
+                let cvs = layout_box.computed_values();
+                display_list.push_clip(content.into());
+                prepare_str(
+                    display_list,
+                    text,
+                    cvs.color.rgba(),
+                    &cvs.text_shadow.0,
+                    &alt,
+                    Au::from(cvs.font_size.size),
+                    Vector2F::new(content.start_x.to_f32_px(), content.start_y.to_f32_px()),
+                );
+                display_list.push(DisplayCommand::PopClip);
+            }
+        }
+        None => {}
+    }
+}
+
 /// Prepares the background of `layout_box` for display by converting it to display command(s).
-fn prepare_background(display_list: &mut DisplayList, layout_box: &LayoutBox) {
-    let bg_color = layout_box.computed_values().background_color.rgba();
+/// The background is painted beneath the border, so it follows the rounded corners of the box it's
+/// clipped to.  The color is clipped like the bottom-most background layer, and painted beneath
+/// all of them.  The images of the root element are painted over the canvas instead.
+fn prepare_background(display_list: &mut DisplayList, layout_box: &LayoutBox, scale_factor: f32) {
+    let cvs = layout_box.computed_values();
+    let bg_color = cvs.background_color.rgba();
     if bg_color != RGBA::transparent() {
-        display_list.push(DisplayCommand::RectSolidColor(
-            bg_color,
-            layout_box.physical_dimensions().border_box(),
-        ))
+        let bottom_layer = cvs.background_image.0.len() - 1;
+        let area =
+            painting_area(layout_box, cvs.background_clip.layer(bottom_layer)).snapped_to_pixels();
+        if area.rect.width > Au(0) && area.rect.height > Au(0) {
+            display_list.push(if area.radii.is_zero() {
+                DisplayCommand::RectSolidColor(bg_color, area.rect)
+            } else {
+                DisplayCommand::RoundedRectSolidColor(bg_color, area)
+            })
+        }
+    }
+    // TODO: The images of the root element should cover the whole canvas, which is at least as
+    // large as the viewport, but the size of the viewport isn't known here, so they cover the
+    // margin box of the root element instead.
+    let canvas = if is_root_element(layout_box) {
+        let d = layout_box.physical_dimensions();
+        Some(d.border_box().expanded_by(d.margin))
+    } else {
+        None
+    };
+    prepare_background_images(display_list, layout_box, scale_factor, canvas);
+}
+
+/// Prepares the background layers of `layout_box` that have an image for display, each clipped to
+/// its painting area, or to `canvas` if given.
+fn prepare_background_images(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    scale_factor: f32,
+    canvas: Option<Rect>,
+) {
+    for layer in background_layers(layout_box, scale_factor, canvas) {
+        let bounds = match layer.tiles.bounds() {
+            Some(bounds) => bounds,
+            None => continue,
+        };
+        // Tiles that are all within a rectangular painting area don't need to be clipped to it.
+        let needs_clip =
+            !layer.clip.radii.is_zero() || bounds.intersection(layer.clip.rect) != bounds;
+        if needs_clip {
+            display_list.push_clip(layer.clip);
+        }
+        match layer.image {
+            LayerImage::Raster(key, image) => {
+                display_list.images.entry(key).or_insert(image);
+                display_list.push(DisplayCommand::ImageTiles(key, layer.tiles));
+            }
+            LayerImage::Gradient(gradient) => {
+                display_list.push(DisplayCommand::GradientTiles(gradient, layer.tiles));
+            }
+        }
+        if needs_clip {
+            display_list.push(DisplayCommand::PopClip);
+        }
+    }
+}
+
+/// Prepares the outer `box-shadow`s of `layout_box` for display, or its inset ones if `inset`.
+fn prepare_box_shadows(
+    display_list: &mut DisplayList,
+    layout_box: &LayoutBox,
+    scale_factor: f32,
+    inset: bool,
+) {
+    for shadow in box_shadows(layout_box, scale_factor, inset) {
+        display_list.push(DisplayCommand::BoxShadow(shadow));
     }
 }
 
@@ -194,52 +1177,152 @@ fn prepare_border(display_list: &mut DisplayList, layout_box: &LayoutBox, side:
     let border_color_rgba = cvs.border_color_rgba(side);
     // The border size has already been calculated during layout, so we don't need to get it from
     // the computed values here.
-    let border_size_px = d.border_size(side);
+    let border_size = d.border_size(side);
     if border_style == LineStyle::None
         || border_style == LineStyle::Hidden
         || border_color_rgba == RGBA::transparent()
-        || border_size_px == 0.
+        || border_size == Au(0)
     {
         return;
     }
+    display_list.push(DisplayCommand::Border(BorderCommand::new(
+        side,
+        border_style,
+        border_color_rgba,
+        layout_box.rounded_border_box().snapped_to_pixels(),
+        layout_box.rounded_padding_box().snapped_to_pixels(),
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::values::computed::length::CSSPixelLength;
 
-    let border_box = d.border_box();
-    match side {
-        Side::Bottom => display_list.push(DisplayCommand::RectSolidColor(
-            border_color_rgba,
-            Rect {
-                start_x: border_box.start_x,
-                start_y: (border_box.start_y + border_box.height - border_size_px).px(),
-                width: border_box.width,
-                height: border_size_px,
-            },
-        )),
-        Side::Left => display_list.push(DisplayCommand::RectSolidColor(
-            border_color_rgba,
-            Rect {
-                start_x: border_box.start_x,
-                start_y: border_box.start_y,
-                width: border_size_px,
-                height: border_box.height,
-            },
-        )),
-        Side::Right => display_list.push(DisplayCommand::RectSolidColor(
-            border_color_rgba,
-            Rect {
-                start_x: (border_box.start_x + border_box.width - border_size_px).px(),
-                start_y: border_box.start_y,
-                width: border_size_px,
-                height: border_box.height,
-            },
-        )),
-        Side::Top => display_list.push(DisplayCommand::RectSolidColor(
-            border_color_rgba,
-            Rect {
-                start_x: border_box.start_x,
-                start_y: border_box.start_y,
-                width: border_box.width,
-                height: border_size_px,
-            },
-        )),
+    /// A monospace font with a glyph for each ASCII character, whose ID is its code point.
+    struct AsciiFont;
+
+    impl GlyphSource for AsciiFont {
+        fn units_per_em(&self) -> u32 {
+            1000
+        }
+
+        fn ascent(&self) -> f32 {
+            800.
+        }
+
+        fn glyph_id(&self, ch: char) -> Option<u32> {
+            if ch.is_ascii() {
+                Some(ch as u32)
+            } else {
+                None
+            }
+        }
+
+        fn advance_x(&self, _glyph_id: u32) -> Result<f32, GlyphLoadingError> {
+            Ok(500.)
+        }
+    }
+
+    fn glyphs(display_list: &DisplayList) -> Vec<&GlyphCommand> {
+        display_list
+            .commands()
+            .iter()
+            .map(|command| match command {
+                DisplayCommand::Glyph(glyph) => glyph,
+                command => panic!("expected a glyph, got {:?}", command),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn glyphs_are_placed_along_a_snapped_baseline() {
+        let mut display_list = DisplayList::new();
+        let text = TextContext {
+            font_key: FontKey(0),
+            font: &AsciiFont,
+            scale_factor: 2.,
+        };
+        let color = RGBA::new(10, 20, 30, 255);
+        prepare_str(
+            &mut display_list,
+            &text,
+            color,
+            &[],
+            "ab\u{2603}c",
+            Au::from_px(10),
+            Vector2F::new(3., 4.3),
+        );
+        let glyphs = glyphs(&display_list);
+        // The snowman has no glyph, so it's skipped without advancing the pen.
+        assert_eq!(
+            glyphs.iter().map(|glyph| glyph.ch()).collect::<String>(),
+            "abc"
+        );
+        for (glyph, x) in glyphs.iter().zip(&[3., 13., 23.]) {
+            assert_eq!(glyph.font(), FontKey(0));
+            assert_eq!(glyph.glyph_id(), glyph.ch() as u32);
+            assert_eq!(glyph.color(), color);
+            assert_eq!(glyph.size(), Au::from_px(20));
+            // The ascent of 16px below 4.3px is rounded to the nearest device pixel.
+            assert_eq!(glyph.origin(), Vector2F::new(*x, 20.));
+            assert_eq!(glyph.blur_radius(), Au(0));
+        }
+    }
+
+    #[test]
+    fn text_shadows_are_painted_beneath_the_text_with_the_first_on_top() {
+        let mut display_list = DisplayList::new();
+        let text = TextContext {
+            font_key: FontKey(0),
+            font: &AsciiFont,
+            scale_factor: 2.,
+        };
+        let shadow = |color, offset| Shadow {
+            color,
+            offset_x: CSSPixelLength::new(offset),
+            offset_y: CSSPixelLength::new(offset),
+            blur_radius: CSSPixelLength::new(1.5),
+            spread: CSSPixelLength::new(0.),
+            inset: false,
+        };
+        let first = RGBA::new(255, 0, 0, 255);
+        let last = RGBA::new(0, 0, 255, 128);
+        let transparent = RGBA::new(0, 255, 0, 0);
+        prepare_str(
+            &mut display_list,
+            &text,
+            RGBA::new(0, 0, 0, 255),
+            &[shadow(first, 1.), shadow(transparent, 2.), shadow(last, 3.)],
+            "ab",
+            Au::from_px(10),
+            Vector2F::new(0., 0.),
+        );
+        let glyphs = glyphs(&display_list);
+        // Transparent shadows are left out altogether.
+        assert_eq!(glyphs.len(), 6);
+        let painted = glyphs
+            .iter()
+            .map(|glyph| {
+                (
+                    glyph.ch(),
+                    glyph.color(),
+                    glyph.origin(),
+                    glyph.blur_radius(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let blur_radius = Au::from_px(3);
+        assert_eq!(
+            painted,
+            vec![
+                ('a', last, Vector2F::new(6., 22.), blur_radius),
+                ('b', last, Vector2F::new(16., 22.), blur_radius),
+                ('a', first, Vector2F::new(2., 18.), blur_radius),
+                ('b', first, Vector2F::new(12., 18.), blur_radius),
+                ('a', RGBA::new(0, 0, 0, 255), Vector2F::new(0., 16.), Au(0)),
+                ('b', RGBA::new(0, 0, 0, 255), Vector2F::new(10., 16.), Au(0)),
+            ]
+        );
     }
 }
@@ -1,46 +1,136 @@
+use crate::gfx::shadow::{blur_margin, blur_mask};
 use accountable_refcell::{Ref, RefCell};
-use font_kit::error::{FontLoadingError, SelectionError};
+use font_kit::canvas::{Canvas, Format, RasterizationOptions};
+use font_kit::error::{FontLoadingError, GlyphLoadingError, SelectionError};
+use font_kit::family_name::FamilyName;
+use font_kit::hinting::HintingOptions;
 use font_kit::loaders::default::Font;
+use font_kit::properties::Properties;
 use font_kit::source::SystemSource;
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::{Vector2F, Vector2I};
 use std::collections::HashMap;
 
 /// Provides a handle for loading and caching fonts that abstracts over all different font loaders
 /// and sources.
 #[derive(Default)]
 pub struct FontHandle {
-    cached_fonts: RefCell<HashMap<String, Font>>,
+    /// The fonts loaded so far, indexed by their keys.
+    fonts: RefCell<Vec<Font>>,
+    /// The keys of the fonts loaded so far, by the name they were loaded with.
+    keys: RefCell<HashMap<String, FontKey>>,
 }
 
 pub type PostscriptName = String;
 
+/// Identifies a font loaded through a `FontHandle`, so that display lists can refer to fonts
+/// without holding on to them.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FontKey(pub(super) usize);
+
 impl FontHandle {
     pub fn new() -> FontHandle {
         FontHandle {
-            cached_fonts: RefCell::new(HashMap::new()),
+            fonts: RefCell::new(Vec::new()),
+            keys: RefCell::new(HashMap::new()),
         }
     }
 
-    pub fn get_font(&self, postscript_name: &str) -> Result<Ref<Font>, FontError> {
-        let key = postscript_name.to_owned();
-        {
-            let mut cached_fonts = self.cached_fonts.borrow_mut();
-            if !cached_fonts.contains_key(&key) {
-                let font = load_font(postscript_name)?;
-                cached_fonts.insert(key.clone(), font);
-            }
+    pub fn get_font(&self, postscript_name: &str) -> Result<FontKey, FontError> {
+        self.get_or_load(postscript_name, || load_font(postscript_name))
+    }
+
+    /// Gets the font text is painted with.  This is the system's default sans-serif font.
+    ///
+    /// TODO: Select fonts based on `font-family`, `font-weight`, and `font-style`, once they're
+    /// supported.
+    pub fn get_default_font(&self) -> Result<FontKey, FontError> {
+        self.get_or_load(DEFAULT_FONT_NAME, || {
+            Ok(SystemSource::new()
+                .select_best_match(&[FamilyName::SansSerif], &Properties::new())?
+                .load()?)
+        })
+    }
+
+    /// Gets the font identified by `key`.
+    ///
+    /// Panics if `key` wasn't given out by this handle.
+    pub fn font(&self, key: FontKey) -> Ref<Font> {
+        Ref::map(self.fonts.borrow(), |fonts| &fonts[key.0])
+    }
+
+    fn get_or_load<F>(&self, name: &str, load: F) -> Result<FontKey, FontError>
+    where
+        F: FnOnce() -> Result<Font, FontError>,
+    {
+        if let Some(key) = self.keys.borrow().get(name) {
+            return Ok(*key);
         }
-        let cached_fonts = self.cached_fonts.borrow();
-        let font = Ref::map(cached_fonts, |fonts| fonts.get(&key).unwrap());
-        Ok(font)
+        let font = load()?;
+        let mut fonts = self.fonts.borrow_mut();
+        let key = FontKey(fonts.len());
+        fonts.push(font);
+        self.keys.borrow_mut().insert(name.to_owned(), key);
+        Ok(key)
     }
 }
 
+/// The name the default font is cached under, which can't be mistaken for a postscript name since
+/// those can't contain spaces.
+const DEFAULT_FONT_NAME: &str = "default sans-serif";
+
 fn load_font(postscript_name: &str) -> Result<Font, FontError> {
     Ok(SystemSource::new()
         .select_by_postscript_name(postscript_name)?
         .load()?)
 }
 
+/// Rasterizes the glyph `glyph_id` of `font` at `size_px`, with its origin `offset` to the right
+/// of and below the corner of a pixel, and blurs it by a Gaussian of standard deviation `sigma` if
+/// that's above zero.  The canvas extends `margin` blank pixels past its right and bottom edges.
+/// Returns the canvas and the offset from the glyph origin to its top-left corner, or `None` if
+/// there's nothing to paint, such as for spaces.
+pub fn rasterize_glyph(
+    font: &Font,
+    glyph_id: u32,
+    size_px: f32,
+    offset: Vector2F,
+    sigma: f32,
+    margin: i32,
+) -> Result<Option<(Canvas, Vector2I)>, GlyphLoadingError> {
+    let bounds = font.raster_bounds(
+        glyph_id,
+        size_px,
+        Transform2F::from_translation(offset),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )?;
+    if bounds.width() <= 0 || bounds.height() <= 0 {
+        return Ok(None);
+    }
+    // The blur spreads the glyph beyond its bounds.
+    let blur = Vector2I::splat(blur_margin(sigma));
+    let origin = bounds.origin() - blur;
+    let size = bounds.size() + blur + blur;
+    let mut canvas = Canvas::new(size + Vector2I::splat(margin), Format::A8);
+    font.rasterize_glyph(
+        &mut canvas,
+        glyph_id,
+        size_px,
+        Transform2F::from_translation(offset - origin.to_f32()),
+        HintingOptions::None,
+        RasterizationOptions::GrayscaleAa,
+    )?;
+    blur_mask(
+        &mut canvas.pixels,
+        size.x() as usize,
+        size.y() as usize,
+        canvas.stride,
+        sigma,
+    );
+    Ok(Some((canvas, origin)))
+}
+
 // TODO: This error type seems a bit too general.  May want to refactor as this module evolves.
 #[derive(Debug)]
 pub enum FontError {
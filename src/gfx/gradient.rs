@@ -0,0 +1,279 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-images-3/#gradients
+//  * https://drafts.csswg.org/css-images-3/#coloring-gradient-line
+use cssparser::RGBA;
+use image::{Rgba, RgbaImage};
+use pathfinder_geometry::vector::Vector2F;
+
+/// The most times the stops of a repeating gradient are repeated by `Gradient::unrepeated`.
+const MAX_REPETITIONS: i32 = 1024;
+
+/// A gradient whose geometry has been resolved against the size of the rectangle it's painted
+/// over, in device pixels relative to the top-left corner of that rectangle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    shape: GradientShape,
+    /// The colors of the gradient, and how far along its gradient line or ray each is, in
+    /// non-decreasing order.  There are at least two.
+    stops: Vec<GradientStop>,
+    /// Whether the stops repeat infinitely in both directions, rather than the colors of the first
+    /// and last stop extending before and after them.
+    repeating: bool,
+}
+
+/// The line or ray along which the colors of a gradient change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientShape {
+    /// The colors of a linear gradient change along the line from `start` to `end`, and are the
+    /// same along lines perpendicular to it.
+    Linear { start: Vector2F, end: Vector2F },
+    /// The colors of a radial gradient are the same along ellipses around `center` with the same
+    /// ratio as `radius_x` and `radius_y`, and change along the horizontal ray from `center` to
+    /// `radius_x` to its right.
+    Radial {
+        center: Vector2F,
+        radius_x: f32,
+        radius_y: f32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    /// How far along the gradient line or ray the stop is, in device pixels.
+    pub offset: f32,
+    pub color: RGBA,
+}
+
+impl Gradient {
+    pub fn new(shape: GradientShape, stops: Vec<GradientStop>, repeating: bool) -> Gradient {
+        Gradient {
+            shape,
+            stops,
+            repeating,
+        }
+    }
+
+    pub fn shape(&self) -> GradientShape {
+        self.shape
+    }
+
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    pub fn is_repeating(&self) -> bool {
+        self.repeating
+    }
+
+    /// How far the stops of this gradient repeat after one another, which is `None` if they don't
+    /// repeat, or all are at the same offset.
+    pub fn period(&self) -> Option<f32> {
+        let period = self.stops.last()?.offset - self.stops.first()?.offset;
+        if self.repeating && period > 0. {
+            Some(period)
+        } else {
+            None
+        }
+    }
+
+    /// How far along the gradient line or ray the colors at `point` are.
+    pub fn offset_at(&self, point: Vector2F) -> f32 {
+        match self.shape {
+            GradientShape::Linear { start, end } => {
+                let line = end - start;
+                let length = line.length();
+                if length == 0. {
+                    return 0.;
+                }
+                (point - start).dot(line) / length
+            }
+            GradientShape::Radial {
+                center,
+                radius_x,
+                radius_y,
+            } => {
+                let delta = point - center;
+                let ratio = radius_x / radius_y;
+                Vector2F::new(delta.x(), delta.y() * ratio).length()
+            }
+        }
+    }
+
+    /// Gets the color `offset` along the gradient line or ray.  Colors are interpolated in
+    /// premultiplied alpha, so that transparent stops don't darken the colors next to them.
+    pub fn color_at_offset(&self, offset: f32) -> RGBA {
+        let first = self.stops[0];
+        let offset = match self.period() {
+            Some(period) => first.offset + (offset - first.offset).rem_euclid(period),
+            None => offset,
+        };
+        let next = match self.stops.iter().position(|stop| stop.offset > offset) {
+            Some(0) => return first.color,
+            Some(next) => next,
+            None => return self.stops[self.stops.len() - 1].color,
+        };
+        let (before, after) = (self.stops[next - 1], self.stops[next]);
+        let progress = (offset - before.offset) / (after.offset - before.offset);
+        interpolate(before.color, after.color, progress)
+    }
+
+    /// Gets the color of this gradient at `point`.
+    pub fn color_at(&self, point: Vector2F) -> RGBA {
+        self.color_at_offset(self.offset_at(point))
+    }
+
+    /// Paints this gradient into an image `width` by `height` pixels in size, sampling the color
+    /// at the center of each pixel.
+    pub fn rasterize(&self, width: u32, height: u32) -> RgbaImage {
+        RgbaImage::from_fn(width, height, |x, y| {
+            let color = self.color_at(Vector2F::new(x as f32 + 0.5, y as f32 + 0.5));
+            Rgba([color.red, color.green, color.blue, color.alpha])
+        })
+    }
+
+    /// Gets a gradient that isn't repeating, but paints the same colors as this one over a
+    /// rectangle `width` by `height` pixels in size, for painting backends that can't repeat
+    /// gradients.  The stops are repeated as many times as it takes to cover the rectangle.
+    pub fn unrepeated(&self, width: f32, height: f32) -> Gradient {
+        let period = match self.period() {
+            Some(period) => period,
+            None => {
+                return Gradient {
+                    repeating: false,
+                    ..self.clone()
+                }
+            }
+        };
+        let offsets = [
+            self.offset_at(Vector2F::new(0., 0.)),
+            self.offset_at(Vector2F::new(width, 0.)),
+            self.offset_at(Vector2F::new(0., height)),
+            self.offset_at(Vector2F::new(width, height)),
+        ];
+        let mut min = offsets.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = offsets.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        if let GradientShape::Radial { center, .. } = self.shape {
+            // The offset is smallest at the center, which may be inside the rectangle.
+            if center.x() >= 0. && center.x() <= width && center.y() >= 0. && center.y() <= height {
+                min = 0.;
+            }
+        }
+        let first = self.stops[0].offset;
+        let first_repetition = ((min - first) / period).floor() as i32;
+        let last_repetition = ((max - first) / period)
+            .ceil()
+            .min((first_repetition + MAX_REPETITIONS) as f32) as i32;
+        let mut stops = Vec::new();
+        for repetition in first_repetition..last_repetition {
+            stops.extend(self.stops.iter().map(|stop| GradientStop {
+                offset: stop.offset + repetition as f32 * period,
+                color: stop.color,
+            }));
+        }
+        Gradient {
+            shape: self.shape,
+            stops,
+            repeating: false,
+        }
+    }
+}
+
+/// Interpolates between `from` and `to` by `progress`, between 0 and 1, in premultiplied alpha.
+fn interpolate(from: RGBA, to: RGBA, progress: f32) -> RGBA {
+    let alpha = from.alpha_f32() + (to.alpha_f32() - from.alpha_f32()) * progress;
+    if alpha == 0. {
+        return RGBA::transparent();
+    }
+    let channel = |from_channel: f32, to_channel: f32| {
+        let from_channel = from_channel * from.alpha_f32();
+        let to_channel = to_channel * to.alpha_f32();
+        (from_channel + (to_channel - from_channel) * progress) / alpha
+    };
+    RGBA::from_floats(
+        channel(from.red_f32(), to.red_f32()),
+        channel(from.green_f32(), to.green_f32()),
+        channel(from.blue_f32(), to.blue_f32()),
+        alpha,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop(offset: f32, color: RGBA) -> GradientStop {
+        GradientStop { offset, color }
+    }
+
+    fn horizontal(stops: Vec<GradientStop>, repeating: bool) -> Gradient {
+        Gradient::new(
+            GradientShape::Linear {
+                start: Vector2F::new(0., 0.),
+                end: Vector2F::new(100., 0.),
+            },
+            stops,
+            repeating,
+        )
+    }
+
+    #[test]
+    fn colors_before_and_after_the_stops_extend_from_them() {
+        let red = RGBA::new(255, 0, 0, 255);
+        let blue = RGBA::new(0, 0, 255, 255);
+        let gradient = horizontal(vec![stop(20., red), stop(80., blue)], false);
+        assert_eq!(gradient.color_at(Vector2F::new(0., 50.)), red);
+        assert_eq!(gradient.color_at(Vector2F::new(90., 50.)), blue);
+        assert_eq!(
+            gradient.color_at(Vector2F::new(50., 0.)),
+            RGBA::new(128, 0, 128, 255)
+        );
+    }
+
+    #[test]
+    fn stops_at_the_same_offset_make_a_hard_edge() {
+        let red = RGBA::new(255, 0, 0, 255);
+        let blue = RGBA::new(0, 0, 255, 255);
+        let gradient = horizontal(
+            vec![
+                stop(0., red),
+                stop(50., red),
+                stop(50., blue),
+                stop(100., blue),
+            ],
+            false,
+        );
+        assert_eq!(gradient.color_at_offset(49.9), red);
+        assert_eq!(gradient.color_at_offset(50.), blue);
+    }
+
+    #[test]
+    fn transparent_stops_are_interpolated_in_premultiplied_alpha() {
+        let red = RGBA::new(255, 0, 0, 255);
+        let gradient = horizontal(vec![stop(0., red), stop(100., RGBA::transparent())], false);
+        let middle = gradient.color_at_offset(50.);
+        assert_eq!((middle.red, middle.green, middle.blue), (255, 0, 0));
+        assert_eq!(middle.alpha, 128);
+    }
+
+    #[test]
+    fn repeating_stops_repeat_in_both_directions() {
+        let red = RGBA::new(255, 0, 0, 255);
+        let blue = RGBA::new(0, 0, 255, 255);
+        let gradient = horizontal(vec![stop(10., red), stop(30., blue)], true);
+        assert_eq!(gradient.period(), Some(20.));
+        assert_eq!(gradient.color_at_offset(50.), red);
+        assert_eq!(gradient.color_at_offset(-10.), red);
+        assert_eq!(gradient.color_at_offset(60.), gradient.color_at_offset(20.));
+
+        let unrepeated = gradient.unrepeated(100., 10.);
+        assert!(!unrepeated.is_repeating());
+        assert_eq!(unrepeated.stops().first().unwrap().offset, -10.);
+        assert_eq!(unrepeated.stops().last().unwrap().offset, 110.);
+        for offset in &[0., 15., 42., 99.] {
+            assert_eq!(
+                unrepeated.color_at_offset(*offset),
+                gradient.color_at_offset(*offset)
+            );
+        }
+    }
+}
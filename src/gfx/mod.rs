@@ -8,11 +8,16 @@ use glutin::{ContextBuilder, GlProfile, PossiblyCurrent, WindowedContext};
 use image::ImageFormat;
 use std::io::Cursor;
 
-pub mod char;
+pub mod atlas;
+pub mod background;
+pub mod border;
 pub mod display;
 pub mod font;
+pub mod gradient;
 pub mod ndc;
 pub mod paint;
+pub mod shadow;
+pub mod stacking_context;
 
 static DEFAULT_INNER_WINDOW_WIDTH_PX: f32 = 1920.;
 static DEFAULT_INNER_WINDOW_HEIGHT_PX: f32 = 1080.;
@@ -44,6 +49,8 @@ pub fn init_main_window_and_gl(
         ));
     let windowed_context = ContextBuilder::new()
         .with_gl_profile(GlProfile::Core)
+        // Rounded clips are drawn into the stencil buffer.
+        .with_stencil_buffer(8)
         .build_windowed(wb, &el)
         .unwrap();
     let windowed_context = unsafe { windowed_context.make_current().unwrap() };
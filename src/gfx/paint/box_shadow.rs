@@ -0,0 +1,184 @@
+use crate::gfx::ndc::{ndc_x, ndc_y};
+use crate::gfx::paint::build_program;
+use crate::gfx::shadow::BoxShadow;
+use crate::layout::rect::RoundedRect;
+use gl::program::Program;
+use gl::types::{GLint, GLvoid};
+use gl::vao::VertexArrayObject;
+use gl::vbo::VertexBufferObject;
+use gl::Gl;
+use std::ffi::CString;
+
+/// The number of floats each vertex of a shadow's bounds is made of: its position in normalized
+/// device coordinates, and in pixels.
+const FLOATS_PER_VERTEX: usize = 2 + 2;
+
+/// Uses given OpenGL instance to paint the blurred shadows of boxes, whose coverage is computed
+/// analytically by a fragment shader from the shape and blur of each shadow.
+pub struct BoxShadowPainter {
+    /// The OpenGL program that will be used to paint shadows.
+    program: Program,
+    /// The locations of the uniforms of `program`.
+    shape_rect: GLint,
+    shape_horizontal_radii: GLint,
+    shape_vertical_radii: GLint,
+    clip_rect: GLint,
+    clip_horizontal_radii: GLint,
+    clip_vertical_radii: GLint,
+    sigma: GLint,
+    inset: GLint,
+    color: GLint,
+    /// The VAO to use to paint shadows.
+    vao: VertexArrayObject,
+    /// An instance of OpenGL.
+    gl: Gl,
+}
+
+impl BoxShadowPainter {
+    pub fn new(gl: &Gl) -> Result<BoxShadowPainter, String> {
+        let vbo = VertexBufferObject::new(gl);
+        let config_vao = |gl: &Gl| {
+            let stride = (FLOATS_PER_VERTEX * std::mem::size_of::<f32>()) as GLint;
+            for &(index, offset) in &[(0, 0), (1, 2)] {
+                unsafe {
+                    gl.EnableVertexAttribArray(index);
+                    gl.VertexAttribPointer(
+                        index,
+                        2,
+                        gl::FLOAT,
+                        gl::FALSE,
+                        stride,
+                        (offset * std::mem::size_of::<f32>()) as *const GLvoid,
+                    );
+                }
+            }
+        };
+        let vao = unsafe { VertexArrayObject::new(vbo, config_vao, gl) };
+        let program = build_box_shadow_program(gl)?;
+        let uniform = |name: &str| {
+            let name = CString::new(name).unwrap();
+            unsafe { gl.GetUniformLocation(program.id(), name.as_ptr()) }
+        };
+
+        Ok(BoxShadowPainter {
+            shape_rect: uniform("ShapeRect"),
+            shape_horizontal_radii: uniform("ShapeHorizontalRadii"),
+            shape_vertical_radii: uniform("ShapeVerticalRadii"),
+            clip_rect: uniform("ClipRect"),
+            clip_horizontal_radii: uniform("ClipHorizontalRadii"),
+            clip_vertical_radii: uniform("ClipVerticalRadii"),
+            sigma: uniform("Sigma"),
+            inset: uniform("Inset"),
+            color: uniform("ShadowColor"),
+            program,
+            vao,
+            gl: gl.clone(),
+        })
+    }
+
+    /// Paints `shadow` over its bounds.
+    pub fn paint(&mut self, shadow: &BoxShadow, viewport_width: f32, viewport_height: f32) {
+        let bounds = shadow.bounds();
+        let (left, top, right, bottom) = (
+            bounds.start_x.to_f32_px(),
+            bounds.start_y.to_f32_px(),
+            bounds.end_x().to_f32_px(),
+            bounds.end_y().to_f32_px(),
+        );
+        let mut vertices = Vec::with_capacity(6 * FLOATS_PER_VERTEX);
+        for &(x, y) in &[
+            (left, top),
+            (right, top),
+            (left, bottom),
+            (left, bottom),
+            (right, top),
+            (right, bottom),
+        ] {
+            vertices.extend_from_slice(&[
+                ndc_x(x, viewport_width),
+                ndc_y(y, viewport_height),
+                x,
+                y,
+            ]);
+        }
+        let color = shadow.color();
+
+        self.program.use_globally();
+        self.vao.store_vertex_data(&vertices);
+        unsafe {
+            self.set_rounded_rect(
+                &shadow.shape(),
+                self.shape_rect,
+                self.shape_horizontal_radii,
+                self.shape_vertical_radii,
+            );
+            self.set_rounded_rect(
+                &shadow.clip(),
+                self.clip_rect,
+                self.clip_horizontal_radii,
+                self.clip_vertical_radii,
+            );
+            self.gl.Uniform1f(self.sigma, shadow.sigma());
+            self.gl.Uniform1i(self.inset, shadow.is_inset() as i32);
+            self.gl.Uniform4f(
+                self.color,
+                color.red_f32(),
+                color.green_f32(),
+                color.blue_f32(),
+                color.alpha_f32(),
+            );
+            self.gl.BindVertexArray(self.vao.name());
+            self.gl.DrawArrays(gl::TRIANGLES, 0, 6);
+            self.gl.BindVertexArray(0);
+        }
+    }
+
+    /// Sets the uniforms at `rect`, `horizontal_radii`, and `vertical_radii` to the edges and
+    /// corner radii of `rrect`.
+    unsafe fn set_rounded_rect(
+        &self,
+        rrect: &RoundedRect,
+        rect: GLint,
+        horizontal_radii: GLint,
+        vertical_radii: GLint,
+    ) {
+        self.gl.Uniform4f(
+            rect,
+            rrect.rect.start_x.to_f32_px(),
+            rrect.rect.start_y.to_f32_px(),
+            rrect.rect.end_x().to_f32_px(),
+            rrect.rect.end_y().to_f32_px(),
+        );
+        let corners = rrect.radii.corners();
+        self.gl.Uniform4f(
+            horizontal_radii,
+            corners[0].horizontal.to_f32_px(),
+            corners[1].horizontal.to_f32_px(),
+            corners[2].horizontal.to_f32_px(),
+            corners[3].horizontal.to_f32_px(),
+        );
+        self.gl.Uniform4f(
+            vertical_radii,
+            corners[0].vertical.to_f32_px(),
+            corners[1].vertical.to_f32_px(),
+            corners[2].vertical.to_f32_px(),
+            corners[3].vertical.to_f32_px(),
+        );
+    }
+}
+
+fn build_box_shadow_program(gl: &Gl) -> Result<Program, String> {
+    let vertex_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/box_shadow.vert"
+    )))
+    .expect("could not create cstring for box shadow program");
+
+    let frag_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/box_shadow.frag"
+    )))
+    .expect("could not create cstring for box shadow program");
+
+    build_program(vertex_shader_src, frag_shader_src, gl)
+}
@@ -0,0 +1,276 @@
+use crate::gfx::atlas::{AtlasError, AtlasRect, ShelfAtlas, GUTTER};
+use crate::gfx::display::GlyphCommand;
+use crate::gfx::font::{rasterize_glyph, FontKey};
+use app_units::Au;
+use font_kit::canvas::Canvas;
+use font_kit::error::GlyphLoadingError;
+use font_kit::font::Font;
+use gl::texture::{Texture, TextureId, TextureKind};
+use gl::types::GLint;
+use gl::{
+    Gl, CLAMP_TO_EDGE, LINEAR, RED, TEXTURE_2D, TEXTURE_MAG_FILTER, TEXTURE_MIN_FILTER,
+    TEXTURE_WRAP_S, TEXTURE_WRAP_T, UNPACK_ALIGNMENT, UNSIGNED_BYTE,
+};
+use pathfinder_geometry::rect::RectF;
+use pathfinder_geometry::vector::Vector2F;
+use std::collections::HashMap;
+use std::os::raw::c_void;
+
+/// The width and height of each glyph atlas, in texels.
+const ATLAS_SIZE: u32 = 1024;
+/// The number of horizontal positions within a pixel glyphs are rasterized at, so that they can
+/// be placed more precisely than whole pixels allow while still being copied from the atlas one
+/// texel per pixel.
+const SUBPIXEL_POSITIONS: u8 = 4;
+
+/// Identifies a glyph atlas, so that the glyphs painted from it can be painted together.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AtlasId(u32);
+
+/// The glyphs of each font, at each size, subpixel offset, and blur radius, are kept in an atlas of
+/// their own.
+type AtlasKey = (FontKey, Au, u8, Au);
+
+/// A glyph that's been rasterized into an atlas.
+#[derive(Clone, Copy, Debug)]
+struct AtlasGlyph {
+    /// Offset from the origin of the glyph to the top-left corner of its rasterization.
+    bearing: Vector2F,
+    /// Where the rasterization is in the atlas, or `None` if there's nothing to paint, such as for
+    /// spaces.
+    rect: Option<AtlasRect>,
+}
+
+/// A glyph ready to be painted from an atlas.
+#[derive(Clone, Copy, Debug)]
+pub struct GlyphQuad {
+    /// The atlas the glyph was rasterized into.
+    pub atlas: AtlasId,
+    /// Where to paint the glyph on the viewport, in device pixels.
+    pub rect: RectF,
+    /// Where the glyph is in its atlas, in texture coordinates, which range from 0 to 1.
+    pub tex_coords: RectF,
+}
+
+/// An OpenGL texture holding the rasterized glyphs of one font, at one size, subpixel offset, and
+/// blur radius.
+struct GlyphAtlas {
+    id: AtlasId,
+    allocator: ShelfAtlas<u32>,
+    glyphs: HashMap<u32, AtlasGlyph>,
+    texture: Texture,
+    gl: Gl,
+}
+
+impl GlyphAtlas {
+    fn new(id: AtlasId, gl: &Gl) -> GlyphAtlas {
+        let texture = Texture::new(TextureKind::TwoDimensional, gl);
+        // Start with a blank texture, so that the gutters around glyphs are empty.
+        let blank = vec![0u8; (ATLAS_SIZE * ATLAS_SIZE) as usize];
+        unsafe {
+            // Set alignment to a single byte since we only use one byte per pixel.
+            gl.PixelStorei(UNPACK_ALIGNMENT, 1);
+            gl.BindTexture(TEXTURE_2D, texture.id());
+            gl.TexImage2D(
+                TEXTURE_2D,
+                0,
+                RED as GLint,
+                ATLAS_SIZE as GLint,
+                ATLAS_SIZE as GLint,
+                0,
+                RED,
+                UNSIGNED_BYTE,
+                blank.as_ptr() as *const c_void,
+            );
+            gl.TexParameteri(TEXTURE_2D, TEXTURE_WRAP_S, CLAMP_TO_EDGE as GLint);
+            gl.TexParameteri(TEXTURE_2D, TEXTURE_WRAP_T, CLAMP_TO_EDGE as GLint);
+            gl.TexParameteri(TEXTURE_2D, TEXTURE_MIN_FILTER, LINEAR as GLint);
+            gl.TexParameteri(TEXTURE_2D, TEXTURE_MAG_FILTER, LINEAR as GLint);
+            // Reset byte-alignment to default value of 4.
+            gl.PixelStorei(UNPACK_ALIGNMENT, 4);
+            gl.BindTexture(TEXTURE_2D, 0);
+        }
+        GlyphAtlas {
+            id,
+            allocator: ShelfAtlas::new(ATLAS_SIZE, ATLAS_SIZE),
+            glyphs: HashMap::new(),
+            texture,
+            gl: gl.clone(),
+        }
+    }
+
+    /// Gets the glyph `glyph_id` of `font` from this atlas, rasterizing it into the atlas first if
+    /// it isn't there.
+    fn get_glyph(
+        &mut self,
+        glyph_id: u32,
+        font: &Font,
+        size: Au,
+        subpixel_position: u8,
+        blur_radius: Au,
+    ) -> Result<AtlasGlyph, GlyphError> {
+        if let Some(glyph) = self.glyphs.get(&glyph_id) {
+            if glyph.rect.is_some() {
+                self.allocator.get(&glyph_id);
+            }
+            return Ok(*glyph);
+        }
+        let subpixel_offset =
+            Vector2F::new(subpixel_position as f32 / SUBPIXEL_POSITIONS as f32, 0.);
+        // The canvas covers the gutter too, which clears whatever was there before.
+        let rasterized = rasterize_glyph(
+            font,
+            glyph_id,
+            size.to_f32_px(),
+            subpixel_offset,
+            blur_radius.to_f32_px() / 2.,
+            GUTTER as i32,
+        )?;
+        let (rect, bearing) = match rasterized {
+            Some((canvas, bearing)) => {
+                let (width, height) = (canvas.size.x() as u32, canvas.size.y() as u32);
+                let allocation =
+                    self.allocator
+                        .allocate(glyph_id, width - GUTTER, height - GUTTER)?;
+                for evicted in allocation.evicted {
+                    self.glyphs.remove(&evicted);
+                }
+                self.upload(&canvas, allocation.rect);
+                (Some(allocation.rect), bearing.to_f32())
+            }
+            None => (None, Vector2F::default()),
+        };
+        let glyph = AtlasGlyph { bearing, rect };
+        self.glyphs.insert(glyph_id, glyph);
+        Ok(glyph)
+    }
+    /// Copies `canvas` into the atlas texture, with its top-left corner at that of `rect`.
+    fn upload(&self, canvas: &Canvas, rect: AtlasRect) {
+        unsafe {
+            self.gl.PixelStorei(UNPACK_ALIGNMENT, 1);
+            self.gl.BindTexture(TEXTURE_2D, self.texture.id());
+            self.gl.TexSubImage2D(
+                TEXTURE_2D,
+                0,
+                rect.x as GLint,
+                rect.y as GLint,
+                canvas.size.x(),
+                canvas.size.y(),
+                RED,
+                UNSIGNED_BYTE,
+                canvas.pixels.as_ptr() as *const c_void,
+            );
+            self.gl.PixelStorei(UNPACK_ALIGNMENT, 4);
+            self.gl.BindTexture(TEXTURE_2D, 0);
+        }
+    }
+
+    /// Converts `rect` to texture coordinates, which range from 0 to 1 across the atlas.
+    fn tex_coords(&self, rect: AtlasRect) -> RectF {
+        let atlas_size = ATLAS_SIZE as f32;
+        RectF::from_points(
+            Vector2F::new(rect.x as f32, rect.y as f32) / atlas_size,
+            Vector2F::new((rect.x + rect.width) as f32, (rect.y + rect.height) as f32) / atlas_size,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum GlyphError {
+    Atlas(AtlasError),
+    Loading(GlyphLoadingError),
+}
+
+impl From<AtlasError> for GlyphError {
+    fn from(err: AtlasError) -> Self {
+        GlyphError::Atlas(err)
+    }
+}
+
+impl From<GlyphLoadingError> for GlyphError {
+    fn from(err: GlyphLoadingError) -> Self {
+        GlyphError::Loading(err)
+    }
+}
+
+/// Rasterizes the glyphs of `GlyphCommand`s into OpenGL textures, packing those of each font, size,
+/// subpixel offset, and blur radius into an atlas of their own, so that they can be painted
+/// together.
+pub struct GlyphAtlases {
+    atlases: HashMap<AtlasKey, GlyphAtlas>,
+    gl: Gl,
+}
+
+impl GlyphAtlases {
+    pub fn new(gl: &Gl) -> Self {
+        GlyphAtlases {
+            atlases: HashMap::new(),
+            gl: gl.clone(),
+        }
+    }
+
+    /// Starts painting a new frame.  Glyphs that were only used in earlier frames may be evicted
+    /// from their atlas to make room for others from now on.
+    pub fn next_frame(&mut self) {
+        for atlas in self.atlases.values_mut() {
+            atlas.allocator.next_generation();
+        }
+    }
+
+    /// Gets the texture of the atlas `id`.
+    pub fn texture_id(&self, id: AtlasId) -> Option<TextureId> {
+        self.atlases
+            .values()
+            .find(|atlas| atlas.id == id)
+            .map(|atlas| atlas.texture.id())
+    }
+
+    /// Gets the quad to paint the glyph of `glyph` with, rasterizing it into an atlas first if it
+    /// isn't in one, or `None` if there's nothing to paint.  `font` is the font `glyph` refers
+    /// to.  The horizontal position of the glyph origin is rounded to the nearest of the subpixel
+    /// positions glyphs are rasterized at.
+    pub fn get_quad(
+        &mut self,
+        glyph: &GlyphCommand,
+        font: &Font,
+    ) -> Result<Option<GlyphQuad>, GlyphError> {
+        let origin = glyph.origin();
+        let mut whole_x = origin.x().floor();
+        let mut subpixel_position =
+            ((origin.x() - whole_x) * SUBPIXEL_POSITIONS as f32).round() as u8;
+        if subpixel_position == SUBPIXEL_POSITIONS {
+            whole_x += 1.;
+            subpixel_position = 0;
+        }
+
+        let next_id = AtlasId(self.atlases.len() as u32);
+        let gl = &self.gl;
+        let atlas = self
+            .atlases
+            .entry((
+                glyph.font(),
+                glyph.size(),
+                subpixel_position,
+                glyph.blur_radius(),
+            ))
+            .or_insert_with(|| GlyphAtlas::new(next_id, gl));
+        let atlas_glyph = atlas.get_glyph(
+            glyph.glyph_id(),
+            font,
+            glyph.size(),
+            subpixel_position,
+            glyph.blur_radius(),
+        )?;
+        Ok(atlas_glyph.rect.map(|rect| {
+            let top_left = Vector2F::new(whole_x, origin.y()) + atlas_glyph.bearing;
+            GlyphQuad {
+                atlas: atlas.id,
+                rect: RectF::new(
+                    top_left,
+                    Vector2F::new(rect.width as f32, rect.height as f32),
+                ),
+                tex_coords: atlas.tex_coords(rect),
+            }
+        }))
+    }
+}
@@ -0,0 +1,179 @@
+use crate::gfx::gradient::{Gradient, GradientShape};
+use crate::gfx::ndc::{ndc_x, ndc_y};
+use crate::gfx::paint::build_program;
+use crate::layout::rect::Rect;
+use gl::program::Program;
+use gl::types::{GLint, GLvoid};
+use gl::vao::VertexArrayObject;
+use gl::vbo::VertexBufferObject;
+use gl::Gl;
+use std::ffi::CString;
+
+/// The number of floats each vertex of a tile is made of: its position in normalized device
+/// coordinates, and in pixels relative to the top-left corner of its tile.
+const FLOATS_PER_VERTEX: usize = 2 + 2;
+
+/// The most stops the fragment shader interpolates between, which is the size of its stop arrays.
+const MAX_STOPS: usize = 16;
+
+/// Uses given OpenGL instance to paint tiles of linear and radial gradients, whose colors are
+/// computed by a fragment shader from the gradient's geometry and stops.
+pub struct GradientPainter {
+    /// The OpenGL program that will be used to paint gradients.
+    program: Program,
+    /// The locations of the uniforms of `program`.
+    shape: GLint,
+    geometry: GLint,
+    stop_count: GLint,
+    stop_offsets: GLint,
+    stop_colors: GLint,
+    period: GLint,
+    /// The VAO to use to paint gradients.
+    vao: VertexArrayObject,
+    /// An instance of OpenGL.
+    gl: Gl,
+}
+
+impl GradientPainter {
+    pub fn new(gl: &Gl) -> Result<GradientPainter, String> {
+        let vbo = VertexBufferObject::new(gl);
+        let config_vao = |gl: &Gl| {
+            let stride = (FLOATS_PER_VERTEX * std::mem::size_of::<f32>()) as GLint;
+            for &(index, offset) in &[(0, 0), (1, 2)] {
+                unsafe {
+                    gl.EnableVertexAttribArray(index);
+                    gl.VertexAttribPointer(
+                        index,
+                        2,
+                        gl::FLOAT,
+                        gl::FALSE,
+                        stride,
+                        (offset * std::mem::size_of::<f32>()) as *const GLvoid,
+                    );
+                }
+            }
+        };
+        let vao = unsafe { VertexArrayObject::new(vbo, config_vao, gl) };
+        let program = build_gradient_program(gl)?;
+        let uniform = |name: &str| {
+            let name = CString::new(name).unwrap();
+            unsafe { gl.GetUniformLocation(program.id(), name.as_ptr()) }
+        };
+
+        Ok(GradientPainter {
+            shape: uniform("Shape"),
+            geometry: uniform("Geometry"),
+            stop_count: uniform("StopCount"),
+            stop_offsets: uniform("StopOffsets"),
+            stop_colors: uniform("StopColors"),
+            period: uniform("Period"),
+            program,
+            vao,
+            gl: gl.clone(),
+        })
+    }
+
+    /// Paints `gradient` in each of `tiles`.
+    ///
+    /// TODO: Gradients with more than `MAX_STOPS` stops are painted with only the first of them.
+    pub fn paint(
+        &mut self,
+        gradient: &Gradient,
+        tiles: &[Rect],
+        viewport_width: f32,
+        viewport_height: f32,
+    ) {
+        if tiles.is_empty() {
+            return;
+        }
+        let mut vertices = Vec::with_capacity(tiles.len() * 6 * FLOATS_PER_VERTEX);
+        for tile in tiles {
+            let (left, top, right, bottom) = (
+                tile.start_x.to_f32_px(),
+                tile.start_y.to_f32_px(),
+                tile.end_x().to_f32_px(),
+                tile.end_y().to_f32_px(),
+            );
+            for &(x, y) in &[
+                (left, top),
+                (right, top),
+                (left, bottom),
+                (left, bottom),
+                (right, top),
+                (right, bottom),
+            ] {
+                vertices.extend_from_slice(&[
+                    ndc_x(x, viewport_width),
+                    ndc_y(y, viewport_height),
+                    x - left,
+                    y - top,
+                ]);
+            }
+        }
+        let vertex_count = vertices.len() / FLOATS_PER_VERTEX;
+        // Panic rather than truncate data.
+        assert!(vertex_count <= i32::MAX as usize);
+
+        let (shape, geometry) = match gradient.shape() {
+            GradientShape::Linear { start, end } => (0, [start.x(), start.y(), end.x(), end.y()]),
+            GradientShape::Radial {
+                center,
+                radius_x,
+                radius_y,
+            } => (1, [center.x(), center.y(), radius_x, radius_y]),
+        };
+        let stops = &gradient.stops()[..gradient.stops().len().min(MAX_STOPS)];
+        let offsets = stops.iter().map(|stop| stop.offset).collect::<Vec<_>>();
+        let colors = stops
+            .iter()
+            .flat_map(|stop| {
+                vec![
+                    stop.color.red_f32(),
+                    stop.color.green_f32(),
+                    stop.color.blue_f32(),
+                    stop.color.alpha_f32(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        self.program.use_globally();
+        self.vao.store_vertex_data(&vertices);
+        unsafe {
+            self.gl.Uniform1i(self.shape, shape);
+            self.gl.Uniform4f(
+                self.geometry,
+                geometry[0],
+                geometry[1],
+                geometry[2],
+                geometry[3],
+            );
+            self.gl.Uniform1i(self.stop_count, stops.len() as i32);
+            self.gl
+                .Uniform1fv(self.stop_offsets, stops.len() as i32, offsets.as_ptr());
+            self.gl
+                .Uniform4fv(self.stop_colors, stops.len() as i32, colors.as_ptr());
+            self.gl
+                .Uniform1f(self.period, gradient.period().unwrap_or(0.));
+            self.gl.BindVertexArray(self.vao.name());
+            // Safe because of the assert above.
+            self.gl.DrawArrays(gl::TRIANGLES, 0, vertex_count as i32);
+            self.gl.BindVertexArray(0);
+        }
+    }
+}
+
+fn build_gradient_program(gl: &Gl) -> Result<Program, String> {
+    let vertex_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/gradient.vert"
+    )))
+    .expect("could not create cstring for gradient program");
+
+    let frag_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/gradient.frag"
+    )))
+    .expect("could not create cstring for gradient program");
+
+    build_program(vertex_shader_src, frag_shader_src, gl)
+}
@@ -0,0 +1,182 @@
+use crate::dom::image::ImageKey;
+use crate::gfx::display::DisplayList;
+use crate::gfx::ndc::{ndc_x, ndc_y};
+use crate::gfx::paint::build_program;
+use crate::layout::rect::Rect;
+use gl::program::Program;
+use gl::texture::{Texture, TextureKind};
+use gl::types::{GLint, GLsizeiptr};
+use gl::vao::VertexArrayObject;
+use gl::vbo::VertexBufferObject;
+use gl::{
+    Gl, ARRAY_BUFFER, CLAMP_TO_EDGE, DYNAMIC_DRAW, FALSE, FLOAT, LINEAR, RGBA, TEXTURE0,
+    TEXTURE_2D, TEXTURE_MAG_FILTER, TEXTURE_MIN_FILTER, TEXTURE_WRAP_S, TEXTURE_WRAP_T, TRIANGLES,
+    UNSIGNED_BYTE,
+};
+use image::RgbaImage;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::raw::c_void;
+
+/// Data necessary to paint an image with OpenGL.
+#[derive(Clone, Debug)]
+pub struct ImagePaintData {
+    pub image: ImageKey,
+    pub vertices: Vec<f32>,
+}
+
+impl ImagePaintData {
+    pub fn new(image: ImageKey, vertices: Vec<f32>) -> Self {
+        ImagePaintData { image, vertices }
+    }
+}
+
+/// Uses given OpenGL instance to paint decoded images, such as those of `<img>` elements.
+pub struct ImagePainter {
+    /// An instance of OpenGL.
+    gl: Gl,
+    /// The OpenGL program that will be used to paint images.
+    program: Program,
+    /// The VAO to use to paint images.
+    vao: VertexArrayObject,
+    /// The textures the images painted so far were uploaded to.
+    textures: HashMap<ImageKey, Texture>,
+}
+
+impl ImagePainter {
+    pub fn new(gl: &Gl) -> Result<ImagePainter, String> {
+        let vbo = VertexBufferObject::new(gl);
+        let config_vao = |gl: &Gl| {
+            unsafe {
+                // Like text, images are painted as a 2D quad of 6 vertices, each of which is a
+                // position and a texture coordinate of 2 floats each.
+                gl.BufferData(
+                    ARRAY_BUFFER,
+                    (6 * 4 * std::mem::size_of::<f32>()) as GLsizeiptr,
+                    std::ptr::null(),
+                    DYNAMIC_DRAW,
+                );
+                gl.EnableVertexAttribArray(0);
+                gl.VertexAttribPointer(
+                    0,
+                    4,
+                    FLOAT,
+                    FALSE,
+                    (4 * std::mem::size_of::<f32>()) as GLint,
+                    std::ptr::null(),
+                );
+            }
+        };
+        let vao = unsafe { VertexArrayObject::new(vbo, config_vao, gl) };
+
+        Ok(ImagePainter {
+            program: build_image_program(gl)?,
+            vao,
+            textures: HashMap::new(),
+            gl: gl.clone(),
+        })
+    }
+
+    /// Paints `paintable_images`, whose pixels are those of the images of `display_list`.
+    pub fn paint(&mut self, paintable_images: &[ImagePaintData], display_list: &DisplayList) {
+        self.program.use_globally();
+        unsafe {
+            self.gl.ActiveTexture(TEXTURE0);
+            self.gl.BindVertexArray(self.vao.name());
+        }
+        for image in paintable_images {
+            // Panic rather than truncate data.
+            assert!(image.vertices.len() <= i32::MAX as usize);
+
+            let gl = &self.gl;
+            let texture_id = match self.textures.entry(image.image) {
+                Entry::Occupied(entry) => entry.get().id(),
+                Entry::Vacant(entry) => match display_list.image(image.image) {
+                    Some(pixels) => entry.insert(setup_texture(gl, pixels)).id(),
+                    None => continue,
+                },
+            };
+            unsafe {
+                self.gl.BindTexture(TEXTURE_2D, texture_id);
+                self.vao.store_vertex_data(&image.vertices);
+                // Casting the `usize` to `GLint` will not truncate due to the above assert!().
+                self.gl
+                    .DrawArrays(TRIANGLES, 0, image.vertices.len() as i32);
+            }
+        }
+
+        unsafe {
+            self.gl.BindVertexArray(0);
+            self.gl.BindTexture(TEXTURE_2D, 0);
+        }
+    }
+
+    /// Deletes the textures of the images that aren't in `display_list`, which are kept until then
+    /// so that images are only uploaded once for as long as they're painted.
+    pub fn retain_textures(&mut self, display_list: &DisplayList) {
+        self.textures
+            .retain(|key, _| display_list.image(*key).is_some());
+    }
+}
+
+/// Uploads the pixels of `image` to a new texture.
+fn setup_texture(gl: &Gl, image: &RgbaImage) -> Texture {
+    let texture = Texture::new(TextureKind::TwoDimensional, gl);
+    let (width, height) = image.dimensions();
+    unsafe {
+        gl.BindTexture(TEXTURE_2D, texture.id());
+        // Each pixel is four bytes, so rows are always aligned to the default of 4 bytes.
+        gl.TexImage2D(
+            TEXTURE_2D,
+            0,
+            RGBA as GLint,
+            width as GLint,
+            height as GLint,
+            0,
+            RGBA,
+            UNSIGNED_BYTE,
+            image.as_ptr() as *const c_void,
+        );
+        gl.TexParameteri(TEXTURE_2D, TEXTURE_WRAP_S, CLAMP_TO_EDGE as GLint);
+        gl.TexParameteri(TEXTURE_2D, TEXTURE_WRAP_T, CLAMP_TO_EDGE as GLint);
+        gl.TexParameteri(TEXTURE_2D, TEXTURE_MIN_FILTER, LINEAR as GLint);
+        gl.TexParameteri(TEXTURE_2D, TEXTURE_MAG_FILTER, LINEAR as GLint);
+    }
+    texture
+}
+
+fn build_image_program(gl: &Gl) -> Result<Program, String> {
+    let vertex_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/image.vert"
+    )))
+    .expect("could not create cstring for image program");
+
+    let frag_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/image.frag"
+    )))
+    .expect("could not create cstring for image program");
+
+    build_program(vertex_shader_src, frag_shader_src, gl)
+}
+
+/// Converts `rect` to the vertices of a quad textured with an image stretched over it, with the
+/// first row of the image's pixels at the top.
+pub fn image_vertices(rect: &Rect, viewport_width: f32, viewport_height: f32) -> Vec<f32> {
+    let left = ndc_x(rect.start_x.to_f32_px(), viewport_width);
+    let right = ndc_x(rect.end_x().to_f32_px(), viewport_width);
+    let top = ndc_y(rect.start_y.to_f32_px(), viewport_height);
+    let bottom = ndc_y(rect.end_y().to_f32_px(), viewport_height);
+
+    let mut vertices = Vec::new();
+    vertices.extend_from_slice(&[left, top, 0.0, 0.0]);
+    vertices.extend_from_slice(&[left, bottom, 0.0, 1.0]);
+    vertices.extend_from_slice(&[right, bottom, 1.0, 1.0]);
+
+    vertices.extend_from_slice(&[left, top, 0.0, 0.0]);
+    vertices.extend_from_slice(&[right, bottom, 1.0, 1.0]);
+    vertices.extend_from_slice(&[right, top, 1.0, 0.0]);
+    vertices
+}
@@ -0,0 +1,259 @@
+use crate::gfx::paint::build_program;
+use gl::program::Program;
+use gl::texture::{Texture, TextureKind};
+use gl::types::{GLint, GLsizeiptr, GLuint};
+use gl::vao::VertexArrayObject;
+use gl::vbo::VertexBufferObject;
+use gl::Gl;
+use std::ffi::CString;
+
+/// Paints the content of each group of things with an opacity to an offscreen layer of its own,
+/// and composites the layer with that opacity once the group is painted, so the opacity applies
+/// to the group as a whole rather than to each of its pieces.
+///
+/// Layers hold colors premultiplied by their alpha, which is what blending colors with the usual
+/// `SRC_ALPHA, ONE_MINUS_SRC_ALPHA` function into a transparent layer accumulates, as long as the
+/// alpha of the layer is blended with `ONE, ONE_MINUS_SRC_ALPHA` instead.
+///
+/// https://www.w3.org/TR/css-color-3/#transparency
+pub struct LayerPainter {
+    /// The OpenGL program that will be used to composite layers.
+    program: Program,
+    /// The location of the opacity uniform of `program`.
+    opacity: GLint,
+    /// The VAO to use to composite layers.
+    vao: VertexArrayObject,
+    /// The layers of the groups being painted, the innermost last.
+    layers: Vec<Layer>,
+    /// Layers that have been composited, which are painted to again by later groups rather than
+    /// allocating new ones each time.
+    idle_layers: Vec<Layer>,
+    /// An instance of OpenGL.
+    gl: Gl,
+}
+
+impl LayerPainter {
+    pub fn new(gl: &Gl) -> Result<LayerPainter, String> {
+        let vbo = VertexBufferObject::new(gl);
+        let config_vao = |gl: &Gl| {
+            unsafe {
+                // Like images, layers are composited as a 2D quad of 6 vertices, each of which is
+                // a position and a texture coordinate of 2 floats each.
+                gl.BufferData(
+                    gl::ARRAY_BUFFER,
+                    (6 * 4 * std::mem::size_of::<f32>()) as GLsizeiptr,
+                    std::ptr::null(),
+                    gl::DYNAMIC_DRAW,
+                );
+                gl.EnableVertexAttribArray(0);
+                gl.VertexAttribPointer(
+                    0,
+                    4,
+                    gl::FLOAT,
+                    gl::FALSE,
+                    (4 * std::mem::size_of::<f32>()) as GLint,
+                    std::ptr::null(),
+                );
+            }
+        };
+        let vao = unsafe { VertexArrayObject::new(vbo, config_vao, gl) };
+
+        let program = build_layer_program(gl)?;
+        let opacity_name = CString::new("Opacity").unwrap();
+        let opacity = unsafe { gl.GetUniformLocation(program.id(), opacity_name.as_ptr()) };
+
+        Ok(LayerPainter {
+            program,
+            opacity,
+            vao,
+            layers: Vec::new(),
+            idle_layers: Vec::new(),
+            gl: gl.clone(),
+        })
+    }
+
+    /// Whether a group is being painted to a layer.
+    pub fn is_painting_group(&self) -> bool {
+        !self.layers.is_empty()
+    }
+
+    /// Starts painting a group that's composited with `opacity` to a new, transparent layer the
+    /// size of the viewport.
+    ///
+    /// Rounded clips are drawn into the stencil buffer, so the stencil buffer of the layer starts
+    /// out as `rounded_clip_depth`, the depth of the rounded clips already in effect, which the
+    /// group is clipped to when it's composited.
+    pub fn push_group(
+        &mut self,
+        opacity: f32,
+        rounded_clip_depth: u32,
+        viewport_width: f32,
+        viewport_height: f32,
+    ) {
+        let (width, height) = (viewport_width as i32, viewport_height as i32);
+        self.idle_layers
+            .retain(|layer| layer.width == width && layer.height == height);
+        let mut layer = match self.idle_layers.pop() {
+            Some(layer) => layer,
+            None => Layer::new(width, height, &self.gl),
+        };
+        layer.opacity = opacity;
+        unsafe {
+            self.gl.BindFramebuffer(gl::FRAMEBUFFER, layer.framebuffer);
+            self.gl.ClearColor(0., 0., 0., 0.);
+            self.gl.ClearStencil(rounded_clip_depth as i32);
+            self.gl.Clear(gl::COLOR_BUFFER_BIT | gl::STENCIL_BUFFER_BIT);
+            self.gl.ClearStencil(0);
+            self.gl.BlendFuncSeparate(
+                gl::SRC_ALPHA,
+                gl::ONE_MINUS_SRC_ALPHA,
+                gl::ONE,
+                gl::ONE_MINUS_SRC_ALPHA,
+            );
+        }
+        self.layers.push(layer);
+    }
+
+    /// Finishes painting the innermost group, and composites its layer with its opacity into the
+    /// layer of the group around it, or the default framebuffer if there isn't one.  Everything
+    /// queued up in the group must already be painted.
+    pub fn pop_group(&mut self) {
+        let layer = match self.layers.pop() {
+            Some(layer) => layer,
+            None => return,
+        };
+        let outer_framebuffer = self.layers.last().map_or(0, |outer| outer.framebuffer);
+        // Layers are stored from their bottom row up, so the quad samples them upside down
+        // compared to images.
+        let vertices: [f32; 24] = [
+            -1., 1., 0., 1., //
+            -1., -1., 0., 0., //
+            1., -1., 1., 0., //
+            -1., 1., 0., 1., //
+            1., -1., 1., 0., //
+            1., 1., 1., 1., //
+        ];
+        self.program.use_globally();
+        self.vao.store_vertex_data(&vertices);
+        unsafe {
+            self.gl.BindFramebuffer(gl::FRAMEBUFFER, outer_framebuffer);
+            self.gl.BlendFunc(gl::ONE, gl::ONE_MINUS_SRC_ALPHA);
+            self.gl.ActiveTexture(gl::TEXTURE0);
+            self.gl.BindTexture(gl::TEXTURE_2D, layer.texture.id());
+            self.gl.Uniform1f(self.opacity, layer.opacity);
+            self.gl.BindVertexArray(self.vao.name());
+            self.gl.DrawArrays(gl::TRIANGLES, 0, 6);
+            self.gl.BindVertexArray(0);
+            self.gl.BindTexture(gl::TEXTURE_2D, 0);
+            if self.layers.is_empty() {
+                self.gl.BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            } else {
+                self.gl.BlendFuncSeparate(
+                    gl::SRC_ALPHA,
+                    gl::ONE_MINUS_SRC_ALPHA,
+                    gl::ONE,
+                    gl::ONE_MINUS_SRC_ALPHA,
+                );
+            }
+        }
+        self.idle_layers.push(layer);
+    }
+}
+
+/// An offscreen framebuffer a group is painted to, with a color texture it's composited from and
+/// a stencil buffer for the rounded clips within it.
+struct Layer {
+    framebuffer: GLuint,
+    texture: Texture,
+    /// The renderbuffer the stencil buffer of the layer is in.  OpenGL only guarantees stencil
+    /// renderbuffers to be supported when they're combined with a depth buffer.
+    depth_stencil: GLuint,
+    width: i32,
+    height: i32,
+    /// The opacity the layer is composited with.
+    opacity: f32,
+    /// An instance of OpenGL.
+    gl: Gl,
+}
+
+impl Layer {
+    fn new(width: i32, height: i32, gl: &Gl) -> Layer {
+        let texture = Texture::new(TextureKind::TwoDimensional, gl);
+        let mut framebuffer = 0;
+        let mut depth_stencil = 0;
+        unsafe {
+            gl.BindTexture(gl::TEXTURE_2D, texture.id());
+            gl.TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                gl::RGBA8 as GLint,
+                width,
+                height,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                std::ptr::null(),
+            );
+            // Layers are the size of the viewport, so each pixel is sampled as it is.
+            gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as GLint);
+            gl.TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as GLint);
+            gl.BindTexture(gl::TEXTURE_2D, 0);
+
+            gl.GenRenderbuffers(1, &mut depth_stencil);
+            gl.BindRenderbuffer(gl::RENDERBUFFER, depth_stencil);
+            gl.RenderbufferStorage(gl::RENDERBUFFER, gl::DEPTH24_STENCIL8, width, height);
+            gl.BindRenderbuffer(gl::RENDERBUFFER, 0);
+
+            gl.GenFramebuffers(1, &mut framebuffer);
+            gl.BindFramebuffer(gl::FRAMEBUFFER, framebuffer);
+            gl.FramebufferTexture2D(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::TEXTURE_2D,
+                texture.id(),
+                0,
+            );
+            gl.FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                gl::DEPTH_STENCIL_ATTACHMENT,
+                gl::RENDERBUFFER,
+                depth_stencil,
+            );
+        }
+        Layer {
+            framebuffer,
+            texture,
+            depth_stencil,
+            width,
+            height,
+            opacity: 1.,
+            gl: gl.clone(),
+        }
+    }
+}
+
+impl Drop for Layer {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteFramebuffers(1, &self.framebuffer);
+            self.gl.DeleteRenderbuffers(1, &self.depth_stencil);
+        }
+    }
+}
+
+fn build_layer_program(gl: &Gl) -> Result<Program, String> {
+    // Layers are composited with the same vertices as images are painted with.
+    let vertex_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/image.vert"
+    )))
+    .expect("could not create cstring for layer program");
+
+    let frag_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/layer.frag"
+    )))
+    .expect("could not create cstring for layer program");
+
+    build_program(vertex_shader_src, frag_shader_src, gl)
+}
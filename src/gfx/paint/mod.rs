@@ -1,107 +1,343 @@
-use crate::gfx::display::DisplayCommand;
+use crate::gfx::border::border_shapes;
+use crate::gfx::display::{DisplayCommand, DisplayList};
+use crate::gfx::font::FontHandle;
+use crate::gfx::paint::box_shadow::BoxShadowPainter;
+use crate::gfx::paint::glyph_atlas::{AtlasId, GlyphAtlases};
+use crate::gfx::paint::gradient::GradientPainter;
+use crate::gfx::paint::image::{image_vertices, ImagePaintData, ImagePainter};
+use crate::gfx::paint::layer::LayerPainter;
 use crate::gfx::paint::rect::RectPainter;
+use crate::gfx::paint::rounded_rect::RoundedRectPainter;
 use crate::gfx::paint::text::TextPainter;
+use crate::layout::rect::{Rect, RoundedRect};
 use crate::style::values::CSSFloat;
+use app_units::Au;
 use cssparser::RGBA;
 use gl::program::Program;
 use gl::shader::{Shader, ShaderKind};
-use gl::texture::TextureId;
 use gl::Gl;
-use glutin::{PossiblyCurrent, WindowedContext};
+use std::collections::HashMap;
 use std::ffi::CString;
 
+pub mod box_shadow;
+pub mod glyph_atlas;
+pub mod gradient;
+pub mod image;
+pub mod layer;
+pub mod pdf;
 pub mod rect;
+pub mod rounded_rect;
+pub mod svg;
 pub mod text;
 
+/// Paints display lists to some kind of output, such as an OpenGL context or a vector graphics
+/// document.  Backends are given nothing but the display list and what's needed to load the
+/// resources it refers to, and manage whatever they make of those resources themselves.
+pub trait PaintBackend {
+    /// Paints `display_list` to an output `viewport_width` by `viewport_height` device pixels in
+    /// size.  The fonts its glyphs refer to are loaded through `font_handle`.
+    fn paint(
+        &mut self,
+        display_list: &DisplayList,
+        viewport_width: CSSFloat,
+        viewport_height: CSSFloat,
+        font_handle: &FontHandle,
+    );
+}
+
+/// The kinds of primitives `MasterPainter` queues up to paint together, each of which is painted
+/// by a painter of its own.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Bucket {
+    Rect,
+    RoundedRect,
+    Image,
+    Text,
+}
+
 /// Wraps other painters to ensure they are only painting OpenGL vertex data (paint) that
 /// corresponds to their "bucket".  This is necessary because vertex data for a rectangle needs to
 /// be painted differently (namely, different OpenGL drawing sequences) than vertex data for text,
 /// as an example.
 pub struct MasterPainter {
+    /// The clips pushed by the display list being painted, each intersected with the ones before
+    /// it, so the last is the one in effect.
+    clip_stack: Vec<Clip>,
+    /// The number of rounded clips in `clip_stack`, which is the value of the stencil buffer
+    /// wherever all of them cover.
+    rounded_clip_depth: u32,
+    /// The bucket with primitives queued up in it, if any.  Only one bucket is filled at a time,
+    /// so that primitives are painted in the order they're in the display list.
+    queued_bucket: Option<Bucket>,
+    box_shadow_painter: BoxShadowPainter,
+    gradient_painter: GradientPainter,
+    image_painter: ImagePainter,
+    image_vertices: Vec<ImagePaintData>,
+    /// Paints the content within each `PushOpacity` and `PopOpacity` to a layer of its own.
+    layer_painter: LayerPainter,
     rect_painter: RectPainter,
     rect_vertices: Vec<f32>,
+    rounded_rect_painter: RoundedRectPainter,
+    rounded_rect_vertices: Vec<f32>,
     text_painter: TextPainter,
-    text_vertices: Vec<CharPaintData>,
+    /// The vertices of the glyphs to paint from each glyph atlas.
+    text_vertices: HashMap<AtlasId, Vec<f32>>,
+    /// The blur radius of the glyphs in `text_vertices`, which are painted before any glyphs with
+    /// a different one so that text shadows stay beneath the text casting them.
+    text_blur_radius: Au,
+    glyph_atlases: GlyphAtlases,
     /// The OpenGL instance to paint to.
     gl: Gl,
 }
 
-/// Data necessary to paint a character with OpenGL.
-#[derive(Clone, Debug)]
-pub struct CharPaintData {
-    // TODO: It would probably be cleaner to handle colors like RectPainter does vs. passing it here
-    pub color: RGBA,
-    pub texture_id: TextureId,
-    pub vertices: Vec<f32>,
-}
-
-impl CharPaintData {
-    pub fn new(color: RGBA, texture_id: TextureId, vertices: Vec<f32>) -> Self {
-        CharPaintData {
-            color,
-            texture_id,
-            vertices,
-        }
-    }
-}
-
 impl MasterPainter {
     pub fn new(gl: &Gl) -> Result<MasterPainter, String> {
         Ok(MasterPainter {
+            clip_stack: Vec::new(),
+            rounded_clip_depth: 0,
+            queued_bucket: None,
+            box_shadow_painter: BoxShadowPainter::new(gl)?,
+            gradient_painter: GradientPainter::new(gl)?,
+            image_painter: ImagePainter::new(gl)?,
+            image_vertices: Vec::new(),
+            layer_painter: LayerPainter::new(gl)?,
             rect_painter: RectPainter::new(gl)?,
             rect_vertices: Vec::new(),
+            rounded_rect_painter: RoundedRectPainter::new(gl)?,
+            rounded_rect_vertices: Vec::new(),
             text_painter: TextPainter::new(gl)?,
-            text_vertices: Vec::new(),
+            text_vertices: HashMap::new(),
+            text_blur_radius: Au(0),
+            glyph_atlases: GlyphAtlases::new(gl),
             gl: gl.clone(),
         })
     }
 
-    pub fn paint(
+    /// Paints everything in the paint buckets, and empties them.  `display_list` is the display
+    /// list being painted.
+    fn flush(&mut self, display_list: &DisplayList) {
+        self.rect_painter.paint(self.rect_vertices.as_slice());
+        self.rounded_rect_painter
+            .paint(self.rounded_rect_vertices.as_slice());
+        self.image_painter
+            .paint(self.image_vertices.as_slice(), display_list);
+        self.text_painter
+            .paint(&self.text_vertices, &self.glyph_atlases);
+        self.rect_vertices.clear();
+        self.rounded_rect_vertices.clear();
+        self.image_vertices.clear();
+        for vertices in self.text_vertices.values_mut() {
+            vertices.clear();
+        }
+        self.queued_bucket = None;
+    }
+
+    /// Makes the last clip in `clip_stack` the one in effect, using the OpenGL scissor test.
+    /// Everything queued up under the previous clip is painted first, since the scissor applies
+    /// at the time of drawing.
+    fn apply_clip(&mut self, display_list: &DisplayList, viewport_height: CSSFloat) {
+        self.flush(display_list);
+        match self.clip_stack.last().map(|clip| clip.rect) {
+            Some(clip) => unsafe {
+                // The scissor box is measured from the bottom-left corner of the viewport, rather
+                // than the top-left.
+                self.gl.Enable(gl::SCISSOR_TEST);
+                self.gl.Scissor(
+                    clip.start_x.to_nearest_px(),
+                    viewport_height as i32 - clip.end_y().to_nearest_px(),
+                    clip.width.to_nearest_px(),
+                    clip.height.to_nearest_px(),
+                );
+            },
+            None => unsafe { self.gl.Disable(gl::SCISSOR_TEST) },
+        }
+    }
+
+    /// Pushes a clip to the bounds `rect`, which is `rounded` if it's a rounded clip, onto the clip
+    /// stack, and makes it the one in effect.
+    fn push_clip(
         &mut self,
-        windowed_context: &WindowedContext<PossiblyCurrent>,
-        display_list: &[DisplayCommand],
+        rect: Rect,
+        rounded: Option<RoundedRect>,
+        display_list: &DisplayList,
+        viewport_height: CSSFloat,
     ) {
-        // Note: For semantic correctness, the OpenGL instance (the `gl` member on `self`) must
-        // also have its viewport specified to the below dimensions, presumably done outside this
-        // function when the window is resized.
-        // https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glViewport.xhtml
-        let viewport_width = windowed_context.window().inner_size().width;
-        let viewport_height = windowed_context.window().inner_size().height;
+        let rect = match self.clip_stack.last() {
+            Some(outer_clip) => outer_clip.rect.intersection(rect),
+            None => rect,
+        };
+        self.clip_stack.push(Clip { rect, rounded });
+        self.apply_clip(display_list, viewport_height);
+    }
 
-        for command in display_list {
-            self.process_display_command(
-                command,
-                viewport_width as CSSFloat,
-                viewport_height as CSSFloat,
-            );
+    /// Adds `clip` to the stencil buffer if `increment` is true, or removes it if not, and only
+    /// lets what's painted afterwards through where the stencil buffer is `rounded_clip_depth`.
+    ///
+    /// Each rounded clip increments the stencil buffer where it covers the clips before it, so
+    /// where the stencil buffer is the number of rounded clips in effect is where all of them
+    /// cover.  Popping a clip decrements the same pixels again.
+    fn draw_clip_to_stencil(
+        &mut self,
+        clip: &RoundedRect,
+        increment: bool,
+        viewport_width: CSSFloat,
+        viewport_height: CSSFloat,
+    ) {
+        let vertices =
+            (clip, &RGBA::new(0, 0, 0, 255)).to_vertices(viewport_width, viewport_height);
+        unsafe {
+            self.gl.Enable(gl::STENCIL_TEST);
+            self.gl
+                .ColorMask(gl::FALSE, gl::FALSE, gl::FALSE, gl::FALSE);
+            let (depth, operation) = if increment {
+                (self.rounded_clip_depth - 1, gl::INCR)
+            } else {
+                (self.rounded_clip_depth + 1, gl::DECR)
+            };
+            self.gl.StencilFunc(gl::EQUAL, depth as i32, 0xff);
+            self.gl.StencilOp(gl::KEEP, gl::KEEP, operation);
+        }
+        self.rounded_rect_painter.paint_mask(&vertices);
+        unsafe {
+            self.gl.ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE);
+            self.gl.StencilOp(gl::KEEP, gl::KEEP, gl::KEEP);
+            if self.rounded_clip_depth == 0 {
+                self.gl.Disable(gl::STENCIL_TEST);
+            } else {
+                self.gl
+                    .StencilFunc(gl::EQUAL, self.rounded_clip_depth as i32, 0xff);
+            }
         }
-        self.rect_painter.paint(self.rect_vertices.as_slice());
-        self.text_painter.paint(self.text_vertices.as_slice());
-        // Now that we've painted, let's dump the paint buckets so they're clean for the next paint.
-        self.rect_vertices.clear();
-        self.text_vertices.clear();
-        windowed_context
-            .swap_buffers()
-            .expect("couldn't swap window buffers");
+    }
+
+    /// Paints what's queued up if it's in a bucket other than `bucket`, which is about to be
+    /// added to, so that primitives of different kinds are painted in the order they're in the
+    /// display list.
+    fn flush_if_switching(&mut self, display_list: &DisplayList, bucket: Bucket) {
+        if matches!(self.queued_bucket, Some(queued) if queued != bucket) {
+            self.flush(display_list);
+        }
+        self.queued_bucket = Some(bucket);
     }
 
     fn process_display_command(
         &mut self,
         command: &DisplayCommand,
+        display_list: &DisplayList,
         viewport_width: CSSFloat,
         viewport_height: CSSFloat,
+        font_handle: &FontHandle,
     ) {
         match command {
-            DisplayCommand::Char(char_command) => {
-                self.text_vertices.push(CharPaintData::new(
-                    char_command.color(),
-                    char_command.texture_id(),
-                    char_command.to_vertices(viewport_width, viewport_height),
+            DisplayCommand::Border(border) => {
+                self.flush_if_switching(display_list, Bucket::Rect);
+                for shape in border_shapes(border) {
+                    let rgba = shape.color();
+                    for polygon in shape.convex_polygons() {
+                        self.rect_vertices.extend(
+                            (polygon.as_slice(), &rgba)
+                                .to_vertices(viewport_width, viewport_height),
+                        );
+                    }
+                }
+            }
+            DisplayCommand::BoxShadow(shadow) => {
+                // Shadows aren't bucketed, so everything before them is painted first.
+                self.flush(display_list);
+                self.box_shadow_painter
+                    .paint(shadow, viewport_width, viewport_height);
+            }
+            DisplayCommand::Glyph(glyph) => {
+                if glyph.blur_radius() != self.text_blur_radius {
+                    self.flush(display_list);
+                    self.text_blur_radius = glyph.blur_radius();
+                }
+                self.flush_if_switching(display_list, Bucket::Text);
+                let font = font_handle.font(glyph.font());
+                match self.glyph_atlases.get_quad(glyph, &font) {
+                    Ok(Some(quad)) => {
+                        self.text_vertices.entry(quad.atlas).or_default().extend(
+                            (&quad, &glyph.color()).to_vertices(viewport_width, viewport_height),
+                        );
+                    }
+                    Ok(None) => {}
+                    Err(err) => eprintln!("couldn't paint glyph {}: {:?}", glyph.glyph_id(), err),
+                }
+            }
+            DisplayCommand::Image(key, rect) => {
+                self.flush_if_switching(display_list, Bucket::Image);
+                self.image_vertices.push(ImagePaintData::new(
+                    *key,
+                    image_vertices(rect, viewport_width, viewport_height),
                 ));
             }
-            DisplayCommand::RectSolidColor(rgba, rect) => self
-                .rect_vertices
-                .extend((rect, rgba).to_vertices(viewport_width, viewport_height)),
+            DisplayCommand::ImageTiles(key, tiles) => {
+                self.flush_if_switching(display_list, Bucket::Image);
+                for rect in tiles.rects() {
+                    self.image_vertices.push(ImagePaintData::new(
+                        *key,
+                        image_vertices(&rect, viewport_width, viewport_height),
+                    ));
+                }
+            }
+            DisplayCommand::GradientTiles(gradient, tiles) => {
+                // Gradients aren't bucketed, so everything before them is painted first.
+                self.flush(display_list);
+                self.gradient_painter.paint(
+                    gradient,
+                    &tiles.rects(),
+                    viewport_width,
+                    viewport_height,
+                );
+            }
+            DisplayCommand::PushClip(rect) => {
+                self.push_clip(*rect, None, display_list, viewport_height);
+            }
+            DisplayCommand::PushRoundedClip(rrect) => {
+                // The scissor test clips to the bounds of the rounded clip, and the stencil test
+                // to its corners.
+                self.push_clip(rrect.rect, Some(*rrect), display_list, viewport_height);
+                self.rounded_clip_depth += 1;
+                self.draw_clip_to_stencil(rrect, true, viewport_width, viewport_height);
+            }
+            DisplayCommand::PopClip => {
+                if let Some(Clip {
+                    rounded: Some(rrect),
+                    ..
+                }) = self.clip_stack.last().copied()
+                {
+                    self.flush(display_list);
+                    self.rounded_clip_depth -= 1;
+                    self.draw_clip_to_stencil(&rrect, false, viewport_width, viewport_height);
+                }
+                self.clip_stack.pop();
+                self.apply_clip(display_list, viewport_height);
+            }
+            DisplayCommand::PushOpacity(opacity) => {
+                // The group is painted to a layer of its own, which is composited with the
+                // opacity once it's painted, so everything before it is painted first.
+                self.flush(display_list);
+                self.layer_painter.push_group(
+                    *opacity,
+                    self.rounded_clip_depth,
+                    viewport_width,
+                    viewport_height,
+                );
+            }
+            DisplayCommand::PopOpacity => {
+                self.flush(display_list);
+                self.layer_painter.pop_group();
+            }
+            DisplayCommand::RectSolidColor(rgba, rect) => {
+                self.flush_if_switching(display_list, Bucket::Rect);
+                self.rect_vertices
+                    .extend((rect, rgba).to_vertices(viewport_width, viewport_height))
+            }
+            DisplayCommand::RoundedRectSolidColor(rgba, rrect) => {
+                self.flush_if_switching(display_list, Bucket::RoundedRect);
+                self.rounded_rect_vertices
+                    .extend((rrect, rgba).to_vertices(viewport_width, viewport_height))
+            }
             DisplayCommand::ViewportBackground(rgba) => unsafe {
                 self.gl.ClearColor(
                     rgba.red_f32(),
@@ -115,6 +351,61 @@ impl MasterPainter {
     }
 }
 
+impl PaintBackend for MasterPainter {
+    fn paint(
+        &mut self,
+        display_list: &DisplayList,
+        viewport_width: CSSFloat,
+        viewport_height: CSSFloat,
+        font_handle: &FontHandle,
+    ) {
+        // Note: For semantic correctness, the OpenGL instance (the `gl` member on `self`) must
+        // also have its viewport specified to the given dimensions, presumably done outside this
+        // function when the window is resized.
+        // https://www.khronos.org/registry/OpenGL-Refpages/gl4/html/glViewport.xhtml
+        self.glyph_atlases.next_frame();
+        // Rounded clips are drawn into the stencil buffer, which has to start out empty.
+        unsafe {
+            self.gl.ClearStencil(0);
+            self.gl.Clear(gl::STENCIL_BUFFER_BIT);
+        }
+        for command in display_list.commands() {
+            self.process_display_command(
+                command,
+                display_list,
+                viewport_width,
+                viewport_height,
+                font_handle,
+            );
+        }
+        self.flush(display_list);
+        // A well-formed display list pops every opacity it pushes, but composite the group of any
+        // that isn't before the clips around it are popped.
+        while self.layer_painter.is_painting_group() {
+            self.layer_painter.pop_group();
+        }
+        // A well-formed display list pops every clip it pushes, but don't let a clip leak into
+        // the next paint if it doesn't.
+        if !self.clip_stack.is_empty() {
+            self.clip_stack.clear();
+            self.rounded_clip_depth = 0;
+            self.apply_clip(display_list, viewport_height);
+            unsafe { self.gl.Disable(gl::STENCIL_TEST) };
+        }
+        self.image_painter.retain_textures(display_list);
+    }
+}
+
+/// A clip pushed by the display list being painted.
+#[derive(Clone, Copy, Debug)]
+struct Clip {
+    /// The bounds of the clip, intersected with those of the clips before it, which the scissor
+    /// test clips to.
+    rect: Rect,
+    /// The clip itself if it's rounded, which the stencil test clips to its corners.
+    rounded: Option<RoundedRect>,
+}
+
 /// Represents the conversion from some entity to OpenGL vertex data.
 pub trait ToVertices {
     fn to_vertices(&self, viewport_width: CSSFloat, viewport_height: CSSFloat) -> Vec<f32>;
@@ -140,6 +431,6 @@ pub fn build_program(
 ) -> Result<Program, String> {
     let vertex_shader = Shader::from_source(vertex_shader_src, ShaderKind::Vertex, gl)?;
     let fragment_shader = Shader::from_source(fragment_shader_src, ShaderKind::Fragment, gl)?;
-    let program = Program::from_shaders(&[vertex_shader, fragment_shader], &gl)?;
+    let program = Program::from_shaders(&[vertex_shader, fragment_shader], gl)?;
     Ok(program)
 }
@@ -0,0 +1,867 @@
+use crate::dom::image::ImageKey;
+use crate::gfx::border::{border_shapes, BorderShape};
+use crate::gfx::display::{DisplayCommand, DisplayList, GlyphCommand};
+use crate::gfx::font::{rasterize_glyph, FontHandle, FontKey};
+use crate::gfx::paint::PaintBackend;
+use crate::layout::rect::{CornerRadius, Rect, RoundedRect};
+use crate::style::values::CSSFloat;
+use app_units::Au;
+use cssparser::RGBA;
+use font_kit::metrics::Metrics;
+use image::{Rgba, RgbaImage};
+use pathfinder_geometry::vector::Vector2F;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// The number of PDF points per CSS px.  PDF user space is 72 points per inch, and CSS is 96 px
+/// per inch.
+const PT_PER_PX: CSSFloat = 0.75;
+
+/// Paints display lists as the pages of a PDF document, one page per call to `paint`, for
+/// printing.  Rectangles, borders, and clips are painted as paths, glyphs as text in their embedded fonts,
+/// and images as image XObjects with their alpha as a soft mask.  What's painted with an opacity
+/// is painted as a transparency group, so the opacity applies to it as a whole.  Gradients, box
+/// shadows, and blurred glyphs are rasterized into images.
+///
+/// TODO: Compress streams, and subset fonts to the glyphs that are used.  Whole fonts are embedded
+/// for now.
+///
+/// https://www.adobe.com/content/dam/acom/en/devnet/pdf/pdfs/PDF32000_2008.pdf
+#[derive(Debug, Default)]
+pub struct PdfPainter {
+    pages: Vec<PdfPage>,
+    /// The fonts glyphs have been painted in so far, with the resource name of each being `F` and
+    /// its index.
+    fonts: Vec<PdfFont>,
+    font_indices: HashMap<FontKey, usize>,
+    /// The images painted so far, with the resource name of each being `Im` and its index.
+    images: Vec<Rc<RgbaImage>>,
+    image_indices: HashMap<ImageKey, usize>,
+    /// The alphas painted with so far, in thousandths, each of which has a graphics state named
+    /// `GS` and its index.
+    alphas: Vec<u16>,
+    /// The transparency groups painted so far, each of which is a form XObject with the resource
+    /// name `Fm` and its index.
+    forms: Vec<PdfForm>,
+    /// For each `PushOpacity` of the page being painted, the content of the page or group around
+    /// it, which its group is painted into once it's popped, and its opacity.
+    group_stack: Vec<(String, CSSFloat)>,
+}
+
+/// The content of a `PushOpacity` and `PopOpacity`, which is composited as a whole.
+#[derive(Debug)]
+struct PdfForm {
+    width: CSSFloat,
+    height: CSSFloat,
+    content: String,
+}
+
+#[derive(Debug)]
+struct PdfPage {
+    width: CSSFloat,
+    height: CSSFloat,
+    content: String,
+}
+
+#[derive(Debug)]
+struct PdfFont {
+    postscript_name: Option<String>,
+    /// The font file, or `None` if it couldn't be read, in which case glyphs are painted in the
+    /// standard Helvetica font instead.
+    data: Option<Arc<Vec<u8>>>,
+    metrics: Metrics,
+    /// The char each glyph used was chosen for, and its advance in thousandths of an em, by ID.
+    glyphs: BTreeMap<u32, (char, CSSFloat)>,
+}
+
+impl PdfPainter {
+    pub fn new() -> PdfPainter {
+        PdfPainter::default()
+    }
+
+    /// Writes the pages painted so far as a PDF document.
+    pub fn finish(&self) -> Vec<u8> {
+        let mut objects = PdfObjects::default();
+        let catalog_id = objects.reserve();
+        let pages_id = objects.reserve();
+        let resources_id = objects.reserve();
+
+        let mut resources = String::from("<< /ProcSet [/PDF /Text /ImageC]");
+        resources.push_str(" /Font <<");
+        for (index, font) in self.fonts.iter().enumerate() {
+            let font_id = font.write(&mut objects);
+            write!(resources, " /F{} {} 0 R", index, font_id).unwrap();
+        }
+        resources.push_str(" >> /XObject <<");
+        for (index, image) in self.images.iter().enumerate() {
+            let image_id = write_image(image, &mut objects);
+            write!(resources, " /Im{} {} 0 R", index, image_id).unwrap();
+        }
+        for (index, form) in self.forms.iter().enumerate() {
+            // Groups share the resources of the pages, and are in the coordinates of the page
+            // they're painted on.
+            let form_id = objects.add(stream(
+                &format!(
+                    "/Type /XObject /Subtype /Form /BBox [0 0 {} {}] /Group << /S /Transparency >> /Resources {} 0 R",
+                    number(form.width),
+                    number(form.height),
+                    resources_id
+                ),
+                form.content.as_bytes(),
+            ));
+            write!(resources, " /Fm{} {} 0 R", index, form_id).unwrap();
+        }
+        resources.push_str(" >> /ExtGState <<");
+        for (index, alpha) in self.alphas.iter().enumerate() {
+            let alpha = number(*alpha as CSSFloat / 1000.);
+            write!(
+                resources,
+                " /GS{} << /Type /ExtGState /ca {} /CA {} >>",
+                index, alpha, alpha
+            )
+            .unwrap();
+        }
+        resources.push_str(" >> >>");
+        objects.set(resources_id, resources.into_bytes());
+
+        let mut kids = Vec::new();
+        for page in &self.pages {
+            let content_id = objects.add(stream("", page.content.as_bytes()));
+            kids.push(objects.add(
+                format!(
+                    "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources {} 0 R /Contents {} 0 R >>",
+                    pages_id,
+                    number(page.width * PT_PER_PX),
+                    number(page.height * PT_PER_PX),
+                    resources_id,
+                    content_id
+                )
+                .into_bytes(),
+            ));
+        }
+        let kids = kids
+            .iter()
+            .map(|id| format!("{} 0 R", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        objects.set(
+            pages_id,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids,
+                self.pages.len()
+            )
+            .into_bytes(),
+        );
+        objects.set(
+            catalog_id,
+            format!("<< /Type /Catalog /Pages {} 0 R >>", pages_id).into_bytes(),
+        );
+        objects.write(catalog_id)
+    }
+
+    fn process_display_command(
+        &mut self,
+        command: &DisplayCommand,
+        display_list: &DisplayList,
+        font_handle: &FontHandle,
+        page: &mut PdfPage,
+    ) {
+        let content = &mut page.content;
+        match command {
+            // Blurred glyphs, which cast text shadows, are rasterized into images, since PDF has
+            // no way to blur text.
+            DisplayCommand::Glyph(glyph) if glyph.blur_radius() > Au(0) => {
+                self.draw_blurred_glyph(content, glyph, font_handle);
+            }
+            DisplayCommand::Glyph(glyph) => {
+                if let Some(shown) = self.show_glyph(glyph, font_handle) {
+                    let operators = format!(
+                        "BT {} 1 0 0 -1 {} {} Tm {} Tj ET",
+                        shown,
+                        number(glyph.origin().x()),
+                        number(glyph.origin().y()),
+                        glyph_string(glyph, &self.fonts[self.font_indices[&glyph.font()]]),
+                    );
+                    self.fill(content, glyph.color(), &operators);
+                }
+            }
+            DisplayCommand::Image(key, rect) => {
+                if let Some(index) = self.image_index(*key, display_list) {
+                    self.draw_image(content, index, rect);
+                }
+            }
+            DisplayCommand::ImageTiles(key, tiles) => {
+                if let Some(index) = self.image_index(*key, display_list) {
+                    for rect in tiles.rects() {
+                        self.draw_image(content, index, &rect);
+                    }
+                }
+            }
+            // TODO: Paint gradients as shadings, rather than images of one pixel per device
+            // pixel, so they stay smooth however far they're zoomed into.
+            DisplayCommand::GradientTiles(gradient, tiles) => {
+                let (width, height) = (
+                    tiles.tile.width.to_nearest_px(),
+                    tiles.tile.height.to_nearest_px(),
+                );
+                if width <= 0 || height <= 0 {
+                    return;
+                }
+                self.images
+                    .push(Rc::new(gradient.rasterize(width as u32, height as u32)));
+                let index = self.images.len() - 1;
+                for rect in tiles.rects() {
+                    self.draw_image(content, index, &rect);
+                }
+            }
+            DisplayCommand::BoxShadow(shadow) => {
+                self.images.push(Rc::new(shadow.rasterize()));
+                let index = self.images.len() - 1;
+                self.draw_image(content, index, &shadow.bounds());
+            }
+            DisplayCommand::PushClip(rect) => {
+                writeln!(content, "q {} W n", rect_path(rect)).unwrap();
+            }
+            DisplayCommand::PushRoundedClip(rrect) => {
+                writeln!(content, "q {} W n", rounded_rect_path(rrect)).unwrap();
+            }
+            DisplayCommand::PopClip => content.push_str("Q\n"),
+            DisplayCommand::PushOpacity(opacity) => {
+                let outer_content = std::mem::take(content);
+                self.group_stack.push((outer_content, *opacity));
+            }
+            DisplayCommand::PopOpacity => self.pop_group(content, page.width, page.height),
+            DisplayCommand::Border(border) => {
+                for shape in border_shapes(border) {
+                    self.fill(content, shape.color(), &format!("{} f", shape_path(&shape)));
+                }
+            }
+            DisplayCommand::RectSolidColor(rgba, rect) => {
+                self.fill(content, *rgba, &format!("{} f", rect_path(rect)));
+            }
+            DisplayCommand::RoundedRectSolidColor(rgba, rrect) => {
+                self.fill(content, *rgba, &format!("{} f", rounded_rect_path(rrect)));
+            }
+            DisplayCommand::ViewportBackground(rgba) => {
+                let operators = format!("0 0 {} {} re f", number(page.width), number(page.height));
+                self.fill(content, *rgba, &operators);
+            }
+        }
+    }
+
+    /// Gets the index of the image `key` refers to in `images`, adding it if it isn't there yet.
+    fn image_index(&mut self, key: ImageKey, display_list: &DisplayList) -> Option<usize> {
+        let image = display_list.image(key)?;
+        let images = &mut self.images;
+        Some(*self.image_indices.entry(key).or_insert_with(|| {
+            images.push(image.clone());
+            images.len() - 1
+        }))
+    }
+
+    /// Writes the operators painting the image at `index` in `images` stretched over `rect` to
+    /// `content`.
+    fn draw_image(&self, content: &mut String, index: usize, rect: &Rect) {
+        // Images are painted into the unit square, with their first row at the top, so it's
+        // stretched over `rect` upside down to undo the flip of the page.
+        let operators = format!(
+            "{} 0 0 {} {} {} cm /Im{} Do",
+            number(rect.width.to_f32_px()),
+            number(-rect.height.to_f32_px()),
+            number(rect.start_x.to_f32_px()),
+            number(rect.end_y().to_f32_px()),
+            index
+        );
+        writeln!(content, "q {} Q", operators).unwrap();
+    }
+
+    /// Ends the group of the innermost `PushOpacity`, whose content is `content`, and writes the
+    /// operators painting it as a transparency group with its opacity to the content around it,
+    /// which `content` is replaced with.  The page is `width` by `height` px.
+    fn pop_group(&mut self, content: &mut String, width: CSSFloat, height: CSSFloat) {
+        let (outer_content, opacity) = match self.group_stack.pop() {
+            Some(group) => group,
+            None => return,
+        };
+        let group_content = std::mem::replace(content, outer_content);
+        if group_content.is_empty() {
+            return;
+        }
+        let state = match self.graphics_state(opacity) {
+            Some(state) => state,
+            None => return,
+        };
+        self.forms.push(PdfForm {
+            width,
+            height,
+            content: group_content,
+        });
+        let operators = format!("/Fm{} Do", self.forms.len() - 1);
+        match state {
+            Some(state) => writeln!(content, "q /GS{} gs {} Q", state, operators).unwrap(),
+            None => writeln!(content, "q {} Q", operators).unwrap(),
+        }
+    }
+
+    /// Rasterizes `glyph` blurred into an image in its color, and writes the operators painting
+    /// it to `content`.
+    fn draw_blurred_glyph(
+        &mut self,
+        content: &mut String,
+        glyph: &GlyphCommand,
+        font_handle: &FontHandle,
+    ) {
+        let origin = glyph.origin();
+        let whole_px = Vector2F::new(origin.x().floor(), origin.y().floor());
+        let rasterized = rasterize_glyph(
+            &font_handle.font(glyph.font()),
+            glyph.glyph_id(),
+            glyph.size().to_f32_px(),
+            origin - whole_px,
+            glyph.blur_radius().to_f32_px() / 2.,
+            0,
+        );
+        let (canvas, bearing) = match rasterized {
+            Ok(Some(rasterized)) => rasterized,
+            Ok(None) => return,
+            Err(err) => {
+                eprintln!("couldn't paint glyph {}: {:?}", glyph.glyph_id(), err);
+                return;
+            }
+        };
+        let color = glyph.color();
+        let (width, height) = (canvas.size.x() as u32, canvas.size.y() as u32);
+        let image = RgbaImage::from_fn(width, height, |x, y| {
+            let coverage = canvas.pixels[y as usize * canvas.stride + x as usize] as f32 / 255.;
+            Rgba([
+                color.red,
+                color.green,
+                color.blue,
+                (color.alpha as f32 * coverage).round() as u8,
+            ])
+        });
+        self.images.push(Rc::new(image));
+        let index = self.images.len() - 1;
+        let top_left = whole_px + bearing.to_f32();
+        let rect = Rect {
+            start_x: Au::from_f32_px(top_left.x()),
+            start_y: Au::from_f32_px(top_left.y()),
+            width: Au::from_px(width as i32),
+            height: Au::from_px(height as i32),
+        };
+        self.draw_image(content, index, &rect);
+    }
+
+    /// Writes `operators` to `content`, filling in `rgba`.
+    fn fill(&mut self, content: &mut String, rgba: RGBA, operators: &str) {
+        let alpha = rgba.alpha_f32();
+        let color = format!(
+            "{} {} {} rg",
+            number(rgba.red_f32()),
+            number(rgba.green_f32()),
+            number(rgba.blue_f32())
+        );
+        match self.graphics_state(alpha) {
+            Some(Some(state)) => {
+                writeln!(content, "q /GS{} gs {} {} Q", state, color, operators).unwrap()
+            }
+            Some(None) => writeln!(content, "{} {}", color, operators).unwrap(),
+            None => {}
+        }
+    }
+
+    /// Gets the index of the graphics state that paints with `alpha`, which is `Some(None)` if
+    /// painting is opaque, and `None` if it's fully transparent, in which case there's no need to
+    /// paint at all.
+    fn graphics_state(&mut self, alpha: CSSFloat) -> Option<Option<usize>> {
+        let thousandths = (alpha * 1000.).round();
+        if thousandths <= 0. {
+            return None;
+        }
+        if thousandths >= 1000. {
+            return Some(None);
+        }
+        let thousandths = thousandths as u16;
+        Some(Some(
+            match self.alphas.iter().position(|a| *a == thousandths) {
+                Some(index) => index,
+                None => {
+                    self.alphas.push(thousandths);
+                    self.alphas.len() - 1
+                }
+            },
+        ))
+    }
+
+    /// Records that `glyph` is painted in its font, and gets the operator to select that font at
+    /// the glyph's size.  Returns `None` if the glyph can't be shown in the font it's painted in.
+    fn show_glyph(&mut self, glyph: &GlyphCommand, font_handle: &FontHandle) -> Option<String> {
+        let fonts = &mut self.fonts;
+        let index = *self.font_indices.entry(glyph.font()).or_insert_with(|| {
+            let font = font_handle.font(glyph.font());
+            fonts.push(PdfFont {
+                postscript_name: font.postscript_name(),
+                data: font.copy_font_data(),
+                metrics: font.metrics(),
+                glyphs: BTreeMap::new(),
+            });
+            fonts.len() - 1
+        });
+        let pdf_font = &mut self.fonts[index];
+        if pdf_font.data.is_none() && win_ansi_code(glyph.ch()).is_none() {
+            return None;
+        }
+        let advance = font_handle
+            .font(glyph.font())
+            .advance(glyph.glyph_id())
+            .map(|advance| advance.x() * 1000. / pdf_font.metrics.units_per_em as CSSFloat)
+            .unwrap_or(0.);
+        pdf_font
+            .glyphs
+            .entry(glyph.glyph_id())
+            .or_insert((glyph.ch(), advance));
+        Some(format!(
+            "/F{} {} Tf",
+            index,
+            number(glyph.size().to_f32_px())
+        ))
+    }
+}
+
+impl PaintBackend for PdfPainter {
+    fn paint(
+        &mut self,
+        display_list: &DisplayList,
+        viewport_width: CSSFloat,
+        viewport_height: CSSFloat,
+        font_handle: &FontHandle,
+    ) {
+        let mut page = PdfPage {
+            width: viewport_width,
+            height: viewport_height,
+            content: String::new(),
+        };
+        // PDF user space is in points, with its origin at the bottom-left of the page and y
+        // growing upwards, so it's scaled to px and flipped to match the display list.
+        writeln!(
+            page.content,
+            "{} 0 0 {} 0 {} cm",
+            number(PT_PER_PX),
+            number(-PT_PER_PX),
+            number(viewport_height * PT_PER_PX)
+        )
+        .unwrap();
+        for command in display_list.commands() {
+            self.process_display_command(command, display_list, font_handle, &mut page);
+        }
+        // A well-formed display list pops every opacity it pushes, but paint the group of any that
+        // isn't.
+        while !self.group_stack.is_empty() {
+            self.pop_group(&mut page.content, viewport_width, viewport_height);
+        }
+        self.pages.push(page);
+    }
+}
+
+impl PdfFont {
+    /// Writes this font and the objects it refers to, returning the ID of the font dictionary.
+    ///
+    /// Fonts are embedded as CID-keyed fonts with the `Identity-H` encoding, so that the strings
+    /// shown in them are glyph IDs, and with a `ToUnicode` map back to the chars they were chosen
+    /// for, so that text can be copied out of the document.
+    fn write(&self, objects: &mut PdfObjects) -> usize {
+        let data = match &self.data {
+            Some(data) => data,
+            None => {
+                return objects.add(
+                    b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                        .to_vec(),
+                );
+            }
+        };
+        let name = name(self.postscript_name.as_deref().unwrap_or("Kosmonaut"));
+        let scale = 1000. / self.metrics.units_per_em as CSSFloat;
+        // Fonts with CFF outlines are OpenType files starting with this tag, and are embedded as
+        // such.  Everything else is assumed to be TrueType.
+        let (cid_subtype, font_file) = if data.starts_with(b"OTTO") {
+            let file_id = objects.add(stream("/Subtype /OpenType", data));
+            ("CIDFontType0", format!("/FontFile3 {} 0 R", file_id))
+        } else {
+            let file_id = objects.add(stream(&format!("/Length1 {}", data.len()), data));
+            ("CIDFontType2", format!("/FontFile2 {} 0 R", file_id))
+        };
+        let bounds = self.metrics.bounding_box;
+        let descriptor_id = objects.add(
+            format!(
+                "<< /Type /FontDescriptor /FontName /{} /Flags 32 /FontBBox [{} {} {} {}] /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 {} >>",
+                name,
+                number(bounds.min_x() * scale),
+                number(bounds.min_y() * scale),
+                number(bounds.max_x() * scale),
+                number(bounds.max_y() * scale),
+                number(self.metrics.ascent * scale),
+                number(self.metrics.descent * scale),
+                number(self.metrics.cap_height * scale),
+                font_file
+            )
+            .into_bytes(),
+        );
+        let widths = self
+            .glyphs
+            .iter()
+            .map(|(id, (_, advance))| format!("{} [{}]", id, number(*advance)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let cid_font_id = objects.add(
+            format!(
+                "<< /Type /Font /Subtype /{} /BaseFont /{} /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> /FontDescriptor {} 0 R /CIDToGIDMap /Identity /W [{}] >>",
+                cid_subtype, name, descriptor_id, widths
+            )
+            .into_bytes(),
+        );
+        let to_unicode_id = objects.add(stream("", self.to_unicode_cmap().as_bytes()));
+        objects.add(
+            format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H /DescendantFonts [{} 0 R] /ToUnicode {} 0 R >>",
+                name, cid_font_id, to_unicode_id
+            )
+            .into_bytes(),
+        )
+    }
+
+    /// https://www.adobe.com/content/dam/acom/en/devnet/acrobat/pdfs/5411.ToUnicode.pdf
+    fn to_unicode_cmap(&self) -> String {
+        let mut cmap = String::from(
+            "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n/CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+        );
+        let glyphs = self.glyphs.iter().collect::<Vec<_>>();
+        // There can be at most 100 mappings in each block.
+        for chunk in glyphs.chunks(100) {
+            writeln!(cmap, "{} beginbfchar", chunk.len()).unwrap();
+            for (id, (ch, _)) in chunk {
+                let utf16 = ch
+                    .encode_utf16(&mut [0; 2])
+                    .iter()
+                    .map(|unit| format!("{:04X}", unit))
+                    .collect::<String>();
+                writeln!(cmap, "<{:04X}> <{}>", id, utf16).unwrap();
+            }
+            cmap.push_str("endbfchar\n");
+        }
+        cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+        cmap
+    }
+}
+
+/// The string to show `glyph` with in `font`, which is its glyph ID if the font is embedded, and
+/// otherwise its char.
+fn glyph_string(glyph: &GlyphCommand, font: &PdfFont) -> String {
+    match (&font.data, win_ansi_code(glyph.ch())) {
+        (Some(_), _) => format!("<{:04X}>", glyph.glyph_id()),
+        (None, Some(code)) => format!("<{:02X}>", code),
+        (None, None) => String::new(),
+    }
+}
+
+/// The code of `ch` in the `WinAnsiEncoding` of the standard fonts, for the printable chars it
+/// shares with Latin-1.
+fn win_ansi_code(ch: char) -> Option<u8> {
+    match ch as u32 {
+        code @ 0x20..=0x7e | code @ 0xa0..=0xff => Some(code as u8),
+        _ => None,
+    }
+}
+
+/// Writes `image` as an image XObject, with its alpha channel as a soft mask, returning the ID of
+/// the image.
+fn write_image(image: &RgbaImage, objects: &mut PdfObjects) -> usize {
+    let (width, height) = image.dimensions();
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    let mut alpha = Vec::with_capacity((width * height) as usize);
+    for pixel in image.pixels() {
+        rgb.extend_from_slice(&pixel.0[..3]);
+        alpha.push(pixel.0[3]);
+    }
+    let mask_id = objects.add(stream(
+        &format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8",
+            width, height
+        ),
+        &alpha,
+    ));
+    objects.add(stream(
+        &format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /SMask {} 0 R",
+            width, height, mask_id
+        ),
+        &rgb,
+    ))
+}
+
+fn rect_path(rect: &Rect) -> String {
+    format!(
+        "{} {} {} {} re",
+        number(rect.start_x.to_f32_px()),
+        number(rect.start_y.to_f32_px()),
+        number(rect.width.to_f32_px()),
+        number(rect.height.to_f32_px())
+    )
+}
+
+/// The path of `rrect`, with each rounded corner drawn as a Bézier curve approximating a quarter of
+/// an ellipse, as the dots of borders are.
+fn rounded_rect_path(rrect: &RoundedRect) -> String {
+    let px = |au: Au| au.to_f32_px();
+    let (left, top, right, bottom) = (
+        px(rrect.rect.start_x),
+        px(rrect.rect.start_y),
+        px(rrect.rect.end_x()),
+        px(rrect.rect.end_y()),
+    );
+    let radii = rrect.radii;
+    let point = |p: Vector2F| format!("{} {}", number(p.x()), number(p.y()));
+    // Each corner curves from where one side stops to where the next starts, with control points
+    // towards the square corner.
+    let corner = |radius: CornerRadius, from: Vector2F, corner: Vector2F, to: Vector2F| {
+        if radius.is_zero() {
+            return format!("{} l", point(corner));
+        }
+        format!(
+            "{} l {} {} {} c",
+            point(from),
+            point(from.lerp(corner, KAPPA)),
+            point(to.lerp(corner, KAPPA)),
+            point(to)
+        )
+    };
+    let (h, v) = (
+        |radius: CornerRadius| px(radius.horizontal),
+        |radius: CornerRadius| px(radius.vertical),
+    );
+    format!(
+        "{} m {} {} {} {} h",
+        point(Vector2F::new(left + h(radii.top_left), top)),
+        corner(
+            radii.top_right,
+            Vector2F::new(right - h(radii.top_right), top),
+            Vector2F::new(right, top),
+            Vector2F::new(right, top + v(radii.top_right)),
+        ),
+        corner(
+            radii.bottom_right,
+            Vector2F::new(right, bottom - v(radii.bottom_right)),
+            Vector2F::new(right, bottom),
+            Vector2F::new(right - h(radii.bottom_right), bottom),
+        ),
+        corner(
+            radii.bottom_left,
+            Vector2F::new(left + h(radii.bottom_left), bottom),
+            Vector2F::new(left, bottom),
+            Vector2F::new(left, bottom - v(radii.bottom_left)),
+        ),
+        corner(
+            radii.top_left,
+            Vector2F::new(left, top + v(radii.top_left)),
+            Vector2F::new(left, top),
+            Vector2F::new(left + h(radii.top_left), top),
+        ),
+    )
+}
+
+/// How far the control points of a Bézier curve approximating a quarter of a circle are from its
+/// ends, as a fraction of the radius, which makes the curve deviate from the circle by less than
+/// 0.03%.
+const KAPPA: CSSFloat = 0.552_284_8;
+
+/// The path of a shape of a border.  Dots are drawn as four Bézier curves, which is how circles are
+/// usually approximated.
+fn shape_path(shape: &BorderShape) -> String {
+    match shape {
+        BorderShape::Polygon(..) | BorderShape::Strip(..) => {
+            let mut path = String::new();
+            for (i, point) in shape.polygon().iter().enumerate() {
+                let operator = if i == 0 { "m" } else { "l" };
+                write!(
+                    path,
+                    "{} {} {} ",
+                    number(point.x()),
+                    number(point.y()),
+                    operator
+                )
+                .unwrap();
+            }
+            path.push('h');
+            path
+        }
+        BorderShape::Circle { center, radius, .. } => {
+            let k = radius * KAPPA;
+            let (x, y, r) = (center.x(), center.y(), *radius);
+            let point = |x: CSSFloat, y: CSSFloat| format!("{} {}", number(x), number(y));
+            format!(
+                "{} m {} {} {} c {} {} {} c {} {} {} c {} {} {} c h",
+                point(x + r, y),
+                point(x + r, y + k),
+                point(x + k, y + r),
+                point(x, y + r),
+                point(x - k, y + r),
+                point(x - r, y + k),
+                point(x - r, y),
+                point(x - r, y - k),
+                point(x - k, y - r),
+                point(x, y - r),
+                point(x + k, y - r),
+                point(x + r, y - k),
+                point(x + r, y),
+            )
+        }
+    }
+}
+
+/// Formats `value` as a PDF number, with at most three decimal places.
+fn number(value: CSSFloat) -> String {
+    let formatted = format!("{:.3}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "" | "-" | "-0" => "0".to_owned(),
+        _ => trimmed.to_owned(),
+    }
+}
+
+/// Escapes the characters of `text` that can't be in a PDF name as `#` and their hex code.
+fn name(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'!'..=b'~' if !b"#%()/<>[]{}".contains(&byte) => escaped.push(byte as char),
+            _ => write!(escaped, "#{:02X}", byte).unwrap(),
+        }
+    }
+    escaped
+}
+
+/// A stream object, with `dictionary` as the entries of its dictionary besides its length.
+fn stream(dictionary: &str, data: &[u8]) -> Vec<u8> {
+    let mut object = if dictionary.is_empty() {
+        format!("<< /Length {} >>\nstream\n", data.len())
+    } else {
+        format!("<< {} /Length {} >>\nstream\n", dictionary, data.len())
+    }
+    .into_bytes();
+    object.extend_from_slice(data);
+    object.extend_from_slice(b"\nendstream");
+    object
+}
+
+/// The objects of a PDF document, where the ID of each is its index plus one.
+#[derive(Default)]
+struct PdfObjects {
+    objects: Vec<Option<Vec<u8>>>,
+}
+
+impl PdfObjects {
+    /// Reserves an ID for an object that's set later, once the objects it refers to are added.
+    fn reserve(&mut self) -> usize {
+        self.objects.push(None);
+        self.objects.len()
+    }
+
+    fn set(&mut self, id: usize, object: Vec<u8>) {
+        self.objects[id - 1] = Some(object);
+    }
+
+    fn add(&mut self, object: Vec<u8>) -> usize {
+        self.objects.push(Some(object));
+        self.objects.len()
+    }
+
+    /// Writes the document, with the object `root_id` as its catalog.
+    ///
+    /// TODO: Add the comment of binary bytes after the header that's recommended for documents
+    /// with binary streams.  It's left out so that documents without images or fonts are text.
+    fn write(self, root_id: usize) -> Vec<u8> {
+        let mut document = b"%PDF-1.7\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (index, object) in self.objects.into_iter().enumerate() {
+            offsets.push(document.len());
+            document.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            document.extend(object.expect("reserved PDF object was never set"));
+            document.extend_from_slice(b"\nendobj\n");
+        }
+        let xref_offset = document.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+        for offset in offsets.iter() {
+            writeln!(xref, "{:010} 00000 n ", offset).unwrap();
+        }
+        write!(
+            xref,
+            "trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            offsets.len() + 1,
+            root_id,
+            xref_offset
+        )
+        .unwrap();
+        document.extend_from_slice(xref.as_bytes());
+        document
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pathfinder_geometry::rect::RectF;
+
+    #[test]
+    fn formats_numbers_with_at_most_three_decimals() {
+        assert_eq!(number(0.), "0");
+        assert_eq!(number(-0.0001), "0");
+        assert_eq!(number(12.5), "12.5");
+        assert_eq!(number(1. / 3.), "0.333");
+        assert_eq!(number(-595.2756), "-595.276");
+    }
+
+    #[test]
+    fn escapes_pdf_names() {
+        assert_eq!(name("Helvetica-Bold"), "Helvetica-Bold");
+        assert_eq!(name("Open Sans(1)"), "Open#20Sans#281#29");
+    }
+
+    #[test]
+    fn embeds_fonts_as_cid_keyed_fonts_mapped_back_to_chars() {
+        let mut glyphs = BTreeMap::new();
+        glyphs.insert(36, ('A', 600.));
+        glyphs.insert(2, ('\u{1F680}', 1000.5));
+        let font = PdfFont {
+            postscript_name: Some("Test Sans".to_owned()),
+            data: Some(Arc::new(b"\0\x01\0\0glyf".to_vec())),
+            metrics: Metrics {
+                units_per_em: 2000,
+                ascent: 1600.,
+                descent: -400.,
+                line_gap: 0.,
+                underline_position: -200.,
+                underline_thickness: 100.,
+                cap_height: 1400.,
+                x_height: 1000.,
+                bounding_box: RectF::new(Vector2F::new(-100., -400.), Vector2F::new(2100., 2000.)),
+            },
+            glyphs,
+        };
+        let mut objects = PdfObjects::default();
+        let font_id = font.write(&mut objects);
+        let document = String::from_utf8(objects.write(font_id)).unwrap();
+        assert!(document.contains("<< /Length1 8 /Length 8 >>\nstream\n\0\x01\0\0glyf\nendstream"));
+        assert!(document.contains(
+            "/FontName /Test#20Sans /Flags 32 /FontBBox [-50 -200 1000 800] /ItalicAngle 0 /Ascent 800 /Descent -200 /CapHeight 700"
+        ));
+        assert!(document.contains("<< /Type /Font /Subtype /CIDFontType2 /BaseFont /Test#20Sans"));
+        assert!(document.contains("/W [2 [1000.5] 36 [600]]"));
+        // Chars outside the Basic Multilingual Plane are mapped to their UTF-16 surrogate pairs.
+        assert!(document.contains("2 beginbfchar\n<0002> <D83DDE80>\n<0024> <0041>\nendbfchar"));
+        assert!(document.contains(
+            "<< /Type /Font /Subtype /Type0 /BaseFont /Test#20Sans /Encoding /Identity-H /DescendantFonts ["
+        ));
+    }
+}
@@ -7,6 +7,7 @@ use gl::types::{GLint, GLvoid};
 use gl::vao::VertexArrayObject;
 use gl::vbo::VertexBufferObject;
 use gl::Gl;
+use pathfinder_geometry::vector::Vector2F;
 use std::ffi::CString;
 
 /// Uses given OpenGL instance to paint arbitrary rectangles.
@@ -104,21 +105,21 @@ impl ToVertices for (&Rect, &RGBA) {
         let mut vertex_data = Vec::new();
         // Top-left vertex.
         vertex_data.extend_from_slice(&[
-            ndc_x(rect.start_x, viewport_width),
-            ndc_y(rect.start_y, viewport_height),
+            ndc_x(rect.start_x.to_f32_px(), viewport_width),
+            ndc_y(rect.start_y.to_f32_px(), viewport_height),
             // TODO: Implement z-indexing.
             0.0,
         ]);
         vertex_data.extend_from_slice(rect_colors);
 
         let top_right_vertex = &[
-            ndc_x((rect.start_x + rect.width).px(), viewport_width),
-            ndc_y(rect.start_y, viewport_height),
+            ndc_x(rect.end_x().to_f32_px(), viewport_width),
+            ndc_y(rect.start_y.to_f32_px(), viewport_height),
             0.0,
         ];
         let bottom_left_vertex = &[
-            ndc_x(rect.start_x, viewport_width),
-            ndc_y((rect.start_y + rect.height).px(), viewport_height),
+            ndc_x(rect.start_x.to_f32_px(), viewport_width),
+            ndc_y(rect.end_y().to_f32_px(), viewport_height),
             0.0,
         ];
         vertex_data.extend_from_slice(top_right_vertex);
@@ -133,11 +134,35 @@ impl ToVertices for (&Rect, &RGBA) {
         vertex_data.extend_from_slice(rect_colors);
         // Bottom-right vertex.
         vertex_data.extend_from_slice(&[
-            ndc_x((rect.start_x + rect.width).px(), viewport_width),
-            ndc_y((rect.start_y + rect.height).px(), viewport_height),
+            ndc_x(rect.end_x().to_f32_px(), viewport_width),
+            ndc_y(rect.end_y().to_f32_px(), viewport_height),
             0.0,
         ]);
         vertex_data.extend_from_slice(rect_colors);
         vertex_data
     }
 }
+
+/// Converts a convex polygon filled with a solid color to triangles fanning out from its first
+/// point, such as the shapes borders are decomposed into.
+impl ToVertices for (&[Vector2F], &RGBA) {
+    fn to_vertices(&self, viewport_width: f32, viewport_height: f32) -> Vec<f32> {
+        let (polygon, rgba) = *self;
+        let colors = rgba.to_vertices(viewport_width, viewport_height);
+        let mut vertex_data = Vec::new();
+        if polygon.len() < 3 {
+            return vertex_data;
+        }
+        for i in 1..polygon.len() - 1 {
+            for point in &[polygon[0], polygon[i], polygon[i + 1]] {
+                vertex_data.extend_from_slice(&[
+                    ndc_x(point.x(), viewport_width),
+                    ndc_y(point.y(), viewport_height),
+                    0.0,
+                ]);
+                vertex_data.extend_from_slice(&colors);
+            }
+        }
+        vertex_data
+    }
+}
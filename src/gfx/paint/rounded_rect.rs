@@ -0,0 +1,154 @@
+use crate::gfx::ndc::{ndc_x, ndc_y};
+use crate::gfx::paint::{build_program, ToVertices};
+use crate::layout::rect::RoundedRect;
+use cssparser::RGBA;
+use gl::program::Program;
+use gl::types::{GLint, GLvoid};
+use gl::vao::VertexArrayObject;
+use gl::vbo::VertexBufferObject;
+use gl::Gl;
+use std::ffi::CString;
+
+/// The number of floats each vertex of a rounded rectangle is made of: its position in normalized
+/// device coordinates and in pixels, the edges of the rectangle, the horizontal and vertical radii
+/// of its corners, and its color.
+const FLOATS_PER_VERTEX: usize = 2 + 2 + 4 + 4 + 4 + 4;
+
+/// Uses given OpenGL instance to paint rectangles with rounded corners, whose edges are
+/// anti-aliased by a fragment shader that measures the distance of each fragment from them.  Also
+/// draws rounded clips into the stencil buffer.
+pub struct RoundedRectPainter {
+    /// The OpenGL program that will be used to paint rounded rectangles.
+    program: Program,
+    /// The location of the `MinCoverage` uniform of `program`.
+    min_coverage: GLint,
+    /// The VAO to use to paint rounded rectangles.
+    vao: VertexArrayObject,
+    /// An instance of OpenGL.
+    gl: Gl,
+}
+
+impl RoundedRectPainter {
+    pub fn new(gl: &Gl) -> Result<RoundedRectPainter, String> {
+        let vbo = VertexBufferObject::new(gl);
+        let config_vao = |gl: &Gl| {
+            let stride = (FLOATS_PER_VERTEX * std::mem::size_of::<f32>()) as GLint;
+            // The index and number of components of each attribute, in the order they're laid
+            // out in each vertex.
+            let attributes = [(0, 2), (1, 2), (2, 4), (3, 4), (4, 4), (5, 4)];
+            let mut offset = 0;
+            for &(index, components) in attributes.iter() {
+                unsafe {
+                    gl.EnableVertexAttribArray(index);
+                    gl.VertexAttribPointer(
+                        index,
+                        components,
+                        gl::FLOAT,
+                        gl::FALSE,
+                        stride,
+                        (offset * std::mem::size_of::<f32>()) as *const GLvoid,
+                    );
+                }
+                offset += components as usize;
+            }
+        };
+        let vao = unsafe { VertexArrayObject::new(vbo, config_vao, gl) };
+        let program = build_rounded_rect_program(gl)?;
+        let uniform_name = CString::new("MinCoverage").unwrap();
+        let min_coverage = unsafe { gl.GetUniformLocation(program.id(), uniform_name.as_ptr()) };
+
+        Ok(RoundedRectPainter {
+            program,
+            min_coverage,
+            vao,
+            gl: gl.clone(),
+        })
+    }
+
+    /// Paints the rounded rectangles `vertices` are made of, anti-aliasing their edges.
+    pub fn paint(&mut self, vertices: &[f32]) {
+        self.draw(vertices, 0.);
+    }
+
+    /// Draws the rounded rectangles `vertices` are made of without anti-aliasing, covering only
+    /// the pixels whose centers are within them, for drawing them into the stencil buffer.
+    pub fn paint_mask(&mut self, vertices: &[f32]) {
+        self.draw(vertices, 0.5);
+    }
+
+    fn draw(&mut self, vertices: &[f32], min_coverage: f32) {
+        if vertices.is_empty() {
+            return;
+        }
+        let vertex_count = vertices.len() / FLOATS_PER_VERTEX;
+        // Panic rather than truncate data.
+        assert!(vertex_count <= i32::MAX as usize);
+
+        self.program.use_globally();
+        self.vao.store_vertex_data(vertices);
+        unsafe {
+            self.gl.Uniform1f(self.min_coverage, min_coverage);
+            self.gl.BindVertexArray(self.vao.name());
+            // Safe because of the assert above.
+            self.gl.DrawArrays(gl::TRIANGLES, 0, vertex_count as i32);
+            self.gl.BindVertexArray(0);
+        }
+    }
+}
+
+fn build_rounded_rect_program(gl: &Gl) -> Result<Program, String> {
+    let vertex_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/rounded_rect.vert"
+    )))
+    .expect("could not create cstring for rounded rect program");
+
+    let frag_shader_src = &CString::new(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/shader_src/rounded_rect.frag"
+    )))
+    .expect("could not create cstring for rounded rect program");
+
+    build_program(vertex_shader_src, frag_shader_src, gl)
+}
+
+/// Converts a rounded rectangle to the two triangles of a quad a pixel larger than it on every
+/// side, so that the fragments its anti-aliased edges partly cover are shaded too.  Every vertex
+/// carries the whole rectangle, which the fragment shader measures the distance to.
+impl ToVertices for (&RoundedRect, &RGBA) {
+    fn to_vertices(&self, viewport_width: f32, viewport_height: f32) -> Vec<f32> {
+        let (rrect, rgba) = *self;
+        let (left, top, right, bottom) = (
+            rrect.rect.start_x.to_f32_px(),
+            rrect.rect.start_y.to_f32_px(),
+            rrect.rect.end_x().to_f32_px(),
+            rrect.rect.end_y().to_f32_px(),
+        );
+        let corners = rrect.radii.corners();
+        let mut shared = vec![left, top, right, bottom];
+        shared.extend(corners.iter().map(|radius| radius.horizontal.to_f32_px()));
+        shared.extend(corners.iter().map(|radius| radius.vertical.to_f32_px()));
+        shared.extend(rgba.to_vertices(viewport_width, viewport_height));
+
+        let (outer_left, outer_top, outer_right, outer_bottom) =
+            (left - 1., top - 1., right + 1., bottom + 1.);
+        let mut vertex_data = Vec::with_capacity(6 * FLOATS_PER_VERTEX);
+        for &(x, y) in &[
+            (outer_left, outer_top),
+            (outer_right, outer_top),
+            (outer_left, outer_bottom),
+            (outer_left, outer_bottom),
+            (outer_right, outer_top),
+            (outer_right, outer_bottom),
+        ] {
+            vertex_data.extend_from_slice(&[
+                ndc_x(x, viewport_width),
+                ndc_y(y, viewport_height),
+                x,
+                y,
+            ]);
+            vertex_data.extend_from_slice(&shared);
+        }
+        vertex_data
+    }
+}
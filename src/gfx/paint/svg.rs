@@ -0,0 +1,558 @@
+use crate::gfx::border::{border_shapes, BorderShape};
+use crate::gfx::display::{DisplayCommand, DisplayList, GlyphCommand, Tiles};
+use crate::gfx::font::FontHandle;
+use crate::gfx::gradient::{Gradient, GradientShape, GradientStop};
+use crate::gfx::paint::PaintBackend;
+use crate::gfx::shadow::{blur_margin, BoxShadow};
+use crate::layout::rect::{CornerRadius, EdgeSizes, Rect, RoundedRect};
+use crate::style::values::CSSFloat;
+use app_units::Au;
+use cssparser::RGBA;
+use image::png::PngEncoder;
+use image::{ColorType, RgbaImage};
+use std::fmt::Write;
+
+/// Paints display lists as SVG documents, for vector snapshots of pages.  Rectangles are painted as
+/// `<rect>`s, or `<path>`s with arcs if their corners are rounded, borders as `<polygon>`s and
+/// `<circle>`s, glyphs as `<text>` in the family and size of their font, clips as `<clipPath>`s,
+/// and images as `<image>`s with their pixels embedded as PNG data URIs.  Tiled images and
+/// gradients fill a `<rect>` with a `<pattern>` of one tile, and shadows are blurred by
+/// `<filter>`s.
+///
+/// https://www.w3.org/TR/SVG11/
+#[derive(Debug, Default)]
+pub struct SvgPainter {
+    /// The document the last display list was painted as.
+    document: String,
+    /// The number of clip paths defined in `document`, used to give each a unique ID.
+    clip_count: usize,
+    /// The number of patterns defined in `document`, used to give each, and the gradient it may
+    /// be filled with, a unique ID.
+    pattern_count: usize,
+    /// The number of blur filters defined in `document`, used to give each a unique ID.
+    filter_count: usize,
+}
+
+impl SvgPainter {
+    pub fn new() -> SvgPainter {
+        SvgPainter::default()
+    }
+
+    /// Gets the document the last display list was painted as.
+    pub fn document(&self) -> &str {
+        &self.document
+    }
+
+    fn process_display_command(
+        &mut self,
+        command: &DisplayCommand,
+        display_list: &DisplayList,
+        font_handle: &FontHandle,
+        indent: usize,
+    ) {
+        let element = match command {
+            DisplayCommand::Border(border) => border_shapes(border)
+                .iter()
+                .map(shape_element)
+                .collect::<Vec<_>>()
+                .join(&format!("\n{:indent$}", "", indent = indent)),
+            DisplayCommand::BoxShadow(shadow) => self.box_shadow_element(shadow, indent),
+            DisplayCommand::Glyph(glyph) => {
+                let (filter, attribute) =
+                    self.blur_filter(glyph.blur_radius().to_f32_px() / 2., None);
+                format!(
+                    "{}{}",
+                    filter,
+                    glyph_element(glyph, font_handle, &attribute)
+                )
+            }
+            DisplayCommand::Image(key, rect) => match display_list.image(*key) {
+                Some(image) => format!(
+                    r#"<image {} preserveAspectRatio="none" xlink:href="data:image/png;base64,{}"/>"#,
+                    rect_attributes(rect),
+                    base64(&encode_png(image)),
+                ),
+                None => return,
+            },
+            DisplayCommand::ImageTiles(key, tiles) => match display_list.image(*key) {
+                Some(image) => {
+                    let content = format!(
+                        r#"<image width="{}" height="{}" preserveAspectRatio="none" xlink:href="data:image/png;base64,{}"/>"#,
+                        tiles.tile.width.to_f32_px(),
+                        tiles.tile.height.to_f32_px(),
+                        base64(&encode_png(image)),
+                    );
+                    self.tiles_element(tiles, &content, indent)
+                }
+                None => return,
+            },
+            DisplayCommand::GradientTiles(gradient, tiles) => {
+                let id = self.pattern_count + 1;
+                let content = format!(
+                    r#"{}<rect width="{}" height="{}" fill="url(#gradient{})"/>"#,
+                    gradient_element(
+                        id,
+                        gradient,
+                        tiles.tile.width.to_f32_px(),
+                        tiles.tile.height.to_f32_px()
+                    ),
+                    tiles.tile.width.to_f32_px(),
+                    tiles.tile.height.to_f32_px(),
+                    id
+                );
+                self.tiles_element(tiles, &content, indent)
+            }
+            DisplayCommand::PushClip(rect) => {
+                self.clip_count += 1;
+                format!(
+                    r#"<clipPath id="clip{id}"><rect {}/></clipPath>
+{:indent$}<g clip-path="url(#clip{id})">"#,
+                    rect_attributes(rect),
+                    "",
+                    id = self.clip_count,
+                    indent = indent
+                )
+            }
+            DisplayCommand::PushRoundedClip(rrect) => {
+                self.clip_count += 1;
+                format!(
+                    r#"<clipPath id="clip{id}"><path d="{}"/></clipPath>
+{:indent$}<g clip-path="url(#clip{id})">"#,
+                    rounded_rect_path(rrect),
+                    "",
+                    id = self.clip_count,
+                    indent = indent
+                )
+            }
+            DisplayCommand::PushOpacity(opacity) => format!(r#"<g opacity="{}">"#, opacity),
+            DisplayCommand::PopClip | DisplayCommand::PopOpacity => "</g>".to_owned(),
+            DisplayCommand::RectSolidColor(rgba, rect) => {
+                format!(
+                    "<rect {} {}/>",
+                    rect_attributes(rect),
+                    fill_attributes(*rgba)
+                )
+            }
+            DisplayCommand::RoundedRectSolidColor(rgba, rrect) => format!(
+                r#"<path d="{}" {}/>"#,
+                rounded_rect_path(rrect),
+                fill_attributes(*rgba)
+            ),
+            DisplayCommand::ViewportBackground(rgba) => format!(
+                r#"<rect width="100%" height="100%" {}/>"#,
+                fill_attributes(*rgba)
+            ),
+        };
+        writeln!(self.document, "{:indent$}{}", "", element, indent = indent).unwrap();
+    }
+
+    /// Converts `shadow` to its shape, blurred by a filter, in a group clipped to where it's
+    /// painted.  An outer shadow's clip has a hole in it where the box casting it is, and an inset
+    /// shadow is painted as a ring around its shape so that it's the hole that's blurred.
+    fn box_shadow_element(&mut self, shadow: &BoxShadow, indent: usize) -> String {
+        // Far enough past the bounds of the shadow and its shape that the blur of the ring around
+        // an inset shadow doesn't fade before the clip.
+        let margin = Au::from_px(blur_margin(shadow.sigma()) + 1);
+        let surrounding = shadow
+            .bounds()
+            .union(shadow.shape().rect)
+            .expanded_by(EdgeSizes {
+                left: margin,
+                right: margin,
+                top: margin,
+                bottom: margin,
+            });
+        let (filter, filter_attribute) = self.blur_filter(shadow.sigma(), Some(&surrounding));
+        let (clip, shape) = if shadow.is_inset() {
+            (
+                format!(r#"d="{}""#, rounded_rect_path(&shadow.clip())),
+                format!(
+                    r#"d="{} {}" fill-rule="evenodd""#,
+                    rect_path(&surrounding),
+                    rounded_rect_path(&shadow.shape())
+                ),
+            )
+        } else {
+            (
+                format!(
+                    r#"d="{} {}" clip-rule="evenodd""#,
+                    rect_path(&surrounding),
+                    rounded_rect_path(&shadow.clip())
+                ),
+                format!(r#"d="{}""#, rounded_rect_path(&shadow.shape())),
+            )
+        };
+        self.clip_count += 1;
+        format!(
+            r#"{}<clipPath id="clip{id}"><path {}/></clipPath>
+{:indent$}<g clip-path="url(#clip{id})"><path {} {}{}/></g>"#,
+            filter,
+            clip,
+            "",
+            shape,
+            fill_attributes(shadow.color()),
+            filter_attribute,
+            id = self.clip_count,
+            indent = indent
+        )
+    }
+
+    /// Gets a `<filter>` that blurs by a Gaussian of standard deviation `sigma` within `region`,
+    /// and the attribute that applies it, both of which are empty if there's no blur.  Without a
+    /// region, the filter covers twice the size of what it's applied to, centered on it.
+    fn blur_filter(&mut self, sigma: f32, region: Option<&Rect>) -> (String, String) {
+        if sigma <= 0. {
+            return (String::new(), String::new());
+        }
+        self.filter_count += 1;
+        let region = match region {
+            Some(rect) => format!(r#"filterUnits="userSpaceOnUse" {}"#, rect_attributes(rect)),
+            None => r#"x="-50%" y="-50%" width="200%" height="200%""#.to_owned(),
+        };
+        (
+            format!(
+                r#"<filter id="blur{id}" {}><feGaussianBlur stdDeviation="{}"/></filter>"#,
+                region,
+                sigma,
+                id = self.filter_count
+            ),
+            format!(r#" filter="url(#blur{})""#, self.filter_count),
+        )
+    }
+
+    /// Converts `tiles` of `content`, the elements one tile is made of, to a `<pattern>` of one
+    /// tile and a `<rect>` filled with it over the area the tiles cover.  Along an axis the tiles
+    /// aren't repeated in, the pattern is made large enough that the next copy is beyond the area.
+    fn tiles_element(&mut self, tiles: &Tiles, content: &str, indent: usize) -> String {
+        self.pattern_count += 1;
+        let pattern_size = |step: Option<Au>, size: Au, area_size: Au| {
+            step.unwrap_or(size + area_size).to_f32_px()
+        };
+        format!(
+            r#"<pattern id="pattern{id}" patternUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}">{}</pattern>
+{:indent$}<rect {} fill="url(#pattern{id})"/>"#,
+            tiles.tile.start_x.to_f32_px(),
+            tiles.tile.start_y.to_f32_px(),
+            pattern_size(tiles.step_x, tiles.tile.width, tiles.area.width),
+            pattern_size(tiles.step_y, tiles.tile.height, tiles.area.height),
+            content,
+            "",
+            rect_attributes(&tiles.area),
+            id = self.pattern_count,
+            indent = indent
+        )
+    }
+}
+
+impl PaintBackend for SvgPainter {
+    fn paint(
+        &mut self,
+        display_list: &DisplayList,
+        viewport_width: CSSFloat,
+        viewport_height: CSSFloat,
+        font_handle: &FontHandle,
+    ) {
+        self.document.clear();
+        self.clip_count = 0;
+        self.pattern_count = 0;
+        self.filter_count = 0;
+        writeln!(
+            self.document,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = viewport_width,
+            h = viewport_height
+        )
+        .unwrap();
+        // Each push opens a group that lasts until the matching pop, so the commands between them
+        // are indented like the dumped display list.
+        let mut indent: usize = 2;
+        for command in display_list.commands() {
+            if let DisplayCommand::PopClip | DisplayCommand::PopOpacity = command {
+                indent = indent.saturating_sub(2);
+            }
+            self.process_display_command(command, display_list, font_handle, indent);
+            if let DisplayCommand::PushClip(_)
+            | DisplayCommand::PushRoundedClip(_)
+            | DisplayCommand::PushOpacity(_) = command
+            {
+                indent += 2;
+            }
+        }
+        self.document.push_str("</svg>\n");
+    }
+}
+
+/// Converts `glyph` to a `<text>` element of its char, with its origin at the start of the
+/// baseline, and `filter` the attribute of the filter blurring it, if it's blurred.
+///
+/// TODO: This relies on whoever views the document having the font installed.  Glyphs could
+/// instead be painted as their outlines, as `<path>`s.
+fn glyph_element(glyph: &GlyphCommand, font_handle: &FontHandle, filter: &str) -> String {
+    format!(
+        r#"<text x="{}" y="{}" font-family="{}" font-size="{}" {}{}>{}</text>"#,
+        glyph.origin().x(),
+        glyph.origin().y(),
+        escape(&font_handle.font(glyph.font()).family_name()),
+        glyph.size().to_f32_px(),
+        fill_attributes(glyph.color()),
+        filter,
+        escape(&glyph.ch().to_string()),
+    )
+}
+
+/// Converts `gradient` to a `<linearGradient>` or `<radialGradient>` with the ID `gradient{id}`,
+/// for painting a tile `width` by `height` in size.  SVG gradients can't repeat or extend in
+/// both directions from their stops the way CSS gradients do, so repeating stops are repeated
+/// across the tile, and the gradient vector spans from the first stop to the last.
+fn gradient_element(id: usize, gradient: &Gradient, width: f32, height: f32) -> String {
+    let gradient = gradient.unrepeated(width, height);
+    let mut stops = gradient.stops().to_vec();
+    let (element, attributes) = match gradient.shape() {
+        GradientShape::Linear { start, end } => {
+            let (first, last) = (stops[0].offset, stops[stops.len() - 1].offset);
+            let length = (end - start).length();
+            let direction = if length > 0. {
+                (end - start) * (1. / length)
+            } else {
+                end - start
+            };
+            let (from, to) = (start + direction * first, start + direction * last);
+            (
+                "linearGradient",
+                format!(
+                    r#"x1="{}" y1="{}" x2="{}" y2="{}""#,
+                    from.x(),
+                    from.y(),
+                    to.x(),
+                    to.y()
+                ),
+            )
+        }
+        GradientShape::Radial {
+            center,
+            radius_x,
+            radius_y,
+        } => {
+            // Stops before the center are never painted, but the color they make at the center
+            // is.
+            if stops[0].offset < 0. {
+                let color = gradient.color_at_offset(0.);
+                stops.retain(|stop| stop.offset >= 0.);
+                stops.insert(0, GradientStop { offset: 0., color });
+            }
+            // The gradient is a circle scaled vertically into an ellipse.
+            let scale_y = radius_y / radius_x;
+            (
+                "radialGradient",
+                format!(
+                    r#"cx="{}" cy="{}" r="{}" gradientTransform="scale(1 {})""#,
+                    center.x(),
+                    center.y() / scale_y,
+                    stops[stops.len() - 1].offset,
+                    scale_y
+                ),
+            )
+        }
+    };
+    let (first, last) = (stops[0].offset, stops[stops.len() - 1].offset);
+    let span = last - first;
+    let stops = stops
+        .iter()
+        .map(|stop| {
+            let offset = if span > 0. {
+                (stop.offset - first) / span
+            } else {
+                1.
+            };
+            let rgba = stop.color;
+            let mut stop = format!(
+                r#"<stop offset="{}" stop-color="rgb({}, {}, {})""#,
+                offset, rgba.red, rgba.green, rgba.blue
+            );
+            if rgba.alpha != 255 {
+                write!(stop, r#" stop-opacity="{}""#, rgba.alpha_f32()).unwrap();
+            }
+            stop.push_str("/>");
+            stop
+        })
+        .collect::<String>();
+    format!(
+        r#"<{element} id="gradient{}" gradientUnits="userSpaceOnUse" {}>{}</{element}>"#,
+        id,
+        attributes,
+        stops,
+        element = element
+    )
+}
+
+/// Converts a shape of a border to a `<polygon>` of its outline, or a `<circle>` if it's a dot.
+fn shape_element(shape: &BorderShape) -> String {
+    match shape {
+        BorderShape::Polygon(..) | BorderShape::Strip(..) => format!(
+            r#"<polygon points="{}" {}/>"#,
+            shape
+                .polygon()
+                .iter()
+                .map(|point| format!("{},{}", point.x(), point.y()))
+                .collect::<Vec<_>>()
+                .join(" "),
+            fill_attributes(shape.color())
+        ),
+        BorderShape::Circle {
+            color,
+            center,
+            radius,
+        } => format!(
+            r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+            center.x(),
+            center.y(),
+            radius,
+            fill_attributes(*color)
+        ),
+    }
+}
+
+/// Converts `rect` to the data of a `<path>` that goes clockwise around its edges.
+fn rect_path(rect: &Rect) -> String {
+    format!(
+        "M {} {} H {} V {} H {} Z",
+        rect.start_x.to_f32_px(),
+        rect.start_y.to_f32_px(),
+        rect.end_x().to_f32_px(),
+        rect.end_y().to_f32_px(),
+        rect.start_x.to_f32_px()
+    )
+}
+
+fn rect_attributes(rect: &Rect) -> String {
+    format!(
+        r#"x="{}" y="{}" width="{}" height="{}""#,
+        rect.start_x.to_f32_px(),
+        rect.start_y.to_f32_px(),
+        rect.width.to_f32_px(),
+        rect.height.to_f32_px()
+    )
+}
+
+/// Converts `rrect` to the data of a `<path>` that goes clockwise around its edges from the end
+/// of its top-left corner, with each corner an elliptical arc.  Arcs with a zero radius are drawn
+/// as straight lines, so square corners need no special handling.
+fn rounded_rect_path(rrect: &RoundedRect) -> String {
+    let px = |au: Au| au.to_f32_px();
+    let (left, top, right, bottom) = (
+        px(rrect.rect.start_x),
+        px(rrect.rect.start_y),
+        px(rrect.rect.end_x()),
+        px(rrect.rect.end_y()),
+    );
+    let radii = rrect.radii;
+    let arc = |radius: CornerRadius, x: CSSFloat, y: CSSFloat| {
+        format!(
+            "A {} {} 0 0 1 {} {}",
+            px(radius.horizontal),
+            px(radius.vertical),
+            x,
+            y
+        )
+    };
+    format!(
+        "M {} {} H {} {} V {} {} H {} {} V {} {} Z",
+        left + px(radii.top_left.horizontal),
+        top,
+        right - px(radii.top_right.horizontal),
+        arc(radii.top_right, right, top + px(radii.top_right.vertical)),
+        bottom - px(radii.bottom_right.vertical),
+        arc(
+            radii.bottom_right,
+            right - px(radii.bottom_right.horizontal),
+            bottom
+        ),
+        left + px(radii.bottom_left.horizontal),
+        arc(
+            radii.bottom_left,
+            left,
+            bottom - px(radii.bottom_left.vertical)
+        ),
+        top + px(radii.top_left.vertical),
+        arc(radii.top_left, left + px(radii.top_left.horizontal), top),
+    )
+}
+
+/// Converts `rgba` to the attributes of a fill in that color.  The opacity is left out if the
+/// color is opaque, since that's the default.
+fn fill_attributes(rgba: RGBA) -> String {
+    let fill = format!(r#"fill="rgb({}, {}, {})""#, rgba.red, rgba.green, rgba.blue);
+    if rgba.alpha == 255 {
+        fill
+    } else {
+        format!(r#"{} fill-opacity="{}""#, fill, rgba.alpha_f32())
+    }
+}
+
+/// Escapes the characters of `text` that have a special meaning in XML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn encode_png(image: &RgbaImage) -> Vec<u8> {
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .encode(image, image.width(), image.height(), ColorType::Rgba8)
+        .expect("couldn't encode image as PNG");
+    png
+}
+
+/// Encodes `bytes` with the standard base64 alphabet, padded with `=`.
+///
+/// https://tools.ietf.org/html/rfc4648#section-4
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | ((byte as u32) << (16 - 8 * i))
+        });
+        // Each byte of the chunk carries over into one more character.
+        for i in 0..=chunk.len() {
+            let index = (group >> (18 - 6 * i)) & 0x3f;
+            encoded.push(ALPHABET[index as usize] as char);
+        }
+        for _ in chunk.len()..3 {
+            encoded.push('=');
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_partial_groups() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn escapes_xml_special_characters() {
+        assert_eq!(
+            escape(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+}
@@ -1,13 +1,19 @@
-use crate::gfx::display::CharCommand;
 use crate::gfx::ndc::{ndc_x, ndc_y};
-use crate::gfx::paint::{build_program, CharPaintData, ToVertices};
+use crate::gfx::paint::glyph_atlas::{AtlasId, GlyphAtlases, GlyphQuad};
+use crate::gfx::paint::{build_program, ToVertices};
+use cssparser::RGBA;
 use gl::program::Program;
-use gl::types::{GLint, GLsizeiptr};
+use gl::types::{GLint, GLvoid};
 use gl::vao::VertexArrayObject;
 use gl::vbo::VertexBufferObject;
-use gl::{Gl, ARRAY_BUFFER, DYNAMIC_DRAW, FALSE, FLOAT, TEXTURE0, TEXTURE_2D, TRIANGLES};
+use gl::{Gl, FALSE, FLOAT, TEXTURE0, TEXTURE_2D, TRIANGLES};
+use std::collections::HashMap;
 use std::ffi::CString;
 
+/// The number of floats making up each vertex of a glyph: its position, its texture coordinates,
+/// and its color.
+const FLOATS_PER_VERTEX: usize = 8;
+
 /// Uses given OpenGL instance to paint arbitrary text.
 pub struct TextPainter {
     /// An instance of OpenGL.
@@ -23,27 +29,22 @@ impl TextPainter {
         let vbo = VertexBufferObject::new(gl);
         let config_vao = |gl: &Gl| {
             unsafe {
-                // Our 2D quad requires 6 vertices of 4 floats each, so pass that as the size of the buffer.
-                // Pass a null pointer because we don't want to initialize the buffer with any data right now.
-                // DYNAMIC_DRAW because this buffer will be updated very often (once for each character drawn).
-                gl.BufferData(
-                    ARRAY_BUFFER,
-                    (6 * 4 * std::mem::size_of::<f32>()) as GLsizeiptr,
-                    std::ptr::null(),
-                    DYNAMIC_DRAW,
-                );
-                // Enable use of `layout (location=0)` data in our vertex shader.
+                let stride = (FLOATS_PER_VERTEX * std::mem::size_of::<f32>()) as GLint;
+                // Location index 0 is a vec4 of the position and texture coordinates of the vertex.
+                // It isn't normalized, where normalization is the process of OpenGL mapping the
+                // values to a [-1,1] range for signed values or a range of [0,1] for unsigned
+                // values.
                 gl.EnableVertexAttribArray(0);
-                // Location index 0 data has a size of four floats and is not normalized, where normalization
-                // is the process of OpenGL mapping the values to a [-1,1] range for signed values or a range
-                // of [0,1] for unsigned values.
+                gl.VertexAttribPointer(0, 4, FLOAT, FALSE, stride, std::ptr::null());
+                // Location index 1 is a vec4 of the color of the vertex.
+                gl.EnableVertexAttribArray(1);
                 gl.VertexAttribPointer(
-                    0,
+                    1,
                     4,
                     FLOAT,
                     FALSE,
-                    (4 * std::mem::size_of::<f32>()) as GLint,
-                    std::ptr::null(),
+                    stride,
+                    (4 * std::mem::size_of::<f32>()) as *const GLvoid,
                 );
             }
         };
@@ -56,36 +57,34 @@ impl TextPainter {
         })
     }
 
-    pub fn paint(&mut self, paintable_chars: &[CharPaintData]) {
+    /// Paints the glyphs with the given vertices, with a single draw call for all the glyphs from
+    /// each glyph atlas.
+    pub fn paint(
+        &mut self,
+        vertices_by_atlas: &HashMap<AtlasId, Vec<f32>>,
+        glyph_atlases: &GlyphAtlases,
+    ) {
         self.program.use_globally();
-
-        let text_color_str =
-            CString::new("textColor").expect("couldn't create `textColor` cstring");
         unsafe {
             self.gl.ActiveTexture(TEXTURE0);
             self.gl.BindVertexArray(self.vao.name());
         }
-        for ch in paintable_chars {
+        for (atlas, vertices) in vertices_by_atlas {
+            if vertices.is_empty() {
+                continue;
+            }
+            let texture_id = match glyph_atlases.texture_id(*atlas) {
+                Some(texture_id) => texture_id,
+                None => continue,
+            };
+            let vertex_count = vertices.len() / FLOATS_PER_VERTEX;
             // Panic rather than truncate data.
-            assert!(ch.vertices.len() <= i32::max_value() as usize);
-
+            assert!(vertex_count <= i32::MAX as usize);
             unsafe {
-                self.gl.Uniform3f(
-                    self.gl
-                        .GetUniformLocation(self.program.id(), text_color_str.as_ptr()),
-                    ch.color.red_f32(),
-                    ch.color.green_f32(),
-                    ch.color.blue_f32(),
-                );
-                self.gl.BindTexture(TEXTURE_2D, ch.texture_id);
-                self.vao.store_vertex_data(&ch.vertices);
+                self.gl.BindTexture(TEXTURE_2D, texture_id);
+                self.vao.store_vertex_data(vertices);
                 // Casting the `usize` to `GLint` will not truncate due to the above assert!().
-                self.gl.DrawArrays(TRIANGLES, 0, ch.vertices.len() as i32);
-                // TODO:  We need to increment the `x_pos` (which is calculated somewhere else) with the advance of this character.
-                // This should probably happen somewhere else (layout?  display command processing?)
-                // Here is some C++ code that does this work:
-                //     now advance cursors for next glyph (note that advance is number of 1/64 pixels)
-                //     x += (ch.Advance >> 6) * scale; // bitshift by 6 to get value in pixels (2^6 = 64)
+                self.gl.DrawArrays(TRIANGLES, 0, vertex_count as i32);
             }
         }
 
@@ -112,53 +111,39 @@ fn build_text_program(gl: &Gl) -> Result<Program, String> {
     build_program(vertex_shader_src, frag_shader_src, gl)
 }
 
-impl ToVertices for CharCommand {
+impl ToVertices for (&GlyphQuad, &RGBA) {
     fn to_vertices(&self, viewport_width: f32, viewport_height: f32) -> Vec<f32> {
-        let x_pos = self.start_coords().x() + self.bearing().x();
-        let y_pos = self.start_coords().y() - (self.size().y() - self.bearing().y());
-
-        // Transpose the quad width and height values (which are the second half of each vertex)
-        // relative to what https://learnopengl.com/In-Practice/Text-Rendering has because of the
-        // way we flip `ndc_y` values to render them at the top of the screen.
-        let mut vertices = Vec::new();
-        vertices.extend_from_slice(&[
-            ndc_x(x_pos, viewport_width),
-            ndc_y(y_pos + self.size().y(), viewport_height),
-            1.0,
-            1.0,
-        ]);
-        vertices.extend_from_slice(&[
-            ndc_x(x_pos, viewport_width),
-            ndc_y(y_pos, viewport_height),
-            1.0,
-            0.0,
-        ]);
-        vertices.extend_from_slice(&[
-            ndc_x(x_pos + self.size().x(), viewport_width),
-            ndc_y(y_pos, viewport_height),
-            0.0,
-            0.0,
-        ]);
-
-        vertices.extend_from_slice(&[
-            ndc_x(x_pos, viewport_width),
-            ndc_y(y_pos + self.size().y(), viewport_height),
-            1.0,
-            1.0,
-        ]);
-        vertices.extend_from_slice(&[
-            ndc_x(x_pos + self.size().x(), viewport_width),
-            ndc_y(y_pos, viewport_height),
-            0.0,
-            0.0,
-        ]);
-        vertices.extend_from_slice(&[
-            ndc_x(x_pos + self.size().x(), viewport_width),
-            ndc_y(y_pos + self.size().y(), viewport_height),
-            0.0,
-            1.0,
-        ]);
-
+        let (quad, color) = *self;
+        let tex_coords = quad.tex_coords;
+        let (left, top, right, bottom) = (
+            ndc_x(quad.rect.min_x(), viewport_width),
+            ndc_y(quad.rect.min_y(), viewport_height),
+            ndc_x(quad.rect.max_x(), viewport_width),
+            ndc_y(quad.rect.max_y(), viewport_height),
+        );
+        // The first row of the atlas is the top of each glyph, so the minimum vertical texture
+        // coordinate goes with the top of the quad.
+        let corners = [
+            (left, bottom, tex_coords.min_x(), tex_coords.max_y()),
+            (left, top, tex_coords.min_x(), tex_coords.min_y()),
+            (right, top, tex_coords.max_x(), tex_coords.min_y()),
+            (left, bottom, tex_coords.min_x(), tex_coords.max_y()),
+            (right, top, tex_coords.max_x(), tex_coords.min_y()),
+            (right, bottom, tex_coords.max_x(), tex_coords.max_y()),
+        ];
+        let mut vertices = Vec::with_capacity(corners.len() * FLOATS_PER_VERTEX);
+        for &(x, y, u, v) in corners.iter() {
+            vertices.extend_from_slice(&[
+                x,
+                y,
+                u,
+                v,
+                color.red_f32(),
+                color.green_f32(),
+                color.blue_f32(),
+                color.alpha_f32(),
+            ]);
+        }
         vertices
     }
 }
@@ -0,0 +1,455 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-backgrounds-3/#box-shadow
+//  * https://drafts.csswg.org/css-backgrounds-3/#shadow-shape
+//  * https://drafts.csswg.org/css-text-decor-3/#text-shadow-property
+use crate::layout::layout_box::LayoutBox;
+use crate::layout::rect::{CornerRadii, CornerRadius, EdgeSizes, Rect, RoundedRect};
+use crate::style::values::computed::length::CSSPixelLength;
+use app_units::Au;
+use cssparser::RGBA;
+use image::{Rgba, RgbaImage};
+use pathfinder_geometry::vector::Vector2F;
+
+/// The number of rows `BoxShadow::alpha_at` samples the shape of a shadow at, to integrate its
+/// blur vertically.  `shader_src/box_shadow.frag` samples the same number.
+const BLUR_SAMPLES: usize = 8;
+
+/// A shadow cast by a box, blurred by a Gaussian, in device pixels.  Outer shadows are painted
+/// only outside the box casting them, and inset shadows only inside it.
+///
+/// Rather than blurring an image of the shadow, the coverage of the blurred shadow is computed
+/// analytically at each point, which every painting backend does the same way: the shape is a
+/// single span along each row, whose blur along the row is a difference of error functions, and
+/// the blur along columns is integrated from a few sampled rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxShadow {
+    /// The shape of the shadow before it's blurred, which is the box offset and spread.
+    shape: RoundedRect,
+    /// The border box of an outer shadow, which it isn't painted within, or the padding box of
+    /// an inset shadow, which it's only painted within.
+    clip: RoundedRect,
+    color: RGBA,
+    /// The blur radius, which is twice the standard deviation of the Gaussian.
+    blur_radius: Au,
+    inset: bool,
+}
+
+impl BoxShadow {
+    pub fn new(
+        shape: RoundedRect,
+        clip: RoundedRect,
+        color: RGBA,
+        blur_radius: Au,
+        inset: bool,
+    ) -> BoxShadow {
+        BoxShadow {
+            shape,
+            clip,
+            color,
+            blur_radius,
+            inset,
+        }
+    }
+
+    pub fn shape(&self) -> RoundedRect {
+        self.shape
+    }
+
+    pub fn clip(&self) -> RoundedRect {
+        self.clip
+    }
+
+    pub fn color(&self) -> RGBA {
+        self.color
+    }
+
+    pub fn blur_radius(&self) -> Au {
+        self.blur_radius
+    }
+
+    pub fn is_inset(&self) -> bool {
+        self.inset
+    }
+
+    /// This shadow moved by `dx` and `dy`.
+    pub fn translated(&self, dx: Au, dy: Au) -> BoxShadow {
+        BoxShadow {
+            shape: self.shape.translated(dx, dy),
+            clip: self.clip.translated(dx, dy),
+            ..*self
+        }
+    }
+
+    /// The standard deviation of the Gaussian the shadow is blurred by.
+    pub fn sigma(&self) -> f32 {
+        self.blur_radius.to_f32_px() / 2.
+    }
+
+    /// The area the shadow paints within, in whole pixels.  The blur of an outer shadow reaches
+    /// beyond its shape, but is too faint to see more than three standard deviations away.
+    pub fn bounds(&self) -> Rect {
+        if self.inset {
+            return self.clip.rect;
+        }
+        let margin = Au::from_px(blur_margin(self.sigma()));
+        self.shape.rect.expanded_by(EdgeSizes {
+            left: margin,
+            right: margin,
+            top: margin,
+            bottom: margin,
+        })
+    }
+
+    /// How much of the color of the shadow is painted at `point`, from 0 to 1.
+    pub fn alpha_at(&self, point: Vector2F) -> f32 {
+        let shadow = blurred_coverage(&self.shape, self.sigma(), point);
+        let clip = (0.5 - signed_distance(&self.clip, point)).clamp(0., 1.);
+        if self.inset {
+            (1. - shadow) * clip
+        } else {
+            shadow * (1. - clip)
+        }
+    }
+
+    /// Paints the shadow into an image of its bounds, sampling it at the center of each pixel,
+    /// for painting backends that can't compute it themselves.
+    pub fn rasterize(&self) -> RgbaImage {
+        let bounds = self.bounds();
+        let (left, top) = (bounds.start_x.to_f32_px(), bounds.start_y.to_f32_px());
+        let width = bounds.width.to_nearest_px().max(0) as u32;
+        let height = bounds.height.to_nearest_px().max(0) as u32;
+        let color = self.color;
+        RgbaImage::from_fn(width, height, |x, y| {
+            let alpha = self.alpha_at(Vector2F::new(left + x as f32 + 0.5, top + y as f32 + 0.5));
+            Rgba([
+                color.red,
+                color.green,
+                color.blue,
+                (color.alpha as f32 * alpha).round() as u8,
+            ])
+        })
+    }
+}
+
+/// Resolves the outer shadows of `layout_box`, or its inset ones if `inset`, in the order they're
+/// painted in, which is the reverse of the order they're given in.  Shadows that wouldn't paint
+/// anything are left out.
+pub fn box_shadows(layout_box: &LayoutBox, scale_factor: f32, inset: bool) -> Vec<BoxShadow> {
+    let cvs = layout_box.computed_values();
+    let device = |length: CSSPixelLength| Au::from_f32_px(length.px() * scale_factor);
+    let casting_box = if inset {
+        layout_box.rounded_padding_box()
+    } else {
+        layout_box.rounded_border_box()
+    }
+    .snapped_to_pixels();
+    if casting_box.rect.width <= Au(0) || casting_box.rect.height <= Au(0) {
+        return Vec::new();
+    }
+    cvs.box_shadow
+        .0
+        .iter()
+        .rev()
+        .filter(|shadow| shadow.inset == inset && shadow.color.alpha > 0)
+        .filter_map(|shadow| {
+            // Inset shadows are spread inwards, so their shape shrinks.
+            let spread = if inset {
+                -device(shadow.spread)
+            } else {
+                device(shadow.spread)
+            };
+            let shape = spread_by(casting_box, spread)
+                .translated(device(shadow.offset_x), device(shadow.offset_y))
+                .snapped_to_pixels();
+            // An empty shape casts no outer shadow, but leaves the whole padding box shaded by an
+            // inset one.
+            if !inset && (shape.rect.width <= Au(0) || shape.rect.height <= Au(0)) {
+                return None;
+            }
+            Some(BoxShadow::new(
+                shape,
+                casting_box,
+                shadow.color,
+                device(shadow.blur_radius),
+                inset,
+            ))
+        })
+        .collect()
+}
+
+/// Expands `rrect` by `spread` on every side, or shrinks it if `spread` is negative.
+fn spread_by(rrect: RoundedRect, spread: Au) -> RoundedRect {
+    let rect = Rect {
+        start_x: rrect.rect.start_x - spread,
+        start_y: rrect.rect.start_y - spread,
+        width: (rrect.rect.width + spread * 2).max(Au(0)),
+        height: (rrect.rect.height + spread * 2).max(Au(0)),
+    };
+    let spread_px = spread.to_f32_px();
+    let corner = |radius: CornerRadius| CornerRadius {
+        horizontal: Au::from_f32_px(spread_radius(radius.horizontal.to_f32_px(), spread_px)),
+        vertical: Au::from_f32_px(spread_radius(radius.vertical.to_f32_px(), spread_px)),
+    };
+    RoundedRect::new(
+        rect,
+        CornerRadii {
+            top_left: corner(rrect.radii.top_left),
+            top_right: corner(rrect.radii.top_right),
+            bottom_right: corner(rrect.radii.bottom_right),
+            bottom_left: corner(rrect.radii.bottom_left),
+        },
+    )
+}
+
+/// The radius of a corner of a shadow spread by `spread`, whose box has a corner of `radius`.
+/// Radii grow by the spread, except that those smaller than it grow by less, so that square and
+/// slightly rounded corners don't become much rounder.  Shrinking radii stop at zero.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#shadow-shape
+fn spread_radius(radius: f32, spread: f32) -> f32 {
+    if spread < 0. {
+        (radius + spread).max(0.)
+    } else if radius >= spread {
+        radius + spread
+    } else {
+        radius + spread * (1. + (radius / spread - 1.).powi(3))
+    }
+}
+
+/// How far from the edges of a shape blurred by a Gaussian of standard deviation `sigma` the blur
+/// is painted, in whole pixels.
+pub fn blur_margin(sigma: f32) -> i32 {
+    (3. * sigma).ceil().max(0.) as i32
+}
+
+/// Blurs the `width` by `height` coverage mask `pixels`, whose rows start `stride` bytes apart, by
+/// a Gaussian of standard deviation `sigma`.  The blur is separable, so the mask is blurred along
+/// its rows and then along its columns, with the coverage beyond its edges taken to be zero.
+pub fn blur_mask(pixels: &mut [u8], width: usize, height: usize, stride: usize, sigma: f32) {
+    if sigma <= 0. || width == 0 || height == 0 {
+        return;
+    }
+    let radius = blur_margin(sigma) as isize;
+    let mut kernel = (-radius..=radius)
+        .map(|offset| gaussian(offset as f32, sigma))
+        .collect::<Vec<_>>();
+    let total = kernel.iter().sum::<f32>();
+    for weight in &mut kernel {
+        *weight /= total;
+    }
+    // Sums the weighted samples `sample` gives at each offset from `center`, along a line `len`
+    // long.
+    let convolve = |center: usize, len: usize, sample: &dyn Fn(usize) -> f32| {
+        let mut sum = 0.;
+        for (index, weight) in kernel.iter().enumerate() {
+            let at = center as isize + index as isize - radius;
+            if at >= 0 && (at as usize) < len {
+                sum += weight * sample(at as usize);
+            }
+        }
+        sum
+    };
+    let mut rows = vec![0f32; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            rows[y * width + x] = convolve(x, width, &|at| pixels[y * stride + at] as f32);
+        }
+    }
+    for y in 0..height {
+        for x in 0..width {
+            let blurred = convolve(y, height, &|at| rows[at * width + x]);
+            pixels[y * stride + x] = blurred.round().min(255.) as u8;
+        }
+    }
+}
+
+/// How much of `shape` is covered at `point` once blurred by a Gaussian of standard deviation
+/// `sigma`.  Shapes that aren't blurred are anti-aliased the way rounded rectangles are painted.
+fn blurred_coverage(shape: &RoundedRect, sigma: f32, point: Vector2F) -> f32 {
+    if shape.rect.width <= Au(0) || shape.rect.height <= Au(0) {
+        return 0.;
+    }
+    if sigma <= 0. {
+        return (0.5 - signed_distance(shape, point)).clamp(0., 1.);
+    }
+    // Rows further than three standard deviations away contribute next to nothing.
+    let start = (point.y() - 3. * sigma).max(shape.rect.start_y.to_f32_px());
+    let end = (point.y() + 3. * sigma).min(shape.rect.end_y().to_f32_px());
+    if start >= end {
+        return 0.;
+    }
+    let step = (end - start) / BLUR_SAMPLES as f32;
+    let scale = 1. / (std::f32::consts::SQRT_2 * sigma);
+    let mut coverage = 0.;
+    for sample in 0..BLUR_SAMPLES {
+        let y = start + step * (sample as f32 + 0.5);
+        let (left, right) = span_at(shape, y);
+        let along_row = 0.5 * (erf((point.x() - left) * scale) - erf((point.x() - right) * scale));
+        coverage += along_row * gaussian(point.y() - y, sigma) * step;
+    }
+    coverage.clamp(0., 1.)
+}
+
+/// The left and right edges of `rrect` along the row at `y`, which is within it vertically.  The
+/// edges curve inwards at rounded corners.
+fn span_at(rrect: &RoundedRect, y: f32) -> (f32, f32) {
+    let rect = rrect.rect;
+    let (top, bottom) = (rect.start_y.to_f32_px(), rect.end_y().to_f32_px());
+    let (left_radius, right_radius, from_edge) = if y < (top + bottom) / 2. {
+        (rrect.radii.top_left, rrect.radii.top_right, y - top)
+    } else {
+        (
+            rrect.radii.bottom_left,
+            rrect.radii.bottom_right,
+            bottom - y,
+        )
+    };
+    let indent = |radius: CornerRadius| {
+        let (horizontal, vertical) = (radius.horizontal.to_f32_px(), radius.vertical.to_f32_px());
+        if radius.is_zero() || from_edge >= vertical {
+            return 0.;
+        }
+        let above_center = (vertical - from_edge) / vertical;
+        horizontal * (1. - (1. - above_center * above_center).max(0.).sqrt())
+    };
+    (
+        rect.start_x.to_f32_px() + indent(left_radius),
+        rect.end_x().to_f32_px() - indent(right_radius),
+    )
+}
+
+/// The approximate signed distance from `point` to the edge of `rrect`, which is negative inside
+/// it.  This is the same as the distance `shader_src/rounded_rect.frag` anti-aliases edges by.
+fn signed_distance(rrect: &RoundedRect, point: Vector2F) -> f32 {
+    let rect = rrect.rect;
+    let (left, top, right, bottom) = (
+        rect.start_x.to_f32_px(),
+        rect.start_y.to_f32_px(),
+        rect.end_x().to_f32_px(),
+        rect.end_y().to_f32_px(),
+    );
+    let (is_left, is_top) = (
+        point.x() < (left + right) / 2.,
+        point.y() < (top + bottom) / 2.,
+    );
+    let radius = match (is_left, is_top) {
+        (true, true) => rrect.radii.top_left,
+        (false, true) => rrect.radii.top_right,
+        (false, false) => rrect.radii.bottom_right,
+        (true, false) => rrect.radii.bottom_left,
+    };
+    let (radius_x, radius_y) = (radius.horizontal.to_f32_px(), radius.vertical.to_f32_px());
+    // How far `point` is past the center of the ellipse of its corner, towards the corner.
+    let qx = if is_left {
+        left + radius_x - point.x()
+    } else {
+        point.x() - (right - radius_x)
+    };
+    let qy = if is_top {
+        top + radius_y - point.y()
+    } else {
+        point.y() - (bottom - radius_y)
+    };
+    if !radius.is_zero() && qx > 0. && qy > 0. {
+        // The distance to an ellipse, approximated by its implicit function divided by the length
+        // of its gradient.
+        let k0 = Vector2F::new(qx / radius_x, qy / radius_y).length();
+        let k1 = Vector2F::new(qx / (radius_x * radius_x), qy / (radius_y * radius_y)).length();
+        return k0 * (k0 - 1.) / k1;
+    }
+    (left - point.x())
+        .max(point.x() - right)
+        .max(top - point.y())
+        .max(point.y() - bottom)
+}
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    (-(x * x) / (2. * sigma * sigma)).exp() / ((2. * std::f32::consts::PI).sqrt() * sigma)
+}
+
+/// An approximation of the error function, which is the integral of a Gaussian, within 5e-4.
+///
+/// Abramowitz and Stegun, Handbook of Mathematical Functions, formula 7.1.27.
+fn erf(x: f32) -> f32 {
+    let a = x.abs();
+    let t = 1. + (0.278_393 + (0.230_389 + 0.078_108 * a * a) * a) * a;
+    let t2 = t * t;
+    (1. - 1. / (t2 * t2)).copysign(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            start_x: Au::from_px(x),
+            start_y: Au::from_px(y),
+            width: Au::from_px(width),
+            height: Au::from_px(height),
+        }
+    }
+
+    fn black_shadow(shape: Rect, clip: Rect, blur_radius: i32, inset: bool) -> BoxShadow {
+        BoxShadow::new(
+            shape.into(),
+            clip.into(),
+            RGBA::new(0, 0, 0, 255),
+            Au::from_px(blur_radius),
+            inset,
+        )
+    }
+
+    #[test]
+    fn outer_shadows_are_not_painted_under_their_box() {
+        let shadow = black_shadow(rect(10, 10, 100, 100), rect(0, 0, 100, 100), 0, false);
+        assert_eq!(shadow.alpha_at(Vector2F::new(50.5, 50.5)), 0.);
+        assert_eq!(shadow.alpha_at(Vector2F::new(105.5, 105.5)), 1.);
+        assert_eq!(shadow.alpha_at(Vector2F::new(112.5, 50.5)), 0.);
+        assert_eq!(shadow.bounds(), rect(10, 10, 100, 100));
+    }
+
+    #[test]
+    fn blurred_edges_are_half_covered() {
+        let shadow = black_shadow(rect(0, 0, 100, 100), rect(-50, -50, 10, 10), 10, false);
+        assert_eq!(shadow.bounds(), rect(-15, -15, 130, 130));
+        let at = |x: f32| shadow.alpha_at(Vector2F::new(x, 50.));
+        assert!((at(100.) - 0.5).abs() < 0.01);
+        assert!(at(50.) > 0.99);
+        assert!(at(90.) > at(100.) && at(100.) > at(110.));
+        assert!(at(115.) < 0.01);
+    }
+
+    #[test]
+    fn inset_shadows_shade_the_box_outside_their_shape() {
+        let shadow = black_shadow(rect(20, 0, 100, 100), rect(0, 0, 100, 100), 0, true);
+        assert_eq!(shadow.alpha_at(Vector2F::new(10.5, 50.5)), 1.);
+        assert_eq!(shadow.alpha_at(Vector2F::new(50.5, 50.5)), 0.);
+        assert_eq!(shadow.alpha_at(Vector2F::new(-10.5, 50.5)), 0.);
+        assert_eq!(shadow.bounds(), rect(0, 0, 100, 100));
+    }
+
+    #[test]
+    fn spread_keeps_square_corners_square() {
+        assert_eq!(spread_radius(0., 10.), 0.);
+        assert_eq!(spread_radius(20., 10.), 30.);
+        assert!(spread_radius(5., 10.) > 5. && spread_radius(5., 10.) < 15.);
+        assert_eq!(spread_radius(5., -10.), 0.);
+    }
+
+    #[test]
+    fn blurred_masks_spread_out_their_coverage() {
+        let (width, height) = (9, 9);
+        let center = 4 * width + 4;
+        let mut pixels = vec![0; width * height];
+        pixels[center] = 255;
+        blur_mask(&mut pixels, width, height, width, 1.);
+        assert!(pixels[center] < 255);
+        assert!(pixels[center - 1] > 0 && pixels[center - 1] == pixels[center + 1]);
+        assert!(pixels[center - 1] > pixels[center - 2]);
+        assert_eq!(pixels[center - width], pixels[center + width]);
+        // The total coverage is kept, give or take rounding.
+        let total = pixels.iter().map(|pixel| *pixel as u32).sum::<u32>();
+        assert!((total as i32 - 255).abs() < 16, "total coverage {}", total);
+    }
+}
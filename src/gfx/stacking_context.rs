@@ -0,0 +1,216 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/zindex.html
+//  * https://www.w3.org/TR/CSS2/visuren.html#z-index
+//  * https://drafts.csswg.org/css-position-3/#painting-order
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::rect::RoundedRect;
+use crate::style::values::computed::{Opacity, Position, ZIndex};
+use crate::style::values::CSSInteger;
+
+/// A box that's painted as a unit in step 3, 8, or 9 of the painting order of the stacking
+/// context it's part of, along with the descendants that are painted the same way within it.
+///
+/// This is either a box that establishes a stacking context, or a positioned box with `z-index:
+/// auto`, which is painted as if it established one, except that its positioned descendants and
+/// the stacking contexts within it are part of the enclosing stacking context instead.  Such
+/// boxes have no descendant layers of their own.
+#[derive(Debug)]
+pub struct StackingContext<'a> {
+    pub layout_box: &'a LayoutBox,
+    /// The level this is painted at within the enclosing stacking context.
+    pub z_index: CSSInteger,
+    /// The overflow clips of the ancestors of this box within the enclosing stacking context that
+    /// apply to it, outermost first.
+    pub clips: Vec<RoundedRect>,
+    /// The descendant layers with negative z-indices, painted in step 3, in order of z-index and
+    /// then tree order.
+    pub negative_z: Vec<StackingContext<'a>>,
+    /// The descendant layers with z-indices of `auto` or 0, painted in step 8, in tree order.
+    pub zero_z: Vec<StackingContext<'a>>,
+    /// The descendant layers with positive z-indices, painted in step 9, in order of z-index and
+    /// then tree order.
+    pub positive_z: Vec<StackingContext<'a>>,
+}
+
+/// How a box is painted relative to its parent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaintKind {
+    /// The box is painted as part of its parent, in steps 4 to 7 of the painting order.
+    InFlow,
+    /// The box is a float, painted atomically in step 5.
+    Float,
+    /// The box is an inline-level box, or a flex or grid item, painted atomically in step 7.
+    Atomic,
+    /// The box is painted in its own layer of the enclosing stacking context.  See
+    /// `StackingContext`.
+    Layer,
+}
+
+impl<'a> StackingContext<'a> {
+    /// Builds the tree of stacking contexts for the layout tree rooted at `layout_box`, which is
+    /// treated as establishing the root stacking context.
+    pub fn new(layout_box: &'a LayoutBox) -> StackingContext<'a> {
+        StackingContext::new_with_clips(layout_box, 0, Vec::new(), true)
+    }
+
+    fn new_with_clips(
+        layout_box: &'a LayoutBox,
+        z_index: CSSInteger,
+        clips: Vec<RoundedRect>,
+        establishes_stacking_context: bool,
+    ) -> StackingContext<'a> {
+        let mut stacking_context = StackingContext {
+            layout_box,
+            z_index,
+            clips,
+            negative_z: Vec::new(),
+            zero_z: Vec::new(),
+            positive_z: Vec::new(),
+        };
+        if establishes_stacking_context {
+            let child_clips = layout_box.overflow_clip().into_iter().collect::<Vec<_>>();
+            // Absolutely positioned children whose containing block is outside of this stacking
+            // context aren't clipped by this box.
+            let abspos_child_clips = if layout_box.is_positioned() {
+                child_clips.clone()
+            } else {
+                Vec::new()
+            };
+            stacking_context.collect_layers(layout_box, &child_clips, &abspos_child_clips);
+            // Sorting is stable, so layers with the same z-index stay in tree order.
+            stacking_context
+                .negative_z
+                .sort_by_key(|layer| layer.z_index);
+            stacking_context
+                .positive_z
+                .sort_by_key(|layer| layer.z_index);
+        }
+        stacking_context
+    }
+
+    /// Adds the layers among the descendants of `parent` to this stacking context.  `clips` are
+    /// the overflow clips that apply to the in-flow children of `parent`, and `abspos_clips` those
+    /// that apply to its absolutely positioned children.
+    fn collect_layers(
+        &mut self,
+        parent: &'a LayoutBox,
+        clips: &[RoundedRect],
+        abspos_clips: &[RoundedRect],
+    ) {
+        for child in parent.children() {
+            let child_clips = match child.position() {
+                Position::Absolute => abspos_clips.to_vec(),
+                Position::Fixed => Vec::new(),
+                _ => clips.to_vec(),
+            };
+            if paint_kind(child, parent) == PaintKind::Layer {
+                let z_index = z_index(child, parent);
+                let establishes_stacking_context = establishes_stacking_context(child, parent);
+                let layer = StackingContext::new_with_clips(
+                    child,
+                    z_index.unwrap_or(0),
+                    child_clips.clone(),
+                    establishes_stacking_context,
+                );
+                match layer.z_index {
+                    z if z < 0 => self.negative_z.push(layer),
+                    0 => self.zero_z.push(layer),
+                    _ => self.positive_z.push(layer),
+                }
+                if establishes_stacking_context {
+                    continue;
+                }
+            }
+            let mut grandchild_clips = child_clips;
+            grandchild_clips.extend(child.overflow_clip());
+            let grandchild_abspos_clips = if child.is_positioned() {
+                &grandchild_clips
+            } else {
+                abspos_clips
+            };
+            self.collect_layers(child, &grandchild_clips, grandchild_abspos_clips);
+        }
+    }
+}
+
+/// Determines how `layout_box`, a child of `parent`, is painted.
+pub fn paint_kind(layout_box: &LayoutBox, parent: &LayoutBox) -> PaintKind {
+    if is_root_element(layout_box)
+        || layout_box.is_positioned()
+        || establishes_stacking_context(layout_box, parent)
+    {
+        PaintKind::Layer
+    } else if layout_box.is_floated() {
+        PaintKind::Float
+    } else if is_container_item(layout_box, parent)
+        || matches!(
+            layout_box.box_type(),
+            BoxType::InlineBlock
+                | BoxType::InlineFlex
+                | BoxType::InlineGrid
+                | BoxType::InlineTableWrapper
+        )
+    {
+        PaintKind::Atomic
+    } else {
+        PaintKind::InFlow
+    }
+}
+
+/// Determines if `layout_box` was generated by the root element, which establishes the root
+/// stacking context.  The doctype is a child of the document too, so its box has to be told apart
+/// from the root element's, which is never an inline box.
+pub fn is_root_element(layout_box: &LayoutBox) -> bool {
+    layout_box.is_root() && !layout_box.is_anonymous() && layout_box.box_type() != BoxType::Inline
+}
+
+/// Determines if `layout_box`, a child of `parent`, establishes a stacking context, which is the
+/// case for the root element, for positioned boxes and flex and grid items with an integer `z-index`, and for partially
+/// transparent boxes.
+///
+/// https://drafts.csswg.org/css-position-3/#stacking-context
+fn establishes_stacking_context(layout_box: &LayoutBox, parent: &LayoutBox) -> bool {
+    is_root_element(layout_box)
+        || z_index(layout_box, parent).is_some()
+        || !opacity(layout_box).is_opaque()
+}
+
+/// Gets the used `z-index` of `layout_box`, a child of `parent`, or `None` if it's `auto` or
+/// doesn't apply.
+fn z_index(layout_box: &LayoutBox, parent: &LayoutBox) -> Option<CSSInteger> {
+    if !has_own_style(layout_box)
+        || !(layout_box.is_positioned() || is_container_item(layout_box, parent))
+    {
+        return None;
+    }
+    match layout_box.computed_values().z_index {
+        ZIndex::Auto => None,
+        ZIndex::Integer(z_index) => Some(z_index),
+    }
+}
+
+/// Gets the `opacity` of `layout_box`, which is fully opaque for boxes it doesn't apply to.
+pub fn opacity(layout_box: &LayoutBox) -> Opacity {
+    if has_own_style(layout_box) {
+        layout_box.computed_values().opacity
+    } else {
+        Opacity::initial_value()
+    }
+}
+
+/// Determines if the style of `layout_box` is its own, rather than that of the box it was
+/// generated for.  Anonymous boxes borrow the style of their parent, and table boxes share theirs
+/// with the table wrapper box around them, which takes care of painting it as a unit.
+fn has_own_style(layout_box: &LayoutBox) -> bool {
+    !layout_box.is_anonymous() && layout_box.box_type() != BoxType::Table
+}
+
+/// Determines if `layout_box` is an in-flow child of the flex or grid container `parent`.
+fn is_container_item(layout_box: &LayoutBox, parent: &LayoutBox) -> bool {
+    !layout_box.is_anonymous()
+        && !layout_box.is_absolutely_positioned()
+        && matches!(
+            parent.box_type(),
+            BoxType::Flex | BoxType::InlineFlex | BoxType::Grid | BoxType::InlineGrid
+        )
+}
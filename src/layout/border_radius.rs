@@ -0,0 +1,65 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-backgrounds-3/#border-radius
+//  * https://drafts.csswg.org/css-backgrounds-3/#corner-overlap
+use crate::layout::layout_box::LayoutBox;
+use crate::layout::rect::{CornerRadii, CornerRadius, RoundedRect};
+use crate::layout::resolve_length_percentage;
+use crate::style::values::computed::BorderCornerRadius;
+
+impl LayoutBox {
+    /// The radii of the outer corners of the border of this box, clamped so they fit within its
+    /// border box.  Set once layout is complete.
+    pub fn border_radii(&self) -> CornerRadii {
+        self.border_radii
+    }
+
+    /// The border box of this box, rounded by its `border-radius`.
+    pub fn rounded_border_box(&self) -> RoundedRect {
+        RoundedRect::new(self.physical_dimensions().border_box(), self.border_radii)
+    }
+
+    /// The padding box of this box, whose corners are rounded by the inner radii of its border.
+    pub fn rounded_padding_box(&self) -> RoundedRect {
+        let d = self.physical_dimensions();
+        RoundedRect::new(d.padding_box(), self.border_radii.shrunk_by(d.border))
+    }
+
+    /// The content box of this box, whose corners are rounded by the radii of its padding box
+    /// reduced by its padding.
+    pub fn rounded_content_box(&self) -> RoundedRect {
+        let d = self.physical_dimensions();
+        RoundedRect::new(
+            d.content,
+            self.rounded_padding_box().radii.shrunk_by(d.padding),
+        )
+    }
+
+    /// Resolves the `border-radius` of this box against the size of its border box, which must
+    /// already be laid out.  Percentages of horizontal radii are of the width of the border box,
+    /// and those of vertical radii of its height.  Anonymous boxes have square corners.
+    pub(super) fn resolve_border_radii(&mut self, scale_factor: f32) {
+        if self.is_anonymous() {
+            self.border_radii = CornerRadii::default();
+            return;
+        }
+        let border_box = self.physical_dimensions().border_box();
+        let resolve = |radius: BorderCornerRadius| CornerRadius {
+            horizontal: resolve_length_percentage(
+                radius.horizontal,
+                border_box.width,
+                scale_factor,
+            ),
+            vertical: resolve_length_percentage(radius.vertical, border_box.height, scale_factor),
+        };
+        let radii = {
+            let cvs = self.computed_values();
+            CornerRadii {
+                top_left: resolve(cvs.border_top_left_radius),
+                top_right: resolve(cvs.border_top_right_radius),
+                bottom_right: resolve(cvs.border_bottom_right_radius),
+                bottom_left: resolve(cvs.border_bottom_left_radius),
+            }
+        };
+        self.border_radii = radii.clamped_to(border_box);
+    }
+}
@@ -1,9 +1,8 @@
 use crate::layout::rect::{EdgeSizes, Rect};
 use crate::layout::{BoxComponent, LogicalDirection};
-use crate::style::values::computed::length::CSSPixelLength;
 use crate::style::values::computed::{Direction, WritingMode};
-use crate::style::values::CSSFloat;
 use crate::Side;
+use app_units::Au;
 
 #[derive(Debug, Clone, Copy)]
 pub struct LogicalDimensions {
@@ -29,7 +28,7 @@ impl LogicalDimensions {
         self.dimensions
     }
 
-    pub fn border_size(self, side: Side) -> CSSPixelLength {
+    pub fn border_size(self, side: Side) -> Au {
         self.dimensions.border_size(side)
     }
 
@@ -47,7 +46,17 @@ impl LogicalDimensions {
         self.dimensions.scale_edges_by(scale_factor)
     }
 
-    pub fn set_inline_start_coord(&mut self, val: CSSFloat) {
+    pub fn get_inline_start_coord(&self) -> Au {
+        match self.writing_mode {
+            WritingMode::HorizontalTb => self.dimensions.content.start_x,
+            WritingMode::VerticalRl
+            | WritingMode::SidewaysRl
+            | WritingMode::VerticalLr
+            | WritingMode::SidewaysLr => self.dimensions.content.start_y,
+        }
+    }
+
+    pub fn set_inline_start_coord(&mut self, val: Au) {
         match self.writing_mode {
             WritingMode::HorizontalTb => self.set_start_x(val),
             WritingMode::VerticalRl
@@ -57,19 +66,29 @@ impl LogicalDimensions {
         }
     }
 
-    pub fn set_phys_height(&mut self, val: CSSPixelLength) {
+    pub fn set_phys_height(&mut self, val: Au) {
         self.dimensions.content.height = val;
     }
 
-    pub fn set_phys_width(&mut self, val: CSSPixelLength) {
+    pub fn set_phys_width(&mut self, val: Au) {
         self.dimensions.content.width = val;
     }
 
-    pub fn set_start_x(&mut self, val: CSSFloat) {
+    pub fn set_start_x(&mut self, val: Au) {
         self.dimensions.content.start_x = val;
     }
 
-    pub fn set_block_start_coord(&mut self, val: CSSFloat) {
+    pub fn get_block_start_coord(&self) -> Au {
+        match self.writing_mode {
+            WritingMode::HorizontalTb => self.dimensions.content.start_y,
+            WritingMode::VerticalRl
+            | WritingMode::SidewaysRl
+            | WritingMode::VerticalLr
+            | WritingMode::SidewaysLr => self.dimensions.content.start_x,
+        }
+    }
+
+    pub fn set_block_start_coord(&mut self, val: Au) {
         match self.writing_mode {
             WritingMode::HorizontalTb => self.set_start_y(val),
             WritingMode::VerticalRl
@@ -79,23 +98,37 @@ impl LogicalDimensions {
         }
     }
 
-    pub fn set_start_y(&mut self, val: CSSFloat) {
+    pub fn set_start_y(&mut self, val: Au) {
         self.dimensions.content.start_y = val;
     }
 
-    pub fn get_content_block_size(&self) -> CSSPixelLength {
+    /// Moves the content area of these dimensions by the given physical offsets.
+    pub fn translate(&mut self, dx: Au, dy: Au) {
+        self.dimensions.content.start_x += dx;
+        self.dimensions.content.start_y += dy;
+    }
+
+    /// Gets the sum of the margin, border, and padding on the `dir` side of the box, i.e. the
+    /// distance between the margin edge and the content edge.
+    pub fn get_edges_sum(&self, dir: LogicalDirection) -> Au {
+        self.get(dir, BoxComponent::Margin)
+            + self.get(dir, BoxComponent::Border)
+            + self.get(dir, BoxComponent::Padding)
+    }
+
+    pub fn get_content_block_size(&self) -> Au {
         self.get_block_size(None)
     }
 
-    pub fn padding_box_block_size(&self) -> CSSPixelLength {
+    pub fn padding_box_block_size(&self) -> Au {
         self.get_block_size(Some(BoxComponent::Padding))
     }
 
-    pub fn border_box_block_size(&self) -> CSSPixelLength {
+    pub fn border_box_block_size(&self) -> Au {
         self.get_block_size(Some(BoxComponent::Border))
     }
 
-    pub fn margin_box_block_size(&self) -> CSSPixelLength {
+    pub fn margin_box_block_size(&self) -> Au {
         self.get_block_size(Some(BoxComponent::Margin))
     }
 
@@ -108,7 +141,7 @@ impl LogicalDimensions {
     /// If `None`, only the block size of the box content will be returned.
     ///
     /// Note that the block size is also referred to as the logical height.
-    fn get_block_size(&self, expanded_by: Option<BoxComponent>) -> CSSPixelLength {
+    fn get_block_size(&self, expanded_by: Option<BoxComponent>) -> Au {
         match self.writing_mode {
             WritingMode::HorizontalTb => match expanded_by {
                 None => self.dimensions.content.height,
@@ -128,7 +161,7 @@ impl LogicalDimensions {
         }
     }
 
-    pub fn set_block_size(&mut self, val: CSSPixelLength) {
+    pub fn set_block_size(&mut self, val: Au) {
         match self.writing_mode {
             WritingMode::HorizontalTb => self.dimensions.content.height = val,
             WritingMode::VerticalRl
@@ -139,17 +172,37 @@ impl LogicalDimensions {
     }
 
     /// Note that the inline size is also known as the logical width.
-    pub fn get_inline_size(&self) -> CSSPixelLength {
+    pub fn get_inline_size(&self) -> Au {
+        self.get_inline_size_expanded_by(None)
+    }
+
+    pub fn margin_box_inline_size(&self) -> Au {
+        self.get_inline_size_expanded_by(Some(BoxComponent::Margin))
+    }
+
+    /// Gets the inline size of these dimensions, optionally expanded by the given box component
+    /// (and all components inside of it).  See `get_block_size` for the block axis equivalent.
+    fn get_inline_size_expanded_by(&self, expanded_by: Option<BoxComponent>) -> Au {
         match self.writing_mode {
-            WritingMode::HorizontalTb => self.dimensions.content.width,
+            WritingMode::HorizontalTb => match expanded_by {
+                None => self.dimensions.content.width,
+                Some(BoxComponent::Padding) => self.dimensions.padding_box().width,
+                Some(BoxComponent::Border) => self.dimensions.border_box().width,
+                Some(BoxComponent::Margin) => self.dimensions.margin_box().width,
+            },
             WritingMode::VerticalRl
             | WritingMode::SidewaysRl
             | WritingMode::VerticalLr
-            | WritingMode::SidewaysLr => self.dimensions.content.height,
+            | WritingMode::SidewaysLr => match expanded_by {
+                None => self.dimensions.content.height,
+                Some(BoxComponent::Padding) => self.dimensions.padding_box().height,
+                Some(BoxComponent::Border) => self.dimensions.border_box().height,
+                Some(BoxComponent::Margin) => self.dimensions.margin_box().height,
+            },
         }
     }
 
-    pub fn set_inline_size(&mut self, val: CSSPixelLength) {
+    pub fn set_inline_size(&mut self, val: Au) {
         match self.writing_mode {
             WritingMode::HorizontalTb => self.dimensions.content.width = val,
             WritingMode::VerticalRl
@@ -159,143 +212,39 @@ impl LogicalDimensions {
         }
     }
 
-    // Make sure the `get` and `set` tables stay in sync.  There's probably a better way to share
-    // the lookup logic between get and set operations, but copy-paste will do for now.
-    pub fn get(&self, dir: LogicalDirection, box_component: BoxComponent) -> CSSPixelLength {
-        // Maps to this table: https://drafts.csswg.org/css-writing-modes-4/#logical-to-physical
-        match (self.writing_mode, self.direction, dir) {
-            (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Ltr,
-                LogicalDirection::InlineStart,
-            )
-            | (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Rtl,
-                LogicalDirection::InlineEnd,
-            )
-            | (WritingMode::SidewaysLr, Direction::Ltr, LogicalDirection::InlineEnd)
-            | (WritingMode::SidewaysLr, Direction::Rtl, LogicalDirection::InlineStart)
-            | (WritingMode::HorizontalTb, _, LogicalDirection::BlockStart) => match box_component {
-                BoxComponent::Border => self.dimensions.border.top,
-                BoxComponent::Margin => self.dimensions.margin.top,
-                BoxComponent::Padding => self.dimensions.padding.top,
-            },
-            (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Ltr,
-                LogicalDirection::InlineEnd,
-            )
-            | (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Rtl,
-                LogicalDirection::InlineStart,
-            )
-            | (WritingMode::SidewaysLr, Direction::Ltr, LogicalDirection::InlineStart)
-            | (WritingMode::SidewaysLr, Direction::Rtl, LogicalDirection::InlineEnd)
-            | (WritingMode::HorizontalTb, _, LogicalDirection::BlockEnd) => match box_component {
-                BoxComponent::Border => self.dimensions.border.bottom,
-                BoxComponent::Margin => self.dimensions.margin.bottom,
-                BoxComponent::Padding => self.dimensions.padding.bottom,
-            },
-            (WritingMode::VerticalRl | WritingMode::SidewaysRl, _, LogicalDirection::BlockEnd)
-            | (
-                WritingMode::VerticalLr | WritingMode::SidewaysLr,
-                _,
-                LogicalDirection::BlockStart,
-            )
-            | (WritingMode::HorizontalTb, Direction::Ltr, LogicalDirection::InlineStart)
-            | (WritingMode::HorizontalTb, Direction::Rtl, LogicalDirection::InlineEnd) => {
-                match box_component {
-                    BoxComponent::Border => self.dimensions.border.left,
-                    BoxComponent::Margin => self.dimensions.margin.left,
-                    BoxComponent::Padding => self.dimensions.padding.left,
-                }
-            }
-            (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl,
-                _,
-                LogicalDirection::BlockStart,
-            )
-            | (WritingMode::VerticalLr | WritingMode::SidewaysLr, _, LogicalDirection::BlockEnd)
-            | (WritingMode::HorizontalTb, Direction::Ltr, LogicalDirection::InlineEnd)
-            | (WritingMode::HorizontalTb, Direction::Rtl, LogicalDirection::InlineStart) => {
-                match box_component {
-                    BoxComponent::Border => self.dimensions.border.right,
-                    BoxComponent::Margin => self.dimensions.margin.right,
-                    BoxComponent::Padding => self.dimensions.padding.right,
-                }
-            }
-        }
+    /// Maps `dir` to the physical side of the box it corresponds to.
+    fn physical_side(&self, dir: LogicalDirection) -> Side {
+        let flow_relative_side = match dir {
+            LogicalDirection::InlineStart => Side::Left,
+            LogicalDirection::InlineEnd => Side::Right,
+            LogicalDirection::BlockStart => Side::Top,
+            LogicalDirection::BlockEnd => Side::Bottom,
+        };
+        self.writing_mode
+            .physical_side(self.direction, flow_relative_side)
     }
 
-    pub fn set(&mut self, dir: LogicalDirection, box_component: BoxComponent, val: CSSPixelLength) {
-        // Maps to this table: https://drafts.csswg.org/css-writing-modes-4/#logical-to-physical
-        match (self.writing_mode, self.direction, dir) {
-            (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Ltr,
-                LogicalDirection::InlineStart,
-            )
-            | (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Rtl,
-                LogicalDirection::InlineEnd,
-            )
-            | (WritingMode::SidewaysLr, Direction::Ltr, LogicalDirection::InlineEnd)
-            | (WritingMode::SidewaysLr, Direction::Rtl, LogicalDirection::InlineStart)
-            | (WritingMode::HorizontalTb, _, LogicalDirection::BlockStart) => match box_component {
-                BoxComponent::Border => self.dimensions.border.top = val,
-                BoxComponent::Margin => self.dimensions.margin.top = val,
-                BoxComponent::Padding => self.dimensions.padding.top = val,
-            },
-            (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Ltr,
-                LogicalDirection::InlineEnd,
-            )
-            | (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl | WritingMode::VerticalLr,
-                Direction::Rtl,
-                LogicalDirection::InlineStart,
-            )
-            | (WritingMode::SidewaysLr, Direction::Ltr, LogicalDirection::InlineStart)
-            | (WritingMode::SidewaysLr, Direction::Rtl, LogicalDirection::InlineEnd)
-            | (WritingMode::HorizontalTb, _, LogicalDirection::BlockEnd) => match box_component {
-                BoxComponent::Border => self.dimensions.border.bottom = val,
-                BoxComponent::Margin => self.dimensions.margin.bottom = val,
-                BoxComponent::Padding => self.dimensions.padding.bottom = val,
-            },
-            (WritingMode::VerticalRl | WritingMode::SidewaysRl, _, LogicalDirection::BlockEnd)
-            | (
-                WritingMode::VerticalLr | WritingMode::SidewaysLr,
-                _,
-                LogicalDirection::BlockStart,
-            )
-            | (WritingMode::HorizontalTb, Direction::Ltr, LogicalDirection::InlineStart)
-            | (WritingMode::HorizontalTb, Direction::Rtl, LogicalDirection::InlineEnd) => {
-                match box_component {
-                    BoxComponent::Border => self.dimensions.border.left = val,
-                    BoxComponent::Margin => self.dimensions.margin.left = val,
-                    BoxComponent::Padding => self.dimensions.padding.left = val,
-                }
-            }
-            (
-                WritingMode::VerticalRl | WritingMode::SidewaysRl,
-                _,
-                LogicalDirection::BlockStart,
-            )
-            | (WritingMode::VerticalLr | WritingMode::SidewaysLr, _, LogicalDirection::BlockEnd)
-            | (WritingMode::HorizontalTb, Direction::Ltr, LogicalDirection::InlineEnd)
-            | (WritingMode::HorizontalTb, Direction::Rtl, LogicalDirection::InlineStart) => {
-                match box_component {
-                    BoxComponent::Border => self.dimensions.border.right = val,
-                    BoxComponent::Margin => self.dimensions.margin.right = val,
-                    BoxComponent::Padding => self.dimensions.padding.right = val,
-                }
-            }
+    fn edges(&self, box_component: BoxComponent) -> &EdgeSizes {
+        match box_component {
+            BoxComponent::Border => &self.dimensions.border,
+            BoxComponent::Margin => &self.dimensions.margin,
+            BoxComponent::Padding => &self.dimensions.padding,
         }
     }
+
+    pub fn get(&self, dir: LogicalDirection, box_component: BoxComponent) -> Au {
+        self.edges(box_component).side(self.physical_side(dir))
+    }
+
+    pub fn set(&mut self, dir: LogicalDirection, box_component: BoxComponent, val: Au) {
+        let side = self.physical_side(dir);
+        let edges = match box_component {
+            BoxComponent::Border => &mut self.dimensions.border,
+            BoxComponent::Margin => &mut self.dimensions.margin,
+            BoxComponent::Padding => &mut self.dimensions.padding,
+        };
+        *edges.side_mut(side) = val;
+    }
 }
 
 /// https://www.w3.org/TR/2018/WD-css-box-3-20181218/#box-model
@@ -311,7 +260,7 @@ pub struct PhysicalDimensions {
 }
 
 impl PhysicalDimensions {
-    pub fn border_size(self, side: Side) -> CSSPixelLength {
+    pub fn border_size(self, side: Side) -> Au {
         match side {
             Side::Bottom => self.border.bottom,
             Side::Left => self.border.left,
@@ -331,7 +280,6 @@ impl PhysicalDimensions {
     }
 
     /// The area covered by the content area plus padding, borders, and margin.
-    // TODO: This will need to change when we implement margin collapsing: http://www.w3.org/TR/CSS2/box.html#collapsing-margins
     pub fn margin_box(self) -> Rect {
         self.border_box().expanded_by(self.margin)
     }
@@ -0,0 +1,993 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-flexbox-1/#layout-algorithm
+//  * https://drafts.csswg.org/css-align-3/#distribution-flex
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::{resolve_length_percentage, resolve_size, LogicalDirection};
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::{
+    AlignContent, AlignItems, AlignSelf, ComputedValues, FlexBasis, FlexWrap, JustifyContent,
+    Order, Size,
+};
+use crate::style::values::CSSFloat;
+use crate::Side;
+use app_units::Au;
+use std::ops::Range;
+
+/// The properties of a flex item that the flex layout algorithm depends on.
+#[derive(Clone, Copy, Debug)]
+struct FlexItemStyle {
+    order: Order,
+    grow: CSSFloat,
+    shrink: CSSFloat,
+    basis: FlexBasis,
+    align_self: AlignSelf,
+    inline_size: Size,
+    block_size: Size,
+    /// Whether the inline-start, inline-end, block-start, and block-end margins are `auto`.
+    auto_margins: [bool; 4],
+}
+
+impl FlexItemStyle {
+    fn of(layout_box: &LayoutBox) -> FlexItemStyle {
+        // Anonymous flex items take the initial values of every property, rather than the
+        // computed values of the flex container they're associated with.
+        if layout_box.box_type == BoxType::Anonymous {
+            return FlexItemStyle::from(&ComputedValues::default());
+        }
+        FlexItemStyle::from(&*layout_box.computed_values())
+    }
+}
+
+impl From<&ComputedValues> for FlexItemStyle {
+    fn from(cvs: &ComputedValues) -> Self {
+        let auto = LengthPercentageOrAuto::Auto;
+        FlexItemStyle {
+            order: cvs.order,
+            grow: cvs.flex_grow.0,
+            shrink: cvs.flex_shrink.0,
+            basis: cvs.flex_basis,
+            align_self: cvs.align_self,
+            inline_size: cvs.logical_width(),
+            block_size: cvs.logical_height(),
+            auto_margins: [
+                cvs.logical_margin(Side::Left) == auto,
+                cvs.logical_margin(Side::Right) == auto,
+                cvs.logical_margin(Side::Top) == auto,
+                cvs.logical_margin(Side::Bottom) == auto,
+            ],
+        }
+    }
+}
+
+/// How the main and cross axes of a flex container map to its logical axes.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#box-model
+#[derive(Clone, Copy, Debug)]
+struct FlexAxes {
+    /// Whether the main axis is the inline axis.
+    is_row: bool,
+    /// Whether main-start is on the inline-end or block-end side.
+    main_reverse: bool,
+    /// Whether cross-start is on the inline-end or block-end side.
+    cross_reverse: bool,
+}
+
+/// Free space distribution along an axis, with `start` and `end` resolved to the flex-relative
+/// start and end sides.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Distribution {
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Distribution {
+    /// Returns the space before the first subject and between each subject, given the amount of
+    /// free space in the container and the number of alignment subjects sharing it.
+    pub(super) fn offsets(self, free_space: Au, count: usize) -> (Au, Au) {
+        let zero = Au(0);
+        let count = count as i32;
+        match self {
+            Distribution::Start => (zero, zero),
+            Distribution::End => (free_space, zero),
+            Distribution::Center => (free_space / 2, zero),
+            // Each distributed alignment falls back to its default when there is no free space to
+            // distribute, or too few subjects to distribute it between.
+            Distribution::SpaceBetween if free_space > Au(0) && count > 1 => {
+                (zero, free_space / (count - 1))
+            }
+            Distribution::SpaceBetween => (zero, zero),
+            Distribution::SpaceAround if free_space > Au(0) => {
+                (free_space / count / 2, free_space / count)
+            }
+            Distribution::SpaceEvenly if free_space > Au(0) => {
+                (free_space / (count + 1), free_space / (count + 1))
+            }
+            Distribution::SpaceAround | Distribution::SpaceEvenly => (free_space / 2, zero),
+        }
+    }
+}
+
+/// A flex item that's in the process of being laid out.  Unless otherwise noted, sizes are
+/// content-box sizes, measured along the axes of the flex container.
+#[derive(Clone, Debug)]
+struct FlexItem {
+    /// The index of the item's box amongst the children of the flex container.
+    index: usize,
+    grow: CSSFloat,
+    shrink: CSSFloat,
+    align: AlignItems,
+    /// The sum of the item's margins, borders, and padding in the main axis, with `auto` margins
+    /// treated as zero.
+    main_edges: Au,
+    /// The sum of the item's margins, borders, and padding in the cross axis, with `auto` margins
+    /// treated as zero.
+    cross_edges: Au,
+    /// Whether the main-start, main-end, cross-start, and cross-end margins are `auto`.
+    auto_margins: [bool; 4],
+    /// Whether the item's cross size stretches to fill its flex line.
+    stretches: bool,
+    /// The inline size the item was stretched to before its main size was resolved, which only
+    /// happens for items of single-line column flex containers.
+    stretched_inline_size: Option<Au>,
+    flex_base_size: Au,
+    hypothetical_main_size: Au,
+    /// The minimum size of the item in the main axis, which is its automatic minimum size if its
+    /// `min-width` or `min-height` is `auto`.
+    min_main_size: Au,
+    /// The maximum size of the item in the main axis, if it has one.
+    max_main_size: Option<Au>,
+    target_main_size: Au,
+    frozen: bool,
+    cross_size: Au,
+    /// The distance from the cross-start margin edge of the item to its baseline.
+    ascent: Au,
+    /// The distance from the main-start edge of the container's content box to the main-start
+    /// margin edge of the item.
+    main_position: Au,
+    /// The distance from the cross-start edge of the container's content box to the cross-start
+    /// margin edge of the item.
+    cross_position: Au,
+}
+
+impl FlexItem {
+    fn outer_hypothetical_main_size(&self) -> Au {
+        self.hypothetical_main_size + self.main_edges
+    }
+
+    fn outer_target_main_size(&self) -> Au {
+        self.target_main_size + self.main_edges
+    }
+
+    /// Clamps `size` between the minimum and maximum main sizes of this item, with the minimum
+    /// winning over the maximum.
+    fn clamp_main_size(&self, size: Au) -> Au {
+        let size = match self.max_main_size {
+            Some(max) => size.min(max),
+            None => size,
+        };
+        size.max(self.min_main_size)
+    }
+
+    fn outer_cross_size(&self) -> Au {
+        self.cross_size + self.cross_edges
+    }
+
+    fn has_auto_cross_margin(&self) -> bool {
+        self.auto_margins[2] || self.auto_margins[3]
+    }
+}
+
+/// https://drafts.csswg.org/css-flexbox-1/#flex-lines
+#[derive(Clone, Debug)]
+struct FlexLine {
+    /// The range of items, in order-modified document order, on this line.
+    items: Range<usize>,
+    cross_size: Au,
+    /// The largest distance from the cross-start margin edge to the baseline of the items
+    /// participating in baseline alignment on this line.
+    max_ascent: Au,
+    /// The distance from the cross-start edge of the container's content box to this line.
+    cross_position: Au,
+}
+
+impl LayoutBox {
+    /// Assuming `self` is a block-level flex container, calculate the dimensions of this box and
+    /// its flex items.
+    pub(super) fn layout_flex(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) {
+        self.calculate_block_logical_width(containing_block, scale_factor);
+        self.calculate_block_position(containing_block, scale_factor);
+        let definite_block_size = self.resolve_container_sizes(containing_block, scale_factor);
+        self.layout_flex_items(definite_block_size, scale_factor);
+        self.apply_physical_properties(containing_block, scale_factor);
+    }
+
+    /// Prepares a flex or grid container for laying out its items, returning its definite block
+    /// size, if it has one.
+    pub(super) fn resolve_container_sizes(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> Option<Au> {
+        // Items are sized against the inline size of their container, so an explicit inline size
+        // needs to be applied now rather than in `apply_physical_properties`.
+        let specified_inline_size = self.specified_size(
+            self.writing_mode.is_horizontal(),
+            containing_block,
+            scale_factor,
+        );
+        if let Some(inline_size) = specified_inline_size {
+            let constraints = self.inline_size_constraints(Some(containing_block), scale_factor);
+            self.dimensions
+                .set_inline_size(constraints.clamp(inline_size));
+        }
+        self.specified_block_size(containing_block, scale_factor)
+    }
+
+    /// Runs the flex layout algorithm over the children of `self`, which must already have its
+    /// inline size and position set.  Sets the block size of `self`, which is given by
+    /// `definite_block_size` if it's `Some`.
+    ///
+    /// Layout is done in terms of the logical axes of the container.
+    /// TODO: Flex items with a writing mode orthogonal to their container aren't supported.
+    ///
+    /// https://drafts.csswg.org/css-flexbox-1/#layout-algorithm
+    pub(super) fn layout_flex_items(&mut self, definite_block_size: Option<Au>, scale_factor: f32) {
+        let zero = Au(0);
+        let (axes, single_line, justify_content, align_items, align_content, row_gap, column_gap) = {
+            let cvs = self.computed_values();
+            (
+                FlexAxes {
+                    is_row: cvs.flex_direction.is_row(),
+                    main_reverse: cvs.flex_direction.is_reverse(),
+                    cross_reverse: cvs.flex_wrap == FlexWrap::WrapReverse,
+                },
+                cvs.flex_wrap == FlexWrap::Nowrap,
+                cvs.justify_content,
+                cvs.align_items,
+                cvs.align_content,
+                cvs.row_gap.size,
+                cvs.column_gap.size,
+            )
+        };
+        let container = self.dimensions.physical();
+        let inline_size = self.dimensions.get_inline_size();
+        let column_gap = resolve_length_percentage(column_gap, inline_size, scale_factor);
+        // Percentage row gaps resolve to zero if the block size of the container is indefinite.
+        let row_gap = resolve_size(
+            LengthPercentageOrAuto::LengthPercentage(row_gap),
+            definite_block_size,
+            scale_factor,
+        )
+        .unwrap_or(zero);
+        let (main_gap, cross_gap) = if axes.is_row {
+            (column_gap, row_gap)
+        } else {
+            (row_gap, column_gap)
+        };
+        let definite_main_size = if axes.is_row {
+            Some(inline_size)
+        } else {
+            definite_block_size
+        };
+        let definite_cross_size = if axes.is_row {
+            definite_block_size
+        } else {
+            Some(inline_size)
+        };
+
+        // Steps 2 and 3: Determine the flex base size and hypothetical main size of each item,
+        // laying items out in `order`-modified document order.
+        let styles = self
+            .children
+            .iter()
+            .map(FlexItemStyle::of)
+            .collect::<Vec<_>>();
+        // Absolutely positioned children don't participate in flex layout.
+        let mut order = (0..self.children.len())
+            .filter(|&idx| !self.children[idx].is_absolutely_positioned())
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&idx| styles[idx].order);
+        let mut items = order
+            .into_iter()
+            .map(|idx| {
+                self.children[idx].new_flex_item(
+                    idx,
+                    styles[idx],
+                    container,
+                    axes,
+                    single_line,
+                    align_items,
+                    definite_main_size,
+                    scale_factor,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // Step 5: Collect flex items into flex lines.
+        let mut lines = Vec::new();
+        let mut line_start = 0;
+        let mut line_main_size = zero;
+        for (idx, item) in items.iter().enumerate() {
+            let outer_size = item.outer_hypothetical_main_size();
+            if idx > line_start {
+                let fits = match definite_main_size {
+                    Some(available) => line_main_size + main_gap + outer_size <= available,
+                    None => true,
+                };
+                if single_line || fits {
+                    line_main_size += main_gap + outer_size;
+                    continue;
+                }
+                lines.push(FlexLine::new(line_start..idx));
+                line_start = idx;
+            }
+            line_main_size = outer_size;
+        }
+        if !items.is_empty() {
+            lines.push(FlexLine::new(line_start..items.len()));
+        }
+
+        // Step 6: Resolve the flexible lengths of all the flex items to find their used main size.
+        for line in &lines {
+            resolve_flexible_lengths(&mut items[line.items.clone()], definite_main_size, main_gap);
+        }
+
+        // Step 7: Determine the hypothetical cross size of each item by laying it out with its
+        // used main size.
+        for item in &mut items {
+            let child = &mut self.children[item.index];
+            if axes.is_row {
+                child.layout_container_item(
+                    container,
+                    Some(item.target_main_size),
+                    None,
+                    scale_factor,
+                );
+                item.cross_size = child.dimensions.get_content_block_size();
+                item.ascent = child.flex_item_ascent();
+            } else {
+                child.layout_container_item(
+                    container,
+                    item.stretched_inline_size,
+                    Some(item.target_main_size),
+                    scale_factor,
+                );
+                item.cross_size = child.dimensions.get_inline_size();
+            }
+        }
+
+        // Step 8: Calculate the cross size of each flex line.
+        let participates_in_baseline_alignment = |item: &FlexItem| {
+            axes.is_row
+                && !axes.cross_reverse
+                && item.align == AlignItems::Baseline
+                && !item.has_auto_cross_margin()
+        };
+        for line in &mut lines {
+            if let (true, Some(cross_size)) = (single_line, definite_cross_size) {
+                line.cross_size = cross_size;
+            }
+            let mut max_descent = zero;
+            let mut max_outer_cross_size = zero;
+            for item in &items[line.items.clone()] {
+                if participates_in_baseline_alignment(item) {
+                    line.max_ascent = line.max_ascent.max(item.ascent);
+                    max_descent = max_descent.max(item.outer_cross_size() - item.ascent);
+                } else {
+                    max_outer_cross_size = max_outer_cross_size.max(item.outer_cross_size());
+                }
+            }
+            if !single_line || definite_cross_size.is_none() {
+                line.cross_size = max_outer_cross_size.max(line.max_ascent + max_descent);
+            }
+        }
+
+        // Steps 9 and 15: Distribute free space between the lines according to `align-content`,
+        // and determine the used cross size of the flex container.
+        let lines_cross_size = lines.iter().fold(zero, |acc, line| acc + line.cross_size)
+            + cross_gap * lines.len().saturating_sub(1) as i32;
+        let container_cross_size = definite_cross_size.unwrap_or(lines_cross_size);
+        let free_cross_space = container_cross_size - lines_cross_size;
+        let (leading_space, between_space) = if single_line {
+            (zero, zero)
+        } else {
+            let distribution = match align_content {
+                AlignContent::Normal | AlignContent::Stretch => {
+                    if free_cross_space > Au(0) {
+                        let extra = free_cross_space / lines.len() as i32;
+                        for line in &mut lines {
+                            line.cross_size += extra;
+                        }
+                    }
+                    Distribution::Start
+                }
+                AlignContent::FlexStart => Distribution::Start,
+                AlignContent::FlexEnd => Distribution::End,
+                AlignContent::Start if axes.cross_reverse => Distribution::End,
+                AlignContent::Start => Distribution::Start,
+                AlignContent::End if axes.cross_reverse => Distribution::Start,
+                AlignContent::End => Distribution::End,
+                AlignContent::Center => Distribution::Center,
+                AlignContent::SpaceBetween => Distribution::SpaceBetween,
+                AlignContent::SpaceAround => Distribution::SpaceAround,
+                AlignContent::SpaceEvenly => Distribution::SpaceEvenly,
+            };
+            distribution.offsets(free_cross_space, lines.len())
+        };
+        let mut cross_cursor = leading_space;
+        for line in &mut lines {
+            line.cross_position = cross_cursor;
+            cross_cursor += line.cross_size + cross_gap + between_space;
+        }
+
+        // Step 11: Stretch items whose cross size is `auto` to fill their line.
+        for line in &lines {
+            for item in &mut items[line.items.clone()] {
+                if !item.stretches {
+                    continue;
+                }
+                let cross_size = (line.cross_size - item.cross_edges).max(Au(0));
+                if cross_size == item.cross_size {
+                    continue;
+                }
+                let child = &mut self.children[item.index];
+                // The stretched size is still clamped by the item's `min-*` and `max-*` sizes.
+                item.cross_size = if axes.is_row {
+                    child.layout_container_item(
+                        container,
+                        Some(item.target_main_size),
+                        Some(cross_size),
+                        scale_factor,
+                    );
+                    child.dimensions.get_content_block_size()
+                } else {
+                    child.layout_container_item(
+                        container,
+                        Some(cross_size),
+                        Some(item.target_main_size),
+                        scale_factor,
+                    );
+                    child.dimensions.get_inline_size()
+                };
+            }
+        }
+
+        // Steps 12 and 13: Distribute the remaining free space along the main axis of each line,
+        // first to `auto` margins and then according to `justify-content`, and align each item
+        // within its line in the cross axis.
+        let container_main_size = definite_main_size.unwrap_or_else(|| {
+            lines.iter().fold(zero, |acc, line| {
+                acc.max(line.outer_main_size(&items, main_gap))
+            })
+        });
+        let justify_distribution = match justify_content {
+            JustifyContent::Normal | JustifyContent::FlexStart => Distribution::Start,
+            JustifyContent::FlexEnd => Distribution::End,
+            JustifyContent::Start if axes.main_reverse => Distribution::End,
+            JustifyContent::Start => Distribution::Start,
+            JustifyContent::End if axes.main_reverse => Distribution::Start,
+            JustifyContent::End => Distribution::End,
+            JustifyContent::Center => Distribution::Center,
+            JustifyContent::SpaceBetween => Distribution::SpaceBetween,
+            JustifyContent::SpaceAround => Distribution::SpaceAround,
+            JustifyContent::SpaceEvenly => Distribution::SpaceEvenly,
+        };
+        for line in &lines {
+            let free_main_space = container_main_size - line.outer_main_size(&items, main_gap);
+            let line_items = &mut items[line.items.clone()];
+            let auto_margin_count = line_items
+                .iter()
+                .map(|item| item.auto_margins[..2].iter().filter(|&&auto| auto).count())
+                .sum::<usize>();
+            let (leading_space, between_space, auto_margin_size) =
+                if free_main_space > Au(0) && auto_margin_count > 0 {
+                    (zero, zero, free_main_space / auto_margin_count as i32)
+                } else {
+                    let (leading_space, between_space) =
+                        justify_distribution.offsets(free_main_space, line_items.len());
+                    (leading_space, between_space, zero)
+                };
+            let mut main_cursor = leading_space;
+            for item in line_items {
+                if item.auto_margins[0] {
+                    main_cursor += auto_margin_size;
+                }
+                item.main_position = main_cursor;
+                main_cursor += item.outer_target_main_size() + main_gap + between_space;
+                if item.auto_margins[1] {
+                    main_cursor += auto_margin_size;
+                }
+
+                let free_cross_space = line.cross_size - item.outer_cross_size();
+                let cross_offset = if item.has_auto_cross_margin() && free_cross_space > Au(0) {
+                    match (item.auto_margins[2], item.auto_margins[3]) {
+                        (true, true) => free_cross_space / 2,
+                        (true, false) => free_cross_space,
+                        _ => zero,
+                    }
+                } else if participates_in_baseline_alignment(item) {
+                    line.max_ascent - item.ascent
+                } else {
+                    let distribution = match item.align {
+                        AlignItems::Normal
+                        | AlignItems::Stretch
+                        | AlignItems::FlexStart
+                        | AlignItems::Baseline => Distribution::Start,
+                        AlignItems::FlexEnd => Distribution::End,
+                        AlignItems::Start if axes.cross_reverse => Distribution::End,
+                        AlignItems::Start => Distribution::Start,
+                        AlignItems::End if axes.cross_reverse => Distribution::Start,
+                        AlignItems::End => Distribution::End,
+                        AlignItems::Center => Distribution::Center,
+                    };
+                    distribution.offsets(free_cross_space, 1).0
+                };
+                item.cross_position = line.cross_position + cross_offset;
+            }
+        }
+
+        // Finally, move each item into place, converting from flex-relative to physical
+        // coordinates.
+        let horizontal = self.writing_mode.is_horizontal();
+        for item in &items {
+            let main_offset = if axes.main_reverse {
+                container_main_size - item.main_position - item.outer_target_main_size()
+            } else {
+                item.main_position
+            };
+            let cross_offset = if axes.cross_reverse {
+                container_cross_size - item.cross_position - item.outer_cross_size()
+            } else {
+                item.cross_position
+            };
+            let (inline_offset, block_offset) = if axes.is_row {
+                (main_offset, cross_offset)
+            } else {
+                (cross_offset, main_offset)
+            };
+            let (target_x, target_y) = if horizontal {
+                (
+                    container.content.start_x + inline_offset,
+                    container.content.start_y + block_offset,
+                )
+            } else {
+                (
+                    container.content.start_x + block_offset,
+                    container.content.start_y + inline_offset,
+                )
+            };
+            let child = &mut self.children[item.index];
+            let margin_box = child.physical_dimensions().margin_box();
+            child.translate(target_x - margin_box.start_x, target_y - margin_box.start_y);
+        }
+
+        // Step 4 (for column flex containers) and step 15 (for row flex containers): Determine
+        // the block size of the flex container.
+        self.dimensions.set_block_size(if axes.is_row {
+            container_cross_size
+        } else {
+            container_main_size
+        });
+        self.set_container_static_positions();
+    }
+
+    /// Collects the flex item generated by `self`, determining its flex base size and
+    /// hypothetical main size.
+    ///
+    /// https://drafts.csswg.org/css-flexbox-1/#algo-main-item
+    #[allow(clippy::too_many_arguments)]
+    fn new_flex_item(
+        &mut self,
+        index: usize,
+        style: FlexItemStyle,
+        container: PhysicalDimensions,
+        axes: FlexAxes,
+        single_line: bool,
+        align_items: AlignItems,
+        definite_main_size: Option<Au>,
+        scale_factor: f32,
+    ) -> FlexItem {
+        let container_inline_size = if self.writing_mode.is_horizontal() {
+            container.content.width
+        } else {
+            container.content.height
+        };
+        // Lay the item out at its automatic size, which gives us its margins, borders, and
+        // padding, and for column flex containers, the block size of its content.
+        self.layout_container_item(container, None, None, scale_factor);
+        let inline_edges = self.dimensions.get_edges_sum(LogicalDirection::InlineStart)
+            + self.dimensions.get_edges_sum(LogicalDirection::InlineEnd);
+        let block_edges = self.dimensions.get_edges_sum(LogicalDirection::BlockStart)
+            + self.dimensions.get_edges_sum(LogicalDirection::BlockEnd);
+
+        let [inline_start, inline_end, block_start, block_end] = style.auto_margins;
+        let (mut main_margins, mut cross_margins) = if axes.is_row {
+            ([inline_start, inline_end], [block_start, block_end])
+        } else {
+            ([block_start, block_end], [inline_start, inline_end])
+        };
+        if axes.main_reverse {
+            main_margins.reverse();
+        }
+        if axes.cross_reverse {
+            cross_margins.reverse();
+        }
+        let align = style.align_self.resolve(align_items);
+        let auto_cross_size = if axes.is_row {
+            style.block_size == Size::Auto
+        } else {
+            style.inline_size == Size::Auto
+        };
+        let stretches = (align == AlignItems::Normal || align == AlignItems::Stretch)
+            && auto_cross_size
+            && !cross_margins.iter().any(|&auto| auto);
+
+        // The items of a single-line column flex container are stretched before their main size
+        // is measured, since their block size can depend on their inline size.
+        let mut stretched_inline_size = None;
+        if !axes.is_row && single_line && stretches {
+            let inline_size = (container_inline_size - inline_edges).max(Au(0));
+            self.layout_container_item(container, Some(inline_size), None, scale_factor);
+            stretched_inline_size = Some(inline_size);
+        }
+
+        let (main_edges, cross_edges) = if axes.is_row {
+            (inline_edges, block_edges)
+        } else {
+            (block_edges, inline_edges)
+        };
+        // The item shares the writing mode of its container, so its main axis is horizontal if
+        // it's the inline axis of a horizontal writing mode, or the block axis of a vertical one.
+        let main_horizontal = axes.is_row == self.writing_mode.is_horizontal();
+        let main_box_sizing_edges = self.box_sizing_edges(main_horizontal, container, scale_factor);
+        let (min_content_size, max_content_size) = if axes.is_row {
+            self.intrinsic_content_inline_sizes(scale_factor)
+        } else {
+            let content_size = self.dimensions.get_content_block_size();
+            (content_size, content_size)
+        };
+        let resolve_main_size = |size, percentage_basis| {
+            self.resolve_size_property(
+                size,
+                main_horizontal,
+                percentage_basis,
+                main_box_sizing_edges,
+                scale_factor,
+            )
+        };
+        let specified_main_size = if axes.is_row {
+            resolve_main_size(style.inline_size, Some(container_inline_size))
+        } else {
+            resolve_main_size(style.block_size, definite_main_size)
+        };
+
+        // https://drafts.csswg.org/css-flexbox-1/#algo-main-item
+        let flex_base_size = match style.basis {
+            FlexBasis::Width(Size::Auto) => specified_main_size.unwrap_or(max_content_size),
+            FlexBasis::Width(basis) => {
+                resolve_main_size(basis, definite_main_size).unwrap_or(max_content_size)
+            }
+            FlexBasis::Content => max_content_size,
+        };
+        // Percentages of an indefinite main size behave as if they weren't given.
+        let main_size_constraints = self.size_constraints(
+            main_horizontal,
+            definite_main_size.map(|_| container),
+            scale_factor,
+        );
+        let max_main_size = main_size_constraints.max;
+        // The automatic minimum size of a flex item is its content-based minimum size, which is
+        // never more than its maximum size.
+        // https://drafts.csswg.org/css-flexbox-1/#min-size-auto
+        let min_main_size = main_size_constraints.min.unwrap_or_else(|| {
+            let content_size = specified_main_size.map_or(min_content_size, |specified_size| {
+                specified_size.min(min_content_size)
+            });
+            match max_main_size {
+                Some(max) => content_size.min(max),
+                None => content_size,
+            }
+        });
+
+        let zero = Au(0);
+        let mut item = FlexItem {
+            index,
+            grow: style.grow,
+            shrink: style.shrink,
+            align,
+            main_edges,
+            cross_edges,
+            auto_margins: [
+                main_margins[0],
+                main_margins[1],
+                cross_margins[0],
+                cross_margins[1],
+            ],
+            stretches,
+            stretched_inline_size,
+            flex_base_size,
+            hypothetical_main_size: zero,
+            min_main_size,
+            max_main_size,
+            target_main_size: zero,
+            frozen: false,
+            cross_size: zero,
+            ascent: zero,
+            main_position: zero,
+            cross_position: zero,
+        };
+        item.hypothetical_main_size = item.clamp_main_size(flex_base_size);
+        item.target_main_size = item.hypothetical_main_size;
+        item
+    }
+
+    /// Lays out `self` as a flex or grid item of a container with the given dimensions.  The content box
+    /// of the item is given the inline and block sizes passed, if any.  Otherwise, the inline size
+    /// is the item's shrink-to-fit size and its block size follows from its contents, as if it
+    /// were an inline-block.
+    ///
+    /// The item is expected to be moved into place by its container's layout algorithm afterwards.
+    pub(super) fn layout_container_item(
+        &mut self,
+        container: PhysicalDimensions,
+        inline_size: Option<Au>,
+        block_size: Option<Au>,
+        scale_factor: f32,
+    ) {
+        // Sizes given by the container are still clamped by the item's `min-*` and `max-*` sizes.
+        let inline_constraints = self.inline_size_constraints(Some(container), scale_factor);
+        let block_constraints = self.block_size_constraints(Some(container), scale_factor);
+        let inline_size = inline_size.map(|size| inline_constraints.clamp(size));
+        if self.is_replaced() {
+            // Replaced items have no children to lay out, so only their size can be overridden.
+            self.layout_replaced(container, scale_factor);
+            if let Some(inline_size) = inline_size {
+                self.dimensions.set_inline_size(inline_size);
+            }
+            if let Some(block_size) = block_size {
+                self.dimensions
+                    .set_block_size(block_constraints.clamp(block_size));
+            }
+            return;
+        }
+        if self.box_type == BoxType::Anonymous {
+            // Anonymous items have no margins, borders, or padding of their own.
+            let horizontal = self.writing_mode.is_horizontal();
+            let content = container.content;
+            let inline_size = inline_size.unwrap_or_else(|| {
+                let available_inline_size = if horizontal {
+                    content.width
+                } else {
+                    content.height
+                };
+                let (min_content, max_content) = self.intrinsic_content_inline_sizes(scale_factor);
+                min_content.max(available_inline_size).min(max_content)
+            });
+            let d = &mut self.dimensions;
+            d.set_inline_size(inline_size);
+            d.set_start_x(content.start_x);
+            d.set_start_y(content.start_y);
+        } else {
+            self.calculate_inline_block_logical_width(container, scale_factor);
+            if let Some(inline_size) = inline_size {
+                self.dimensions.set_inline_size(inline_size);
+            }
+            self.calculate_block_position(container, scale_factor);
+        }
+
+        let block_size = block_size
+            .map(|size| block_constraints.clamp(size))
+            .or_else(|| self.specified_block_size(container, scale_factor));
+        // Children are stacked after the current block size, so start from an empty content box
+        // in case this item has already been laid out.
+        self.dimensions.set_block_size(Au(0));
+        match self.box_type {
+            BoxType::Flex | BoxType::InlineFlex => self.layout_flex_items(block_size, scale_factor),
+            BoxType::Grid | BoxType::InlineGrid => self.layout_grid_items(block_size, scale_factor),
+            _ => self.layout_block_children(scale_factor),
+        }
+        let block_size = block_size.unwrap_or_else(|| self.dimensions.get_content_block_size());
+        self.dimensions
+            .set_block_size(block_constraints.clamp(block_size));
+    }
+
+    /// Gets the distance from the block-start margin edge of this flex item to its baseline.
+    /// Items without a baseline have one synthesized from their block-end margin edge.
+    ///
+    /// TODO: This should be the first baseline of the item, rather than its last.
+    fn flex_item_ascent(&self) -> Au {
+        let margin_box_block_start = self.dimensions.get_block_start_coord()
+            - self.dimensions.get_edges_sum(LogicalDirection::BlockStart);
+        match self.last_line_baseline() {
+            Some(baseline) => baseline - margin_box_block_start,
+            None => self.dimensions.margin_box_block_size(),
+        }
+    }
+
+    /// Resolves the `height` (or `width`, in vertical writing modes) of `self` against the given
+    /// containing block, clamped by its `min-*` and `max-*` sizes in that axis.  Returns `None` if
+    /// it's `auto`.
+    pub(super) fn specified_block_size(
+        &self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> Option<Au> {
+        if self.box_type == BoxType::Anonymous {
+            return None;
+        }
+        let horizontal = self.writing_mode.is_horizontal();
+        let constraints = self.block_size_constraints(Some(containing_block), scale_factor);
+        self.specified_size(!horizontal, containing_block, scale_factor)
+            .map(|size| constraints.clamp(size))
+    }
+
+    /// Calculates the min-content and max-content inline sizes of a flex container's content.
+    ///
+    /// https://drafts.csswg.org/css-flexbox-1/#intrinsic-sizes
+    pub(super) fn flex_intrinsic_content_inline_sizes(&self, scale_factor: f32) -> (Au, Au) {
+        let zero = Au(0);
+        let cvs = self.computed_values();
+        let contributions = self
+            .children
+            .iter()
+            .filter(|child| !child.is_absolutely_positioned())
+            .map(|child| child.intrinsic_inline_contributions(scale_factor))
+            .collect::<Vec<_>>();
+        if !cvs.flex_direction.is_row() {
+            // Items are stacked in the block axis, so the widest one wins.
+            return contributions
+                .iter()
+                .fold((zero, zero), |(min_acc, max_acc), &(min, max)| {
+                    (min_acc.max(min), max_acc.max(max))
+                });
+        }
+        // Percentage gaps can't be resolved without a containing block, so treat them as zero.
+        let gap = match cvs.column_gap.size {
+            LengthPercentage::Length(len) => Au::from(len * scale_factor),
+            LengthPercentage::Percentage(_) => zero,
+        };
+        let gaps = gap * contributions.len().saturating_sub(1) as i32;
+        let max_content = contributions.iter().fold(zero, |acc, &(_, max)| acc + max) + gaps;
+        let min_content = if cvs.flex_wrap == FlexWrap::Nowrap {
+            contributions.iter().fold(zero, |acc, &(min, _)| acc + min) + gaps
+        } else {
+            contributions
+                .iter()
+                .fold(zero, |acc, &(min, _)| acc.max(min))
+        };
+        (min_content, max_content)
+    }
+}
+
+impl FlexLine {
+    fn new(items: Range<usize>) -> FlexLine {
+        FlexLine {
+            items,
+            cross_size: Au(0),
+            max_ascent: Au(0),
+            cross_position: Au(0),
+        }
+    }
+
+    /// The sum of the outer main sizes of the items on this line, including the gaps between them.
+    fn outer_main_size(&self, items: &[FlexItem], main_gap: Au) -> Au {
+        items[self.items.clone()]
+            .iter()
+            .fold(Au(0), |acc, item| acc + item.outer_target_main_size())
+            + main_gap * self.items.len().saturating_sub(1) as i32
+    }
+}
+
+/// Resolves the flexible lengths of the items on a single flex line, setting the target main
+/// size of each.  If the available main space is indefinite, items keep their hypothetical main
+/// sizes.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#resolve-flexible-lengths
+fn resolve_flexible_lengths(items: &mut [FlexItem], available_main_size: Option<Au>, main_gap: Au) {
+    let zero = Au(0);
+    let available_main_size = match available_main_size {
+        Some(size) => size - main_gap * items.len().saturating_sub(1) as i32,
+        None => {
+            for item in items.iter_mut() {
+                item.target_main_size = item.hypothetical_main_size;
+            }
+            return;
+        }
+    };
+
+    // Step 1: Determine which flex factor to use.
+    let hypothetical_sum = items
+        .iter()
+        .fold(zero, |acc, item| acc + item.outer_hypothetical_main_size());
+    let growing = hypothetical_sum < available_main_size;
+    let flex_factor = |item: &FlexItem| if growing { item.grow } else { item.shrink };
+
+    // Step 2: Size inflexible items.
+    for item in items.iter_mut() {
+        item.target_main_size = item.hypothetical_main_size;
+        item.frozen = flex_factor(item) == 0.
+            || (growing && item.flex_base_size > item.hypothetical_main_size)
+            || (!growing && item.flex_base_size < item.hypothetical_main_size);
+    }
+
+    // Step 3: Calculate initial free space.
+    let free_space = |items: &[FlexItem]| {
+        available_main_size
+            - items.iter().fold(zero, |acc, item| {
+                acc + item.main_edges
+                    + if item.frozen {
+                        item.target_main_size
+                    } else {
+                        item.flex_base_size
+                    }
+            })
+    };
+    let initial_free_space = free_space(items);
+
+    // Step 4: Loop.
+    while items.iter().any(|item| !item.frozen) {
+        let mut remaining_free_space = free_space(items);
+        let factor_sum = items
+            .iter()
+            .filter(|item| !item.frozen)
+            .map(flex_factor)
+            .sum::<CSSFloat>();
+        if factor_sum < 1. {
+            let scaled_free_space = initial_free_space.scale_by(factor_sum);
+            if scaled_free_space.abs() < remaining_free_space.abs() {
+                remaining_free_space = scaled_free_space;
+            }
+        }
+
+        // Distribute free space proportional to the flex factors.
+        let scaled_shrink_factor_sum = items
+            .iter()
+            .filter(|item| !item.frozen)
+            .map(|item| item.shrink * item.flex_base_size.to_f32_px())
+            .sum::<CSSFloat>();
+        for item in items.iter_mut().filter(|item| !item.frozen) {
+            item.target_main_size = if remaining_free_space == Au(0) {
+                item.flex_base_size
+            } else if growing {
+                item.flex_base_size + remaining_free_space.scale_by(item.grow / factor_sum)
+            } else if scaled_shrink_factor_sum > 0. {
+                let ratio =
+                    item.shrink * item.flex_base_size.to_f32_px() / scaled_shrink_factor_sum;
+                item.flex_base_size - remaining_free_space.abs().scale_by(ratio)
+            } else {
+                item.flex_base_size
+            };
+        }
+
+        // Fix min/max violations.  A positive violation means the item was clamped up to its
+        // minimum size, and a negative one that it was clamped down to its maximum size.
+        let mut total_violation = zero;
+        let mut violations = Vec::with_capacity(items.len());
+        for item in items.iter_mut() {
+            let mut violation = zero;
+            if !item.frozen {
+                let clamped_size = item.clamp_main_size(item.target_main_size);
+                violation = clamped_size - item.target_main_size;
+                item.target_main_size = clamped_size;
+            }
+            total_violation += violation;
+            violations.push(violation);
+        }
+
+        // Freeze over-flexed items: all of them if there were no violations, otherwise those
+        // whose violations go in the same direction as the total.
+        for (item, violation) in items.iter_mut().zip(violations) {
+            if total_violation == Au(0)
+                || (total_violation > Au(0) && violation > Au(0))
+                || (total_violation < Au(0) && violation < Au(0))
+            {
+                item.frozen = true;
+            }
+        }
+    }
+}
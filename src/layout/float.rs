@@ -0,0 +1,396 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/visuren.html#floats
+//  * https://www.w3.org/TR/CSS2/visuren.html#float-position
+//  * https://www.w3.org/TR/CSS2/visuren.html#flow-control
+//  * https://drafts.csswg.org/css-logical-1/#float-clear
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::margin::CollapsedMargin;
+use crate::layout::rect::Rect;
+use crate::style::values::computed::{Clear, Direction, Float};
+use app_units::Au;
+
+/// The side of its containing block a float is pushed to.  The line-left side is the side lines
+/// start from in `ltr` text, which is the left side in horizontal writing modes and the top side
+/// otherwise.
+///
+/// https://drafts.csswg.org/css-writing-modes-4/#line-left
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum FloatSide {
+    LineLeft,
+    LineRight,
+}
+
+impl FloatSide {
+    /// Maps the given `float` value onto a side of a containing block with the given direction.
+    fn of(float: Float, direction: Direction) -> Option<FloatSide> {
+        match (float, direction) {
+            (Float::None, _) => None,
+            (Float::Left, _)
+            | (Float::InlineStart, Direction::Ltr)
+            | (Float::InlineEnd, Direction::Rtl) => Some(FloatSide::LineLeft),
+            (Float::Right, _)
+            | (Float::InlineEnd, Direction::Ltr)
+            | (Float::InlineStart, Direction::Rtl) => Some(FloatSide::LineRight),
+        }
+    }
+
+    /// Determines if `clear` keeps a box from being adjacent to floats on this side of a
+    /// containing block with the given direction.
+    fn is_cleared_by(self, clear: Clear, direction: Direction) -> bool {
+        let float = match clear {
+            Clear::None => return false,
+            Clear::Both => return true,
+            Clear::Left => Float::Left,
+            Clear::Right => Float::Right,
+            Clear::InlineStart => Float::InlineStart,
+            Clear::InlineEnd => Float::InlineEnd,
+        };
+        FloatSide::of(float, direction) == Some(self)
+    }
+}
+
+/// The margin box of a float that has been placed, in the coordinates of its float context.
+#[derive(Clone, Copy, Debug)]
+struct PlacedFloat {
+    side: FloatSide,
+    inline_start: Au,
+    inline_end: Au,
+    block_start: Au,
+    block_end: Au,
+}
+
+impl PlacedFloat {
+    /// Determines if this float is beside any part of the block axis range starting at
+    /// `block_start` and spanning `block_size`.  An empty range is beside the floats that span
+    /// its start.
+    fn intersects(&self, block_start: Au, block_size: Au) -> bool {
+        self.block_end > block_start
+            && (self.block_start < block_start + block_size || self.block_start <= block_start)
+    }
+}
+
+/// Tracks the floats placed so far in a block formatting context, which exclude areas from the
+/// line boxes and block formatting context roots that follow them.
+///
+/// Coordinates are page coordinates mapped onto the axes of the block formatting context root,
+/// so the inline axis is the x-axis in horizontal writing modes, and the y-axis otherwise.
+#[derive(Clone, Debug)]
+pub(super) struct FloatContext {
+    horizontal: bool,
+    floats: Vec<PlacedFloat>,
+}
+
+impl FloatContext {
+    pub(super) fn new(horizontal: bool) -> Self {
+        FloatContext {
+            horizontal,
+            floats: Vec::new(),
+        }
+    }
+
+    pub(super) fn is_horizontal(&self) -> bool {
+        self.horizontal
+    }
+
+    /// Finds the part of `inline_range` that isn't excluded by floats beside the given block axis
+    /// range.
+    pub(super) fn available_range(
+        &self,
+        block_start: Au,
+        block_size: Au,
+        inline_range: (Au, Au),
+    ) -> (Au, Au) {
+        let (mut start, mut end) = inline_range;
+        for float in self
+            .floats
+            .iter()
+            .filter(|float| float.intersects(block_start, block_size))
+        {
+            match float.side {
+                FloatSide::LineLeft => start = start.max(float.inline_end),
+                FloatSide::LineRight => end = end.min(float.inline_start),
+            }
+        }
+        (start, end)
+    }
+
+    /// Finds the closest block-end edge of a float past `block_start`, which is the next place
+    /// the available space beside floats could grow.
+    pub(super) fn next_float_end(&self, block_start: Au) -> Option<Au> {
+        self.floats
+            .iter()
+            .map(|float| float.block_end)
+            .filter(|&block_end| block_end > block_start)
+            .fold(None, |next, block_end| match next {
+                Some(next) if next <= block_end => Some(next),
+                _ => Some(block_end),
+            })
+    }
+
+    /// Finds the block-end edge of the lowest float, if any, which the block formatting context
+    /// root must grow to contain.
+    pub(super) fn block_end(&self) -> Option<Au> {
+        self.floats
+            .iter()
+            .map(|float| float.block_end)
+            .fold(None, |lowest, block_end| match lowest {
+                Some(lowest) if lowest >= block_end => Some(lowest),
+                _ => Some(block_end),
+            })
+    }
+
+    /// Finds the block-end edge of the lowest float that a box with the given `clear` value, in a
+    /// containing block with the given direction, must be placed below.
+    fn clearance_edge(&self, clear: Clear, direction: Direction) -> Option<Au> {
+        self.floats
+            .iter()
+            .filter(|float| float.side.is_cleared_by(clear, direction))
+            .map(|float| float.block_end)
+            .fold(None, |lowest, block_end| match lowest {
+                Some(lowest) if lowest >= block_end => Some(lowest),
+                _ => Some(block_end),
+            })
+    }
+
+    /// Places a float with a margin box of the given size as high as possible, but no higher than
+    /// `min_block_start`, and then as far to its side of `inline_range` as possible.  Returns the
+    /// inline and block coordinates of the start of the float's margin box.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#float-rules
+    fn place(
+        &mut self,
+        side: FloatSide,
+        inline_size: Au,
+        block_size: Au,
+        min_block_start: Au,
+        inline_range: (Au, Au),
+    ) -> (Au, Au) {
+        // A float can't be higher than any float placed before it.
+        let mut block_start = self
+            .floats
+            .iter()
+            .map(|float| float.block_start)
+            .fold(min_block_start, Au::max);
+        let (start, end) = loop {
+            let (start, end) = self.available_range(block_start, block_size, inline_range);
+            // Move the float down past other floats until it fits, or until there's nothing left
+            // to move past, in which case it overflows its containing block.
+            if end - start >= inline_size || (start, end) == inline_range {
+                break (start, end);
+            }
+            match self.next_float_end(block_start) {
+                Some(next_float_end) => block_start = next_float_end,
+                None => break (start, end),
+            }
+        };
+        let inline_start = match side {
+            FloatSide::LineLeft => start,
+            FloatSide::LineRight => end - inline_size,
+        };
+        self.floats.push(PlacedFloat {
+            side,
+            inline_start,
+            inline_end: inline_start + inline_size,
+            block_start,
+            block_end: block_start + block_size,
+        });
+        (inline_start, block_start)
+    }
+}
+
+/// Gets the inline-start and block-start coordinates, and the inline and block sizes, of `rect`
+/// along the given axes.
+pub(super) fn logical_rect(rect: Rect, horizontal: bool) -> (Au, Au, Au, Au) {
+    if horizontal {
+        (rect.start_x, rect.start_y, rect.width, rect.height)
+    } else {
+        (rect.start_y, rect.start_x, rect.height, rect.width)
+    }
+}
+
+impl LayoutBox {
+    /// Determines which side of a containing block with the given direction this box floats to,
+    /// if it's floated.
+    pub(super) fn float_side(&self, direction: Direction) -> Option<FloatSide> {
+        if !self.is_floated() {
+            return None;
+        }
+        FloatSide::of(self.computed_values().float, direction)
+    }
+
+    /// Assuming `self` is floated, lays out this box at its shrink-to-fit size at the start of
+    /// `containing_block`.  It's moved into place by `place_float` once the float context it
+    /// participates in decides where it goes.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#float-width
+    /// https://www.w3.org/TR/CSS2/visudet.html#block-root-margin
+    pub(super) fn layout_float(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) {
+        if self.box_type == BoxType::TableWrapper {
+            // Tables are always sized by the table layout algorithm.
+            self.layout_inline_table_wrapper(containing_block, scale_factor);
+        } else {
+            self.layout_container_item(containing_block, None, None, scale_factor);
+        }
+    }
+
+    /// Assuming `self` is a floated box that has been laid out, places it in `floats` no higher
+    /// than `min_block_start`, and within `inline_range`, the inline extent of its containing
+    /// block.
+    pub(super) fn place_float(
+        &mut self,
+        floats: &mut FloatContext,
+        direction: Direction,
+        min_block_start: Au,
+        inline_range: (Au, Au),
+    ) {
+        let side = self
+            .float_side(direction)
+            .expect("only floated boxes can be placed as floats");
+        let horizontal = floats.is_horizontal();
+        let clear = self.computed_values().clear;
+        let min_block_start = floats
+            .clearance_edge(clear, direction)
+            .map_or(min_block_start, |edge| edge.max(min_block_start));
+        let margin_box = self.physical_dimensions().margin_box();
+        let (inline_start, block_start, inline_size, block_size) =
+            logical_rect(margin_box, horizontal);
+        let (target_inline, target_block) =
+            floats.place(side, inline_size, block_size, min_block_start, inline_range);
+        let inline_offset = target_inline - inline_start;
+        let block_offset = target_block - block_start;
+        if horizontal {
+            self.translate(inline_offset, block_offset);
+        } else {
+            self.translate(block_offset, inline_offset);
+        }
+    }
+
+    /// Calculates the clearance of this in-flow box, which is the space added above its top border
+    /// edge to move it below the floats its `clear` value applies to.  `hypothetical` is the
+    /// block axis coordinate the border box would start at if `clear` were `none`, with its
+    /// block-start margin collapsed with the margins before it, and `uncollapsed` is where it
+    /// would start if its block-start margin didn't collapse with them.
+    ///
+    /// Returns `None` if the box has no clearance, since its hypothetical position is already past
+    /// the floats.  Otherwise the clearance places its border box right after the floats, and
+    /// may be negative.  A box with clearance doesn't collapse its block-start margin with the
+    /// margins before it.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#clearance
+    pub(super) fn clearance(
+        &self,
+        floats: &FloatContext,
+        direction: Direction,
+        hypothetical: Au,
+        uncollapsed: Au,
+    ) -> Option<Au> {
+        if self.anonymous {
+            return None;
+        }
+        let clear = self.computed_values().clear;
+        match floats.clearance_edge(clear, direction) {
+            Some(edge) if edge > hypothetical => Some(edge - uncollapsed),
+            _ => None,
+        }
+    }
+
+    /// Assuming `self` is an in-flow, block-level box that establishes a new block formatting
+    /// context, lays it out after the content of `containing_block` so that its border box
+    /// doesn't overlap any of the floats in `floats`.  The box is narrowed to fit next to the
+    /// floats beside it if it can be, and is moved down past them otherwise.
+    ///
+    /// Its block-start margin collapses with `margin_before`, the collapsed margin after the
+    /// content of `containing_block`, unless the box has `clearance`.  Returns its block-end
+    /// margin, which collapses with the margins after it, and the space added above the box to
+    /// fit beside floats.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#bfc-next-to-float
+    pub(super) fn layout_beside_floats(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        floats: &FloatContext,
+        margin_before: CollapsedMargin,
+        clearance: Option<Au>,
+        scale_factor: f32,
+    ) -> (CollapsedMargin, Au) {
+        self.layout(containing_block, scale_factor);
+
+        let horizontal = floats.is_horizontal();
+        let (content_inline_start, _, content_inline_size, _) =
+            logical_rect(containing_block.content, horizontal);
+        let inline_range = (
+            content_inline_start,
+            content_inline_start + content_inline_size,
+        );
+        let d = self.physical_dimensions();
+        let (_, margin_box_block_start, _, margin_box_block_size) =
+            logical_rect(d.margin_box(), horizontal);
+        let (_, border_box_block_start, _, border_box_block_size) =
+            logical_rect(d.border_box(), horizontal);
+        let margin_block_start = border_box_block_start - margin_box_block_start;
+        let margin_block_end = margin_box_block_start + margin_box_block_size
+            - border_box_block_start
+            - border_box_block_size;
+        // The box is laid out with its margin box right after the content of its containing
+        // block, so move its border box past the margins before it, or down to where its
+        // clearance puts it.
+        let flow_block_start = margin_box_block_start
+            + match clearance {
+                Some(clearance) => clearance + margin_block_start,
+                None => margin_before
+                    .adjoin(CollapsedMargin::new(margin_block_start))
+                    .solve(),
+            };
+        let mut block_start = flow_block_start;
+        let mut band = inline_range;
+        let mut relaid_out = false;
+        loop {
+            let (inline_start, _, inline_size, block_size) =
+                logical_rect(self.physical_dimensions().border_box(), horizontal);
+            let available = floats.available_range(block_start, block_size, inline_range);
+            if available.0 <= inline_start && inline_start + inline_size <= available.1 {
+                break;
+            }
+            if available != band && !relaid_out {
+                // Try again with the box shrunk into the space left by the floats.
+                band = available;
+                relaid_out = true;
+                let mut narrowed = containing_block;
+                let (band_start, band_end) = band;
+                if horizontal {
+                    narrowed.content.start_x = band_start;
+                    narrowed.content.width = band_end - band_start;
+                } else {
+                    narrowed.content.start_y = band_start;
+                    narrowed.content.height = band_end - band_start;
+                }
+                // Children are stacked after the current block size, so start from an empty
+                // content box.
+                self.dimensions.set_block_size(Au(0));
+                self.layout(narrowed, scale_factor);
+                continue;
+            }
+            match floats.next_float_end(block_start) {
+                Some(next_float_end) => {
+                    block_start = next_float_end;
+                    relaid_out = false;
+                }
+                None => break,
+            }
+        }
+
+        let (_, current_block_start, _, _) =
+            logical_rect(self.physical_dimensions().border_box(), horizontal);
+        let offset = block_start - current_block_start;
+        if horizontal {
+            self.translate(Au(0), offset);
+        } else {
+            self.translate(offset, Au(0));
+        }
+        (
+            CollapsedMargin::new(margin_block_end),
+            block_start - flow_block_start,
+        )
+    }
+}
@@ -0,0 +1,197 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-break-3/
+//  * https://drafts.csswg.org/css-page-3/
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::style::values::computed::{BreakBetween, BreakInside};
+use app_units::Au;
+
+/// A slice of the block flow of a laid out tree that fits on one page, from `start_y` up to (but
+/// not including) `end_y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageFragment {
+    pub start_y: Au,
+    pub end_y: Au,
+}
+
+impl PageFragment {
+    pub fn height(&self) -> Au {
+        self.end_y - self.start_y
+    }
+}
+
+/// A place in the block flow where a page break may happen.
+///
+/// https://drafts.csswg.org/css-break-3/#possible-breaks
+#[derive(Clone, Copy, Debug)]
+struct BreakPoint {
+    y: Au,
+    /// Whether a page break must happen here.
+    forced: bool,
+    /// Whether a page break should only happen here if there's no better place for it.
+    avoided: bool,
+}
+
+/// Fragments the block flow of `layout_tree`, which must already be laid out at the width of the
+/// page area, into pages that are at most `page_height` tall.  Each page ends at its first forced
+/// break, or else at the last break point that fits on it, preferring those that aren't avoided.
+/// Content that can't be broken anywhere is sliced at the end of the page.
+///
+/// Break points are only found between the in-flow block-level siblings of block containers, at
+/// the border box edge of the later sibling, so that the margins adjoining a break are truncated.
+///
+/// TODO: Break between line boxes, and within flex, grid, and table layouts.  Content should also
+/// be laid out again after a break, rather than continuing where it was laid out on one long page.
+/// This also assumes a horizontal writing mode.
+pub fn paginate(layout_tree: &LayoutBox, page_height: Au) -> Vec<PageFragment> {
+    let content_end = content_end(layout_tree);
+    if page_height <= Au(0) {
+        return vec![PageFragment {
+            start_y: Au(0),
+            end_y: content_end,
+        }];
+    }
+    let mut break_points = Vec::new();
+    // The root of the tree is the box of the document, which contains the box of the root element.
+    for child in layout_tree.children() {
+        collect_break_points(child, false, &mut break_points);
+    }
+    break_points.sort_by_key(|point| point.y);
+
+    let mut pages = Vec::new();
+    let mut page_top = Au(0);
+    loop {
+        let limit = page_top + page_height;
+        let mut candidates = break_points
+            .iter()
+            .filter(|point| point.y > page_top && point.y <= limit);
+        let page_end = if let Some(forced) = candidates.clone().find(|point| point.forced) {
+            forced.y
+        } else if content_end <= limit {
+            pages.push(PageFragment {
+                start_y: page_top,
+                end_y: content_end.max(page_top),
+            });
+            return pages;
+        } else if let Some(point) = candidates.clone().rev().find(|point| !point.avoided) {
+            point.y
+        } else if let Some(point) = candidates.next_back() {
+            point.y
+        } else {
+            limit
+        };
+        pages.push(PageFragment {
+            start_y: page_top,
+            end_y: page_end,
+        });
+        page_top = page_end;
+    }
+}
+
+/// The bottom edge of the lowest margin box in the subtree of `layout_box`.
+fn content_end(layout_box: &LayoutBox) -> Au {
+    layout_box.children().iter().map(content_end).fold(
+        layout_box.physical_dimensions().margin_box().end_y(),
+        Au::max,
+    )
+}
+
+/// Collects the break points between the in-flow block-level children of `layout_box`, and those
+/// of its descendants.  `inside_avoided` is whether an ancestor avoids page breaks within itself.
+fn collect_break_points(
+    layout_box: &LayoutBox,
+    inside_avoided: bool,
+    points: &mut Vec<BreakPoint>,
+) {
+    if !matches!(layout_box.box_type(), BoxType::Block | BoxType::Anonymous) {
+        return;
+    }
+    let inside_avoided = inside_avoided || break_inside(layout_box).avoids_page_break();
+    let mut previous: Option<&LayoutBox> = None;
+    for child in in_flow_block_level_children(layout_box) {
+        if let Some(previous) = previous {
+            let value = combine(break_after(previous), break_before(child));
+            points.push(BreakPoint {
+                y: child.physical_dimensions().border_box().start_y,
+                forced: value.forces_page_break(),
+                avoided: inside_avoided || value.avoids_page_break(),
+            });
+        }
+        collect_break_points(child, inside_avoided, points);
+        previous = Some(child);
+    }
+}
+
+fn in_flow_block_level_children(layout_box: &LayoutBox) -> impl Iterator<Item = &LayoutBox> {
+    layout_box.children().iter().filter(|child| {
+        matches!(
+            child.box_type(),
+            BoxType::Anonymous
+                | BoxType::Block
+                | BoxType::Flex
+                | BoxType::Grid
+                | BoxType::Replaced
+                | BoxType::TableWrapper
+        ) && !child.is_floated()
+            && !child.is_absolutely_positioned()
+    })
+}
+
+/// The `break-before` value of `layout_box`, combined with the one propagated from its first
+/// in-flow block-level child.
+///
+/// https://drafts.csswg.org/css-break-3/#break-propagation
+fn break_before(layout_box: &LayoutBox) -> BreakBetween {
+    let own = if layout_box.is_anonymous() {
+        BreakBetween::Auto
+    } else {
+        layout_box.computed_values().break_before
+    };
+    match in_flow_block_level_children(layout_box).next() {
+        Some(first_child) if propagates_breaks(layout_box) => {
+            combine(own, break_before(first_child))
+        }
+        _ => own,
+    }
+}
+
+/// The `break-after` value of `layout_box`, combined with the one propagated from its last
+/// in-flow block-level child.
+fn break_after(layout_box: &LayoutBox) -> BreakBetween {
+    let own = if layout_box.is_anonymous() {
+        BreakBetween::Auto
+    } else {
+        layout_box.computed_values().break_after
+    };
+    match in_flow_block_level_children(layout_box).last() {
+        Some(last_child) if propagates_breaks(layout_box) => combine(own, break_after(last_child)),
+        _ => own,
+    }
+}
+
+fn break_inside(layout_box: &LayoutBox) -> BreakInside {
+    if layout_box.is_anonymous() {
+        BreakInside::Auto
+    } else {
+        layout_box.computed_values().break_inside
+    }
+}
+
+/// Only block containers have break points among their children, so they're the only boxes that
+/// propagate the breaks of their children to their own edges.
+fn propagates_breaks(layout_box: &LayoutBox) -> bool {
+    matches!(layout_box.box_type(), BoxType::Block | BoxType::Anonymous)
+}
+
+/// Combines the break values that apply at the same break point.  Forced breaks take precedence
+/// over avoided ones, which take precedence over `auto`.
+fn combine(first: BreakBetween, second: BreakBetween) -> BreakBetween {
+    if first.forces_page_break() {
+        first
+    } else if second.forces_page_break() || second.avoids_page_break() {
+        second
+    } else if first.avoids_page_break() {
+        first
+    } else {
+        BreakBetween::Auto
+    }
+}
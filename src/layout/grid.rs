@@ -0,0 +1,1250 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-grid-1/#layout-algorithm
+//  * https://drafts.csswg.org/css-grid-1/#auto-placement-algo
+//  * https://drafts.csswg.org/css-grid-1/#algo-track-sizing
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::flex::Distribution;
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::LogicalDirection;
+use crate::layout::{resolve_length_percentage, resolve_size};
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::{
+    AlignContent, AlignItems, AlignSelf, ComputedValues, GridLine, GridTemplateAreas,
+    GridTemplateComponent, ImplicitGridTracks, JustifyContent, Order, RepeatCount, Size,
+    TrackBreadth, TrackList, TrackListValue, TrackRepeat, TrackSize,
+};
+use crate::style::values::CSSFloat;
+use crate::Side;
+use app_units::Au;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// The properties of a grid item that the grid layout algorithm depends on.
+#[derive(Clone, Debug)]
+struct GridItemStyle {
+    order: Order,
+    row_start: GridLine,
+    row_end: GridLine,
+    column_start: GridLine,
+    column_end: GridLine,
+    align_self: AlignSelf,
+    inline_size: Size,
+    block_size: Size,
+    /// Whether the inline-start, inline-end, block-start, and block-end margins are `auto`.
+    auto_margins: [bool; 4],
+}
+
+impl GridItemStyle {
+    fn of(layout_box: &LayoutBox) -> GridItemStyle {
+        // Like anonymous flex items, anonymous grid items take the initial values of every
+        // property, and so are always auto-placed.
+        if layout_box.box_type == BoxType::Anonymous {
+            return GridItemStyle::from(&ComputedValues::default());
+        }
+        GridItemStyle::from(&*layout_box.computed_values())
+    }
+}
+
+impl From<&ComputedValues> for GridItemStyle {
+    fn from(cvs: &ComputedValues) -> Self {
+        let auto = LengthPercentageOrAuto::Auto;
+        GridItemStyle {
+            order: cvs.order,
+            row_start: cvs.grid_row_start.clone(),
+            row_end: cvs.grid_row_end.clone(),
+            column_start: cvs.grid_column_start.clone(),
+            column_end: cvs.grid_column_end.clone(),
+            align_self: cvs.align_self,
+            inline_size: cvs.logical_width(),
+            block_size: cvs.logical_height(),
+            auto_margins: [
+                cvs.logical_margin(Side::Left) == auto,
+                cvs.logical_margin(Side::Right) == auto,
+                cvs.logical_margin(Side::Top) == auto,
+                cvs.logical_margin(Side::Bottom) == auto,
+            ],
+        }
+    }
+}
+
+/// The explicit grid of a grid container in a single axis.  Lines are identified by their
+/// 0-based index, so the explicit grid runs from line 0 to line `track_count()`, and lines
+/// before the explicit grid have negative indices.
+///
+/// https://drafts.csswg.org/css-grid-1/#explicit-grids
+#[derive(Clone, Debug)]
+struct ExplicitGrid {
+    track_sizes: Vec<TrackSize>,
+    /// The names of each explicit line.  There is always one more entry here than in
+    /// `track_sizes`.
+    line_names: Vec<Vec<String>>,
+    /// The sizes of tracks in the implicit grid, given by `grid-auto-rows` or
+    /// `grid-auto-columns`.
+    implicit_track_sizes: Vec<TrackSize>,
+}
+
+/// The resolved position of a grid item in a single axis.
+///
+/// https://drafts.csswg.org/css-grid-1/#line-placement
+#[derive(Clone, Debug)]
+enum AxisPlacement {
+    /// The item is placed between the given lines.
+    Definite(Range<i32>),
+    /// The item needs to be auto-placed, and spans the given number of tracks.
+    Auto(i32),
+}
+
+/// A grid item that's been placed in the grid, with the indices of the tracks it spans in each
+/// axis.
+#[derive(Clone, Debug)]
+struct PlacedItem {
+    /// The index of the item's box amongst the children of the grid container.
+    index: usize,
+    rows: Range<usize>,
+    columns: Range<usize>,
+}
+
+/// The result of placing the items of a grid container, in order-modified document order.
+#[derive(Clone, Debug)]
+struct GridPlacement {
+    items: Vec<PlacedItem>,
+    row_sizes: Vec<TrackSize>,
+    column_sizes: Vec<TrackSize>,
+}
+
+/// The space that a grid item contributes to the tracks it spans, measured as the size of its
+/// margin box.
+#[derive(Clone, Debug)]
+struct Contribution {
+    tracks: Range<usize>,
+    min_content: Au,
+    max_content: Au,
+}
+
+impl LayoutBox {
+    /// Assuming `self` is a block-level grid container, calculate the dimensions of this box and
+    /// its grid items.
+    pub(super) fn layout_grid(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) {
+        self.calculate_block_logical_width(containing_block, scale_factor);
+        self.calculate_block_position(containing_block, scale_factor);
+        let definite_block_size = self.resolve_container_sizes(containing_block, scale_factor);
+        self.layout_grid_items(definite_block_size, scale_factor);
+        self.apply_physical_properties(containing_block, scale_factor);
+    }
+
+    /// Runs the grid layout algorithm over the children of `self`, which must already have its
+    /// inline size and position set.  Sets the block size of `self`, which is given by
+    /// `definite_block_size` if it's `Some`.
+    ///
+    /// Layout is done in terms of the logical axes of the container, so columns run along the
+    /// inline axis and rows along the block axis.
+    /// TODO: Grid items with a writing mode orthogonal to their container, `justify-self`, and
+    /// `min-*`/`max-*` sizes aren't supported.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#layout-algorithm
+    pub(super) fn layout_grid_items(&mut self, definite_block_size: Option<Au>, scale_factor: f32) {
+        let zero = Au(0);
+        let container = self.dimensions.physical();
+        let inline_size = self.dimensions.get_inline_size();
+        let (justify_content, align_content, align_items, column_gap, row_gap) = {
+            let cvs = self.computed_values();
+            (
+                cvs.justify_content,
+                cvs.align_content,
+                cvs.align_items,
+                resolve_length_percentage(cvs.column_gap.size, inline_size, scale_factor),
+                // Percentage row gaps resolve to zero if the block size of the container is
+                // indefinite.
+                resolve_size(
+                    LengthPercentageOrAuto::LengthPercentage(cvs.row_gap.size),
+                    definite_block_size,
+                    scale_factor,
+                )
+                .unwrap_or(zero),
+            )
+        };
+        let placement = self.place_grid_items(
+            Some(inline_size),
+            definite_block_size,
+            column_gap,
+            row_gap,
+            scale_factor,
+        );
+        let styles = placement
+            .items
+            .iter()
+            .map(|item| GridItemStyle::of(&self.children[item.index]))
+            .collect::<Vec<_>>();
+
+        // Size the columns, and distribute any remaining space between them according to
+        // `justify-content`.
+        let contributions = placement
+            .items
+            .iter()
+            .map(|item| {
+                let (min_content, max_content) =
+                    self.children[item.index].intrinsic_inline_contributions(scale_factor);
+                Contribution {
+                    tracks: item.columns.clone(),
+                    min_content,
+                    max_content,
+                }
+            })
+            .collect::<Vec<_>>();
+        let columns = size_tracks(
+            &placement.column_sizes,
+            &contributions,
+            Some(inline_size),
+            column_gap,
+            false,
+            justify_content == JustifyContent::Normal,
+            scale_factor,
+        );
+        let justify_distribution = match justify_content {
+            JustifyContent::Normal | JustifyContent::Start | JustifyContent::FlexStart => {
+                Distribution::Start
+            }
+            JustifyContent::End | JustifyContent::FlexEnd => Distribution::End,
+            JustifyContent::Center => Distribution::Center,
+            JustifyContent::SpaceBetween => Distribution::SpaceBetween,
+            JustifyContent::SpaceAround => Distribution::SpaceAround,
+            JustifyContent::SpaceEvenly => Distribution::SpaceEvenly,
+        };
+        let column_positions =
+            track_positions(&columns, inline_size, column_gap, justify_distribution);
+
+        // Lay each item out within the columns it spans to find its contribution to the rows.
+        // Items with an `auto` inline size are stretched to fill their grid area.
+        let horizontal = self.writing_mode.is_horizontal();
+        let mut stretched_inline_sizes = Vec::with_capacity(placement.items.len());
+        let mut contributions = Vec::with_capacity(placement.items.len());
+        for (item, style) in placement.items.iter().zip(&styles) {
+            let (column_start, column_size) =
+                span_extent(&column_positions, &columns, &item.columns);
+            let area = grid_area(
+                container,
+                horizontal,
+                (column_start, column_size),
+                (zero, definite_block_size.unwrap_or(zero)),
+            );
+            let child = &mut self.children[item.index];
+            child.layout_container_item(area, None, None, scale_factor);
+            let stretched_inline_size = if style.inline_size == Size::Auto
+                && !style.auto_margins[0]
+                && !style.auto_margins[1]
+            {
+                let inline_edges = child
+                    .dimensions
+                    .get_edges_sum(LogicalDirection::InlineStart)
+                    + child.dimensions.get_edges_sum(LogicalDirection::InlineEnd);
+                let inline_size = (column_size - inline_edges).max(Au(0));
+                child.layout_container_item(area, Some(inline_size), None, scale_factor);
+                Some(inline_size)
+            } else {
+                None
+            };
+            stretched_inline_sizes.push(stretched_inline_size);
+            let block_size = child.dimensions.margin_box_block_size();
+            contributions.push(Contribution {
+                tracks: item.rows.clone(),
+                min_content: block_size,
+                max_content: block_size,
+            });
+        }
+
+        // Size the rows, which determines the block size of the container unless it's definite,
+        // and distribute any remaining space between them according to `align-content`.
+        let rows = size_tracks(
+            &placement.row_sizes,
+            &contributions,
+            definite_block_size,
+            row_gap,
+            false,
+            align_content == AlignContent::Normal || align_content == AlignContent::Stretch,
+            scale_factor,
+        );
+        let block_size = definite_block_size.unwrap_or_else(|| sum_with_gaps(&rows, row_gap));
+        let align_distribution = match align_content {
+            AlignContent::Normal
+            | AlignContent::Stretch
+            | AlignContent::Start
+            | AlignContent::FlexStart => Distribution::Start,
+            AlignContent::End | AlignContent::FlexEnd => Distribution::End,
+            AlignContent::Center => Distribution::Center,
+            AlignContent::SpaceBetween => Distribution::SpaceBetween,
+            AlignContent::SpaceAround => Distribution::SpaceAround,
+            AlignContent::SpaceEvenly => Distribution::SpaceEvenly,
+        };
+        let row_positions = track_positions(&rows, block_size, row_gap, align_distribution);
+
+        // Align each item within its grid area, and move it into place.
+        for ((item, style), stretched_inline_size) in placement
+            .items
+            .iter()
+            .zip(&styles)
+            .zip(stretched_inline_sizes)
+        {
+            let (column_start, column_size) =
+                span_extent(&column_positions, &columns, &item.columns);
+            let (row_start, row_size) = span_extent(&row_positions, &rows, &item.rows);
+            let area = grid_area(
+                container,
+                horizontal,
+                (column_start, column_size),
+                (row_start, row_size),
+            );
+            let child = &mut self.children[item.index];
+            let align = style.align_self.resolve(align_items);
+            let has_auto_block_margin = style.auto_margins[2] || style.auto_margins[3];
+            let stretches = (align == AlignItems::Normal || align == AlignItems::Stretch)
+                && style.block_size == Size::Auto
+                && !has_auto_block_margin;
+            if stretches {
+                let block_edges = child.dimensions.get_edges_sum(LogicalDirection::BlockStart)
+                    + child.dimensions.get_edges_sum(LogicalDirection::BlockEnd);
+                let block_size = (row_size - block_edges).max(Au(0));
+                child.layout_container_item(
+                    area,
+                    stretched_inline_size,
+                    Some(block_size),
+                    scale_factor,
+                );
+            }
+
+            let free_block_space = row_size - child.dimensions.margin_box_block_size();
+            let block_offset = if has_auto_block_margin && free_block_space > Au(0) {
+                auto_margin_offset(
+                    free_block_space,
+                    style.auto_margins[2],
+                    style.auto_margins[3],
+                )
+            } else {
+                let distribution = match align {
+                    AlignItems::Normal
+                    | AlignItems::Stretch
+                    | AlignItems::Start
+                    | AlignItems::FlexStart
+                    | AlignItems::Baseline => Distribution::Start,
+                    AlignItems::End | AlignItems::FlexEnd => Distribution::End,
+                    AlignItems::Center => Distribution::Center,
+                };
+                distribution.offsets(free_block_space, 1).0
+            };
+            let free_inline_space = column_size - child.dimensions.margin_box_inline_size();
+            let inline_offset = if free_inline_space > Au(0) {
+                auto_margin_offset(
+                    free_inline_space,
+                    style.auto_margins[0],
+                    style.auto_margins[1],
+                )
+            } else {
+                zero
+            };
+
+            let inline_offset = column_start + inline_offset;
+            let block_offset = row_start + block_offset;
+            let (target_x, target_y) = if horizontal {
+                (
+                    container.content.start_x + inline_offset,
+                    container.content.start_y + block_offset,
+                )
+            } else {
+                (
+                    container.content.start_x + block_offset,
+                    container.content.start_y + inline_offset,
+                )
+            };
+            let margin_box = child.physical_dimensions().margin_box();
+            child.translate(target_x - margin_box.start_x, target_y - margin_box.start_y);
+        }
+
+        self.dimensions.set_block_size(block_size);
+        self.set_container_static_positions();
+    }
+
+    /// Resolves the explicit grid of `self` and places each of its children in the grid, using
+    /// the given available space to determine the number of auto-repeated tracks.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#placement
+    fn place_grid_items(
+        &self,
+        available_inline_size: Option<Au>,
+        available_block_size: Option<Au>,
+        column_gap: Au,
+        row_gap: Au,
+        scale_factor: f32,
+    ) -> GridPlacement {
+        let cvs = self.computed_values();
+        let no_areas = Vec::new();
+        let (area_rows, area_columns, areas) = match &cvs.grid_template_areas {
+            GridTemplateAreas::None => (0, 0, &no_areas),
+            GridTemplateAreas::Areas {
+                rows,
+                columns,
+                areas,
+            } => (*rows as usize, *columns as usize, areas),
+        };
+        let rows = ExplicitGrid::new(
+            &cvs.grid_template_rows,
+            &cvs.grid_auto_rows,
+            area_rows,
+            areas.iter().map(|area| (&area.name, area.rows.clone())),
+            available_block_size,
+            row_gap,
+            scale_factor,
+        );
+        let columns = ExplicitGrid::new(
+            &cvs.grid_template_columns,
+            &cvs.grid_auto_columns,
+            area_columns,
+            areas.iter().map(|area| (&area.name, area.columns.clone())),
+            available_inline_size,
+            column_gap,
+            scale_factor,
+        );
+
+        // Resolve the definite positions of each item, in order-modified document order.  The
+        // auto-placement algorithm works in terms of the major axis, along which the cursor moves
+        // when it runs out of room, and the minor axis, along which it fills each row or column.
+        let auto_flow = cvs.grid_auto_flow;
+        let styles = self
+            .children
+            .iter()
+            .map(GridItemStyle::of)
+            .collect::<Vec<_>>();
+        // Absolutely positioned children don't participate in grid layout.
+        let mut order = (0..self.children.len())
+            .filter(|&idx| !self.children[idx].is_absolutely_positioned())
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&idx| styles[idx].order);
+        let placements = order
+            .iter()
+            .map(|&idx| {
+                let style = &styles[idx];
+                let row = rows.resolve_placement(&style.row_start, &style.row_end);
+                let column = columns.resolve_placement(&style.column_start, &style.column_end);
+                if auto_flow.column {
+                    [column, row]
+                } else {
+                    [row, column]
+                }
+            })
+            .collect::<Vec<_>>();
+        let explicit_minor_tracks = if auto_flow.column {
+            rows.track_count()
+        } else {
+            columns.track_count()
+        };
+        let areas = auto_place(&placements, explicit_minor_tracks, auto_flow.dense);
+
+        // Size the implicit grid to fit every item, and convert from line indices to the indices
+        // of tracks in the implicit grid.
+        let (row_areas, column_areas): (Vec<_>, Vec<_>) = areas
+            .into_iter()
+            .map(|[major, minor]| {
+                if auto_flow.column {
+                    (minor, major)
+                } else {
+                    (major, minor)
+                }
+            })
+            .unzip();
+        let row_lines = rows.implicit_lines(&row_areas);
+        let column_lines = columns.implicit_lines(&column_areas);
+        let to_tracks = |lines: &Range<i32>, area: &Range<i32>| {
+            (area.start - lines.start) as usize..(area.end - lines.start) as usize
+        };
+        let items = order
+            .into_iter()
+            .zip(row_areas.iter().zip(&column_areas))
+            .map(|(index, (row_area, column_area))| PlacedItem {
+                index,
+                rows: to_tracks(&row_lines, row_area),
+                columns: to_tracks(&column_lines, column_area),
+            })
+            .collect();
+        GridPlacement {
+            items,
+            row_sizes: row_lines.map(|line| rows.track_size(line)).collect(),
+            column_sizes: column_lines.map(|line| columns.track_size(line)).collect(),
+        }
+    }
+
+    /// Calculates the min-content and max-content inline sizes of a grid container's content,
+    /// which are the sums of its columns when sized under a min-content or max-content
+    /// constraint.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#intrinsic-sizes
+    pub(super) fn grid_intrinsic_content_inline_sizes(&self, scale_factor: f32) -> (Au, Au) {
+        let zero = Au(0);
+        // Percentage gaps can't be resolved without a containing block, so treat them as zero.
+        let column_gap = match self.computed_values().column_gap.size {
+            LengthPercentage::Length(len) => Au::from(len * scale_factor),
+            LengthPercentage::Percentage(_) => zero,
+        };
+        let placement = self.place_grid_items(None, None, column_gap, zero, scale_factor);
+        let contributions = placement
+            .items
+            .iter()
+            .map(|item| {
+                let (min_content, max_content) =
+                    self.children[item.index].intrinsic_inline_contributions(scale_factor);
+                Contribution {
+                    tracks: item.columns.clone(),
+                    min_content,
+                    max_content,
+                }
+            })
+            .collect::<Vec<_>>();
+        let size_columns = |min_content| {
+            let columns = size_tracks(
+                &placement.column_sizes,
+                &contributions,
+                None,
+                column_gap,
+                min_content,
+                false,
+                scale_factor,
+            );
+            sum_with_gaps(&columns, column_gap)
+        };
+        (size_columns(true), size_columns(false))
+    }
+}
+
+impl ExplicitGrid {
+    /// Expands a `grid-template-rows` or `grid-template-columns` value into the explicit tracks
+    /// of a single axis, extended to cover `area_count` tracks of `grid-template-areas`.  Each
+    /// named area also defines implicitly-named lines at its edges.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#implicit-named-lines
+    fn new<'a>(
+        template: &GridTemplateComponent,
+        implicit_tracks: &ImplicitGridTracks,
+        area_count: usize,
+        areas: impl Iterator<Item = (&'a String, Range<u32>)>,
+        available_size: Option<Au>,
+        gap: Au,
+        scale_factor: f32,
+    ) -> ExplicitGrid {
+        let mut track_sizes = Vec::new();
+        let mut line_names = vec![Vec::new()];
+        if let GridTemplateComponent::TrackList(list) = template {
+            for (names, value) in list.line_names.iter().zip(&list.values) {
+                line_names.last_mut().unwrap().extend(names.iter().cloned());
+                match value {
+                    TrackListValue::TrackSize(size) => {
+                        track_sizes.push(*size);
+                        line_names.push(Vec::new());
+                    }
+                    TrackListValue::Repeat(repeat) => {
+                        let count = match repeat.count {
+                            RepeatCount::Number(count) => count as usize,
+                            // TODO: Empty tracks repeated by `auto-fit` should be collapsed.
+                            RepeatCount::AutoFill | RepeatCount::AutoFit => {
+                                auto_repeat_count(list, repeat, available_size, gap, scale_factor)
+                            }
+                        };
+                        for _ in 0..count {
+                            for (names, size) in repeat.line_names.iter().zip(&repeat.track_sizes) {
+                                line_names.last_mut().unwrap().extend(names.iter().cloned());
+                                track_sizes.push(*size);
+                                line_names.push(Vec::new());
+                            }
+                            let trailing_names = repeat.line_names.last().into_iter().flatten();
+                            line_names
+                                .last_mut()
+                                .unwrap()
+                                .extend(trailing_names.cloned());
+                        }
+                    }
+                }
+            }
+            let trailing_names = list.line_names.last().into_iter().flatten();
+            line_names
+                .last_mut()
+                .unwrap()
+                .extend(trailing_names.cloned());
+        }
+
+        // Named areas that extend past the tracks defined by the template add tracks sized like
+        // those of the implicit grid.
+        let implicit_track_sizes = implicit_tracks.0.clone();
+        let template_tracks = track_sizes.len();
+        while track_sizes.len() < area_count {
+            let idx = (track_sizes.len() - template_tracks) % implicit_track_sizes.len();
+            track_sizes.push(implicit_track_sizes[idx]);
+            line_names.push(Vec::new());
+        }
+        for (name, tracks) in areas {
+            line_names[tracks.start as usize].push(format!("{}-start", name));
+            line_names[tracks.end as usize].push(format!("{}-end", name));
+        }
+
+        ExplicitGrid {
+            track_sizes,
+            line_names,
+            implicit_track_sizes,
+        }
+    }
+
+    fn track_count(&self) -> i32 {
+        self.track_sizes.len() as i32
+    }
+
+    /// Gets the size of the track that starts at the given line.  Implicit tracks cycle through
+    /// the implicit track sizes, forwards after the explicit grid and backwards before it.
+    fn track_size(&self, line: i32) -> TrackSize {
+        let implicit_count = self.implicit_track_sizes.len() as i32;
+        if line < 0 {
+            let idx = implicit_count - 1 - (-line - 1) % implicit_count;
+            self.implicit_track_sizes[idx as usize]
+        } else if line < self.track_count() {
+            self.track_sizes[line as usize]
+        } else {
+            let idx = (line - self.track_count()) % implicit_count;
+            self.implicit_track_sizes[idx as usize]
+        }
+    }
+
+    /// Gets the indices of the explicit lines with the given name, in ascending order.
+    fn lines_named(&self, name: &str) -> Vec<i32> {
+        self.line_names
+            .iter()
+            .enumerate()
+            .filter(|(_, names)| names.iter().any(|line_name| line_name == name))
+            .map(|(idx, _)| idx as i32)
+            .collect()
+    }
+
+    /// Finds the `n`th line with the given name after `line`.  Every implicit line is assumed to
+    /// have the name if there aren't enough explicit lines with it.
+    fn nth_named_line_after(&self, name: &str, line: i32, n: u32) -> i32 {
+        let lines = self
+            .lines_named(name)
+            .into_iter()
+            .filter(|&named_line| named_line > line)
+            .collect::<Vec<_>>();
+        match lines.get(n as usize - 1) {
+            Some(&named_line) => named_line,
+            None => line.max(self.track_count()) + (n as usize - lines.len()) as i32,
+        }
+    }
+
+    /// Finds the `n`th line with the given name before `line`.  Every implicit line is assumed
+    /// to have the name if there aren't enough explicit lines with it.
+    fn nth_named_line_before(&self, name: &str, line: i32, n: u32) -> i32 {
+        let lines = self
+            .lines_named(name)
+            .into_iter()
+            .rev()
+            .filter(|&named_line| named_line < line)
+            .collect::<Vec<_>>();
+        match lines.get(n as usize - 1) {
+            Some(&named_line) => named_line,
+            None => line.min(0) - (n as usize - lines.len()) as i32,
+        }
+    }
+
+    /// Resolves a line-based placement property to a line index, returning `None` if it doesn't
+    /// refer to a specific line.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#line-placement
+    fn resolve_line(&self, line: &GridLine, is_start: bool) -> Option<i32> {
+        match line {
+            GridLine::Auto | GridLine::Span(..) => None,
+            GridLine::Line(n, None) if *n > 0 => Some(n - 1),
+            GridLine::Line(n, None) => Some(self.track_count() + 1 + n),
+            GridLine::Line(n, Some(name)) if *n > 0 => {
+                Some(self.nth_named_line_after(name, -1, *n as u32))
+            }
+            GridLine::Line(n, Some(name)) => {
+                Some(self.nth_named_line_before(name, self.track_count() + 1, -n as u32))
+            }
+            GridLine::Ident(name) => {
+                // A name refers to the edge of the named area with that name, or else the first
+                // line with that name.
+                let suffix = if is_start { "start" } else { "end" };
+                let area_edge = self.lines_named(&format!("{}-{}", name, suffix));
+                Some(match area_edge.first() {
+                    Some(&line) => line,
+                    None => self.nth_named_line_after(name, -1, 1),
+                })
+            }
+        }
+    }
+
+    /// Resolves the placement of a grid item in this axis from the values of its
+    /// `grid-*-start` and `grid-*-end` properties.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#grid-placement-errors
+    fn resolve_placement(&self, start: &GridLine, end: &GridLine) -> AxisPlacement {
+        match (
+            self.resolve_line(start, true),
+            self.resolve_line(end, false),
+        ) {
+            (Some(start), Some(end)) if start == end => AxisPlacement::Definite(start..start + 1),
+            (Some(start), Some(end)) => AxisPlacement::Definite(start.min(end)..start.max(end)),
+            (Some(start), None) => AxisPlacement::Definite(match end {
+                GridLine::Span(n, None) => start..start + *n as i32,
+                GridLine::Span(n, Some(name)) => start..self.nth_named_line_after(name, start, *n),
+                _ => start..start + 1,
+            }),
+            (None, Some(end)) => AxisPlacement::Definite(match start {
+                GridLine::Span(n, None) => end - *n as i32..end,
+                GridLine::Span(n, Some(name)) => self.nth_named_line_before(name, end, *n)..end,
+                _ => end - 1..end,
+            }),
+            // Named spans are treated as spanning a single track when auto-placed.
+            (None, None) => AxisPlacement::Auto(match (start, end) {
+                (GridLine::Span(n, None), _) => *n as i32,
+                (GridLine::Auto, GridLine::Span(n, None)) => *n as i32,
+                _ => 1,
+            }),
+        }
+    }
+
+    /// Gets the range of lines in the implicit grid, which includes the explicit grid and every
+    /// placed item.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#implicit-grids
+    fn implicit_lines(&self, areas: &[Range<i32>]) -> Range<i32> {
+        areas.iter().fold(0..self.track_count(), |lines, area| {
+            lines.start.min(area.start)..lines.end.max(area.end)
+        })
+    }
+}
+
+/// Determines the number of repetitions of an `auto-fill` or `auto-fit` repeat, which is the
+/// largest number that doesn't overflow the available space, treating each track as its fixed
+/// size.  At least one repetition is always made.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-repeat
+fn auto_repeat_count(
+    list: &TrackList,
+    repeat: &TrackRepeat,
+    available_size: Option<Au>,
+    gap: Au,
+    scale_factor: f32,
+) -> usize {
+    let available_size = match available_size {
+        Some(size) => size,
+        None => return 1,
+    };
+    let zero = Au(0);
+    let fixed_size = |size: &TrackSize| {
+        fixed_breadth(size.max, Some(available_size), scale_factor)
+            .or_else(|| fixed_breadth(size.min, Some(available_size), scale_factor))
+            .unwrap_or(zero)
+    };
+    let mut other_size = zero;
+    let mut other_count = 0;
+    for value in &list.values {
+        if let TrackListValue::TrackSize(size) = value {
+            other_size += fixed_size(size);
+            other_count += 1;
+        }
+    }
+    let repetition_size = repeat
+        .track_sizes
+        .iter()
+        .fold(zero, |acc, size| acc + fixed_size(size))
+        + gap * repeat.track_sizes.len() as i32;
+    // There's one fewer gap than there are tracks.
+    let free_space = available_size - other_size - gap * (other_count - 1);
+    if repetition_size <= zero {
+        return 1;
+    }
+    ((free_space.to_f32_px() / repetition_size.to_f32_px()).floor() as usize).max(1)
+}
+
+/// Runs the auto-placement algorithm over the given placements, which are in order-modified
+/// document order with their major axis first.  Returns the lines spanned by each item in the
+/// major and minor axes.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-placement-algo
+fn auto_place(
+    placements: &[[AxisPlacement; 2]],
+    explicit_minor_tracks: i32,
+    dense: bool,
+) -> Vec<[Range<i32>; 2]> {
+    let overlaps = |a: &Range<i32>, b: &Range<i32>| a.start < b.end && b.start < a.end;
+    let is_free = |areas: &[Option<[Range<i32>; 2]>], major: &Range<i32>, minor: &Range<i32>| {
+        !areas
+            .iter()
+            .flatten()
+            .any(|area| overlaps(&area[0], major) && overlaps(&area[1], minor))
+    };
+
+    // Step 0: Find the lines of the implicit grid in the minor axis, which only grows to fit
+    // definitely-placed items and the largest auto-placed span.  The start of the major axis is
+    // also needed to initialize the auto-placement cursor.
+    let mut major_start = 0;
+    let mut minor_lines = 0..explicit_minor_tracks;
+    for placement in placements {
+        if let AxisPlacement::Definite(major) = &placement[0] {
+            major_start = major_start.min(major.start);
+        }
+        if let AxisPlacement::Definite(minor) = &placement[1] {
+            minor_lines = minor_lines.start.min(minor.start)..minor_lines.end.max(minor.end);
+        }
+    }
+    for placement in placements {
+        if let AxisPlacement::Auto(span) = placement[1] {
+            minor_lines.end = minor_lines.end.max(minor_lines.start + span);
+        }
+    }
+
+    // Step 1: Position anything that's not auto-placed.
+    let mut areas = placements
+        .iter()
+        .map(|placement| match placement {
+            [AxisPlacement::Definite(major), AxisPlacement::Definite(minor)] => {
+                Some([major.clone(), minor.clone()])
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Step 2: Process the items locked to a given row (or column, for column flow).
+    let mut cursors = HashMap::new();
+    for (idx, placement) in placements.iter().enumerate() {
+        if let [AxisPlacement::Definite(major), AxisPlacement::Auto(span)] = placement {
+            let mut minor = if dense {
+                minor_lines.start
+            } else {
+                cursors
+                    .get(&major.start)
+                    .copied()
+                    .unwrap_or(minor_lines.start)
+            };
+            while !is_free(&areas, major, &(minor..minor + span)) {
+                minor += 1;
+            }
+            cursors.insert(major.start, minor + span);
+            areas[idx] = Some([major.clone(), minor..minor + span]);
+        }
+    }
+
+    // Step 4: Position the remaining grid items.
+    let (mut cursor_major, mut cursor_minor) = (major_start, minor_lines.start);
+    for (idx, placement) in placements.iter().enumerate() {
+        let major_span = match placement[0] {
+            AxisPlacement::Auto(span) => span,
+            AxisPlacement::Definite(_) => continue,
+        };
+        if dense {
+            cursor_major = major_start;
+            cursor_minor = minor_lines.start;
+        }
+        let minor = match &placement[1] {
+            AxisPlacement::Definite(minor) => {
+                if !dense && minor.start < cursor_minor {
+                    cursor_major += 1;
+                }
+                cursor_minor = minor.start;
+                while !is_free(&areas, &(cursor_major..cursor_major + major_span), minor) {
+                    cursor_major += 1;
+                }
+                minor.clone()
+            }
+            AxisPlacement::Auto(minor_span) => {
+                let minor_span = *minor_span;
+                loop {
+                    if cursor_minor + minor_span > minor_lines.end {
+                        cursor_major += 1;
+                        cursor_minor = minor_lines.start;
+                    } else if is_free(
+                        &areas,
+                        &(cursor_major..cursor_major + major_span),
+                        &(cursor_minor..cursor_minor + minor_span),
+                    ) {
+                        break;
+                    } else {
+                        cursor_minor += 1;
+                    }
+                }
+                cursor_minor..cursor_minor + minor_span
+            }
+        };
+        areas[idx] = Some([cursor_major..cursor_major + major_span, minor]);
+    }
+
+    areas.into_iter().map(Option::unwrap).collect()
+}
+
+/// Resolves a fixed track sizing function, returning `None` for intrinsic and flexible sizing
+/// functions, and for percentages of an indefinite size.
+fn fixed_breadth(
+    breadth: TrackBreadth,
+    available_size: Option<Au>,
+    scale_factor: f32,
+) -> Option<Au> {
+    match breadth {
+        TrackBreadth::LengthPercentage(lp) => resolve_size(
+            LengthPercentageOrAuto::LengthPercentage(lp),
+            available_size,
+            scale_factor,
+        ),
+        _ => None,
+    }
+}
+
+fn flex_factor(breadth: TrackBreadth) -> Option<CSSFloat> {
+    match breadth {
+        TrackBreadth::Flex(factor) => Some(factor),
+        _ => None,
+    }
+}
+
+/// Runs the track sizing algorithm for a single axis, returning the size of each track.
+///
+/// If `available_size` is `None`, the tracks are sized under a min-content constraint if
+/// `min_content` is set, and a max-content constraint otherwise.  `auto` tracks are stretched to
+/// fill any remaining space if `stretch` is set.
+///
+/// TODO: Space is distributed equally between spanned tracks, rather than first to those with
+/// the smallest growth potential.
+///
+/// https://drafts.csswg.org/css-grid-1/#algo-track-sizing
+fn size_tracks(
+    sizes: &[TrackSize],
+    contributions: &[Contribution],
+    available_size: Option<Au>,
+    gap: Au,
+    min_content: bool,
+    stretch: bool,
+    scale_factor: f32,
+) -> Vec<Au> {
+    let zero = Au(0);
+    let fixed = |breadth| fixed_breadth(breadth, available_size, scale_factor);
+    // Percentages of an indefinite size behave as `auto`.
+    let is_intrinsic = |breadth| fixed(breadth).is_none() && flex_factor(breadth).is_none();
+    let spanned_gaps = |tracks: &Range<usize>| gap * tracks.len().saturating_sub(1) as i32;
+
+    // Initialize the base size and growth limit of each track.  An infinite growth limit is
+    // represented by `None`.
+    // https://drafts.csswg.org/css-grid-1/#algo-init
+    let mut bases = sizes
+        .iter()
+        .map(|size| fixed(size.min).unwrap_or(zero))
+        .collect::<Vec<_>>();
+    let mut limits = sizes
+        .iter()
+        .zip(&bases)
+        .map(|(size, &base)| fixed(size.max).map(|limit| limit.max(base)))
+        .collect::<Vec<_>>();
+
+    // Resolve intrinsic track sizes, first for items that span a single non-flexible track.
+    // https://drafts.csswg.org/css-grid-1/#algo-content
+    let mut spanning_items = Vec::new();
+    let mut flexible_items = Vec::new();
+    for contribution in contributions {
+        let tracks = contribution.tracks.clone();
+        if tracks
+            .clone()
+            .any(|track| flex_factor(sizes[track].max).is_some())
+        {
+            flexible_items.push(contribution);
+            continue;
+        }
+        if tracks.len() > 1 {
+            spanning_items.push(contribution);
+            continue;
+        }
+        let track = tracks.start;
+        let size = sizes[track];
+        if is_intrinsic(size.min) {
+            bases[track] = bases[track].max(match size.min {
+                TrackBreadth::MaxContent => contribution.max_content,
+                _ => contribution.min_content,
+            });
+        }
+        if is_intrinsic(size.max) {
+            let limit = match size.max {
+                TrackBreadth::MinContent => contribution.min_content,
+                _ => contribution.max_content,
+            };
+            limits[track] = Some(limits[track].map_or(limit, |current| current.max(limit)));
+        }
+    }
+
+    // Then increase sizes to fit items that span multiple tracks, from the smallest span to the
+    // largest.
+    spanning_items.sort_by_key(|contribution| contribution.tracks.len());
+    for contribution in spanning_items {
+        let tracks = contribution.tracks.clone();
+        let targets = tracks
+            .clone()
+            .filter(|&track| is_intrinsic(sizes[track].min))
+            .collect::<Vec<_>>();
+        let current = tracks.clone().fold(zero, |acc, track| acc + bases[track]);
+        let extra = contribution.min_content - current - spanned_gaps(&tracks);
+        distribute_equally(&mut bases, &targets, extra);
+
+        let targets = tracks
+            .clone()
+            .filter(|&track| is_intrinsic(sizes[track].max))
+            .collect::<Vec<_>>();
+        let current = tracks.clone().fold(zero, |acc, track| {
+            acc + limits[track].unwrap_or(bases[track])
+        });
+        let extra = contribution.max_content - current - spanned_gaps(&tracks);
+        if extra > Au(0) && !targets.is_empty() {
+            let share = extra / targets.len() as i32;
+            for track in targets {
+                limits[track] = Some(limits[track].unwrap_or(bases[track]) + share);
+            }
+        }
+    }
+
+    // Then increase the base sizes of flexible tracks to fit the items that span them.
+    for contribution in flexible_items {
+        let tracks = contribution.tracks.clone();
+        let targets = tracks
+            .clone()
+            .filter(|&track| {
+                flex_factor(sizes[track].max).is_some() && is_intrinsic(sizes[track].min)
+            })
+            .collect::<Vec<_>>();
+        let current = tracks.clone().fold(zero, |acc, track| acc + bases[track]);
+        let extra = contribution.min_content - current - spanned_gaps(&tracks);
+        distribute_equally(&mut bases, &targets, extra);
+    }
+
+    // Any growth limit that's still infinite, or smaller than its base size, is set to the base
+    // size.
+    let limits = limits
+        .into_iter()
+        .zip(&bases)
+        .map(|(limit, &base)| limit.map_or(base, |limit| limit.max(base)))
+        .collect::<Vec<_>>();
+    let gaps = gap * sizes.len().saturating_sub(1) as i32;
+
+    // Maximize tracks by distributing free space equally among them, up to their growth limits.
+    // https://drafts.csswg.org/css-grid-1/#algo-grow-tracks
+    match available_size {
+        Some(available_size) => {
+            let mut free_space = available_size - sum(&bases) - gaps;
+            loop {
+                let growable = (0..sizes.len())
+                    .filter(|&track| bases[track] < limits[track])
+                    .collect::<Vec<_>>();
+                if free_space <= Au(0) || growable.is_empty() {
+                    break;
+                }
+                let share = free_space / growable.len() as i32;
+                let mut capped = false;
+                for track in growable {
+                    let growth = share.min(limits[track] - bases[track]);
+                    capped |= growth < share;
+                    bases[track] += growth;
+                    free_space -= growth;
+                }
+                if !capped {
+                    break;
+                }
+            }
+        }
+        // Under a max-content constraint, the free space is infinite.
+        None if !min_content => bases.clone_from(&limits),
+        None => {}
+    }
+
+    // Expand flexible tracks.
+    // https://drafts.csswg.org/css-grid-1/#algo-flex-tracks
+    let flexible_tracks = (0..sizes.len())
+        .filter_map(|track| flex_factor(sizes[track].max).map(|factor| (track, factor)))
+        .collect::<Vec<_>>();
+    if !flexible_tracks.is_empty() {
+        let fr_size = match available_size {
+            Some(available_size) => Some(find_fr_size(
+                &bases,
+                &flexible_tracks,
+                0..sizes.len(),
+                available_size - gaps,
+            )),
+            None if !min_content => {
+                let mut fr_size = zero;
+                for &(track, factor) in &flexible_tracks {
+                    fr_size = fr_size.max(if factor > 1. {
+                        bases[track].scale_by(1. / factor)
+                    } else {
+                        bases[track]
+                    });
+                }
+                for contribution in contributions {
+                    let tracks = &contribution.tracks;
+                    let spanned_flexible_tracks = flexible_tracks
+                        .iter()
+                        .copied()
+                        .filter(|(track, _)| tracks.contains(track))
+                        .collect::<Vec<_>>();
+                    if !spanned_flexible_tracks.is_empty() {
+                        fr_size = fr_size.max(find_fr_size(
+                            &bases,
+                            &spanned_flexible_tracks,
+                            tracks.clone(),
+                            contribution.max_content - spanned_gaps(tracks),
+                        ));
+                    }
+                }
+                Some(fr_size)
+            }
+            // Under a min-content constraint, there's no free space for flexible tracks to grow
+            // into.
+            None => None,
+        };
+        if let Some(fr_size) = fr_size {
+            for (track, factor) in flexible_tracks {
+                bases[track] = bases[track].max(fr_size.scale_by(factor));
+            }
+        }
+    }
+
+    // Stretch `auto` tracks to fill any remaining space.
+    // https://drafts.csswg.org/css-grid-1/#algo-stretch
+    if let (true, Some(available_size)) = (stretch, available_size) {
+        let free_space = available_size - sum(&bases) - gaps;
+        let auto_tracks = (0..sizes.len())
+            .filter(|&track| sizes[track].max == TrackBreadth::Auto)
+            .collect::<Vec<_>>();
+        distribute_equally(&mut bases, &auto_tracks, free_space);
+    }
+
+    bases
+}
+
+/// Finds the size of an `fr` that fills `space` with the given tracks, of which `flexible_tracks`
+/// are the flexible tracks and their flex factors.
+///
+/// https://drafts.csswg.org/css-grid-1/#algo-find-fr-size
+fn find_fr_size(
+    bases: &[Au],
+    flexible_tracks: &[(usize, CSSFloat)],
+    tracks: Range<usize>,
+    space: Au,
+) -> Au {
+    let mut inflexible = tracks
+        .clone()
+        .map(|track| {
+            !flexible_tracks
+                .iter()
+                .any(|&(flexible, _)| flexible == track)
+        })
+        .collect::<Vec<_>>();
+    loop {
+        let leftover_space = tracks
+            .clone()
+            .zip(&inflexible)
+            .filter(|(_, &inflexible)| inflexible)
+            .fold(space, |acc, (track, _)| acc - bases[track]);
+        let flex_sum = flexible_tracks
+            .iter()
+            .filter(|&&(track, _)| !inflexible[track - tracks.start])
+            .map(|&(_, factor)| factor)
+            .sum::<CSSFloat>()
+            .max(1.);
+        let fr_size = leftover_space.scale_by(1. / flex_sum);
+
+        // Tracks whose base size is larger than their share are treated as inflexible.
+        let mut restart = false;
+        for &(track, factor) in flexible_tracks {
+            let idx = track - tracks.start;
+            if !inflexible[idx] && fr_size.scale_by(factor) < bases[track] {
+                inflexible[idx] = true;
+                restart = true;
+            }
+        }
+        if !restart {
+            return fr_size;
+        }
+    }
+}
+
+/// Increases the sizes of the `targets` equally so that they grow by `extra` in total, if it's
+/// positive.
+pub(super) fn distribute_equally(sizes: &mut [Au], targets: &[usize], extra: Au) {
+    if extra <= Au(0) || targets.is_empty() {
+        return;
+    }
+    let share = extra / targets.len() as i32;
+    for &target in targets {
+        sizes[target] += share;
+    }
+}
+
+pub(super) fn sum(sizes: &[Au]) -> Au {
+    sizes.iter().fold(Au(0), |acc, &size| acc + size)
+}
+
+/// The sum of the given track sizes, including the gaps between them.
+fn sum_with_gaps(sizes: &[Au], gap: Au) -> Au {
+    sum(sizes) + gap * sizes.len().saturating_sub(1) as i32
+}
+
+/// Calculates the offset of each track from the start of the container's content box, after
+/// distributing any free space between the tracks.
+///
+/// https://drafts.csswg.org/css-align-3/#distribution-grid
+fn track_positions(
+    sizes: &[Au],
+    container_size: Au,
+    gap: Au,
+    distribution: Distribution,
+) -> Vec<Au> {
+    let free_space = container_size - sum_with_gaps(sizes, gap);
+    let (leading_space, between_space) = distribution.offsets(free_space, sizes.len());
+    let mut cursor = leading_space;
+    sizes
+        .iter()
+        .map(|&size| {
+            let position = cursor;
+            cursor += size + gap + between_space;
+            position
+        })
+        .collect()
+}
+
+/// Gets the offset and size of the area covered by the given tracks, including any gaps and
+/// distributed space between them.
+fn span_extent(positions: &[Au], sizes: &[Au], tracks: &Range<usize>) -> (Au, Au) {
+    let start = positions[tracks.start];
+    let last = tracks.end - 1;
+    (start, positions[last] + sizes[last] - start)
+}
+
+/// Builds the containing block for a grid item from the dimensions of its grid container and the
+/// logical offsets and sizes of its grid area.
+///
+/// https://drafts.csswg.org/css-grid-1/#grid-item-sizing
+fn grid_area(
+    container: PhysicalDimensions,
+    horizontal: bool,
+    (inline_offset, inline_size): (Au, Au),
+    (block_offset, block_size): (Au, Au),
+) -> PhysicalDimensions {
+    let mut area = container;
+    if horizontal {
+        area.content.start_x += inline_offset;
+        area.content.start_y += block_offset;
+        area.content.width = inline_size;
+        area.content.height = block_size;
+    } else {
+        area.content.start_x += block_offset;
+        area.content.start_y += inline_offset;
+        area.content.width = block_size;
+        area.content.height = inline_size;
+    }
+    area
+}
+
+/// Gets the offset of an item within its grid area due to its `auto` margins, which absorb the
+/// free space in the area.
+fn auto_margin_offset(free_space: Au, auto_start: bool, auto_end: bool) -> Au {
+    match (auto_start, auto_end) {
+        (true, true) => free_space / 2,
+        (true, false) => free_space,
+        _ => Au(0),
+    }
+}
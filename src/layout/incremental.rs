@@ -0,0 +1,259 @@
+// Useful links:
+//  * https://developer.mozilla.org/en-US/docs/Web/Performance/How_browsers_work#layout
+//
+// Layout results are kept on the layout tree between frames.  A box whose containing block has
+// the same size as it did the last time the box was laid out, and which hasn't been invalidated,
+// is moved into place rather than laid out again, along with its whole subtree.
+use crate::dom::tree::NodeRef;
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::layout_box::LayoutBox;
+use crate::layout::INITIAL_CONTAINING_BLOCK;
+use app_units::Au;
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    /// A monotonic clock, ticked whenever a box is laid out or reused, so that the order of those
+    /// events can be recovered after the fact.  Like the initial containing block, it's per
+    /// thread, so that layouts on different threads don't interleave their ticks.
+    static CLOCK: Cell<u64> = const { Cell::new(0) };
+    /// Whether the computation in progress has read the size of the initial containing block.
+    static VIEWPORT_DEPENDENT: Cell<bool> = const { Cell::new(false) };
+}
+
+fn tick() -> u64 {
+    CLOCK.with(|clock| {
+        clock.set(clock.get() + 1);
+        clock.get()
+    })
+}
+
+/// The size of the initial containing block that a cached result was computed against, or
+/// `None` if the result doesn't depend on it.
+pub(super) type ViewportSize = Option<(Au, Au)>;
+
+/// Records that the computation in progress depends on the size of the initial containing block.
+pub(super) fn note_viewport_dependency() {
+    VIEWPORT_DEPENDENT.with(|dependent| dependent.set(true));
+}
+
+/// Runs `compute`, returning its result along with the size of the initial containing block if
+/// `compute` read it.  Dependencies are propagated to any enclosing computation.
+pub(super) fn track_viewport_dependency<R>(compute: impl FnOnce() -> R) -> (R, ViewportSize) {
+    let outer = VIEWPORT_DEPENDENT.with(|dependent| dependent.replace(false));
+    let result = compute();
+    let dependent =
+        VIEWPORT_DEPENDENT.with(|dependent| dependent.replace(dependent.get() || outer));
+    let viewport = if dependent {
+        let icb = INITIAL_CONTAINING_BLOCK.with(Cell::get);
+        Some((icb.width, icb.height))
+    } else {
+        None
+    };
+    (result, viewport)
+}
+
+/// Determines if a result computed against `viewport` is still valid for the initial containing
+/// block of the layout in progress.  If it is, and is reused, the dependency carries over.
+pub(super) fn is_valid_for_viewport(viewport: ViewportSize) -> bool {
+    match viewport {
+        None => true,
+        Some(size) => {
+            let icb = INITIAL_CONTAINING_BLOCK.with(Cell::get);
+            note_viewport_dependency();
+            size == (icb.width, icb.height)
+        }
+    }
+}
+
+/// The inputs a box was last laid out with, and where it ended up relative to its containing
+/// block.
+#[derive(Clone, Copy, Debug)]
+struct CachedLayout {
+    containing_block_size: (Au, Au),
+    scale_factor: f32,
+    viewport: ViewportSize,
+    /// The offset of the start corner of the box's content box from that of its containing block.
+    content_offset: (Au, Au),
+}
+
+/// The incremental layout state of a box.
+#[derive(Clone, Debug, Default)]
+pub(super) struct LayoutState {
+    /// Whether this box must be laid out again, such as because its style changed.
+    dirty: bool,
+    /// Whether some descendant of this box must be laid out again.
+    descendants_dirty: bool,
+    cached: Option<CachedLayout>,
+    /// The offset applied to this box by relative or sticky positioning, which is undone before
+    /// each layout, since it's applied after normal flow layout.
+    positioned_offset: (Au, Au),
+    /// When this box was last laid out, according to `CLOCK`.
+    laid_out_at: u64,
+    /// When this box last reused its cached layout, according to `CLOCK`.
+    reused_at: u64,
+}
+
+/// Statistics about a single global layout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LayoutStats {
+    /// The number of boxes in the layout tree.
+    pub boxes: usize,
+    /// The number of boxes that were actually laid out, rather than reusing their previous layout.
+    pub laid_out: usize,
+}
+
+impl fmt::Display for LayoutStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "laid out {} of {} boxes", self.laid_out, self.boxes)
+    }
+}
+
+/// Prepares `layout_tree` for a new global layout, returning the time the layout started at.
+pub(super) fn begin_layout(layout_tree: &mut LayoutBox) -> u64 {
+    layout_tree.undo_positioned_offsets();
+    tick()
+}
+
+/// Finishes a global layout of `layout_tree` that started at `started_at`, marking every box as
+/// clean and counting the boxes that were laid out.
+pub(super) fn end_layout(layout_tree: &mut LayoutBox, started_at: u64) -> LayoutStats {
+    let mut stats = LayoutStats::default();
+    layout_tree.finish_layout(started_at, (0, false), &mut stats);
+    stats
+}
+
+impl LayoutBox {
+    /// Lays out this box within `containing_block`, reusing its previous layout if this box and
+    /// its descendants haven't been invalidated since, and its containing block has the same size.
+    /// See `LayoutBox::layout_uncached` for the layout itself.
+    pub fn layout(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) {
+        if self.reuse_layout(containing_block, scale_factor) {
+            return;
+        }
+        self.layout_state.laid_out_at = tick();
+        let ((), viewport) =
+            track_viewport_dependency(|| self.layout_uncached(containing_block, scale_factor));
+        let content = self.dimensions.physical().content;
+        self.layout_state.cached = Some(CachedLayout {
+            containing_block_size: (
+                containing_block.content.width,
+                containing_block.content.height,
+            ),
+            scale_factor,
+            viewport,
+            content_offset: (
+                content.start_x - containing_block.content.start_x,
+                content.start_y - containing_block.content.start_y,
+            ),
+        });
+    }
+
+    /// Moves this box into place within `containing_block` if its cached layout is still valid.
+    /// Returns whether it was.
+    fn reuse_layout(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) -> bool {
+        let state = &self.layout_state;
+        let cached = match state.cached {
+            Some(cached) if !state.dirty && !state.descendants_dirty => cached,
+            _ => return false,
+        };
+        if cached.containing_block_size
+            != (
+                containing_block.content.width,
+                containing_block.content.height,
+            )
+            || cached.scale_factor != scale_factor
+            || !is_valid_for_viewport(cached.viewport)
+        {
+            return false;
+        }
+        let content = self.dimensions.physical().content;
+        self.translate(
+            containing_block.content.start_x + cached.content_offset.0 - content.start_x,
+            containing_block.content.start_y + cached.content_offset.1 - content.start_y,
+        );
+        self.layout_state.reused_at = tick();
+        true
+    }
+
+    /// Records that this box is being laid out outside of `LayoutBox::layout`, as absolutely
+    /// positioned boxes are.
+    pub(super) fn note_laid_out(&mut self) {
+        self.layout_state.laid_out_at = tick();
+    }
+
+    /// Moves this box by the offset given by relative or sticky positioning, remembering it so
+    /// that it can be undone before the next layout.
+    pub(super) fn apply_positioned_offset(&mut self, dx: Au, dy: Au) {
+        self.translate(dx, dy);
+        let offset = &mut self.layout_state.positioned_offset;
+        offset.0 += dx;
+        offset.1 += dy;
+    }
+
+    /// Moves every box in this subtree back to where normal flow layout left it.
+    fn undo_positioned_offsets(&mut self) {
+        let (dx, dy) = self.layout_state.positioned_offset;
+        if dx != Au(0) || dy != Au(0) {
+            self.translate(-dx, -dy);
+        }
+        self.layout_state.positioned_offset = (Au(0), Au(0));
+        for child in &mut self.children {
+            child.undo_positioned_offsets();
+        }
+    }
+
+    /// Marks the boxes generated by `node`, and their descendants, as needing to be laid out
+    /// again, such as after a change to the style of `node`.  Returns whether `node` generated any
+    /// boxes in this subtree.
+    pub fn invalidate(&mut self, node: &NodeRef) -> bool {
+        if !self.anonymous && self.node == *node {
+            self.invalidate_subtree();
+            return true;
+        }
+        let mut invalidated = false;
+        for child in &mut self.children {
+            invalidated |= child.invalidate(node);
+        }
+        if invalidated {
+            // The intrinsic sizes of a box depend on those of its descendants.
+            self.layout_state.descendants_dirty = true;
+            self.intrinsic_sizes.clear();
+        }
+        invalidated
+    }
+
+    fn invalidate_subtree(&mut self) {
+        self.layout_state.dirty = true;
+        self.intrinsic_sizes.clear();
+        for child in &mut self.children {
+            child.invalidate_subtree();
+        }
+    }
+
+    /// Marks this subtree as clean after a global layout that started at `started_at`, counting
+    /// its boxes into `stats`.  `inherited` is the time and kind of the latest layout event of an
+    /// ancestor: whether the ancestor was laid out, or reused its cached layout.
+    ///
+    /// A box was laid out if it or its nearest ancestor to do anything during this layout was laid
+    /// out, rather than reused.
+    fn finish_layout(&mut self, started_at: u64, inherited: (u64, bool), stats: &mut LayoutStats) {
+        let state = &mut self.layout_state;
+        let mut latest = inherited;
+        if state.laid_out_at > started_at && state.laid_out_at > latest.0 {
+            latest = (state.laid_out_at, true);
+        }
+        if state.reused_at > started_at && state.reused_at > latest.0 {
+            latest = (state.reused_at, false);
+        }
+        stats.boxes += 1;
+        if latest.1 || state.laid_out_at > started_at {
+            stats.laid_out += 1;
+        }
+        state.dirty = false;
+        state.descendants_dirty = false;
+        for child in &mut self.children {
+            child.finish_layout(started_at, latest, stats);
+        }
+    }
+}
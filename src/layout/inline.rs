@@ -0,0 +1,387 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/visuren.html#inline-formatting
+//  * https://www.w3.org/TR/CSS2/visudet.html#line-height
+//  * https://drafts.csswg.org/css-inline-3/#model
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::float::{logical_rect, FloatContext};
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use app_units::Au;
+
+/// A single piece of inline-level content participating in line layout.
+#[derive(Clone, Debug)]
+struct InlineItem {
+    /// The indices of the children to follow from the root inline box to get to the box this item
+    /// belongs to.
+    path: Vec<usize>,
+    kind: InlineItemKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum InlineItemKind {
+    /// An atomic inline, such as an inline-block, which can't be split across lines.  The ascent
+    /// is the distance from the block-start margin edge of the box to its baseline, and the
+    /// descent is the distance from its baseline to its block-end margin edge.
+    Atomic {
+        inline_size: Au,
+        ascent: Au,
+        descent: Au,
+    },
+    /// Marks where a non-atomic inline box (including text) begins.
+    InlineBoxStart,
+    /// Marks where a non-atomic inline box (including text) ends.
+    InlineBoxEnd,
+    /// An absolutely positioned box, which takes up no space in the line, but whose static
+    /// position is where it appears in it.
+    OutOfFlow,
+    /// A floated box, which is placed at the top of the line it appears in if it fits there, and
+    /// below it otherwise.  The inline size is that of its margin box.
+    Float { inline_size: Au },
+}
+
+/// https://www.w3.org/TR/CSS2/visuren.html#line-box
+#[derive(Clone, Copy, Debug, Default)]
+struct LineBox {
+    /// The distance from the block-start edge of the root inline box to this line box.
+    block_offset: Au,
+    /// The distance from the inline-start edge of the root inline box to this line box, which is
+    /// shortened to make room for any floats beside it.
+    inline_offset: Au,
+    /// The inline size of this line box.
+    inline_size: Au,
+    /// The distance from the block-start edge of this line box to its baseline.
+    ascent: Au,
+    /// The distance from the baseline of this line box to its block-end edge.
+    descent: Au,
+    /// Line boxes that contain no atomic inlines are treated as zero-height line boxes.
+    /// https://www.w3.org/TR/CSS2/visuren.html#phantom-line-box
+    has_atomic_inlines: bool,
+}
+
+impl LineBox {
+    fn block_size(&self) -> Au {
+        self.ascent + self.descent
+    }
+}
+
+impl LayoutBox {
+    /// Assuming `self` is a root inline box, lays out its inline-level descendants into line boxes
+    /// stacked in the block axis, aligning the boxes in each line on their baselines.  The line
+    /// boxes are shortened around the floats in `floats`, which any floats among the descendants
+    /// are placed in too.
+    pub(super) fn layout_inline_formatting_context(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        floats: &mut FloatContext,
+        scale_factor: f32,
+    ) {
+        // The root inline box has no margins, borders, or padding.  It spans the entire inline
+        // size of its block container, and starts after any content laid out before it.
+        let horizontal = self.writing_mode.is_horizontal();
+        let content = containing_block.content;
+        let (inline_start, block_start, available_inline_size) = if horizontal {
+            (content.start_x, content.end_y(), content.width)
+        } else {
+            (content.start_y, content.end_x(), content.height)
+        };
+        self.dimensions.set_inline_start_coord(inline_start);
+        self.dimensions.set_block_start_coord(block_start);
+        self.dimensions.set_inline_size(available_inline_size);
+        self.dimensions.set_block_size(Au(0));
+        self.line_baseline = None;
+
+        // Atomic inlines are laid out at the start of the root inline box, and then moved to
+        // their final position once we know which line they belong to and how tall it is.
+        let physical_dimensions = self.dimensions.physical();
+        let mut items = Vec::new();
+        for (idx, child) in self.children.iter_mut().enumerate() {
+            collect_inline_items(
+                child,
+                vec![idx],
+                physical_dimensions,
+                scale_factor,
+                horizontal,
+                &mut items,
+            );
+        }
+
+        // Break the items into lines, remembering the line and inline offset of each item.  Lines
+        // are stacked in the block axis as they're created, since the space beside floats depends
+        // on where each line is.
+        // TODO: Support `direction: rtl`, which should start each line from the inline-end side.
+        // TODO: Line boxes should start with a strut derived from the font and `line-height` of
+        // the block container, but layout doesn't have access to font metrics yet.
+        let direction = self.direction;
+        let inline_range = (inline_start, inline_start + available_inline_size);
+        let available_range = |floats: &FloatContext, line: &LineBox, block_size| {
+            floats.available_range(
+                block_start + line.block_offset,
+                line.block_size().max(block_size),
+                inline_range,
+            )
+        };
+        let mut lines = vec![LineBox::default()];
+        let mut positions = Vec::with_capacity(items.len());
+        let mut inline_offset = Au(0);
+        // Floats that didn't fit on the line they appeared in, which are placed below it.
+        let mut pending_floats = Vec::new();
+        for item in &items {
+            match item.kind {
+                InlineItemKind::Atomic {
+                    inline_size,
+                    ascent,
+                    descent,
+                } => loop {
+                    let current_line = *lines.last().expect("there is always at least one line");
+                    let (start, end) = available_range(floats, &current_line, ascent + descent);
+                    let line_inline_size = end - start;
+                    if current_line.has_atomic_inlines
+                        && inline_offset + inline_size > line_inline_size
+                    {
+                        let line_block_start =
+                            current_line.block_offset + current_line.block_size();
+                        for path in pending_floats.drain(..) {
+                            self.descendant_mut(path).place_float(
+                                floats,
+                                direction,
+                                block_start + line_block_start,
+                                inline_range,
+                            );
+                        }
+                        lines.push(LineBox {
+                            block_offset: line_block_start,
+                            ..LineBox::default()
+                        });
+                        inline_offset = Au(0);
+                        continue;
+                    }
+                    if !current_line.has_atomic_inlines
+                        && inline_size > line_inline_size
+                        && (start, end) != inline_range
+                    {
+                        // Move the empty line down past the floats beside it, in the hope of
+                        // finding enough room for this item.
+                        let line_block_start = block_start + current_line.block_offset;
+                        if let Some(next_float_end) = floats.next_float_end(line_block_start) {
+                            lines
+                                .last_mut()
+                                .expect("there is always at least one line")
+                                .block_offset = next_float_end - block_start;
+                            continue;
+                        }
+                    }
+                    let current_line = lines.last_mut().expect("there is always at least one line");
+                    current_line.has_atomic_inlines = true;
+                    current_line.ascent = current_line.ascent.max(ascent);
+                    current_line.descent = current_line.descent.max(descent);
+                    positions.push((lines.len() - 1, inline_offset));
+                    inline_offset += inline_size;
+                    break;
+                },
+                InlineItemKind::Float { inline_size } => {
+                    let current_line = *lines.last().expect("there is always at least one line");
+                    let (start, end) = available_range(floats, &current_line, Au(0));
+                    if pending_floats.is_empty() && inline_offset + inline_size <= end - start {
+                        self.descendant_mut(&item.path).place_float(
+                            floats,
+                            direction,
+                            block_start + current_line.block_offset,
+                            inline_range,
+                        );
+                    } else {
+                        pending_floats.push(&item.path);
+                    }
+                    positions.push((lines.len() - 1, inline_offset));
+                }
+                InlineItemKind::InlineBoxStart
+                | InlineItemKind::InlineBoxEnd
+                | InlineItemKind::OutOfFlow => positions.push((lines.len() - 1, inline_offset)),
+            }
+        }
+        let last_line = *lines.last().expect("there is always at least one line");
+        let block_offset = last_line.block_offset + last_line.block_size();
+        for path in pending_floats {
+            self.descendant_mut(path).place_float(
+                floats,
+                direction,
+                block_start + block_offset,
+                inline_range,
+            );
+        }
+
+        // Now that every float is in place, fit each line into the space beside them.
+        for line in &mut lines {
+            let (start, end) = available_range(floats, line, Au(0));
+            line.inline_offset = start - inline_start;
+            line.inline_size = end - start;
+        }
+        self.dimensions.set_block_size(block_offset);
+        self.line_baseline = lines
+            .iter()
+            .rev()
+            .find(|line| line.has_atomic_inlines)
+            .map(|line| line.block_offset + line.ascent);
+
+        // Finally, move every item into its place.
+        let mut open_inline_boxes = Vec::new();
+        for (item, (line_idx, inline_offset)) in items.iter().zip(positions) {
+            let line = lines[line_idx];
+            match item.kind {
+                InlineItemKind::Atomic { ascent, .. } => {
+                    let target_inline = inline_start + line.inline_offset + inline_offset;
+                    let target_block = block_start + line.block_offset + line.ascent - ascent;
+                    let atomic_box = self.descendant_mut(&item.path);
+                    let margin_box = atomic_box.physical_dimensions().margin_box();
+                    if horizontal {
+                        atomic_box.translate(
+                            target_inline - margin_box.start_x,
+                            target_block - margin_box.start_y,
+                        );
+                    } else {
+                        atomic_box.translate(
+                            target_block - margin_box.start_x,
+                            target_inline - margin_box.start_y,
+                        );
+                    }
+                }
+                InlineItemKind::InlineBoxStart => open_inline_boxes.push((line_idx, inline_offset)),
+                // Floats were placed during line breaking.
+                InlineItemKind::Float { .. } => {}
+                InlineItemKind::OutOfFlow => {
+                    let static_inline = inline_start + line.inline_offset + inline_offset;
+                    let static_block = block_start + line.block_offset;
+                    self.descendant_mut(&item.path).static_position = if horizontal {
+                        (static_inline, static_block)
+                    } else {
+                        (static_block, static_inline)
+                    };
+                }
+                InlineItemKind::InlineBoxEnd => {
+                    let (start_line_idx, start_inline_offset) = open_inline_boxes
+                        .pop()
+                        .expect("inline box ended without being started");
+                    // TODO: Inline boxes that are split across lines should generate one fragment
+                    // per line.  For now, only the fragment on the first line is represented.
+                    let start_line = lines[start_line_idx];
+                    let end_inline_offset = if start_line_idx == line_idx {
+                        inline_offset
+                    } else {
+                        start_line.inline_size
+                    };
+                    let inline_box = self.descendant_mut(&item.path);
+                    let d = &mut inline_box.dimensions;
+                    d.set_inline_start_coord(
+                        inline_start + start_line.inline_offset + start_inline_offset,
+                    );
+                    d.set_block_start_coord(block_start + start_line.block_offset);
+                    d.set_inline_size(end_inline_offset - start_inline_offset);
+                    // TODO: The block size of an inline box is given by its font metrics, which
+                    // layout doesn't have access to yet.
+                    d.set_block_size(Au(0));
+                }
+            }
+        }
+    }
+
+    /// Gets the descendant of `self` found by following the given child indices.
+    fn descendant_mut(&mut self, path: &[usize]) -> &mut LayoutBox {
+        path.iter()
+            .fold(self, |layout_box, &idx| &mut layout_box.children[idx])
+    }
+}
+
+/// Collects the inline items generated by `layout_box` and its descendants, in order, into
+/// `items`.  Any atomic inlines found are laid out within `containing_block` in the process.
+///
+/// `horizontal` describes the writing mode of the inline formatting context, which determines
+/// the axis lines are laid out along.
+fn collect_inline_items(
+    layout_box: &mut LayoutBox,
+    path: Vec<usize>,
+    containing_block: PhysicalDimensions,
+    scale_factor: f32,
+    horizontal: bool,
+    items: &mut Vec<InlineItem>,
+) {
+    match layout_box.box_type {
+        _ if layout_box.is_absolutely_positioned() => items.push(InlineItem {
+            path,
+            kind: InlineItemKind::OutOfFlow,
+        }),
+        _ if layout_box.is_floated() => {
+            layout_box.layout_float(containing_block, scale_factor);
+            let margin_box = layout_box.physical_dimensions().margin_box();
+            let (_, _, inline_size, _) = logical_rect(margin_box, horizontal);
+            items.push(InlineItem {
+                path,
+                kind: InlineItemKind::Float { inline_size },
+            });
+        }
+        // TODO: Block-level boxes inside inline boxes should split the inline box around them
+        // (https://www.w3.org/TR/CSS2/visuren.html#box-gen).  Until the layout tree is built that
+        // way, lay them out as if they were atomic inlines, which gives them a line of their own.
+        BoxType::Block
+        | BoxType::Flex
+        | BoxType::Grid
+        | BoxType::InlineBlock
+        | BoxType::InlineFlex
+        | BoxType::InlineGrid
+        | BoxType::Replaced
+        | BoxType::InlineReplaced
+        | BoxType::TableWrapper
+        | BoxType::InlineTableWrapper
+        | BoxType::Table
+        | BoxType::TableRowGroup
+        | BoxType::TableRow
+        | BoxType::TableCell
+        | BoxType::TableColumnGroup
+        | BoxType::TableColumn
+        | BoxType::TableCaption => {
+            layout_box.layout(containing_block, scale_factor);
+            let margin_box = layout_box.physical_dimensions().margin_box();
+            let (inline_size, block_start, block_size) = if horizontal {
+                (margin_box.width, margin_box.start_y, margin_box.height)
+            } else {
+                (margin_box.height, margin_box.start_x, margin_box.width)
+            };
+            // The baseline of an inline-block is the baseline of its last line box in the normal
+            // flow, unless it has no in-flow line boxes, in which case it's the bottom margin edge.
+            // https://www.w3.org/TR/CSS2/visudet.html#propdef-vertical-align
+            let ascent = match layout_box.last_line_baseline() {
+                Some(baseline) if layout_box.writing_mode.is_horizontal() == horizontal => {
+                    baseline - block_start
+                }
+                _ => block_size,
+            };
+            items.push(InlineItem {
+                path,
+                kind: InlineItemKind::Atomic {
+                    inline_size,
+                    ascent,
+                    descent: block_size - ascent,
+                },
+            });
+        }
+        BoxType::Anonymous | BoxType::AnonymousInline | BoxType::Inline => {
+            items.push(InlineItem {
+                path: path.clone(),
+                kind: InlineItemKind::InlineBoxStart,
+            });
+            for (idx, child) in layout_box.children.iter_mut().enumerate() {
+                let mut child_path = path.clone();
+                child_path.push(idx);
+                collect_inline_items(
+                    child,
+                    child_path,
+                    containing_block,
+                    scale_factor,
+                    horizontal,
+                    items,
+                );
+            }
+            items.push(InlineItem {
+                path,
+                kind: InlineItemKind::InlineBoxEnd,
+            });
+        }
+    }
+}
@@ -1,28 +1,62 @@
 use crate::dom::tree::{NodeData, NodeRef};
 use crate::layout::dimensions::{LogicalDimensions, PhysicalDimensions};
-use crate::layout::{BoxComponent, DumpLayout, DumpLayoutFormat, LogicalDirection};
-use crate::style::values::computed::length::{
-    CSSPixelLength, LengthPercentage, LengthPercentageOrAuto,
+use crate::layout::float::{logical_rect, FloatContext};
+use crate::layout::incremental::LayoutState;
+use crate::layout::margin::CollapsedMargin;
+use crate::layout::rect::{CornerRadii, Rect, RoundedRect};
+use crate::layout::sizing::IntrinsicSizesCache;
+use crate::layout::{
+    initial_containing_block, BoxComponent, DumpLayout, DumpLayoutFormat, LogicalDirection,
 };
-use crate::style::values::computed::{ComputedValues, Direction, WritingMode};
-use crate::style::values::used::ToPx;
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::{
+    ComputedValues, Direction, Display, Float, Position, Size, WritingMode,
+};
+use crate::style::values::used::ToAu;
 use crate::Side;
+use app_units::Au;
 use std::cell::Ref;
 use std::io::Write;
 use std::mem::discriminant;
 
 #[derive(Clone, Debug)]
 pub struct LayoutBox {
-    box_type: BoxType,
-    children: Vec<LayoutBox>,
-    dimensions: LogicalDimensions,
-    direction: Direction,
+    /// Whether this box was generated by layout rather than by an element, such as the anonymous
+    /// table objects generated around misparented table boxes.  Boxes of the `Anonymous` and
+    /// `AnonymousInline` types are always anonymous.
+    pub(super) anonymous: bool,
+    /// The clamped radii of the corners of this box's border box.  Set once layout is complete.
+    /// See `LayoutBox::resolve_border_radii`.
+    pub(super) border_radii: CornerRadii,
+    pub(super) box_type: BoxType,
+    pub(super) children: Vec<LayoutBox>,
+    pub(super) dimensions: LogicalDimensions,
+    pub(super) direction: Direction,
+    /// The intrinsic inline sizes of this box, once they've been measured.
+    pub(super) intrinsic_sizes: IntrinsicSizesCache,
+    /// The cached layout of this box, and whether it needs to be laid out again.
+    pub(super) layout_state: LayoutState,
+    /// Only set for root inline boxes that contain at least one non-empty line box.  The distance
+    /// from the block-start edge of this box's content area to the baseline of its last line box.
+    pub(super) line_baseline: Option<Au>,
     /// Reference to the closest non-anonymous node.  This distinction only matters for anonymous
     /// boxes, since anonymous boxes are by definition not associated with a node, but need access
     /// to a node to get computed values during layout.  If the box is a block, inline, or any other
     /// non-anonymous box, this field is simply the actual DOM node associated with this box.
-    node: NodeRef,
-    writing_mode: WritingMode,
+    pub(super) node: NodeRef,
+    /// The area the content of this box is clipped to, if its `overflow` clips in either axis.
+    /// Set once layout is complete.  See `LayoutBox::compute_overflow`.
+    pub(super) overflow_clip: Option<RoundedRect>,
+    /// The area that can be scrolled to within this box, if it's a scroll container.  Set once
+    /// layout is complete.
+    pub(super) scrollable_overflow: Option<Rect>,
+    /// Only meaningful for absolutely positioned boxes.  The physical coordinates of the top-left
+    /// corner of this box's margin box, had it been in normal flow instead.  Recorded by the
+    /// layout of the box's parent, and used to place the box when its insets are `auto`.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#static-position
+    pub(super) static_position: (Au, Au),
+    pub(super) writing_mode: WritingMode,
 }
 
 impl LayoutBox {
@@ -36,15 +70,38 @@ impl LayoutBox {
         writing_mode: WritingMode,
     ) -> LayoutBox {
         LayoutBox {
+            anonymous: matches!(box_type, BoxType::Anonymous | BoxType::AnonymousInline),
+            border_radii: CornerRadii::default(),
             box_type,
             dimensions: LogicalDimensions::new(writing_mode, direction),
             direction,
             children: Vec::new(),
+            intrinsic_sizes: IntrinsicSizesCache::default(),
+            layout_state: LayoutState::default(),
+            line_baseline: None,
             node,
+            overflow_clip: None,
+            scrollable_overflow: None,
+            static_position: (Au(0), Au(0)),
             writing_mode,
         }
     }
 
+    /// Creates a new anonymous layout box of the given type, such as an anonymous table row.  As
+    /// with `LayoutBox::new`, `node` should be the DOM node associated with the closest
+    /// non-anonymous box.
+    pub fn new_anonymous(
+        box_type: BoxType,
+        node: NodeRef,
+        direction: Direction,
+        writing_mode: WritingMode,
+    ) -> LayoutBox {
+        LayoutBox {
+            anonymous: true,
+            ..LayoutBox::new(box_type, node, direction, writing_mode)
+        }
+    }
+
     pub fn physical_dimensions(&self) -> PhysicalDimensions {
         self.dimensions.physical()
     }
@@ -57,11 +114,59 @@ impl LayoutBox {
         &self.children
     }
 
+    /// Determines if this box was generated by layout rather than by an element, in which case it
+    /// has no style of its own.
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    /// Gets the text of the text node this box was generated for, if it was generated for one.
+    pub fn text(&self) -> Option<String> {
+        self.node.as_text().map(|text| text.borrow().clone())
+    }
+
     /// Retrieve the computed values of the node associated with this layout box.
     pub fn computed_values(&self) -> Ref<ComputedValues> {
         self.node.computed_values()
     }
 
+    /// Gets the positioning scheme of this box.  Anonymous boxes are never positioned, and neither
+    /// is the root box, which always takes up the whole viewport.  Table boxes leave positioning
+    /// to their table wrapper box.
+    ///
+    /// https://www.w3.org/TR/css-position-3/#position-property
+    pub fn position(&self) -> Position {
+        if self.anonymous || self.box_type == BoxType::Table || self.is_root() {
+            return Position::Static;
+        }
+        self.computed_values().position
+    }
+
+    /// Determines if this box is positioned, meaning it's offset from its normal flow position or
+    /// taken out of flow entirely.
+    pub fn is_positioned(&self) -> bool {
+        self.position() != Position::Static
+    }
+
+    /// Determines if this box is absolutely positioned, and so out of flow.  Such boxes are
+    /// skipped by the layout of their parent, aside from recording their static position, and
+    /// are laid out once their containing block is known.
+    pub fn is_absolutely_positioned(&self) -> bool {
+        self.position().is_absolute()
+    }
+
+    /// Determines if this box is floated, and so taken out of flow and shifted to one side of its
+    /// containing block.  Absolutely positioned boxes and the root box are never floated.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#dis-pos-flo
+    pub fn is_floated(&self) -> bool {
+        !self.anonymous
+            && self.box_type != BoxType::Table
+            && !self.is_root()
+            && !self.is_absolutely_positioned()
+            && self.computed_values().float != Float::None
+    }
+
     /// Determines if this layout box is associated with the root DOM node (<html>).
     pub fn is_root(&self) -> bool {
         match self.node.parent() {
@@ -70,6 +175,139 @@ impl LayoutBox {
         }
     }
 
+    /// Determines if this layout box establishes a new block formatting context for its contents.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#block-formatting
+    /// https://drafts.csswg.org/css-display/#establish-an-independent-formatting-context
+    pub fn establishes_bfc(&self) -> bool {
+        match self.box_type {
+            BoxType::Anonymous
+            | BoxType::AnonymousInline
+            | BoxType::Inline
+            | BoxType::Replaced
+            | BoxType::InlineReplaced
+            | BoxType::TableRowGroup
+            | BoxType::TableRow
+            | BoxType::TableColumnGroup
+            | BoxType::TableColumn => false,
+            BoxType::Flex
+            | BoxType::Grid
+            | BoxType::InlineBlock
+            | BoxType::InlineFlex
+            | BoxType::InlineGrid
+            | BoxType::TableWrapper
+            | BoxType::InlineTableWrapper
+            | BoxType::Table
+            | BoxType::TableCell
+            | BoxType::TableCaption => true,
+            BoxType::Block => {
+                self.is_root()
+                    || self.is_floated()
+                    || self.is_absolutely_positioned()
+                    || self.is_orthogonal_flow_root()
+                    || self.is_scroll_container()
+                    || self.computed_values().display == Display::FlowRoot
+            }
+        }
+    }
+
+    /// Gets the writing mode of this box's containing block, which is the writing mode its
+    /// position and the flow-relative sizes it takes up in its containing block are in.
+    ///
+    /// Anonymous boxes always share the writing mode of their parent, and the root box takes the
+    /// writing mode of the initial containing block from it.
+    ///
+    /// https://drafts.csswg.org/css-writing-modes-4/#logical-direction-layout
+    pub(super) fn containing_block_writing_mode(&self) -> WritingMode {
+        if self.anonymous {
+            return self.writing_mode;
+        }
+        match self.node.parent() {
+            Some(parent) if !matches!(*parent.data(), NodeData::Document(_)) => {
+                parent.computed_values().writing_mode
+            }
+            _ => self.writing_mode,
+        }
+    }
+
+    /// Gets the direction of this box's containing block, which is found the same way as its
+    /// writing mode.  See `LayoutBox::containing_block_writing_mode`.
+    pub(super) fn containing_block_direction(&self) -> Direction {
+        if self.anonymous {
+            return self.direction;
+        }
+        match self.node.parent() {
+            Some(parent) if !matches!(*parent.data(), NodeData::Document(_)) => {
+                parent.computed_values().direction
+            }
+            _ => self.direction,
+        }
+    }
+
+    /// Determines if this box is an orthogonal flow root, meaning its writing mode is
+    /// perpendicular to that of its containing block, so its inline axis is the block axis of its
+    /// containing block.
+    ///
+    /// https://drafts.csswg.org/css-writing-modes-4/#orthogonal-flows
+    pub fn is_orthogonal_flow_root(&self) -> bool {
+        self.writing_mode.is_horizontal() != self.containing_block_writing_mode().is_horizontal()
+    }
+
+    /// Gets the inline size of the containing block, in the writing mode of the containing block.
+    /// Percentage margins and padding are resolved against it, in both axes.
+    ///
+    /// https://drafts.csswg.org/css-writing-modes-4/#dimension-mapping
+    pub(super) fn containing_block_inline_size(&self, containing_block: PhysicalDimensions) -> Au {
+        if self.containing_block_writing_mode().is_horizontal() {
+            containing_block.content.width
+        } else {
+            containing_block.content.height
+        }
+    }
+
+    /// Gets the space available to this box in its own inline axis within the given containing
+    /// block.
+    ///
+    /// The inline axis of an orthogonal flow root is the block axis of its containing block, where
+    /// the space available is the block size of the containing block, if it's definite.
+    /// Otherwise, it's the size of the initial containing block in that axis.
+    ///
+    /// https://drafts.csswg.org/css-writing-modes-4/#orthogonal-auto
+    pub(super) fn available_inline_size(
+        &self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> Au {
+        let horizontal = self.writing_mode.is_horizontal();
+        if !self.is_orthogonal_flow_root() {
+            return if horizontal {
+                containing_block.content.width
+            } else {
+                containing_block.content.height
+            };
+        }
+        // The containing block's block size is still being laid out, so only a fixed block size
+        // is known to be definite.
+        // TODO: Percentage block sizes resolved against a definite size are definite too.
+        let definite_block_size =
+            self.node
+                .parent()
+                .and_then(|parent| match parent.computed_values().logical_height() {
+                    Size::LengthPercentage(LengthPercentage::Length(len)) => {
+                        Some(Au::from(len * scale_factor))
+                    }
+                    _ => None,
+                });
+        definite_block_size.unwrap_or_else(|| {
+            let icb = initial_containing_block();
+            if horizontal {
+                icb.width
+            } else {
+                icb.height
+            }
+        })
+    }
+
     /// Directly adds `new_child` to this layout box's children.
     pub fn add_child(&mut self, new_child: LayoutBox) {
         self.children.push(new_child)
@@ -91,7 +329,25 @@ impl LayoutBox {
     fn get_root_inline_box(&mut self) -> &mut LayoutBox {
         match self.box_type {
             BoxType::Anonymous | BoxType::AnonymousInline | BoxType::Inline => self,
-            BoxType::Block => {
+            // Flex and grid containers don't have a root inline box, since their inline-level
+            // children are blockified or wrapped in anonymous items when the layout tree is built.
+            BoxType::Block
+            | BoxType::Flex
+            | BoxType::Grid
+            | BoxType::InlineBlock
+            | BoxType::InlineFlex
+            | BoxType::InlineGrid
+            | BoxType::Replaced
+            | BoxType::InlineReplaced
+            | BoxType::TableWrapper
+            | BoxType::InlineTableWrapper
+            | BoxType::Table
+            | BoxType::TableRowGroup
+            | BoxType::TableRow
+            | BoxType::TableCell
+            | BoxType::TableColumnGroup
+            | BoxType::TableColumn
+            | BoxType::TableCaption => {
                 let root_inline_box_idx_opt =
                     self.children.iter().enumerate().find_map(|(idx, child)| {
                         if discriminant(&child.box_type) == discriminant(&BoxType::AnonymousInline)
@@ -125,13 +381,42 @@ impl LayoutBox {
     ///
     /// In this step, we will be taking computed values and calculating actual, used values
     /// based on the constraint of our environment.
-    pub fn layout(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) {
+    pub(super) fn layout_uncached(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
         match self.box_type {
-            BoxType::Anonymous | BoxType::AnonymousInline => {
+            BoxType::Anonymous => {
                 //                println!("layout anonymous box types not implemented");
                 layout_non_block_because_only_block_is_impl(self, containing_block, scale_factor);
             }
+            BoxType::AnonymousInline => {
+                let mut floats = FloatContext::new(self.writing_mode.is_horizontal());
+                self.layout_inline_formatting_context(containing_block, &mut floats, scale_factor)
+            }
             BoxType::Block => self.layout_block(containing_block, scale_factor),
+            BoxType::Flex => self.layout_flex(containing_block, scale_factor),
+            BoxType::Grid => self.layout_grid(containing_block, scale_factor),
+            BoxType::InlineBlock | BoxType::InlineFlex | BoxType::InlineGrid => {
+                self.layout_atomic_inline(containing_block, scale_factor)
+            }
+            BoxType::Replaced | BoxType::InlineReplaced => {
+                self.layout_replaced(containing_block, scale_factor)
+            }
+            BoxType::TableWrapper => self.layout_table_wrapper(containing_block, scale_factor),
+            BoxType::InlineTableWrapper => {
+                self.layout_inline_table_wrapper(containing_block, scale_factor)
+            }
+            BoxType::TableCaption => self.layout_block(containing_block, scale_factor),
+            // Table boxes and their internal boxes are laid out by their table wrapper box, since
+            // their sizes depend on the rest of the table.
+            BoxType::Table
+            | BoxType::TableRowGroup
+            | BoxType::TableRow
+            | BoxType::TableCell
+            | BoxType::TableColumnGroup
+            | BoxType::TableColumn => {}
             BoxType::Inline => {
                 // TODO: The root element is an inline box-type, so when we can actually layout
                 // inline boxes, make sure to handle the root element.  This current implementation
@@ -179,139 +464,352 @@ impl LayoutBox {
         self.layout_block_children(scale_factor);
 
         // Now that we've performed a layout with logical properties, let's apply any physical
-        // properties explicitly given for this block (e.g. `width`, `height`, bottom/left/right/top
-        // properties).
+        // properties explicitly given for this block (e.g. `width`, `height`).  The
+        // bottom/left/right/top insets are applied once normal flow layout is done, since they
+        // depend on the positioning scheme of the box.
         self.apply_physical_properties(containing_block, scale_factor);
     }
 
-    /// Calculate the logical width (inline size) of a block-level non-replaced element in normal
-    /// flow.
+    /// Assuming `self` is an atomic inline-level box (an inline-block, or an inline-level flex or
+    /// grid container), calculate the dimensions of this box and its contents.  It's sized like an
+    /// inline-block, and lays out its contents the way its block-level counterpart does.
     ///
-    /// https://www.w3.org/TR/CSS2/visudet.html#blockwidth
-    /// https://drafts.csswg.org/css-writing-modes-4/#vertical-layout
+    /// The box is positioned at the start of its containing block, and is expected to be moved
+    /// into place by the line layout of the inline formatting context it participates in.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#inline-formatting
+    pub(super) fn layout_atomic_inline(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
+        self.calculate_inline_block_logical_width(containing_block, scale_factor);
+        self.calculate_block_position(containing_block, scale_factor);
+        match self.box_type {
+            BoxType::InlineFlex => {
+                let definite_block_size =
+                    self.resolve_container_sizes(containing_block, scale_factor);
+                self.layout_flex_items(definite_block_size, scale_factor);
+            }
+            BoxType::InlineGrid => {
+                let definite_block_size =
+                    self.resolve_container_sizes(containing_block, scale_factor);
+                self.layout_grid_items(definite_block_size, scale_factor);
+            }
+            _ => self.layout_block_children(scale_factor),
+        }
+        self.apply_physical_properties(containing_block, scale_factor);
+    }
+
+    /// Calculate the logical width (inline size) of an inline-block, non-replaced element in
+    /// normal flow.  If the width is `auto`, the used value is the shrink-to-fit width.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#inlineblock-width
     ///
     /// Sets the inline margin/padding/border dimensions, and the inline size.
-    fn calculate_block_logical_width(
+    pub(super) fn calculate_inline_block_logical_width(
         &mut self,
         containing_block: PhysicalDimensions,
         scale_factor: f32,
     ) {
-        // FIXME: In all of our abstract layout code, we use self.writing_mode to determine logical
-        // dimensions, but I believe we should instead be looking at the writing mode of the
-        // containing block.  https://drafts.csswg.org/css-writing-modes-4/#logical-direction-layout
-
-        // FIXME: Always using the physical containing block width here is almost certainly wrong, but
-        // works...sometimes.  This should probably be the containing block `width` or `height`
-        // depending on the self.writing mode (or containing block writing mode?)...but this didn't
-        // work when I tried it, likely due to other bugs, such as the FIXME directly above.
-        // https://drafts.csswg.org/css-writing-modes-4/#logical-direction-layout
-        let containing_width = containing_block.content.width;
+        let containing_width = self.containing_block_inline_size(containing_block);
+        let available_inline_size = self.available_inline_size(containing_block, scale_factor);
         let cvs = self.node.computed_values();
 
-        let mut logical_width = cvs.logical_width();
-        let mut logical_margin_left = cvs.logical_margin(Side::Left);
-        let mut logical_margin_right = cvs.logical_margin(Side::Right);
+        // A computed value of 'auto' for 'margin-left' or 'margin-right' becomes a used value of
+        // '0', which is what `to_au` gives us for `auto`.
+        let logical_margin_left = cvs.logical_margin(Side::Left).to_au(containing_width);
+        let logical_margin_right = cvs.logical_margin(Side::Right).to_au(containing_width);
+        let logical_border_left = Au::from(cvs.logical_border_width(Side::Left));
+        let logical_border_right = Au::from(cvs.logical_border_width(Side::Right));
+        let logical_padding_left = cvs.logical_padding(Side::Left).to_au(containing_width);
+        let logical_padding_right = cvs.logical_padding(Side::Right).to_au(containing_width);
 
-        let logical_border_left = cvs.logical_border_width(Side::Left);
-        let logical_border_right = cvs.logical_border_width(Side::Right);
+        let horizontal = self.writing_mode.is_horizontal();
+        let box_sizing_edges = self.box_sizing_edges(horizontal, containing_block, scale_factor);
+        let specified_width = self.resolve_size_property(
+            cvs.logical_width(),
+            horizontal,
+            Some(available_inline_size),
+            box_sizing_edges,
+            scale_factor,
+        );
+        let logical_width = specified_width.unwrap_or_else(|| {
+            // Run the shrink-to-fit calculation _with_ the device scale factor applied, since the
+            // containing width and intrinsic sizes are already scaled.
+            let edges = (logical_margin_left
+                + logical_margin_right
+                + logical_border_left
+                + logical_border_right
+                + logical_padding_left
+                + logical_padding_right)
+                .scale_by(scale_factor);
+            let available_width = (available_inline_size - edges).max(Au(0));
+            let (min_content, max_content) = self.intrinsic_content_inline_sizes(scale_factor);
+            min_content.max(available_width).min(max_content)
+        });
+        // https://www.w3.org/TR/CSS2/visudet.html#min-max-widths
+        let logical_width = self
+            .inline_size_constraints(Some(containing_block), scale_factor)
+            .clamp(logical_width);
 
-        let logical_padding_left = cvs.logical_padding(Side::Left);
-        let logical_padding_right = cvs.logical_padding(Side::Right);
+        let d = &mut self.dimensions;
+        d.set_inline_size(logical_width);
+        d.set(
+            LogicalDirection::InlineStart,
+            BoxComponent::Padding,
+            logical_padding_left,
+        );
+        d.set(
+            LogicalDirection::InlineEnd,
+            BoxComponent::Padding,
+            logical_padding_right,
+        );
+        d.set(
+            LogicalDirection::InlineStart,
+            BoxComponent::Border,
+            logical_border_left,
+        );
+        d.set(
+            LogicalDirection::InlineEnd,
+            BoxComponent::Border,
+            logical_border_right,
+        );
+        d.set(
+            LogicalDirection::InlineStart,
+            BoxComponent::Margin,
+            logical_margin_left,
+        );
+        d.set(
+            LogicalDirection::InlineEnd,
+            BoxComponent::Margin,
+            logical_margin_right,
+        );
+    }
 
-        // Run block layout _with_ the device scale factor applied to ensure the proper values are
-        // computed.
-        let block_width = (logical_margin_left.to_px(containing_width)
-            + logical_margin_right.to_px(containing_width)
-            + logical_border_left
-            + logical_border_right
-            + logical_padding_left.to_px(containing_width)
-            + logical_padding_right.to_px(containing_width)
-            + logical_width.to_px(containing_width))
-            * scale_factor;
-
-        let auto = LengthPercentageOrAuto::Auto;
-        // If 'width' is not 'auto' and 'border-left-width' + 'padding-left' + 'width' +
-        // 'padding-right' + 'border-right-width' (plus any of 'margin-left' or 'margin-right'
-        // that are not 'auto') is larger than the width of the containing block, then any 'auto'
-        // values for 'margin-left' or 'margin-right' are, for the following rules, treated as zero.
-        if logical_width != auto && block_width > containing_width {
-            if logical_margin_left == auto {
-                logical_margin_left =
-                    LengthPercentageOrAuto::LengthPercentage(LengthPercentage::new_len(0.));
+    /// Gets the min-content and max-content inline sizes of this box's content, which are used
+    /// to compute shrink-to-fit widths and to resolve the intrinsic sizing keywords.  They're
+    /// measured the first time they're needed, and cached on the box from then on.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#shrink-to-fit-float
+    /// https://drafts.csswg.org/css-sizing-3/#intrinsic-sizes
+    pub(super) fn intrinsic_content_inline_sizes(&self, scale_factor: f32) -> (Au, Au) {
+        self.intrinsic_sizes.content_sizes(scale_factor, || {
+            self.measure_intrinsic_content_inline_sizes(scale_factor)
+        })
+    }
+
+    /// Measures the min-content and max-content inline sizes of this box's content.  See
+    /// `LayoutBox::intrinsic_content_inline_sizes`.
+    fn measure_intrinsic_content_inline_sizes(&self, scale_factor: f32) -> (Au, Au) {
+        let mut min_content = Au(0);
+        let mut max_content = Au(0);
+        match self.box_type {
+            BoxType::Anonymous
+            | BoxType::Block
+            | BoxType::InlineBlock
+            | BoxType::TableRowGroup
+            | BoxType::TableRow
+            | BoxType::TableCell
+            | BoxType::TableCaption => {
+                // Block-level children are stacked in the block axis, so the widest one wins.
+                for child in &self.children {
+                    let (child_min, child_max) = child.intrinsic_inline_contributions(scale_factor);
+                    min_content = min_content.max(child_min);
+                    max_content = max_content.max(child_max);
+                }
             }
-            if logical_margin_right == auto {
-                logical_margin_right =
-                    LengthPercentageOrAuto::LengthPercentage(LengthPercentage::new_len(0.));
+            BoxType::AnonymousInline | BoxType::Inline => {
+                // Inline-level children sit next to each other on a line, unless the line is
+                // broken between each of them.
+                // TODO: Text has no intrinsic size until we can measure it during layout.
+                for child in &self.children {
+                    let (child_min, child_max) = child.intrinsic_inline_contributions(scale_factor);
+                    min_content = min_content.max(child_min);
+                    max_content += child_max;
+                }
             }
-        }
-        // This value can be negative, indicating an overflow or "overconstraint", if the width of
-        // this box is greater than that of the containing one.
-        let underflow = containing_width - block_width;
-        match (
-            logical_width == auto,
-            logical_margin_left == auto,
-            logical_margin_right == auto,
-        ) {
-            // If all of the above have a computed value other than 'auto', the values are said to be
-            // "over-constrained" and one of the used values will have to be different from its computed
-            // value. If the 'direction' property of the containing block has the value 'ltr', the
-            // specified value of 'margin-right' is ignored and the value is calculated so as to make
-            // the equality true. If the value of 'direction' is 'rtl', this happens to 'margin-left' instead.
-            (false, false, false) => {
-                // TODO: Support `direction: rtl` property/value
-                logical_margin_right = LengthPercentageOrAuto::new_len_px(
-                    logical_margin_right.to_px(containing_width) + underflow,
-                )
+            BoxType::Flex | BoxType::InlineFlex => {
+                return self.flex_intrinsic_content_inline_sizes(scale_factor);
             }
-            // If there is exactly one margin value specified as 'auto', its used value follows
-            // from the equality.
-            (false, true, false) => {
-                logical_margin_left = LengthPercentageOrAuto::new_len_px(underflow)
+            BoxType::Grid | BoxType::InlineGrid => {
+                return self.grid_intrinsic_content_inline_sizes(scale_factor);
             }
-            (false, false, true) => {
-                logical_margin_right = LengthPercentageOrAuto::new_len_px(underflow)
+            BoxType::TableWrapper | BoxType::InlineTableWrapper => {
+                return self.table_wrapper_intrinsic_content_inline_sizes(scale_factor);
             }
-            // If both 'margin-left' and 'margin-right' are 'auto', their used values are equal.
-            // This centers the element with respect to the edges of the containing block.
-            (false, true, true) => {
-                logical_margin_left = LengthPercentageOrAuto::new_len_px(underflow / 2.);
-                logical_margin_right = LengthPercentageOrAuto::new_len_px(underflow / 2.);
+            BoxType::Table => return self.table_intrinsic_content_inline_sizes(scale_factor),
+            BoxType::Replaced | BoxType::InlineReplaced => {
+                let inline_size = self.replaced_intrinsic_inline_size(scale_factor);
+                return (inline_size, inline_size);
             }
-            // If 'width' is set to 'auto', any other 'auto' values become '0' and 'width' follows
-            // from the resulting equality.
-            (true, _, _) => {
-                if logical_margin_left == auto {
-                    logical_margin_left = LengthPercentageOrAuto::new_len(0.)
+            // Columns have no content.
+            BoxType::TableColumnGroup | BoxType::TableColumn => {}
+        }
+        (min_content, max_content)
+    }
+
+    /// Gets the min-content and max-content contributions of this box, which are the sizes of
+    /// its margin box when laid out at its min-content or max-content size.  Like the intrinsic
+    /// sizes of its content, they're cached on the box once measured.
+    ///
+    /// Percentages are treated as zero, since they can't be resolved without a containing block.
+    pub(super) fn intrinsic_inline_contributions(&self, scale_factor: f32) -> (Au, Au) {
+        self.intrinsic_sizes.contributions(scale_factor, || {
+            self.measure_intrinsic_inline_contributions(scale_factor)
+        })
+    }
+
+    /// Measures the min-content and max-content contributions of this box.  See
+    /// `LayoutBox::intrinsic_inline_contributions`.
+    fn measure_intrinsic_inline_contributions(&self, scale_factor: f32) -> (Au, Au) {
+        match self.box_type {
+            // Absolutely positioned boxes don't affect the size of their parent.
+            _ if self.is_absolutely_positioned() => (Au(0), Au(0)),
+            // The inline axis of the parent of an orthogonal flow root is the block axis of the
+            // orthogonal flow root, so it contributes its block size, which is only known once it's
+            // been laid out.
+            // https://drafts.csswg.org/css-writing-modes-4/#orthogonal-auto
+            _ if self.is_orthogonal_flow_root() => {
+                let mut laid_out = self.clone();
+                laid_out.layout(PhysicalDimensions::default(), scale_factor);
+                let margin_box = laid_out.physical_dimensions().margin_box();
+                let block_size = if self.writing_mode.is_horizontal() {
+                    margin_box.height
+                } else {
+                    margin_box.width
                 };
-                if logical_margin_right == auto {
-                    logical_margin_right = LengthPercentageOrAuto::new_len(0.)
+                (block_size, block_size)
+            }
+            // TODO: The margins, borders, and padding of inline boxes are ignored in line layout,
+            // so they don't contribute here either.
+            BoxType::Anonymous | BoxType::AnonymousInline | BoxType::Inline => {
+                self.intrinsic_content_inline_sizes(scale_factor)
+            }
+            // The size of a table box is determined by its wrapper, and the sizes of internal
+            // table boxes by their table.
+            BoxType::TableWrapper | BoxType::InlineTableWrapper => {
+                self.table_wrapper_intrinsic_inline_contributions(scale_factor)
+            }
+            BoxType::Table
+            | BoxType::TableRowGroup
+            | BoxType::TableRow
+            | BoxType::TableCell
+            | BoxType::TableColumnGroup
+            | BoxType::TableColumn => self.intrinsic_content_inline_sizes(scale_factor),
+            BoxType::TableCaption
+            | BoxType::Block
+            | BoxType::Flex
+            | BoxType::Grid
+            | BoxType::InlineBlock
+            | BoxType::InlineFlex
+            | BoxType::InlineGrid
+            | BoxType::Replaced
+            | BoxType::InlineReplaced => {
+                let cvs = self.node.computed_values();
+                let zero = Au(0);
+                let edges = (cvs.logical_margin(Side::Left).to_au(zero)
+                    + cvs.logical_margin(Side::Right).to_au(zero)
+                    + Au::from(cvs.logical_border_width(Side::Left))
+                    + Au::from(cvs.logical_border_width(Side::Right))
+                    + cvs.logical_padding(Side::Left).to_au(zero)
+                    + cvs.logical_padding(Side::Right).to_au(zero))
+                .scale_by(scale_factor);
+                let horizontal = self.writing_mode.is_horizontal();
+                let box_sizing_edges =
+                    self.box_sizing_edges(horizontal, PhysicalDimensions::default(), scale_factor);
+                let specified_size = self.resolve_size_property(
+                    cvs.logical_width(),
+                    horizontal,
+                    None,
+                    box_sizing_edges,
+                    scale_factor,
+                );
+                let (min_content, max_content) = match specified_size {
+                    Some(size) => (size, size),
+                    None => self.intrinsic_content_inline_sizes(scale_factor),
                 };
+                let constraints = self.inline_size_constraints(None, scale_factor);
+                (
+                    constraints.clamp(min_content) + edges,
+                    constraints.clamp(max_content) + edges,
+                )
+            }
+        }
+    }
 
-                if underflow >= CSSPixelLength::new(0.) {
-                    logical_width = LengthPercentageOrAuto::new_len_px(underflow)
-                } else {
-                    // Width cannot be negative, adjust `margin-right` instead
-                    // TODO: Support `direction: rtl` property/value
-                    logical_width = LengthPercentageOrAuto::new_len(0.);
-                    logical_margin_right = LengthPercentageOrAuto::new_len_px(
-                        logical_margin_right.to_px(containing_width) + underflow,
-                    );
-                }
+    /// Calculate the logical width (inline size) of a block-level non-replaced element in normal
+    /// flow.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#blockwidth
+    /// https://drafts.csswg.org/css-writing-modes-4/#vertical-layout
+    ///
+    /// Sets the inline margin/padding/border dimensions, and the inline size.
+    pub(super) fn calculate_block_logical_width(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
+        // The inline axis of an orthogonal flow root is the block axis of its containing block,
+        // where it's sized to fit its contents rather than stretched, and `auto` margins are zero.
+        // https://drafts.csswg.org/css-writing-modes-4/#orthogonal-auto
+        if self.is_orthogonal_flow_root() {
+            return self.calculate_inline_block_logical_width(containing_block, scale_factor);
+        }
+
+        // Otherwise, the inline axis of this box is that of its containing block.
+        let containing_width = self.containing_block_inline_size(containing_block);
+        let specified_width = self.specified_size(
+            self.writing_mode.is_horizontal(),
+            containing_block,
+            scale_factor,
+        );
+        let (mut logical_width, mut logical_margin_left, mut logical_margin_right) =
+            self.solve_block_logical_width(specified_width, containing_width, scale_factor);
+
+        // If the tentative width is greater than 'max-width', the rules above are applied again
+        // using the computed value of 'max-width' as the computed value for 'width'.  If the
+        // resulting width is smaller than 'min-width', they're applied again using 'min-width'.
+        // https://www.w3.org/TR/CSS2/visudet.html#min-max-widths
+        let constraints = self.inline_size_constraints(Some(containing_block), scale_factor);
+        if let Some(max) = constraints.max {
+            if logical_width > max {
+                let solution =
+                    self.solve_block_logical_width(Some(max), containing_width, scale_factor);
+                logical_width = solution.0;
+                logical_margin_left = solution.1;
+                logical_margin_right = solution.2;
+            }
+        }
+        if let Some(min) = constraints.min {
+            if logical_width < min {
+                let solution =
+                    self.solve_block_logical_width(Some(min), containing_width, scale_factor);
+                logical_width = solution.0;
+                logical_margin_left = solution.1;
+                logical_margin_right = solution.2;
             }
         }
+
+        let cvs = self.node.computed_values();
+        let logical_border_left = Au::from(cvs.logical_border_width(Side::Left));
+        let logical_border_right = Au::from(cvs.logical_border_width(Side::Right));
+        let logical_padding_left = cvs.logical_padding(Side::Left);
+        let logical_padding_right = cvs.logical_padding(Side::Right);
+
         // Now that we've calculated the inline used values, store them in this box's dimensions.
         let d = &mut self.dimensions;
-        d.set_inline_size(logical_width.to_px(containing_width));
+        d.set_inline_size(logical_width);
 
         d.set(
             LogicalDirection::InlineStart,
             BoxComponent::Padding,
-            logical_padding_left.to_px(containing_width),
+            logical_padding_left.to_au(containing_width),
         );
         d.set(
             LogicalDirection::InlineEnd,
             BoxComponent::Padding,
-            logical_padding_right.to_px(containing_width),
+            logical_padding_right.to_au(containing_width),
         );
 
         d.set(
@@ -328,137 +826,488 @@ impl LayoutBox {
         d.set(
             LogicalDirection::InlineStart,
             BoxComponent::Margin,
-            logical_margin_left.to_px(containing_width),
+            logical_margin_left,
         );
         d.set(
             LogicalDirection::InlineEnd,
             BoxComponent::Margin,
-            logical_margin_right.to_px(containing_width),
+            logical_margin_right,
         );
     }
 
+    /// Solves the constraint that the inline margins, borders, padding, and width of a block-level
+    /// non-replaced box sum to the width of its containing block, given the content-box width to
+    /// use, or `None` if it's `auto`.
+    ///
+    /// Returns the used width, and the used inline-start and inline-end margins.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#blockwidth
+    fn solve_block_logical_width(
+        &self,
+        logical_width: Option<Au>,
+        containing_width: Au,
+        scale_factor: f32,
+    ) -> (Au, Au, Au) {
+        let cvs = self.node.computed_values();
+        let zero = Au(0);
+        let margin = |side| match cvs.logical_margin(side) {
+            LengthPercentageOrAuto::LengthPercentage(lp) => Some(lp.to_au(containing_width)),
+            LengthPercentageOrAuto::Auto => None,
+        };
+        let mut logical_margin_left = margin(Side::Left);
+        let mut logical_margin_right = margin(Side::Right);
+
+        // Run block layout _with_ the device scale factor applied to ensure the proper values are
+        // computed.  The width is a used size, so it already is.
+        let block_width = (logical_margin_left.unwrap_or(zero)
+            + logical_margin_right.unwrap_or(zero)
+            + Au::from(cvs.logical_border_width(Side::Left))
+            + Au::from(cvs.logical_border_width(Side::Right))
+            + cvs.logical_padding(Side::Left).to_au(containing_width)
+            + cvs.logical_padding(Side::Right).to_au(containing_width))
+        .scale_by(scale_factor)
+            + logical_width.unwrap_or(zero);
+
+        // If 'width' is not 'auto' and 'border-left-width' + 'padding-left' + 'width' +
+        // 'padding-right' + 'border-right-width' (plus any of 'margin-left' or 'margin-right'
+        // that are not 'auto') is larger than the width of the containing block, then any 'auto'
+        // values for 'margin-left' or 'margin-right' are, for the following rules, treated as zero.
+        if logical_width.is_some() && block_width > containing_width {
+            logical_margin_left = logical_margin_left.or(Some(zero));
+            logical_margin_right = logical_margin_right.or(Some(zero));
+        }
+        // This value can be negative, indicating an overflow or "overconstraint", if the width of
+        // this box is greater than that of the containing one.
+        let underflow = containing_width - block_width;
+        match (logical_width, logical_margin_left, logical_margin_right) {
+            // If all of the above have a computed value other than 'auto', the values are said to be
+            // "over-constrained" and one of the used values will have to be different from its computed
+            // value. If the 'direction' property of the containing block has the value 'ltr', the
+            // specified value of 'margin-right' is ignored and the value is calculated so as to make
+            // the equality true. If the value of 'direction' is 'rtl', this happens to 'margin-left' instead.
+            (Some(width), Some(margin_left), Some(margin_right)) => {
+                // TODO: Support `direction: rtl` property/value
+                (width, margin_left, margin_right + underflow)
+            }
+            // If there is exactly one margin value specified as 'auto', its used value follows
+            // from the equality.
+            (Some(width), None, Some(margin_right)) => (width, underflow, margin_right),
+            (Some(width), Some(margin_left), None) => (width, margin_left, underflow),
+            // If both 'margin-left' and 'margin-right' are 'auto', their used values are equal.
+            // This centers the element with respect to the edges of the containing block.
+            (Some(width), None, None) => (width, underflow / 2, underflow - underflow / 2),
+            // If 'width' is set to 'auto', any other 'auto' values become '0' and 'width' follows
+            // from the resulting equality.
+            (None, margin_left, margin_right) => {
+                let margin_left = margin_left.unwrap_or(zero);
+                let margin_right = margin_right.unwrap_or(zero);
+                if underflow >= zero {
+                    (underflow, margin_left, margin_right)
+                } else {
+                    // Width cannot be negative, adjust `margin-right` instead
+                    // TODO: Support `direction: rtl` property/value
+                    (zero, margin_left, margin_right + underflow)
+                }
+            }
+        }
+    }
+
     /// Calculates this box's (x, y) position on the page.
-    fn calculate_block_position(
+    pub(super) fn calculate_block_position(
         &mut self,
         containing_block: PhysicalDimensions,
         scale_factor: f32,
     ) {
         let cvs = self.node.computed_values();
-        let containing_width = if self.writing_mode.is_horizontal() {
-            containing_block.content.width
-        } else {
-            containing_block.content.height
-        };
+        let containing_width = self.containing_block_inline_size(containing_block);
+        let containing_horizontal = self.containing_block_writing_mode().is_horizontal();
         let d = &mut self.dimensions;
 
         d.set(
             LogicalDirection::BlockEnd,
             BoxComponent::Padding,
-            cvs.padding_bottom.size.to_px(containing_width),
+            cvs.logical_padding(Side::Bottom).to_au(containing_width),
         );
         d.set(
             LogicalDirection::BlockStart,
             BoxComponent::Padding,
-            cvs.padding_top.size.to_px(containing_width),
+            cvs.logical_padding(Side::Top).to_au(containing_width),
         );
 
         d.set(
             LogicalDirection::BlockEnd,
             BoxComponent::Border,
-            cvs.border_bottom_width.size,
+            Au::from(cvs.logical_border_width(Side::Bottom)),
         );
         d.set(
             LogicalDirection::BlockStart,
             BoxComponent::Border,
-            cvs.border_top_width.size,
+            Au::from(cvs.logical_border_width(Side::Top)),
         );
 
         d.set(
             LogicalDirection::BlockEnd,
             BoxComponent::Margin,
-            cvs.margin_bottom.size.to_px(containing_width),
+            cvs.logical_margin(Side::Bottom).to_au(containing_width),
         );
         d.set(
             LogicalDirection::BlockStart,
             BoxComponent::Margin,
-            cvs.margin_top.size.to_px(containing_width),
+            cvs.logical_margin(Side::Top).to_au(containing_width),
         );
 
         // Ensure window scale factor is applied before computing the start-{x, y} coordinates.
         d.scale_edges_by(scale_factor);
 
-        let container_inline_start_coord = if self.writing_mode.is_horizontal() {
-            containing_block.content.start_x
+        // The box is placed in the flow of its containing block, so its position is found in the
+        // writing mode of the containing block: at the inline-start edge of the containing block,
+        // and after the block size laid out in the containing block so far.
+        // https://drafts.csswg.org/css-writing-modes-4/#logical-direction-layout
+        // TODO: Support `direction: rtl` and the right-to-left block flow direction of
+        // `vertical-rl`, which both need the final size of the containing block to place boxes.
+        let d = d.physical();
+        let content = containing_block.content;
+        let (start_x, start_y) = if containing_horizontal {
+            (content.start_x, content.end_y())
         } else {
-            containing_block.content.start_y
+            (content.end_x(), content.start_y)
         };
+        self.dimensions
+            .set_start_x(start_x + d.margin.left + d.border.left + d.padding.left);
+        self.dimensions
+            .set_start_y(start_y + d.margin.top + d.border.top + d.padding.top);
+    }
 
-        let container_block_size = if self.writing_mode.is_horizontal() {
-            containing_block.content.height
-        } else {
-            containing_block.content.width
+    /// Assuming `self` is a block-level block box that doesn't establish a new block formatting
+    /// context, calculate the dimensions of this box and any children.  Floats in its content
+    /// are placed in `floats`, the float context of the block formatting context it's part of.
+    ///
+    /// The block-start margin of this box collapses with `margin_before`, the collapsed margin
+    /// after the content laid out before it, unless it has `clearance`.  Returns the collapsed
+    /// margin after this box, and whether the box was collapsed through, in which case that
+    /// margin also includes `margin_before`, and the box takes up no space in its containing block.
+    ///
+    /// https://www.w3.org/TR/CSS2/box.html#collapsing-margins
+    fn layout_block_in_flow(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        floats: &mut FloatContext,
+        margin_before: CollapsedMargin,
+        clearance: Option<Au>,
+        scale_factor: f32,
+    ) -> (CollapsedMargin, bool) {
+        self.calculate_block_logical_width(containing_block, scale_factor);
+        self.calculate_block_position(containing_block, scale_factor);
+
+        // The box doesn't establish a new block formatting context, so it isn't an orthogonal flow
+        // root, and its block axis is that of its containing block.
+        let horizontal = self.writing_mode.is_horizontal();
+        let d = self.physical_dimensions();
+        let (_, margin_box_block_start, _, margin_box_block_size) =
+            logical_rect(d.margin_box(), horizontal);
+        let (_, border_box_block_start, _, border_box_block_size) =
+            logical_rect(d.border_box(), horizontal);
+        let (_, content_block_start, _, content_block_size) = logical_rect(d.content, horizontal);
+        let margin_block_start = border_box_block_start - margin_box_block_start;
+        let margin_block_end = margin_box_block_start + margin_box_block_size
+            - border_box_block_start
+            - border_box_block_size;
+        let edges_block_start = content_block_start - border_box_block_start;
+        let edges_block_end = border_box_block_start + border_box_block_size
+            - content_block_start
+            - content_block_size;
+
+        // The box is positioned with its margin box right after the content before it.  Its
+        // block-start margin collapses with the margins before it, and with those of its first
+        // in-flow child if nothing separates them, in which case the box is moved into place
+        // once the margins of its children are known.
+        let margin_before = margin_before.adjoin(CollapsedMargin::new(margin_block_start));
+        let adjoins_children = clearance.is_none() && edges_block_start == Au(0);
+        let offset = match clearance {
+            Some(clearance) => clearance,
+            None if adjoins_children => -margin_block_start,
+            None => margin_before.solve() - margin_block_start,
         };
-        d.set_inline_start_coord(
-            (container_inline_start_coord
-                + d.get(LogicalDirection::InlineStart, BoxComponent::Margin)
-                + d.get(LogicalDirection::InlineStart, BoxComponent::Border)
-                + d.get(LogicalDirection::InlineStart, BoxComponent::Padding))
-            .into(),
-        );
-        // TODO: Always adding containing_block.content.start_y is almost certainly wrong, but
-        // works...sometimes..  This should probably be `container_block_start_coord`...but this
-        // didn't work when I tried it, likely due to other bugs, such as the fact that we may not
-        // be mapping flow-relative directions correctly (we should sometimes be using the
-        // containing block writing-mode, but we always use the writing-mode of the `self` block.)
-        // https://drafts.csswg.org/css-writing-modes-4/#logical-direction-layout
-        d.set_block_start_coord(
-            (container_block_size
-                + containing_block.content.start_y
-                + d.get(LogicalDirection::BlockStart, BoxComponent::Margin)
-                + d.get(LogicalDirection::BlockStart, BoxComponent::Border)
-                + d.get(LogicalDirection::BlockStart, BoxComponent::Padding))
-            .into(),
+        self.dimensions
+            .set_block_start_coord(self.dimensions.get_block_start_coord() + offset);
+        let (margin, start_unresolved) = self.layout_block_children_in(
+            floats,
+            if adjoins_children {
+                Some(margin_before)
+            } else {
+                None
+            },
+            scale_factor,
         );
+        if start_unresolved {
+            self.dimensions
+                .set_block_start_coord(self.dimensions.get_block_start_coord() + margin.solve());
+        }
+
+        // The block-end margin of the box collapses with that of its last in-flow child if its
+        // block size depends on its content, and nothing separates them.  A box whose block-start
+        // and block-end margins adjoin is collapsed through.
+        let adjoins_last_child = edges_block_end == Au(0)
+            && self
+                .specified_block_size(containing_block, scale_factor)
+                .is_none();
+        let collapsed_through = start_unresolved
+            && adjoins_last_child
+            && self.dimensions.get_content_block_size() == Au(0)
+            && self
+                .block_size_constraints(Some(containing_block), scale_factor)
+                .min
+                .unwrap_or(Au(0))
+                == Au(0);
+        let margin_after = if start_unresolved {
+            // The margins of the children were all collapsed into the block-start margin.
+            if collapsed_through {
+                margin.adjoin(CollapsedMargin::new(margin_block_end))
+            } else {
+                CollapsedMargin::new(margin_block_end)
+            }
+        } else if adjoins_last_child {
+            margin.adjoin(CollapsedMargin::new(margin_block_end))
+        } else {
+            self.dimensions
+                .set_block_size(self.dimensions.get_content_block_size() + margin.solve());
+            CollapsedMargin::new(margin_block_end)
+        };
+        self.apply_physical_properties(containing_block, scale_factor);
+        (margin_after, collapsed_through)
     }
 
-    fn layout_block_children(&mut self, scale_factor: f32) {
+    /// Lays out the children of this block container, which establishes a new block formatting
+    /// context for them.  The block size of this box grows to contain any floats among its
+    /// descendants, and the margins of its children, which don't collapse through it.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#root-height
+    pub(super) fn layout_block_children(&mut self, scale_factor: f32) {
+        let mut floats = FloatContext::new(self.writing_mode.is_horizontal());
+        let (margin, _) = self.layout_block_children_in(&mut floats, None, scale_factor);
+        self.dimensions
+            .set_block_size((self.dimensions.get_content_block_size() + margin.solve()).max(Au(0)));
+        if let Some(float_block_end) = floats.block_end() {
+            let floats_block_size = float_block_end - self.dimensions.get_block_start_coord();
+            if floats_block_size > self.dimensions.get_content_block_size() {
+                self.dimensions.set_block_size(floats_block_size);
+            }
+        }
+    }
+
+    /// Lays out the children of this block container, placing floats in `floats`.
+    ///
+    /// If `start_margin` is set, the block-start margin of this box adjoins that of its first
+    /// in-flow child, and is collapsed with the margins before it into `start_margin`.  Its
+    /// content then starts where the border box of the first child that isn't collapsed through
+    /// does.
+    ///
+    /// Returns the collapsed margin after the last in-flow child, which is yet to be added to the
+    /// block size of this box, and whether the start of this box is still unresolved since every
+    /// child was collapsed through, in which case that margin includes `start_margin`.
+    fn layout_block_children_in(
+        &mut self,
+        floats: &mut FloatContext,
+        start_margin: Option<CollapsedMargin>,
+        scale_factor: f32,
+    ) -> (CollapsedMargin, bool) {
+        // Children are stacked after the current block size, which is left over from the previous
+        // layout if this box has been laid out before.
+        self.dimensions.set_block_size(Au(0));
+        let direction = self.direction;
+        let horizontal = self.writing_mode.is_horizontal();
+        let mut start_unresolved = start_margin.is_some();
+        let mut margin = start_margin.unwrap_or_default();
         let mut physical_dimensions = self.dimensions.physical();
         for child in &mut self.children {
-            child.layout(physical_dimensions, scale_factor);
-            // Track the block size so each child is laid out after the previous one.
+            if child.is_absolutely_positioned() {
+                // Out-of-flow boxes don't take up any space, but would have started right after
+                // the content laid out so far.
+                let content = physical_dimensions.content;
+                child.static_position = if horizontal {
+                    (content.start_x, content.end_y())
+                } else {
+                    (content.end_x(), content.start_y)
+                };
+                continue;
+            }
+            if child.is_floated() {
+                // Floats don't take up any space either, but are placed no higher than the
+                // content laid out so far, and the margins after it.
+                child.layout_float(physical_dimensions, scale_factor);
+                let (inline_start, block_start, inline_size, block_size) =
+                    logical_rect(physical_dimensions.content, floats.is_horizontal());
+                child.place_float(
+                    floats,
+                    direction,
+                    block_start + block_size + margin.solve(),
+                    (inline_start, inline_start + inline_size),
+                );
+                continue;
+            }
+
+            // Clearance is found from where the border box of the child would be if its
+            // block-start margin collapsed with the margins before it.  If it has clearance, those
+            // margins are resolved here instead.
+            let flow_block_end =
+                self.dimensions.get_block_start_coord() + self.dimensions.get_content_block_size();
+            let margin_block_start = child.block_start_margin(physical_dimensions, scale_factor);
+            let clearance = child.clearance(
+                floats,
+                direction,
+                flow_block_end
+                    + margin
+                        .adjoin(CollapsedMargin::new(margin_block_start))
+                        .solve(),
+                flow_block_end + margin.solve() + margin_block_start,
+            );
+            if clearance.is_some() {
+                if start_unresolved {
+                    self.dimensions.set_block_start_coord(
+                        self.dimensions.get_block_start_coord() + margin.solve(),
+                    );
+                    start_unresolved = false;
+                } else {
+                    self.dimensions
+                        .set_block_size(self.dimensions.get_content_block_size() + margin.solve());
+                }
+                margin = CollapsedMargin::default();
+                physical_dimensions = self.dimensions.physical();
+            }
+
+            let mut space_beside_floats = Au(0);
+            let (margin_after, collapsed_through) = match child.box_type {
+                BoxType::AnonymousInline => {
+                    // Line boxes separate the margins before them from those after them, unless
+                    // there aren't any, so the inline formatting context starts after the margins.
+                    let mut containing_block = physical_dimensions;
+                    if horizontal {
+                        containing_block.content.height += margin.solve();
+                    } else {
+                        containing_block.content.width += margin.solve();
+                    }
+                    child.layout_inline_formatting_context(containing_block, floats, scale_factor);
+                    if child.dimensions.get_content_block_size() == Au(0) {
+                        (margin, true)
+                    } else {
+                        (CollapsedMargin::default(), false)
+                    }
+                }
+                BoxType::Block if !child.establishes_bfc() => child.layout_block_in_flow(
+                    physical_dimensions,
+                    floats,
+                    margin,
+                    clearance,
+                    scale_factor,
+                ),
+                _ => {
+                    let (margin_after, space) = child.layout_beside_floats(
+                        physical_dimensions,
+                        floats,
+                        margin,
+                        clearance,
+                        scale_factor,
+                    );
+                    space_beside_floats = space;
+                    (margin_after, false)
+                }
+            };
+            margin = margin_after;
+            if collapsed_through {
+                continue;
+            }
+
+            // Track the block size so each child is laid out after the previous one.  The child
+            // may be an orthogonal flow root, so measure it in the writing mode of this box.
+            let (_, border_box_block_start, _, border_box_block_size) =
+                logical_rect(child.physical_dimensions().border_box(), horizontal);
+            if start_unresolved {
+                // The margins before the child collapsed with the block-start margin of this box,
+                // so its content starts where the child would have without any floats in the way.
+                self.dimensions
+                    .set_block_start_coord(border_box_block_start - space_beside_floats);
+                start_unresolved = false;
+            }
             self.dimensions.set_block_size(
-                self.dimensions.get_content_block_size() + child.dimensions.margin_box_block_size(),
+                border_box_block_start + border_box_block_size
+                    - self.dimensions.get_block_start_coord(),
             );
             physical_dimensions = self.dimensions.physical();
         }
+        (margin, start_unresolved)
+    }
+
+    /// Moves this box and all of its descendants by the given physical offsets.
+    pub(super) fn translate(&mut self, dx: Au, dy: Au) {
+        self.dimensions.translate(dx, dy);
+        self.static_position.0 += dx;
+        self.static_position.1 += dy;
+        for child in &mut self.children {
+            child.translate(dx, dy);
+        }
+    }
+
+    /// Gets the block axis coordinate of the baseline of this block container's last in-flow line
+    /// box, if there is one.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#propdef-vertical-align
+    pub(super) fn last_line_baseline(&self) -> Option<Au> {
+        self.children
+            .iter()
+            .rev()
+            .find_map(|child| match child.box_type {
+                _ if child.is_absolutely_positioned() || child.is_floated() => None,
+                BoxType::AnonymousInline => child
+                    .line_baseline
+                    .map(|baseline| child.dimensions.get_block_start_coord() + baseline),
+                BoxType::Block | BoxType::Flex | BoxType::Grid => child.last_line_baseline(),
+                // TODO: The baseline of a table is the baseline of its first row.
+                BoxType::Anonymous
+                | BoxType::Inline
+                | BoxType::InlineBlock
+                | BoxType::InlineFlex
+                | BoxType::InlineGrid
+                | BoxType::Replaced
+                | BoxType::InlineReplaced
+                | BoxType::TableWrapper
+                | BoxType::InlineTableWrapper
+                | BoxType::Table
+                | BoxType::TableRowGroup
+                | BoxType::TableRow
+                | BoxType::TableCell
+                | BoxType::TableColumnGroup
+                | BoxType::TableColumn
+                | BoxType::TableCaption => None,
+            })
     }
 
     /// If this block has any explicitly set values (e.g. lenght or percentage values, NOT auto) for
-    /// physical properties (e.g. `width`, `height`, left/bottom/right/top properties), this
+    /// physical properties (e.g. `width`, `height`), this
     /// function will set them.  Otherwise, the used values will be those given by other layout
     /// equations.
-    fn apply_physical_properties(
+    ///
+    /// Either way, the used sizes are then clamped by `min-width`/`max-width` and
+    /// `min-height`/`max-height`.
+    pub(super) fn apply_physical_properties(
         &mut self,
         containing_block: PhysicalDimensions,
         scale_factor: f32,
     ) {
-        let width = self.node.computed_values().width.size;
-        if let LengthPercentageOrAuto::LengthPercentage(lp) = width {
-            self.dimensions
-                .set_phys_width(lp.to_px(containing_block.content.width) * scale_factor);
-        }
+        let content = self.dimensions.physical().content;
 
-        let height = self.node.computed_values().height.size;
-        if let LengthPercentageOrAuto::LengthPercentage(lp) = height {
-            self.dimensions
-                .set_phys_height(lp.to_px(containing_block.content.height) * scale_factor);
-        }
+        let width = self
+            .specified_size(true, containing_block, scale_factor)
+            .unwrap_or(content.width);
+        let width_constraints = self.size_constraints(true, Some(containing_block), scale_factor);
+        self.dimensions
+            .set_phys_width(width_constraints.clamp(width));
 
-        // FIXME: The physical bottom/left/right/top properties for margin, border, and padding
-        // are broken in non-horizontal writing modes because they are applied logically, when
-        // these properties should instead be applied physically.  E.g., margin-left should always affect
-        // the page-relative left margin of the box, but instead reflects the flow relative margin
-        // left, which physically ends up being the top margin.
+        let height = self
+            .specified_size(false, containing_block, scale_factor)
+            .unwrap_or(content.height);
+        let height_constraints = self.size_constraints(false, Some(containing_block), scale_factor);
+        self.dimensions
+            .set_phys_height(height_constraints.clamp(height));
     }
 }
 
@@ -474,9 +1323,10 @@ impl LayoutBox {
 /// BX-O--LC --          NAV RenderFlexibleBox at (0,0) size 260x819 renderer->(0x30ddf2e20) node->(0x30dde41c0)
 impl DumpLayout for LayoutBox {
     fn dump_layout<W: Write>(&self, write_to: &mut W, indent_spaces: usize) {
-        let node_name = match self.box_type {
-            BoxType::Anonymous | BoxType::AnonymousInline => "".to_owned(),
-            BoxType::Block | BoxType::Inline => self.node.data().dump_layout_format(),
+        let node_name = if self.anonymous {
+            "".to_owned()
+        } else {
+            self.node.data().dump_layout_format()
         };
         let physical_dimensions = self.dimensions.physical();
         writeln!(
@@ -492,6 +1342,19 @@ impl DumpLayout for LayoutBox {
             indent_spaces = indent_spaces,
         )
         .expect("error writing layout dump");
+        if let Some(overflow) = self.scrollable_overflow {
+            writeln!(
+                write_to,
+                "{:indent_spaces$}  scrollable overflow at ({}, {}) size {}x{}",
+                "",
+                overflow.start_x.dump_layout_format(),
+                overflow.start_y.dump_layout_format(),
+                overflow.width.dump_layout_format(),
+                overflow.height.dump_layout_format(),
+                indent_spaces = indent_spaces,
+            )
+            .expect("error writing layout dump");
+        }
 
         self.children.iter().for_each(|child| {
             child.dump_layout(write_to, indent_spaces + 2);
@@ -507,6 +1370,53 @@ pub enum BoxType {
     ///
     /// For more information about this box type, see: https://drafts.csswg.org/css-inline-3/#model
     AnonymousInline,
+    /// A block-level block container, such as those generated by `display: block` and
+    /// `display: flow-root`.
     Block,
+    /// A block-level flex container, generated by `display: flex`.
+    ///
+    /// https://drafts.csswg.org/css-flexbox-1/#flex-containers
+    Flex,
+    /// A block-level grid container, generated by `display: grid`.
+    ///
+    /// https://drafts.csswg.org/css-grid-1/#grid-containers
+    Grid,
+    /// A block-level replaced box, such as an `<img>` with `display: block`.  Its content is an
+    /// image rather than child boxes.
+    ///
+    /// https://www.w3.org/TR/CSS2/conform.html#replaced-element
+    Replaced,
     Inline,
+    /// An inline-level block container, which is laid out as a single atomic inline box.
+    InlineBlock,
+    /// An inline-level flex container, generated by `display: inline-flex`, which is laid out as a
+    /// single atomic inline box.
+    InlineFlex,
+    /// An inline-level grid container, generated by `display: inline-grid`, which is laid out as a
+    /// single atomic inline box.
+    InlineGrid,
+    /// An inline-level replaced box, such as an `<img>`, which is laid out as a single atomic
+    /// inline box.
+    InlineReplaced,
+    /// The block-level box generated by `display: table`, which contains the table box and its
+    /// caption boxes.  Takes the margins of the table element.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#model
+    TableWrapper,
+    /// The inline-level box generated by `display: inline-table`, which is laid out as a single
+    /// atomic inline box.  Otherwise the same as `TableWrapper`.
+    InlineTableWrapper,
+    /// The box containing the rows and columns of a table, which takes the borders, padding, and
+    /// background of the table element.
+    Table,
+    /// A row group, generated by `display: table-row-group`, `table-header-group`, or
+    /// `table-footer-group`.
+    TableRowGroup,
+    TableRow,
+    /// A table cell, which is a block container.
+    TableCell,
+    TableColumnGroup,
+    TableColumn,
+    /// A table caption, which is a block container placed above or below the table box.
+    TableCaption,
 }
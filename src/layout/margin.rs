@@ -0,0 +1,65 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/box.html#collapsing-margins
+//  * https://drafts.csswg.org/css-box-4/#margin-collapse
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::layout_box::LayoutBox;
+use crate::style::values::used::ToAu;
+use crate::Side;
+use app_units::Au;
+
+/// A set of adjoining block axis margins, which collapse into a single margin as wide as the
+/// largest positive margin plus the most negative one.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(super) struct CollapsedMargin {
+    max_positive: Au,
+    min_negative: Au,
+}
+
+impl CollapsedMargin {
+    pub(super) fn new(margin: Au) -> Self {
+        CollapsedMargin {
+            max_positive: margin.max(Au(0)),
+            min_negative: margin.min(Au(0)),
+        }
+    }
+
+    /// Collapses these margins with the adjoining `other` margins.
+    pub(super) fn adjoin(self, other: CollapsedMargin) -> Self {
+        CollapsedMargin {
+            max_positive: self.max_positive.max(other.max_positive),
+            min_negative: self.min_negative.min(other.min_negative),
+        }
+    }
+
+    /// Gets the width of the collapsed margin.
+    pub(super) fn solve(self) -> Au {
+        self.max_positive + self.min_negative
+    }
+}
+
+impl LayoutBox {
+    /// Resolves the margin of this in-flow, block-level box on its block-start side in the block
+    /// axis of its containing block, before the box is laid out.  Anonymous boxes have no
+    /// margins.
+    ///
+    /// Boxes are placed in the flow of their containing block from its top side in horizontal
+    /// writing modes, and from its left side otherwise.  See `calculate_block_position`.
+    pub(super) fn block_start_margin(
+        &self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> Au {
+        if self.anonymous {
+            return Au(0);
+        }
+        let side = if self.containing_block_writing_mode().is_horizontal() {
+            Side::Top
+        } else {
+            Side::Left
+        };
+        self.computed_values()
+            .margin(side)
+            .to_au(self.containing_block_inline_size(containing_block))
+            .scale_by(scale_factor)
+    }
+}
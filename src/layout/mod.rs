@@ -1,48 +1,171 @@
 // Useful links:
 //  * https://www.w3.org/TR/css-display-3/#css-box
 //  * https://www.w3.org/TR/2018/WD-css-box-3-20181218/#intro
+mod border_radius;
 pub mod dimensions;
+mod flex;
+mod float;
+pub mod fragmentation;
+mod grid;
+mod incremental;
+mod inline;
 pub mod layout_box;
+mod margin;
+mod overflow;
+mod position;
 pub mod rect;
+mod replaced;
+mod sizing;
+mod table;
 
 use crate::dom::tree::{NodeData, NodeRef};
 use crate::layout::dimensions::PhysicalDimensions;
+pub use crate::layout::incremental::LayoutStats;
 use crate::layout::layout_box::{BoxType, LayoutBox};
 use crate::layout::rect::Rect;
-use crate::style::values::computed::length::CSSPixelLength;
-use crate::style::values::computed::Display;
+use crate::style::values::computed::length::{
+    CSSPixelLength, LengthPercentage, LengthPercentageOrAuto,
+};
+use crate::style::values::computed::{Display, Float};
 use crate::style::values::CSSFloat;
+use app_units::Au;
+use std::cell::Cell;
 use std::io::Write;
 
+thread_local! {
+    /// The initial containing block of the layout in progress, which has the dimensions of the
+    /// viewport.  Set by `global_layout`.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#containing-block-details
+    static INITIAL_CONTAINING_BLOCK: Cell<Rect> = Cell::new(Rect::default());
+}
+
+/// Gets the initial containing block of the layout in progress.  Whatever is computed from it is
+/// recorded as depending on the size of the viewport, so that it isn't reused after a resize.
+fn initial_containing_block() -> Rect {
+    incremental::note_viewport_dependency();
+    INITIAL_CONTAINING_BLOCK.with(Cell::get)
+}
+
 /// Takes a DOM node and builds the corresponding layout tree of it and its children.  Returns
 /// `None` if `node` is a `Display::None`.
 pub fn build_layout_tree(node: NodeRef) -> Option<LayoutBox> {
+    build_layout_box(node, false)
+}
+
+/// Builds the layout box for `node` and its children.  If `blockify` is true, the box's outer
+/// display type is made block-level, as is done for flex items.  Absolutely positioned and floated
+/// boxes are always blockified.
+///
+/// https://www.w3.org/TR/CSS2/visuren.html#dis-pos-flo
+fn build_layout_box(node: NodeRef, blockify: bool) -> Option<LayoutBox> {
     let computed_values = &*node.computed_values();
-    // TODO: We need to think about the validity of making strong-ref clones to nodes here (and elsewhere).
-    // Will things get properly dropped?  Maybe LayoutBox should store a `Weak` ref?
-    let mut layout_box = match computed_values.display {
-        Display::Block => LayoutBox::new(
-            BoxType::Block,
-            node.clone(),
-            computed_values.direction,
-            computed_values.writing_mode,
-        ),
-        Display::Inline => LayoutBox::new(
-            BoxType::Inline,
-            node.clone(),
-            computed_values.direction,
-            computed_values.writing_mode,
-        ),
+    let display = if blockify || is_absolutely_positioned(&node) || is_floated(&node) {
+        computed_values.display.blockified()
+    } else {
+        computed_values.display
+    };
+    let box_type = match display {
+        // A `flow-root` box is a block box that always establishes a new block formatting context.
+        Display::Block | Display::FlowRoot => BoxType::Block,
+        Display::Flex => BoxType::Flex,
+        Display::Grid => BoxType::Grid,
+        Display::Inline => BoxType::Inline,
+        Display::InlineBlock => BoxType::InlineBlock,
+        Display::InlineFlex => BoxType::InlineFlex,
+        Display::InlineGrid => BoxType::InlineGrid,
+        Display::Table => BoxType::TableWrapper,
+        Display::InlineTable => BoxType::InlineTableWrapper,
+        Display::TableRowGroup | Display::TableHeaderGroup | Display::TableFooterGroup => {
+            BoxType::TableRowGroup
+        }
+        Display::TableRow => BoxType::TableRow,
+        Display::TableCell => BoxType::TableCell,
+        Display::TableColumnGroup => BoxType::TableColumnGroup,
+        Display::TableColumn => BoxType::TableColumn,
+        Display::TableCaption => BoxType::TableCaption,
         Display::None => {
             return None;
         }
     };
+    // The inner display type of a replaced element is ignored, since its content is an image
+    // rather than child boxes.
+    // https://drafts.csswg.org/css-display/#replaced-element
+    let box_type = if is_replaced(&node) {
+        match box_type {
+            BoxType::Block | BoxType::Flex | BoxType::Grid | BoxType::TableWrapper => {
+                BoxType::Replaced
+            }
+            BoxType::Inline
+            | BoxType::InlineBlock
+            | BoxType::InlineFlex
+            | BoxType::InlineGrid
+            | BoxType::InlineTableWrapper => BoxType::InlineReplaced,
+            _ => box_type,
+        }
+    } else {
+        box_type
+    };
+    // TODO: We need to think about the validity of making strong-ref clones to nodes here (and elsewhere).
+    // Will things get properly dropped?  Maybe LayoutBox should store a `Weak` ref?
+    let mut layout_box = LayoutBox::new(
+        box_type,
+        node.clone(),
+        computed_values.direction,
+        computed_values.writing_mode,
+    );
 
-    for child in node.children() {
-        let child_computed_values = &*child.computed_values();
-        match child_computed_values.display {
-            Display::Block => {
-                if let Some(child_box) = build_layout_tree(child.clone()) {
+    match box_type {
+        BoxType::Flex | BoxType::Grid | BoxType::InlineFlex | BoxType::InlineGrid => {
+            add_container_items(&mut layout_box, &node)
+        }
+        BoxType::TableWrapper | BoxType::InlineTableWrapper => {
+            table::add_table_children(&mut layout_box, node.children())
+        }
+        BoxType::TableRowGroup => table::add_row_group_children(&mut layout_box, node.children()),
+        BoxType::TableRow => table::add_row_children(&mut layout_box, node.children()),
+        BoxType::TableColumnGroup => {
+            table::add_column_group_children(&mut layout_box, node.children())
+        }
+        // Columns don't render their children, and neither do replaced boxes.
+        BoxType::TableColumn | BoxType::Replaced | BoxType::InlineReplaced => {}
+        _ => add_block_container_children(&mut layout_box, node.children()),
+    }
+    Some(layout_box)
+}
+
+/// Adds the boxes generated by `children` to `layout_box`, in normal flow.
+///
+/// Consecutive internal table boxes, such as cells and rows, are misparented here, and so are
+/// wrapped in an anonymous table along with any white space between them.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+fn add_block_container_children<I: IntoIterator<Item = NodeRef>>(
+    layout_box: &mut LayoutBox,
+    children: I,
+) {
+    let mut misparented = Vec::new();
+    for child in children {
+        let display = child.computed_values().display;
+        // Absolutely positioned and floated internal table boxes are blockified rather than
+        // misparented.  Other absolutely positioned and floated boxes are blockified too, but stay
+        // among the inline-level content they'd have been part of, so that their static position
+        // can be found, or so that they can float beside the lines that follow them.
+        let display = if display.is_internal_table()
+            && (is_absolutely_positioned(&child) || is_floated(&child))
+        {
+            display.blockified()
+        } else {
+            display
+        };
+        if display.is_internal_table() || (!misparented.is_empty() && is_white_space_text(&child)) {
+            misparented.push(child);
+            continue;
+        }
+        table::add_anonymous_table(layout_box, &mut misparented);
+        match display {
+            Display::Block | Display::FlowRoot | Display::Flex | Display::Grid | Display::Table => {
+                if let Some(child_box) = build_layout_box(child.clone(), false) {
                     // TODO: We don't handle the case where a block-flow child box is added to an inline box.
                     // This current behavior is wrong — we should be checking if `node` is an `Display::Inline` and
                     // doing something different here.  To fix, see: https://www.w3.org/TR/CSS2/visuren.html#box-gen
@@ -51,39 +174,185 @@ pub fn build_layout_tree(node: NodeRef) -> Option<LayoutBox> {
                     layout_box.add_child(child_box)
                 }
             }
-            Display::Inline => {
-                if let Some(child_box) = build_layout_tree(child.clone()) {
+            Display::Inline
+            | Display::InlineBlock
+            | Display::InlineFlex
+            | Display::InlineGrid
+            | Display::InlineTable => {
+                if let Some(child_box) = build_layout_box(child.clone(), false) {
                     layout_box.add_child_inline(child_box)
                 }
             }
-            Display::None => {}
+            // Internal table boxes were handled above.
+            Display::TableRowGroup
+            | Display::TableHeaderGroup
+            | Display::TableFooterGroup
+            | Display::TableRow
+            | Display::TableCell
+            | Display::TableColumnGroup
+            | Display::TableColumn
+            | Display::TableCaption
+            | Display::None => {}
         }
     }
-    Some(layout_box)
+    table::add_anonymous_table(layout_box, &mut misparented);
+}
+
+/// Determines if the box generated by `node` would be absolutely positioned.  See
+/// `LayoutBox::is_absolutely_positioned`.
+fn is_absolutely_positioned(node: &NodeRef) -> bool {
+    match node.data() {
+        NodeData::Element(_) => {
+            node.computed_values().position.is_absolute()
+                && !matches!(
+                    *node.parent().expect("elements have a parent").data(),
+                    NodeData::Document(_)
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Determines if `node` is a replaced element, meaning it has an image to be rendered in place of
+/// its content.  See `LayoutBox::is_replaced`.
+fn is_replaced(node: &NodeRef) -> bool {
+    match node.as_element() {
+        Some(element) => element.image.borrow().is_some(),
+        None => false,
+    }
+}
+
+/// Determines if the box generated by `node` would be floated.  See `LayoutBox::is_floated`.
+fn is_floated(node: &NodeRef) -> bool {
+    match node.data() {
+        NodeData::Element(_) => {
+            node.computed_values().float != Float::None
+                && !is_absolutely_positioned(node)
+                && !matches!(
+                    *node.parent().expect("elements have a parent").data(),
+                    NodeData::Document(_)
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Determines if `node` is a text node containing only white space.
+fn is_white_space_text(node: &NodeRef) -> bool {
+    match node.data() {
+        NodeData::Text(text) => text.borrow().trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Adds the flex or grid items generated by the children of `node` to the flex or grid container
+/// `container`.
+///
+/// Each in-flow child element becomes a blockified item, and each contiguous run of text is
+/// wrapped in an anonymous item.  Runs containing only white space aren't rendered.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-items
+/// https://drafts.csswg.org/css-grid-1/#grid-items
+fn add_container_items(container: &mut LayoutBox, node: &NodeRef) {
+    let mut anonymous_item: Option<LayoutBox> = None;
+    for child in node.children() {
+        match child.data() {
+            NodeData::Text(_) => {
+                if is_white_space_text(&child) {
+                    continue;
+                }
+                if let Some(text_box) = build_layout_box(child.clone(), false) {
+                    anonymous_item
+                        .get_or_insert_with(|| {
+                            LayoutBox::new(
+                                BoxType::Anonymous,
+                                node.clone(),
+                                container.direction,
+                                container.writing_mode,
+                            )
+                        })
+                        .add_child_inline(text_box);
+                }
+            }
+            NodeData::Element(_) => {
+                if let Some(item) = build_layout_box(child.clone(), true) {
+                    if let Some(anonymous) = anonymous_item.take() {
+                        container.add_child(anonymous);
+                    }
+                    container.add_child(item);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(anonymous) = anonymous_item {
+        container.add_child(anonymous);
+    }
 }
 
 /// Given a `window` and what probably should be the root of a `layout_tree`, perform a layout
 /// with the dimensions of the `window`.
+///
+/// The layout tree keeps its layout afterwards, so it can be laid out again after the window is
+/// resized, or after some of its boxes are invalidated with `LayoutBox::invalidate`.  Only the
+/// invalidated subtrees, and the boxes whose containing block changed size, are laid out again.
+/// Returns how many boxes that was.
 pub fn global_layout(
     layout_tree: &mut LayoutBox,
     inner_window_width: f32,
     inner_window_height: f32,
     scale_factor: f32,
-) {
-    layout_tree.layout(
-        PhysicalDimensions {
-            content: Rect {
-                start_x: 0.0,
-                start_y: 0.0,
-                width: CSSPixelLength::new(inner_window_width),
-                height: CSSPixelLength::new(inner_window_height),
-            },
-            padding: Default::default(),
-            border: Default::default(),
-            margin: Default::default(),
+) -> LayoutStats {
+    let viewport = PhysicalDimensions {
+        content: Rect {
+            start_x: Au(0),
+            start_y: Au(0),
+            width: Au::from_f32_px(inner_window_width),
+            height: Au::from_f32_px(inner_window_height),
         },
-        scale_factor,
-    );
+        padding: Default::default(),
+        border: Default::default(),
+        margin: Default::default(),
+    };
+    INITIAL_CONTAINING_BLOCK.with(|icb| icb.set(viewport.content));
+    let started_at = incremental::begin_layout(layout_tree);
+    layout_tree.layout(viewport, scale_factor);
+    // Absolutely positioned boxes can only be laid out once their containing block has been, and
+    // relative offsets are applied after normal flow, so both are done in a second pass.
+    layout_tree.layout_positioned_descendants(viewport, viewport, scale_factor);
+    layout_tree.compute_overflow(scale_factor);
+    incremental::end_layout(layout_tree, started_at)
+}
+
+/// Resolves a size property to a content-box size, returning `None` if it's `auto` or a
+/// percentage of an indefinite size.
+fn resolve_size(
+    size: LengthPercentageOrAuto,
+    percentage_basis: Option<Au>,
+    scale_factor: f32,
+) -> Option<Au> {
+    match size {
+        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(len)) => {
+            Some(Au::from(len * scale_factor))
+        }
+        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(percentage)) => {
+            percentage_basis.map(|basis| percentage.au_relative_to(basis))
+        }
+        LengthPercentageOrAuto::Auto => None,
+    }
+}
+
+/// Resolves a `<length-percentage>`.  Unlike lengths, percentages don't need to be scaled, since
+/// the size they're resolved against already is.
+pub(crate) fn resolve_length_percentage(
+    lp: LengthPercentage,
+    percentage_basis: Au,
+    scale_factor: f32,
+) -> Au {
+    match lp {
+        LengthPercentage::Length(len) => Au::from(len * scale_factor),
+        LengthPercentage::Percentage(percentage) => percentage.au_relative_to(percentage_basis),
+    }
 }
 
 /// https://drafts.csswg.org/css-writing-modes-4/#logical-directions
@@ -130,6 +399,12 @@ impl DumpLayoutFormat for CSSPixelLength {
     }
 }
 
+impl DumpLayoutFormat for Au {
+    fn dump_layout_format(&self) -> String {
+        self.to_f32_px().dump_layout_format()
+    }
+}
+
 impl DumpLayoutFormat for NodeData {
     fn dump_layout_format(&self) -> String {
         let possibly_lowercase = match self {
@@ -0,0 +1,125 @@
+// Useful links:
+//  * https://drafts.csswg.org/css-overflow-3/#overflow-properties
+//  * https://drafts.csswg.org/css-overflow-3/#scrollable
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::rect::{Rect, RoundedRect};
+use crate::style::values::computed::{Overflow, Position};
+use app_units::Au;
+
+impl LayoutBox {
+    /// Gets the computed values of `overflow-x` and `overflow-y` of this box, or `visible` for
+    /// boxes they don't apply to, which are those that aren't block, flex, or grid containers.
+    ///
+    /// TODO: The `overflow` of the root element, or of the `<body>` if the root's is `visible`,
+    /// should be propagated to the viewport.
+    pub fn overflow(&self) -> (Overflow, Overflow) {
+        let applies = !self.anonymous
+            && !self.is_root()
+            && matches!(
+                self.box_type,
+                BoxType::Block
+                    | BoxType::Flex
+                    | BoxType::Grid
+                    | BoxType::InlineBlock
+                    | BoxType::InlineFlex
+                    | BoxType::InlineGrid
+                    | BoxType::TableCell
+                    | BoxType::TableCaption
+            );
+        if !applies {
+            return (Overflow::Visible, Overflow::Visible);
+        }
+        let cvs = self.computed_values();
+        (cvs.overflow_x, cvs.overflow_y)
+    }
+
+    /// Determines if this box is a scroll container, meaning its overflowing content is clipped,
+    /// but can be scrolled to.
+    ///
+    /// https://drafts.csswg.org/css-overflow-3/#scroll-container
+    pub fn is_scroll_container(&self) -> bool {
+        let (overflow_x, overflow_y) = self.overflow();
+        overflow_x.is_scrollable() || overflow_y.is_scrollable()
+    }
+
+    /// The area that can be scrolled to within this box, if it's a scroll container.
+    pub fn scrollable_overflow(&self) -> Option<Rect> {
+        self.scrollable_overflow
+    }
+
+    /// The area the content of this box is clipped to, if it clips its overflow in either axis.
+    /// Its corners follow the inner edge of the border if it clips in both axes, and are square
+    /// otherwise.
+    pub fn overflow_clip(&self) -> Option<RoundedRect> {
+        self.overflow_clip
+    }
+
+    /// Computes the overflow of every box in this subtree once its layout is complete: the
+    /// scrollable overflow rectangle of each scroll container, and the clip of each box that clips
+    /// its overflow, along with the border radii those clips are rounded by.  Returns the area
+    /// this subtree takes up, as far as the scrollable overflow of its ancestors is concerned.
+    ///
+    /// TODO: Absolutely positioned descendants whose containing block is an ancestor of a scroll
+    /// container shouldn't count towards its scrollable overflow.
+    pub(super) fn compute_overflow(&mut self, scale_factor: f32) -> Rect {
+        self.resolve_border_radii(scale_factor);
+        let d = self.physical_dimensions();
+        let (border_box, padding_box) = (d.border_box(), d.padding_box());
+        let mut content_overflow: Option<Rect> = None;
+        for child in &mut self.children {
+            let child_overflow = child.compute_overflow(scale_factor);
+            // Fixed positioned boxes are positioned relative to the viewport, so they don't
+            // overflow any box.  Neither do empty boxes, such as collapsed whitespace.
+            if child.position() == Position::Fixed
+                || child_overflow.width == Au(0)
+                || child_overflow.height == Au(0)
+            {
+                continue;
+            }
+            content_overflow = Some(match content_overflow {
+                Some(overflow) => overflow.union(child_overflow),
+                None => child_overflow,
+            });
+        }
+        let overflow = content_overflow.map_or(border_box, |content| border_box.union(content));
+
+        let (overflow_x, overflow_y) = self.overflow();
+        self.overflow_clip = if overflow_x.is_clipped() && overflow_y.is_clipped() {
+            Some(self.rounded_padding_box())
+        } else if overflow_x.is_clipped() || overflow_y.is_clipped() {
+            let mut clip = overflow;
+            if overflow_x.is_clipped() {
+                clip.start_x = padding_box.start_x;
+                clip.width = padding_box.width;
+            }
+            if overflow_y.is_clipped() {
+                clip.start_y = padding_box.start_y;
+                clip.height = padding_box.height;
+            }
+            Some(RoundedRect::from(clip))
+        } else {
+            None
+        };
+        // Content that overflows the start edges of a scroll container can't be scrolled to.
+        //
+        // TODO: Which edges those are depends on the writing mode and direction of the box, and
+        // the block-end and inline-end padding should be added to the in-flow content.
+        self.scrollable_overflow = if self.is_scroll_container() {
+            let overflow =
+                content_overflow.map_or(padding_box, |content| padding_box.union(content));
+            Some(Rect {
+                start_x: padding_box.start_x,
+                start_y: padding_box.start_y,
+                width: overflow.end_x() - padding_box.start_x,
+                height: overflow.end_y() - padding_box.start_y,
+            })
+        } else {
+            None
+        };
+
+        match self.overflow_clip {
+            Some(clip) => border_box.union(clip.rect),
+            None => overflow,
+        }
+    }
+}
@@ -0,0 +1,418 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/visuren.html#positioning-scheme
+//  * https://www.w3.org/TR/CSS2/visudet.html#abs-non-replaced-width
+//  * https://www.w3.org/TR/CSS2/visudet.html#abs-non-replaced-height
+//  * https://www.w3.org/TR/css-position-3/#sticky-pos
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::float::logical_rect;
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::rect::Rect;
+use crate::layout::resolve_size;
+use crate::style::values::computed::length::LengthPercentageOrAuto;
+use crate::style::values::computed::{Direction, Position, Size, WritingMode};
+use crate::Side;
+use app_units::Au;
+
+/// The used values of the `top`, `right`, `bottom`, and `left` properties of a box, which are
+/// `None` if `auto`.
+#[derive(Clone, Copy, Debug)]
+struct Insets {
+    top: Option<Au>,
+    right: Option<Au>,
+    bottom: Option<Au>,
+    left: Option<Au>,
+}
+
+/// The insets of a box mapped onto the flow-relative sides of its containing block.
+#[derive(Clone, Copy, Debug)]
+struct LogicalInsets {
+    inline_start: Option<Au>,
+    inline_end: Option<Au>,
+    block_start: Option<Au>,
+    block_end: Option<Au>,
+}
+
+impl Insets {
+    /// Maps these insets onto the flow-relative sides of a containing block with the given
+    /// writing mode and direction.
+    fn logical(self, writing_mode: WritingMode, direction: Direction) -> LogicalInsets {
+        let inset =
+            |flow_relative_side| match writing_mode.physical_side(direction, flow_relative_side) {
+                Side::Top => self.top,
+                Side::Right => self.right,
+                Side::Bottom => self.bottom,
+                Side::Left => self.left,
+            };
+        LogicalInsets {
+            inline_start: inset(Side::Left),
+            inline_end: inset(Side::Right),
+            block_start: inset(Side::Top),
+            block_end: inset(Side::Bottom),
+        }
+    }
+}
+
+impl LayoutBox {
+    /// Lays out the absolutely positioned descendants of `self`, and applies the offsets of its
+    /// relatively and stickily positioned descendants.  Expects the normal flow layout of `self`
+    /// to be complete.
+    ///
+    /// `containing_block` is the padding box of the nearest positioned ancestor, which absolutely
+    /// positioned boxes are laid out against.  Fixed positioned boxes are laid out against the
+    /// `viewport` instead.
+    pub(super) fn layout_positioned_descendants(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        viewport: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
+        let parent_content = self.dimensions.physical().content;
+        for child in &mut self.children {
+            match child.position() {
+                Position::Static => {}
+                Position::Relative => child.apply_relative_offset(parent_content, scale_factor),
+                Position::Sticky => {
+                    child.apply_sticky_offset(parent_content, viewport.content, scale_factor)
+                }
+                Position::Absolute => child.layout_absolute(containing_block, scale_factor),
+                Position::Fixed => child.layout_absolute(viewport, scale_factor),
+            }
+            let child_containing_block = if child.is_positioned() {
+                PhysicalDimensions {
+                    content: child.dimensions.padding_box(),
+                    ..Default::default()
+                }
+            } else {
+                containing_block
+            };
+            child.layout_positioned_descendants(child_containing_block, viewport, scale_factor);
+        }
+    }
+
+    /// Sets the static position of each absolutely positioned child of this flex or grid
+    /// container to the start corner of its content box, which is where the child would go if it
+    /// were the sole item of the container.
+    ///
+    /// TODO: The static position should take the alignment properties of the container into
+    /// account.
+    ///
+    /// https://drafts.csswg.org/css-flexbox-1/#abspos-items
+    pub(super) fn set_container_static_positions(&mut self) {
+        let content = self.dimensions.physical().content;
+        for child in &mut self.children {
+            if child.is_absolutely_positioned() {
+                child.static_position = (content.start_x, content.start_y);
+            }
+        }
+    }
+
+    /// Resolves the insets of `self` against `containing_block`.  Percentages of `left` and
+    /// `right` refer to its width, and percentages of `top` and `bottom` to its height.
+    fn insets(&self, containing_block: Rect, scale_factor: f32) -> Insets {
+        let cvs = self.computed_values();
+        let width = Some(containing_block.width);
+        let height = Some(containing_block.height);
+        let resolve = |inset, percentage_basis| resolve_size(inset, percentage_basis, scale_factor);
+        Insets {
+            top: resolve(cvs.top.size, height),
+            right: resolve(cvs.right.size, width),
+            bottom: resolve(cvs.bottom.size, height),
+            left: resolve(cvs.left.size, width),
+        }
+    }
+
+    /// Moves this relatively positioned box from its normal flow position by its insets.  If
+    /// both insets in an axis are given, `left` and `top` win.
+    ///
+    /// TODO: `right` should win over `left` if the containing block is `direction: rtl`.
+    ///
+    /// https://www.w3.org/TR/CSS2/visuren.html#relative-positioning
+    fn apply_relative_offset(&mut self, containing_block: Rect, scale_factor: f32) {
+        let insets = self.insets(containing_block, scale_factor);
+        let dx = match (insets.left, insets.right) {
+            (Some(left), _) => left,
+            (None, Some(right)) => -right,
+            (None, None) => Au(0),
+        };
+        let dy = match (insets.top, insets.bottom) {
+            (Some(top), _) => top,
+            (None, Some(bottom)) => -bottom,
+            (None, None) => Au(0),
+        };
+        self.apply_positioned_offset(dx, dy);
+    }
+
+    /// Moves this stickily positioned box from its normal flow position just enough to keep its
+    /// border box within `scrollport` shrunk by its insets, without moving its margin box out of
+    /// `containing_block`.
+    ///
+    /// TODO: The scrollport should be that of the nearest scroll container, and account for how
+    /// far it has been scrolled.  Since nothing can be scrolled yet, the viewport is used.
+    ///
+    /// https://www.w3.org/TR/css-position-3/#stickypos-insets
+    fn apply_sticky_offset(&mut self, containing_block: Rect, scrollport: Rect, scale_factor: f32) {
+        let insets = self.insets(scrollport, scale_factor);
+        let d = self.physical_dimensions();
+        let (border_box, margin_box) = (d.border_box(), d.margin_box());
+        let dx = sticky_offset(
+            (border_box.start_x, border_box.end_x()),
+            (margin_box.start_x, margin_box.end_x()),
+            (
+                insets.left.map(|left| scrollport.start_x + left),
+                insets.right.map(|right| scrollport.end_x() - right),
+            ),
+            (containing_block.start_x, containing_block.end_x()),
+        );
+        let dy = sticky_offset(
+            (border_box.start_y, border_box.end_y()),
+            (margin_box.start_y, margin_box.end_y()),
+            (
+                insets.top.map(|top| scrollport.start_y + top),
+                insets.bottom.map(|bottom| scrollport.end_y() - bottom),
+            ),
+            (containing_block.start_y, containing_block.end_y()),
+        );
+        self.apply_positioned_offset(dx, dy);
+    }
+
+    /// Lays out this absolutely positioned box within `containing_block`, which is the padding
+    /// box of its containing block.
+    ///
+    /// The insets are mapped onto the flow-relative sides of the containing block, and the size
+    /// and margins of the box are solved in each of its axes.  In the inline axis, inset-start +
+    /// margin-start + inline size + margin-end + inset-end = the inline size of the containing
+    /// block, with `auto` insets taken from the static position where needed.  The same goes for
+    /// the block axis.  An `auto` size is shrink-to-fit in the inline axis of the box itself, and
+    /// given by its content in its block axis, unless both insets in that axis are given.
+    ///
+    /// https://drafts.csswg.org/css-position-3/#abspos-layout
+    fn layout_absolute(&mut self, containing_block: PhysicalDimensions, scale_factor: f32) {
+        self.note_laid_out();
+        let writing_mode = self.containing_block_writing_mode();
+        let direction = self.containing_block_direction();
+        let horizontal = writing_mode.is_horizontal();
+        let content = containing_block.content;
+        let insets = self
+            .insets(content, scale_factor)
+            .logical(writing_mode, direction);
+        let inline_insets = (insets.inline_start, insets.inline_end);
+        let block_insets = (insets.block_start, insets.block_end);
+        let (inline_size_is_auto, block_size_is_auto, auto_inline_margins, auto_block_margins) = {
+            let cvs = self.computed_values();
+            let (width_is_auto, height_is_auto) =
+                (cvs.width.size == Size::Auto, cvs.height.size == Size::Auto);
+            let margin_is_auto = |flow_relative_side| {
+                cvs.margin(writing_mode.physical_side(direction, flow_relative_side))
+                    == LengthPercentageOrAuto::Auto
+            };
+            let (inline_size_is_auto, block_size_is_auto) = if horizontal {
+                (width_is_auto, height_is_auto)
+            } else {
+                (height_is_auto, width_is_auto)
+            };
+            (
+                inline_size_is_auto,
+                block_size_is_auto,
+                (margin_is_auto(Side::Left), margin_is_auto(Side::Right)),
+                (margin_is_auto(Side::Top), margin_is_auto(Side::Bottom)),
+            )
+        };
+
+        // Resolve the margins, borders, and padding of the box, with `auto` margins treated as
+        // zero for now, so that the space left for the content box is known.
+        self.calculate_inline_block_logical_width(containing_block, scale_factor);
+        self.calculate_block_position(containing_block, scale_factor);
+        let d = self.physical_dimensions();
+        let (_, _, margin_box_inline_size, margin_box_block_size) =
+            logical_rect(d.margin_box(), horizontal);
+        let (_, _, content_inline_size, content_block_size) = logical_rect(d.content, horizontal);
+        let (
+            containing_inline_start,
+            containing_block_start,
+            containing_inline_size,
+            containing_block_size,
+        ) = logical_rect(content, horizontal);
+        let available_space =
+            |containing_size: Au, (start, end): (Option<Au>, Option<Au>), edges| {
+                (containing_size - start.unwrap_or_default() - end.unwrap_or_default() - edges)
+                    .max(Au(0))
+            };
+        let available_inline_space = available_space(
+            containing_inline_size,
+            inline_insets,
+            margin_box_inline_size - content_inline_size,
+        );
+        let available_block_space = available_space(
+            containing_block_size,
+            block_insets,
+            margin_box_block_size - content_block_size,
+        );
+
+        // The inline axis of an orthogonal flow root is the block axis of its containing block.
+        // The used size of a replaced box doesn't depend on its insets.
+        // https://www.w3.org/TR/CSS2/visudet.html#abs-replaced-width
+        let inline_axis = (inline_size_is_auto, inline_insets, available_inline_space);
+        let block_axis = (block_size_is_auto, block_insets, available_block_space);
+        let (own_inline_axis, own_block_axis) = if self.is_orthogonal_flow_root() {
+            (block_axis, inline_axis)
+        } else {
+            (inline_axis, block_axis)
+        };
+        let replaced = self.is_replaced();
+        let inline_size = match own_inline_axis {
+            (true, (Some(_), Some(_)), available) if !replaced => Some(available),
+            (true, _, available) if !replaced => {
+                let (min_content, max_content) = self.intrinsic_content_inline_sizes(scale_factor);
+                Some(min_content.max(available).min(max_content))
+            }
+            _ => None,
+        };
+        let block_size = match own_block_axis {
+            (true, (Some(_), Some(_)), available) if !replaced => Some(available),
+            _ => None,
+        };
+        if self.box_type == BoxType::TableWrapper {
+            // Tables are always sized by the table layout algorithm.
+            self.layout_inline_table_wrapper(containing_block, scale_factor);
+        } else {
+            self.layout_container_item(containing_block, inline_size, block_size, scale_factor);
+        }
+
+        // If neither the size nor the insets of an axis are `auto`, any `auto` margins take up
+        // the remaining space.  Otherwise, the equation is over-constrained, and the end inset is
+        // ignored.
+        let mut d = self.physical_dimensions();
+        let (_, _, margin_box_inline_size, margin_box_block_size) =
+            logical_rect(d.margin_box(), horizontal);
+        if let (false, (Some(start), Some(end))) = (inline_size_is_auto, inline_insets) {
+            let remaining = containing_inline_size - start - end - margin_box_inline_size;
+            let (margin_start, margin_end) =
+                distribute_to_auto_margins(remaining, auto_inline_margins);
+            *d.margin
+                .side_mut(writing_mode.physical_side(direction, Side::Left)) += margin_start;
+            *d.margin
+                .side_mut(writing_mode.physical_side(direction, Side::Right)) += margin_end;
+        }
+        if let (false, (Some(start), Some(end))) = (block_size_is_auto, block_insets) {
+            let remaining = containing_block_size - start - end - margin_box_block_size;
+            let (margin_start, margin_end) =
+                distribute_to_auto_margins(remaining, auto_block_margins);
+            *d.margin
+                .side_mut(writing_mode.physical_side(direction, Side::Top)) += margin_start;
+            *d.margin
+                .side_mut(writing_mode.physical_side(direction, Side::Bottom)) += margin_end;
+        }
+        self.dimensions.replace_inner_physical(d);
+
+        // Finally, move the box into place.
+        let margin_box = d.margin_box();
+        let (
+            margin_box_inline_start,
+            margin_box_block_start,
+            margin_box_inline_size,
+            margin_box_block_size,
+        ) = logical_rect(margin_box, horizontal);
+        let (static_x, static_y) = self.static_position;
+        let (static_inline, static_block) = if horizontal {
+            (static_x, static_y)
+        } else {
+            (static_y, static_x)
+        };
+        let starts_at_physical_start = |flow_relative_side| {
+            matches!(
+                writing_mode.physical_side(direction, flow_relative_side),
+                Side::Left | Side::Top
+            )
+        };
+        let target_inline = inset_position(
+            (
+                containing_inline_start,
+                containing_inline_start + containing_inline_size,
+            ),
+            inline_insets,
+            starts_at_physical_start(Side::Left),
+            margin_box_inline_size,
+            static_inline,
+        );
+        let target_block = inset_position(
+            (
+                containing_block_start,
+                containing_block_start + containing_block_size,
+            ),
+            block_insets,
+            starts_at_physical_start(Side::Top),
+            margin_box_block_size,
+            static_block,
+        );
+        let (dx, dy) = (
+            target_inline - margin_box_inline_start,
+            target_block - margin_box_block_start,
+        );
+        if horizontal {
+            self.translate(dx, dy);
+        } else {
+            self.translate(dy, dx);
+        }
+        // The static position was recorded by the parent, and stays put until the parent is laid
+        // out again, so the box must not take it along.
+        self.static_position = (static_x, static_y);
+    }
+}
+
+/// Finds the coordinate of the start of the margin box of an absolutely positioned box along one
+/// axis of its containing block, which spans `range`.  `insets` are the start and end insets of
+/// the box in that axis, of which the start inset wins if both are given, and `forward` is
+/// whether the flow-relative start of the axis is its physical start (its left or top side).
+/// If both insets are `auto`, the box stays at its static position.
+fn inset_position(
+    range: (Au, Au),
+    insets: (Option<Au>, Option<Au>),
+    forward: bool,
+    margin_box_size: Au,
+    static_position: Au,
+) -> Au {
+    match (insets, forward) {
+        ((Some(start), _), true) | ((None, Some(start)), false) => range.0 + start,
+        ((Some(end), _), false) | ((None, Some(end)), true) => range.1 - end - margin_box_size,
+        ((None, None), _) => static_position,
+    }
+}
+
+/// Splits `remaining` space between the start and end margins of an absolutely positioned box
+/// in one axis of its containing block, returning how much to add to each.  `auto_margins` is
+/// whether each of them is `auto`.  If both are, they're made equal, unless that would make
+/// them negative.  If neither is, the end margin absorbs the difference.
+fn distribute_to_auto_margins(remaining: Au, auto_margins: (bool, bool)) -> (Au, Au) {
+    match auto_margins {
+        (true, true) if remaining >= Au(0) => {
+            let start = Au(remaining.0 / 2);
+            (start, remaining - start)
+        }
+        (true, false) => (remaining, Au(0)),
+        _ => (Au(0), remaining),
+    }
+}
+
+/// Finds how far a stickily positioned box needs to move in one axis.  `border_box` and
+/// `margin_box` are the start and end coordinates of the box, `view` those of the sticky view
+/// rectangle, which are `None` if the corresponding inset is `auto`, and `limits` those of the
+/// containing block.  If the box doesn't fit in the sticky view rectangle, its start edge wins.
+fn sticky_offset(
+    border_box: (Au, Au),
+    margin_box: (Au, Au),
+    view: (Option<Au>, Option<Au>),
+    limits: (Au, Au),
+) -> Au {
+    let mut offset = Au(0);
+    if let Some(view_end) = view.1 {
+        offset = offset.min(view_end - border_box.1);
+    }
+    if let Some(view_start) = view.0 {
+        offset = offset.max(view_start - border_box.0);
+    }
+    if offset > Au(0) {
+        offset.min((limits.1 - margin_box.1).max(Au(0)))
+    } else {
+        offset.max((limits.0 - margin_box.0).min(Au(0)))
+    }
+}
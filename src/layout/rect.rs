@@ -1,40 +1,282 @@
-use crate::style::values::computed::length::CSSPixelLength;
-use crate::style::values::CSSFloat;
+use crate::Side;
+use app_units::Au;
 
-#[derive(Clone, Copy, Debug, Default)]
+/// A rectangle of layout geometry.  Geometry is kept in app units rather than floats, so that the
+/// same layout is computed regardless of platform or scale factor, and is only snapped to whole
+/// pixels when it's painted.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Rect {
     /// The exact point Where the rectangle begins on the x-axis.
-    pub start_x: CSSFloat,
+    pub start_x: Au,
     /// The exact point Where the rectangle begins on the y-axis.
-    pub start_y: CSSFloat,
-    pub width: CSSPixelLength,
-    pub height: CSSPixelLength,
+    pub start_y: Au,
+    pub width: Au,
+    pub height: Au,
 }
 
 impl Rect {
     pub fn expanded_by(self, edge: EdgeSizes) -> Rect {
         Rect {
-            start_x: (self.start_x - edge.left).px(),
-            start_y: (self.start_y - edge.top).px(),
+            start_x: self.start_x - edge.left,
+            start_y: self.start_y - edge.top,
             width: self.width + edge.left + edge.right,
             height: self.height + edge.top + edge.bottom,
         }
     }
+
+    /// The point where the rectangle ends on the x-axis.
+    pub fn end_x(&self) -> Au {
+        self.start_x + self.width
+    }
+
+    /// The point where the rectangle ends on the y-axis.
+    pub fn end_y(&self) -> Au {
+        self.start_y + self.height
+    }
+
+    /// The smallest rectangle containing both this rectangle and `other`.
+    pub fn union(&self, other: Rect) -> Rect {
+        let (start_x, start_y) = (
+            self.start_x.min(other.start_x),
+            self.start_y.min(other.start_y),
+        );
+        Rect {
+            start_x,
+            start_y,
+            width: self.end_x().max(other.end_x()) - start_x,
+            height: self.end_y().max(other.end_y()) - start_y,
+        }
+    }
+
+    /// The area covered by both this rectangle and `other`, which is empty if they don't overlap.
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let (start_x, start_y) = (
+            self.start_x.max(other.start_x),
+            self.start_y.max(other.start_y),
+        );
+        Rect {
+            start_x,
+            start_y,
+            width: (self.end_x().min(other.end_x()) - start_x).max(Au(0)),
+            height: (self.end_y().min(other.end_y()) - start_y).max(Au(0)),
+        }
+    }
+
+    /// This rectangle moved by `dx` and `dy`.
+    pub fn translated(&self, dx: Au, dy: Au) -> Rect {
+        Rect {
+            start_x: self.start_x + dx,
+            start_y: self.start_y + dy,
+            ..*self
+        }
+    }
+
+    /// Snaps the edges of this rectangle to the nearest whole pixels, for painting.  Each edge is
+    /// snapped on its own, rather than the size, so that rectangles that share an edge still do
+    /// once snapped, leaving no gaps or overlaps between them.
+    pub fn snapped_to_pixels(&self) -> Rect {
+        let snap = |au: Au| Au::from_px(au.to_nearest_px());
+        let (start_x, start_y) = (snap(self.start_x), snap(self.start_y));
+        Rect {
+            start_x,
+            start_y,
+            width: snap(self.end_x()) - start_x,
+            height: snap(self.end_y()) - start_y,
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct EdgeSizes {
-    pub left: CSSPixelLength,
-    pub right: CSSPixelLength,
-    pub top: CSSPixelLength,
-    pub bottom: CSSPixelLength,
+    pub left: Au,
+    pub right: Au,
+    pub top: Au,
+    pub bottom: Au,
 }
 
 impl EdgeSizes {
+    pub fn side(&self, side: Side) -> Au {
+        match side {
+            Side::Bottom => self.bottom,
+            Side::Left => self.left,
+            Side::Right => self.right,
+            Side::Top => self.top,
+        }
+    }
+
+    pub fn side_mut(&mut self, side: Side) -> &mut Au {
+        match side {
+            Side::Bottom => &mut self.bottom,
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+            Side::Top => &mut self.top,
+        }
+    }
+
     pub fn scale_by(&mut self, scale_factor: f32) {
-        self.left *= scale_factor;
-        self.right *= scale_factor;
-        self.top *= scale_factor;
-        self.bottom *= scale_factor;
+        self.left = self.left.scale_by(scale_factor);
+        self.right = self.right.scale_by(scale_factor);
+        self.top = self.top.scale_by(scale_factor);
+        self.bottom = self.bottom.scale_by(scale_factor);
+    }
+}
+
+/// The horizontal and vertical radii of the quarter ellipse a corner of a box is rounded by.  The
+/// corner is square if either radius is zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadius {
+    pub horizontal: Au,
+    pub vertical: Au,
+}
+
+impl CornerRadius {
+    pub fn is_zero(&self) -> bool {
+        self.horizontal <= Au(0) || self.vertical <= Au(0)
+    }
+}
+
+/// The radii of the four corners of a box.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#corner-shaping
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: CornerRadius,
+    pub top_right: CornerRadius,
+    pub bottom_right: CornerRadius,
+    pub bottom_left: CornerRadius,
+}
+
+impl CornerRadii {
+    /// Determines if every corner is square.
+    pub fn is_zero(&self) -> bool {
+        self.corners().iter().all(CornerRadius::is_zero)
+    }
+
+    /// The radii of the top-left, top-right, bottom-right, and bottom-left corners, in that order.
+    pub fn corners(&self) -> [CornerRadius; 4] {
+        [
+            self.top_left,
+            self.top_right,
+            self.bottom_right,
+            self.bottom_left,
+        ]
+    }
+
+    /// These radii scaled down, all by the same factor, so that the curves of adjacent corners
+    /// don't overlap along any side of `rect`.  Corners with a zero radius are made square in both
+    /// directions.
+    ///
+    /// https://drafts.csswg.org/css-backgrounds-3/#corner-overlap
+    pub fn clamped_to(&self, rect: Rect) -> CornerRadii {
+        let square = |radius: CornerRadius| {
+            if radius.is_zero() {
+                CornerRadius::default()
+            } else {
+                radius
+            }
+        };
+        let radii = CornerRadii {
+            top_left: square(self.top_left),
+            top_right: square(self.top_right),
+            bottom_right: square(self.bottom_right),
+            bottom_left: square(self.bottom_left),
+        };
+        let sides = [
+            (
+                rect.width,
+                radii.top_left.horizontal + radii.top_right.horizontal,
+            ),
+            (
+                rect.width,
+                radii.bottom_left.horizontal + radii.bottom_right.horizontal,
+            ),
+            (
+                rect.height,
+                radii.top_left.vertical + radii.bottom_left.vertical,
+            ),
+            (
+                rect.height,
+                radii.top_right.vertical + radii.bottom_right.vertical,
+            ),
+        ];
+        let factor = sides
+            .iter()
+            .filter(|(_, sum)| *sum > Au(0))
+            .map(|(length, sum)| length.0.max(0) as f64 / sum.0 as f64)
+            .fold(1., f64::min);
+        if factor >= 1. {
+            return radii;
+        }
+        // Rounding down keeps the sums of the scaled radii within the sides.
+        let scale = |au: Au| Au((au.0 as f64 * factor) as i32);
+        let scale_corner = |radius: CornerRadius| CornerRadius {
+            horizontal: scale(radius.horizontal),
+            vertical: scale(radius.vertical),
+        };
+        CornerRadii {
+            top_left: scale_corner(radii.top_left),
+            top_right: scale_corner(radii.top_right),
+            bottom_right: scale_corner(radii.bottom_right),
+            bottom_left: scale_corner(radii.bottom_left),
+        }
+    }
+
+    /// The radii of the inner edge of a border of the given widths, whose outer edge has these
+    /// radii.  Each radius is reduced by the width of the border it's alongside, so that the inner
+    /// corners stay concentric with the outer ones, or become square once the border is as wide as
+    /// the radius.
+    ///
+    /// https://drafts.csswg.org/css-backgrounds-3/#corner-shaping
+    pub fn shrunk_by(&self, edges: EdgeSizes) -> CornerRadii {
+        let shrink = |radius: CornerRadius, horizontal: Au, vertical: Au| CornerRadius {
+            horizontal: (radius.horizontal - horizontal).max(Au(0)),
+            vertical: (radius.vertical - vertical).max(Au(0)),
+        };
+        CornerRadii {
+            top_left: shrink(self.top_left, edges.left, edges.top),
+            top_right: shrink(self.top_right, edges.right, edges.top),
+            bottom_right: shrink(self.bottom_right, edges.right, edges.bottom),
+            bottom_left: shrink(self.bottom_left, edges.left, edges.bottom),
+        }
+    }
+}
+
+/// A rectangle with rounded corners, such as the border box of a box with a `border-radius`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RoundedRect {
+    pub rect: Rect,
+    pub radii: CornerRadii,
+}
+
+impl RoundedRect {
+    /// Creates a rounded rectangle, with `radii` clamped so they fit within `rect`.
+    pub fn new(rect: Rect, radii: CornerRadii) -> RoundedRect {
+        RoundedRect {
+            rect,
+            radii: radii.clamped_to(rect),
+        }
+    }
+
+    /// This rounded rectangle moved by `dx` and `dy`.
+    pub fn translated(&self, dx: Au, dy: Au) -> RoundedRect {
+        RoundedRect {
+            rect: self.rect.translated(dx, dy),
+            radii: self.radii,
+        }
+    }
+
+    /// Snaps the edges of this rounded rectangle to whole pixels, as `Rect::snapped_to_pixels`
+    /// does.  The radii are left as they are, though clamped again in case the rectangle shrank.
+    pub fn snapped_to_pixels(&self) -> RoundedRect {
+        RoundedRect::new(self.rect.snapped_to_pixels(), self.radii)
+    }
+}
+
+impl From<Rect> for RoundedRect {
+    fn from(rect: Rect) -> Self {
+        RoundedRect {
+            rect,
+            radii: CornerRadii::default(),
+        }
     }
 }
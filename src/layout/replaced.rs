@@ -0,0 +1,410 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/visudet.html#inline-replaced-width
+//  * https://www.w3.org/TR/CSS2/visudet.html#inline-replaced-height
+//  * https://www.w3.org/TR/CSS2/visudet.html#block-replaced-width
+//  * https://html.spec.whatwg.org/multipage/rendering.html#attributes-for-embedded-content-and-images
+use crate::dom::image::ElementImage;
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::sizing::SizeConstraints;
+use crate::layout::{BoxComponent, LogicalDirection};
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::percentage::Percentage;
+use crate::style::values::computed::Size;
+use crate::style::values::used::ToAu;
+use crate::style::values::CSSFloat;
+use crate::Side;
+use app_units::Au;
+
+/// The size of a replaced element with no intrinsic size and no specified size in either axis.
+///
+/// https://www.w3.org/TR/CSS2/visudet.html#inline-replaced-width
+const DEFAULT_WIDTH: CSSFloat = 300.;
+const DEFAULT_HEIGHT: CSSFloat = 150.;
+
+impl LayoutBox {
+    /// Determines if this box is a replaced box, whose content is an image rather than children.
+    pub fn is_replaced(&self) -> bool {
+        matches!(self.box_type, BoxType::Replaced | BoxType::InlineReplaced)
+    }
+
+    /// Gets the image this box is replaced with, if it's a replaced box.
+    pub fn replaced_image(&self) -> Option<ElementImage> {
+        self.node
+            .as_element()
+            .and_then(|element| element.image.borrow().clone())
+    }
+
+    /// Gets the `alt` text of the element this box was generated for, with runs of white space
+    /// collapsed, if it has any.
+    pub fn alt_text(&self) -> Option<String> {
+        let element = self.node.as_element()?;
+        let attributes = element.attributes.borrow();
+        let alt = attributes
+            .get("alt")?
+            .split_whitespace()
+            .collect::<Vec<_>>();
+        if alt.is_empty() {
+            None
+        } else {
+            Some(alt.join(" "))
+        }
+    }
+
+    /// Calculates the used physical width and height of this replaced box's content box within
+    /// the given containing block, following the rules for inline replaced elements, which also
+    /// apply to block-level, floated, and absolutely positioned replaced elements.
+    ///
+    /// The `width` and `height` attributes of the element are used when the corresponding property
+    /// is `auto`.
+    ///
+    /// TODO: These attributes should be mapped to the `width` and `height` properties as
+    /// presentational hints with a specificity of zero, which the cascade doesn't support yet.
+    /// This means an `auto` value from an author style sheet doesn't override them.
+    pub(super) fn replaced_used_size(
+        &self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> (Au, Au) {
+        self.replaced_size(Some(containing_block), scale_factor)
+    }
+
+    /// Calculates the used physical width and height of this replaced box's content box, as
+    /// `replaced_used_size` does.  Without a containing block, percentage sizes are treated as
+    /// zero, and percentage `min-*` and `max-*` sizes are ignored.
+    ///
+    /// The size is then clamped by the `min-*` and `max-*` sizes of the box, preserving the
+    /// intrinsic ratio of the box when neither its width nor height is specified.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#min-max-widths
+    fn replaced_size(
+        &self,
+        containing_block: Option<PhysicalDimensions>,
+        scale_factor: f32,
+    ) -> (Au, Au) {
+        let width_constraints = self.size_constraints(true, containing_block, scale_factor);
+        let height_constraints = self.size_constraints(false, containing_block, scale_factor);
+        let containing_block = containing_block.unwrap_or_default();
+        let (width, height) = {
+            let cvs = self.computed_values();
+            (
+                self.specified_replaced_size(cvs.width.size, "width"),
+                self.specified_replaced_size(cvs.height.size, "height"),
+            )
+        };
+        let width = width.map(|lp| {
+            let edges = self.box_sizing_edges(true, containing_block, scale_factor);
+            (lp.to_au(containing_block.content.width)
+                .scale_by(scale_factor)
+                - edges)
+                .max(Au(0))
+        });
+        let height = height.map(|lp| {
+            let edges = self.box_sizing_edges(false, containing_block, scale_factor);
+            (lp.to_au(containing_block.content.height)
+                .scale_by(scale_factor)
+                - edges)
+                .max(Au(0))
+        });
+        let image = self.replaced_image();
+        let intrinsic_size =
+            image
+                .as_ref()
+                .and_then(ElementImage::intrinsic_size)
+                .map(|(width, height)| {
+                    (
+                        Au::from_f32_px(width as CSSFloat * scale_factor),
+                        Au::from_f32_px(height as CSSFloat * scale_factor),
+                    )
+                });
+        // The intrinsic ratio of an image with a zero width or height is undefined.
+        let intrinsic_ratio = intrinsic_size.and_then(|(width, height)| {
+            if width > Au(0) && height > Au(0) {
+                Some(width.to_f32_px() / height.to_f32_px())
+            } else {
+                None
+            }
+        });
+        let default_width = Au::from_f32_px(DEFAULT_WIDTH * scale_factor);
+        let default_height = Au::from_f32_px(DEFAULT_HEIGHT * scale_factor);
+
+        // A size that follows from the other one is found from the clamped size of the other,
+        // and then clamped itself.
+        match (width, height) {
+            (Some(width), Some(height)) => (
+                width_constraints.clamp(width),
+                height_constraints.clamp(height),
+            ),
+            (Some(width), None) => {
+                let width = width_constraints.clamp(width);
+                let height = match (intrinsic_ratio, intrinsic_size) {
+                    (Some(ratio), _) => width.scale_by(1. / ratio),
+                    (None, Some((_, height))) => height,
+                    (None, None) => default_height,
+                };
+                (width, height_constraints.clamp(height))
+            }
+            (None, Some(height)) => {
+                let height = height_constraints.clamp(height);
+                let width = match (intrinsic_ratio, intrinsic_size) {
+                    (Some(ratio), _) => height.scale_by(ratio),
+                    (None, Some((width, _))) => width,
+                    (None, None) => default_width,
+                };
+                (width_constraints.clamp(width), height)
+            }
+            (None, None) => {
+                let (width, height) = match (intrinsic_size, image) {
+                    (Some(intrinsic_size), _) => intrinsic_size,
+                    // TODO: A broken image should be sized to fit its `alt` text, which can't be
+                    // measured until text is laid out, so it's empty for now.
+                    (None, Some(ElementImage::Broken)) => (Au(0), Au(0)),
+                    (None, _) => (default_width, default_height),
+                };
+                if intrinsic_ratio.is_some() {
+                    constrain_with_ratio(width, height, width_constraints, height_constraints)
+                } else {
+                    (
+                        width_constraints.clamp(width),
+                        height_constraints.clamp(height),
+                    )
+                }
+            }
+        }
+    }
+
+    /// Gets the specified value of the `width` or `height` of this replaced box, falling back to
+    /// the attribute of the same name if the property is `auto`.  Returns `None` if neither is
+    /// given.
+    fn specified_replaced_size(&self, property: Size, attribute: &str) -> Option<LengthPercentage> {
+        match property {
+            Size::LengthPercentage(lp) => Some(lp),
+            Size::Auto => self.node.as_element().and_then(|element| {
+                parse_dimension_value(element.attributes.borrow().get(attribute)?)
+            }),
+            // The min-content and max-content sizes of a replaced box are its natural size.
+            Size::IntrinsicSize(_) => None,
+        }
+    }
+
+    /// Gets the used size of this replaced box's content box in its inline axis, which is also
+    /// its min-content and max-content size.
+    ///
+    /// Percentages are treated as zero, since they can't be resolved without a containing block.
+    pub(super) fn replaced_intrinsic_inline_size(&self, scale_factor: f32) -> Au {
+        let (width, height) = self.replaced_size(None, scale_factor);
+        if self.writing_mode.is_horizontal() {
+            width
+        } else {
+            height
+        }
+    }
+
+    /// Assuming `self` is a replaced box, calculate its dimensions.
+    ///
+    /// Inline-level replaced boxes are positioned at the start of their containing block, and are
+    /// expected to be moved into place by the line layout of the inline formatting context they
+    /// participate in.  Block-level replaced boxes are centered in their containing block if both
+    /// of their inline margins are `auto`.
+    pub(super) fn layout_replaced(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
+        let (width, height) = self.replaced_used_size(containing_block, scale_factor);
+        let (inline_size, block_size) = if self.writing_mode.is_horizontal() {
+            (width, height)
+        } else {
+            (height, width)
+        };
+
+        // Resolve the edges of the box, with any `auto` margins treated as zero for now.
+        self.calculate_inline_block_logical_width(containing_block, scale_factor);
+        self.dimensions.set_inline_size(inline_size);
+        if self.box_type == BoxType::Replaced {
+            self.resolve_auto_inline_margins(containing_block);
+        }
+        self.calculate_block_position(containing_block, scale_factor);
+        self.dimensions.set_block_size(block_size);
+    }
+
+    /// Resolves the `auto` inline margins of a block-level replaced box as they would be for a
+    /// block-level non-replaced box with the same used width.
+    ///
+    /// https://www.w3.org/TR/CSS2/visudet.html#block-replaced-width
+    fn resolve_auto_inline_margins(&mut self, containing_block: PhysicalDimensions) {
+        let (auto_start, auto_end) = {
+            let cvs = self.computed_values();
+            (
+                cvs.logical_margin(Side::Left) == LengthPercentageOrAuto::Auto,
+                cvs.logical_margin(Side::Right) == LengthPercentageOrAuto::Auto,
+            )
+        };
+        let containing_inline_size = if self.writing_mode.is_horizontal() {
+            containing_block.content.width
+        } else {
+            containing_block.content.height
+        };
+        let d = &mut self.dimensions;
+        let underflow = (containing_inline_size - d.margin_box_inline_size()).max(Au(0));
+        let (start, end) = match (auto_start, auto_end) {
+            (true, true) => (underflow / 2, underflow - underflow / 2),
+            (true, false) => (underflow, Au(0)),
+            // TODO: Support `direction: rtl` property/value, where an over-constrained box's
+            // inline-start margin is adjusted instead.
+            (false, _) => return,
+        };
+        for (direction, margin) in [
+            (LogicalDirection::InlineStart, start),
+            (LogicalDirection::InlineEnd, end),
+        ]
+        .iter()
+        {
+            let specified = d.get(*direction, BoxComponent::Margin);
+            d.set(*direction, BoxComponent::Margin, specified + *margin);
+        }
+    }
+}
+
+/// Clamps the size of a replaced box with an intrinsic ratio, and neither a specified width nor
+/// height, by its `min-*` and `max-*` sizes, preserving the ratio where possible.  This follows
+/// the table of constraint violations in CSS 2.1, with any maximum smaller than its minimum first
+/// raised to the minimum.
+///
+/// https://www.w3.org/TR/CSS2/visudet.html#min-max-widths
+fn constrain_with_ratio(
+    width: Au,
+    height: Au,
+    width_constraints: SizeConstraints,
+    height_constraints: SizeConstraints,
+) -> (Au, Au) {
+    let zero = Au(0);
+    let min_width = width_constraints.min.unwrap_or(zero);
+    let min_height = height_constraints.min.unwrap_or(zero);
+    let max_width = width_constraints.max.map(|max| max.max(min_width));
+    let max_height = height_constraints.max.map(|max| max.max(min_height));
+    let too_wide = match max_width {
+        Some(max_width) => width > max_width,
+        None => false,
+    };
+    let too_tall = match max_height {
+        Some(max_height) => height > max_height,
+        None => false,
+    };
+    let too_narrow = width < min_width;
+    let too_short = height < min_height;
+    let upper = |size: Au, max: Option<Au>| match max {
+        Some(max) => size.min(max),
+        None => size,
+    };
+    // A box only has an intrinsic ratio if its intrinsic width and height are both non-zero, so
+    // neither is divided by zero.
+    match (too_wide, too_tall, too_narrow, too_short) {
+        (true, true, _, _) => {
+            let max_width = max_width.unwrap_or(width);
+            let max_height = max_height.unwrap_or(height);
+            if max_width.to_f32_px() / width.to_f32_px()
+                <= max_height.to_f32_px() / height.to_f32_px()
+            {
+                (
+                    max_width,
+                    max_width
+                        .scale_by(height.to_f32_px() / width.to_f32_px())
+                        .max(min_height),
+                )
+            } else {
+                (
+                    max_height
+                        .scale_by(width.to_f32_px() / height.to_f32_px())
+                        .max(min_width),
+                    max_height,
+                )
+            }
+        }
+        (_, _, true, true) => {
+            if min_width.to_f32_px() / width.to_f32_px()
+                <= min_height.to_f32_px() / height.to_f32_px()
+            {
+                (
+                    upper(
+                        min_height.scale_by(width.to_f32_px() / height.to_f32_px()),
+                        max_width,
+                    ),
+                    min_height,
+                )
+            } else {
+                (
+                    min_width,
+                    upper(
+                        min_width.scale_by(height.to_f32_px() / width.to_f32_px()),
+                        max_height,
+                    ),
+                )
+            }
+        }
+        (_, true, true, _) => (min_width, max_height.unwrap_or(height)),
+        (true, _, _, true) => (max_width.unwrap_or(width), min_height),
+        (true, _, _, _) => {
+            let max_width = max_width.unwrap_or(width);
+            (
+                max_width,
+                max_width
+                    .scale_by(height.to_f32_px() / width.to_f32_px())
+                    .max(min_height),
+            )
+        }
+        (_, _, true, _) => (
+            min_width,
+            upper(
+                min_width.scale_by(height.to_f32_px() / width.to_f32_px()),
+                max_height,
+            ),
+        ),
+        (_, true, _, _) => {
+            let max_height = max_height.unwrap_or(height);
+            (
+                max_height
+                    .scale_by(width.to_f32_px() / height.to_f32_px())
+                    .max(min_width),
+                max_height,
+            )
+        }
+        (_, _, _, true) => (
+            upper(
+                min_height.scale_by(width.to_f32_px() / height.to_f32_px()),
+                max_width,
+            ),
+            min_height,
+        ),
+        (false, false, false, false) => (width, height),
+    }
+}
+
+/// Parses the value of a `width` or `height` attribute, which is a non-negative number of CSS
+/// pixels or a percentage.  Anything after the number, other than a percent sign, is ignored.
+///
+/// https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-dimension-values
+fn parse_dimension_value(value: &str) -> Option<LengthPercentage> {
+    let value = value.trim_start();
+    let integer_len = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    if integer_len == 0 {
+        return None;
+    }
+    let mut number_len = integer_len;
+    if value[integer_len..].starts_with('.') {
+        let fraction_len = value[integer_len + 1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len() - integer_len - 1);
+        if fraction_len > 0 {
+            number_len += 1 + fraction_len;
+        }
+    }
+    let number = value[..number_len].parse::<CSSFloat>().ok()?;
+    if value[number_len..].starts_with('%') {
+        Some(LengthPercentage::Percentage(Percentage(number / 100.)))
+    } else {
+        Some(LengthPercentage::new_len(number))
+    }
+}
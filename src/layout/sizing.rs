@@ -0,0 +1,303 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS2/visudet.html#min-max-widths
+//  * https://www.w3.org/TR/CSS2/visudet.html#min-max-heights
+//  * https://drafts.csswg.org/css-sizing-3/#box-sizing
+//  * https://drafts.csswg.org/css-sizing-3/#min-size-properties
+//  * https://drafts.csswg.org/css-sizing-3/#sizing-values
+use crate::layout::dimensions::PhysicalDimensions;
+use crate::layout::incremental::{is_valid_for_viewport, track_viewport_dependency, ViewportSize};
+use crate::layout::layout_box::LayoutBox;
+use crate::layout::{resolve_length_percentage, resolve_size};
+use crate::style::values::computed::length::LengthPercentageOrAuto;
+use crate::style::values::computed::{BoxSizing, IntrinsicSize, MaxSize, Size};
+use crate::Side;
+use app_units::Au;
+use std::cell::Cell;
+
+/// The min-content and max-content inline sizes of a box, measured at a given scale factor.
+#[derive(Clone, Copy, Debug)]
+struct MeasuredSizes {
+    scale_factor: f32,
+    viewport: ViewportSize,
+    min_content: Au,
+    max_content: Au,
+}
+
+/// Caches the intrinsic inline sizes of a box the first time they're measured.  Measuring a box
+/// measures its whole subtree, and every box that sizes itself to fit its content measures its
+/// children, so without the cache, nested shrink-to-fit boxes would measure the same descendants
+/// over and over.
+///
+/// Intrinsic sizes don't depend on the containing block, so they stay valid until the box or one
+/// of its descendants is invalidated, but they're scaled, so they're measured again at a different
+/// scale factor.  Orthogonal flows are measured against the initial containing block, so theirs
+/// are measured again when the viewport is resized, too.
+#[derive(Clone, Debug, Default)]
+pub(super) struct IntrinsicSizesCache {
+    content_sizes: Cell<Option<MeasuredSizes>>,
+    contributions: Cell<Option<MeasuredSizes>>,
+}
+
+impl IntrinsicSizesCache {
+    /// Gets the cached min-content and max-content sizes of the content of the box, or measures
+    /// them with `measure`.
+    pub(super) fn content_sizes(
+        &self,
+        scale_factor: f32,
+        measure: impl FnOnce() -> (Au, Au),
+    ) -> (Au, Au) {
+        get_or_measure(&self.content_sizes, scale_factor, measure)
+    }
+
+    /// Gets the cached min-content and max-content contributions of the box, or measures them
+    /// with `measure`.
+    pub(super) fn contributions(
+        &self,
+        scale_factor: f32,
+        measure: impl FnOnce() -> (Au, Au),
+    ) -> (Au, Au) {
+        get_or_measure(&self.contributions, scale_factor, measure)
+    }
+
+    /// Forgets the measured sizes, so that they're measured again the next time they're needed.
+    pub(super) fn clear(&self) {
+        self.content_sizes.set(None);
+        self.contributions.set(None);
+    }
+}
+
+fn get_or_measure(
+    cache: &Cell<Option<MeasuredSizes>>,
+    scale_factor: f32,
+    measure: impl FnOnce() -> (Au, Au),
+) -> (Au, Au) {
+    match cache.get() {
+        Some(sizes)
+            if sizes.scale_factor == scale_factor && is_valid_for_viewport(sizes.viewport) =>
+        {
+            (sizes.min_content, sizes.max_content)
+        }
+        _ => {
+            let ((min_content, max_content), viewport) = track_viewport_dependency(measure);
+            cache.set(Some(MeasuredSizes {
+                scale_factor,
+                viewport,
+                min_content,
+                max_content,
+            }));
+            (min_content, max_content)
+        }
+    }
+}
+
+/// The bounds given by the `min-*` and `max-*` properties of a box in one of its physical axes,
+/// as content-box sizes.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct SizeConstraints {
+    /// The minimum size, or `None` if it's `auto`.  An `auto` minimum is zero, except for flex
+    /// items, whose automatic minimum size depends on their content.
+    pub(super) min: Option<Au>,
+    /// The maximum size, or `None` if it's `none`.
+    pub(super) max: Option<Au>,
+}
+
+impl SizeConstraints {
+    /// Constraints that leave any size as it is.
+    pub(super) fn none() -> SizeConstraints {
+        SizeConstraints {
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Clamps `size` between the minimum and maximum.  If the minimum is greater than the maximum,
+    /// the minimum wins.
+    pub(super) fn clamp(&self, size: Au) -> Au {
+        let size = match self.max {
+            Some(max) => size.min(max),
+            None => size,
+        };
+        size.max(self.min.unwrap_or(Au(0)))
+    }
+}
+
+impl LayoutBox {
+    /// Gets the padding and borders of this box along the given physical axis that its sizing
+    /// properties include, which is none of them unless it has `box-sizing: border-box`.
+    /// Anonymous boxes have no style of their own, so their sizing properties include nothing.
+    pub(super) fn box_sizing_edges(
+        &self,
+        horizontal: bool,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> Au {
+        let cvs = self.computed_values();
+        if self.anonymous || cvs.box_sizing == BoxSizing::ContentBox {
+            return Au(0);
+        }
+        // Percentage padding is resolved against the inline size of the containing block on
+        // every side.
+        let padding_basis = self.containing_block_inline_size(containing_block);
+        let sides = if horizontal {
+            [Side::Left, Side::Right]
+        } else {
+            [Side::Top, Side::Bottom]
+        };
+        sides.iter().fold(Au(0), |acc, &side| {
+            acc + Au::from(cvs.border_width(side) * scale_factor)
+                + resolve_length_percentage(cvs.padding(side), padding_basis, scale_factor)
+        })
+    }
+
+    /// Resolves the `width` (if `horizontal`) or `height` of this box against the given
+    /// containing block, as a content-box size.  Returns `None` if it's `auto`, or if this box is
+    /// anonymous.
+    pub(super) fn specified_size(
+        &self,
+        horizontal: bool,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) -> Option<Au> {
+        if self.anonymous {
+            return None;
+        }
+        let (size, percentage_basis) = {
+            let cvs = self.computed_values();
+            if horizontal {
+                (cvs.width.size, containing_block.content.width)
+            } else {
+                (cvs.height.size, containing_block.content.height)
+            }
+        };
+        let box_sizing_edges = self.box_sizing_edges(horizontal, containing_block, scale_factor);
+        self.resolve_size_property(
+            size,
+            horizontal,
+            Some(percentage_basis),
+            box_sizing_edges,
+            scale_factor,
+        )
+    }
+
+    /// Resolves the value of a sizing property of this box along the given physical axis to a
+    /// content-box size, or `None` if it's `auto`.  Percentages are resolved against
+    /// `percentage_basis`, and behave as `auto` if it's indefinite.  `box_sizing_edges` are the
+    /// padding and borders that lengths and percentages include.
+    ///
+    /// Intrinsic sizing keywords are only resolved in the inline axis of non-replaced boxes.  In
+    /// the block axis, the min-content and max-content sizes of a box are the size of its
+    /// content, which is what `auto` already gives it, and a replaced box is given its natural
+    /// size by `auto` too.
+    pub(super) fn resolve_size_property(
+        &self,
+        size: Size,
+        horizontal: bool,
+        percentage_basis: Option<Au>,
+        box_sizing_edges: Au,
+        scale_factor: f32,
+    ) -> Option<Au> {
+        let to_content_size = |size: Au| (size - box_sizing_edges).max(Au(0));
+        match size {
+            Size::LengthPercentage(lp) => resolve_size(
+                LengthPercentageOrAuto::LengthPercentage(lp),
+                percentage_basis,
+                scale_factor,
+            )
+            .map(to_content_size),
+            Size::Auto => None,
+            Size::IntrinsicSize(_)
+                if horizontal != self.writing_mode.is_horizontal() || self.is_replaced() =>
+            {
+                None
+            }
+            Size::IntrinsicSize(intrinsic) => {
+                let (min_content, max_content) = self.intrinsic_content_inline_sizes(scale_factor);
+                match intrinsic {
+                    IntrinsicSize::MinContent => Some(min_content),
+                    IntrinsicSize::MaxContent => Some(max_content),
+                    IntrinsicSize::FitContent(lp) => resolve_size(
+                        LengthPercentageOrAuto::LengthPercentage(lp),
+                        percentage_basis,
+                        scale_factor,
+                    )
+                    .map(|size| to_content_size(size).max(min_content).min(max_content)),
+                }
+            }
+        }
+    }
+
+    /// Resolves the `min-width` and `max-width` (if `horizontal`) or `min-height` and
+    /// `max-height` of this box against the given containing block.
+    ///
+    /// Without a containing block, as when measuring intrinsic sizes, percentages can't be
+    /// resolved, so they behave as the initial value of their property instead.
+    pub(super) fn size_constraints(
+        &self,
+        horizontal: bool,
+        containing_block: Option<PhysicalDimensions>,
+        scale_factor: f32,
+    ) -> SizeConstraints {
+        if self.anonymous {
+            return SizeConstraints::none();
+        }
+        let (min, max) = {
+            let cvs = self.computed_values();
+            if horizontal {
+                (cvs.min_width.size, cvs.max_width.size)
+            } else {
+                (cvs.min_height.size, cvs.max_height.size)
+            }
+        };
+        let percentage_basis = containing_block.map(|containing_block| {
+            if horizontal {
+                containing_block.content.width
+            } else {
+                containing_block.content.height
+            }
+        });
+        let edges = self.box_sizing_edges(
+            horizontal,
+            containing_block.unwrap_or_default(),
+            scale_factor,
+        );
+        let resolve = |size| {
+            self.resolve_size_property(size, horizontal, percentage_basis, edges, scale_factor)
+        };
+        SizeConstraints {
+            min: resolve(min),
+            max: match max {
+                MaxSize::LengthPercentage(lp) => resolve(Size::LengthPercentage(lp)),
+                MaxSize::None => None,
+                MaxSize::IntrinsicSize(intrinsic) => resolve(Size::IntrinsicSize(intrinsic)),
+            },
+        }
+    }
+
+    /// Gets the size constraints of this box in its inline axis.  See
+    /// `LayoutBox::size_constraints`.
+    pub(super) fn inline_size_constraints(
+        &self,
+        containing_block: Option<PhysicalDimensions>,
+        scale_factor: f32,
+    ) -> SizeConstraints {
+        self.size_constraints(
+            self.writing_mode.is_horizontal(),
+            containing_block,
+            scale_factor,
+        )
+    }
+
+    /// Gets the size constraints of this box in its block axis.  See
+    /// `LayoutBox::size_constraints`.
+    pub(super) fn block_size_constraints(
+        &self,
+        containing_block: Option<PhysicalDimensions>,
+        scale_factor: f32,
+    ) -> SizeConstraints {
+        self.size_constraints(
+            !self.writing_mode.is_horizontal(),
+            containing_block,
+            scale_factor,
+        )
+    }
+}
@@ -0,0 +1,1381 @@
+// Useful links:
+//  * https://www.w3.org/TR/CSS22/tables.html
+//  * https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+//  * https://www.w3.org/TR/CSS22/tables.html#width-layout
+//  * https://html.spec.whatwg.org/multipage/tables.html#forming-a-table
+use crate::dom::tree::NodeRef;
+use crate::layout::dimensions::{LogicalDimensions, PhysicalDimensions};
+use crate::layout::grid::{distribute_equally, sum};
+use crate::layout::layout_box::{BoxType, LayoutBox};
+use crate::layout::{build_layout_box, is_white_space_text, BoxComponent, LogicalDirection};
+use crate::layout::{resolve_length_percentage, resolve_size};
+use crate::style::values::computed::length::LengthPercentage;
+use crate::style::values::computed::{
+    BorderCollapse, CaptionSide, ComputedValues, Display, Size, TableLayout,
+};
+use crate::Side;
+use app_units::Au;
+use std::ops::Range;
+
+/// The largest `colspan` and `rowspan` attribute values allowed by HTML.
+///
+/// https://html.spec.whatwg.org/multipage/tables.html#attributes-common-to-td-and-th-elements
+const MAX_COLSPAN: usize = 1000;
+const MAX_ROWSPAN: usize = 65534;
+
+/// Adds the boxes generated by `children` to the table wrapper box `wrapper`.  Captions become
+/// children of the wrapper, and everything else is placed in a table box, which is always the
+/// first child of the wrapper.
+///
+/// Children that aren't proper table children are wrapped in anonymous rows, and white space
+/// between table boxes isn't rendered.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+pub(super) fn add_table_children<I: IntoIterator<Item = NodeRef>>(
+    wrapper: &mut LayoutBox,
+    children: I,
+) {
+    let mut table = if wrapper.anonymous {
+        LayoutBox::new_anonymous(
+            BoxType::Table,
+            wrapper.node.clone(),
+            wrapper.direction,
+            wrapper.writing_mode,
+        )
+    } else {
+        LayoutBox::new(
+            BoxType::Table,
+            wrapper.node.clone(),
+            wrapper.direction,
+            wrapper.writing_mode,
+        )
+    };
+    let mut anonymous_row = Vec::new();
+    for child in children {
+        let display = child.computed_values().display;
+        match display {
+            Display::TableCaption
+            | Display::TableRowGroup
+            | Display::TableHeaderGroup
+            | Display::TableFooterGroup
+            | Display::TableRow
+            | Display::TableColumnGroup
+            | Display::TableColumn => {
+                add_anonymous_row(&mut table, &mut anonymous_row);
+                if let Some(child_box) = build_layout_box(child.clone(), false) {
+                    if child_box.box_type == BoxType::TableCaption {
+                        wrapper.add_child(child_box);
+                    } else {
+                        table.add_child(child_box);
+                    }
+                }
+            }
+            _ => {
+                if anonymous_row.is_empty() && is_white_space_text(&child) {
+                    continue;
+                }
+                anonymous_row.push(child.clone());
+            }
+        }
+    }
+    add_anonymous_row(&mut table, &mut anonymous_row);
+    wrapper.children.insert(0, table);
+}
+
+/// Adds the boxes generated by `children` to the row group box `row_group`, wrapping anything
+/// that isn't a row in an anonymous row.
+pub(super) fn add_row_group_children<I: IntoIterator<Item = NodeRef>>(
+    row_group: &mut LayoutBox,
+    children: I,
+) {
+    let mut anonymous_row = Vec::new();
+    for child in children {
+        if child.computed_values().display == Display::TableRow {
+            add_anonymous_row(row_group, &mut anonymous_row);
+            if let Some(row) = build_layout_box(child.clone(), false) {
+                row_group.add_child(row);
+            }
+        } else if !anonymous_row.is_empty() || !is_white_space_text(&child) {
+            anonymous_row.push(child.clone());
+        }
+    }
+    add_anonymous_row(row_group, &mut anonymous_row);
+}
+
+/// Adds the boxes generated by `children` to the row box `row`, wrapping anything that isn't a
+/// cell in an anonymous cell.
+pub(super) fn add_row_children<I: IntoIterator<Item = NodeRef>>(row: &mut LayoutBox, children: I) {
+    let mut anonymous_cell = Vec::new();
+    for child in children {
+        if child.computed_values().display == Display::TableCell {
+            add_anonymous_cell(row, &mut anonymous_cell);
+            if let Some(cell) = build_layout_box(child.clone(), false) {
+                row.add_child(cell);
+            }
+        } else if !anonymous_cell.is_empty() || !is_white_space_text(&child) {
+            anonymous_cell.push(child.clone());
+        }
+    }
+    add_anonymous_cell(row, &mut anonymous_cell);
+}
+
+/// Adds the columns generated by `children` to the column group box `column_group`.  Any other
+/// children of a column group aren't rendered.
+pub(super) fn add_column_group_children<I: IntoIterator<Item = NodeRef>>(
+    column_group: &mut LayoutBox,
+    children: I,
+) {
+    for child in children {
+        if child.computed_values().display == Display::TableColumn {
+            if let Some(column) = build_layout_box(child.clone(), false) {
+                column_group.add_child(column);
+            }
+        }
+    }
+}
+
+/// Wraps the boxes generated by `misparented`, a run of consecutive siblings starting with an
+/// internal table box, in an anonymous table and adds it to `parent`.  The anonymous table is
+/// inline-level if `parent` is an inline box.  Drains `misparented`.
+pub(super) fn add_anonymous_table(parent: &mut LayoutBox, misparented: &mut Vec<NodeRef>) {
+    if misparented.is_empty() {
+        return;
+    }
+    let inline = matches!(parent.box_type, BoxType::Inline | BoxType::AnonymousInline);
+    let mut wrapper = LayoutBox::new_anonymous(
+        if inline {
+            BoxType::InlineTableWrapper
+        } else {
+            BoxType::TableWrapper
+        },
+        parent.node.clone(),
+        parent.direction,
+        parent.writing_mode,
+    );
+    add_table_children(&mut wrapper, misparented.drain(..));
+    if inline {
+        parent.add_child_inline(wrapper);
+    } else {
+        parent.add_child(wrapper);
+    }
+}
+
+/// Wraps the boxes generated by `children` in an anonymous row, and adds it to `parent`.  Drains
+/// `children`.
+fn add_anonymous_row(parent: &mut LayoutBox, children: &mut Vec<NodeRef>) {
+    if children.is_empty() {
+        return;
+    }
+    let mut row = LayoutBox::new_anonymous(
+        BoxType::TableRow,
+        parent.node.clone(),
+        parent.direction,
+        parent.writing_mode,
+    );
+    add_row_children(&mut row, children.drain(..));
+    parent.add_child(row);
+}
+
+/// Wraps the boxes generated by `children` in an anonymous cell, and adds it to `row`.  Drains
+/// `children`.
+fn add_anonymous_cell(row: &mut LayoutBox, children: &mut Vec<NodeRef>) {
+    if children.is_empty() {
+        return;
+    }
+    let mut cell = LayoutBox::new_anonymous(
+        BoxType::TableCell,
+        row.node.clone(),
+        row.direction,
+        row.writing_mode,
+    );
+    super::add_block_container_children(&mut cell, children.drain(..));
+    row.add_child(cell);
+}
+
+/// Gets the style of a table box or internal table box.  Anonymous table boxes inherit the
+/// inherited properties of the box they were generated in, and take the initial value of every
+/// other property.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#anonymous-boxes
+fn table_box_style(layout_box: &LayoutBox) -> ComputedValues {
+    let cvs = layout_box.computed_values();
+    if !layout_box.anonymous {
+        return cvs.clone();
+    }
+    ComputedValues {
+        border_collapse: cvs.border_collapse,
+        border_spacing: cvs.border_spacing,
+        caption_side: cvs.caption_side,
+        color: cvs.color,
+        direction: cvs.direction,
+        font_size: cvs.font_size,
+        writing_mode: cvs.writing_mode,
+        ..ComputedValues::default()
+    }
+}
+
+/// Reads a non-negative integer attribute, such as `colspan`, from the element that generated
+/// `layout_box`.
+///
+/// https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#rules-for-parsing-non-negative-integers
+fn integer_attribute(layout_box: &LayoutBox, name: &str) -> Option<usize> {
+    if layout_box.anonymous {
+        return None;
+    }
+    let element = layout_box.node.as_element()?;
+    let attributes = element.attributes.borrow();
+    let value = attributes.get(name)?.trim_start();
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(value, |end| &value[..end]);
+    digits.parse().ok()
+}
+
+/// The sizes of the four edges of a box in one of its box components, in logical terms.
+#[derive(Clone, Copy, Debug, Default)]
+struct Edges {
+    inline_start: Au,
+    inline_end: Au,
+    block_start: Au,
+    block_end: Au,
+}
+
+impl Edges {
+    fn border(cvs: &ComputedValues, scale_factor: f32) -> Edges {
+        Edges {
+            inline_start: Au::from(cvs.logical_border_width(Side::Left) * scale_factor),
+            inline_end: Au::from(cvs.logical_border_width(Side::Right) * scale_factor),
+            block_start: Au::from(cvs.logical_border_width(Side::Top) * scale_factor),
+            block_end: Au::from(cvs.logical_border_width(Side::Bottom) * scale_factor),
+        }
+    }
+
+    /// Percentage padding is resolved against `percentage_basis`, the inline size of the
+    /// containing block.
+    fn padding(cvs: &ComputedValues, percentage_basis: Au, scale_factor: f32) -> Edges {
+        let resolve = |side| {
+            resolve_length_percentage(cvs.logical_padding(side), percentage_basis, scale_factor)
+        };
+        Edges {
+            inline_start: resolve(Side::Left),
+            inline_end: resolve(Side::Right),
+            block_start: resolve(Side::Top),
+            block_end: resolve(Side::Bottom),
+        }
+    }
+
+    fn inline_sum(&self) -> Au {
+        self.inline_start + self.inline_end
+    }
+
+    fn block_sum(&self) -> Au {
+        self.block_start + self.block_end
+    }
+
+    fn apply(&self, dimensions: &mut LogicalDimensions, box_component: BoxComponent) {
+        dimensions.set(
+            LogicalDirection::InlineStart,
+            box_component,
+            self.inline_start,
+        );
+        dimensions.set(LogicalDirection::InlineEnd, box_component, self.inline_end);
+        dimensions.set(
+            LogicalDirection::BlockStart,
+            box_component,
+            self.block_start,
+        );
+        dimensions.set(LogicalDirection::BlockEnd, box_component, self.block_end);
+    }
+}
+
+/// The location of a row or column box within its table box: the index of the child of the table
+/// box, and if that child is a group, the index of the row or column within the group.
+#[derive(Clone, Copy, Debug)]
+struct BoxPath {
+    child: usize,
+    grandchild: Option<usize>,
+}
+
+/// A cell that's been assigned the slots of the table grid it covers.
+#[derive(Clone, Debug)]
+struct PlacedCell {
+    /// The index of the cell's row in the layout order of rows.
+    row: usize,
+    /// The index of the cell's box amongst the children of its row box.
+    index: usize,
+    rows: Range<usize>,
+    columns: Range<usize>,
+}
+
+/// The rows and columns of a table, and the slots of the grid its cells cover.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#table-layout
+#[derive(Clone, Debug)]
+struct TableGrid {
+    /// The row boxes of the table, in layout order, which places the first header group at the
+    /// start and the first footer group at the end.
+    rows: Vec<BoxPath>,
+    /// Each row group, with the index of its box amongst the table's children, and the range of
+    /// rows it contains.  Consecutive rows that aren't in a row group form a group without a box.
+    row_groups: Vec<(Option<usize>, Range<usize>)>,
+    cells: Vec<PlacedCell>,
+    /// Each column and column group box, with the range of columns it spans.
+    columns: Vec<(BoxPath, Range<usize>)>,
+    column_count: usize,
+}
+
+impl TableGrid {
+    /// Forms the grid of the table box `table`, assigning each cell to slots in the order given by
+    /// the HTML table model.  Row spans don't extend past the end of their row group.
+    ///
+    /// https://html.spec.whatwg.org/multipage/tables.html#forming-a-table
+    fn new(table: &LayoutBox) -> TableGrid {
+        let group_display = |child: &LayoutBox| {
+            if child.box_type == BoxType::TableRowGroup {
+                Some(child.computed_values().display)
+            } else {
+                None
+            }
+        };
+        let header = table
+            .children
+            .iter()
+            .position(|child| group_display(child) == Some(Display::TableHeaderGroup));
+        let footer = table
+            .children
+            .iter()
+            .position(|child| group_display(child) == Some(Display::TableFooterGroup));
+        let group_rows = |idx: usize| {
+            table.children[idx]
+                .children
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row.box_type == BoxType::TableRow)
+                .map(move |(row_idx, _)| BoxPath {
+                    child: idx,
+                    grandchild: Some(row_idx),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut groups: Vec<(Option<usize>, Vec<BoxPath>)> = Vec::new();
+        if let Some(header) = header {
+            groups.push((Some(header), group_rows(header)));
+        }
+        let mut in_row_run = false;
+        for (idx, child) in table.children.iter().enumerate() {
+            match child.box_type {
+                BoxType::TableRow => {
+                    let path = BoxPath {
+                        child: idx,
+                        grandchild: None,
+                    };
+                    match groups.last_mut() {
+                        Some((None, rows)) if in_row_run => rows.push(path),
+                        _ => groups.push((None, vec![path])),
+                    }
+                    in_row_run = true;
+                    continue;
+                }
+                BoxType::TableRowGroup if Some(idx) != header && Some(idx) != footer => {
+                    groups.push((Some(idx), group_rows(idx)));
+                }
+                _ => {}
+            }
+            in_row_run = false;
+        }
+        if let Some(footer) = footer {
+            groups.push((Some(footer), group_rows(footer)));
+        }
+
+        let mut rows = Vec::new();
+        let mut row_groups = Vec::new();
+        for (group_box, group) in groups {
+            let start = rows.len();
+            rows.extend(group);
+            row_groups.push((group_box, start..rows.len()));
+        }
+
+        // Assign each cell to the first slots in its row that aren't covered by a cell from a
+        // previous row.
+        let mut occupied = vec![Vec::<bool>::new(); rows.len()];
+        let mut cells = Vec::new();
+        for (_, group) in &row_groups {
+            for row_idx in group.clone() {
+                let mut column = 0;
+                for (index, cell) in table.row(rows[row_idx]).children.iter().enumerate() {
+                    if cell.box_type != BoxType::TableCell {
+                        continue;
+                    }
+                    while occupied[row_idx].get(column).copied().unwrap_or(false) {
+                        column += 1;
+                    }
+                    let colspan = integer_attribute(cell, "colspan")
+                        .unwrap_or(1)
+                        .clamp(1, MAX_COLSPAN);
+                    // A `rowspan` of zero extends the cell to the end of its row group.
+                    let rows_left = group.end - row_idx;
+                    let rowspan = match integer_attribute(cell, "rowspan").unwrap_or(1) {
+                        0 => rows_left,
+                        rowspan => rowspan.min(MAX_ROWSPAN).min(rows_left),
+                    };
+                    let columns = column..column + colspan;
+                    for occupied_row in &mut occupied[row_idx..row_idx + rowspan] {
+                        if occupied_row.len() < columns.end {
+                            occupied_row.resize(columns.end, false);
+                        }
+                        for slot in &mut occupied_row[columns.clone()] {
+                            *slot = true;
+                        }
+                    }
+                    cells.push(PlacedCell {
+                        row: row_idx,
+                        index,
+                        rows: row_idx..row_idx + rowspan,
+                        columns,
+                    });
+                    column += colspan;
+                }
+            }
+        }
+
+        // Columns are spanned by `table-column` boxes, and by `table-column-group` boxes without
+        // any columns.
+        let span = |column: &LayoutBox| {
+            integer_attribute(column, "span")
+                .unwrap_or(1)
+                .clamp(1, MAX_COLSPAN)
+        };
+        let mut columns = Vec::new();
+        let mut column_count = 0;
+        for (idx, child) in table.children.iter().enumerate() {
+            let start = column_count;
+            match child.box_type {
+                BoxType::TableColumn => column_count += span(child),
+                BoxType::TableColumnGroup if child.children.is_empty() => {
+                    column_count += span(child)
+                }
+                BoxType::TableColumnGroup => {
+                    for (column_idx, column) in child.children.iter().enumerate() {
+                        let column_start = column_count;
+                        column_count += span(column);
+                        columns.push((
+                            BoxPath {
+                                child: idx,
+                                grandchild: Some(column_idx),
+                            },
+                            column_start..column_count,
+                        ));
+                    }
+                }
+                _ => continue,
+            }
+            columns.push((
+                BoxPath {
+                    child: idx,
+                    grandchild: None,
+                },
+                start..column_count,
+            ));
+        }
+        let column_count = occupied.iter().map(Vec::len).fold(column_count, usize::max);
+
+        TableGrid {
+            rows,
+            row_groups,
+            cells,
+            columns,
+            column_count,
+        }
+    }
+}
+
+/// Everything about a table box needed to size its columns, which doesn't depend on the inline
+/// size of the table.
+#[derive(Clone, Debug)]
+struct TableMetrics {
+    style: ComputedValues,
+    grid: TableGrid,
+    border: Edges,
+    padding: Edges,
+    cell_borders: Vec<Edges>,
+    cell_paddings: Vec<Edges>,
+    /// The horizontal and vertical `border-spacing`, mapped to the inline and block axes.  Zero
+    /// in the collapsing border model.
+    inline_spacing: Au,
+    block_spacing: Au,
+    /// The used value of `width`, or `None` if it's `auto`.
+    inline_size: Option<Au>,
+    /// The min-content and max-content widths of each column, as given by the automatic table
+    /// layout algorithm.
+    column_min: Vec<Au>,
+    column_max: Vec<Au>,
+}
+
+impl TableMetrics {
+    /// The total size of the spacing in the inline axis, which surrounds every column.
+    fn inline_spacing_sum(&self) -> Au {
+        spacing_sum(self.inline_spacing, self.grid.column_count)
+    }
+
+    /// The total inline size of the border and padding of the table box.
+    fn inline_edges(&self) -> Au {
+        self.border.inline_sum() + self.padding.inline_sum()
+    }
+
+    /// The min-content and max-content inline sizes of the table grid, including spacing.
+    fn grid_inline_sizes(&self) -> (Au, Au) {
+        let spacing = self.inline_spacing_sum();
+        (
+            sum(&self.column_min) + spacing,
+            sum(&self.column_max) + spacing,
+        )
+    }
+}
+
+/// The total size of the spacing around and between `count` tracks.
+fn spacing_sum(spacing: Au, count: usize) -> Au {
+    if count == 0 {
+        Au(0)
+    } else {
+        spacing * (count + 1) as i32
+    }
+}
+
+/// The size of the given tracks, including the spacing between them.
+fn span_size(sizes: &[Au], span: &Range<usize>, spacing: Au) -> Au {
+    sum(&sizes[span.clone()]) + spacing * span.len().saturating_sub(1) as i32
+}
+
+/// The offset of each track from the start of the table's content box.
+fn track_offsets(sizes: &[Au], spacing: Au) -> Vec<Au> {
+    let mut offset = spacing;
+    sizes
+        .iter()
+        .map(|&size| {
+            let start = offset;
+            offset += size + spacing;
+            start
+        })
+        .collect()
+}
+
+/// Distributes `available`, the inline size of the table's content box minus its spacing, to
+/// columns with the given min-content and max-content widths.  Columns are never narrower than
+/// their min-content width.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+fn distribute_column_widths(column_min: &[Au], column_max: &[Au], available: Au) -> Vec<Au> {
+    let min_sum = sum(column_min);
+    let max_sum = sum(column_max);
+    if available >= max_sum {
+        // Grow each column in proportion to its max-content width, or equally if every column
+        // is empty.
+        let extra = available - max_sum;
+        column_max
+            .iter()
+            .map(|&max| {
+                if max_sum > Au(0) {
+                    max + extra.scale_by(max.to_f32_px() / max_sum.to_f32_px())
+                } else {
+                    max + extra / column_max.len() as i32
+                }
+            })
+            .collect()
+    } else if available > min_sum {
+        // Interpolate between the min-content and max-content widths.
+        let ratio = ((available - min_sum).to_f32_px() / (max_sum - min_sum).to_f32_px()).min(1.);
+        column_min
+            .iter()
+            .zip(column_max)
+            .map(|(&min, &max)| min + (max - min).scale_by(ratio))
+            .collect()
+    } else {
+        column_min.to_vec()
+    }
+}
+
+/// Gets the `width` of a table box or internal table box, if it's a length.
+/// TODO: Percentage widths of cells and columns aren't supported.
+fn fixed_inline_size(style: &ComputedValues, scale_factor: f32) -> Option<Au> {
+    match style.logical_width() {
+        Size::LengthPercentage(LengthPercentage::Length(len)) => Some(Au::from(len * scale_factor)),
+        _ => None,
+    }
+}
+
+/// Gets the `height` of a table box or internal table box, if it's a length.
+fn fixed_block_size(style: &ComputedValues, scale_factor: f32) -> Option<Au> {
+    match style.logical_height() {
+        Size::LengthPercentage(LengthPercentage::Length(len)) => Some(Au::from(len * scale_factor)),
+        _ => None,
+    }
+}
+
+/// Gets the physical offsets corresponding to the given logical offsets.
+fn physical_offsets(horizontal: bool, inline_offset: Au, block_offset: Au) -> (Au, Au) {
+    if horizontal {
+        (inline_offset, block_offset)
+    } else {
+        (block_offset, inline_offset)
+    }
+}
+
+impl LayoutBox {
+    /// Assuming `self` is a block-level table wrapper box, calculate the dimensions of this box,
+    /// its table box, and its captions.
+    pub(super) fn layout_table_wrapper(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
+        self.layout_table_wrapper_box(containing_block, false, scale_factor);
+    }
+
+    /// Assuming `self` is an inline-level table wrapper box, calculate the dimensions of this box,
+    /// its table box, and its captions.
+    ///
+    /// Like inline-blocks, the box is positioned at the start of its containing block, and is
+    /// expected to be moved into place by line layout.
+    pub(super) fn layout_inline_table_wrapper(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        scale_factor: f32,
+    ) {
+        self.layout_table_wrapper_box(containing_block, true, scale_factor);
+    }
+
+    /// Lays out the table wrapper box `self`.  The wrapper is as wide as its table box, unless a
+    /// caption needs more room, and its margins are resolved like those of a block box whose
+    /// width isn't `auto`.  Captions are laid out as blocks above or below the table box, as
+    /// given by `caption-side`.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#model
+    /// https://www.w3.org/TR/CSS22/tables.html#caption-position
+    fn layout_table_wrapper_box(
+        &mut self,
+        containing_block: PhysicalDimensions,
+        inline_level: bool,
+        scale_factor: f32,
+    ) {
+        let zero = Au(0);
+        let horizontal = self.writing_mode.is_horizontal();
+        let style = table_box_style(self);
+        let content = containing_block.content;
+        let (containing_inline_size, containing_inline_start, containing_block_size) = if horizontal
+        {
+            (content.width, content.start_x, content.height)
+        } else {
+            (content.height, content.start_y, content.width)
+        };
+        let margin = |side| {
+            resolve_size(
+                style.logical_margin(side),
+                Some(containing_inline_size),
+                scale_factor,
+            )
+        };
+        let (margin_inline_start, margin_inline_end) = (margin(Side::Left), margin(Side::Right));
+        let available_inline_size = (containing_inline_size
+            - margin_inline_start.unwrap_or(zero)
+            - margin_inline_end.unwrap_or(zero))
+        .max(Au(0));
+
+        // Lay the table box out at the origin of the wrapper's containing block, so it can be
+        // moved into place once the captions above it have been laid out.
+        let table_idx = self
+            .children
+            .iter()
+            .position(|child| child.box_type == BoxType::Table)
+            .expect("table wrapper boxes always contain a table box");
+        let table = &mut self.children[table_idx];
+        let metrics = table.table_metrics(containing_inline_size, scale_factor);
+        let table_inline_size = table.used_table_inline_size(&metrics, available_inline_size);
+        table.layout_table_box(&metrics, containing_block, table_inline_size, scale_factor);
+        let table_inline_size = table.dimensions.margin_box_inline_size();
+        let table_block_size = table.dimensions.margin_box_block_size();
+        let table_origin = table.physical_dimensions().margin_box();
+
+        let caption_min_content = self
+            .children
+            .iter()
+            .filter(|child| child.box_type == BoxType::TableCaption)
+            .map(|caption| caption.intrinsic_inline_contributions(scale_factor).0)
+            .fold(zero, Au::max);
+        let inline_size = table_inline_size.max(caption_min_content);
+
+        // Resolve any `auto` margins, which center the wrapper if they're both `auto`.  Inline
+        // table wrappers treat `auto` margins as zero.
+        let free_space = containing_inline_size
+            - inline_size
+            - margin_inline_start.unwrap_or(zero)
+            - margin_inline_end.unwrap_or(zero);
+        let (margin_inline_start, margin_inline_end) =
+            match (inline_level, margin_inline_start, margin_inline_end) {
+                (false, None, None) => {
+                    let margin = free_space.max(Au(0)) / 2;
+                    (margin, margin)
+                }
+                (false, None, Some(end)) => (free_space.max(Au(0)), end),
+                (false, Some(start), None) => (start, free_space.max(Au(0))),
+                (_, start, end) => (start.unwrap_or(zero), end.unwrap_or(zero)),
+            };
+        let margin_block_start = margin(Side::Top).unwrap_or(zero);
+        let margin_block_end = margin(Side::Bottom).unwrap_or(zero);
+
+        let d = &mut self.dimensions;
+        d.set_inline_size(inline_size);
+        d.set(
+            LogicalDirection::InlineStart,
+            BoxComponent::Margin,
+            margin_inline_start,
+        );
+        d.set(
+            LogicalDirection::InlineEnd,
+            BoxComponent::Margin,
+            margin_inline_end,
+        );
+        d.set(
+            LogicalDirection::BlockStart,
+            BoxComponent::Margin,
+            margin_block_start,
+        );
+        d.set(
+            LogicalDirection::BlockEnd,
+            BoxComponent::Margin,
+            margin_block_end,
+        );
+        d.set_inline_start_coord(containing_inline_start + margin_inline_start);
+        // Like other block-level boxes, the wrapper starts after the content of its containing
+        // block laid out so far.  See `LayoutBox::calculate_block_position`.
+        d.set_block_start_coord(containing_block_size + content.start_y + margin_block_start);
+        d.set_block_size(zero);
+
+        let caption_side = style.caption_side;
+        self.layout_table_captions(CaptionSide::Top, caption_side, scale_factor);
+        let wrapper_content = self.dimensions.physical().content;
+        let (inline_offset, block_offset) = if horizontal {
+            (
+                wrapper_content.start_x - table_origin.start_x,
+                wrapper_content.start_y + wrapper_content.height - table_origin.start_y,
+            )
+        } else {
+            (
+                wrapper_content.start_y - table_origin.start_y,
+                wrapper_content.start_x + wrapper_content.width - table_origin.start_x,
+            )
+        };
+        let (dx, dy) = if horizontal {
+            (inline_offset, block_offset)
+        } else {
+            (block_offset, inline_offset)
+        };
+        self.children[table_idx].translate(dx, dy);
+        self.dimensions
+            .set_block_size(self.dimensions.get_content_block_size() + table_block_size);
+        self.layout_table_captions(CaptionSide::Bottom, caption_side, scale_factor);
+    }
+
+    /// Lays out the captions of the table wrapper box `self` on the given side of the table, if
+    /// it's the side given by `caption_side`.  Captions are stacked after the content laid out so
+    /// far, like block-level boxes.
+    fn layout_table_captions(
+        &mut self,
+        side: CaptionSide,
+        caption_side: CaptionSide,
+        scale_factor: f32,
+    ) {
+        if side != caption_side {
+            return;
+        }
+        for idx in 0..self.children.len() {
+            if self.children[idx].box_type != BoxType::TableCaption {
+                continue;
+            }
+            let physical_dimensions = self.dimensions.physical();
+            let caption = &mut self.children[idx];
+            caption.layout(physical_dimensions, scale_factor);
+            let caption_block_size = caption.dimensions.margin_box_block_size();
+            self.dimensions
+                .set_block_size(self.dimensions.get_content_block_size() + caption_block_size);
+        }
+    }
+
+    /// Calculates the min-content and max-content inline sizes of the content of a table wrapper
+    /// box, which is the border box of its table box, or its widest caption.
+    pub(super) fn table_wrapper_intrinsic_content_inline_sizes(
+        &self,
+        scale_factor: f32,
+    ) -> (Au, Au) {
+        let zero = Au(0);
+        let mut sizes = (zero, zero);
+        for child in &self.children {
+            match child.box_type {
+                BoxType::Table => {
+                    let metrics = child.table_metrics(zero, scale_factor);
+                    let (min_content, max_content) = metrics.grid_inline_sizes();
+                    let edges = metrics.inline_edges();
+                    let (min_content, max_content) =
+                        match fixed_inline_size(&metrics.style, scale_factor) {
+                            Some(inline_size) => {
+                                let inline_size = inline_size.max(min_content + edges);
+                                (inline_size, inline_size)
+                            }
+                            None => (min_content + edges, max_content + edges),
+                        };
+                    sizes.0 = sizes.0.max(min_content);
+                    sizes.1 = sizes.1.max(max_content);
+                }
+                BoxType::TableCaption => {
+                    let (caption_min_content, _) =
+                        child.intrinsic_inline_contributions(scale_factor);
+                    sizes.0 = sizes.0.max(caption_min_content);
+                    sizes.1 = sizes.1.max(caption_min_content);
+                }
+                _ => {}
+            }
+        }
+        sizes
+    }
+
+    /// Calculates the min-content and max-content contributions of a table wrapper box, which
+    /// has margins but no borders or padding of its own.
+    pub(super) fn table_wrapper_intrinsic_inline_contributions(
+        &self,
+        scale_factor: f32,
+    ) -> (Au, Au) {
+        let zero = Au(0);
+        let style = table_box_style(self);
+        let margins = resolve_size(style.logical_margin(Side::Left), Some(zero), scale_factor)
+            .unwrap_or(zero)
+            + resolve_size(style.logical_margin(Side::Right), Some(zero), scale_factor)
+                .unwrap_or(zero);
+        let (min_content, max_content) =
+            self.table_wrapper_intrinsic_content_inline_sizes(scale_factor);
+        (min_content + margins, max_content + margins)
+    }
+
+    /// Calculates the min-content and max-content inline sizes of the content box of a table box.
+    pub(super) fn table_intrinsic_content_inline_sizes(&self, scale_factor: f32) -> (Au, Au) {
+        self.table_metrics(Au(0), scale_factor).grid_inline_sizes()
+    }
+
+    /// Gets the row or column box of the table box `self` at the given path.
+    fn row(&self, path: BoxPath) -> &LayoutBox {
+        let child = &self.children[path.child];
+        match path.grandchild {
+            Some(idx) => &child.children[idx],
+            None => child,
+        }
+    }
+
+    /// Gets the row or column box of the table box `self` at the given path.
+    fn table_descendant_mut(&mut self, path: BoxPath) -> &mut LayoutBox {
+        let child = &mut self.children[path.child];
+        match path.grandchild {
+            Some(idx) => &mut child.children[idx],
+            None => child,
+        }
+    }
+
+    /// Forms the grid of the table box `self`, and calculates the borders and padding of it and
+    /// its cells, and the min-content and max-content widths of its columns.  Percentage padding
+    /// on the table box resolves against `containing_inline_size`.
+    fn table_metrics(&self, containing_inline_size: Au, scale_factor: f32) -> TableMetrics {
+        let zero = Au(0);
+        let style = table_box_style(self);
+        let grid = TableGrid::new(self);
+        let cell_styles = grid
+            .cells
+            .iter()
+            .map(|cell| table_box_style(&self.row(grid.rows[cell.row]).children[cell.index]))
+            .collect::<Vec<_>>();
+        // TODO: Percentage padding of cells should resolve against the width of the table.
+        let cell_paddings = cell_styles
+            .iter()
+            .map(|cell_style| Edges::padding(cell_style, zero, scale_factor))
+            .collect::<Vec<_>>();
+
+        let (border, padding, cell_borders, inline_spacing, block_spacing) =
+            match style.border_collapse {
+                BorderCollapse::Separate => {
+                    let spacing = style.border_spacing;
+                    let (inline_spacing, block_spacing) = if style.writing_mode.is_horizontal() {
+                        (spacing.horizontal, spacing.vertical)
+                    } else {
+                        (spacing.vertical, spacing.horizontal)
+                    };
+                    (
+                        Edges::border(&style, scale_factor),
+                        Edges::padding(&style, containing_inline_size, scale_factor),
+                        cell_styles
+                            .iter()
+                            .map(|cell_style| Edges::border(cell_style, scale_factor))
+                            .collect(),
+                        Au::from(inline_spacing * scale_factor),
+                        Au::from(block_spacing * scale_factor),
+                    )
+                }
+                BorderCollapse::Collapse => {
+                    let (border, cell_borders) = collapse_borders(
+                        &grid,
+                        Edges::border(&style, scale_factor),
+                        cell_styles
+                            .iter()
+                            .map(|cell_style| Edges::border(cell_style, scale_factor))
+                            .collect(),
+                    );
+                    // Tables in the collapsing border model have no padding or spacing.
+                    (border, Edges::default(), cell_borders, zero, zero)
+                }
+            };
+
+        // Find the min-content and max-content widths of each cell, and use them to find those
+        // of the columns.  Columns with a `width` are never narrower than it.
+        let mut column_min = vec![zero; grid.column_count];
+        for (path, columns) in &grid.columns {
+            let column = self.row(*path);
+            if column.box_type != BoxType::TableColumn {
+                continue;
+            }
+            if let Some(inline_size) = fixed_inline_size(&table_box_style(column), scale_factor) {
+                for column_min in &mut column_min[columns.clone()] {
+                    *column_min = (*column_min).max(inline_size);
+                }
+            }
+        }
+        let mut column_max = column_min.clone();
+        let mut cell_order = (0..grid.cells.len()).collect::<Vec<_>>();
+        cell_order.sort_by_key(|&idx| grid.cells[idx].columns.len());
+        for idx in cell_order {
+            let cell = &grid.cells[idx];
+            let cell_box = &self.row(grid.rows[cell.row]).children[cell.index];
+            let edges = cell_borders[idx].inline_sum() + cell_paddings[idx].inline_sum();
+            let (min_content, max_content) = cell_box.intrinsic_content_inline_sizes(scale_factor);
+            let (min_content, max_content) =
+                match fixed_inline_size(&cell_styles[idx], scale_factor) {
+                    // A cell's `width` takes the place of its max-content width, but can't make it
+                    // narrower than its min-content width.
+                    Some(inline_size) => {
+                        let inline_size = inline_size.max(min_content);
+                        (inline_size + edges, inline_size + edges)
+                    }
+                    None => (min_content + edges, max_content + edges),
+                };
+            // Cells that span multiple columns widen each of them equally if they're too narrow.
+            let spanned = cell.columns.clone().collect::<Vec<_>>();
+            let spanned_min = span_size(&column_min, &cell.columns, inline_spacing);
+            distribute_equally(&mut column_min, &spanned, min_content - spanned_min);
+            let spanned_max = span_size(&column_max, &cell.columns, inline_spacing);
+            distribute_equally(&mut column_max, &spanned, max_content - spanned_max);
+        }
+        for (max, &min) in column_max.iter_mut().zip(&column_min) {
+            *max = (*max).max(min);
+        }
+
+        // Tables are always at least as wide as their content, so the intrinsic sizing keywords
+        // behave as `auto`.
+        let inline_size = match style.logical_width() {
+            Size::LengthPercentage(lp) => Some(resolve_length_percentage(
+                lp,
+                containing_inline_size,
+                scale_factor,
+            )),
+            Size::Auto | Size::IntrinsicSize(_) => None,
+        };
+        TableMetrics {
+            style,
+            grid,
+            border,
+            padding,
+            cell_borders,
+            cell_paddings,
+            inline_spacing,
+            block_spacing,
+            inline_size,
+            column_min,
+            column_max,
+        }
+    }
+
+    /// Calculates the used inline size of the border box of the table box `self`.  A table with
+    /// a `width` is never narrower than its min-content width, and tables with an `auto` width
+    /// are sized to fit `available_inline_size`, like floats.
+    ///
+    /// As in browsers, the `width` of a table box gives the size of its border box.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#auto-table-layout
+    fn used_table_inline_size(&self, metrics: &TableMetrics, available_inline_size: Au) -> Au {
+        let (min_content, max_content) = metrics.grid_inline_sizes();
+        let edges = metrics.inline_edges();
+        match metrics.inline_size {
+            // The fixed table layout algorithm widens the table if its columns don't fit.
+            Some(inline_size) if metrics.style.table_layout == TableLayout::Fixed => inline_size,
+            Some(inline_size) => inline_size.max(min_content + edges),
+            None => (min_content + edges).max(available_inline_size.min(max_content + edges)),
+        }
+    }
+
+    /// Calculates the widths of the columns of the table box `self` with the fixed table layout
+    /// algorithm, which only looks at the widths of columns and the cells in the first row.  The
+    /// columns share any space left over.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#fixed-table-layout
+    fn fixed_column_widths(
+        &self,
+        metrics: &TableMetrics,
+        available: Au,
+        scale_factor: f32,
+    ) -> Vec<Au> {
+        let grid = &metrics.grid;
+        let mut widths = vec![None; grid.column_count];
+        for (path, columns) in &grid.columns {
+            let column = self.row(*path);
+            if column.box_type != BoxType::TableColumn {
+                continue;
+            }
+            if let Some(inline_size) = fixed_inline_size(&table_box_style(column), scale_factor) {
+                for width in &mut widths[columns.clone()] {
+                    *width = Some(inline_size);
+                }
+            }
+        }
+        for (idx, cell) in grid.cells.iter().enumerate() {
+            if cell.row != 0 {
+                continue;
+            }
+            let cell_box = &self.row(grid.rows[cell.row]).children[cell.index];
+            if let Some(inline_size) = fixed_inline_size(&table_box_style(cell_box), scale_factor) {
+                let edges = metrics.cell_borders[idx].inline_sum()
+                    + metrics.cell_paddings[idx].inline_sum();
+                let spacing = metrics.inline_spacing * (cell.columns.len() - 1) as i32;
+                let share = (inline_size + edges - spacing).max(Au(0)) / cell.columns.len() as i32;
+                for width in &mut widths[cell.columns.clone()] {
+                    width.get_or_insert(share);
+                }
+            }
+        }
+
+        let zero = Au(0);
+        let remaining = available
+            - widths
+                .iter()
+                .fold(zero, |acc, width| acc + width.unwrap_or(zero));
+        let unsized_columns = widths
+            .iter()
+            .enumerate()
+            .filter(|(_, width)| width.is_none())
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let mut widths = widths
+            .into_iter()
+            .map(|width| width.unwrap_or(zero))
+            .collect::<Vec<_>>();
+        if unsized_columns.is_empty() {
+            let all = (0..widths.len()).collect::<Vec<_>>();
+            distribute_equally(&mut widths, &all, remaining);
+        } else {
+            distribute_equally(&mut widths, &unsized_columns, remaining);
+        }
+        widths
+    }
+
+    /// Lays out the table box `self` and its rows, columns, and cells, with the border box of the
+    /// table at the start of `containing_block` and `inline_size` wide.
+    ///
+    /// TODO: Cells are always aligned to the top of their rows, since `vertical-align` isn't
+    /// supported.
+    ///
+    /// https://www.w3.org/TR/CSS22/tables.html#height-layout
+    fn layout_table_box(
+        &mut self,
+        metrics: &TableMetrics,
+        containing_block: PhysicalDimensions,
+        inline_size: Au,
+        scale_factor: f32,
+    ) {
+        let zero = Au(0);
+        let horizontal = self.writing_mode.is_horizontal();
+        let grid = &metrics.grid;
+        let inline_spacing = metrics.inline_spacing;
+        let block_spacing = metrics.block_spacing;
+
+        // Size the columns.
+        let mut content_inline_size = (inline_size - metrics.inline_edges()).max(Au(0));
+        let available = (content_inline_size - metrics.inline_spacing_sum()).max(Au(0));
+        let columns =
+            if metrics.style.table_layout == TableLayout::Fixed && metrics.inline_size.is_some() {
+                self.fixed_column_widths(metrics, available, scale_factor)
+            } else {
+                distribute_column_widths(&metrics.column_min, &metrics.column_max, available)
+            };
+        content_inline_size = content_inline_size.max(sum(&columns) + metrics.inline_spacing_sum());
+
+        let d = &mut self.dimensions;
+        metrics.border.apply(d, BoxComponent::Border);
+        metrics.padding.apply(d, BoxComponent::Padding);
+        Edges::default().apply(d, BoxComponent::Margin);
+        d.set_inline_size(content_inline_size);
+        let (inline_start, block_start) = if horizontal {
+            (
+                containing_block.content.start_x,
+                containing_block.content.start_y,
+            )
+        } else {
+            (
+                containing_block.content.start_y,
+                containing_block.content.start_x,
+            )
+        };
+        d.set_inline_start_coord(
+            inline_start + metrics.border.inline_start + metrics.padding.inline_start,
+        );
+        d.set_block_start_coord(
+            block_start + metrics.border.block_start + metrics.padding.block_start,
+        );
+        let origin = self.dimensions.physical();
+        let column_offsets = track_offsets(&columns, inline_spacing);
+
+        // Lay each cell out in the columns it spans, to find the block size it needs.
+        let mut cell_block_sizes = Vec::with_capacity(grid.cells.len());
+        for (idx, cell) in grid.cells.iter().enumerate() {
+            let inline_offset = column_offsets[cell.columns.start];
+            let inline_size = span_size(&columns, &cell.columns, inline_spacing);
+            let border = metrics.cell_borders[idx];
+            let padding = metrics.cell_paddings[idx];
+            let cell_box = &mut self.table_descendant_mut(grid.rows[cell.row]).children[cell.index];
+            cell_box.layout_table_cell(
+                origin,
+                inline_offset,
+                inline_size,
+                border,
+                padding,
+                scale_factor,
+            );
+            // A cell's `height` acts as a minimum height.
+            let mut content_block_size = cell_box.dimensions.get_content_block_size();
+            if let Some(block_size) = fixed_block_size(&table_box_style(cell_box), scale_factor) {
+                content_block_size = content_block_size.max(block_size);
+            }
+            cell_block_sizes.push(content_block_size + border.block_sum() + padding.block_sum());
+        }
+
+        // Size the rows, which are as tall as their `height` and the cells in them.  Cells that
+        // span multiple rows make each of them equally taller if they're too short.
+        let mut rows = grid
+            .rows
+            .iter()
+            .map(|&path| {
+                fixed_block_size(&table_box_style(self.row(path)), scale_factor).unwrap_or(zero)
+            })
+            .collect::<Vec<_>>();
+        let mut cell_order = (0..grid.cells.len()).collect::<Vec<_>>();
+        cell_order.sort_by_key(|&idx| grid.cells[idx].rows.len());
+        for idx in cell_order {
+            let spanned_rows = &grid.cells[idx].rows;
+            let spanned = spanned_rows.clone().collect::<Vec<_>>();
+            let extra = cell_block_sizes[idx] - span_size(&rows, spanned_rows, block_spacing);
+            distribute_equally(&mut rows, &spanned, extra);
+        }
+        let block_spacing_sum = spacing_sum(block_spacing, rows.len());
+        let grid_block_size = sum(&rows) + block_spacing_sum;
+        // Like its `width`, the `height` of a table gives the size of its border box.  Any extra
+        // height is shared by the rows.
+        let block_edges = metrics.border.block_sum() + metrics.padding.block_sum();
+        let content_block_size = match fixed_block_size(&metrics.style, scale_factor) {
+            Some(block_size) => {
+                let content_block_size = block_size - block_edges;
+                let all = (0..rows.len()).collect::<Vec<_>>();
+                distribute_equally(&mut rows, &all, content_block_size - grid_block_size);
+                content_block_size.max(grid_block_size)
+            }
+            None => grid_block_size,
+        };
+        let row_offsets = track_offsets(&rows, block_spacing);
+
+        // Stretch each cell to the height of the rows it spans, and move it into place.
+        for (idx, cell) in grid.cells.iter().enumerate() {
+            let block_size = span_size(&rows, &cell.rows, block_spacing)
+                - metrics.cell_borders[idx].block_sum()
+                - metrics.cell_paddings[idx].block_sum();
+            let (dx, dy) = physical_offsets(horizontal, zero, row_offsets[cell.rows.start]);
+            let cell_box = &mut self.table_descendant_mut(grid.rows[cell.row]).children[cell.index];
+            cell_box.dimensions.set_block_size(block_size.max(Au(0)));
+            cell_box.translate(dx, dy);
+        }
+
+        // Rows, row groups, and columns cover the cells in them.
+        let grid_inline_start = column_offsets.first().copied().unwrap_or(zero);
+        let grid_inline_size = span_size(&columns, &(0..columns.len()), inline_spacing);
+        let grid_block_start = row_offsets.first().copied().unwrap_or(zero);
+        let rows_block_size = span_size(&rows, &(0..rows.len()), block_spacing);
+        let set_area = |layout_box: &mut LayoutBox,
+                        inline_offset: Au,
+                        inline_size: Au,
+                        block_offset: Au,
+                        block_size: Au| {
+            let (inline_start, block_start) = if horizontal {
+                (origin.content.start_x, origin.content.start_y)
+            } else {
+                (origin.content.start_y, origin.content.start_x)
+            };
+            let d = &mut layout_box.dimensions;
+            d.set_inline_start_coord(inline_start + inline_offset);
+            d.set_block_start_coord(block_start + block_offset);
+            d.set_inline_size(inline_size);
+            d.set_block_size(block_size);
+        };
+        for (row_idx, &path) in grid.rows.iter().enumerate() {
+            set_area(
+                self.table_descendant_mut(path),
+                grid_inline_start,
+                grid_inline_size,
+                row_offsets[row_idx],
+                rows[row_idx],
+            );
+        }
+        for (group, group_rows) in &grid.row_groups {
+            if let Some(group) = group {
+                let (block_offset, block_size) = if group_rows.is_empty() {
+                    (grid_block_start, zero)
+                } else {
+                    (
+                        row_offsets[group_rows.start],
+                        span_size(&rows, group_rows, block_spacing),
+                    )
+                };
+                set_area(
+                    &mut self.children[*group],
+                    grid_inline_start,
+                    grid_inline_size,
+                    block_offset,
+                    block_size,
+                );
+            }
+        }
+        for (path, column_range) in &grid.columns {
+            let (inline_offset, inline_size) = if column_range.is_empty() {
+                (grid_inline_start, zero)
+            } else {
+                (
+                    column_offsets[column_range.start],
+                    span_size(&columns, column_range, inline_spacing),
+                )
+            };
+            set_area(
+                self.table_descendant_mut(*path),
+                inline_offset,
+                inline_size,
+                grid_block_start,
+                rows_block_size,
+            );
+        }
+
+        self.dimensions.set_block_size(content_block_size);
+    }
+
+    /// Lays out the table cell `self` in a column `inline_size` wide, starting `inline_offset` from
+    /// the start of the content box of `table`.  The cell starts at the block-start edge of the
+    /// table's content box, and is expected to be moved into its row afterwards.
+    fn layout_table_cell(
+        &mut self,
+        table: PhysicalDimensions,
+        inline_offset: Au,
+        inline_size: Au,
+        border: Edges,
+        padding: Edges,
+        scale_factor: f32,
+    ) {
+        let (inline_start, block_start) = if self.writing_mode.is_horizontal() {
+            (table.content.start_x, table.content.start_y)
+        } else {
+            (table.content.start_y, table.content.start_x)
+        };
+        let d = &mut self.dimensions;
+        border.apply(d, BoxComponent::Border);
+        padding.apply(d, BoxComponent::Padding);
+        Edges::default().apply(d, BoxComponent::Margin);
+        d.set_inline_size((inline_size - border.inline_sum() - padding.inline_sum()).max(Au(0)));
+        d.set_inline_start_coord(
+            inline_start + inline_offset + border.inline_start + padding.inline_start,
+        );
+        d.set_block_start_coord(block_start + border.block_start + padding.block_start);
+        d.set_block_size(Au(0));
+        self.layout_block_children(scale_factor);
+    }
+}
+
+/// Resolves the borders of the cells of a table in the collapsing border model.  Each border
+/// between two cells, or between a cell and the edge of the table, is as wide as the widest of
+/// the borders meeting there, and is centered on the grid line.  Each cell gets half of the
+/// borders around it, and the table gets half of the borders at its outer edges.  Returns the
+/// used borders of the table and of each cell.
+///
+/// TODO: Borders of rows, row groups, and columns don't take part in the conflict resolution,
+/// and `border-style: hidden` doesn't suppress the borders it meets.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#collapsing-borders
+fn collapse_borders(grid: &TableGrid, table: Edges, cells: Vec<Edges>) -> (Edges, Vec<Edges>) {
+    let row_count = grid.rows.len();
+    let column_count = grid.column_count;
+    // The widths of the lines between columns in each row, and between rows in each column.
+    let mut column_lines = vec![vec![Au(0); column_count + 1]; row_count];
+    let mut row_lines = vec![vec![Au(0); column_count]; row_count + 1];
+    for line in &mut column_lines {
+        line[0] = table.inline_start;
+        line[column_count] = table.inline_end;
+    }
+    row_lines[0] = vec![table.block_start; column_count];
+    row_lines[row_count] = vec![table.block_end; column_count];
+    for (cell, border) in grid.cells.iter().zip(&cells) {
+        for line in &mut column_lines[cell.rows.clone()] {
+            line[cell.columns.start] = line[cell.columns.start].max(border.inline_start);
+            line[cell.columns.end] = line[cell.columns.end].max(border.inline_end);
+        }
+        for column in cell.columns.clone() {
+            row_lines[cell.rows.start][column] =
+                row_lines[cell.rows.start][column].max(border.block_start);
+            row_lines[cell.rows.end][column] =
+                row_lines[cell.rows.end][column].max(border.block_end);
+        }
+    }
+
+    let widest = |lines: &mut dyn Iterator<Item = Au>| lines.fold(Au(0), Au::max) / 2;
+    let cells = grid
+        .cells
+        .iter()
+        .map(|cell| Edges {
+            inline_start: widest(
+                &mut column_lines[cell.rows.clone()]
+                    .iter()
+                    .map(|line| line[cell.columns.start]),
+            ),
+            inline_end: widest(
+                &mut column_lines[cell.rows.clone()]
+                    .iter()
+                    .map(|line| line[cell.columns.end]),
+            ),
+            block_start: widest(
+                &mut row_lines[cell.rows.start][cell.columns.clone()]
+                    .iter()
+                    .copied(),
+            ),
+            block_end: widest(
+                &mut row_lines[cell.rows.end][cell.columns.clone()]
+                    .iter()
+                    .copied(),
+            ),
+        })
+        .collect();
+    let table = if row_count == 0 || column_count == 0 {
+        Edges::default()
+    } else {
+        Edges {
+            inline_start: widest(&mut column_lines.iter().map(|line| line[0])),
+            inline_end: widest(&mut column_lines.iter().map(|line| line[column_count])),
+            block_start: widest(&mut row_lines[0].iter().copied()),
+            block_end: widest(&mut row_lines[row_count].iter().copied()),
+        }
+    };
+    (table, cells)
+}
@@ -12,7 +12,9 @@ extern crate strum_macros;
 extern crate derive_builder;
 
 use std::fs::File;
+use std::path::Path;
 
+use crate::dom::image::load_images;
 use crate::dom::parser::parse_html;
 use crate::dom::traits::TendrilSink;
 use glutin::event::{Event, WindowEvent};
@@ -30,14 +32,21 @@ pub mod layout;
 pub mod style;
 
 use crate::cli::{
-    dump_layout_tree, html_file_path_from_files, inner_window_height, inner_window_width,
-    scale_factor, setup_and_get_cli_args, stylesheets_from_files,
+    dump_display_list, dump_layout_tree, html_file_path_from_files, inner_window_height,
+    inner_window_width, invalidate_selectors, layout_stats, print_pdf_output, relayout_from_width,
+    render_svg, scale_factor, setup_and_get_cli_args, stylesheets_from_files,
 };
-use crate::gfx::char::CharHandle;
 use crate::gfx::display::build_display_list;
-use crate::gfx::paint::MasterPainter;
+use crate::gfx::font::FontHandle;
+use crate::gfx::paint::pdf::PdfPainter;
+use crate::gfx::paint::svg::SvgPainter;
+use crate::gfx::paint::{MasterPainter, PaintBackend};
 use crate::gfx::{init_main_window_and_gl, print_gl_info, resize_window};
+use crate::layout::fragmentation::paginate;
 use crate::layout::layout_box::LayoutBox;
+use crate::layout::rect::Rect;
+use crate::style::page::PageContext;
+use app_units::Au;
 pub use common::Side;
 use gl::Gl;
 use glutin::event_loop::ControlFlow;
@@ -57,6 +66,12 @@ fn main() {
         .from_utf8()
         .read_from(&mut File::open(html_file).unwrap())
         .unwrap();
+    load_images(
+        &dom,
+        Path::new(html_file)
+            .parent()
+            .unwrap_or_else(|| Path::new("")),
+    );
     let ua_sheet = style::stylesheet::parse_css_to_stylesheet(
         Some("browser.css".to_owned()),
         &mut std::fs::read_to_string("web/browser.css").expect("file fail"),
@@ -69,7 +84,8 @@ fn main() {
         )
         .expect("parse stylesheet fail")]
     });
-    apply_styles(dom.clone(), &[ua_sheet], &[], &author_sheets);
+    let ua_sheets = [ua_sheet];
+    apply_styles(dom.clone(), &ua_sheets, &[], &author_sheets);
     let (inner_width_opt, inner_height_opt) = (
         inner_window_width(&arg_matches),
         inner_window_height(&arg_matches),
@@ -79,54 +95,173 @@ fn main() {
     if dump_layout_tree(&arg_matches) {
         let scale_factor = scale_factor_opt
             .expect("scale factor must be explicitly specified when running layout dump");
-        run_layout_dump(dom, inner_width_opt, inner_height_opt, scale_factor);
+        run_layout_dump(
+            dom,
+            inner_width_opt,
+            inner_height_opt,
+            scale_factor,
+            relayout_from_width(&arg_matches),
+            invalidate_selectors(&arg_matches),
+        );
+        return;
+    }
+    if render_svg(&arg_matches) {
+        let scale_factor =
+            scale_factor_opt.expect("scale factor must be explicitly specified when rendering SVG");
+        run_svg_render(dom, inner_width_opt, inner_height_opt, scale_factor);
+        return;
+    }
+    if let Some(output_path) = print_pdf_output(&arg_matches) {
+        let page_context = PageContext::from_sheets(&ua_sheets, &[], &author_sheets);
+        run_pdf_print(dom, page_context, output_path);
+        return;
+    }
+    if dump_display_list(&arg_matches) {
+        let scale_factor = scale_factor_opt
+            .expect("scale factor must be explicitly specified when running display list dump");
+        run_display_list_dump(dom, inner_width_opt, inner_height_opt, scale_factor);
         return;
     }
     let (windowed_context, event_loop, gl) =
         init_main_window_and_gl(inner_width_opt, inner_height_opt);
     print_gl_info(&windowed_context, &gl);
-    run_event_loop(event_loop, gl, dom, windowed_context, scale_factor_opt);
+    run_event_loop(
+        event_loop,
+        gl,
+        dom,
+        windowed_context,
+        scale_factor_opt,
+        layout_stats(&arg_matches),
+    );
 }
 
+/// Lays out `styled_dom` and dumps the layout tree to stdout.  If `relayout_from_width` is given,
+/// the tree is first laid out at that width, and the elements matching `invalidate_selectors` are
+/// invalidated, so that the dumped layout is done incrementally.  How many boxes it laid out is
+/// then printed to stderr.
 fn run_layout_dump(
     styled_dom: NodeRef,
     inner_width_opt: Option<f32>,
     inner_height_opt: Option<f32>,
     scale_factor: f32,
+    relayout_from_width: Option<f32>,
+    invalidate_selectors: Option<&str>,
 ) {
-    let mut layout_tree = build_layout_tree(styled_dom).unwrap();
-    global_layout(
-        &mut layout_tree,
-        inner_width_opt
-            .expect("Inner window width CLI arg 'width' must be specified for dump-layout."),
-        inner_height_opt
-            .expect("Inner window height CLI arg 'height' must be specified for dump-layout."),
-        scale_factor,
-    );
+    let mut layout_tree = build_layout_tree(styled_dom.clone()).unwrap();
+    let inner_width = inner_width_opt
+        .expect("Inner window width CLI arg 'width' must be specified for dump-layout.");
+    let inner_height = inner_height_opt
+        .expect("Inner window height CLI arg 'height' must be specified for dump-layout.");
+    if let Some(initial_width) = relayout_from_width {
+        global_layout(&mut layout_tree, initial_width, inner_height, scale_factor);
+        if let Some(selectors) = invalidate_selectors {
+            let elements = styled_dom
+                .select_str(selectors)
+                .expect("couldn't parse 'invalidate' arg as selectors");
+            for element in elements {
+                layout_tree.invalidate(element.as_node());
+            }
+        }
+    }
+    let stats = global_layout(&mut layout_tree, inner_width, inner_height, scale_factor);
+    if relayout_from_width.is_some() {
+        eprintln!("{}", stats);
+    }
     layout_tree.dump_layout(&mut std::io::stdout(), 0);
 }
 
+/// Lays out `styled_dom` and paints it as an SVG document to stdout.
+fn run_svg_render(
+    styled_dom: NodeRef,
+    inner_width_opt: Option<f32>,
+    inner_height_opt: Option<f32>,
+    scale_factor: f32,
+) {
+    let mut layout_tree = build_layout_tree(styled_dom).unwrap();
+    let inner_width = inner_width_opt
+        .expect("Inner window width CLI arg 'width' must be specified for render-svg.");
+    let inner_height = inner_height_opt
+        .expect("Inner window height CLI arg 'height' must be specified for render-svg.");
+    global_layout(&mut layout_tree, inner_width, inner_height, scale_factor);
+    let font_handle = FontHandle::new();
+    let display_list = build_display_list(&layout_tree, Some(&font_handle), scale_factor);
+    let mut svg_painter = SvgPainter::new();
+    svg_painter.paint(&display_list, inner_width, inner_height, &font_handle);
+    print!("{}", svg_painter.document());
+}
+
+/// Lays out `styled_dom` at the width of the page area of `page_context`, fragments it into pages,
+/// and writes them as a PDF document to `output_path`.
+fn run_pdf_print(styled_dom: NodeRef, page_context: PageContext, output_path: &str) {
+    let mut layout_tree = build_layout_tree(styled_dom).unwrap();
+    let (content_width, content_height) =
+        (page_context.content_width(), page_context.content_height());
+    // Pages are printed at their size in CSS px, regardless of the scale factor of any screen.
+    global_layout(&mut layout_tree, content_width, content_height, 1.);
+    let font_handle = FontHandle::new();
+    let display_list = build_display_list(&layout_tree, Some(&font_handle), 1.);
+    let mut pdf_painter = PdfPainter::new();
+    for page in paginate(&layout_tree, Au::from_f32_px(content_height)) {
+        let area = Rect {
+            start_x: Au(0),
+            start_y: page.start_y,
+            width: Au::from_f32_px(content_width),
+            height: page.height(),
+        };
+        let page_display_list = display_list.fragment(
+            area,
+            Au::from_f32_px(page_context.margin_left),
+            Au::from_f32_px(page_context.margin_top) - page.start_y,
+        );
+        pdf_painter.paint(
+            &page_display_list,
+            page_context.width,
+            page_context.height,
+            &font_handle,
+        );
+    }
+    std::fs::write(output_path, pdf_painter.finish()).expect("couldn't write PDF document");
+}
+
+/// Lays out `styled_dom`, builds the display list to paint it with, and dumps that to stdout.
+fn run_display_list_dump(
+    styled_dom: NodeRef,
+    inner_width_opt: Option<f32>,
+    inner_height_opt: Option<f32>,
+    scale_factor: f32,
+) {
+    let mut layout_tree = build_layout_tree(styled_dom).unwrap();
+    let inner_width = inner_width_opt
+        .expect("Inner window width CLI arg 'width' must be specified for dump-display-list.");
+    let inner_height = inner_height_opt
+        .expect("Inner window height CLI arg 'height' must be specified for dump-display-list.");
+    global_layout(&mut layout_tree, inner_width, inner_height, scale_factor);
+    let display_list = build_display_list(&layout_tree, None, scale_factor);
+    gfx::display::dump_display_list(&display_list, &mut std::io::stdout());
+}
+
 pub fn run_event_loop(
     event_loop: EventLoop<()>,
     gl: Gl,
     styled_dom: NodeRef,
     windowed_context: WindowedContext<PossiblyCurrent>,
     cli_specified_scale_factor: Option<f32>,
+    print_layout_stats: bool,
 ) {
     let mut master_painter = MasterPainter::new(&gl).unwrap();
-    let char_handle = CharHandle::new(&gl);
-    // An un-laid-out tree of boxes, to be cloned from whenever a global layout is required.
-    // This saves us from having to rebuild the entire layout tree from the DOM when necessary,
-    // instead only needing a clone.
-    let clean_layout_tree = build_layout_tree(styled_dom).unwrap();
+    let font_handle = FontHandle::new();
+    // The tree of boxes keeps its layout between frames, so that only the boxes affected by a
+    // resize are laid out again.
+    let mut layout_tree = build_layout_tree(styled_dom).unwrap();
     let mut scale =
         cli_specified_scale_factor.unwrap_or(windowed_context.window().scale_factor() as f32);
     paint(
-        clean_layout_tree.clone(),
+        &mut layout_tree,
         &windowed_context,
-        &char_handle,
+        &font_handle,
         &mut master_painter,
         scale,
+        print_layout_stats,
     );
     event_loop.run(move |event, _, control_flow| {
         // println!("{:?}", event);
@@ -137,11 +272,12 @@ pub fn run_event_loop(
                 WindowEvent::Resized(physical_size) => {
                     resize_window(&gl, &windowed_context, physical_size);
                     paint(
-                        clean_layout_tree.clone(),
+                        &mut layout_tree,
                         &windowed_context,
-                        &char_handle,
+                        &font_handle,
                         &mut master_painter,
                         scale,
+                        print_layout_stats,
                     )
                 }
                 WindowEvent::ScaleFactorChanged {
@@ -151,11 +287,12 @@ pub fn run_event_loop(
                     scale = *scale_factor as f32;
                     resize_window(&gl, &windowed_context, new_inner_size);
                     paint(
-                        clean_layout_tree.clone(),
+                        &mut layout_tree,
                         &windowed_context,
-                        &char_handle,
+                        &font_handle,
                         &mut master_painter,
                         scale,
+                        print_layout_stats,
                     )
                 }
                 WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
@@ -166,20 +303,32 @@ pub fn run_event_loop(
     });
 
     fn paint(
-        mut layout_tree: LayoutBox,
+        layout_tree: &mut LayoutBox,
         windowed_context: &WindowedContext<PossiblyCurrent>,
-        char_handle: &CharHandle,
+        font_handle: &FontHandle,
         painter: &mut MasterPainter,
         scale_factor: f32,
+        print_layout_stats: bool,
     ) {
         let inner_window_size = windowed_context.window().inner_size();
-        global_layout(
-            &mut layout_tree,
+        let stats = global_layout(
+            layout_tree,
             inner_window_size.width as f32,
             inner_window_size.width as f32,
             scale_factor,
         );
-        let display_list = build_display_list(&layout_tree, &char_handle, scale_factor);
-        painter.paint(&windowed_context, &display_list);
+        if print_layout_stats {
+            eprintln!("{}", stats);
+        }
+        let display_list = build_display_list(layout_tree, Some(font_handle), scale_factor);
+        painter.paint(
+            &display_list,
+            inner_window_size.width as f32,
+            inner_window_size.height as f32,
+            font_handle,
+        );
+        windowed_context
+            .swap_buffers()
+            .expect("couldn't swap window buffers");
     }
 }
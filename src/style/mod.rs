@@ -1,19 +1,28 @@
 use std::convert::From;
+use std::path::Path;
 
 use cssparser::{
-    AtRuleParser, CowRcStr, ParseError, Parser, QualifiedRuleParser, SourceLocation, Token,
+    AtRuleParser, AtRuleType, CowRcStr, ParseError, Parser, QualifiedRuleParser, SourceLocation,
+    Token,
 };
 use selectors::parser::SelectorParseErrorKind;
 
 use crate::dom::tree::{NodeData, NodeRef};
-use crate::style::properties::{parse_property_declaration_list, PropertyDeclarationBlock};
+use crate::style::page::{parse_page_descriptors, PageRule};
+use crate::style::properties::id::LonghandId;
+use crate::style::properties::{
+    parse_property_declaration_list, ContextualPropertyDeclaration, PropertyDeclaration,
+    PropertyDeclarationBlock,
+};
 use crate::style::select::Selectors;
 use crate::style::stylesheet::{apply_stylesheet_to_node, Stylesheet};
-use crate::style::values::computed::compute_values;
+use crate::style::values::computed::direction::WritingMode;
+use crate::style::values::computed::{compute_values, Direction};
 
 #[macro_use]
 mod macros;
 
+pub mod page;
 pub mod properties;
 pub mod select;
 pub mod stylesheet;
@@ -73,16 +82,49 @@ pub fn apply_styles(
 pub fn cascade_and_compute(start_node: &NodeRef) {
     start_node.inclusive_descendants().for_each(|node| {
         // Step 2
+        map_flow_relative_decls(&node);
         node.contextual_decls_mut().cascade_sort();
         // Step 3 and 4
         compute_values(node);
     });
 }
 
+/// Maps the flow-relative declarations of `node` to the physical properties they set, which depend
+/// on the node's `writing-mode` and `direction`.  Since both of those are inherited, the node's
+/// parent must already have its computed values.
+///
+/// https://drafts.csswg.org/css-logical/#box
+fn map_flow_relative_decls(node: &NodeRef) {
+    let (parent_writing_mode, parent_direction) = node.parent().map_or(
+        (WritingMode::initial_value(), Direction::initial_value()),
+        |parent| {
+            let parent_cvs = parent.computed_values();
+            (parent_cvs.writing_mode, parent_cvs.direction)
+        },
+    );
+    let mut decls = node.contextual_decls_mut();
+    let writing_mode = match decls.cascaded_by_longhand(LonghandId::WritingMode) {
+        Some(ContextualPropertyDeclaration {
+            inner_decl: PropertyDeclaration::WritingMode(writing_mode),
+            ..
+        }) => *writing_mode,
+        _ => parent_writing_mode,
+    };
+    let direction = match decls.cascaded_by_longhand(LonghandId::Direction) {
+        Some(ContextualPropertyDeclaration {
+            inner_decl: PropertyDeclaration::Direction(direction),
+            ..
+        }) => *direction,
+        _ => parent_direction,
+    };
+    decls.map_flow_relative_to_physical(writing_mode, direction);
+}
+
 // TODO: Servo supports many different types of rules, but we won't support those yet.  https://github.com/servo/servo/blob/d2856ce8aeca11e543bc4d9f869400d73451374e/components/style/stylesheets/mod.rs#L236
 #[derive(Clone, Debug)]
 pub enum CssRule {
     Style(StyleRule),
+    Page(PageRule),
     None,
 }
 
@@ -110,6 +152,22 @@ pub enum CssOrigin {
     Sheet(StylesheetOrigin),
 }
 
+impl CssOrigin {
+    /// The directory that relative URLs in declarations from this origin are resolved against,
+    /// which is the one containing the stylesheet for declarations from a stylesheet.
+    ///
+    /// TODO: URLs in `style` attributes and `<style>` elements should be resolved against the
+    /// directory containing the document, rather than the working directory.
+    pub fn base_dir(&self) -> &Path {
+        match self {
+            CssOrigin::Sheet(origin) => Path::new(&origin.sheet_name)
+                .parent()
+                .unwrap_or_else(|| Path::new("")),
+            CssOrigin::Inline | CssOrigin::Embedded => Path::new(""),
+        }
+    }
+}
+
 /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#cascading-origins
 #[derive(Clone, Debug, PartialEq)]
 pub enum CascadeOrigin {
@@ -141,17 +199,51 @@ pub struct Color {
 /// Parser for top-level CSS rules.
 pub struct TopLevelRuleParser {}
 
-// TODO: Support @ rules
+// TODO: Support @ rules without blocks, e.g. `@import`
 pub enum AtRuleNonBlockPrelude {}
 
-pub enum AtRuleBlockPrelude {}
+pub enum AtRuleBlockPrelude {
+    /// The page selectors of a `@page` rule, as written.
+    Page(String),
+}
 
-/// Kosmonaut currently does not support @rules, so fall back to the default @rule error impl.
+/// Kosmonaut only supports `@page` rules so far.  Any other @rule is an error.
 impl<'i> AtRuleParser<'i> for TopLevelRuleParser {
     type PreludeNoBlock = AtRuleNonBlockPrelude;
     type PreludeBlock = AtRuleBlockPrelude;
     type AtRule = CssRule;
     type Error = StyleParseErrorKind<'i>;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<AtRuleType<Self::PreludeNoBlock, Self::PreludeBlock>, ParseError<'i, Self::Error>>
+    {
+        if !name.eq_ignore_ascii_case("page") {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnsupportedAtRule(name)));
+        }
+        let start = input.position();
+        while input.next().is_ok() {}
+        Ok(AtRuleType::WithBlock(AtRuleBlockPrelude::Page(
+            input.slice_from(start).trim().to_owned(),
+        )))
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::PreludeBlock,
+        source_location: SourceLocation,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<CssRule, ParseError<'i, Self::Error>> {
+        match prelude {
+            AtRuleBlockPrelude::Page(selectors) => Ok(CssRule::Page(PageRule {
+                selectors,
+                descriptors: parse_page_descriptors(input),
+                source_location,
+            })),
+        }
+    }
 }
 
 impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser {
@@ -0,0 +1,371 @@
+use cssparser::{
+    parse_important, AtRuleParser, CowRcStr, DeclarationListParser, DeclarationParser, ParseError,
+    Parser, SourceLocation, Token,
+};
+
+use crate::style::properties::id::PropertyId;
+use crate::style::properties::PropertyDeclaration;
+use crate::style::stylesheet::Stylesheet;
+use crate::style::values::specified::length::{AU_PER_IN, AU_PER_MM, AU_PER_PX};
+use crate::style::values::specified::{LengthPercentage, LengthPercentageOrAuto, NoCalcLength};
+use crate::style::values::CSSFloat;
+use crate::style::{CssRule, StyleParseErrorKind};
+
+/// A `@page` rule, which sets the size and margins of the pages a document is printed on.
+///
+/// https://drafts.csswg.org/css-page-3/#at-page-rule
+#[derive(Clone, Debug)]
+pub struct PageRule {
+    /// The page selectors of this rule, e.g. `:first`, as written.  Empty if the rule applies to
+    /// every page.
+    ///
+    /// TODO: Match page selectors against pages.  Until then, rules with selectors are ignored.
+    pub selectors: String,
+    /// The descriptors declared in the rule's block.
+    pub descriptors: PageDescriptors,
+    /// The location in the sheet where it was found.
+    pub source_location: SourceLocation,
+}
+
+/// The descriptors of a `@page` rule, which are `None` unless the rule declared them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PageDescriptors {
+    pub size: Option<PageSize>,
+    pub margin_top: Option<LengthPercentageOrAuto>,
+    pub margin_right: Option<LengthPercentageOrAuto>,
+    pub margin_bottom: Option<LengthPercentageOrAuto>,
+    pub margin_left: Option<LengthPercentageOrAuto>,
+}
+
+impl PageDescriptors {
+    /// Overrides the descriptors of `self` with those declared in `other`.
+    fn cascade(&mut self, other: &PageDescriptors) {
+        self.size = other.size.or(self.size);
+        self.margin_top = other.margin_top.or(self.margin_top);
+        self.margin_right = other.margin_right.or(self.margin_right);
+        self.margin_bottom = other.margin_bottom.or(self.margin_bottom);
+        self.margin_left = other.margin_left.or(self.margin_left);
+    }
+}
+
+/// Parses the block of a `@page` rule, ignoring any declarations that are invalid or not allowed
+/// in page rules.
+pub fn parse_page_descriptors(input: &mut Parser) -> PageDescriptors {
+    let mut descriptors = PageDescriptors::default();
+    let decl_iter = DeclarationListParser::new(
+        input,
+        PageDeclarationParser {
+            descriptors: &mut descriptors,
+        },
+    );
+    for declaration in decl_iter {
+        if let Err(parse_err) = declaration {
+            dbg!(parse_err);
+        }
+    }
+    descriptors
+}
+
+struct PageDeclarationParser<'a> {
+    descriptors: &'a mut PageDescriptors,
+}
+
+impl<'a, 'i> DeclarationParser<'i> for PageDeclarationParser<'a> {
+    type Declaration = ();
+    type Error = StyleParseErrorKind<'i>;
+
+    fn parse_value<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<(), ParseError<'i, Self::Error>> {
+        if name.eq_ignore_ascii_case("size") {
+            let size = PageSize::parse(input)?;
+            let _ = input.try_parse(parse_important);
+            input.expect_exhausted()?;
+            self.descriptors.size = Some(size);
+            return Ok(());
+        }
+        // The margin properties are parsed like they are in style rules, and are the only
+        // properties allowed here.
+        let id = match PropertyId::parse(&name) {
+            Some(id) => id,
+            None => {
+                return Err(input.new_custom_error(StyleParseErrorKind::UnknownProperty(name)));
+            }
+        };
+        let mut declarations = Vec::new();
+        PropertyDeclaration::parse_into(&mut declarations, id, input)?;
+        let _ = input.try_parse(parse_important);
+        input.expect_exhausted()?;
+        let mut descriptors = self.descriptors.clone();
+        for declaration in declarations {
+            // Pages are always in the `horizontal-tb` writing mode with an `ltr` direction, so
+            // the flow-relative margins map to physical ones the same way for every page.
+            let (margin, lp_or_auto) = match declaration {
+                PropertyDeclaration::MarginTop(margin) => {
+                    (&mut descriptors.margin_top, margin.lp_or_auto)
+                }
+                PropertyDeclaration::MarginRight(margin) => {
+                    (&mut descriptors.margin_right, margin.lp_or_auto)
+                }
+                PropertyDeclaration::MarginBottom(margin) => {
+                    (&mut descriptors.margin_bottom, margin.lp_or_auto)
+                }
+                PropertyDeclaration::MarginLeft(margin) => {
+                    (&mut descriptors.margin_left, margin.lp_or_auto)
+                }
+                PropertyDeclaration::MarginBlockStart(lp_or_auto) => {
+                    (&mut descriptors.margin_top, lp_or_auto)
+                }
+                PropertyDeclaration::MarginInlineEnd(lp_or_auto) => {
+                    (&mut descriptors.margin_right, lp_or_auto)
+                }
+                PropertyDeclaration::MarginBlockEnd(lp_or_auto) => {
+                    (&mut descriptors.margin_bottom, lp_or_auto)
+                }
+                PropertyDeclaration::MarginInlineStart(lp_or_auto) => {
+                    (&mut descriptors.margin_left, lp_or_auto)
+                }
+                _ => return Err(input.new_custom_error(StyleParseErrorKind::NotAllowedInPageRule)),
+            };
+            *margin = Some(lp_or_auto);
+        }
+        *self.descriptors = descriptors;
+        Ok(())
+    }
+}
+
+/// Page rules can't contain nested at-rules, so fall back to the default "error" implementation.
+impl<'a, 'i> AtRuleParser<'i> for PageDeclarationParser<'a> {
+    type PreludeNoBlock = ();
+    type PreludeBlock = ();
+    type AtRule = ();
+    type Error = StyleParseErrorKind<'i>;
+}
+
+/// The `size` descriptor of a `@page` rule.
+///
+/// https://drafts.csswg.org/css-page-3/#page-size-prop
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageSize {
+    /// The page is the size and orientation of the default paper, A4 portrait.
+    Auto,
+    /// The page has this width and height.
+    Lengths(NoCalcLength, NoCalcLength),
+    /// The page is the size of this paper, in this orientation, or the paper's own if `None`.
+    Paper(PaperSize, Option<PageOrientation>),
+    /// The page is the size of the default paper, in this orientation.
+    Orientation(PageOrientation),
+}
+
+impl PageSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+            return Ok(PageSize::Auto);
+        }
+        if let Ok(width) = input.try_parse(parse_non_negative_length) {
+            let height = input.try_parse(parse_non_negative_length).unwrap_or(width);
+            return Ok(PageSize::Lengths(width, height));
+        }
+        // The paper size and the orientation can be given in either order.
+        let mut paper = input.try_parse(PaperSize::parse).ok();
+        let orientation = input.try_parse(PageOrientation::parse).ok();
+        if paper.is_none() {
+            paper = input.try_parse(PaperSize::parse).ok();
+        }
+        match (paper, orientation) {
+            (Some(paper), orientation) => Ok(PageSize::Paper(paper, orientation)),
+            (None, Some(orientation)) => Ok(PageSize::Orientation(orientation)),
+            (None, None) => Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError)),
+        }
+    }
+
+    /// Resolves this size to the width and height of the page, in CSS px.
+    pub fn to_px(self) -> (CSSFloat, CSSFloat) {
+        match self {
+            PageSize::Auto => PaperSize::A4.to_px(),
+            PageSize::Lengths(NoCalcLength::Absolute(width), NoCalcLength::Absolute(height)) => {
+                (width.to_px(), height.to_px())
+            }
+            PageSize::Paper(paper, None) => paper.to_px(),
+            PageSize::Paper(paper, Some(orientation)) => orientation.orient(paper.to_px()),
+            PageSize::Orientation(orientation) => orientation.orient(PaperSize::A4.to_px()),
+        }
+    }
+}
+
+fn parse_non_negative_length<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<NoCalcLength, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } if value >= 0. => NoCalcLength::parse_dimension(value, unit)
+            .map_err(|()| location.new_unexpected_token_error(token.clone())),
+        _ => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+/// The standard paper sizes the `size` descriptor can name.
+///
+/// https://drafts.csswg.org/css-page-3/#typedef-page-size-page-size
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaperSize {
+    A5,
+    A4,
+    A3,
+    B5,
+    B4,
+    JisB5,
+    JisB4,
+    Letter,
+    Legal,
+    Ledger,
+}
+
+impl PaperSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "a5" => Ok(PaperSize::A5),
+            "a4" => Ok(PaperSize::A4),
+            "a3" => Ok(PaperSize::A3),
+            "b5" => Ok(PaperSize::B5),
+            "b4" => Ok(PaperSize::B4),
+            "jis-b5" => Ok(PaperSize::JisB5),
+            "jis-b4" => Ok(PaperSize::JisB4),
+            "letter" => Ok(PaperSize::Letter),
+            "legal" => Ok(PaperSize::Legal),
+            "ledger" => Ok(PaperSize::Ledger),
+        }
+    }
+
+    /// The width and height of this paper in portrait orientation, in CSS px.
+    pub fn to_px(self) -> (CSSFloat, CSSFloat) {
+        let mm = |width: CSSFloat, height: CSSFloat| {
+            let px_per_mm = AU_PER_MM / AU_PER_PX;
+            (width * px_per_mm, height * px_per_mm)
+        };
+        let inches = |width: CSSFloat, height: CSSFloat| {
+            let px_per_in = AU_PER_IN / AU_PER_PX;
+            (width * px_per_in, height * px_per_in)
+        };
+        match self {
+            PaperSize::A5 => mm(148., 210.),
+            PaperSize::A4 => mm(210., 297.),
+            PaperSize::A3 => mm(297., 420.),
+            PaperSize::B5 => mm(176., 250.),
+            PaperSize::B4 => mm(250., 353.),
+            PaperSize::JisB5 => mm(182., 257.),
+            PaperSize::JisB4 => mm(257., 364.),
+            PaperSize::Letter => inches(8.5, 11.),
+            PaperSize::Legal => inches(8.5, 14.),
+            PaperSize::Ledger => inches(11., 17.),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PageOrientation {
+    Portrait,
+    Landscape,
+}
+
+impl PageOrientation {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "portrait" => Ok(PageOrientation::Portrait),
+            "landscape" => Ok(PageOrientation::Landscape),
+        }
+    }
+
+    /// Swaps the given width and height if needed for the page to be in this orientation.
+    fn orient(self, (width, height): (CSSFloat, CSSFloat)) -> (CSSFloat, CSSFloat) {
+        let is_landscape = width > height;
+        if is_landscape == (self == PageOrientation::Landscape) {
+            (width, height)
+        } else {
+            (height, width)
+        }
+    }
+}
+
+/// The size and margins of the pages a document is printed on, in CSS px.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageContext {
+    pub width: CSSFloat,
+    pub height: CSSFloat,
+    pub margin_top: CSSFloat,
+    pub margin_right: CSSFloat,
+    pub margin_bottom: CSSFloat,
+    pub margin_left: CSSFloat,
+}
+
+impl PageContext {
+    /// Cascades the `@page` rules of the given sheets, in the same origin order as `apply_styles`,
+    /// and resolves them to the context of every page.
+    ///
+    /// TODO: `!important` page declarations should win over those of later origins.
+    pub fn from_sheets(
+        ua_sheets: &[Stylesheet],
+        user_sheets: &[Stylesheet],
+        author_sheets: &[Stylesheet],
+    ) -> PageContext {
+        let mut descriptors = PageDescriptors::default();
+        ua_sheets
+            .iter()
+            .chain(user_sheets)
+            .chain(author_sheets)
+            .flat_map(|sheet| sheet.rules())
+            .for_each(|rule| {
+                if let CssRule::Page(page_rule) = rule {
+                    if page_rule.selectors.is_empty() {
+                        descriptors.cascade(&page_rule.descriptors);
+                    }
+                }
+            });
+        PageContext::from_descriptors(&descriptors)
+    }
+
+    pub fn from_descriptors(descriptors: &PageDescriptors) -> PageContext {
+        let (width, height) = descriptors.size.unwrap_or(PageSize::Auto).to_px();
+        // Percentage margins are relative to the page's width for the left and right margins, and
+        // to its height for the top and bottom ones.  `auto` margins are zero.
+        // https://drafts.csswg.org/css-page-3/#page-box-page-rule
+        let resolve = |margin: Option<LengthPercentageOrAuto>, basis: CSSFloat| match margin {
+            Some(LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(
+                NoCalcLength::Absolute(length),
+            ))) => length.to_px(),
+            Some(LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(
+                percentage,
+            ))) => percentage.0 * basis,
+            Some(LengthPercentageOrAuto::Auto) | None => 0.,
+        };
+        PageContext {
+            width,
+            height,
+            margin_top: resolve(descriptors.margin_top, height),
+            margin_right: resolve(descriptors.margin_right, width),
+            margin_bottom: resolve(descriptors.margin_bottom, height),
+            margin_left: resolve(descriptors.margin_left, width),
+        }
+    }
+
+    /// The width of the page area, which is the page box inside its margins.
+    pub fn content_width(&self) -> CSSFloat {
+        (self.width - self.margin_left - self.margin_right).max(0.)
+    }
+
+    /// The height of the page area, which is the page box inside its margins.
+    pub fn content_height(&self) -> CSSFloat {
+        (self.height - self.margin_top - self.margin_bottom).max(0.)
+    }
+}
@@ -23,48 +23,156 @@ impl PropertyId {
     pub fn parse(prop_name: &str) -> Option<PropertyId> {
         let id = match prop_name {
             // Longhands
+            "align-content" => PropertyId::Longhand(LonghandId::AlignContent),
+            "align-items" => PropertyId::Longhand(LonghandId::AlignItems),
+            "align-self" => PropertyId::Longhand(LonghandId::AlignSelf),
+            "background-clip" => PropertyId::Longhand(LonghandId::BackgroundClip),
             "background-color" => PropertyId::Longhand(LonghandId::BackgroundColor),
+            "background-image" => PropertyId::Longhand(LonghandId::BackgroundImage),
+            "background-origin" => PropertyId::Longhand(LonghandId::BackgroundOrigin),
+            "background-position-x" => PropertyId::Longhand(LonghandId::BackgroundPositionX),
+            "background-position-y" => PropertyId::Longhand(LonghandId::BackgroundPositionY),
+            "background-repeat" => PropertyId::Longhand(LonghandId::BackgroundRepeat),
+            "background-size" => PropertyId::Longhand(LonghandId::BackgroundSize),
+            "block-size" => PropertyId::Longhand(LonghandId::BlockSize),
+            "border-block-end-color" => PropertyId::Longhand(LonghandId::BorderBlockEndColor),
+            "border-block-start-color" => PropertyId::Longhand(LonghandId::BorderBlockStartColor),
             "border-bottom-color" => PropertyId::Longhand(LonghandId::BorderBottomColor),
+            "border-collapse" => PropertyId::Longhand(LonghandId::BorderCollapse),
+            "border-inline-end-color" => PropertyId::Longhand(LonghandId::BorderInlineEndColor),
+            "border-inline-start-color" => PropertyId::Longhand(LonghandId::BorderInlineStartColor),
             "border-left-color" => PropertyId::Longhand(LonghandId::BorderLeftColor),
             "border-right-color" => PropertyId::Longhand(LonghandId::BorderRightColor),
             "border-top-color" => PropertyId::Longhand(LonghandId::BorderTopColor),
+            "border-block-end-style" => PropertyId::Longhand(LonghandId::BorderBlockEndStyle),
+            "border-block-start-style" => PropertyId::Longhand(LonghandId::BorderBlockStartStyle),
             "border-bottom-style" => PropertyId::Longhand(LonghandId::BorderBottomStyle),
+            "border-inline-end-style" => PropertyId::Longhand(LonghandId::BorderInlineEndStyle),
+            "border-inline-start-style" => PropertyId::Longhand(LonghandId::BorderInlineStartStyle),
             "border-left-style" => PropertyId::Longhand(LonghandId::BorderLeftStyle),
             "border-right-style" => PropertyId::Longhand(LonghandId::BorderRightStyle),
             "border-top-style" => PropertyId::Longhand(LonghandId::BorderTopStyle),
+            "border-block-end-width" => PropertyId::Longhand(LonghandId::BorderBlockEndWidth),
+            "border-block-start-width" => PropertyId::Longhand(LonghandId::BorderBlockStartWidth),
             "border-bottom-width" => PropertyId::Longhand(LonghandId::BorderBottomWidth),
+            "border-inline-end-width" => PropertyId::Longhand(LonghandId::BorderInlineEndWidth),
+            "border-inline-start-width" => PropertyId::Longhand(LonghandId::BorderInlineStartWidth),
             "border-left-width" => PropertyId::Longhand(LonghandId::BorderLeftWidth),
             "border-right-width" => PropertyId::Longhand(LonghandId::BorderRightWidth),
             "border-top-width" => PropertyId::Longhand(LonghandId::BorderTopWidth),
+            "border-bottom-left-radius" => PropertyId::Longhand(LonghandId::BorderBottomLeftRadius),
+            "border-bottom-right-radius" => {
+                PropertyId::Longhand(LonghandId::BorderBottomRightRadius)
+            }
+            "border-end-end-radius" => PropertyId::Longhand(LonghandId::BorderEndEndRadius),
+            "border-end-start-radius" => PropertyId::Longhand(LonghandId::BorderEndStartRadius),
+            "border-start-end-radius" => PropertyId::Longhand(LonghandId::BorderStartEndRadius),
+            "border-start-start-radius" => PropertyId::Longhand(LonghandId::BorderStartStartRadius),
+            "border-top-left-radius" => PropertyId::Longhand(LonghandId::BorderTopLeftRadius),
+            "border-top-right-radius" => PropertyId::Longhand(LonghandId::BorderTopRightRadius),
+            "border-spacing" => PropertyId::Longhand(LonghandId::BorderSpacing),
+            "bottom" => PropertyId::Longhand(LonghandId::Bottom),
+            "box-shadow" => PropertyId::Longhand(LonghandId::BoxShadow),
+            "box-sizing" => PropertyId::Longhand(LonghandId::BoxSizing),
+            "break-after" => PropertyId::Longhand(LonghandId::BreakAfter),
+            "break-before" => PropertyId::Longhand(LonghandId::BreakBefore),
+            "break-inside" => PropertyId::Longhand(LonghandId::BreakInside),
+            "caption-side" => PropertyId::Longhand(LonghandId::CaptionSide),
+            "clear" => PropertyId::Longhand(LonghandId::Clear),
             "color" => PropertyId::Longhand(LonghandId::Color),
+            "column-gap" => PropertyId::Longhand(LonghandId::ColumnGap),
             "direction" => PropertyId::Longhand(LonghandId::Direction),
             "display" => PropertyId::Longhand(LonghandId::Display),
-            //            "float" => PropertyId::Longhand(LonghandId::Float),
+            "flex-basis" => PropertyId::Longhand(LonghandId::FlexBasis),
+            "flex-direction" => PropertyId::Longhand(LonghandId::FlexDirection),
+            "flex-grow" => PropertyId::Longhand(LonghandId::FlexGrow),
+            "flex-shrink" => PropertyId::Longhand(LonghandId::FlexShrink),
+            "flex-wrap" => PropertyId::Longhand(LonghandId::FlexWrap),
+            "float" => PropertyId::Longhand(LonghandId::Float),
             //            "font-style" => PropertyId::Longhand(LonghandId::FontStyle),
             //            "font-weight" => PropertyId::Longhand(LonghandId::FontWeight),
             //            "visibility" => PropertyId::Longhand(LonghandId::Visibility),
             "font-size" => PropertyId::Longhand(LonghandId::FontSize),
+            "grid-auto-columns" => PropertyId::Longhand(LonghandId::GridAutoColumns),
+            "grid-auto-flow" => PropertyId::Longhand(LonghandId::GridAutoFlow),
+            "grid-auto-rows" => PropertyId::Longhand(LonghandId::GridAutoRows),
+            "grid-column-end" => PropertyId::Longhand(LonghandId::GridColumnEnd),
+            "grid-column-start" => PropertyId::Longhand(LonghandId::GridColumnStart),
+            "grid-row-end" => PropertyId::Longhand(LonghandId::GridRowEnd),
+            "grid-row-start" => PropertyId::Longhand(LonghandId::GridRowStart),
+            "grid-template-areas" => PropertyId::Longhand(LonghandId::GridTemplateAreas),
+            "grid-template-columns" => PropertyId::Longhand(LonghandId::GridTemplateColumns),
+            "grid-template-rows" => PropertyId::Longhand(LonghandId::GridTemplateRows),
             "height" => PropertyId::Longhand(LonghandId::Height),
+            "inline-size" => PropertyId::Longhand(LonghandId::InlineSize),
+            "inset-block-end" => PropertyId::Longhand(LonghandId::InsetBlockEnd),
+            "inset-block-start" => PropertyId::Longhand(LonghandId::InsetBlockStart),
+            "inset-inline-end" => PropertyId::Longhand(LonghandId::InsetInlineEnd),
+            "inset-inline-start" => PropertyId::Longhand(LonghandId::InsetInlineStart),
+            "justify-content" => PropertyId::Longhand(LonghandId::JustifyContent),
+            "left" => PropertyId::Longhand(LonghandId::Left),
+            "margin-block-end" => PropertyId::Longhand(LonghandId::MarginBlockEnd),
+            "margin-block-start" => PropertyId::Longhand(LonghandId::MarginBlockStart),
             "margin-bottom" => PropertyId::Longhand(LonghandId::MarginBottom),
+            "margin-inline-end" => PropertyId::Longhand(LonghandId::MarginInlineEnd),
+            "margin-inline-start" => PropertyId::Longhand(LonghandId::MarginInlineStart),
             "margin-left" => PropertyId::Longhand(LonghandId::MarginLeft),
             "margin-right" => PropertyId::Longhand(LonghandId::MarginRight),
             "margin-top" => PropertyId::Longhand(LonghandId::MarginTop),
+            "max-block-size" => PropertyId::Longhand(LonghandId::MaxBlockSize),
+            "max-height" => PropertyId::Longhand(LonghandId::MaxHeight),
+            "max-inline-size" => PropertyId::Longhand(LonghandId::MaxInlineSize),
+            "max-width" => PropertyId::Longhand(LonghandId::MaxWidth),
+            "min-block-size" => PropertyId::Longhand(LonghandId::MinBlockSize),
+            "min-height" => PropertyId::Longhand(LonghandId::MinHeight),
+            "min-inline-size" => PropertyId::Longhand(LonghandId::MinInlineSize),
+            "min-width" => PropertyId::Longhand(LonghandId::MinWidth),
+            "opacity" => PropertyId::Longhand(LonghandId::Opacity),
+            "order" => PropertyId::Longhand(LonghandId::Order),
+            "overflow-x" => PropertyId::Longhand(LonghandId::OverflowX),
+            "overflow-y" => PropertyId::Longhand(LonghandId::OverflowY),
+            "padding-block-end" => PropertyId::Longhand(LonghandId::PaddingBlockEnd),
+            "padding-block-start" => PropertyId::Longhand(LonghandId::PaddingBlockStart),
             "padding-bottom" => PropertyId::Longhand(LonghandId::PaddingBottom),
+            "padding-inline-end" => PropertyId::Longhand(LonghandId::PaddingInlineEnd),
+            "padding-inline-start" => PropertyId::Longhand(LonghandId::PaddingInlineStart),
             "padding-left" => PropertyId::Longhand(LonghandId::PaddingLeft),
             "padding-right" => PropertyId::Longhand(LonghandId::PaddingRight),
             "padding-top" => PropertyId::Longhand(LonghandId::PaddingTop),
+            "position" => PropertyId::Longhand(LonghandId::Position),
+            "right" => PropertyId::Longhand(LonghandId::Right),
+            "row-gap" => PropertyId::Longhand(LonghandId::RowGap),
+            "table-layout" => PropertyId::Longhand(LonghandId::TableLayout),
+            "text-shadow" => PropertyId::Longhand(LonghandId::TextShadow),
+            "top" => PropertyId::Longhand(LonghandId::Top),
             "width" => PropertyId::Longhand(LonghandId::Width),
             "writing-mode" => PropertyId::Longhand(LonghandId::WritingMode),
+            "z-index" => PropertyId::Longhand(LonghandId::ZIndex),
             // Shorthands
             "background" => PropertyId::Shorthand(ShorthandId::Background),
+            "background-position" => PropertyId::Shorthand(ShorthandId::BackgroundPosition),
             "border-width" => PropertyId::Shorthand(ShorthandId::BorderWidth),
             "border-top" => PropertyId::Shorthand(ShorthandId::BorderTop),
             "border-right" => PropertyId::Shorthand(ShorthandId::BorderRight),
             "border-bottom" => PropertyId::Shorthand(ShorthandId::BorderBottom),
             "border-left" => PropertyId::Shorthand(ShorthandId::BorderLeft),
             "border" => PropertyId::Shorthand(ShorthandId::Border),
+            "border-radius" => PropertyId::Shorthand(ShorthandId::BorderRadius),
+            "flex" => PropertyId::Shorthand(ShorthandId::Flex),
+            "flex-flow" => PropertyId::Shorthand(ShorthandId::FlexFlow),
+            "gap" => PropertyId::Shorthand(ShorthandId::Gap),
+            "grid-area" => PropertyId::Shorthand(ShorthandId::GridArea),
+            "grid-column" => PropertyId::Shorthand(ShorthandId::GridColumn),
+            "grid-row" => PropertyId::Shorthand(ShorthandId::GridRow),
+            "inset-block" => PropertyId::Shorthand(ShorthandId::InsetBlock),
+            "inset-inline" => PropertyId::Shorthand(ShorthandId::InsetInline),
             "margin" => PropertyId::Shorthand(ShorthandId::Margin),
+            "margin-block" => PropertyId::Shorthand(ShorthandId::MarginBlock),
+            "margin-inline" => PropertyId::Shorthand(ShorthandId::MarginInline),
+            "overflow" => PropertyId::Shorthand(ShorthandId::Overflow),
             "padding" => PropertyId::Shorthand(ShorthandId::Padding),
+            "padding-block" => PropertyId::Shorthand(ShorthandId::PaddingBlock),
+            "padding-inline" => PropertyId::Shorthand(ShorthandId::PaddingInline),
             _ => return None,
         };
         Some(id)
@@ -76,26 +184,26 @@ impl PropertyId {
 #[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
 #[repr(u16)]
 pub enum LonghandId {
-    //    /// align-content
-    //    AlignContent = 0,
-    //    /// align-items
-    //    AlignItems = 1,
-    //    /// align-self
-    //    AlignSelf = 2,
+    /// align-content
+    AlignContent = 0,
+    /// align-items
+    AlignItems = 1,
+    /// align-self
+    AlignSelf = 2,
     //    /// aspect-ratio
     //    AspectRatio = 3,
     //    /// backface-visibility
     //    BackfaceVisibility = 4,
     //    /// border-collapse
-    //    BorderCollapse = 5,
+    BorderCollapse = 5,
     //    /// border-image-repeat
     //    BorderImageRepeat = 6,
-    //    /// box-sizing
-    //    BoxSizing = 7,
+    /// box-sizing
+    BoxSizing = 7,
     //    /// caption-side
-    //    CaptionSide = 8,
-    //    /// clear
-    //    Clear = 9,
+    CaptionSide = 8,
+    /// clear
+    Clear = 9,
     //    /// column-count
     //    ColumnCount = 10,
     /// direction
@@ -104,12 +212,12 @@ pub enum LonghandId {
     Display = 12,
     //    /// empty-cells
     //    EmptyCells = 13,
-    //    /// flex-direction
-    //    FlexDirection = 14,
-    //    /// flex-wrap
-    //    FlexWrap = 15,
-    //    /// float
-    //    Float = 16,
+    /// flex-direction
+    FlexDirection = 14,
+    /// flex-wrap
+    FlexWrap = 15,
+    /// float
+    Float = 16,
     //    /// font-stretch
     //    FontStretch = 17,
     //    /// font-style
@@ -120,18 +228,18 @@ pub enum LonghandId {
     //    FontWeight = 20,
     //    /// image-rendering
     //    ImageRendering = 21,
-    //    /// justify-content
-    //    JustifyContent = 22,
+    /// justify-content
+    JustifyContent = 22,
     //    /// list-style-position
     //    ListStylePosition = 23,
     //    /// list-style-type
     //    ListStyleType = 24,
     //    /// mix-blend-mode
     //    MixBlendMode = 25,
-    //    /// opacity
-    //    Opacity = 26,
-    //    /// order
-    //    Order = 27,
+    /// opacity
+    Opacity = 26,
+    /// order
+    Order = 27,
     //    /// outline-style
     //    OutlineStyle = 28,
     //    /// overflow-wrap
@@ -139,9 +247,9 @@ pub enum LonghandId {
     //    /// pointer-events
     //    PointerEvents = 30,
     //    /// position
-    //    Position = 31,
+    Position = 31,
     //    /// table-layout
-    //    TableLayout = 32,
+    TableLayout = 32,
     //    /// text-align
     //    TextAlign = 33,
     //    /// text-decoration-line
@@ -164,30 +272,30 @@ pub enum LonghandId {
     //    WordBreak = 42,
     /// writing-mode
     WritingMode = 43,
-    //    /// z-index
-    //    ZIndex = 44,
-    //    /// flex-grow
-    //    FlexGrow = 45,
-    //    /// flex-shrink
-    //    FlexShrink = 46,
+    /// z-index
+    ZIndex = 44,
+    /// flex-grow
+    FlexGrow = 45,
+    /// flex-shrink
+    FlexShrink = 46,
     //    /// overflow-block
     //    OverflowBlock = 47,
     //    /// overflow-inline
     //    OverflowInline = 48,
-    //    /// overflow-x
-    //    OverflowX = 49,
-    //    /// overflow-y
-    //    OverflowY = 50,
-    //    /// border-block-end-style
-    //    BorderBlockEndStyle = 51,
-    //    /// border-block-start-style
-    //    BorderBlockStartStyle = 52,
+    /// overflow-x
+    OverflowX = 49,
+    /// overflow-y
+    OverflowY = 50,
+    /// border-block-end-style
+    BorderBlockEndStyle = 51,
+    /// border-block-start-style
+    BorderBlockStartStyle = 52,
     /// border-bottom-style
     BorderBottomStyle = 53,
-    //    /// border-inline-end-style
-    //    BorderInlineEndStyle = 54,
-    //    /// border-inline-start-style
-    //    BorderInlineStartStyle = 55,
+    /// border-inline-end-style
+    BorderInlineEndStyle = 54,
+    /// border-inline-start-style
+    BorderInlineStartStyle = 55,
     /// border-left-style
     BorderLeftStyle = 56,
     /// border-right-style
@@ -212,20 +320,20 @@ pub enum LonghandId {
     //    AnimationTimingFunction = 66,
     //    /// background-attachment
     //    BackgroundAttachment = 67,
-    //    /// background-clip
-    //    BackgroundClip = 68,
-    //    /// background-image
-    //    BackgroundImage = 69,
-    //    /// background-origin
-    //    BackgroundOrigin = 70,
-    //    /// background-position-x
-    //    BackgroundPositionX = 71,
-    //    /// background-position-y
-    //    BackgroundPositionY = 72,
-    //    /// background-repeat
-    //    BackgroundRepeat = 73,
-    //    /// background-size
-    //    BackgroundSize = 74,
+    /// background-clip
+    BackgroundClip = 68,
+    /// background-image
+    BackgroundImage = 69,
+    /// background-origin
+    BackgroundOrigin = 70,
+    /// background-position-x
+    BackgroundPositionX = 71,
+    /// background-position-y
+    BackgroundPositionY = 72,
+    /// background-repeat
+    BackgroundRepeat = 73,
+    /// background-size
+    BackgroundSize = 74,
     //    /// border-image-outset
     //    BorderImageOutset = 75,
     //    /// border-image-slice
@@ -235,15 +343,15 @@ pub enum LonghandId {
     //    /// border-image-width
     //    BorderImageWidth = 78,
     //    /// border-spacing
-    //    BorderSpacing = 79,
-    //    /// box-shadow
-    //    BoxShadow = 80,
+    BorderSpacing = 79,
+    /// box-shadow
+    BoxShadow = 80,
     //    /// clip
     //    Clip = 81,
     /// color
     Color = 82,
-    //    /// column-gap
-    //    ColumnGap = 83,
+    /// column-gap
+    ColumnGap = 83,
     //    /// column-width
     //    ColumnWidth = 84,
     //    /// content
@@ -256,8 +364,8 @@ pub enum LonghandId {
     //    Cursor = 88,
     //    /// filter
     //    Filter = 89,
-    //    /// flex-basis
-    //    FlexBasis = 90,
+    /// flex-basis
+    FlexBasis = 90,
     //    /// font-family
     //    FontFamily = 91,
     /// font-size
@@ -284,8 +392,8 @@ pub enum LonghandId {
     //    TextIndent = 102,
     //    /// text-overflow
     //    TextOverflow = 103,
-    //    /// text-shadow
-    //    TextShadow = 104,
+    /// text-shadow
+    TextShadow = 104,
     //    /// transform
     //    Transform = 105,
     //    /// transform-origin
@@ -304,72 +412,72 @@ pub enum LonghandId {
     //    VerticalAlign = 112,
     //    /// word-spacing
     //    WordSpacing = 113,
-    //    /// max-block-size
-    //    MaxBlockSize = 114,
-    //    /// max-height
-    //    MaxHeight = 115,
-    //    /// max-inline-size
-    //    MaxInlineSize = 116,
-    //    /// max-width
-    //    MaxWidth = 117,
-    //    /// border-bottom-left-radius
-    //    BorderBottomLeftRadius = 118,
-    //    /// border-bottom-right-radius
-    //    BorderBottomRightRadius = 119,
-    //    /// border-end-end-radius
-    //    BorderEndEndRadius = 120,
-    //    /// border-end-start-radius
-    //    BorderEndStartRadius = 121,
-    //    /// border-start-end-radius
-    //    BorderStartEndRadius = 122,
-    //    /// border-start-start-radius
-    //    BorderStartStartRadius = 123,
-    //    /// border-top-left-radius
-    //    BorderTopLeftRadius = 124,
-    //    /// border-top-right-radius
-    //    BorderTopRightRadius = 125,
-    //    /// padding-block-end
-    //    PaddingBlockEnd = 126,
-    //    /// padding-block-start
-    //    PaddingBlockStart = 127,
+    /// max-block-size
+    MaxBlockSize = 114,
+    /// max-height
+    MaxHeight = 115,
+    /// max-inline-size
+    MaxInlineSize = 116,
+    /// max-width
+    MaxWidth = 117,
+    /// border-bottom-left-radius
+    BorderBottomLeftRadius = 118,
+    /// border-bottom-right-radius
+    BorderBottomRightRadius = 119,
+    /// border-end-end-radius
+    BorderEndEndRadius = 120,
+    /// border-end-start-radius
+    BorderEndStartRadius = 121,
+    /// border-start-end-radius
+    BorderStartEndRadius = 122,
+    /// border-start-start-radius
+    BorderStartStartRadius = 123,
+    /// border-top-left-radius
+    BorderTopLeftRadius = 124,
+    /// border-top-right-radius
+    BorderTopRightRadius = 125,
+    /// padding-block-end
+    PaddingBlockEnd = 126,
+    /// padding-block-start
+    PaddingBlockStart = 127,
     /// padding-bottom
     PaddingBottom = 128,
     /// padding-inline-end
-    //    PaddingInlineEnd = 129,
-    //    /// padding-inline-start
-    //    PaddingInlineStart = 130,
-    //    /// padding-left
+    PaddingInlineEnd = 129,
+    /// padding-inline-start
+    PaddingInlineStart = 130,
+    /// padding-left
     PaddingLeft = 131,
     /// padding-right
     PaddingRight = 132,
     /// padding-top
     PaddingTop = 133,
-    //    /// block-size
-    //    BlockSize = 134,
+    /// block-size
+    BlockSize = 134,
     /// height
     Height = 135,
-    //    /// inline-size
-    //    InlineSize = 136,
-    //    /// min-block-size
-    //    MinBlockSize = 137,
-    //    /// min-height
-    //    MinHeight = 138,
-    //    /// min-inline-size
-    //    MinInlineSize = 139,
-    //    /// min-width
-    //    MinWidth = 140,
+    /// inline-size
+    InlineSize = 136,
+    /// min-block-size
+    MinBlockSize = 137,
+    /// min-height
+    MinHeight = 138,
+    /// min-inline-size
+    MinInlineSize = 139,
+    /// min-width
+    MinWidth = 140,
     /// width
     Width = 141,
-    //    /// border-block-end-width
-    //    BorderBlockEndWidth = 142,
-    //    /// border-block-start-width
-    //    BorderBlockStartWidth = 143,
+    /// border-block-end-width
+    BorderBlockEndWidth = 142,
+    /// border-block-start-width
+    BorderBlockStartWidth = 143,
     /// border-bottom-width
     BorderBottomWidth = 144,
-    //    /// border-inline-end-width
-    //    BorderInlineEndWidth = 145,
-    //    /// border-inline-start-width
-    //    BorderInlineStartWidth = 146,
+    /// border-inline-end-width
+    BorderInlineEndWidth = 145,
+    /// border-inline-start-width
+    BorderInlineStartWidth = 146,
     /// border-left-width
     BorderLeftWidth = 147,
     /// border-right-width
@@ -380,16 +488,16 @@ pub enum LonghandId {
     //    OutlineWidth = 150,
     /// background-color
     BackgroundColor = 151,
-    //    /// border-block-end-color
-    //    BorderBlockEndColor = 152,
-    //    /// border-block-start-color
-    //    BorderBlockStartColor = 153,
+    /// border-block-end-color
+    BorderBlockEndColor = 152,
+    /// border-block-start-color
+    BorderBlockStartColor = 153,
     /// border-bottom-color
     BorderBottomColor = 154,
-    //    /// border-inline-end-color
-    //    BorderInlineEndColor = 155,
-    //    /// border-inline-start-color
-    //    BorderInlineStartColor = 156,
+    /// border-inline-end-color
+    BorderInlineEndColor = 155,
+    /// border-inline-start-color
+    BorderInlineStartColor = 156,
     /// border-left-color
     BorderLeftColor = 157,
     /// border-right-color
@@ -399,27 +507,27 @@ pub enum LonghandId {
     //    /// outline-color
     //    OutlineColor = 160,
     //    /// bottom
-    //    Bottom = 161,
-    //    /// inset-block-end
-    //    InsetBlockEnd = 162,
-    //    /// inset-block-start
-    //    InsetBlockStart = 163,
-    //    /// inset-inline-end
-    //    InsetInlineEnd = 164,
-    //    /// inset-inline-start
-    //    InsetInlineStart = 165,
+    Bottom = 161,
+    /// inset-block-end
+    InsetBlockEnd = 162,
+    /// inset-block-start
+    InsetBlockStart = 163,
+    /// inset-inline-end
+    InsetInlineEnd = 164,
+    /// inset-inline-start
+    InsetInlineStart = 165,
     //    /// left
-    //    Left = 166,
-    //    /// margin-block-end
-    //    MarginBlockEnd = 167,
-    //    /// margin-block-start
-    //    MarginBlockStart = 168,
+    Left = 166,
+    /// margin-block-end
+    MarginBlockEnd = 167,
+    /// margin-block-start
+    MarginBlockStart = 168,
     /// margin-bottom
     MarginBottom = 169,
-    //    /// margin-inline-end
-    //    MarginInlineEnd = 170,
-    //    /// margin-inline-start
-    //    MarginInlineStart = 171,
+    /// margin-inline-end
+    MarginInlineEnd = 170,
+    /// margin-inline-start
+    MarginInlineStart = 171,
     /// margin-left
     MarginLeft = 172,
     /// margin-right
@@ -427,21 +535,84 @@ pub enum LonghandId {
     /// margin-top
     MarginTop = 174,
     //    /// right
-    //    Right = 175,
+    Right = 175,
     //    /// top
-    //    Top = 176,
+    Top = 176,
+    /// row-gap
+    RowGap = 177,
+    /// grid-auto-columns
+    GridAutoColumns = 178,
+    /// grid-auto-flow
+    GridAutoFlow = 179,
+    /// grid-auto-rows
+    GridAutoRows = 180,
+    /// grid-column-end
+    GridColumnEnd = 181,
+    /// grid-column-start
+    GridColumnStart = 182,
+    /// grid-row-end
+    GridRowEnd = 183,
+    /// grid-row-start
+    GridRowStart = 184,
+    /// grid-template-areas
+    GridTemplateAreas = 185,
+    /// grid-template-columns
+    GridTemplateColumns = 186,
+    /// grid-template-rows
+    GridTemplateRows = 187,
+    /// break-after
+    BreakAfter = 188,
+    /// break-before
+    BreakBefore = 189,
+    /// break-inside
+    BreakInside = 190,
 }
 
 impl LonghandId {
     #[allow(unreachable_patterns)]
     pub fn value_default(self, cv_builder: &mut ComputedValuesBuilder, ctx: &ComputeContext) {
         match self {
+            LonghandId::AlignContent => {
+                cv_builder.align_content(computed::AlignContent::value_default(ctx));
+            }
+            LonghandId::AlignItems => {
+                cv_builder.align_items(computed::AlignItems::value_default(ctx));
+            }
+            LonghandId::AlignSelf => {
+                cv_builder.align_self(computed::AlignSelf::value_default(ctx));
+            }
+            LonghandId::BackgroundClip => {
+                cv_builder.background_clip(computed::BackgroundClip::value_default(ctx));
+            }
             LonghandId::BackgroundColor => {
                 cv_builder.background_color(specified::BackgroundColor::value_default(ctx));
             }
+            LonghandId::BackgroundImage => {
+                cv_builder.background_image(specified::BackgroundImage::value_default(ctx));
+            }
+            LonghandId::BackgroundOrigin => {
+                cv_builder.background_origin(computed::BackgroundOrigin::value_default(ctx));
+            }
+            LonghandId::BackgroundPositionX => {
+                cv_builder
+                    .background_position_x(specified::BackgroundPositionX::value_default(ctx));
+            }
+            LonghandId::BackgroundPositionY => {
+                cv_builder
+                    .background_position_y(specified::BackgroundPositionY::value_default(ctx));
+            }
+            LonghandId::BackgroundRepeat => {
+                cv_builder.background_repeat(computed::BackgroundRepeat::value_default(ctx));
+            }
+            LonghandId::BackgroundSize => {
+                cv_builder.background_size(specified::BackgroundSize::value_default(ctx));
+            }
             LonghandId::BorderBottomColor => {
                 cv_builder.border_bottom_color(specified::BorderBottomColor::value_default(ctx));
             }
+            LonghandId::BorderCollapse => {
+                cv_builder.border_collapse(computed::BorderCollapse::value_default(ctx));
+            }
             LonghandId::BorderLeftColor => {
                 cv_builder.border_left_color(specified::BorderLeftColor::value_default(ctx));
             }
@@ -475,21 +646,122 @@ impl LonghandId {
             LonghandId::BorderTopWidth => {
                 cv_builder.border_top_width(specified::BorderTopWidth::value_default(ctx));
             }
+            LonghandId::BorderBottomLeftRadius => {
+                cv_builder
+                    .border_bottom_left_radius(specified::BorderCornerRadius::value_default(ctx));
+            }
+            LonghandId::BorderBottomRightRadius => {
+                cv_builder
+                    .border_bottom_right_radius(specified::BorderCornerRadius::value_default(ctx));
+            }
+            LonghandId::BorderTopLeftRadius => {
+                cv_builder
+                    .border_top_left_radius(specified::BorderCornerRadius::value_default(ctx));
+            }
+            LonghandId::BorderTopRightRadius => {
+                cv_builder
+                    .border_top_right_radius(specified::BorderCornerRadius::value_default(ctx));
+            }
             LonghandId::Color => {
                 cv_builder.color(specified::Color::value_default(ctx));
             }
+            LonghandId::ColumnGap => {
+                cv_builder.column_gap(specified::Gap::value_default(ctx));
+            }
+            LonghandId::BorderSpacing => {
+                cv_builder.border_spacing(specified::BorderSpacing::value_default(ctx));
+            }
+            LonghandId::Bottom => {
+                cv_builder.bottom(specified::Bottom::value_default(ctx));
+            }
+            LonghandId::BoxShadow => {
+                cv_builder.box_shadow(specified::BoxShadow::value_default(ctx));
+            }
+            LonghandId::BoxSizing => {
+                cv_builder.box_sizing(computed::BoxSizing::value_default(ctx));
+            }
+            LonghandId::BreakAfter => {
+                cv_builder.break_after(computed::BreakBetween::value_default(ctx));
+            }
+            LonghandId::BreakBefore => {
+                cv_builder.break_before(computed::BreakBetween::value_default(ctx));
+            }
+            LonghandId::BreakInside => {
+                cv_builder.break_inside(computed::BreakInside::value_default(ctx));
+            }
+            LonghandId::CaptionSide => {
+                cv_builder.caption_side(computed::CaptionSide::value_default(ctx));
+            }
+            LonghandId::Clear => {
+                cv_builder.clear(computed::Clear::value_default(ctx));
+            }
             LonghandId::Direction => {
                 cv_builder.direction(computed::Direction::value_default(ctx));
             }
             LonghandId::Display => {
                 cv_builder.display(computed::Display::value_default(ctx));
             }
+            LonghandId::Float => {
+                cv_builder.float(computed::Float::value_default(ctx));
+            }
+            LonghandId::FlexBasis => {
+                cv_builder.flex_basis(specified::FlexBasis::value_default(ctx));
+            }
+            LonghandId::FlexDirection => {
+                cv_builder.flex_direction(computed::FlexDirection::value_default(ctx));
+            }
+            LonghandId::FlexGrow => {
+                cv_builder.flex_grow(computed::FlexGrow::value_default(ctx));
+            }
+            LonghandId::FlexShrink => {
+                cv_builder.flex_shrink(computed::FlexShrink::value_default(ctx));
+            }
+            LonghandId::FlexWrap => {
+                cv_builder.flex_wrap(computed::FlexWrap::value_default(ctx));
+            }
             LonghandId::FontSize => {
                 cv_builder.font_size(specified::FontSize::value_default(ctx));
             }
+            LonghandId::GridAutoColumns => {
+                cv_builder.grid_auto_columns(specified::ImplicitGridTracks::value_default(ctx));
+            }
+            LonghandId::GridAutoFlow => {
+                cv_builder.grid_auto_flow(computed::GridAutoFlow::value_default(ctx));
+            }
+            LonghandId::GridAutoRows => {
+                cv_builder.grid_auto_rows(specified::ImplicitGridTracks::value_default(ctx));
+            }
+            LonghandId::GridColumnEnd => {
+                cv_builder.grid_column_end(computed::GridLine::value_default(ctx));
+            }
+            LonghandId::GridColumnStart => {
+                cv_builder.grid_column_start(computed::GridLine::value_default(ctx));
+            }
+            LonghandId::GridRowEnd => {
+                cv_builder.grid_row_end(computed::GridLine::value_default(ctx));
+            }
+            LonghandId::GridRowStart => {
+                cv_builder.grid_row_start(computed::GridLine::value_default(ctx));
+            }
+            LonghandId::GridTemplateAreas => {
+                cv_builder.grid_template_areas(computed::GridTemplateAreas::value_default(ctx));
+            }
+            LonghandId::GridTemplateColumns => {
+                cv_builder
+                    .grid_template_columns(specified::GridTemplateComponent::value_default(ctx));
+            }
+            LonghandId::GridTemplateRows => {
+                cv_builder.grid_template_rows(specified::GridTemplateComponent::value_default(ctx));
+            }
             LonghandId::Height => {
                 cv_builder.height(specified::Height::value_default(ctx));
             }
+            LonghandId::JustifyContent => {
+                cv_builder.justify_content(computed::JustifyContent::value_default(ctx));
+            }
+            LonghandId::Left => {
+                cv_builder.left(specified::Left::value_default(ctx));
+            }
             LonghandId::MarginBottom => {
                 cv_builder.margin_bottom(specified::MarginBottom::value_default(ctx));
             }
@@ -502,6 +774,30 @@ impl LonghandId {
             LonghandId::MarginTop => {
                 cv_builder.margin_top(specified::MarginTop::value_default(ctx));
             }
+            LonghandId::MaxHeight => {
+                cv_builder.max_height(specified::MaxHeight::value_default(ctx));
+            }
+            LonghandId::MaxWidth => {
+                cv_builder.max_width(specified::MaxWidth::value_default(ctx));
+            }
+            LonghandId::MinHeight => {
+                cv_builder.min_height(specified::MinHeight::value_default(ctx));
+            }
+            LonghandId::MinWidth => {
+                cv_builder.min_width(specified::MinWidth::value_default(ctx));
+            }
+            LonghandId::Opacity => {
+                cv_builder.opacity(computed::Opacity::value_default(ctx));
+            }
+            LonghandId::Order => {
+                cv_builder.order(computed::Order::value_default(ctx));
+            }
+            LonghandId::OverflowX => {
+                cv_builder.overflow_x(ctx.overflow_x());
+            }
+            LonghandId::OverflowY => {
+                cv_builder.overflow_y(ctx.overflow_y());
+            }
             LonghandId::PaddingBottom => {
                 cv_builder.padding_bottom(specified::PaddingBottom::value_default(ctx));
             }
@@ -514,12 +810,70 @@ impl LonghandId {
             LonghandId::PaddingTop => {
                 cv_builder.padding_top(specified::PaddingTop::value_default(ctx));
             }
+            LonghandId::Position => {
+                cv_builder.position(computed::Position::value_default(ctx));
+            }
+            LonghandId::Right => {
+                cv_builder.right(specified::Right::value_default(ctx));
+            }
+            LonghandId::RowGap => {
+                cv_builder.row_gap(specified::Gap::value_default(ctx));
+            }
+            LonghandId::TableLayout => {
+                cv_builder.table_layout(computed::TableLayout::value_default(ctx));
+            }
+            LonghandId::TextShadow => {
+                cv_builder.text_shadow(specified::TextShadow::value_default(ctx));
+            }
+            LonghandId::Top => {
+                cv_builder.top(specified::Top::value_default(ctx));
+            }
             LonghandId::Width => {
                 cv_builder.width(specified::Width::value_default(ctx));
             }
             LonghandId::WritingMode => {
                 cv_builder.writing_mode(computed::WritingMode::value_default(ctx));
             }
+            LonghandId::ZIndex => {
+                cv_builder.z_index(computed::ZIndex::value_default(ctx));
+            }
+            // Flow-relative properties have no computed values of their own.  They're mapped to the
+            // physical properties they share a value with before the cascade, so those are defaulted
+            // instead.
+            LonghandId::BlockSize
+            | LonghandId::BorderBlockEndColor
+            | LonghandId::BorderBlockEndStyle
+            | LonghandId::BorderBlockEndWidth
+            | LonghandId::BorderBlockStartColor
+            | LonghandId::BorderBlockStartStyle
+            | LonghandId::BorderBlockStartWidth
+            | LonghandId::BorderEndEndRadius
+            | LonghandId::BorderEndStartRadius
+            | LonghandId::BorderInlineEndColor
+            | LonghandId::BorderInlineEndStyle
+            | LonghandId::BorderInlineEndWidth
+            | LonghandId::BorderInlineStartColor
+            | LonghandId::BorderInlineStartStyle
+            | LonghandId::BorderInlineStartWidth
+            | LonghandId::BorderStartEndRadius
+            | LonghandId::BorderStartStartRadius
+            | LonghandId::InlineSize
+            | LonghandId::InsetBlockEnd
+            | LonghandId::InsetBlockStart
+            | LonghandId::InsetInlineEnd
+            | LonghandId::InsetInlineStart
+            | LonghandId::MarginBlockEnd
+            | LonghandId::MarginBlockStart
+            | LonghandId::MarginInlineEnd
+            | LonghandId::MarginInlineStart
+            | LonghandId::MaxBlockSize
+            | LonghandId::MaxInlineSize
+            | LonghandId::MinBlockSize
+            | LonghandId::MinInlineSize
+            | LonghandId::PaddingBlockEnd
+            | LonghandId::PaddingBlockStart
+            | LonghandId::PaddingInlineEnd
+            | LonghandId::PaddingInlineStart => {}
             _ => unimplemented!(
                 "{}",
                 format!("value default by longhand for id: {:?}", self)
@@ -531,8 +885,32 @@ impl LonghandId {
 impl From<&PropertyDeclaration> for LonghandId {
     fn from(prop_decl: &PropertyDeclaration) -> Self {
         match prop_decl {
+            PropertyDeclaration::AlignContent(_) => LonghandId::AlignContent,
+            PropertyDeclaration::AlignItems(_) => LonghandId::AlignItems,
+            PropertyDeclaration::AlignSelf(_) => LonghandId::AlignSelf,
+            PropertyDeclaration::BackgroundClip(_) => LonghandId::BackgroundClip,
             PropertyDeclaration::BackgroundColor(_) => LonghandId::BackgroundColor,
+            PropertyDeclaration::BackgroundImage(_) => LonghandId::BackgroundImage,
+            PropertyDeclaration::BackgroundOrigin(_) => LonghandId::BackgroundOrigin,
+            PropertyDeclaration::BackgroundPositionX(_) => LonghandId::BackgroundPositionX,
+            PropertyDeclaration::BackgroundPositionY(_) => LonghandId::BackgroundPositionY,
+            PropertyDeclaration::BackgroundRepeat(_) => LonghandId::BackgroundRepeat,
+            PropertyDeclaration::BackgroundSize(_) => LonghandId::BackgroundSize,
+            PropertyDeclaration::BlockSize(_) => LonghandId::BlockSize,
+            PropertyDeclaration::BorderBlockEndColor(_) => LonghandId::BorderBlockEndColor,
+            PropertyDeclaration::BorderBlockEndStyle(_) => LonghandId::BorderBlockEndStyle,
+            PropertyDeclaration::BorderBlockEndWidth(_) => LonghandId::BorderBlockEndWidth,
+            PropertyDeclaration::BorderBlockStartColor(_) => LonghandId::BorderBlockStartColor,
+            PropertyDeclaration::BorderBlockStartStyle(_) => LonghandId::BorderBlockStartStyle,
+            PropertyDeclaration::BorderBlockStartWidth(_) => LonghandId::BorderBlockStartWidth,
             PropertyDeclaration::BorderBottomColor(_) => LonghandId::BorderBottomColor,
+            PropertyDeclaration::BorderCollapse(_) => LonghandId::BorderCollapse,
+            PropertyDeclaration::BorderInlineEndColor(_) => LonghandId::BorderInlineEndColor,
+            PropertyDeclaration::BorderInlineEndStyle(_) => LonghandId::BorderInlineEndStyle,
+            PropertyDeclaration::BorderInlineEndWidth(_) => LonghandId::BorderInlineEndWidth,
+            PropertyDeclaration::BorderInlineStartColor(_) => LonghandId::BorderInlineStartColor,
+            PropertyDeclaration::BorderInlineStartStyle(_) => LonghandId::BorderInlineStartStyle,
+            PropertyDeclaration::BorderInlineStartWidth(_) => LonghandId::BorderInlineStartWidth,
             PropertyDeclaration::BorderLeftColor(_) => LonghandId::BorderLeftColor,
             PropertyDeclaration::BorderRightColor(_) => LonghandId::BorderRightColor,
             PropertyDeclaration::BorderTopColor(_) => LonghandId::BorderTopColor,
@@ -544,21 +922,89 @@ impl From<&PropertyDeclaration> for LonghandId {
             PropertyDeclaration::BorderLeftWidth(_) => LonghandId::BorderLeftWidth,
             PropertyDeclaration::BorderRightWidth(_) => LonghandId::BorderRightWidth,
             PropertyDeclaration::BorderTopWidth(_) => LonghandId::BorderTopWidth,
+            PropertyDeclaration::BorderBottomLeftRadius(_) => LonghandId::BorderBottomLeftRadius,
+            PropertyDeclaration::BorderBottomRightRadius(_) => LonghandId::BorderBottomRightRadius,
+            PropertyDeclaration::BorderEndEndRadius(_) => LonghandId::BorderEndEndRadius,
+            PropertyDeclaration::BorderEndStartRadius(_) => LonghandId::BorderEndStartRadius,
+            PropertyDeclaration::BorderStartEndRadius(_) => LonghandId::BorderStartEndRadius,
+            PropertyDeclaration::BorderStartStartRadius(_) => LonghandId::BorderStartStartRadius,
+            PropertyDeclaration::BorderTopLeftRadius(_) => LonghandId::BorderTopLeftRadius,
+            PropertyDeclaration::BorderTopRightRadius(_) => LonghandId::BorderTopRightRadius,
             PropertyDeclaration::Color(_) => LonghandId::Color,
+            PropertyDeclaration::ColumnGap(_) => LonghandId::ColumnGap,
+            PropertyDeclaration::BorderSpacing(_) => LonghandId::BorderSpacing,
+            PropertyDeclaration::Bottom(_) => LonghandId::Bottom,
+            PropertyDeclaration::BoxShadow(_) => LonghandId::BoxShadow,
+            PropertyDeclaration::BoxSizing(_) => LonghandId::BoxSizing,
+            PropertyDeclaration::BreakAfter(_) => LonghandId::BreakAfter,
+            PropertyDeclaration::BreakBefore(_) => LonghandId::BreakBefore,
+            PropertyDeclaration::BreakInside(_) => LonghandId::BreakInside,
+            PropertyDeclaration::CaptionSide(_) => LonghandId::CaptionSide,
+            PropertyDeclaration::Clear(_) => LonghandId::Clear,
             PropertyDeclaration::Direction(_) => LonghandId::Direction,
             PropertyDeclaration::Display(_) => LonghandId::Display,
+            PropertyDeclaration::FlexBasis(_) => LonghandId::FlexBasis,
+            PropertyDeclaration::FlexDirection(_) => LonghandId::FlexDirection,
+            PropertyDeclaration::FlexGrow(_) => LonghandId::FlexGrow,
+            PropertyDeclaration::FlexShrink(_) => LonghandId::FlexShrink,
+            PropertyDeclaration::FlexWrap(_) => LonghandId::FlexWrap,
+            PropertyDeclaration::Float(_) => LonghandId::Float,
             PropertyDeclaration::FontSize(_) => LonghandId::FontSize,
+            PropertyDeclaration::GridAutoColumns(_) => LonghandId::GridAutoColumns,
+            PropertyDeclaration::GridAutoFlow(_) => LonghandId::GridAutoFlow,
+            PropertyDeclaration::GridAutoRows(_) => LonghandId::GridAutoRows,
+            PropertyDeclaration::GridColumnEnd(_) => LonghandId::GridColumnEnd,
+            PropertyDeclaration::GridColumnStart(_) => LonghandId::GridColumnStart,
+            PropertyDeclaration::GridRowEnd(_) => LonghandId::GridRowEnd,
+            PropertyDeclaration::GridRowStart(_) => LonghandId::GridRowStart,
+            PropertyDeclaration::GridTemplateAreas(_) => LonghandId::GridTemplateAreas,
+            PropertyDeclaration::GridTemplateColumns(_) => LonghandId::GridTemplateColumns,
+            PropertyDeclaration::GridTemplateRows(_) => LonghandId::GridTemplateRows,
             PropertyDeclaration::Height(_) => LonghandId::Height,
+            PropertyDeclaration::InlineSize(_) => LonghandId::InlineSize,
+            PropertyDeclaration::InsetBlockEnd(_) => LonghandId::InsetBlockEnd,
+            PropertyDeclaration::InsetBlockStart(_) => LonghandId::InsetBlockStart,
+            PropertyDeclaration::InsetInlineEnd(_) => LonghandId::InsetInlineEnd,
+            PropertyDeclaration::InsetInlineStart(_) => LonghandId::InsetInlineStart,
+            PropertyDeclaration::JustifyContent(_) => LonghandId::JustifyContent,
+            PropertyDeclaration::Left(_) => LonghandId::Left,
+            PropertyDeclaration::MarginBlockEnd(_) => LonghandId::MarginBlockEnd,
+            PropertyDeclaration::MarginBlockStart(_) => LonghandId::MarginBlockStart,
             PropertyDeclaration::MarginBottom(_) => LonghandId::MarginBottom,
+            PropertyDeclaration::MarginInlineEnd(_) => LonghandId::MarginInlineEnd,
+            PropertyDeclaration::MarginInlineStart(_) => LonghandId::MarginInlineStart,
             PropertyDeclaration::MarginLeft(_) => LonghandId::MarginLeft,
             PropertyDeclaration::MarginRight(_) => LonghandId::MarginRight,
             PropertyDeclaration::MarginTop(_) => LonghandId::MarginTop,
+            PropertyDeclaration::MaxBlockSize(_) => LonghandId::MaxBlockSize,
+            PropertyDeclaration::MaxHeight(_) => LonghandId::MaxHeight,
+            PropertyDeclaration::MaxInlineSize(_) => LonghandId::MaxInlineSize,
+            PropertyDeclaration::MaxWidth(_) => LonghandId::MaxWidth,
+            PropertyDeclaration::MinBlockSize(_) => LonghandId::MinBlockSize,
+            PropertyDeclaration::MinHeight(_) => LonghandId::MinHeight,
+            PropertyDeclaration::MinInlineSize(_) => LonghandId::MinInlineSize,
+            PropertyDeclaration::MinWidth(_) => LonghandId::MinWidth,
+            PropertyDeclaration::Opacity(_) => LonghandId::Opacity,
+            PropertyDeclaration::Order(_) => LonghandId::Order,
+            PropertyDeclaration::OverflowX(_) => LonghandId::OverflowX,
+            PropertyDeclaration::OverflowY(_) => LonghandId::OverflowY,
+            PropertyDeclaration::PaddingBlockEnd(_) => LonghandId::PaddingBlockEnd,
+            PropertyDeclaration::PaddingBlockStart(_) => LonghandId::PaddingBlockStart,
             PropertyDeclaration::PaddingBottom(_) => LonghandId::PaddingBottom,
+            PropertyDeclaration::PaddingInlineEnd(_) => LonghandId::PaddingInlineEnd,
+            PropertyDeclaration::PaddingInlineStart(_) => LonghandId::PaddingInlineStart,
             PropertyDeclaration::PaddingLeft(_) => LonghandId::PaddingLeft,
             PropertyDeclaration::PaddingRight(_) => LonghandId::PaddingRight,
             PropertyDeclaration::PaddingTop(_) => LonghandId::PaddingTop,
+            PropertyDeclaration::Position(_) => LonghandId::Position,
+            PropertyDeclaration::Right(_) => LonghandId::Right,
+            PropertyDeclaration::RowGap(_) => LonghandId::RowGap,
+            PropertyDeclaration::TableLayout(_) => LonghandId::TableLayout,
+            PropertyDeclaration::TextShadow(_) => LonghandId::TextShadow,
+            PropertyDeclaration::Top(_) => LonghandId::Top,
             PropertyDeclaration::Width(_) => LonghandId::Width,
             PropertyDeclaration::WritingMode(_) => LonghandId::WritingMode,
+            PropertyDeclaration::ZIndex(_) => LonghandId::ZIndex,
         }
     }
 }
@@ -588,8 +1034,8 @@ impl From<ContextualPropertyDeclaration> for LonghandId {
 pub enum ShorthandId {
     /// background
     Background = 0,
-    //    /// background-position
-    //    BackgroundPosition = 1,
+    /// background-position
+    BackgroundPosition = 1,
     //    /// border-color
     //    BorderColor = 2,
     //    /// border-style
@@ -614,8 +1060,8 @@ pub enum ShorthandId {
     //    BorderInlineEnd = 12,
     /// border
     Border = 13,
-    //    /// border-radius
-    //    BorderRadius = 14,
+    /// border-radius
+    BorderRadius = 14,
     //    /// border-image
     //    BorderImage = 15,
     //    /// border-block-width
@@ -634,8 +1080,8 @@ pub enum ShorthandId {
     //    BorderBlock = 22,
     //    /// border-inline
     //    BorderInline = 23,
-    //    /// overflow
-    //    Overflow = 24,
+    /// overflow
+    Overflow = 24,
     //    /// transition
     //    Transition = 25,
     //    /// animation
@@ -650,30 +1096,38 @@ pub enum ShorthandId {
     //    ListStyle = 30,
     /// margin
     Margin = 31,
-    //    /// margin-block
-    //    MarginBlock = 32,
-    //    /// margin-inline
-    //    MarginInline = 33,
+    /// margin-block
+    MarginBlock = 32,
+    /// margin-inline
+    MarginInline = 33,
     //    /// outline
     //    Outline = 34,
     /// padding
     Padding = 35,
-    //    /// padding-block
-    //    PaddingBlock = 36,
-    //    /// padding-inline
-    //    PaddingInline = 37,
-    //    /// flex-flow
-    //    FlexFlow = 38,
-    //    /// flex
-    //    Flex = 39,
+    /// padding-block
+    PaddingBlock = 36,
+    /// padding-inline
+    PaddingInline = 37,
+    /// flex-flow
+    FlexFlow = 38,
+    /// flex
+    Flex = 39,
     //    /// inset
     //    Inset = 40,
-    //    /// inset-block
-    //    InsetBlock = 41,
-    //    /// inset-inline
-    //    InsetInline = 42,
+    /// inset-block
+    InsetBlock = 41,
+    /// inset-inline
+    InsetInline = 42,
     //    /// text-decoration
     //    TextDecoration = 43,
     //    /// all
     //    All = 44,
+    /// gap
+    Gap = 45,
+    /// grid-row
+    GridRow = 46,
+    /// grid-column
+    GridColumn = 47,
+    /// grid-area
+    GridArea = 48,
 }
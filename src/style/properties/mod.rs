@@ -8,20 +8,32 @@ use cssparser::{
 };
 use smallbitvec::SmallBitVec;
 
-use crate::style::properties::id::{LonghandId, PropertyId};
+use crate::style::properties::id::{LonghandId, PropertyId, ShorthandId};
 use crate::style::select::Specificity;
 use crate::style::values::computed::direction::WritingMode;
-use crate::style::values::computed::{Direction, Display, LineStyle};
+use crate::style::values::computed::{
+    AlignContent, AlignItems, AlignSelf, BackgroundClip, BackgroundOrigin, BackgroundRepeat,
+    BorderCollapse, BoxSizing, BreakBetween, BreakInside, CaptionSide, Clear, Direction, Display,
+    FlexDirection, FlexGrow, FlexShrink, FlexWrap, Float, GridAutoFlow, GridLine,
+    GridTemplateAreas, JustifyContent, LineStyle, Opacity, Order, Overflow, Position, TableLayout,
+    ZIndex,
+};
 use crate::style::values::specified::border::{
-    BorderBottomColor, BorderLeftColor, BorderRightColor, BorderTopColor,
+    BorderBottomColor, BorderLeftColor, BorderRightColor, BorderTopColor, LineWidth,
 };
 use crate::style::values::specified::{
-    BackgroundColor, BorderBottomWidth, BorderLeftWidth, BorderRightWidth, BorderTopWidth, Color,
-    FontSize, Height, MarginBottom, MarginLeft, MarginRight, MarginTop, PaddingBottom, PaddingLeft,
-    PaddingRight, PaddingTop, Width,
+    Background, BackgroundColor, BackgroundImage, BackgroundPosition, BackgroundPositionX,
+    BackgroundPositionY, BackgroundSize, BorderBottomWidth, BorderCornerRadius, BorderLeftWidth,
+    BorderRadius, BorderRightWidth, BorderSpacing, BorderTopWidth, Bottom, BoxShadow, Color,
+    ColorUnit, Flex, FlexBasis, FlexFlow, FontSize, Gap, GridArea, GridLinePair,
+    GridTemplateComponent, Height, ImplicitGridTracks, Left, LengthPercentage,
+    LengthPercentageOrAuto, MarginBottom, MarginLeft, MarginRight, MarginTop, MaxHeight, MaxSize,
+    MaxWidth, MinHeight, MinWidth, PaddingBottom, PaddingLeft, PaddingRight, PaddingTop, Right,
+    Size, TextShadow, Top, Width,
 };
 use crate::style::CascadeOrigin;
 use crate::style::{CssOrigin, StyleParseErrorKind};
+use crate::Side;
 use std::mem::discriminant;
 
 pub mod id;
@@ -109,29 +121,26 @@ pub struct PropertyDeclarationBlock {
 impl PropertyDeclarationBlock {
     /// Adds a new declaration to the block, de-duping with any existing property declarations
     /// of the same type.
-    pub fn add_declaration(
-        &mut self,
-        mut new_decl: PropertyDeclaration,
-        new_importance: Importance,
-    ) {
-        let mut swap_index = None;
+    pub fn add_declaration(&mut self, new_decl: PropertyDeclaration, new_importance: Importance) {
+        let mut existing_index = None;
         for (i, existing_decl) in self.declarations.iter().enumerate() {
             if mem::discriminant(existing_decl) == mem::discriminant(&new_decl) {
                 // the props are the same "type", e.g. both `font-size, both `display`, etc
                 // take the `new_decl`, since the latest/newest prop should always be taken
-                swap_index = Some(i);
+                existing_index = Some(i);
             }
         }
 
-        if let Some(idx) = swap_index {
-            mem::swap(&mut self.declarations[idx], &mut new_decl);
-            self.declarations_importance
-                .set(idx, new_importance.important());
-        } else {
-            self.declarations.push(new_decl);
-            self.declarations_importance
-                .push(new_importance.important());
+        // Remove the existing declaration rather than replacing it in place, since the new one
+        // must keep its order of appearance relative to declarations of other properties in the
+        // same logical property group, e.g. `margin-left` and `margin-inline-start`.
+        if let Some(idx) = existing_index {
+            self.declarations.remove(idx);
+            self.declarations_importance.remove(idx);
         }
+        self.declarations.push(new_decl);
+        self.declarations_importance
+            .push(new_importance.important());
     }
 }
 
@@ -162,12 +171,108 @@ impl PropertyDeclaration {
     ) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
         match id {
             PropertyId::Longhand(longhand) => match longhand {
+                LonghandId::AlignContent => declarations.push(PropertyDeclaration::AlignContent(
+                    AlignContent::parse(input)?,
+                )),
+                LonghandId::AlignItems => {
+                    declarations.push(PropertyDeclaration::AlignItems(AlignItems::parse(input)?))
+                }
+                LonghandId::AlignSelf => {
+                    declarations.push(PropertyDeclaration::AlignSelf(AlignSelf::parse(input)?))
+                }
+                LonghandId::BackgroundClip => declarations.push(
+                    PropertyDeclaration::BackgroundClip(BackgroundClip::parse(input)?),
+                ),
                 LonghandId::BackgroundColor => declarations.push(
                     PropertyDeclaration::BackgroundColor(BackgroundColor::parse(input)?),
                 ),
+                LonghandId::BackgroundImage => declarations.push(
+                    PropertyDeclaration::BackgroundImage(BackgroundImage::parse(input)?),
+                ),
+                LonghandId::BackgroundOrigin => declarations.push(
+                    PropertyDeclaration::BackgroundOrigin(BackgroundOrigin::parse(input)?),
+                ),
+                LonghandId::BackgroundPositionX => declarations.push(
+                    PropertyDeclaration::BackgroundPositionX(BackgroundPositionX::parse(input)?),
+                ),
+                LonghandId::BackgroundPositionY => declarations.push(
+                    PropertyDeclaration::BackgroundPositionY(BackgroundPositionY::parse(input)?),
+                ),
+                LonghandId::BackgroundRepeat => declarations.push(
+                    PropertyDeclaration::BackgroundRepeat(BackgroundRepeat::parse(input)?),
+                ),
+                LonghandId::BackgroundSize => declarations.push(
+                    PropertyDeclaration::BackgroundSize(BackgroundSize::parse(input)?),
+                ),
+                LonghandId::BlockSize => {
+                    declarations.push(PropertyDeclaration::BlockSize(Size::parse(input)?));
+                }
+                LonghandId::BorderBlockEndColor => {
+                    declarations.push(PropertyDeclaration::BorderBlockEndColor(ColorUnit::parse(
+                        input,
+                    )?));
+                }
+                LonghandId::BorderBlockEndStyle => {
+                    declarations.push(PropertyDeclaration::BorderBlockEndStyle(LineStyle::parse(
+                        input,
+                    )?));
+                }
+                LonghandId::BorderBlockEndWidth => {
+                    declarations.push(PropertyDeclaration::BorderBlockEndWidth(LineWidth::parse(
+                        input,
+                    )?));
+                }
+                LonghandId::BorderBlockStartColor => {
+                    declarations.push(PropertyDeclaration::BorderBlockStartColor(
+                        ColorUnit::parse(input)?,
+                    ));
+                }
+                LonghandId::BorderBlockStartStyle => {
+                    declarations.push(PropertyDeclaration::BorderBlockStartStyle(
+                        LineStyle::parse(input)?,
+                    ));
+                }
+                LonghandId::BorderBlockStartWidth => {
+                    declarations.push(PropertyDeclaration::BorderBlockStartWidth(
+                        LineWidth::parse(input)?,
+                    ));
+                }
                 LonghandId::BorderBottomColor => declarations.push(
                     PropertyDeclaration::BorderBottomColor(BorderBottomColor::parse(input)?),
                 ),
+                LonghandId::BorderCollapse => declarations.push(
+                    PropertyDeclaration::BorderCollapse(BorderCollapse::parse(input)?),
+                ),
+                LonghandId::BorderInlineEndColor => {
+                    declarations.push(PropertyDeclaration::BorderInlineEndColor(ColorUnit::parse(
+                        input,
+                    )?));
+                }
+                LonghandId::BorderInlineEndStyle => {
+                    declarations.push(PropertyDeclaration::BorderInlineEndStyle(LineStyle::parse(
+                        input,
+                    )?));
+                }
+                LonghandId::BorderInlineEndWidth => {
+                    declarations.push(PropertyDeclaration::BorderInlineEndWidth(LineWidth::parse(
+                        input,
+                    )?));
+                }
+                LonghandId::BorderInlineStartColor => {
+                    declarations.push(PropertyDeclaration::BorderInlineStartColor(
+                        ColorUnit::parse(input)?,
+                    ));
+                }
+                LonghandId::BorderInlineStartStyle => {
+                    declarations.push(PropertyDeclaration::BorderInlineStartStyle(
+                        LineStyle::parse(input)?,
+                    ));
+                }
+                LonghandId::BorderInlineStartWidth => {
+                    declarations.push(PropertyDeclaration::BorderInlineStartWidth(
+                        LineWidth::parse(input)?,
+                    ));
+                }
                 LonghandId::BorderLeftColor => declarations.push(
                     PropertyDeclaration::BorderLeftColor(BorderLeftColor::parse(input)?),
                 ),
@@ -201,26 +306,177 @@ impl PropertyDeclaration {
                 LonghandId::BorderTopWidth => declarations.push(
                     PropertyDeclaration::BorderTopWidth(BorderTopWidth::parse(input)?),
                 ),
+                LonghandId::BorderBottomLeftRadius => declarations.push(
+                    PropertyDeclaration::BorderBottomLeftRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderBottomRightRadius => declarations.push(
+                    PropertyDeclaration::BorderBottomRightRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderEndEndRadius => declarations.push(
+                    PropertyDeclaration::BorderEndEndRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderEndStartRadius => declarations.push(
+                    PropertyDeclaration::BorderEndStartRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderStartEndRadius => declarations.push(
+                    PropertyDeclaration::BorderStartEndRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderStartStartRadius => declarations.push(
+                    PropertyDeclaration::BorderStartStartRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderTopLeftRadius => declarations.push(
+                    PropertyDeclaration::BorderTopLeftRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderTopRightRadius => declarations.push(
+                    PropertyDeclaration::BorderTopRightRadius(BorderCornerRadius::parse(input)?),
+                ),
+                LonghandId::BorderSpacing => declarations.push(PropertyDeclaration::BorderSpacing(
+                    BorderSpacing::parse(input)?,
+                )),
+                LonghandId::Bottom => {
+                    declarations.push(PropertyDeclaration::Bottom(Bottom::parse(input)?));
+                }
+                LonghandId::BoxShadow => {
+                    declarations.push(PropertyDeclaration::BoxShadow(BoxShadow::parse(input)?))
+                }
+                LonghandId::BoxSizing => {
+                    declarations.push(PropertyDeclaration::BoxSizing(BoxSizing::parse(input)?))
+                }
+                LonghandId::BreakAfter => {
+                    declarations.push(PropertyDeclaration::BreakAfter(BreakBetween::parse(input)?))
+                }
+                LonghandId::BreakBefore => declarations.push(PropertyDeclaration::BreakBefore(
+                    BreakBetween::parse(input)?,
+                )),
+                LonghandId::BreakInside => {
+                    declarations.push(PropertyDeclaration::BreakInside(BreakInside::parse(input)?))
+                }
+                LonghandId::CaptionSide => {
+                    declarations.push(PropertyDeclaration::CaptionSide(CaptionSide::parse(input)?))
+                }
+                LonghandId::Clear => {
+                    declarations.push(PropertyDeclaration::Clear(Clear::parse(input)?))
+                }
                 LonghandId::Color => {
                     declarations.push(PropertyDeclaration::Color(Color::parse(input)?))
                 }
+                LonghandId::ColumnGap => {
+                    declarations.push(PropertyDeclaration::ColumnGap(Gap::parse(input)?))
+                }
                 LonghandId::Direction => {
                     declarations.push(PropertyDeclaration::Direction(Direction::parse(input)?))
                 }
                 LonghandId::Display => {
                     declarations.push(PropertyDeclaration::Display(Display::parse(input)?))
                 }
+                LonghandId::Float => {
+                    declarations.push(PropertyDeclaration::Float(Float::parse(input)?))
+                }
+                LonghandId::FlexBasis => {
+                    declarations.push(PropertyDeclaration::FlexBasis(FlexBasis::parse(input)?))
+                }
+                LonghandId::FlexDirection => declarations.push(PropertyDeclaration::FlexDirection(
+                    FlexDirection::parse(input)?,
+                )),
+                LonghandId::FlexGrow => {
+                    declarations.push(PropertyDeclaration::FlexGrow(FlexGrow::parse(input)?))
+                }
+                LonghandId::FlexShrink => {
+                    declarations.push(PropertyDeclaration::FlexShrink(FlexShrink::parse(input)?))
+                }
+                LonghandId::FlexWrap => {
+                    declarations.push(PropertyDeclaration::FlexWrap(FlexWrap::parse(input)?))
+                }
                 LonghandId::FontSize => {
                     declarations.push(PropertyDeclaration::FontSize(FontSize::parse(input)?));
                 }
+                LonghandId::GridAutoColumns => declarations.push(
+                    PropertyDeclaration::GridAutoColumns(ImplicitGridTracks::parse(input)?),
+                ),
+                LonghandId::GridAutoFlow => declarations.push(PropertyDeclaration::GridAutoFlow(
+                    GridAutoFlow::parse(input)?,
+                )),
+                LonghandId::GridAutoRows => declarations.push(PropertyDeclaration::GridAutoRows(
+                    ImplicitGridTracks::parse(input)?,
+                )),
+                LonghandId::GridColumnEnd => {
+                    declarations.push(PropertyDeclaration::GridColumnEnd(GridLine::parse(input)?))
+                }
+                LonghandId::GridColumnStart => declarations.push(
+                    PropertyDeclaration::GridColumnStart(GridLine::parse(input)?),
+                ),
+                LonghandId::GridRowEnd => {
+                    declarations.push(PropertyDeclaration::GridRowEnd(GridLine::parse(input)?))
+                }
+                LonghandId::GridRowStart => {
+                    declarations.push(PropertyDeclaration::GridRowStart(GridLine::parse(input)?))
+                }
+                LonghandId::GridTemplateAreas => declarations.push(
+                    PropertyDeclaration::GridTemplateAreas(GridTemplateAreas::parse(input)?),
+                ),
+                LonghandId::GridTemplateColumns => declarations.push(
+                    PropertyDeclaration::GridTemplateColumns(GridTemplateComponent::parse(input)?),
+                ),
+                LonghandId::GridTemplateRows => declarations.push(
+                    PropertyDeclaration::GridTemplateRows(GridTemplateComponent::parse(input)?),
+                ),
                 LonghandId::Height => {
                     declarations.push(PropertyDeclaration::Height(Height::parse(input)?));
                 }
+                LonghandId::InlineSize => {
+                    declarations.push(PropertyDeclaration::InlineSize(Size::parse(input)?));
+                }
+                LonghandId::InsetBlockEnd => {
+                    declarations.push(PropertyDeclaration::InsetBlockEnd(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
+                LonghandId::InsetBlockStart => {
+                    declarations.push(PropertyDeclaration::InsetBlockStart(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
+                LonghandId::InsetInlineEnd => {
+                    declarations.push(PropertyDeclaration::InsetInlineEnd(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
+                LonghandId::InsetInlineStart => {
+                    declarations.push(PropertyDeclaration::InsetInlineStart(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
+                LonghandId::JustifyContent => declarations.push(
+                    PropertyDeclaration::JustifyContent(JustifyContent::parse(input)?),
+                ),
+                LonghandId::Left => {
+                    declarations.push(PropertyDeclaration::Left(Left::parse(input)?));
+                }
+                LonghandId::MarginBlockEnd => {
+                    declarations.push(PropertyDeclaration::MarginBlockEnd(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
+                LonghandId::MarginBlockStart => {
+                    declarations.push(PropertyDeclaration::MarginBlockStart(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
                 LonghandId::MarginBottom => {
                     declarations.push(PropertyDeclaration::MarginBottom(MarginBottom::parse(
                         input,
                     )?));
                 }
+                LonghandId::MarginInlineEnd => {
+                    declarations.push(PropertyDeclaration::MarginInlineEnd(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
+                LonghandId::MarginInlineStart => {
+                    declarations.push(PropertyDeclaration::MarginInlineStart(
+                        LengthPercentageOrAuto::parse(input)?,
+                    ));
+                }
                 LonghandId::MarginLeft => {
                     declarations.push(PropertyDeclaration::MarginLeft(MarginLeft::parse(input)?));
                 }
@@ -230,11 +486,67 @@ impl PropertyDeclaration {
                 LonghandId::MarginTop => {
                     declarations.push(PropertyDeclaration::MarginTop(MarginTop::parse(input)?));
                 }
+                LonghandId::MaxBlockSize => {
+                    declarations.push(PropertyDeclaration::MaxBlockSize(MaxSize::parse(input)?));
+                }
+                LonghandId::MaxHeight => {
+                    declarations.push(PropertyDeclaration::MaxHeight(MaxHeight::parse(input)?));
+                }
+                LonghandId::MaxInlineSize => {
+                    declarations.push(PropertyDeclaration::MaxInlineSize(MaxSize::parse(input)?));
+                }
+                LonghandId::MaxWidth => {
+                    declarations.push(PropertyDeclaration::MaxWidth(MaxWidth::parse(input)?));
+                }
+                LonghandId::MinBlockSize => {
+                    declarations.push(PropertyDeclaration::MinBlockSize(Size::parse(input)?));
+                }
+                LonghandId::MinHeight => {
+                    declarations.push(PropertyDeclaration::MinHeight(MinHeight::parse(input)?));
+                }
+                LonghandId::MinInlineSize => {
+                    declarations.push(PropertyDeclaration::MinInlineSize(Size::parse(input)?));
+                }
+                LonghandId::MinWidth => {
+                    declarations.push(PropertyDeclaration::MinWidth(MinWidth::parse(input)?));
+                }
+                LonghandId::Opacity => {
+                    declarations.push(PropertyDeclaration::Opacity(Opacity::parse(input)?))
+                }
+                LonghandId::Order => {
+                    declarations.push(PropertyDeclaration::Order(Order::parse(input)?))
+                }
+                LonghandId::OverflowX => {
+                    declarations.push(PropertyDeclaration::OverflowX(Overflow::parse(input)?))
+                }
+                LonghandId::OverflowY => {
+                    declarations.push(PropertyDeclaration::OverflowY(Overflow::parse(input)?))
+                }
+                LonghandId::PaddingBlockEnd => {
+                    declarations.push(PropertyDeclaration::PaddingBlockEnd(
+                        LengthPercentage::parse(input)?,
+                    ));
+                }
+                LonghandId::PaddingBlockStart => {
+                    declarations.push(PropertyDeclaration::PaddingBlockStart(
+                        LengthPercentage::parse(input)?,
+                    ));
+                }
                 LonghandId::PaddingBottom => {
                     declarations.push(PropertyDeclaration::PaddingBottom(PaddingBottom::parse(
                         input,
                     )?));
                 }
+                LonghandId::PaddingInlineEnd => {
+                    declarations.push(PropertyDeclaration::PaddingInlineEnd(
+                        LengthPercentage::parse(input)?,
+                    ));
+                }
+                LonghandId::PaddingInlineStart => {
+                    declarations.push(PropertyDeclaration::PaddingInlineStart(
+                        LengthPercentage::parse(input)?,
+                    ));
+                }
                 LonghandId::PaddingLeft => {
                     declarations.push(PropertyDeclaration::PaddingLeft(PaddingLeft::parse(input)?));
                 }
@@ -246,28 +558,417 @@ impl PropertyDeclaration {
                 LonghandId::PaddingTop => {
                     declarations.push(PropertyDeclaration::PaddingTop(PaddingTop::parse(input)?));
                 }
+                LonghandId::Position => {
+                    declarations.push(PropertyDeclaration::Position(Position::parse(input)?));
+                }
+                LonghandId::Right => {
+                    declarations.push(PropertyDeclaration::Right(Right::parse(input)?));
+                }
+                LonghandId::RowGap => {
+                    declarations.push(PropertyDeclaration::RowGap(Gap::parse(input)?))
+                }
+                LonghandId::TableLayout => {
+                    declarations.push(PropertyDeclaration::TableLayout(TableLayout::parse(input)?))
+                }
+                LonghandId::TextShadow => {
+                    declarations.push(PropertyDeclaration::TextShadow(TextShadow::parse(input)?))
+                }
+                LonghandId::Top => {
+                    declarations.push(PropertyDeclaration::Top(Top::parse(input)?));
+                }
                 LonghandId::Width => {
                     declarations.push(PropertyDeclaration::Width(Width::parse(input)?));
                 }
                 LonghandId::WritingMode => {
                     declarations.push(PropertyDeclaration::WritingMode(WritingMode::parse(input)?));
                 }
+                LonghandId::ZIndex => {
+                    declarations.push(PropertyDeclaration::ZIndex(ZIndex::parse(input)?))
+                }
                 _ => unimplemented!(
                     "{}",
                     format!("value default by longhand for id: {:?}", longhand)
                 ),
             },
-            PropertyId::Shorthand(_short_id) => {}
+            PropertyId::Shorthand(short_id) => match short_id {
+                ShorthandId::Background => {
+                    let background = Background::parse(input)?;
+                    declarations.push(PropertyDeclaration::BackgroundColor(background.color));
+                    declarations.push(PropertyDeclaration::BackgroundImage(background.image));
+                    declarations.push(PropertyDeclaration::BackgroundPositionX(
+                        background.position.x,
+                    ));
+                    declarations.push(PropertyDeclaration::BackgroundPositionY(
+                        background.position.y,
+                    ));
+                    declarations.push(PropertyDeclaration::BackgroundSize(background.size));
+                    declarations.push(PropertyDeclaration::BackgroundRepeat(background.repeat));
+                    declarations.push(PropertyDeclaration::BackgroundOrigin(background.origin));
+                    declarations.push(PropertyDeclaration::BackgroundClip(background.clip));
+                }
+                ShorthandId::BackgroundPosition => {
+                    let position = BackgroundPosition::parse(input)?;
+                    declarations.push(PropertyDeclaration::BackgroundPositionX(position.x));
+                    declarations.push(PropertyDeclaration::BackgroundPositionY(position.y));
+                }
+                ShorthandId::BorderRadius => {
+                    let border_radius = BorderRadius::parse(input)?;
+                    declarations.push(PropertyDeclaration::BorderTopLeftRadius(
+                        border_radius.top_left,
+                    ));
+                    declarations.push(PropertyDeclaration::BorderTopRightRadius(
+                        border_radius.top_right,
+                    ));
+                    declarations.push(PropertyDeclaration::BorderBottomRightRadius(
+                        border_radius.bottom_right,
+                    ));
+                    declarations.push(PropertyDeclaration::BorderBottomLeftRadius(
+                        border_radius.bottom_left,
+                    ));
+                }
+                ShorthandId::Flex => {
+                    let flex = Flex::parse(input)?;
+                    declarations.push(PropertyDeclaration::FlexGrow(flex.grow));
+                    declarations.push(PropertyDeclaration::FlexShrink(flex.shrink));
+                    declarations.push(PropertyDeclaration::FlexBasis(flex.basis));
+                }
+                ShorthandId::FlexFlow => {
+                    let flex_flow = FlexFlow::parse(input)?;
+                    declarations.push(PropertyDeclaration::FlexDirection(flex_flow.direction));
+                    declarations.push(PropertyDeclaration::FlexWrap(flex_flow.wrap));
+                }
+                ShorthandId::Gap => {
+                    let row_gap = Gap::parse(input)?;
+                    let column_gap = input.try_parse(Gap::parse).unwrap_or(row_gap);
+                    declarations.push(PropertyDeclaration::RowGap(row_gap));
+                    declarations.push(PropertyDeclaration::ColumnGap(column_gap));
+                }
+                ShorthandId::GridArea => {
+                    let grid_area = GridArea::parse(input)?;
+                    declarations.push(PropertyDeclaration::GridRowStart(grid_area.row_start));
+                    declarations.push(PropertyDeclaration::GridColumnStart(grid_area.column_start));
+                    declarations.push(PropertyDeclaration::GridRowEnd(grid_area.row_end));
+                    declarations.push(PropertyDeclaration::GridColumnEnd(grid_area.column_end));
+                }
+                ShorthandId::GridColumn => {
+                    let grid_column = GridLinePair::parse(input)?;
+                    declarations.push(PropertyDeclaration::GridColumnStart(grid_column.start));
+                    declarations.push(PropertyDeclaration::GridColumnEnd(grid_column.end));
+                }
+                ShorthandId::GridRow => {
+                    let grid_row = GridLinePair::parse(input)?;
+                    declarations.push(PropertyDeclaration::GridRowStart(grid_row.start));
+                    declarations.push(PropertyDeclaration::GridRowEnd(grid_row.end));
+                }
+                ShorthandId::InsetBlock => {
+                    let start = LengthPercentageOrAuto::parse(input)?;
+                    let end = input
+                        .try_parse(LengthPercentageOrAuto::parse)
+                        .unwrap_or(start);
+                    declarations.push(PropertyDeclaration::InsetBlockStart(start));
+                    declarations.push(PropertyDeclaration::InsetBlockEnd(end));
+                }
+                ShorthandId::InsetInline => {
+                    let start = LengthPercentageOrAuto::parse(input)?;
+                    let end = input
+                        .try_parse(LengthPercentageOrAuto::parse)
+                        .unwrap_or(start);
+                    declarations.push(PropertyDeclaration::InsetInlineStart(start));
+                    declarations.push(PropertyDeclaration::InsetInlineEnd(end));
+                }
+                ShorthandId::MarginBlock => {
+                    let start = LengthPercentageOrAuto::parse(input)?;
+                    let end = input
+                        .try_parse(LengthPercentageOrAuto::parse)
+                        .unwrap_or(start);
+                    declarations.push(PropertyDeclaration::MarginBlockStart(start));
+                    declarations.push(PropertyDeclaration::MarginBlockEnd(end));
+                }
+                ShorthandId::MarginInline => {
+                    let start = LengthPercentageOrAuto::parse(input)?;
+                    let end = input
+                        .try_parse(LengthPercentageOrAuto::parse)
+                        .unwrap_or(start);
+                    declarations.push(PropertyDeclaration::MarginInlineStart(start));
+                    declarations.push(PropertyDeclaration::MarginInlineEnd(end));
+                }
+                ShorthandId::Overflow => {
+                    let overflow_x = Overflow::parse(input)?;
+                    let overflow_y = input.try_parse(Overflow::parse).unwrap_or(overflow_x);
+                    declarations.push(PropertyDeclaration::OverflowX(overflow_x));
+                    declarations.push(PropertyDeclaration::OverflowY(overflow_y));
+                }
+                ShorthandId::PaddingBlock => {
+                    let start = LengthPercentage::parse(input)?;
+                    let end = input.try_parse(LengthPercentage::parse).unwrap_or(start);
+                    declarations.push(PropertyDeclaration::PaddingBlockStart(start));
+                    declarations.push(PropertyDeclaration::PaddingBlockEnd(end));
+                }
+                ShorthandId::PaddingInline => {
+                    let start = LengthPercentage::parse(input)?;
+                    let end = input.try_parse(LengthPercentage::parse).unwrap_or(start);
+                    declarations.push(PropertyDeclaration::PaddingInlineStart(start));
+                    declarations.push(PropertyDeclaration::PaddingInlineEnd(end));
+                }
+                // TODO: Expand the remaining shorthands into their longhands.
+                _ => {}
+            },
         }
         Ok(())
     }
+
+    /// Maps a flow-relative declaration to a declaration of the physical property it sets on an
+    /// element with the given writing mode and direction.  Returns `None` for declarations of
+    /// properties that aren't flow-relative.
+    ///
+    /// https://drafts.csswg.org/css-logical/#box
+    pub fn to_physical(
+        &self,
+        writing_mode: WritingMode,
+        direction: Direction,
+    ) -> Option<PropertyDeclaration> {
+        let side = |flow_relative_side| writing_mode.physical_side(direction, flow_relative_side);
+        let physical_decl = match *self {
+            PropertyDeclaration::BlockSize(size) => {
+                if writing_mode.is_horizontal() {
+                    PropertyDeclaration::Height(size.into())
+                } else {
+                    PropertyDeclaration::Width(size.into())
+                }
+            }
+            PropertyDeclaration::InlineSize(size) => {
+                if writing_mode.is_horizontal() {
+                    PropertyDeclaration::Width(size.into())
+                } else {
+                    PropertyDeclaration::Height(size.into())
+                }
+            }
+            PropertyDeclaration::MaxBlockSize(size) => {
+                if writing_mode.is_horizontal() {
+                    PropertyDeclaration::MaxHeight(size.into())
+                } else {
+                    PropertyDeclaration::MaxWidth(size.into())
+                }
+            }
+            PropertyDeclaration::MaxInlineSize(size) => {
+                if writing_mode.is_horizontal() {
+                    PropertyDeclaration::MaxWidth(size.into())
+                } else {
+                    PropertyDeclaration::MaxHeight(size.into())
+                }
+            }
+            PropertyDeclaration::MinBlockSize(size) => {
+                if writing_mode.is_horizontal() {
+                    PropertyDeclaration::MinHeight(size.into())
+                } else {
+                    PropertyDeclaration::MinWidth(size.into())
+                }
+            }
+            PropertyDeclaration::MinInlineSize(size) => {
+                if writing_mode.is_horizontal() {
+                    PropertyDeclaration::MinWidth(size.into())
+                } else {
+                    PropertyDeclaration::MinHeight(size.into())
+                }
+            }
+            PropertyDeclaration::BorderBlockEndColor(color) => {
+                border_color_declaration(side(Side::Bottom), color)
+            }
+            PropertyDeclaration::BorderBlockEndStyle(line_style) => {
+                border_style_declaration(side(Side::Bottom), line_style)
+            }
+            PropertyDeclaration::BorderBlockEndWidth(line_width) => {
+                border_width_declaration(side(Side::Bottom), line_width)
+            }
+            PropertyDeclaration::BorderBlockStartColor(color) => {
+                border_color_declaration(side(Side::Top), color)
+            }
+            PropertyDeclaration::BorderBlockStartStyle(line_style) => {
+                border_style_declaration(side(Side::Top), line_style)
+            }
+            PropertyDeclaration::BorderBlockStartWidth(line_width) => {
+                border_width_declaration(side(Side::Top), line_width)
+            }
+            PropertyDeclaration::BorderInlineEndColor(color) => {
+                border_color_declaration(side(Side::Right), color)
+            }
+            PropertyDeclaration::BorderInlineEndStyle(line_style) => {
+                border_style_declaration(side(Side::Right), line_style)
+            }
+            PropertyDeclaration::BorderInlineEndWidth(line_width) => {
+                border_width_declaration(side(Side::Right), line_width)
+            }
+            PropertyDeclaration::BorderInlineStartColor(color) => {
+                border_color_declaration(side(Side::Left), color)
+            }
+            PropertyDeclaration::BorderInlineStartStyle(line_style) => {
+                border_style_declaration(side(Side::Left), line_style)
+            }
+            PropertyDeclaration::BorderInlineStartWidth(line_width) => {
+                border_width_declaration(side(Side::Left), line_width)
+            }
+            PropertyDeclaration::BorderEndEndRadius(radius) => {
+                border_radius_declaration(side(Side::Bottom), side(Side::Right), radius)
+            }
+            PropertyDeclaration::BorderEndStartRadius(radius) => {
+                border_radius_declaration(side(Side::Bottom), side(Side::Left), radius)
+            }
+            PropertyDeclaration::BorderStartEndRadius(radius) => {
+                border_radius_declaration(side(Side::Top), side(Side::Right), radius)
+            }
+            PropertyDeclaration::BorderStartStartRadius(radius) => {
+                border_radius_declaration(side(Side::Top), side(Side::Left), radius)
+            }
+            PropertyDeclaration::InsetBlockEnd(lp_or_auto) => {
+                inset_declaration(side(Side::Bottom), lp_or_auto)
+            }
+            PropertyDeclaration::InsetBlockStart(lp_or_auto) => {
+                inset_declaration(side(Side::Top), lp_or_auto)
+            }
+            PropertyDeclaration::InsetInlineEnd(lp_or_auto) => {
+                inset_declaration(side(Side::Right), lp_or_auto)
+            }
+            PropertyDeclaration::InsetInlineStart(lp_or_auto) => {
+                inset_declaration(side(Side::Left), lp_or_auto)
+            }
+            PropertyDeclaration::MarginBlockEnd(lp_or_auto) => {
+                margin_declaration(side(Side::Bottom), lp_or_auto)
+            }
+            PropertyDeclaration::MarginBlockStart(lp_or_auto) => {
+                margin_declaration(side(Side::Top), lp_or_auto)
+            }
+            PropertyDeclaration::MarginInlineEnd(lp_or_auto) => {
+                margin_declaration(side(Side::Right), lp_or_auto)
+            }
+            PropertyDeclaration::MarginInlineStart(lp_or_auto) => {
+                margin_declaration(side(Side::Left), lp_or_auto)
+            }
+            PropertyDeclaration::PaddingBlockEnd(length_percentage) => {
+                padding_declaration(side(Side::Bottom), length_percentage)
+            }
+            PropertyDeclaration::PaddingBlockStart(length_percentage) => {
+                padding_declaration(side(Side::Top), length_percentage)
+            }
+            PropertyDeclaration::PaddingInlineEnd(length_percentage) => {
+                padding_declaration(side(Side::Right), length_percentage)
+            }
+            PropertyDeclaration::PaddingInlineStart(length_percentage) => {
+                padding_declaration(side(Side::Left), length_percentage)
+            }
+            _ => return None,
+        };
+        Some(physical_decl)
+    }
+}
+
+fn border_color_declaration(side: Side, color: ColorUnit) -> PropertyDeclaration {
+    match side {
+        Side::Bottom => PropertyDeclaration::BorderBottomColor(BorderBottomColor { color }),
+        Side::Left => PropertyDeclaration::BorderLeftColor(BorderLeftColor { color }),
+        Side::Right => PropertyDeclaration::BorderRightColor(BorderRightColor { color }),
+        Side::Top => PropertyDeclaration::BorderTopColor(BorderTopColor { color }),
+    }
+}
+
+fn border_style_declaration(side: Side, line_style: LineStyle) -> PropertyDeclaration {
+    match side {
+        Side::Bottom => PropertyDeclaration::BorderBottomStyle(line_style),
+        Side::Left => PropertyDeclaration::BorderLeftStyle(line_style),
+        Side::Right => PropertyDeclaration::BorderRightStyle(line_style),
+        Side::Top => PropertyDeclaration::BorderTopStyle(line_style),
+    }
+}
+
+fn border_width_declaration(side: Side, line_width: LineWidth) -> PropertyDeclaration {
+    match side {
+        Side::Bottom => PropertyDeclaration::BorderBottomWidth(BorderBottomWidth { line_width }),
+        Side::Left => PropertyDeclaration::BorderLeftWidth(BorderLeftWidth { line_width }),
+        Side::Right => PropertyDeclaration::BorderRightWidth(BorderRightWidth { line_width }),
+        Side::Top => PropertyDeclaration::BorderTopWidth(BorderTopWidth { line_width }),
+    }
+}
+
+/// The declaration of the radius of the corner between the physical sides a flow-relative corner
+/// maps to, one on the block axis and one on the inline axis.  Its horizontal and vertical radii
+/// stay physical.
+fn border_radius_declaration(
+    block_side: Side,
+    inline_side: Side,
+    radius: BorderCornerRadius,
+) -> PropertyDeclaration {
+    match (block_side, inline_side) {
+        (Side::Bottom, Side::Left) | (Side::Left, Side::Bottom) => {
+            PropertyDeclaration::BorderBottomLeftRadius(radius)
+        }
+        (Side::Bottom, Side::Right) | (Side::Right, Side::Bottom) => {
+            PropertyDeclaration::BorderBottomRightRadius(radius)
+        }
+        (Side::Top, Side::Left) | (Side::Left, Side::Top) => {
+            PropertyDeclaration::BorderTopLeftRadius(radius)
+        }
+        (Side::Top, Side::Right) | (Side::Right, Side::Top) => {
+            PropertyDeclaration::BorderTopRightRadius(radius)
+        }
+        _ => unreachable!("the block and inline axes are never parallel"),
+    }
+}
+
+fn inset_declaration(side: Side, lp_or_auto: LengthPercentageOrAuto) -> PropertyDeclaration {
+    match side {
+        Side::Bottom => PropertyDeclaration::Bottom(Bottom { lp_or_auto }),
+        Side::Left => PropertyDeclaration::Left(Left { lp_or_auto }),
+        Side::Right => PropertyDeclaration::Right(Right { lp_or_auto }),
+        Side::Top => PropertyDeclaration::Top(Top { lp_or_auto }),
+    }
+}
+
+fn margin_declaration(side: Side, lp_or_auto: LengthPercentageOrAuto) -> PropertyDeclaration {
+    match side {
+        Side::Bottom => PropertyDeclaration::MarginBottom(MarginBottom { lp_or_auto }),
+        Side::Left => PropertyDeclaration::MarginLeft(MarginLeft { lp_or_auto }),
+        Side::Right => PropertyDeclaration::MarginRight(MarginRight { lp_or_auto }),
+        Side::Top => PropertyDeclaration::MarginTop(MarginTop { lp_or_auto }),
+    }
+}
+
+fn padding_declaration(side: Side, length_percentage: LengthPercentage) -> PropertyDeclaration {
+    match side {
+        Side::Bottom => PropertyDeclaration::PaddingBottom(PaddingBottom { length_percentage }),
+        Side::Left => PropertyDeclaration::PaddingLeft(PaddingLeft { length_percentage }),
+        Side::Right => PropertyDeclaration::PaddingRight(PaddingRight { length_percentage }),
+        Side::Top => PropertyDeclaration::PaddingTop(PaddingTop { length_percentage }),
+    }
 }
 
 #[derive(Clone, Debug)]
 #[repr(u16)]
 pub enum PropertyDeclaration {
+    AlignContent(crate::style::values::computed::AlignContent),
+    AlignItems(crate::style::values::computed::AlignItems),
+    AlignSelf(crate::style::values::computed::AlignSelf),
+    BackgroundClip(crate::style::values::computed::BackgroundClip),
     BackgroundColor(crate::style::values::specified::BackgroundColor),
+    BackgroundImage(crate::style::values::specified::BackgroundImage),
+    BackgroundOrigin(crate::style::values::computed::BackgroundOrigin),
+    BackgroundPositionX(crate::style::values::specified::BackgroundPositionX),
+    BackgroundPositionY(crate::style::values::specified::BackgroundPositionY),
+    BackgroundRepeat(crate::style::values::computed::BackgroundRepeat),
+    BackgroundSize(crate::style::values::specified::BackgroundSize),
+    BlockSize(crate::style::values::specified::Size),
+    BorderBlockEndColor(crate::style::values::specified::ColorUnit),
+    BorderBlockEndStyle(crate::style::values::computed::LineStyle),
+    BorderBlockEndWidth(crate::style::values::specified::border::LineWidth),
+    BorderBlockStartColor(crate::style::values::specified::ColorUnit),
+    BorderBlockStartStyle(crate::style::values::computed::LineStyle),
+    BorderBlockStartWidth(crate::style::values::specified::border::LineWidth),
     BorderBottomColor(crate::style::values::specified::BorderBottomColor),
+    BorderCollapse(crate::style::values::computed::BorderCollapse),
+    BorderInlineEndColor(crate::style::values::specified::ColorUnit),
+    BorderInlineEndStyle(crate::style::values::computed::LineStyle),
+    BorderInlineEndWidth(crate::style::values::specified::border::LineWidth),
+    BorderInlineStartColor(crate::style::values::specified::ColorUnit),
+    BorderInlineStartStyle(crate::style::values::computed::LineStyle),
+    BorderInlineStartWidth(crate::style::values::specified::border::LineWidth),
     BorderLeftColor(crate::style::values::specified::BorderLeftColor),
     BorderRightColor(crate::style::values::specified::BorderRightColor),
     BorderTopColor(crate::style::values::specified::BorderTopColor),
@@ -279,21 +980,89 @@ pub enum PropertyDeclaration {
     BorderLeftWidth(crate::style::values::specified::BorderLeftWidth),
     BorderRightWidth(crate::style::values::specified::BorderRightWidth),
     BorderTopWidth(crate::style::values::specified::BorderTopWidth),
+    BorderBottomLeftRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderBottomRightRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderEndEndRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderEndStartRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderStartEndRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderStartStartRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderTopLeftRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderTopRightRadius(crate::style::values::specified::BorderCornerRadius),
+    BorderSpacing(crate::style::values::specified::BorderSpacing),
+    Bottom(crate::style::values::specified::Bottom),
+    BoxShadow(crate::style::values::specified::BoxShadow),
+    BoxSizing(crate::style::values::computed::BoxSizing),
+    BreakAfter(crate::style::values::computed::BreakBetween),
+    BreakBefore(crate::style::values::computed::BreakBetween),
+    BreakInside(crate::style::values::computed::BreakInside),
+    CaptionSide(crate::style::values::computed::CaptionSide),
+    Clear(crate::style::values::computed::Clear),
     Color(crate::style::values::specified::Color),
+    ColumnGap(crate::style::values::specified::Gap),
     Direction(crate::style::values::computed::Direction),
     Display(crate::style::values::computed::Display),
+    FlexBasis(crate::style::values::specified::FlexBasis),
+    FlexDirection(crate::style::values::computed::FlexDirection),
+    FlexGrow(crate::style::values::computed::FlexGrow),
+    FlexShrink(crate::style::values::computed::FlexShrink),
+    FlexWrap(crate::style::values::computed::FlexWrap),
+    Float(crate::style::values::computed::Float),
     FontSize(crate::style::values::specified::FontSize),
+    GridAutoColumns(crate::style::values::specified::ImplicitGridTracks),
+    GridAutoFlow(crate::style::values::computed::GridAutoFlow),
+    GridAutoRows(crate::style::values::specified::ImplicitGridTracks),
+    GridColumnEnd(crate::style::values::computed::GridLine),
+    GridColumnStart(crate::style::values::computed::GridLine),
+    GridRowEnd(crate::style::values::computed::GridLine),
+    GridRowStart(crate::style::values::computed::GridLine),
+    GridTemplateAreas(crate::style::values::computed::GridTemplateAreas),
+    GridTemplateColumns(crate::style::values::specified::GridTemplateComponent),
+    GridTemplateRows(crate::style::values::specified::GridTemplateComponent),
     Height(crate::style::values::specified::Height),
+    InlineSize(crate::style::values::specified::Size),
+    InsetBlockEnd(crate::style::values::specified::LengthPercentageOrAuto),
+    InsetBlockStart(crate::style::values::specified::LengthPercentageOrAuto),
+    InsetInlineEnd(crate::style::values::specified::LengthPercentageOrAuto),
+    InsetInlineStart(crate::style::values::specified::LengthPercentageOrAuto),
+    JustifyContent(crate::style::values::computed::JustifyContent),
+    Left(crate::style::values::specified::Left),
+    MarginBlockEnd(crate::style::values::specified::LengthPercentageOrAuto),
+    MarginBlockStart(crate::style::values::specified::LengthPercentageOrAuto),
     MarginBottom(crate::style::values::specified::MarginBottom),
+    MarginInlineEnd(crate::style::values::specified::LengthPercentageOrAuto),
+    MarginInlineStart(crate::style::values::specified::LengthPercentageOrAuto),
     MarginLeft(crate::style::values::specified::MarginLeft),
     MarginRight(crate::style::values::specified::MarginRight),
     MarginTop(crate::style::values::specified::MarginTop),
+    MaxBlockSize(crate::style::values::specified::MaxSize),
+    MaxHeight(crate::style::values::specified::MaxHeight),
+    MaxInlineSize(crate::style::values::specified::MaxSize),
+    MaxWidth(crate::style::values::specified::MaxWidth),
+    MinBlockSize(crate::style::values::specified::Size),
+    MinHeight(crate::style::values::specified::MinHeight),
+    MinInlineSize(crate::style::values::specified::Size),
+    MinWidth(crate::style::values::specified::MinWidth),
+    Opacity(crate::style::values::computed::Opacity),
+    Order(crate::style::values::computed::Order),
+    OverflowX(crate::style::values::computed::Overflow),
+    OverflowY(crate::style::values::computed::Overflow),
+    PaddingBlockEnd(crate::style::values::specified::LengthPercentage),
+    PaddingBlockStart(crate::style::values::specified::LengthPercentage),
     PaddingBottom(crate::style::values::specified::PaddingBottom),
+    PaddingInlineEnd(crate::style::values::specified::LengthPercentage),
+    PaddingInlineStart(crate::style::values::specified::LengthPercentage),
     PaddingLeft(crate::style::values::specified::PaddingLeft),
     PaddingRight(crate::style::values::specified::PaddingRight),
     PaddingTop(crate::style::values::specified::PaddingTop),
+    Position(crate::style::values::computed::Position),
+    Right(crate::style::values::specified::Right),
+    RowGap(crate::style::values::specified::Gap),
+    TableLayout(crate::style::values::computed::TableLayout),
+    TextShadow(crate::style::values::specified::TextShadow),
+    Top(crate::style::values::specified::Top),
     Width(crate::style::values::specified::Width),
     WritingMode(crate::style::values::computed::WritingMode),
+    ZIndex(crate::style::values::computed::ZIndex),
 }
 
 pub struct ComputedPropertyDeclarations {}
@@ -338,8 +1107,14 @@ impl ContextualPropertyDeclarations {
     #[inline]
     pub fn cascade_sort(&mut self) {
         // ContextualPropertyDeclarations override `Ord`, so this will sort by origin, importance,
-        // specificity.
-        self.decls.as_mut_slice().sort();
+        // specificity.  Declarations that win the cascade compare as greater, so sort in
+        // descending order to put them first.  Declarations of different properties always compare
+        // as equal, so group by property first to keep the ordering total.
+        self.decls.as_mut_slice().sort_by(|a, b| {
+            (LonghandId::from(a) as u16)
+                .cmp(&(LonghandId::from(b) as u16))
+                .then_with(|| b.cmp(a))
+        });
         // Now sort by order of appearance.
         let mut swaps: Vec<(usize, usize)> = Vec::new();
         for (outer_idx, outer_decl) in self.decls.iter().enumerate() {
@@ -390,6 +1165,45 @@ impl ContextualPropertyDeclarations {
         }
     }
 
+    /// Replaces flow-relative declarations with declarations of the physical properties they map
+    /// to for the given writing mode and direction, so that both cascade as one property group.
+    /// Each replacement keeps its place in the order of appearance, so this must be done before
+    /// sorting.
+    ///
+    /// https://drafts.csswg.org/css-logical/#box
+    pub fn map_flow_relative_to_physical(
+        &mut self,
+        writing_mode: WritingMode,
+        direction: Direction,
+    ) {
+        let mut mapped_any = false;
+        for decl in self.decls.iter_mut() {
+            if let Some(physical_decl) = decl.inner_decl.to_physical(writing_mode, direction) {
+                decl.inner_decl = physical_decl;
+                mapped_any = true;
+            }
+        }
+        if mapped_any {
+            self.longhands = self.decls.iter().map(LonghandId::from).collect();
+            self.is_sorted = false;
+        }
+    }
+
+    /// Finds the `ContextualPropertyDeclaration` of `longhand` that wins the cascade, without
+    /// relying on the declarations being sorted.  Assumes the declarations are still in order of
+    /// appearance.
+    pub fn cascaded_by_longhand(
+        &self,
+        longhand: LonghandId,
+    ) -> Option<&ContextualPropertyDeclaration> {
+        // Of declarations that compare equally, `max_by` returns the last one, which is the one
+        // that appears last.
+        self.decls
+            .iter()
+            .filter(|decl| LonghandId::from(*decl) == longhand)
+            .max_by(|a, b| a.cmp(b))
+    }
+
     #[inline]
     pub fn contains(&self, longhand: LonghandId) -> bool {
         self.longhands.contains(&longhand)
@@ -557,9 +1371,13 @@ mod tests {
     use crate::style::test_utils::{display_by_type, font_size_px, font_size_px_or_panic};
 
     use super::*;
-    use crate::style::values::computed::Display;
-    use crate::style::values::specified::{AbsoluteLength, LengthPercentage, NoCalcLength};
+    use crate::style::values::computed::{Display, GridLine, Percentage};
+    use crate::style::values::specified::{
+        AbsoluteLength, FlexBasis, IntrinsicSize, LengthPercentage, LengthPercentageOrAuto,
+        LengthPercentageOrNone, NoCalcLength, Width,
+    };
     use crate::style::StylesheetOrigin;
+    use cssparser::ParserInput;
     use std::clone::Clone;
 
     #[test]
@@ -713,6 +1531,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decl_sort_author_origin_wins() {
+        let mut ua_display = display_by_type(Display::Block);
+        ua_display.origin = CssOrigin::Sheet(StylesheetOrigin {
+            sheet_name: "browser.css".to_owned(),
+            cascade_origin: CascadeOrigin::UserAgent,
+        });
+        let mut author_display = display_by_type(Display::Inline);
+        author_display.origin = CssOrigin::Sheet(StylesheetOrigin {
+            sheet_name: "file.css".to_owned(),
+            cascade_origin: CascadeOrigin::Author,
+        });
+
+        let mut decls = ContextualPropertyDeclarations::new();
+        decls.add(ua_display);
+        decls.add(font_size_px(12.0));
+        decls.add(author_display);
+        decls.cascade_sort();
+        let winning_display = decls
+            .get_by_longhand(LonghandId::Display)
+            .expect("decl_sort_author_origin_wins should get display");
+        match winning_display.inner_decl {
+            PropertyDeclaration::Display(display_type) => {
+                assert_eq!(display_type, Display::Inline);
+            }
+            _ => panic!("`winning_display` should have property decl type of display"),
+        }
+    }
+
     #[test]
     fn decl_cmp_diff_prop_types_are_equal() {
         let font_size = ContextualPropertyDeclaration {
@@ -758,4 +1605,428 @@ mod tests {
         assert_eq!(decl_block.declarations.len(), 1);
         assert_eq!(&24.0, font_size_px_or_panic(&decl_block.declarations[0]));
     }
+
+    #[test]
+    fn flex_shorthand_expands_to_longhands() {
+        let mut input = ParserInput::new("flex: 2");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        assert_eq!(decl_block.declarations.len(), 3);
+        match &decl_block.declarations[..] {
+            [PropertyDeclaration::FlexGrow(grow), PropertyDeclaration::FlexShrink(shrink), PropertyDeclaration::FlexBasis(FlexBasis::Width(Width::LengthPercentageOrAuto(
+                LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(
+                    NoCalcLength::Absolute(AbsoluteLength::Px(basis)),
+                )),
+            )))] => {
+                assert_eq!(grow.0, 2.0);
+                assert_eq!(shrink.0, 1.0);
+                assert_eq!(*basis, 0.0);
+            }
+            decls => panic!("unexpected declarations for `flex: 2`: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn border_radius_shorthand_expands_to_corners() {
+        let px = |px| LengthPercentage::Length(NoCalcLength::Absolute(AbsoluteLength::Px(px)));
+        let mut input = ParserInput::new("border-radius: 4px 10% / 2px");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match &decl_block.declarations[..] {
+            [PropertyDeclaration::BorderTopLeftRadius(top_left), PropertyDeclaration::BorderTopRightRadius(top_right), PropertyDeclaration::BorderBottomRightRadius(bottom_right), PropertyDeclaration::BorderBottomLeftRadius(bottom_left)] =>
+            {
+                assert_eq!(top_left.horizontal, px(4.));
+                assert_eq!(
+                    top_right.horizontal,
+                    LengthPercentage::Percentage(Percentage(0.1))
+                );
+                assert_eq!(bottom_right.horizontal, px(4.));
+                assert_eq!(
+                    bottom_left.horizontal,
+                    LengthPercentage::Percentage(Percentage(0.1))
+                );
+                for corner in &[top_left, top_right, bottom_right, bottom_left] {
+                    assert_eq!(corner.vertical, px(2.));
+                }
+            }
+            decls => panic!(
+                "unexpected declarations for `border-radius: 4px 10% / 2px`: {:?}",
+                decls
+            ),
+        }
+    }
+
+    #[test]
+    fn grid_area_shorthand_copies_named_lines() {
+        let mut input = ParserInput::new("grid-area: header / 2");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match &decl_block.declarations[..] {
+            [PropertyDeclaration::GridRowStart(row_start), PropertyDeclaration::GridColumnStart(column_start), PropertyDeclaration::GridRowEnd(row_end), PropertyDeclaration::GridColumnEnd(column_end)] =>
+            {
+                assert_eq!(*row_start, GridLine::Ident("header".to_owned()));
+                assert_eq!(*column_start, GridLine::Line(2, None));
+                assert_eq!(*row_end, GridLine::Ident("header".to_owned()));
+                assert_eq!(*column_end, GridLine::Auto);
+            }
+            decls => panic!(
+                "unexpected declarations for `grid-area: header / 2`: {:?}",
+                decls
+            ),
+        }
+    }
+
+    #[test]
+    fn border_spacing_second_length_is_vertical() {
+        let mut input = ParserInput::new("border-spacing: 2px 0");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match &decl_block.declarations[..] {
+            [PropertyDeclaration::BorderSpacing(spacing)] => {
+                assert_eq!(
+                    spacing.horizontal,
+                    NoCalcLength::Absolute(AbsoluteLength::Px(2.0))
+                );
+                assert_eq!(
+                    spacing.vertical,
+                    NoCalcLength::Absolute(AbsoluteLength::Px(0.0))
+                );
+            }
+            decls => panic!(
+                "unexpected declarations for `border-spacing: 2px 0`: {:?}",
+                decls
+            ),
+        }
+    }
+
+    #[test]
+    fn margin_inline_shorthand_expands_to_start_and_end() {
+        let mut input = ParserInput::new("margin-inline: 4px auto");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match &decl_block.declarations[..] {
+            [PropertyDeclaration::MarginInlineStart(LengthPercentageOrAuto::LengthPercentage(
+                LengthPercentage::Length(NoCalcLength::Absolute(AbsoluteLength::Px(start))),
+            )), PropertyDeclaration::MarginInlineEnd(LengthPercentageOrAuto::Auto)] => {
+                assert_eq!(*start, 4.0);
+            }
+            decls => panic!(
+                "unexpected declarations for `margin-inline: 4px auto`: {:?}",
+                decls
+            ),
+        }
+    }
+
+    #[test]
+    fn overflow_shorthand_expands_to_x_and_y() {
+        let expand = |css| {
+            let mut input = ParserInput::new(css);
+            parse_property_declaration_list(&mut Parser::new(&mut input)).declarations
+        };
+        match &expand("overflow: clip")[..] {
+            [PropertyDeclaration::OverflowX(Overflow::Clip), PropertyDeclaration::OverflowY(Overflow::Clip)] =>
+                {}
+            decls => panic!("unexpected declarations for `overflow: clip`: {:?}", decls),
+        }
+        match &expand("overflow: hidden scroll")[..] {
+            [PropertyDeclaration::OverflowX(Overflow::Hidden), PropertyDeclaration::OverflowY(Overflow::Scroll)] =>
+                {}
+            decls => panic!(
+                "unexpected declarations for `overflow: hidden scroll`: {:?}",
+                decls
+            ),
+        }
+    }
+
+    #[test]
+    fn z_index_and_opacity_parse() {
+        let mut input = ParserInput::new("z-index: -3; opacity: 150%");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match decl_block.declarations() {
+            [PropertyDeclaration::ZIndex(ZIndex::Integer(-3)), PropertyDeclaration::Opacity(Opacity(opacity))] =>
+            {
+                assert_eq!(*opacity, 1.)
+            }
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+        let mut input = ParserInput::new("z-index: auto; opacity: 0.25; z-index: 1.5");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match decl_block.declarations() {
+            [PropertyDeclaration::ZIndex(ZIndex::Auto), PropertyDeclaration::Opacity(Opacity(opacity))] =>
+            {
+                assert_eq!(*opacity, 0.25)
+            }
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn break_properties_parse() {
+        let mut input = ParserInput::new(
+            "break-before: page; break-after: avoid-page; break-inside: avoid; break-inside: page",
+        );
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match decl_block.declarations() {
+            [PropertyDeclaration::BreakBefore(BreakBetween::Page), PropertyDeclaration::BreakAfter(BreakBetween::AvoidPage), PropertyDeclaration::BreakInside(BreakInside::Avoid)] =>
+                {}
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn overflow_visible_or_clip_computes_to_scrollable_beside_scrollable_axis() {
+        assert_eq!(
+            Overflow::compute_pair(Overflow::Scroll, Overflow::Visible),
+            (Overflow::Scroll, Overflow::Auto)
+        );
+        assert_eq!(
+            Overflow::compute_pair(Overflow::Clip, Overflow::Hidden),
+            (Overflow::Hidden, Overflow::Hidden)
+        );
+        assert_eq!(
+            Overflow::compute_pair(Overflow::Clip, Overflow::Visible),
+            (Overflow::Clip, Overflow::Visible)
+        );
+    }
+
+    #[test]
+    fn flow_relative_decls_map_to_physical_sides() {
+        let mut input = ParserInput::new(
+            "margin-inline-start: 1px; padding-block-end: 2px; inline-size: 3px; width: 4px",
+        );
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        let physical_decls = decl_block
+            .declarations()
+            .iter()
+            .map(|decl| decl.to_physical(WritingMode::VerticalRl, Direction::Rtl))
+            .collect::<Vec<_>>();
+        match &physical_decls[..] {
+            [Some(PropertyDeclaration::MarginBottom(_)), Some(PropertyDeclaration::PaddingLeft(_)), Some(PropertyDeclaration::Height(_)), None] =>
+                {}
+            decls => panic!("unexpected physical declarations: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn max_block_size_maps_to_max_width_in_vertical_writing_modes() {
+        let mut input = ParserInput::new("max-block-size: none; min-inline-size: 10%");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        let physical_decls = decl_block
+            .declarations()
+            .iter()
+            .map(|decl| decl.to_physical(WritingMode::VerticalLr, Direction::Ltr))
+            .collect::<Vec<_>>();
+        match &physical_decls[..] {
+            [Some(PropertyDeclaration::MaxWidth(MaxWidth::LengthPercentageOrNone(
+                LengthPercentageOrNone::None,
+            ))), Some(PropertyDeclaration::MinHeight(MinHeight::LengthPercentageOrAuto(
+                LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(_)),
+            )))] => {}
+            decls => panic!("unexpected physical declarations: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn intrinsic_size_keywords_parse_and_map_to_physical_properties() {
+        let mut input =
+            ParserInput::new("inline-size: fit-content(50%); max-block-size: min-content");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        let physical_decls = decl_block
+            .declarations()
+            .iter()
+            .map(|decl| decl.to_physical(WritingMode::VerticalRl, Direction::Ltr))
+            .collect::<Vec<_>>();
+        match &physical_decls[..] {
+            [Some(PropertyDeclaration::Height(Height::IntrinsicSize(
+                IntrinsicSize::FitContent(LengthPercentage::Percentage(_)),
+            ))), Some(PropertyDeclaration::MaxWidth(MaxWidth::IntrinsicSize(
+                IntrinsicSize::MinContent,
+            )))] => {}
+            decls => panic!("unexpected physical declarations: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn background_shorthand_sets_every_layer_longhand() {
+        use crate::style::values::computed::{BgRepeat, BoxArea, RepeatStyle};
+        use crate::style::values::specified::background::{
+            BgPositionComponent, BgSize, GradientKind, Image, LineDirection,
+        };
+        let mut input = ParserInput::new(
+            "background: url(a.png) right 10px top / 20px no-repeat content-box, \
+             repeating-linear-gradient(to left, red, blue 10%) teal",
+        );
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        let px = |px| LengthPercentage::Length(NoCalcLength::Absolute(AbsoluteLength::Px(px)));
+        let start = BgPositionComponent {
+            from_end: false,
+            offset: LengthPercentage::Percentage(Percentage(0.)),
+        };
+        match decl_block.declarations() {
+            [PropertyDeclaration::BackgroundColor(_), PropertyDeclaration::BackgroundImage(image), PropertyDeclaration::BackgroundPositionX(x), PropertyDeclaration::BackgroundPositionY(y), PropertyDeclaration::BackgroundSize(size), PropertyDeclaration::BackgroundRepeat(repeat), PropertyDeclaration::BackgroundOrigin(origin), PropertyDeclaration::BackgroundClip(clip)] =>
+            {
+                match &image.0[..] {
+                    [Image::Url(url), Image::Gradient(gradient)] => {
+                        assert_eq!(url, "a.png");
+                        assert!(gradient.repeating);
+                        assert_eq!(
+                            gradient.kind,
+                            GradientKind::Linear(LineDirection::Angle(270.))
+                        );
+                        assert_eq!(gradient.stops.len(), 2);
+                    }
+                    images => panic!("unexpected images: {:?}", images),
+                }
+                assert_eq!(
+                    x.0,
+                    vec![
+                        BgPositionComponent {
+                            from_end: true,
+                            offset: px(10.),
+                        },
+                        start,
+                    ]
+                );
+                assert_eq!(y.0, vec![start, start]);
+                assert_eq!(
+                    size.0,
+                    vec![
+                        BgSize::Explicit {
+                            width: LengthPercentageOrAuto::LengthPercentage(px(20.)),
+                            height: LengthPercentageOrAuto::Auto,
+                        },
+                        BgSize::Explicit {
+                            width: LengthPercentageOrAuto::Auto,
+                            height: LengthPercentageOrAuto::Auto,
+                        },
+                    ]
+                );
+                assert_eq!(
+                    repeat.0,
+                    vec![
+                        BgRepeat {
+                            horizontal: RepeatStyle::NoRepeat,
+                            vertical: RepeatStyle::NoRepeat,
+                        },
+                        BgRepeat {
+                            horizontal: RepeatStyle::Repeat,
+                            vertical: RepeatStyle::Repeat,
+                        },
+                    ]
+                );
+                assert_eq!(origin.0, vec![BoxArea::ContentBox, BoxArea::PaddingBox]);
+                assert_eq!(clip.0, vec![BoxArea::ContentBox, BoxArea::BorderBox]);
+            }
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+
+        // Only the last layer may have a color.
+        let mut input = ParserInput::new("background: teal url(a.png), none");
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        assert!(decl_block.declarations().is_empty());
+    }
+
+    #[test]
+    fn radial_gradients_and_positions_parse() {
+        use crate::style::values::specified::background::{
+            BgPositionComponent, EndingShape, GradientKind, Image, ShapeExtent,
+        };
+        let mut input = ParserInput::new(
+            "background-image: radial-gradient(circle closest-side at 20% 30px, red, white, blue); \
+             background-position: bottom 5px right",
+        );
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        let px = |px| LengthPercentage::Length(NoCalcLength::Absolute(AbsoluteLength::Px(px)));
+        match decl_block.declarations() {
+            [PropertyDeclaration::BackgroundImage(image), PropertyDeclaration::BackgroundPositionX(x), PropertyDeclaration::BackgroundPositionY(y)] =>
+            {
+                match &image.0[..] {
+                    [Image::Gradient(gradient)] => match gradient.kind {
+                        GradientKind::Radial(shape, position) => {
+                            assert_eq!(shape, EndingShape::CircleExtent(ShapeExtent::ClosestSide));
+                            assert_eq!(
+                                position.horizontal.offset,
+                                LengthPercentage::Percentage(Percentage(0.2))
+                            );
+                            assert_eq!(position.vertical.offset, px(30.));
+                        }
+                        kind => panic!("unexpected gradient: {:?}", kind),
+                    },
+                    images => panic!("unexpected images: {:?}", images),
+                }
+                assert_eq!(
+                    x.0,
+                    vec![BgPositionComponent {
+                        from_end: true,
+                        offset: LengthPercentage::Percentage(Percentage(0.)),
+                    }]
+                );
+                assert_eq!(
+                    y.0,
+                    vec![BgPositionComponent {
+                        from_end: true,
+                        offset: px(5.),
+                    }]
+                );
+            }
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+    }
+
+    #[test]
+    fn box_and_text_shadows_parse() {
+        use crate::style::values::specified::shadow::Shadow;
+        use cssparser::RGBA;
+        let mut input = ParserInput::new(
+            "box-shadow: 1px 2px, inset red 0 0 4px -1px; text-shadow: 3px 4px 5px blue",
+        );
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        let px = |px| NoCalcLength::Absolute(AbsoluteLength::Px(px));
+        let red = ColorUnit::Numeric(RGBA::new(255, 0, 0, 255));
+        let blue = ColorUnit::Numeric(RGBA::new(0, 0, 255, 255));
+        match decl_block.declarations() {
+            [PropertyDeclaration::BoxShadow(box_shadow), PropertyDeclaration::TextShadow(text_shadow)] =>
+            {
+                assert_eq!(
+                    box_shadow.0,
+                    vec![
+                        Shadow {
+                            color: None,
+                            offset_x: px(1.),
+                            offset_y: px(2.),
+                            blur_radius: px(0.),
+                            spread: px(0.),
+                            inset: false,
+                        },
+                        Shadow {
+                            color: Some(red),
+                            offset_x: px(0.),
+                            offset_y: px(0.),
+                            blur_radius: px(4.),
+                            spread: px(-1.),
+                            inset: true,
+                        }
+                    ]
+                );
+                assert_eq!(
+                    text_shadow.0,
+                    vec![Shadow {
+                        color: Some(blue),
+                        offset_x: px(3.),
+                        offset_y: px(4.),
+                        blur_radius: px(5.),
+                        spread: px(0.),
+                        inset: false,
+                    }]
+                );
+            }
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+
+        // Blur radii can't be negative, and text shadows can't be spread or inset.
+        let mut input = ParserInput::new(
+            "box-shadow: 1px 1px -2px; text-shadow: 1px 1px 1px 1px; text-shadow: inset 1px 1px; \
+             box-shadow: none",
+        );
+        let decl_block = parse_property_declaration_list(&mut Parser::new(&mut input));
+        match decl_block.declarations() {
+            [PropertyDeclaration::BoxShadow(box_shadow)] => assert!(box_shadow.0.is_empty()),
+            decls => panic!("unexpected declarations: {:?}", decls),
+        }
+    }
 }
@@ -136,7 +136,7 @@ impl Stylesheet {
                                 }
                             }
                         }
-                        CssRule::None => {}
+                        CssRule::Page(_) | CssRule::None => {}
                     }
                 }
 
@@ -144,7 +144,7 @@ impl Stylesheet {
                     self.rules.remove(index);
                 }
             }
-            CssRule::None => {}
+            CssRule::Page(_) | CssRule::None => {}
         }
         self.rules.push(new_rule);
     }
@@ -152,6 +152,7 @@ impl Stylesheet {
 
 #[cfg(test)]
 mod tests {
+    use crate::style::page::{PageContext, PaperSize};
     use crate::style::test_utils::font_size_px_or_panic;
 
     // Note this useful idiom: importing names from outer (for mod tests) scope.
@@ -179,4 +180,30 @@ mod tests {
             _ => panic!("should always be a `StyleRule` CssRule"),
         }
     }
+
+    #[test]
+    fn page_rules_are_parsed_and_cascaded() {
+        let sheet = parse_css_to_stylesheet(
+            None,
+            &mut "@page { size: a5 landscape; margin-top: 10%; margin-inline-start: 1in; color: red } \
+                  @page :first { margin-top: 0px } \
+                  @page { margin-top: auto; margin-block-end: 48px }"
+                .to_owned(),
+        )
+        .expect("failed parsing sheet with page rules");
+        assert_eq!(sheet.rules.len(), 3);
+        match &sheet.rules[1] {
+            CssRule::Page(page_rule) => assert_eq!(page_rule.selectors, ":first"),
+            _ => panic!("should always be a `PageRule` CssRule"),
+        }
+        // The `:first` rule is ignored, and the `color` declaration isn't allowed in page rules.
+        let context = PageContext::from_sheets(&[], &[], &[sheet]);
+        let (a5_width, a5_height) = PaperSize::A5.to_px();
+        assert_eq!((context.width, context.height), (a5_height, a5_width));
+        assert_eq!(context.margin_top, 0.);
+        assert_eq!(context.margin_bottom, 48.);
+        assert_eq!(context.margin_left, 96.);
+        assert_eq!(context.margin_right, 0.);
+        assert_eq!(context.content_height(), a5_width - 48.);
+    }
 }
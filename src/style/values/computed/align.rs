@@ -0,0 +1,215 @@
+use crate::style::values::computed::{ComputeContext, ValueDefault};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Distributes space between and around items along the main axis of a flex container.
+///
+/// https://drafts.csswg.org/css-align-3/#propdef-justify-content
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JustifyContent {
+    /// Behaves as `flex-start` in flex containers.
+    Normal,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl JustifyContent {
+    pub fn initial_value() -> Self {
+        JustifyContent::Normal
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(JustifyContent::Normal),
+            "start" => Ok(JustifyContent::Start),
+            "end" => Ok(JustifyContent::End),
+            "flex-start" => Ok(JustifyContent::FlexStart),
+            "flex-end" => Ok(JustifyContent::FlexEnd),
+            "center" => Ok(JustifyContent::Center),
+            "space-between" => Ok(JustifyContent::SpaceBetween),
+            "space-around" => Ok(JustifyContent::SpaceAround),
+            "space-evenly" => Ok(JustifyContent::SpaceEvenly),
+        }
+    }
+}
+
+impl ValueDefault for JustifyContent {
+    type ComputedValue = JustifyContent;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        JustifyContent::initial_value()
+    }
+}
+
+/// Distributes space between and around flex lines along the cross axis of a multi-line flex
+/// container.
+///
+/// https://drafts.csswg.org/css-align-3/#propdef-align-content
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlignContent {
+    /// Behaves as `stretch` in flex containers.
+    Normal,
+    Stretch,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl AlignContent {
+    pub fn initial_value() -> Self {
+        AlignContent::Normal
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(AlignContent::Normal),
+            "stretch" => Ok(AlignContent::Stretch),
+            "start" => Ok(AlignContent::Start),
+            "end" => Ok(AlignContent::End),
+            "flex-start" => Ok(AlignContent::FlexStart),
+            "flex-end" => Ok(AlignContent::FlexEnd),
+            "center" => Ok(AlignContent::Center),
+            "space-between" => Ok(AlignContent::SpaceBetween),
+            "space-around" => Ok(AlignContent::SpaceAround),
+            "space-evenly" => Ok(AlignContent::SpaceEvenly),
+        }
+    }
+}
+
+impl ValueDefault for AlignContent {
+    type ComputedValue = AlignContent;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        AlignContent::initial_value()
+    }
+}
+
+/// The default alignment of items along the cross axis of their flex line.
+///
+/// https://drafts.csswg.org/css-align-3/#propdef-align-items
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlignItems {
+    /// Behaves as `stretch` for flex items.
+    Normal,
+    Stretch,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+}
+
+impl AlignItems {
+    pub fn initial_value() -> Self {
+        AlignItems::Normal
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "normal" => Ok(AlignItems::Normal),
+            "stretch" => Ok(AlignItems::Stretch),
+            "start" => Ok(AlignItems::Start),
+            "end" => Ok(AlignItems::End),
+            "flex-start" => Ok(AlignItems::FlexStart),
+            "flex-end" => Ok(AlignItems::FlexEnd),
+            "center" => Ok(AlignItems::Center),
+            "baseline" => Ok(AlignItems::Baseline),
+        }
+    }
+}
+
+impl ValueDefault for AlignItems {
+    type ComputedValue = AlignItems;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        AlignItems::initial_value()
+    }
+}
+
+/// Overrides the `align-items` value of an item's parent for that item.
+///
+/// https://drafts.csswg.org/css-align-3/#propdef-align-self
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlignSelf {
+    /// Use the parent's `align-items` value.
+    Auto,
+    Normal,
+    Stretch,
+    Start,
+    End,
+    FlexStart,
+    FlexEnd,
+    Center,
+    Baseline,
+}
+
+impl AlignSelf {
+    pub fn initial_value() -> Self {
+        AlignSelf::Auto
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+            return Ok(AlignSelf::Auto);
+        }
+        AlignItems::parse(input).map(AlignSelf::from)
+    }
+
+    /// Resolves `auto` against the `align-items` value of the box's parent.
+    pub fn resolve(self, parent_align_items: AlignItems) -> AlignItems {
+        match self {
+            AlignSelf::Auto => parent_align_items,
+            AlignSelf::Normal => AlignItems::Normal,
+            AlignSelf::Stretch => AlignItems::Stretch,
+            AlignSelf::Start => AlignItems::Start,
+            AlignSelf::End => AlignItems::End,
+            AlignSelf::FlexStart => AlignItems::FlexStart,
+            AlignSelf::FlexEnd => AlignItems::FlexEnd,
+            AlignSelf::Center => AlignItems::Center,
+            AlignSelf::Baseline => AlignItems::Baseline,
+        }
+    }
+}
+
+impl From<AlignItems> for AlignSelf {
+    fn from(align_items: AlignItems) -> Self {
+        match align_items {
+            AlignItems::Normal => AlignSelf::Normal,
+            AlignItems::Stretch => AlignSelf::Stretch,
+            AlignItems::Start => AlignSelf::Start,
+            AlignItems::End => AlignSelf::End,
+            AlignItems::FlexStart => AlignSelf::FlexStart,
+            AlignItems::FlexEnd => AlignSelf::FlexEnd,
+            AlignItems::Center => AlignSelf::Center,
+            AlignItems::Baseline => AlignSelf::Baseline,
+        }
+    }
+}
+
+impl ValueDefault for AlignSelf {
+    type ComputedValue = AlignSelf;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        AlignSelf::initial_value()
+    }
+}
@@ -1,6 +1,17 @@
-use crate::style::values::computed::{ComputeContext, ComputeValueWithContext, ValueDefault};
+use crate::dom::image::{load_shared_image, ImageKey};
+use crate::style::values::computed::length::{
+    CSSPixelLength, LengthPercentage, LengthPercentageOrAuto,
+};
+use crate::style::values::computed::{
+    ComputeContext, ComputeValue, ComputeValueWithContext, Percentage, ValueDefault,
+};
 use crate::style::values::specified;
-use cssparser::RGBA;
+use crate::style::values::specified::background::{LineDirection, ShapeExtent};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, RGBA};
+use image::RgbaImage;
+use std::path::Path;
+use std::rc::Rc;
 
 /// Computed values for the `background-color` property.
 ///
@@ -36,3 +47,489 @@ impl ValueDefault for specified::BackgroundColor {
         BackgroundColor::initial_value(context.color().rgba())
     }
 }
+
+/// Gets the value of the background layer at `index` from `values`, which are repeated as many
+/// times as needed if there are fewer of them than there are layers.  The number of layers is
+/// the number of `background-image` values.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#layering
+fn layer<T: Copy>(values: &[T], index: usize) -> T {
+    values[index % values.len()]
+}
+
+/// Computed values for the `background-image` property, one per background layer.  Images that
+/// couldn't be loaded compute to `none`.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-image
+#[derive(Clone, Debug)]
+pub struct BackgroundImage(pub Vec<Image>);
+
+impl BackgroundImage {
+    pub fn initial_value() -> BackgroundImage {
+        BackgroundImage(vec![Image::None])
+    }
+}
+
+impl specified::BackgroundImage {
+    /// Computes these values, loading the images of their URLs, which are relative to `base_dir`.
+    pub fn compute_with_base_dir(
+        &self,
+        context: &ComputeContext,
+        base_dir: &Path,
+    ) -> BackgroundImage {
+        BackgroundImage(
+            self.0
+                .iter()
+                .map(|image| match image {
+                    specified::background::Image::None => Image::None,
+                    specified::background::Image::Url(url) => load_shared_image(url, base_dir)
+                        .map_or(Image::None, |(key, image)| Image::Raster(key, image)),
+                    specified::background::Image::Gradient(gradient) => {
+                        Image::Gradient(gradient.compute_value_with_context(context))
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ValueDefault for specified::BackgroundImage {
+    type ComputedValue = BackgroundImage;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundImage::initial_value()
+    }
+}
+
+/// The computed image of a background layer.
+#[derive(Clone, Debug)]
+pub enum Image {
+    None,
+    /// A decoded image, and the key it's painted by.
+    Raster(ImageKey, Rc<RgbaImage>),
+    Gradient(Gradient),
+}
+
+/// A computed gradient, whose colors are resolved, but whose geometry depends on the size of the
+/// box it's painted in.
+///
+/// https://drafts.csswg.org/css-images-3/#gradients
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<ColorStop>,
+    pub repeating: bool,
+}
+
+impl ComputeValueWithContext for specified::background::Gradient {
+    type ComputedValue = Gradient;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        let kind = match self.kind {
+            specified::background::GradientKind::Linear(direction) => {
+                GradientKind::Linear(direction)
+            }
+            specified::background::GradientKind::Radial(shape, position) => {
+                let shape = match shape {
+                    specified::background::EndingShape::Circle(radius) => {
+                        EndingShape::Circle(radius.compute_value())
+                    }
+                    specified::background::EndingShape::CircleExtent(extent) => {
+                        EndingShape::CircleExtent(extent)
+                    }
+                    specified::background::EndingShape::Ellipse(horizontal, vertical) => {
+                        EndingShape::Ellipse(horizontal.compute_value(), vertical.compute_value())
+                    }
+                    specified::background::EndingShape::EllipseExtent(extent) => {
+                        EndingShape::EllipseExtent(extent)
+                    }
+                };
+                GradientKind::Radial(shape, position.compute_value())
+            }
+        };
+        Gradient {
+            kind,
+            stops: self
+                .stops
+                .iter()
+                .map(|stop| ColorStop {
+                    color: stop.color.compute_value_with_context(context),
+                    position: stop.position.map(|position| position.compute_value()),
+                })
+                .collect(),
+            repeating: self.repeating,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientKind {
+    Linear(LineDirection),
+    Radial(EndingShape, BgPosition),
+}
+
+/// The computed ending shape of a radial gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EndingShape {
+    Circle(CSSPixelLength),
+    CircleExtent(ShapeExtent),
+    Ellipse(LengthPercentage, LengthPercentage),
+    EllipseExtent(ShapeExtent),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    pub color: RGBA,
+    pub position: Option<LengthPercentage>,
+}
+
+/// Computed values for the `background-position-x` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-4/#background-position-longhands
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundPositionX(pub Vec<BgPositionComponent>);
+
+impl BackgroundPositionX {
+    pub fn initial_value() -> BackgroundPositionX {
+        BackgroundPositionX(vec![BgPositionComponent {
+            from_end: false,
+            offset: LengthPercentage::Percentage(Percentage(0.)),
+        }])
+    }
+
+    pub fn layer(&self, index: usize) -> BgPositionComponent {
+        layer(&self.0, index)
+    }
+}
+
+impl ComputeValueWithContext for specified::BackgroundPositionX {
+    type ComputedValue = BackgroundPositionX;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundPositionX(self.0.iter().map(ComputeValue::compute_value).collect())
+    }
+}
+
+impl ValueDefault for specified::BackgroundPositionX {
+    type ComputedValue = BackgroundPositionX;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundPositionX::initial_value()
+    }
+}
+
+/// Computed values for the `background-position-y` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-4/#background-position-longhands
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundPositionY(pub Vec<BgPositionComponent>);
+
+impl BackgroundPositionY {
+    pub fn initial_value() -> BackgroundPositionY {
+        BackgroundPositionY(vec![BgPositionComponent {
+            from_end: false,
+            offset: LengthPercentage::Percentage(Percentage(0.)),
+        }])
+    }
+
+    pub fn layer(&self, index: usize) -> BgPositionComponent {
+        layer(&self.0, index)
+    }
+}
+
+impl ComputeValueWithContext for specified::BackgroundPositionY {
+    type ComputedValue = BackgroundPositionY;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundPositionY(self.0.iter().map(ComputeValue::compute_value).collect())
+    }
+}
+
+impl ValueDefault for specified::BackgroundPositionY {
+    type ComputedValue = BackgroundPositionY;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundPositionY::initial_value()
+    }
+}
+
+/// A computed `<bg-position>`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BgPosition {
+    pub horizontal: BgPositionComponent,
+    pub vertical: BgPositionComponent,
+}
+
+impl ComputeValue for specified::background::BgPosition {
+    type ComputedValue = BgPosition;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        BgPosition {
+            horizontal: self.horizontal.compute_value(),
+            vertical: self.vertical.compute_value(),
+        }
+    }
+}
+
+/// The computed position of something along one axis of a box, as an offset from its start or
+/// end edge in that axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BgPositionComponent {
+    pub from_end: bool,
+    pub offset: LengthPercentage,
+}
+
+impl ComputeValue for specified::background::BgPositionComponent {
+    type ComputedValue = BgPositionComponent;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        BgPositionComponent {
+            from_end: self.from_end,
+            offset: self.offset.compute_value(),
+        }
+    }
+}
+
+/// Computed values for the `background-size` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-size
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundSize(pub Vec<BgSize>);
+
+impl BackgroundSize {
+    pub fn initial_value() -> BackgroundSize {
+        BackgroundSize(vec![BgSize::Explicit {
+            width: LengthPercentageOrAuto::Auto,
+            height: LengthPercentageOrAuto::Auto,
+        }])
+    }
+
+    pub fn layer(&self, index: usize) -> BgSize {
+        layer(&self.0, index)
+    }
+}
+
+impl ComputeValueWithContext for specified::BackgroundSize {
+    type ComputedValue = BackgroundSize;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundSize(
+            self.0
+                .iter()
+                .map(|size| match size {
+                    specified::background::BgSize::Explicit { width, height } => BgSize::Explicit {
+                        width: width.compute_value(),
+                        height: height.compute_value(),
+                    },
+                    specified::background::BgSize::Cover => BgSize::Cover,
+                    specified::background::BgSize::Contain => BgSize::Contain,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ValueDefault for specified::BackgroundSize {
+    type ComputedValue = BackgroundSize;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundSize::initial_value()
+    }
+}
+
+/// The computed size of the image of a background layer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BgSize {
+    Explicit {
+        width: LengthPercentageOrAuto,
+        height: LengthPercentageOrAuto,
+    },
+    Cover,
+    Contain,
+}
+
+/// Values for the `background-repeat` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-repeat
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundRepeat(pub Vec<BgRepeat>);
+
+impl BackgroundRepeat {
+    pub fn initial_value() -> BackgroundRepeat {
+        BackgroundRepeat(vec![BgRepeat {
+            horizontal: RepeatStyle::Repeat,
+            vertical: RepeatStyle::Repeat,
+        }])
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(BgRepeat::parse)
+            .map(BackgroundRepeat)
+    }
+
+    pub fn layer(&self, index: usize) -> BgRepeat {
+        layer(&self.0, index)
+    }
+}
+
+impl ValueDefault for BackgroundRepeat {
+    type ComputedValue = BackgroundRepeat;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundRepeat::initial_value()
+    }
+}
+
+/// How the image of a background layer is repeated horizontally and vertically.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#typedef-repeat-style
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BgRepeat {
+    pub horizontal: RepeatStyle,
+    pub vertical: RepeatStyle,
+}
+
+impl BgRepeat {
+    /// Parses `repeat-x`, `repeat-y`, or one or two repeat styles, the first horizontal and the
+    /// second vertical.  A single style is used for both axes.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(horizontal) = input.try_parse(RepeatStyle::parse) {
+            let vertical = input.try_parse(RepeatStyle::parse).unwrap_or(horizontal);
+            return Ok(BgRepeat {
+                horizontal,
+                vertical,
+            });
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "repeat-x" => Ok(BgRepeat {
+                horizontal: RepeatStyle::Repeat,
+                vertical: RepeatStyle::NoRepeat,
+            }),
+            "repeat-y" => Ok(BgRepeat {
+                horizontal: RepeatStyle::NoRepeat,
+                vertical: RepeatStyle::Repeat,
+            }),
+        }
+    }
+}
+
+/// How the image of a background layer is repeated along one axis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepeatStyle {
+    /// The image is repeated as often as needed to cover the background painting area, and
+    /// clipped where it doesn't fit.
+    Repeat,
+    /// The image is repeated as often as it fits within the background positioning area without
+    /// being clipped, and the space left over is spread out between the repetitions.
+    Space,
+    /// The image is scaled so that it fits a whole number of times within the background
+    /// positioning area, and repeated like `repeat`.
+    Round,
+    /// The image is painted once.
+    NoRepeat,
+}
+
+impl RepeatStyle {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "repeat" => Ok(RepeatStyle::Repeat),
+            "space" => Ok(RepeatStyle::Space),
+            "round" => Ok(RepeatStyle::Round),
+            "no-repeat" => Ok(RepeatStyle::NoRepeat),
+        }
+    }
+}
+
+/// Values for the `background-origin` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-origin
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundOrigin(pub Vec<BoxArea>);
+
+impl BackgroundOrigin {
+    pub fn initial_value() -> BackgroundOrigin {
+        BackgroundOrigin(vec![BoxArea::PaddingBox])
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(BoxArea::parse)
+            .map(BackgroundOrigin)
+    }
+
+    pub fn layer(&self, index: usize) -> BoxArea {
+        layer(&self.0, index)
+    }
+}
+
+impl ValueDefault for BackgroundOrigin {
+    type ComputedValue = BackgroundOrigin;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundOrigin::initial_value()
+    }
+}
+
+/// Values for the `background-clip` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-clip
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundClip(pub Vec<BoxArea>);
+
+impl BackgroundClip {
+    pub fn initial_value() -> BackgroundClip {
+        BackgroundClip(vec![BoxArea::BorderBox])
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(BoxArea::parse)
+            .map(BackgroundClip)
+    }
+
+    pub fn layer(&self, index: usize) -> BoxArea {
+        layer(&self.0, index)
+    }
+}
+
+impl ValueDefault for BackgroundClip {
+    type ComputedValue = BackgroundClip;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BackgroundClip::initial_value()
+    }
+}
+
+/// One of the boxes of a box, which the image of a background layer is positioned within or
+/// clipped to.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#typedef-box
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoxArea {
+    BorderBox,
+    PaddingBox,
+    ContentBox,
+}
+
+impl BoxArea {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "border-box" => Ok(BoxArea::BorderBox),
+            "padding-box" => Ok(BoxArea::PaddingBox),
+            "content-box" => Ok(BoxArea::ContentBox),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::style::values::computed::length::CSSPixelLength;
+use crate::style::values::computed::length::{CSSPixelLength, LengthPercentage};
 use crate::style::values::computed::{
     ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
 };
@@ -351,6 +351,44 @@ impl ValueDefault for specified::BorderTopWidth {
     }
 }
 
+/// Computed value of a `border-<corner>-radius` property.  Percentages of the horizontal radius
+/// refer to the width of the border box, and those of the vertical radius to its height.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#border-radius
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderCornerRadius {
+    pub horizontal: LengthPercentage,
+    pub vertical: LengthPercentage,
+}
+
+impl BorderCornerRadius {
+    pub fn initial_value() -> BorderCornerRadius {
+        BorderCornerRadius {
+            horizontal: LengthPercentage::new_len(0.),
+            vertical: LengthPercentage::new_len(0.),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::BorderCornerRadius {
+    type ComputedValue = BorderCornerRadius;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        BorderCornerRadius {
+            horizontal: self.horizontal.compute_value(),
+            vertical: self.vertical.compute_value(),
+        }
+    }
+}
+
+impl ValueDefault for specified::BorderCornerRadius {
+    type ComputedValue = BorderCornerRadius;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BorderCornerRadius::initial_value()
+    }
+}
+
 /// The border `<line-style>` type.
 ///
 /// https://www.w3.org/TR/2017/CR-css-backgrounds-3-20171017/#typedef-line-style
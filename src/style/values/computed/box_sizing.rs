@@ -0,0 +1,39 @@
+use crate::style::values::computed::{ComputeContext, ValueDefault};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Determines which box the sizing properties (`width`, `height`, and their `min-` and `max-`
+/// counterparts) refer to.
+///
+/// https://www.w3.org/TR/css-sizing-3/#box-sizing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoxSizing {
+    /// The sizing properties refer to the content box.
+    ContentBox,
+    /// The sizing properties refer to the border box, so padding and borders are subtracted from
+    /// them to find the size of the content box.
+    BorderBox,
+}
+
+impl BoxSizing {
+    pub fn initial_value() -> Self {
+        BoxSizing::ContentBox
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "content-box" => Ok(BoxSizing::ContentBox),
+            "border-box" => Ok(BoxSizing::BorderBox),
+        }
+    }
+}
+
+impl ValueDefault for BoxSizing {
+    type ComputedValue = BoxSizing;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BoxSizing::initial_value()
+    }
+}
@@ -33,7 +33,7 @@ impl Color {
             red: 255,
             green: 255,
             blue: 255,
-            alpha: 1,
+            alpha: 255,
         })
     }
 
@@ -42,7 +42,7 @@ impl Color {
             red: 0,
             green: 0,
             blue: 0,
-            alpha: 1,
+            alpha: 255,
         })
     }
 }
@@ -1,5 +1,6 @@
 use crate::style::values::computed::{ComputeContext, ValueDefault};
 use crate::style::StyleParseErrorKind;
+use crate::Side;
 use cssparser::{ParseError, Parser};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -71,6 +72,40 @@ impl WritingMode {
             WritingMode::HorizontalTb => true,
         }
     }
+
+    /// Maps a flow-relative side of a box with this writing mode and the given `direction` to the
+    /// physical side it corresponds to.  The flow-relative side is given as a `Side`, where
+    /// `Left`, `Right`, `Top`, and `Bottom` stand for the inline-start, inline-end, block-start,
+    /// and block-end sides respectively.
+    ///
+    /// https://drafts.csswg.org/css-writing-modes-4/#logical-to-physical
+    pub fn physical_side(self, direction: Direction, flow_relative_side: Side) -> Side {
+        let (inline_start, inline_end) = match (self, direction) {
+            (WritingMode::HorizontalTb, Direction::Ltr) => (Side::Left, Side::Right),
+            (WritingMode::HorizontalTb, Direction::Rtl) => (Side::Right, Side::Left),
+            (
+                WritingMode::VerticalRl | WritingMode::VerticalLr | WritingMode::SidewaysRl,
+                Direction::Ltr,
+            )
+            | (WritingMode::SidewaysLr, Direction::Rtl) => (Side::Top, Side::Bottom),
+            (
+                WritingMode::VerticalRl | WritingMode::VerticalLr | WritingMode::SidewaysRl,
+                Direction::Rtl,
+            )
+            | (WritingMode::SidewaysLr, Direction::Ltr) => (Side::Bottom, Side::Top),
+        };
+        let (block_start, block_end) = match self {
+            WritingMode::HorizontalTb => (Side::Top, Side::Bottom),
+            WritingMode::VerticalRl | WritingMode::SidewaysRl => (Side::Right, Side::Left),
+            WritingMode::VerticalLr | WritingMode::SidewaysLr => (Side::Left, Side::Right),
+        };
+        match flow_relative_side {
+            Side::Left => inline_start,
+            Side::Right => inline_end,
+            Side::Top => block_start,
+            Side::Bottom => block_end,
+        }
+    }
 }
 
 impl ValueDefault for WritingMode {
@@ -21,24 +21,24 @@ use cssparser::{ParseError, Parser};
 pub enum Display {
     None,
     Block,
-    //    FlowRoot,
+    FlowRoot,
     Inline,
-    //    InlineBlock,
+    InlineBlock,
     //    ListItem,
-    //    Table,
-    //    InlineTable,
-    //    TableRowGroup,
-    //    TableColumn,
-    //    TableColumnGroup,
-    //    TableHeaderGroup,
-    //    TableFooterGroup,
-    //    TableRow,
-    //    TableCell,
-    //    TableCaption,
-    //    Flex,
-    //    InlineFlex,
-    //    Grid,
-    //    InlineGrid,
+    Table,
+    InlineTable,
+    TableRowGroup,
+    TableColumn,
+    TableColumnGroup,
+    TableHeaderGroup,
+    TableFooterGroup,
+    TableRow,
+    TableCell,
+    TableCaption,
+    Flex,
+    InlineFlex,
+    Grid,
+    InlineGrid,
 }
 
 /// https://www.w3.org/TR/2019/CR-css-display-3-20190711/#property-index
@@ -49,13 +49,75 @@ impl Display {
         try_match_ident_ignore_ascii_case! { input,
             "none" => Ok(Display::None),
             "block" => Ok(Display::Block),
+            "flow-root" => Ok(Display::FlowRoot),
             "inline" => Ok(Display::Inline),
+            "inline-block" => Ok(Display::InlineBlock),
+            "table" => Ok(Display::Table),
+            "inline-table" => Ok(Display::InlineTable),
+            "table-row-group" => Ok(Display::TableRowGroup),
+            "table-column" => Ok(Display::TableColumn),
+            "table-column-group" => Ok(Display::TableColumnGroup),
+            "table-header-group" => Ok(Display::TableHeaderGroup),
+            "table-footer-group" => Ok(Display::TableFooterGroup),
+            "table-row" => Ok(Display::TableRow),
+            "table-cell" => Ok(Display::TableCell),
+            "table-caption" => Ok(Display::TableCaption),
+            "flex" => Ok(Display::Flex),
+            "inline-flex" => Ok(Display::InlineFlex),
+            "grid" => Ok(Display::Grid),
+            "inline-grid" => Ok(Display::InlineGrid),
         }
     }
 
     pub fn initial_value() -> Self {
         Display::Inline
     }
+
+    /// Returns the block-level equivalent of this display type, used for boxes whose outer display
+    /// type is forced to be block-level, such as flex and grid items.
+    ///
+    /// https://drafts.csswg.org/css-display-3/#blockify
+    pub fn blockified(self) -> Self {
+        match self {
+            Display::Inline
+            | Display::InlineBlock
+            | Display::TableRowGroup
+            | Display::TableColumn
+            | Display::TableColumnGroup
+            | Display::TableHeaderGroup
+            | Display::TableFooterGroup
+            | Display::TableRow
+            | Display::TableCell
+            | Display::TableCaption => Display::Block,
+            Display::InlineFlex => Display::Flex,
+            Display::InlineGrid => Display::Grid,
+            Display::InlineTable => Display::Table,
+            Display::None
+            | Display::Block
+            | Display::FlowRoot
+            | Display::Flex
+            | Display::Grid
+            | Display::Table => self,
+        }
+    }
+
+    /// Whether this is one of the internal table display types, which only generate proper boxes
+    /// when parented by the matching table box.
+    ///
+    /// https://www.w3.org/TR/css-display-3/#layout-specific-display
+    pub fn is_internal_table(self) -> bool {
+        matches!(
+            self,
+            Display::TableRowGroup
+                | Display::TableColumn
+                | Display::TableColumnGroup
+                | Display::TableHeaderGroup
+                | Display::TableFooterGroup
+                | Display::TableRow
+                | Display::TableCell
+                | Display::TableCaption
+        )
+    }
 }
 
 impl ValueDefault for Display {
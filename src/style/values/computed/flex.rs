@@ -0,0 +1,214 @@
+use crate::style::values::computed::sizing::Size;
+use crate::style::values::computed::{ComputeContext, ComputeValueWithContext, ValueDefault};
+use crate::style::values::{specified, CSSFloat, CSSInteger};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// https://drafts.csswg.org/css-flexbox-1/#flex-direction-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlexDirection {
+    Row,
+    RowReverse,
+    Column,
+    ColumnReverse,
+}
+
+impl FlexDirection {
+    pub fn initial_value() -> Self {
+        FlexDirection::Row
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "row" => Ok(FlexDirection::Row),
+            "row-reverse" => Ok(FlexDirection::RowReverse),
+            "column" => Ok(FlexDirection::Column),
+            "column-reverse" => Ok(FlexDirection::ColumnReverse),
+        }
+    }
+
+    /// Whether the main axis of the flex container is its inline axis.
+    pub fn is_row(self) -> bool {
+        match self {
+            FlexDirection::Row | FlexDirection::RowReverse => true,
+            FlexDirection::Column | FlexDirection::ColumnReverse => false,
+        }
+    }
+
+    /// Whether the main-start and main-end directions are swapped.
+    pub fn is_reverse(self) -> bool {
+        match self {
+            FlexDirection::RowReverse | FlexDirection::ColumnReverse => true,
+            FlexDirection::Row | FlexDirection::Column => false,
+        }
+    }
+}
+
+impl ValueDefault for FlexDirection {
+    type ComputedValue = FlexDirection;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        FlexDirection::initial_value()
+    }
+}
+
+/// https://drafts.csswg.org/css-flexbox-1/#flex-wrap-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlexWrap {
+    Nowrap,
+    Wrap,
+    WrapReverse,
+}
+
+impl FlexWrap {
+    pub fn initial_value() -> Self {
+        FlexWrap::Nowrap
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "nowrap" => Ok(FlexWrap::Nowrap),
+            "wrap" => Ok(FlexWrap::Wrap),
+            "wrap-reverse" => Ok(FlexWrap::WrapReverse),
+        }
+    }
+}
+
+impl ValueDefault for FlexWrap {
+    type ComputedValue = FlexWrap;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        FlexWrap::initial_value()
+    }
+}
+
+/// The `flex-grow` property, which takes a non-negative `<number>`.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-grow-property
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlexGrow(pub CSSFloat);
+
+impl FlexGrow {
+    pub fn initial_value() -> Self {
+        FlexGrow(0.)
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        parse_non_negative_number(input).map(FlexGrow)
+    }
+}
+
+impl ValueDefault for FlexGrow {
+    type ComputedValue = FlexGrow;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        FlexGrow::initial_value()
+    }
+}
+
+/// The `flex-shrink` property, which takes a non-negative `<number>`.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-shrink-property
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlexShrink(pub CSSFloat);
+
+impl FlexShrink {
+    pub fn initial_value() -> Self {
+        FlexShrink(1.)
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        parse_non_negative_number(input).map(FlexShrink)
+    }
+}
+
+impl ValueDefault for FlexShrink {
+    type ComputedValue = FlexShrink;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        FlexShrink::initial_value()
+    }
+}
+
+fn parse_non_negative_number<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<CSSFloat, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let number = input.expect_number()?;
+    if number < 0. {
+        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+    }
+    Ok(number)
+}
+
+/// Computed value of `flex-basis`.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-basis-property
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FlexBasis {
+    /// Automatically size the item based on its content.
+    Content,
+    /// Any value accepted by the `width` property.
+    Width(Size),
+}
+
+impl FlexBasis {
+    pub fn initial_value() -> Self {
+        FlexBasis::Width(Size::Auto)
+    }
+}
+
+impl ComputeValueWithContext for specified::FlexBasis {
+    type ComputedValue = FlexBasis;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::FlexBasis::Content => FlexBasis::Content,
+            specified::FlexBasis::Width(width) => {
+                FlexBasis::Width(width.compute_value_with_context(context).size)
+            }
+        }
+    }
+}
+
+impl ValueDefault for specified::FlexBasis {
+    type ComputedValue = FlexBasis;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        FlexBasis::initial_value()
+    }
+}
+
+/// The `order` property, which controls the order in which flex and grid items are laid out.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#order-property
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Order(pub CSSInteger);
+
+impl Order {
+    pub fn initial_value() -> Self {
+        Order(0)
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input.expect_integer().map(Order).map_err(|e| e.into())
+    }
+}
+
+impl ValueDefault for Order {
+    type ComputedValue = Order;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Order::initial_value()
+    }
+}
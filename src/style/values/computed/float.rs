@@ -0,0 +1,84 @@
+use crate::style::values::computed::{ComputeContext, ValueDefault};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Determines whether a box is floated, and if so, which side of its containing block it's
+/// pushed to.
+///
+/// https://www.w3.org/TR/CSS2/visuren.html#float-position
+/// https://drafts.csswg.org/css-logical-1/#float-clear
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Float {
+    None,
+    Left,
+    Right,
+    InlineStart,
+    InlineEnd,
+}
+
+impl Float {
+    pub fn initial_value() -> Self {
+        Float::None
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(Float::None),
+            "left" => Ok(Float::Left),
+            "right" => Ok(Float::Right),
+            "inline-start" => Ok(Float::InlineStart),
+            "inline-end" => Ok(Float::InlineEnd),
+        }
+    }
+}
+
+impl ValueDefault for Float {
+    type ComputedValue = Float;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Float::initial_value()
+    }
+}
+
+/// Indicates which sides of a box may not be adjacent to an earlier float.
+///
+/// https://www.w3.org/TR/CSS2/visuren.html#flow-control
+/// https://drafts.csswg.org/css-logical-1/#float-clear
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Clear {
+    None,
+    Left,
+    Right,
+    Both,
+    InlineStart,
+    InlineEnd,
+}
+
+impl Clear {
+    pub fn initial_value() -> Self {
+        Clear::None
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(Clear::None),
+            "left" => Ok(Clear::Left),
+            "right" => Ok(Clear::Right),
+            "both" => Ok(Clear::Both),
+            "inline-start" => Ok(Clear::InlineStart),
+            "inline-end" => Ok(Clear::InlineEnd),
+        }
+    }
+}
+
+impl ValueDefault for Clear {
+    type ComputedValue = Clear;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Clear::initial_value()
+    }
+}
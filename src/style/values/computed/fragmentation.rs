@@ -0,0 +1,113 @@
+use crate::style::values::computed::{ComputeContext, ValueDefault};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// The `break-before` and `break-after` properties, which control whether the fragmentation of
+/// content into pages or columns may, must, or must not break between a box and its siblings.
+///
+/// https://drafts.csswg.org/css-break-3/#break-between
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreakBetween {
+    Auto,
+    Avoid,
+    AvoidPage,
+    Page,
+    Left,
+    Right,
+    Recto,
+    Verso,
+    AvoidColumn,
+    Column,
+}
+
+impl BreakBetween {
+    pub fn initial_value() -> Self {
+        BreakBetween::Auto
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(BreakBetween::Auto),
+            "avoid" => Ok(BreakBetween::Avoid),
+            "avoid-page" => Ok(BreakBetween::AvoidPage),
+            "page" => Ok(BreakBetween::Page),
+            "left" => Ok(BreakBetween::Left),
+            "right" => Ok(BreakBetween::Right),
+            "recto" => Ok(BreakBetween::Recto),
+            "verso" => Ok(BreakBetween::Verso),
+            "avoid-column" => Ok(BreakBetween::AvoidColumn),
+            "column" => Ok(BreakBetween::Column),
+        }
+    }
+
+    /// Determines if this value forces a page break.
+    ///
+    /// TODO: `left`, `right`, `recto`, and `verso` should insert a blank page when the break
+    /// would otherwise land on a page of the wrong side.
+    pub fn forces_page_break(self) -> bool {
+        matches!(
+            self,
+            BreakBetween::Page
+                | BreakBetween::Left
+                | BreakBetween::Right
+                | BreakBetween::Recto
+                | BreakBetween::Verso
+        )
+    }
+
+    /// Determines if this value avoids a page break.
+    pub fn avoids_page_break(self) -> bool {
+        matches!(self, BreakBetween::Avoid | BreakBetween::AvoidPage)
+    }
+}
+
+impl ValueDefault for BreakBetween {
+    type ComputedValue = BreakBetween;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BreakBetween::initial_value()
+    }
+}
+
+/// The `break-inside` property, which controls whether fragmentation may break within a box.
+///
+/// https://drafts.csswg.org/css-break-3/#break-within
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreakInside {
+    Auto,
+    Avoid,
+    AvoidPage,
+    AvoidColumn,
+}
+
+impl BreakInside {
+    pub fn initial_value() -> Self {
+        BreakInside::Auto
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(BreakInside::Auto),
+            "avoid" => Ok(BreakInside::Avoid),
+            "avoid-page" => Ok(BreakInside::AvoidPage),
+            "avoid-column" => Ok(BreakInside::AvoidColumn),
+        }
+    }
+
+    /// Determines if this value avoids page breaks within the box.
+    pub fn avoids_page_break(self) -> bool {
+        matches!(self, BreakInside::Avoid | BreakInside::AvoidPage)
+    }
+}
+
+impl ValueDefault for BreakInside {
+    type ComputedValue = BreakInside;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BreakInside::initial_value()
+    }
+}
@@ -0,0 +1,47 @@
+use crate::style::values::computed::length::LengthPercentage;
+use crate::style::values::computed::{
+    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
+};
+use crate::style::values::specified;
+
+/// Computed value of `row-gap` or `column-gap`.
+#[derive(Clone, Copy, Debug)]
+pub struct Gap {
+    pub size: LengthPercentage,
+}
+
+impl Gap {
+    pub fn initial_value() -> Gap {
+        Gap {
+            size: LengthPercentage::new_len(0.),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Gap {
+    type ComputedValue = Gap;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        let size = match self {
+            // TODO: `normal` is `1em` in multi-column containers, which we don't support yet.
+            specified::Gap::Normal => LengthPercentage::new_len(0.),
+            specified::Gap::LengthPercentage(lp) => match lp {
+                specified::LengthPercentage::Length(no_calc_length) => {
+                    no_calc_length.compute_value().into()
+                }
+                specified::LengthPercentage::Percentage(percentage) => {
+                    LengthPercentage::Percentage(*percentage)
+                }
+            },
+        };
+        Gap { size }
+    }
+}
+
+impl ValueDefault for specified::Gap {
+    type ComputedValue = Gap;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Gap::initial_value()
+    }
+}
@@ -0,0 +1,491 @@
+use crate::style::values::computed::length::LengthPercentage;
+use crate::style::values::computed::{
+    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
+};
+use crate::style::values::{specified, CSSFloat, CSSInteger};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+use std::ops::Range;
+
+/// Parses a `<custom-ident>`, excluding the keywords that would make grid properties ambiguous.
+///
+/// https://drafts.csswg.org/css-values-4/#custom-idents
+pub fn parse_custom_ident<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<String, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let ident = input.expect_ident()?;
+    match_ignore_ascii_case! { ident,
+        "auto" | "span" | "initial" | "inherit" | "unset" | "default" => {
+            Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+        },
+        _ => Ok(ident.to_string()),
+    }
+}
+
+/// A single track sizing function.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-track-breadth
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackBreadth {
+    LengthPercentage(LengthPercentage),
+    /// A flexible length, in `fr` units.
+    Flex(CSSFloat),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+impl TrackBreadth {
+    /// Whether this is an intrinsic sizing function, whose size depends on the track's contents.
+    pub fn is_intrinsic(self) -> bool {
+        match self {
+            TrackBreadth::Auto | TrackBreadth::MinContent | TrackBreadth::MaxContent => true,
+            TrackBreadth::LengthPercentage(_) | TrackBreadth::Flex(_) => false,
+        }
+    }
+}
+
+impl ComputeValue for specified::TrackBreadth {
+    type ComputedValue = TrackBreadth;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        match self {
+            specified::TrackBreadth::LengthPercentage(lp) => {
+                TrackBreadth::LengthPercentage(lp.compute_value())
+            }
+            specified::TrackBreadth::Flex(flex) => TrackBreadth::Flex(*flex),
+            specified::TrackBreadth::Auto => TrackBreadth::Auto,
+            specified::TrackBreadth::MinContent => TrackBreadth::MinContent,
+            specified::TrackBreadth::MaxContent => TrackBreadth::MaxContent,
+        }
+    }
+}
+
+/// The computed size of a grid track, as a pair of minimum and maximum track sizing functions.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-track-size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackSize {
+    pub min: TrackBreadth,
+    pub max: TrackBreadth,
+}
+
+impl TrackSize {
+    pub fn auto() -> TrackSize {
+        TrackSize {
+            min: TrackBreadth::Auto,
+            max: TrackBreadth::Auto,
+        }
+    }
+}
+
+impl ComputeValue for specified::TrackSize {
+    type ComputedValue = TrackSize;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        match self {
+            // A flexible length on its own is treated as `minmax(auto, <flex>)`.
+            specified::TrackSize::Breadth(specified::TrackBreadth::Flex(flex)) => TrackSize {
+                min: TrackBreadth::Auto,
+                max: TrackBreadth::Flex(*flex),
+            },
+            specified::TrackSize::Breadth(breadth) => {
+                let breadth = breadth.compute_value();
+                TrackSize {
+                    min: breadth,
+                    max: breadth,
+                }
+            }
+            specified::TrackSize::Minmax(min, max) => TrackSize {
+                min: min.compute_value(),
+                max: max.compute_value(),
+            },
+        }
+    }
+}
+
+/// The number of times the tracks of a `repeat()` are repeated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RepeatCount {
+    Number(u32),
+    /// Repeat as many times as will fit in the grid container.
+    AutoFill,
+    /// Like `auto-fill`, but empty repeated tracks are collapsed.
+    AutoFit,
+}
+
+/// A computed `repeat()` function.
+///
+/// https://drafts.csswg.org/css-grid-1/#repeat-notation
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackRepeat {
+    pub count: RepeatCount,
+    /// The line names before each track, and after the last.  There is always one more entry here
+    /// than in `track_sizes`.
+    pub line_names: Vec<Vec<String>>,
+    pub track_sizes: Vec<TrackSize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrackListValue {
+    TrackSize(TrackSize),
+    Repeat(TrackRepeat),
+}
+
+/// A computed track list, as given to `grid-template-rows` and `grid-template-columns`.
+///
+/// https://drafts.csswg.org/css-grid-1/#track-sizing
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackList {
+    /// The line names before each value, and after the last.  There is always one more entry here
+    /// than in `values`.
+    pub line_names: Vec<Vec<String>>,
+    pub values: Vec<TrackListValue>,
+}
+
+/// Computed value of `grid-template-rows` or `grid-template-columns`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridTemplateComponent {
+    None,
+    TrackList(TrackList),
+}
+
+impl GridTemplateComponent {
+    pub fn initial_value() -> Self {
+        GridTemplateComponent::None
+    }
+}
+
+impl ComputeValueWithContext for specified::GridTemplateComponent {
+    type ComputedValue = GridTemplateComponent;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        match self {
+            specified::GridTemplateComponent::None => GridTemplateComponent::None,
+            specified::GridTemplateComponent::TrackList(track_list) => {
+                GridTemplateComponent::TrackList(TrackList {
+                    line_names: track_list.line_names.clone(),
+                    values: track_list
+                        .values
+                        .iter()
+                        .map(|value| match value {
+                            specified::TrackListValue::TrackSize(track_size) => {
+                                TrackListValue::TrackSize(track_size.compute_value())
+                            }
+                            specified::TrackListValue::Repeat(repeat) => {
+                                TrackListValue::Repeat(TrackRepeat {
+                                    count: repeat.count,
+                                    line_names: repeat.line_names.clone(),
+                                    track_sizes: repeat
+                                        .track_sizes
+                                        .iter()
+                                        .map(ComputeValue::compute_value)
+                                        .collect(),
+                                })
+                            }
+                        })
+                        .collect(),
+                })
+            }
+        }
+    }
+}
+
+impl ValueDefault for specified::GridTemplateComponent {
+    type ComputedValue = GridTemplateComponent;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        GridTemplateComponent::initial_value()
+    }
+}
+
+/// Computed value of `grid-auto-rows` or `grid-auto-columns`, the sizes of implicitly-created
+/// tracks.  The sizes are repeated as many times as needed.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-tracks
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImplicitGridTracks(pub Vec<TrackSize>);
+
+impl ImplicitGridTracks {
+    pub fn initial_value() -> Self {
+        ImplicitGridTracks(vec![TrackSize::auto()])
+    }
+}
+
+impl ComputeValueWithContext for specified::ImplicitGridTracks {
+    type ComputedValue = ImplicitGridTracks;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        ImplicitGridTracks(self.0.iter().map(ComputeValue::compute_value).collect())
+    }
+}
+
+impl ValueDefault for specified::ImplicitGridTracks {
+    type ComputedValue = ImplicitGridTracks;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        ImplicitGridTracks::initial_value()
+    }
+}
+
+/// https://drafts.csswg.org/css-grid-1/#grid-auto-flow-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GridAutoFlow {
+    /// Whether auto-placed items fill each column in turn, rather than each row.
+    pub column: bool,
+    /// Whether auto-placement fills holes left earlier in the grid.
+    pub dense: bool,
+}
+
+impl GridAutoFlow {
+    pub fn initial_value() -> Self {
+        GridAutoFlow {
+            column: false,
+            dense: false,
+        }
+    }
+
+    /// Parses `[ row | column ] || dense`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let mut column = None;
+        let mut dense = false;
+        loop {
+            if column.is_none() {
+                if input.try_parse(|i| i.expect_ident_matching("row")).is_ok() {
+                    column = Some(false);
+                    continue;
+                }
+                if input
+                    .try_parse(|i| i.expect_ident_matching("column"))
+                    .is_ok()
+                {
+                    column = Some(true);
+                    continue;
+                }
+            }
+            if !dense
+                && input
+                    .try_parse(|i| i.expect_ident_matching("dense"))
+                    .is_ok()
+            {
+                dense = true;
+                continue;
+            }
+            break;
+        }
+
+        if column.is_none() && !dense {
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        Ok(GridAutoFlow {
+            column: column.unwrap_or(false),
+            dense,
+        })
+    }
+}
+
+impl ValueDefault for GridAutoFlow {
+    type ComputedValue = GridAutoFlow;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        GridAutoFlow::initial_value()
+    }
+}
+
+/// A grid line, as given to the `grid-row-start`, `grid-row-end`, `grid-column-start`, and
+/// `grid-column-end` properties.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-grid-row-start-grid-line
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GridLine {
+    Auto,
+    /// A named area or named line.
+    Ident(String),
+    /// The nth grid line, or the nth line with the given name.  Negative numbers count from the
+    /// end of the explicit grid.
+    Line(CSSInteger, Option<String>),
+    /// Spans n grid lines, or n lines with the given name.
+    Span(u32, Option<String>),
+}
+
+impl GridLine {
+    pub fn initial_value() -> Self {
+        GridLine::Auto
+    }
+
+    /// Parses `auto | <custom-ident> | [ <integer> && <custom-ident>? ] |
+    /// [ span && [ <integer> || <custom-ident> ] ]`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+            return Ok(GridLine::Auto);
+        }
+
+        let location = input.current_source_location();
+        let mut span = false;
+        let mut integer = None;
+        let mut name = None;
+        loop {
+            if !span && input.try_parse(|i| i.expect_ident_matching("span")).is_ok() {
+                span = true;
+                continue;
+            }
+            if integer.is_none() {
+                if let Ok(parsed_integer) = input.try_parse(|i| i.expect_integer()) {
+                    integer = Some(parsed_integer);
+                    continue;
+                }
+            }
+            if name.is_none() {
+                if let Ok(parsed_name) = input.try_parse(parse_custom_ident) {
+                    name = Some(parsed_name);
+                    continue;
+                }
+            }
+            break;
+        }
+
+        let invalid = || Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        match (span, integer, name) {
+            (true, None, None) | (false, None, None) => invalid(),
+            (true, Some(count), _) if count <= 0 => invalid(),
+            (true, count, name) => Ok(GridLine::Span(count.unwrap_or(1) as u32, name)),
+            (false, Some(0), _) => invalid(),
+            (false, Some(line), name) => Ok(GridLine::Line(line, name)),
+            (false, None, Some(name)) => Ok(GridLine::Ident(name)),
+        }
+    }
+}
+
+impl ValueDefault for GridLine {
+    type ComputedValue = GridLine;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        GridLine::initial_value()
+    }
+}
+
+/// A rectangular named grid area defined by `grid-template-areas`, in terms of the indices of the
+/// rows and columns it spans.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NamedArea {
+    pub name: String,
+    pub rows: Range<u32>,
+    pub columns: Range<u32>,
+}
+
+/// https://drafts.csswg.org/css-grid-1/#grid-template-areas-property
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GridTemplateAreas {
+    None,
+    Areas {
+        rows: u32,
+        columns: u32,
+        areas: Vec<NamedArea>,
+    },
+}
+
+impl GridTemplateAreas {
+    pub fn initial_value() -> Self {
+        GridTemplateAreas::None
+    }
+
+    /// Parses `none | <string>+`, where each string is a row of the grid, and each name in a row is
+    /// a cell belonging to the area of that name.  Cells consisting of one or more `.`s don't
+    /// belong to any area.  Every row must have the same number of cells, and every area must be
+    /// rectangular.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(GridTemplateAreas::None);
+        }
+
+        let location = input.current_source_location();
+        let invalid = || location.new_custom_error(StyleParseErrorKind::UnspecifiedError);
+        let mut cells: Vec<Vec<Option<String>>> = Vec::new();
+        while let Ok(string) = input.try_parse(|i| i.expect_string().map(|s| s.to_string())) {
+            let mut row = Vec::new();
+            for cell in string.split_ascii_whitespace() {
+                if cell.chars().all(|c| c == '.') {
+                    row.push(None);
+                } else if cell
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii())
+                {
+                    row.push(Some(cell.to_owned()));
+                } else {
+                    return Err(invalid());
+                }
+            }
+            if row.is_empty() || cells.iter().any(|other| other.len() != row.len()) {
+                return Err(invalid());
+            }
+            cells.push(row);
+        }
+        if cells.is_empty() {
+            return Err(invalid());
+        }
+
+        // Each area is the bounding box of the cells with its name, which must all be filled by
+        // that name for the area to be rectangular.
+        let mut areas: Vec<NamedArea> = Vec::new();
+        for (row_idx, row) in cells.iter().enumerate() {
+            for (column_idx, name) in row.iter().enumerate() {
+                let name = match name {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let (row_idx, column_idx) = (row_idx as u32, column_idx as u32);
+                match areas.iter_mut().find(|area| &area.name == name) {
+                    Some(area) => {
+                        area.rows.end = area.rows.end.max(row_idx + 1);
+                        area.columns.start = area.columns.start.min(column_idx);
+                        area.columns.end = area.columns.end.max(column_idx + 1);
+                    }
+                    None => areas.push(NamedArea {
+                        name: name.clone(),
+                        rows: row_idx..row_idx + 1,
+                        columns: column_idx..column_idx + 1,
+                    }),
+                }
+            }
+        }
+        for area in &areas {
+            for row in &cells[area.rows.start as usize..area.rows.end as usize] {
+                for cell in &row[area.columns.start as usize..area.columns.end as usize] {
+                    if cell.as_ref() != Some(&area.name) {
+                        return Err(invalid());
+                    }
+                }
+            }
+        }
+        let named_cells = cells.iter().flatten().filter(|cell| cell.is_some()).count();
+        let area_cells = areas
+            .iter()
+            .map(|area| area.rows.len() * area.columns.len())
+            .sum::<usize>();
+        if named_cells != area_cells {
+            return Err(invalid());
+        }
+
+        Ok(GridTemplateAreas::Areas {
+            rows: cells.len() as u32,
+            columns: cells[0].len() as u32,
+            areas,
+        })
+    }
+}
+
+impl ValueDefault for GridTemplateAreas {
+    type ComputedValue = GridTemplateAreas;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        GridTemplateAreas::initial_value()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::sizing::{MaxSize, Size};
 use crate::style::values::computed::{
     ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
 };
@@ -7,14 +7,12 @@ use crate::style::values::specified;
 /// Computed value of a `height`.
 #[derive(Clone, Copy, Debug)]
 pub struct Height {
-    pub size: LengthPercentageOrAuto,
+    pub size: Size,
 }
 
 impl Height {
     pub fn initial_value() -> Height {
-        Height {
-            size: LengthPercentageOrAuto::Auto,
-        }
+        Height { size: Size::Auto }
     }
 }
 
@@ -22,27 +20,13 @@ impl ComputeValueWithContext for specified::Height {
     type ComputedValue = Height;
 
     fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
-        let computed_lp_auto: LengthPercentageOrAuto = match self {
-            // TODO: I think we repeat computing the value of specified::LengthPercentageOrAuto a lot...eventually consider
-            // simply implementing `ComputeValueWithContext` for specified::LengthPercentageOrAuto.
-            specified::Height::LengthPercentageOrAuto(lp_auto) => match lp_auto {
-                specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
-                specified::LengthPercentageOrAuto::LengthPercentage(lp) => match lp {
-                    specified::LengthPercentage::Length(no_calc_length) => {
-                        no_calc_length.compute_value().into()
-                    }
-                    specified::LengthPercentage::Percentage(percentage) => {
-                        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(
-                            *percentage,
-                        ))
-                    }
-                },
-            },
+        let size = match self {
+            specified::Height::LengthPercentageOrAuto(lp_auto) => lp_auto.compute_value().into(),
+            specified::Height::IntrinsicSize(intrinsic) => {
+                Size::IntrinsicSize(intrinsic.compute_value())
+            }
         };
-
-        Height {
-            size: computed_lp_auto,
-        }
+        Height { size }
     }
 }
 
@@ -53,3 +37,73 @@ impl ValueDefault for specified::Height {
         Height::initial_value()
     }
 }
+
+/// Computed value of a `min-height`.
+#[derive(Clone, Copy, Debug)]
+pub struct MinHeight {
+    pub size: Size,
+}
+
+impl MinHeight {
+    pub fn initial_value() -> MinHeight {
+        MinHeight { size: Size::Auto }
+    }
+}
+
+impl ComputeValueWithContext for specified::MinHeight {
+    type ComputedValue = MinHeight;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        let size = match self {
+            specified::MinHeight::LengthPercentageOrAuto(lp_auto) => lp_auto.compute_value().into(),
+            specified::MinHeight::IntrinsicSize(intrinsic) => {
+                Size::IntrinsicSize(intrinsic.compute_value())
+            }
+        };
+        MinHeight { size }
+    }
+}
+
+impl ValueDefault for specified::MinHeight {
+    type ComputedValue = MinHeight;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        MinHeight::initial_value()
+    }
+}
+
+/// Computed value of a `max-height`.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxHeight {
+    pub size: MaxSize,
+}
+
+impl MaxHeight {
+    pub fn initial_value() -> MaxHeight {
+        MaxHeight {
+            size: MaxSize::None,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::MaxHeight {
+    type ComputedValue = MaxHeight;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        let size = match self {
+            specified::MaxHeight::LengthPercentageOrNone(lp_none) => lp_none.compute_value(),
+            specified::MaxHeight::IntrinsicSize(intrinsic) => {
+                MaxSize::IntrinsicSize(intrinsic.compute_value())
+            }
+        };
+        MaxHeight { size }
+    }
+}
+
+impl ValueDefault for specified::MaxHeight {
+    type ComputedValue = MaxHeight;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        MaxHeight::initial_value()
+    }
+}
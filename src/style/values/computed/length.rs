@@ -247,3 +247,31 @@ impl ComputeValue for specified::NoCalcLength {
         }
     }
 }
+
+impl ComputeValue for specified::LengthPercentage {
+    type ComputedValue = LengthPercentage;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        match self {
+            specified::LengthPercentage::Length(no_calc_length) => {
+                LengthPercentage::Length(no_calc_length.compute_value())
+            }
+            specified::LengthPercentage::Percentage(percentage) => {
+                LengthPercentage::Percentage(*percentage)
+            }
+        }
+    }
+}
+
+impl ComputeValue for specified::LengthPercentageOrAuto {
+    type ComputedValue = LengthPercentageOrAuto;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        match self {
+            specified::LengthPercentageOrAuto::LengthPercentage(lp) => {
+                LengthPercentageOrAuto::LengthPercentage(lp.compute_value())
+            }
+            specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
+        }
+    }
+}
@@ -1,26 +1,39 @@
 /// Some of this code was taken from Servo: https://github.com/servo/servo
 /// Kosmonaut complies with Servo's license, the Mozilla Public License 2.0.
+pub mod align;
 pub mod background;
 pub mod border;
+pub mod box_sizing;
 pub mod color;
 pub mod direction;
 pub mod display;
+pub mod flex;
+pub mod float;
 pub mod font;
+pub mod fragmentation;
+pub mod gap;
+pub mod grid;
 pub mod height;
 pub mod length;
 pub mod margin;
+pub mod opacity;
+pub mod overflow;
 pub mod padding;
 pub mod percentage;
+pub mod position;
+pub mod shadow;
+pub mod sizing;
+pub mod table;
 pub mod width;
 
-use crate::style::values::computed::height::Height;
+use crate::style::values::computed::height::{Height, MaxHeight, MinHeight};
 pub use crate::style::values::computed::margin::{
     MarginBottom, MarginLeft, MarginRight, MarginTop,
 };
 pub use crate::style::values::computed::padding::{
     PaddingBottom, PaddingLeft, PaddingRight, PaddingTop,
 };
-use crate::style::values::computed::width::Width;
+use crate::style::values::computed::width::{MaxWidth, MinWidth, Width};
 
 use crate::dom::tree::NodeRef;
 use crate::style::properties::id::LonghandId;
@@ -32,19 +45,39 @@ use crate::style::values::computed::length::{
     CSSPixelLength, LengthPercentage, LengthPercentageOrAuto,
 };
 use crate::Side;
-pub use background::BackgroundColor;
+pub use align::{AlignContent, AlignItems, AlignSelf, JustifyContent};
+pub use background::{
+    BackgroundClip, BackgroundColor, BackgroundImage, BackgroundOrigin, BackgroundPositionX,
+    BackgroundPositionY, BackgroundRepeat, BackgroundSize, BgRepeat, BoxArea, RepeatStyle,
+};
 pub use border::LineStyle;
 pub use border::{
-    border_side_initial_style, BorderBottomColor, BorderBottomWidth, BorderLeftColor,
-    BorderLeftWidth, BorderRightColor, BorderRightWidth, BorderTopColor, BorderTopWidth,
+    border_side_initial_style, BorderBottomColor, BorderBottomWidth, BorderCornerRadius,
+    BorderLeftColor, BorderLeftWidth, BorderRightColor, BorderRightWidth, BorderTopColor,
+    BorderTopWidth,
 };
+pub use box_sizing::BoxSizing;
 pub use color::Color;
 use cssparser::RGBA;
 pub use direction::Direction;
 pub use display::Display;
+pub use flex::{FlexBasis, FlexDirection, FlexGrow, FlexShrink, FlexWrap, Order};
+pub use float::{Clear, Float};
 pub use font::FontSize;
+pub use fragmentation::{BreakBetween, BreakInside};
+pub use gap::Gap;
+pub use grid::{
+    GridAutoFlow, GridLine, GridTemplateAreas, GridTemplateComponent, ImplicitGridTracks,
+    NamedArea, RepeatCount, TrackBreadth, TrackList, TrackListValue, TrackRepeat, TrackSize,
+};
+pub use opacity::Opacity;
+pub use overflow::Overflow;
 pub use percentage::Percentage;
+pub use position::{Bottom, Left, Position, Right, Top, ZIndex};
+pub use shadow::{BoxShadow, TextShadow};
+pub use sizing::{IntrinsicSize, MaxSize, Size};
 use strum::IntoEnumIterator;
+pub use table::{BorderCollapse, BorderSpacing, CaptionSide, TableLayout};
 
 /// A trait to represent the conversion between computed and specified values where a context is
 /// required to properly compute the specified value.
@@ -87,11 +120,26 @@ pub trait ValueDefault {
 /// https://www.w3.org/TR/2018/CR-css-cascade-3-20180828/#computed-value
 #[derive(Debug, Clone, Builder)]
 pub struct ComputedValues {
+    pub align_content: AlignContent,
+    pub align_items: AlignItems,
+    pub align_self: AlignSelf,
+    pub background_clip: BackgroundClip,
     pub background_color: BackgroundColor,
+    pub background_image: BackgroundImage,
+    pub background_origin: BackgroundOrigin,
+    pub background_position_x: BackgroundPositionX,
+    pub background_position_y: BackgroundPositionY,
+    pub background_repeat: BackgroundRepeat,
+    pub background_size: BackgroundSize,
     pub border_bottom_color: BorderBottomColor,
+    pub border_bottom_left_radius: BorderCornerRadius,
+    pub border_bottom_right_radius: BorderCornerRadius,
+    pub border_collapse: BorderCollapse,
     pub border_left_color: BorderLeftColor,
     pub border_right_color: BorderRightColor,
     pub border_top_color: BorderTopColor,
+    pub border_top_left_radius: BorderCornerRadius,
+    pub border_top_right_radius: BorderCornerRadius,
     pub border_bottom_style: LineStyle,
     pub border_left_style: LineStyle,
     pub border_right_style: LineStyle,
@@ -100,21 +148,64 @@ pub struct ComputedValues {
     pub border_left_width: BorderLeftWidth,
     pub border_right_width: BorderRightWidth,
     pub border_top_width: BorderTopWidth,
+    pub border_spacing: BorderSpacing,
+    pub bottom: Bottom,
+    pub box_shadow: BoxShadow,
+    pub box_sizing: BoxSizing,
+    pub break_after: BreakBetween,
+    pub break_before: BreakBetween,
+    pub break_inside: BreakInside,
+    pub caption_side: CaptionSide,
+    pub clear: Clear,
     pub color: Color,
     pub direction: Direction,
+    pub column_gap: Gap,
     pub display: Display,
+    pub flex_basis: FlexBasis,
+    pub flex_direction: FlexDirection,
+    pub flex_grow: FlexGrow,
+    pub flex_shrink: FlexShrink,
+    pub flex_wrap: FlexWrap,
+    pub float: Float,
     pub font_size: FontSize,
+    pub grid_auto_columns: ImplicitGridTracks,
+    pub grid_auto_flow: GridAutoFlow,
+    pub grid_auto_rows: ImplicitGridTracks,
+    pub grid_column_end: GridLine,
+    pub grid_column_start: GridLine,
+    pub grid_row_end: GridLine,
+    pub grid_row_start: GridLine,
+    pub grid_template_areas: GridTemplateAreas,
+    pub grid_template_columns: GridTemplateComponent,
+    pub grid_template_rows: GridTemplateComponent,
     pub height: Height,
+    pub justify_content: JustifyContent,
+    pub left: Left,
     pub margin_bottom: MarginBottom,
     pub margin_left: MarginLeft,
     pub margin_right: MarginRight,
     pub margin_top: MarginTop,
+    pub max_height: MaxHeight,
+    pub max_width: MaxWidth,
+    pub min_height: MinHeight,
+    pub min_width: MinWidth,
+    pub opacity: Opacity,
+    pub order: Order,
+    pub overflow_x: Overflow,
+    pub overflow_y: Overflow,
     pub padding_bottom: PaddingBottom,
     pub padding_left: PaddingLeft,
     pub padding_right: PaddingRight,
     pub padding_top: PaddingTop,
+    pub position: Position,
+    pub right: Right,
+    pub row_gap: Gap,
+    pub table_layout: TableLayout,
+    pub text_shadow: TextShadow,
+    pub top: Top,
     pub width: Width,
     pub writing_mode: WritingMode,
+    pub z_index: ZIndex,
 }
 
 impl ComputedValues {
@@ -136,7 +227,7 @@ impl ComputedValues {
         }
     }
 
-    pub fn logical_width(&self) -> LengthPercentageOrAuto {
+    pub fn logical_width(&self) -> Size {
         if self.writing_mode.is_horizontal() {
             self.width.size
         } else {
@@ -144,7 +235,7 @@ impl ComputedValues {
         }
     }
 
-    pub fn logical_height(&self) -> LengthPercentageOrAuto {
+    pub fn logical_height(&self) -> Size {
         if self.writing_mode.is_horizontal() {
             self.height.size
         } else {
@@ -152,107 +243,51 @@ impl ComputedValues {
         }
     }
 
-    pub fn logical_padding(&self, side: Side) -> LengthPercentage {
-        let horizontal_mode = self.writing_mode.is_horizontal();
+    pub fn padding(&self, side: Side) -> LengthPercentage {
         match side {
-            Side::Bottom => {
-                if horizontal_mode {
-                    self.padding_bottom.size
-                } else {
-                    self.padding_right.size
-                }
-            }
-            Side::Left => {
-                if horizontal_mode {
-                    self.padding_left.size
-                } else {
-                    self.padding_top.size
-                }
-            }
-            Side::Right => {
-                if horizontal_mode {
-                    self.padding_right.size
-                } else {
-                    self.padding_bottom.size
-                }
-            }
-            Side::Top => {
-                if horizontal_mode {
-                    self.padding_top.size
-                } else {
-                    self.padding_left.size
-                }
-            }
+            Side::Bottom => self.padding_bottom.size,
+            Side::Left => self.padding_left.size,
+            Side::Right => self.padding_right.size,
+            Side::Top => self.padding_top.size,
         }
     }
 
-    pub fn logical_border_width(&self, side: Side) -> CSSPixelLength {
-        let horizontal_mode = self.writing_mode.is_horizontal();
+    pub fn border_width(&self, side: Side) -> CSSPixelLength {
         match side {
-            Side::Bottom => {
-                if horizontal_mode {
-                    self.border_bottom_width.size
-                } else {
-                    self.border_right_width.size
-                }
-            }
-            Side::Left => {
-                if horizontal_mode {
-                    self.border_left_width.size
-                } else {
-                    self.border_top_width.size
-                }
-            }
-            Side::Right => {
-                if horizontal_mode {
-                    self.border_right_width.size
-                } else {
-                    self.border_bottom_width.size
-                }
-            }
-            Side::Top => {
-                if horizontal_mode {
-                    self.border_top_width.size
-                } else {
-                    self.border_left_width.size
-                }
-            }
+            Side::Bottom => self.border_bottom_width.size,
+            Side::Left => self.border_left_width.size,
+            Side::Right => self.border_right_width.size,
+            Side::Top => self.border_top_width.size,
         }
     }
 
-    pub fn logical_margin(&self, side: Side) -> LengthPercentageOrAuto {
-        let horizontal_mode = self.writing_mode.is_horizontal();
+    pub fn margin(&self, side: Side) -> LengthPercentageOrAuto {
         match side {
-            Side::Bottom => {
-                if horizontal_mode {
-                    self.margin_bottom.size
-                } else {
-                    self.margin_right.size
-                }
-            }
-            Side::Left => {
-                if horizontal_mode {
-                    self.margin_left.size
-                } else {
-                    self.margin_top.size
-                }
-            }
-            Side::Right => {
-                if horizontal_mode {
-                    self.margin_right.size
-                } else {
-                    self.margin_bottom.size
-                }
-            }
-            Side::Top => {
-                if horizontal_mode {
-                    self.margin_top.size
-                } else {
-                    self.margin_left.size
-                }
-            }
+            Side::Bottom => self.margin_bottom.size,
+            Side::Left => self.margin_left.size,
+            Side::Right => self.margin_right.size,
+            Side::Top => self.margin_top.size,
         }
     }
+
+    /// Gets the padding on a flow-relative side of this element's box, where `Left`, `Right`,
+    /// `Top`, and `Bottom` stand for the inline-start, inline-end, block-start, and block-end
+    /// sides.  See `WritingMode::physical_side`.
+    pub fn logical_padding(&self, side: Side) -> LengthPercentage {
+        self.padding(self.writing_mode.physical_side(self.direction, side))
+    }
+
+    /// Gets the border width on a flow-relative side of this element's box.  See
+    /// `ComputedValues::logical_padding`.
+    pub fn logical_border_width(&self, side: Side) -> CSSPixelLength {
+        self.border_width(self.writing_mode.physical_side(self.direction, side))
+    }
+
+    /// Gets the margin on a flow-relative side of this element's box.  See
+    /// `ComputedValues::logical_padding`.
+    pub fn logical_margin(&self, side: Side) -> LengthPercentageOrAuto {
+        self.margin(self.writing_mode.physical_side(self.direction, side))
+    }
 }
 
 /// Create a default set of computed values.  Likely most useful for the case in which we're working
@@ -264,11 +299,26 @@ impl Default for ComputedValues {
         let initial_color_prop = Color::initial_value();
         let initial_border_style = border_side_initial_style();
         ComputedValues {
+            align_content: AlignContent::initial_value(),
+            align_items: AlignItems::initial_value(),
+            align_self: AlignSelf::initial_value(),
+            background_clip: BackgroundClip::initial_value(),
             background_color: BackgroundColor::initial_value(initial_color_prop.rgba()),
+            background_image: BackgroundImage::initial_value(),
+            background_origin: BackgroundOrigin::initial_value(),
+            background_position_x: BackgroundPositionX::initial_value(),
+            background_position_y: BackgroundPositionY::initial_value(),
+            background_repeat: BackgroundRepeat::initial_value(),
+            background_size: BackgroundSize::initial_value(),
             border_bottom_color: BorderBottomColor::initial_value(initial_color_prop.rgba()),
+            border_bottom_left_radius: BorderCornerRadius::initial_value(),
+            border_bottom_right_radius: BorderCornerRadius::initial_value(),
+            border_collapse: BorderCollapse::initial_value(),
             border_left_color: BorderLeftColor::initial_value(initial_color_prop.rgba()),
             border_right_color: BorderRightColor::initial_value(initial_color_prop.rgba()),
             border_top_color: BorderTopColor::initial_value(initial_color_prop.rgba()),
+            border_top_left_radius: BorderCornerRadius::initial_value(),
+            border_top_right_radius: BorderCornerRadius::initial_value(),
             border_bottom_style: initial_border_style,
             border_left_style: initial_border_style,
             border_right_style: initial_border_style,
@@ -277,21 +327,64 @@ impl Default for ComputedValues {
             border_left_width: BorderLeftWidth::initial_value(initial_border_style),
             border_right_width: BorderRightWidth::initial_value(initial_border_style),
             border_top_width: BorderTopWidth::initial_value(initial_border_style),
+            border_spacing: BorderSpacing::initial_value(),
+            bottom: Bottom::initial_value(),
+            box_shadow: BoxShadow::initial_value(),
+            box_sizing: BoxSizing::initial_value(),
+            break_after: BreakBetween::initial_value(),
+            break_before: BreakBetween::initial_value(),
+            break_inside: BreakInside::initial_value(),
+            caption_side: CaptionSide::initial_value(),
+            clear: Clear::initial_value(),
             color: initial_color_prop,
             direction: Direction::initial_value(),
+            column_gap: Gap::initial_value(),
             display: Display::initial_value(),
+            flex_basis: FlexBasis::initial_value(),
+            flex_direction: FlexDirection::initial_value(),
+            flex_grow: FlexGrow::initial_value(),
+            flex_shrink: FlexShrink::initial_value(),
+            flex_wrap: FlexWrap::initial_value(),
+            float: Float::initial_value(),
             font_size: FontSize::initial_value(),
+            grid_auto_columns: ImplicitGridTracks::initial_value(),
+            grid_auto_flow: GridAutoFlow::initial_value(),
+            grid_auto_rows: ImplicitGridTracks::initial_value(),
+            grid_column_end: GridLine::initial_value(),
+            grid_column_start: GridLine::initial_value(),
+            grid_row_end: GridLine::initial_value(),
+            grid_row_start: GridLine::initial_value(),
+            grid_template_areas: GridTemplateAreas::initial_value(),
+            grid_template_columns: GridTemplateComponent::initial_value(),
+            grid_template_rows: GridTemplateComponent::initial_value(),
             height: Height::initial_value(),
+            justify_content: JustifyContent::initial_value(),
+            left: Left::initial_value(),
             margin_bottom: MarginBottom::initial_value(),
             margin_left: MarginLeft::initial_value(),
             margin_right: MarginRight::initial_value(),
             margin_top: MarginTop::initial_value(),
+            max_height: MaxHeight::initial_value(),
+            max_width: MaxWidth::initial_value(),
+            min_height: MinHeight::initial_value(),
+            min_width: MinWidth::initial_value(),
+            opacity: Opacity::initial_value(),
+            order: Order::initial_value(),
+            overflow_x: Overflow::initial_value(),
+            overflow_y: Overflow::initial_value(),
             padding_bottom: PaddingBottom::initial_value(),
             padding_left: PaddingLeft::initial_value(),
             padding_right: PaddingRight::initial_value(),
             padding_top: PaddingTop::initial_value(),
+            position: Position::initial_value(),
+            right: Right::initial_value(),
+            row_gap: Gap::initial_value(),
+            table_layout: TableLayout::initial_value(),
+            text_shadow: TextShadow::initial_value(),
+            top: Top::initial_value(),
             width: Width::initial_value(),
             writing_mode: WritingMode::initial_value(),
+            z_index: ZIndex::initial_value(),
         }
     }
 }
@@ -316,6 +409,12 @@ pub struct ComputeContext<'a> {
     ///
     /// `None` if these values haven't been computed yet.
     pub computed_border_styles: Option<BorderSideStyleContext>,
+
+    /// The computed values of `overflow-x` and `overflow-y` for the node being computed, which
+    /// depend on each other.
+    ///
+    /// `None` if these values haven't been computed yet.
+    pub computed_overflow: Option<(Overflow, Overflow)>,
 }
 
 impl ComputeContext<'_> {
@@ -341,6 +440,18 @@ impl ComputeContext<'_> {
         self.computed_border_styles
             .expect("border styles properties not yet computed and applied to compute context")
     }
+
+    pub fn overflow_x(&self) -> Overflow {
+        self.overflow().0
+    }
+    pub fn overflow_y(&self) -> Overflow {
+        self.overflow().1
+    }
+
+    fn overflow(&self) -> (Overflow, Overflow) {
+        self.computed_overflow
+            .expect("overflow properties not yet computed and applied to compute context")
+    }
 }
 
 /// Container for computation context information about the `border-<side>-style` properties.
@@ -365,6 +476,7 @@ pub fn compute_values(node: NodeRef) {
         parent_computed_values: &parent_computed_values,
         computed_color: None,
         computed_border_styles: None,
+        computed_overflow: None,
     };
     compute_early_properties(node.clone(), &mut context);
 
@@ -372,16 +484,58 @@ pub fn compute_values(node: NodeRef) {
         match node.contextual_decls().get_by_longhand(longhand) {
             Some(contextual_decl) => {
                 match &contextual_decl.inner_decl {
+                    PropertyDeclaration::AlignContent(align_content) => {
+                        cv_builder.align_content(*align_content);
+                    }
+                    PropertyDeclaration::AlignItems(align_items) => {
+                        cv_builder.align_items(*align_items);
+                    }
+                    PropertyDeclaration::AlignSelf(align_self) => {
+                        cv_builder.align_self(*align_self);
+                    }
+                    PropertyDeclaration::BackgroundClip(background_clip) => {
+                        cv_builder.background_clip(background_clip.clone());
+                    }
                     PropertyDeclaration::BackgroundColor(background_color) => {
                         cv_builder.background_color(
                             background_color.compute_value_with_context(&context),
                         );
                     }
+                    PropertyDeclaration::BackgroundImage(background_image) => {
+                        cv_builder.background_image(background_image.compute_with_base_dir(
+                            &context,
+                            contextual_decl.origin.base_dir(),
+                        ));
+                    }
+                    PropertyDeclaration::BackgroundOrigin(background_origin) => {
+                        cv_builder.background_origin(background_origin.clone());
+                    }
+                    PropertyDeclaration::BackgroundPositionX(background_position_x) => {
+                        cv_builder.background_position_x(
+                            background_position_x.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::BackgroundPositionY(background_position_y) => {
+                        cv_builder.background_position_y(
+                            background_position_y.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::BackgroundRepeat(background_repeat) => {
+                        cv_builder.background_repeat(background_repeat.clone());
+                    }
+                    PropertyDeclaration::BackgroundSize(background_size) => {
+                        cv_builder.background_size(
+                            background_size.compute_value_with_context(&context),
+                        );
+                    }
                     PropertyDeclaration::BorderBottomColor(border_bottom_color) => {
                         cv_builder.border_bottom_color(
                             border_bottom_color.compute_value_with_context(&context),
                         );
                     }
+                    PropertyDeclaration::BorderCollapse(border_collapse) => {
+                        cv_builder.border_collapse(*border_collapse);
+                    }
                     PropertyDeclaration::BorderLeftColor(border_left_color) => {
                         cv_builder.border_left_color(
                             border_left_color.compute_value_with_context(&context),
@@ -429,9 +583,60 @@ pub fn compute_values(node: NodeRef) {
                             border_top_width.compute_value_with_context(&context),
                         );
                     }
+                    PropertyDeclaration::BorderBottomLeftRadius(border_bottom_left_radius) => {
+                        cv_builder.border_bottom_left_radius(
+                            border_bottom_left_radius.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::BorderBottomRightRadius(border_bottom_right_radius) => {
+                        cv_builder.border_bottom_right_radius(
+                            border_bottom_right_radius.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::BorderTopLeftRadius(border_top_left_radius) => {
+                        cv_builder.border_top_left_radius(
+                            border_top_left_radius.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::BorderTopRightRadius(border_top_right_radius) => {
+                        cv_builder.border_top_right_radius(
+                            border_top_right_radius.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::BorderSpacing(border_spacing) => {
+                        cv_builder
+                            .border_spacing(border_spacing.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::Bottom(bottom) => {
+                        cv_builder.bottom(bottom.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::BoxShadow(box_shadow) => {
+                        cv_builder.box_shadow(box_shadow.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::BoxSizing(box_sizing) => {
+                        cv_builder.box_sizing(*box_sizing);
+                    }
+                    PropertyDeclaration::BreakAfter(break_after) => {
+                        cv_builder.break_after(*break_after);
+                    }
+                    PropertyDeclaration::BreakBefore(break_before) => {
+                        cv_builder.break_before(*break_before);
+                    }
+                    PropertyDeclaration::BreakInside(break_inside) => {
+                        cv_builder.break_inside(*break_inside);
+                    }
+                    PropertyDeclaration::CaptionSide(caption_side) => {
+                        cv_builder.caption_side(*caption_side);
+                    }
+                    PropertyDeclaration::Clear(clear) => {
+                        cv_builder.clear(*clear);
+                    }
                     PropertyDeclaration::Color(_) => {
                         cv_builder.color(context.color());
                     }
+                    PropertyDeclaration::ColumnGap(column_gap) => {
+                        cv_builder.column_gap(column_gap.compute_value_with_context(&context));
+                    }
                     PropertyDeclaration::Direction(direction) => {
                         cv_builder.direction(*direction);
                     }
@@ -441,12 +646,73 @@ pub fn compute_values(node: NodeRef) {
                         // Computing display might not be straightforward — see: https://github.com/w3c/csswg-drafts/issues/1716
                         cv_builder.display(*display);
                     }
+                    PropertyDeclaration::Float(float) => {
+                        cv_builder.float(*float);
+                    }
+                    PropertyDeclaration::FlexBasis(flex_basis) => {
+                        cv_builder.flex_basis(flex_basis.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::FlexDirection(flex_direction) => {
+                        cv_builder.flex_direction(*flex_direction);
+                    }
+                    PropertyDeclaration::FlexGrow(flex_grow) => {
+                        cv_builder.flex_grow(*flex_grow);
+                    }
+                    PropertyDeclaration::FlexShrink(flex_shrink) => {
+                        cv_builder.flex_shrink(*flex_shrink);
+                    }
+                    PropertyDeclaration::FlexWrap(flex_wrap) => {
+                        cv_builder.flex_wrap(*flex_wrap);
+                    }
                     PropertyDeclaration::Height(height) => {
                         cv_builder.height(height.compute_value_with_context(&context));
                     }
                     PropertyDeclaration::FontSize(font_size) => {
                         cv_builder.font_size(font_size.compute_value_with_context(&context));
                     }
+                    PropertyDeclaration::GridAutoColumns(grid_auto_columns) => {
+                        cv_builder.grid_auto_columns(
+                            grid_auto_columns.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::GridAutoFlow(grid_auto_flow) => {
+                        cv_builder.grid_auto_flow(*grid_auto_flow);
+                    }
+                    PropertyDeclaration::GridAutoRows(grid_auto_rows) => {
+                        cv_builder
+                            .grid_auto_rows(grid_auto_rows.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::GridColumnEnd(grid_column_end) => {
+                        cv_builder.grid_column_end(grid_column_end.clone());
+                    }
+                    PropertyDeclaration::GridColumnStart(grid_column_start) => {
+                        cv_builder.grid_column_start(grid_column_start.clone());
+                    }
+                    PropertyDeclaration::GridRowEnd(grid_row_end) => {
+                        cv_builder.grid_row_end(grid_row_end.clone());
+                    }
+                    PropertyDeclaration::GridRowStart(grid_row_start) => {
+                        cv_builder.grid_row_start(grid_row_start.clone());
+                    }
+                    PropertyDeclaration::GridTemplateAreas(grid_template_areas) => {
+                        cv_builder.grid_template_areas(grid_template_areas.clone());
+                    }
+                    PropertyDeclaration::GridTemplateColumns(grid_template_columns) => {
+                        cv_builder.grid_template_columns(
+                            grid_template_columns.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::GridTemplateRows(grid_template_rows) => {
+                        cv_builder.grid_template_rows(
+                            grid_template_rows.compute_value_with_context(&context),
+                        );
+                    }
+                    PropertyDeclaration::JustifyContent(justify_content) => {
+                        cv_builder.justify_content(*justify_content);
+                    }
+                    PropertyDeclaration::Left(left) => {
+                        cv_builder.left(left.compute_value_with_context(&context));
+                    }
                     PropertyDeclaration::MarginBottom(margin_bottom) => {
                         cv_builder
                             .margin_bottom(margin_bottom.compute_value_with_context(&context));
@@ -460,6 +726,30 @@ pub fn compute_values(node: NodeRef) {
                     PropertyDeclaration::MarginTop(margin_top) => {
                         cv_builder.margin_top(margin_top.compute_value_with_context(&context));
                     }
+                    PropertyDeclaration::MaxHeight(max_height) => {
+                        cv_builder.max_height(max_height.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::MaxWidth(max_width) => {
+                        cv_builder.max_width(max_width.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::MinHeight(min_height) => {
+                        cv_builder.min_height(min_height.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::MinWidth(min_width) => {
+                        cv_builder.min_width(min_width.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::Opacity(opacity) => {
+                        cv_builder.opacity(*opacity);
+                    }
+                    PropertyDeclaration::Order(order) => {
+                        cv_builder.order(*order);
+                    }
+                    PropertyDeclaration::OverflowX(_) => {
+                        cv_builder.overflow_x(context.overflow_x());
+                    }
+                    PropertyDeclaration::OverflowY(_) => {
+                        cv_builder.overflow_y(context.overflow_y());
+                    }
                     PropertyDeclaration::PaddingBottom(padding_bottom) => {
                         cv_builder
                             .padding_bottom(padding_bottom.compute_value_with_context(&context));
@@ -474,12 +764,71 @@ pub fn compute_values(node: NodeRef) {
                     PropertyDeclaration::PaddingTop(padding_top) => {
                         cv_builder.padding_top(padding_top.compute_value_with_context(&context));
                     }
+                    PropertyDeclaration::Position(position) => {
+                        cv_builder.position(*position);
+                    }
+                    PropertyDeclaration::Right(right) => {
+                        cv_builder.right(right.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::RowGap(row_gap) => {
+                        cv_builder.row_gap(row_gap.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::TableLayout(table_layout) => {
+                        cv_builder.table_layout(*table_layout);
+                    }
+                    PropertyDeclaration::TextShadow(text_shadow) => {
+                        cv_builder.text_shadow(text_shadow.compute_value_with_context(&context));
+                    }
+                    PropertyDeclaration::Top(top) => {
+                        cv_builder.top(top.compute_value_with_context(&context));
+                    }
                     PropertyDeclaration::Width(width) => {
                         cv_builder.width(width.compute_value_with_context(&context));
                     }
                     PropertyDeclaration::WritingMode(writing_mode) => {
                         cv_builder.writing_mode(*writing_mode);
                     }
+                    PropertyDeclaration::ZIndex(z_index) => {
+                        cv_builder.z_index(*z_index);
+                    }
+                    PropertyDeclaration::BlockSize(_)
+                    | PropertyDeclaration::BorderBlockEndColor(_)
+                    | PropertyDeclaration::BorderBlockEndStyle(_)
+                    | PropertyDeclaration::BorderBlockEndWidth(_)
+                    | PropertyDeclaration::BorderBlockStartColor(_)
+                    | PropertyDeclaration::BorderBlockStartStyle(_)
+                    | PropertyDeclaration::BorderBlockStartWidth(_)
+                    | PropertyDeclaration::BorderEndEndRadius(_)
+                    | PropertyDeclaration::BorderEndStartRadius(_)
+                    | PropertyDeclaration::BorderInlineEndColor(_)
+                    | PropertyDeclaration::BorderInlineEndStyle(_)
+                    | PropertyDeclaration::BorderInlineEndWidth(_)
+                    | PropertyDeclaration::BorderInlineStartColor(_)
+                    | PropertyDeclaration::BorderInlineStartStyle(_)
+                    | PropertyDeclaration::BorderInlineStartWidth(_)
+                    | PropertyDeclaration::BorderStartEndRadius(_)
+                    | PropertyDeclaration::BorderStartStartRadius(_)
+                    | PropertyDeclaration::InlineSize(_)
+                    | PropertyDeclaration::InsetBlockEnd(_)
+                    | PropertyDeclaration::InsetBlockStart(_)
+                    | PropertyDeclaration::InsetInlineEnd(_)
+                    | PropertyDeclaration::InsetInlineStart(_)
+                    | PropertyDeclaration::MarginBlockEnd(_)
+                    | PropertyDeclaration::MarginBlockStart(_)
+                    | PropertyDeclaration::MarginInlineEnd(_)
+                    | PropertyDeclaration::MarginInlineStart(_)
+                    | PropertyDeclaration::MaxBlockSize(_)
+                    | PropertyDeclaration::MaxInlineSize(_)
+                    | PropertyDeclaration::MinBlockSize(_)
+                    | PropertyDeclaration::MinInlineSize(_)
+                    | PropertyDeclaration::PaddingBlockEnd(_)
+                    | PropertyDeclaration::PaddingBlockStart(_)
+                    | PropertyDeclaration::PaddingInlineEnd(_)
+                    | PropertyDeclaration::PaddingInlineStart(_) => {
+                        unreachable!(
+                            "flow-relative declarations are mapped to physical ones before the cascade"
+                        )
+                    }
                 }
             }
             None => {
@@ -505,7 +854,27 @@ fn compute_early_properties(node: NodeRef, context: &mut ComputeContext) {
         context.computed_color = Some(specified::Color::value_default(&context));
     }
 
-    context.computed_border_styles = Some(compute_border_styles_early(node));
+    context.computed_border_styles = Some(compute_border_styles_early(node.clone()));
+    context.computed_overflow = Some(compute_overflow_early(node));
+}
+
+fn compute_overflow_early(node: NodeRef) -> (Overflow, Overflow) {
+    let decls = node.contextual_decls();
+    let x = match decls.get_by_longhand(LonghandId::OverflowX) {
+        Some(contextual_decl) => match &contextual_decl.inner_decl {
+            PropertyDeclaration::OverflowX(overflow) => *overflow,
+            _ => panic!("needed overflow-x property declaration"),
+        },
+        None => Overflow::initial_value(),
+    };
+    let y = match decls.get_by_longhand(LonghandId::OverflowY) {
+        Some(contextual_decl) => match &contextual_decl.inner_decl {
+            PropertyDeclaration::OverflowY(overflow) => *overflow,
+            _ => panic!("needed overflow-y property declaration"),
+        },
+        None => Overflow::initial_value(),
+    };
+    Overflow::compute_pair(x, y)
 }
 
 fn compute_border_styles_early(node: NodeRef) -> BorderSideStyleContext {
@@ -0,0 +1,42 @@
+use crate::style::values::computed::{ComputeContext, ValueDefault};
+use crate::style::values::CSSFloat;
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// The `opacity` property, which makes a box and its contents partially transparent as a group.
+/// Values below 1 make the box establish a stacking context.
+///
+/// https://drafts.csswg.org/css-color/#transparency
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Opacity(pub CSSFloat);
+
+impl Opacity {
+    pub fn initial_value() -> Self {
+        Opacity(1.)
+    }
+
+    /// Parses an `<alpha-value>`, which is a number or a percentage, clamped to the range
+    /// [0, 1].
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let value = match input.try_parse(|i| i.expect_percentage()) {
+            Ok(unit_value) => unit_value,
+            Err(_) => input.expect_number()?,
+        };
+        Ok(Opacity(value.clamp(0., 1.)))
+    }
+
+    /// Determines if this value leaves a box fully opaque.
+    pub fn is_opaque(self) -> bool {
+        self.0 >= 1.
+    }
+}
+
+impl ValueDefault for Opacity {
+    type ComputedValue = Opacity;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Opacity::initial_value()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Determines what happens to the content of a box that overflows its padding box in one axis.
+///
+/// https://drafts.csswg.org/css-overflow-3/#overflow-properties
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Overflow {
+    /// Overflowing content is painted outside of the box.
+    Visible,
+    /// Overflowing content is clipped, but can still be scrolled to programmatically.
+    Hidden,
+    /// Overflowing content is clipped, and can't be scrolled to at all.
+    Clip,
+    /// Overflowing content is clipped, and the user can always scroll to it.
+    Scroll,
+    /// Like `scroll`, except that scrollbars are only shown if content actually overflows.
+    Auto,
+}
+
+impl Overflow {
+    pub fn initial_value() -> Self {
+        Overflow::Visible
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "visible" => Ok(Overflow::Visible),
+            "hidden" => Ok(Overflow::Hidden),
+            "clip" => Ok(Overflow::Clip),
+            "scroll" => Ok(Overflow::Scroll),
+            "auto" => Ok(Overflow::Auto),
+        }
+    }
+
+    /// Determines if this value makes a box a scroll container.
+    pub fn is_scrollable(self) -> bool {
+        matches!(self, Overflow::Hidden | Overflow::Scroll | Overflow::Auto)
+    }
+
+    /// Determines if this value clips overflowing content to the box's padding box.
+    pub fn is_clipped(self) -> bool {
+        self != Overflow::Visible
+    }
+
+    /// Computes the values of `overflow-x` and `overflow-y` from their specified values.  A box
+    /// can't be scrollable in one axis and not the other, so if either value is scrollable, a
+    /// `visible` value in the other axis computes to `auto`, and `clip` to `hidden`.
+    ///
+    /// https://drafts.csswg.org/css-overflow-3/#overflow-control
+    pub fn compute_pair(x: Overflow, y: Overflow) -> (Overflow, Overflow) {
+        let make_scrollable = |overflow| match overflow {
+            Overflow::Visible => Overflow::Auto,
+            Overflow::Clip => Overflow::Hidden,
+            _ => overflow,
+        };
+        if x.is_scrollable() || y.is_scrollable() {
+            (make_scrollable(x), make_scrollable(y))
+        } else {
+            (x, y)
+        }
+    }
+}
@@ -35,6 +35,11 @@ impl Percentage {
 
     /// Calculates the absolute pixel length of this percentage relative to `val`.
     pub fn px_relative_to(self, val: CSSPixelLength) -> CSSPixelLength {
-        CSSPixelLength::from(Au::from(val).scale_by(self.0))
+        CSSPixelLength::from(self.au_relative_to(Au::from(val)))
+    }
+
+    /// Calculates the length in app units of this percentage relative to `val`.
+    pub fn au_relative_to(self, val: Au) -> Au {
+        val.scale_by(self.0)
     }
 }
@@ -0,0 +1,233 @@
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::{
+    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
+};
+use crate::style::values::{specified, CSSInteger};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Selects the positioning scheme used to lay out a box.
+///
+/// https://www.w3.org/TR/css-position-3/#position-property
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Position {
+    Static,
+    Relative,
+    Absolute,
+    Fixed,
+    Sticky,
+}
+
+impl Position {
+    pub fn initial_value() -> Self {
+        Position::Static
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "static" => Ok(Position::Static),
+            "relative" => Ok(Position::Relative),
+            "absolute" => Ok(Position::Absolute),
+            "fixed" => Ok(Position::Fixed),
+            "sticky" => Ok(Position::Sticky),
+        }
+    }
+
+    /// Determines if this value takes a box out of flow, in which case it's absolutely
+    /// positioned.
+    ///
+    /// https://www.w3.org/TR/css-position-3/#absolute-pos
+    pub fn is_absolute(self) -> bool {
+        matches!(self, Position::Absolute | Position::Fixed)
+    }
+}
+
+impl ValueDefault for Position {
+    type ComputedValue = Position;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Position::initial_value()
+    }
+}
+
+/// The `z-index` property, which determines whether a positioned box establishes a stacking
+/// context, and where it's painted within its parent stacking context.
+///
+/// https://www.w3.org/TR/CSS2/visuren.html#z-index
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ZIndex {
+    /// The box doesn't establish a stacking context, and is painted at level 0.
+    Auto,
+    /// The box establishes a stacking context, and is painted at the given level.
+    Integer(CSSInteger),
+}
+
+impl ZIndex {
+    pub fn initial_value() -> Self {
+        ZIndex::Auto
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+            return Ok(ZIndex::Auto);
+        }
+        input
+            .expect_integer()
+            .map(ZIndex::Integer)
+            .map_err(|e| e.into())
+    }
+}
+
+impl ValueDefault for ZIndex {
+    type ComputedValue = ZIndex;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        ZIndex::initial_value()
+    }
+}
+
+/// Computed value of `bottom`.
+#[derive(Clone, Copy, Debug)]
+pub struct Bottom {
+    pub size: LengthPercentageOrAuto,
+}
+
+impl Bottom {
+    pub fn initial_value() -> Bottom {
+        Bottom {
+            size: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Bottom {
+    type ComputedValue = Bottom;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        Bottom {
+            size: computed_inset_size(&self.lp_or_auto),
+        }
+    }
+}
+
+impl ValueDefault for specified::Bottom {
+    type ComputedValue = Bottom;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Bottom::initial_value()
+    }
+}
+
+/// Computed value of `left`.
+#[derive(Clone, Copy, Debug)]
+pub struct Left {
+    pub size: LengthPercentageOrAuto,
+}
+
+impl Left {
+    pub fn initial_value() -> Left {
+        Left {
+            size: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Left {
+    type ComputedValue = Left;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        Left {
+            size: computed_inset_size(&self.lp_or_auto),
+        }
+    }
+}
+
+impl ValueDefault for specified::Left {
+    type ComputedValue = Left;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Left::initial_value()
+    }
+}
+
+/// Computed value of `right`.
+#[derive(Clone, Copy, Debug)]
+pub struct Right {
+    pub size: LengthPercentageOrAuto,
+}
+
+impl Right {
+    pub fn initial_value() -> Right {
+        Right {
+            size: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Right {
+    type ComputedValue = Right;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        Right {
+            size: computed_inset_size(&self.lp_or_auto),
+        }
+    }
+}
+
+impl ValueDefault for specified::Right {
+    type ComputedValue = Right;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Right::initial_value()
+    }
+}
+
+/// Computed value of `top`.
+#[derive(Clone, Copy, Debug)]
+pub struct Top {
+    pub size: LengthPercentageOrAuto,
+}
+
+impl Top {
+    pub fn initial_value() -> Top {
+        Top {
+            size: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::Top {
+    type ComputedValue = Top;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        Top {
+            size: computed_inset_size(&self.lp_or_auto),
+        }
+    }
+}
+
+impl ValueDefault for specified::Top {
+    type ComputedValue = Top;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        Top::initial_value()
+    }
+}
+
+fn computed_inset_size(lp_auto: &specified::LengthPercentageOrAuto) -> LengthPercentageOrAuto {
+    match lp_auto {
+        specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
+        specified::LengthPercentageOrAuto::LengthPercentage(lp) => match lp {
+            specified::LengthPercentage::Length(no_calc_length) => {
+                no_calc_length.compute_value().into()
+            }
+            specified::LengthPercentage::Percentage(percentage) => {
+                LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(*percentage))
+            }
+        },
+    }
+}
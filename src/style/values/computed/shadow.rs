@@ -0,0 +1,93 @@
+use crate::style::values::computed::length::CSSPixelLength;
+use crate::style::values::computed::{
+    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
+};
+use crate::style::values::specified;
+use cssparser::RGBA;
+
+/// Computed value of `box-shadow`.  The first shadow is painted on top of the rest.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#box-shadow
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxShadow(pub Vec<Shadow>);
+
+impl BoxShadow {
+    pub fn initial_value() -> Self {
+        BoxShadow(Vec::new())
+    }
+}
+
+impl ComputeValueWithContext for specified::BoxShadow {
+    type ComputedValue = BoxShadow;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        BoxShadow(compute_shadows(&self.0, context))
+    }
+}
+
+impl ValueDefault for specified::BoxShadow {
+    type ComputedValue = BoxShadow;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        BoxShadow::initial_value()
+    }
+}
+
+/// Computed value of `text-shadow`, which is inherited.  The first shadow is painted on top of the
+/// rest, and none of them are inset or spread.
+///
+/// https://drafts.csswg.org/css-text-decor-3/#text-shadow-property
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextShadow(pub Vec<Shadow>);
+
+impl TextShadow {
+    pub fn initial_value() -> Self {
+        TextShadow(Vec::new())
+    }
+}
+
+impl ComputeValueWithContext for specified::TextShadow {
+    type ComputedValue = TextShadow;
+
+    fn compute_value_with_context(&self, context: &ComputeContext) -> Self::ComputedValue {
+        TextShadow(compute_shadows(&self.0, context))
+    }
+}
+
+impl ValueDefault for specified::TextShadow {
+    type ComputedValue = TextShadow;
+
+    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
+        context.parent_computed_values.text_shadow.clone()
+    }
+}
+
+/// A single computed shadow of `box-shadow` or `text-shadow`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shadow {
+    pub color: RGBA,
+    pub offset_x: CSSPixelLength,
+    pub offset_y: CSSPixelLength,
+    pub blur_radius: CSSPixelLength,
+    pub spread: CSSPixelLength,
+    pub inset: bool,
+}
+
+fn compute_shadows(shadows: &[specified::shadow::Shadow], context: &ComputeContext) -> Vec<Shadow> {
+    shadows
+        .iter()
+        .map(|shadow| Shadow {
+            // Shadows without a color are the color of the element casting them, the same as
+            // those that are explicitly `currentColor`.
+            color: match shadow.color {
+                None | Some(specified::ColorUnit::CurrentColor) => context.color().rgba(),
+                Some(unit) => unit.compute_value_with_context(context),
+            },
+            offset_x: shadow.offset_x.compute_value(),
+            offset_y: shadow.offset_y.compute_value(),
+            blur_radius: shadow.blur_radius.compute_value(),
+            spread: shadow.spread.compute_value(),
+            inset: shadow.inset,
+        })
+        .collect()
+}
@@ -0,0 +1,67 @@
+use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::ComputeValue;
+use crate::style::values::specified;
+
+/// A computed intrinsic sizing keyword.  These are resolved against the min-content and
+/// max-content sizes of a box during layout, and only in its inline axis.  In its block axis,
+/// they behave as the initial value of their property.
+///
+/// https://drafts.csswg.org/css-sizing-3/#sizing-values
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum IntrinsicSize {
+    MinContent,
+    MaxContent,
+    FitContent(LengthPercentage),
+}
+
+/// A computed value of `width`, `height`, `min-width`, or `min-height`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Size {
+    LengthPercentage(LengthPercentage),
+    Auto,
+    IntrinsicSize(IntrinsicSize),
+}
+
+/// A computed value of `max-width` or `max-height`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum MaxSize {
+    LengthPercentage(LengthPercentage),
+    None,
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl From<LengthPercentageOrAuto> for Size {
+    fn from(lp_or_auto: LengthPercentageOrAuto) -> Self {
+        match lp_or_auto {
+            LengthPercentageOrAuto::LengthPercentage(lp) => Size::LengthPercentage(lp),
+            LengthPercentageOrAuto::Auto => Size::Auto,
+        }
+    }
+}
+
+impl ComputeValue for specified::IntrinsicSize {
+    type ComputedValue = IntrinsicSize;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        match self {
+            specified::IntrinsicSize::MinContent => IntrinsicSize::MinContent,
+            specified::IntrinsicSize::MaxContent => IntrinsicSize::MaxContent,
+            specified::IntrinsicSize::FitContent(lp) => {
+                IntrinsicSize::FitContent(lp.compute_value())
+            }
+        }
+    }
+}
+
+impl ComputeValue for specified::LengthPercentageOrNone {
+    type ComputedValue = MaxSize;
+
+    fn compute_value(&self) -> Self::ComputedValue {
+        match self {
+            specified::LengthPercentageOrNone::LengthPercentage(lp) => {
+                MaxSize::LengthPercentage(lp.compute_value())
+            }
+            specified::LengthPercentageOrNone::None => MaxSize::None,
+        }
+    }
+}
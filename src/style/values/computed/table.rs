@@ -0,0 +1,139 @@
+use crate::style::values::computed::length::CSSPixelLength;
+use crate::style::values::computed::{
+    ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
+};
+use crate::style::values::specified;
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Selects between the separated and collapsing table border models.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#borders
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BorderCollapse {
+    Separate,
+    Collapse,
+}
+
+impl BorderCollapse {
+    pub fn initial_value() -> Self {
+        BorderCollapse::Separate
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "separate" => Ok(BorderCollapse::Separate),
+            "collapse" => Ok(BorderCollapse::Collapse),
+        }
+    }
+}
+
+impl ValueDefault for BorderCollapse {
+    type ComputedValue = BorderCollapse;
+
+    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
+        context.parent_computed_values.border_collapse
+    }
+}
+
+/// Computed value of `border-spacing`, the distance between the borders of adjacent cells in the
+/// separated borders model.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#separated-borders
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderSpacing {
+    pub horizontal: CSSPixelLength,
+    pub vertical: CSSPixelLength,
+}
+
+impl BorderSpacing {
+    pub fn initial_value() -> Self {
+        BorderSpacing {
+            horizontal: CSSPixelLength::new(0.),
+            vertical: CSSPixelLength::new(0.),
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::BorderSpacing {
+    type ComputedValue = BorderSpacing;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        BorderSpacing {
+            horizontal: self.horizontal.compute_value(),
+            vertical: self.vertical.compute_value(),
+        }
+    }
+}
+
+impl ValueDefault for specified::BorderSpacing {
+    type ComputedValue = BorderSpacing;
+
+    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
+        context.parent_computed_values.border_spacing
+    }
+}
+
+/// https://www.w3.org/TR/CSS22/tables.html#caption-position
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaptionSide {
+    Top,
+    Bottom,
+}
+
+impl CaptionSide {
+    pub fn initial_value() -> Self {
+        CaptionSide::Top
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "top" => Ok(CaptionSide::Top),
+            "bottom" => Ok(CaptionSide::Bottom),
+        }
+    }
+}
+
+impl ValueDefault for CaptionSide {
+    type ComputedValue = CaptionSide;
+
+    fn value_default(context: &ComputeContext) -> Self::ComputedValue {
+        context.parent_computed_values.caption_side
+    }
+}
+
+/// Selects the algorithm used to lay out the columns of a table.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#width-layout
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableLayout {
+    Auto,
+    Fixed,
+}
+
+impl TableLayout {
+    pub fn initial_value() -> Self {
+        TableLayout::Auto
+    }
+
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(TableLayout::Auto),
+            "fixed" => Ok(TableLayout::Fixed),
+        }
+    }
+}
+
+impl ValueDefault for TableLayout {
+    type ComputedValue = TableLayout;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        TableLayout::initial_value()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::style::values::computed::length::{LengthPercentage, LengthPercentageOrAuto};
+use crate::style::values::computed::sizing::{MaxSize, Size};
 use crate::style::values::computed::{
     ComputeContext, ComputeValue, ComputeValueWithContext, ValueDefault,
 };
@@ -7,14 +7,12 @@ use crate::style::values::specified;
 /// Computed value of a `width`.
 #[derive(Clone, Copy, Debug)]
 pub struct Width {
-    pub size: LengthPercentageOrAuto,
+    pub size: Size,
 }
 
 impl Width {
     pub fn initial_value() -> Width {
-        Width {
-            size: LengthPercentageOrAuto::Auto,
-        }
+        Width { size: Size::Auto }
     }
 }
 
@@ -22,27 +20,13 @@ impl ComputeValueWithContext for specified::Width {
     type ComputedValue = Width;
 
     fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
-        let computed_lp_auto: LengthPercentageOrAuto = match self {
-            // TODO: I think we repeat computing the value of specified::LengthPercentageOrAuto a lot...eventually consider
-            // simply implementing `ComputeValueWithContext` for specified::LengthPercentageOrAuto.
-            specified::Width::LengthPercentageOrAuto(lp_auto) => match lp_auto {
-                specified::LengthPercentageOrAuto::Auto => LengthPercentageOrAuto::Auto,
-                specified::LengthPercentageOrAuto::LengthPercentage(lp) => match lp {
-                    specified::LengthPercentage::Length(no_calc_length) => {
-                        no_calc_length.compute_value().into()
-                    }
-                    specified::LengthPercentage::Percentage(percentage) => {
-                        LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Percentage(
-                            *percentage,
-                        ))
-                    }
-                },
-            },
+        let size = match self {
+            specified::Width::LengthPercentageOrAuto(lp_auto) => lp_auto.compute_value().into(),
+            specified::Width::IntrinsicSize(intrinsic) => {
+                Size::IntrinsicSize(intrinsic.compute_value())
+            }
         };
-
-        Width {
-            size: computed_lp_auto,
-        }
+        Width { size }
     }
 }
 
@@ -53,3 +37,73 @@ impl ValueDefault for specified::Width {
         Width::initial_value()
     }
 }
+
+/// Computed value of a `min-width`.
+#[derive(Clone, Copy, Debug)]
+pub struct MinWidth {
+    pub size: Size,
+}
+
+impl MinWidth {
+    pub fn initial_value() -> MinWidth {
+        MinWidth { size: Size::Auto }
+    }
+}
+
+impl ComputeValueWithContext for specified::MinWidth {
+    type ComputedValue = MinWidth;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        let size = match self {
+            specified::MinWidth::LengthPercentageOrAuto(lp_auto) => lp_auto.compute_value().into(),
+            specified::MinWidth::IntrinsicSize(intrinsic) => {
+                Size::IntrinsicSize(intrinsic.compute_value())
+            }
+        };
+        MinWidth { size }
+    }
+}
+
+impl ValueDefault for specified::MinWidth {
+    type ComputedValue = MinWidth;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        MinWidth::initial_value()
+    }
+}
+
+/// Computed value of a `max-width`.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxWidth {
+    pub size: MaxSize,
+}
+
+impl MaxWidth {
+    pub fn initial_value() -> MaxWidth {
+        MaxWidth {
+            size: MaxSize::None,
+        }
+    }
+}
+
+impl ComputeValueWithContext for specified::MaxWidth {
+    type ComputedValue = MaxWidth;
+
+    fn compute_value_with_context(&self, _context: &ComputeContext) -> Self::ComputedValue {
+        let size = match self {
+            specified::MaxWidth::LengthPercentageOrNone(lp_none) => lp_none.compute_value(),
+            specified::MaxWidth::IntrinsicSize(intrinsic) => {
+                MaxSize::IntrinsicSize(intrinsic.compute_value())
+            }
+        };
+        MaxWidth { size }
+    }
+}
+
+impl ValueDefault for specified::MaxWidth {
+    type ComputedValue = MaxWidth;
+
+    fn value_default(_context: &ComputeContext) -> Self::ComputedValue {
+        MaxWidth::initial_value()
+    }
+}
@@ -1,6 +1,11 @@
-use crate::style::values::specified::ColorUnit;
+use crate::style::values::computed::{self, BoxArea, RepeatStyle};
+use crate::style::values::specified::{
+    AbsoluteLength, ColorUnit, LengthPercentage, LengthPercentageOrAuto, NoCalcLength,
+};
+use crate::style::values::CSSFloat;
 use crate::style::StyleParseErrorKind;
-use cssparser::{ParseError, Parser};
+use crate::Side;
+use cssparser::{ParseError, Parser, Token};
 
 /// Specified values for the `background-color` property.
 ///
@@ -25,3 +30,871 @@ impl BackgroundColor {
         self.0
     }
 }
+
+/// Specified values for the `background-image` property, one per background layer.  The first
+/// layer is painted on top of the rest.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-image
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundImage(pub Vec<Image>);
+
+impl BackgroundImage {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(Image::parse)
+            .map(BackgroundImage)
+    }
+}
+
+/// The image of a background layer.
+///
+/// https://drafts.csswg.org/css-images-3/#image-values
+#[derive(Clone, Debug, PartialEq)]
+pub enum Image {
+    /// The `none` keyword, which makes the layer paint nothing.
+    None,
+    /// An image file, as written in its `url()`.
+    Url(String),
+    Gradient(Gradient),
+}
+
+impl Image {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(Image::None);
+        }
+        if let Ok(url) = input.try_parse(|i| i.expect_url()) {
+            return Ok(Image::Url(url.as_ref().to_owned()));
+        }
+        Gradient::parse(input).map(Image::Gradient)
+    }
+}
+
+/// A `linear-gradient()`, `radial-gradient()`, or repeating version of either.
+///
+/// https://drafts.csswg.org/css-images-3/#gradients
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    /// The color stops of the gradient, of which there are at least two.
+    pub stops: Vec<ColorStop>,
+    /// Whether the color stops repeat infinitely in both directions, as they do in
+    /// `repeating-linear-gradient()` and `repeating-radial-gradient()`.
+    pub repeating: bool,
+}
+
+impl Gradient {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let function = input.expect_function()?.clone();
+        let (radial, repeating) = match_ignore_ascii_case! { &function,
+            "linear-gradient" => (false, false),
+            "repeating-linear-gradient" => (false, true),
+            "radial-gradient" => (true, false),
+            "repeating-radial-gradient" => (true, true),
+            _ => return Err(location.new_unexpected_token_error(Token::Function(function.clone()))),
+        };
+        input.parse_nested_block(|i| {
+            let kind = if radial {
+                parse_radial_gradient_kind(i)?
+            } else {
+                parse_linear_gradient_kind(i)?
+            };
+            let mut stops = Vec::new();
+            for stop in i.parse_comma_separated(ColorStop::parse_one_or_two)? {
+                stops.extend(stop);
+            }
+            if stops.len() < 2 {
+                return Err(i.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+            }
+            Ok(Gradient {
+                kind,
+                stops,
+                repeating,
+            })
+        })
+    }
+}
+
+/// Parses the direction of a linear gradient and the comma after it, if there is one.  The
+/// direction is `to bottom` by default.
+fn parse_linear_gradient_kind<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<GradientKind, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let direction = match input.try_parse(LineDirection::parse) {
+        Ok(direction) => {
+            input.expect_comma()?;
+            direction
+        }
+        Err(_) => LineDirection::Angle(180.),
+    };
+    Ok(GradientKind::Linear(direction))
+}
+
+/// Parses the ending shape and position of a radial gradient and the comma after them, if there
+/// are any.  The gradient is a `farthest-corner` ellipse in the center by default.
+fn parse_radial_gradient_kind<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<GradientKind, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let mut circle = None;
+    let mut extent = None;
+    let mut lengths = Vec::new();
+    loop {
+        if circle.is_none() {
+            if let Ok(is_circle) = input.try_parse(parse_is_circle) {
+                circle = Some(is_circle);
+                continue;
+            }
+        }
+        if extent.is_none() && lengths.is_empty() {
+            if let Ok(keyword) = input.try_parse(ShapeExtent::parse) {
+                extent = Some(keyword);
+                continue;
+            }
+            while lengths.len() < 2 {
+                match input.try_parse(parse_non_negative_length_percentage) {
+                    Ok(length) => lengths.push(length),
+                    Err(_) => break,
+                }
+            }
+            if !lengths.is_empty() {
+                continue;
+            }
+        }
+        break;
+    }
+    let position = if input.try_parse(|i| i.expect_ident_matching("at")).is_ok() {
+        Some(BgPosition::parse(input)?)
+    } else {
+        None
+    };
+    if circle.is_some() || extent.is_some() || !lengths.is_empty() || position.is_some() {
+        input.expect_comma()?;
+    }
+
+    let shape = match (circle, extent, lengths.as_slice()) {
+        // A single length can only be the radius of a circle, and percentages aren't allowed for
+        // it, since it wouldn't be clear which dimension of the box they refer to.
+        (Some(true), None, [LengthPercentage::Length(radius)])
+        | (None, None, [LengthPercentage::Length(radius)]) => EndingShape::Circle(*radius),
+        (Some(false), None, [horizontal, vertical]) | (None, None, [horizontal, vertical]) => {
+            EndingShape::Ellipse(*horizontal, *vertical)
+        }
+        (Some(true), extent, []) => {
+            EndingShape::CircleExtent(extent.unwrap_or(ShapeExtent::FarthestCorner))
+        }
+        (Some(false), extent, []) | (None, extent, []) => {
+            EndingShape::EllipseExtent(extent.unwrap_or(ShapeExtent::FarthestCorner))
+        }
+        _ => return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError)),
+    };
+    Ok(GradientKind::Radial(
+        shape,
+        position.unwrap_or_else(BgPosition::center),
+    ))
+}
+
+/// Parses the `circle` or `ellipse` keyword, as whether it's `circle`.
+fn parse_is_circle<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<bool, ParseError<'i, StyleParseErrorKind<'i>>> {
+    try_match_ident_ignore_ascii_case! { input,
+        "circle" => Ok(true),
+        "ellipse" => Ok(false),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientKind {
+    /// The colors of a linear gradient change along a line in the given direction through the
+    /// center of the box, and are the same along lines perpendicular to it.
+    Linear(LineDirection),
+    /// The colors of a radial gradient change along a ray from its center outward, and are the
+    /// same along ellipses of the given shape around it.
+    Radial(EndingShape, BgPosition),
+}
+
+/// The direction of the gradient line of a linear gradient.
+///
+/// https://drafts.csswg.org/css-images-3/#linear-gradient-syntax
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineDirection {
+    /// An angle in degrees, where `0deg` points up and angles increase clockwise.
+    Angle(CSSFloat),
+    /// Towards the corner between the given horizontal and vertical sides.  The angle this
+    /// corresponds to depends on the dimensions of the box.
+    Corner(Side, Side),
+}
+
+impl LineDirection {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(angle) = input.try_parse(parse_angle) {
+            return Ok(LineDirection::Angle(angle));
+        }
+        input.expect_ident_matching("to")?;
+        let first = parse_side(input)?;
+        let second = input.try_parse(parse_side).ok();
+        let is_vertical = |side: Side| side == Side::Top || side == Side::Bottom;
+        Ok(match second {
+            None => LineDirection::Angle(match first {
+                Side::Top => 0.,
+                Side::Right => 90.,
+                Side::Bottom => 180.,
+                Side::Left => 270.,
+            }),
+            Some(second) if is_vertical(first) != is_vertical(second) => {
+                if is_vertical(first) {
+                    LineDirection::Corner(second, first)
+                } else {
+                    LineDirection::Corner(first, second)
+                }
+            }
+            Some(_) => return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError)),
+        })
+    }
+}
+
+fn parse_side<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Side, ParseError<'i, StyleParseErrorKind<'i>>> {
+    try_match_ident_ignore_ascii_case! { input,
+        "top" => Ok(Side::Top),
+        "right" => Ok(Side::Right),
+        "bottom" => Ok(Side::Bottom),
+        "left" => Ok(Side::Left),
+    }
+}
+
+/// Parses an `<angle>`, converted to degrees.  Zero doesn't need a unit.
+///
+/// https://drafts.csswg.org/css-values-3/#angles
+fn parse_angle<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<CSSFloat, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    let degrees = match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } => match_ignore_ascii_case! { unit,
+            "deg" => Some(value),
+            "grad" => Some(value * 0.9),
+            "rad" => Some(value.to_degrees()),
+            "turn" => Some(value * 360.),
+            _ => None,
+        },
+        Token::Number { value: 0., .. } => Some(0.),
+        _ => None,
+    };
+    degrees.ok_or_else(|| location.new_unexpected_token_error(token.clone()))
+}
+
+/// The shape and size of the ellipses a radial gradient's colors are the same along, at the point
+/// where its gradient ray ends.
+///
+/// https://drafts.csswg.org/css-images-3/#radial-gradient-syntax
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EndingShape {
+    /// A circle with the given radius.
+    Circle(NoCalcLength),
+    /// A circle that meets the box as described by the given keyword.
+    CircleExtent(ShapeExtent),
+    /// An ellipse with the given horizontal and vertical radii.  Percentages are of the width and
+    /// height of the box.
+    Ellipse(LengthPercentage, LengthPercentage),
+    /// An ellipse that meets the box as described by the given keyword.
+    EllipseExtent(ShapeExtent),
+}
+
+/// A keyword sizing the ending shape of a radial gradient relative to the box it's painted in.
+///
+/// https://drafts.csswg.org/css-images-3/#typedef-radial-extent
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShapeExtent {
+    /// The shape touches the side of the box closest to its center.
+    ClosestSide,
+    /// The shape touches the side of the box farthest from its center.
+    FarthestSide,
+    /// The shape passes through the corner of the box closest to its center.
+    ClosestCorner,
+    /// The shape passes through the corner of the box farthest from its center.
+    FarthestCorner,
+}
+
+impl ShapeExtent {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "closest-side" => Ok(ShapeExtent::ClosestSide),
+            "farthest-side" => Ok(ShapeExtent::FarthestSide),
+            "closest-corner" => Ok(ShapeExtent::ClosestCorner),
+            "farthest-corner" => Ok(ShapeExtent::FarthestCorner),
+        }
+    }
+}
+
+/// A color of a gradient, and optionally where along the gradient line or ray it's at.  Stops
+/// without a position are spread out evenly between those that have one.
+///
+/// https://drafts.csswg.org/css-images-3/#color-stop-syntax
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorStop {
+    pub color: ColorUnit,
+    pub position: Option<LengthPercentage>,
+}
+
+impl ColorStop {
+    /// Parses a color followed by up to two positions.  A color with two positions is the same as
+    /// two stops of that color, one at each position.
+    fn parse_one_or_two<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Vec<ColorStop>, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let color = ColorUnit::parse(input)?;
+        let mut stops = vec![ColorStop {
+            color,
+            position: input.try_parse(parse_length_percentage).ok(),
+        }];
+        if stops[0].position.is_some() {
+            if let Ok(position) = input.try_parse(parse_length_percentage) {
+                stops.push(ColorStop {
+                    color,
+                    position: Some(position),
+                });
+            }
+        }
+        Ok(stops)
+    }
+}
+
+/// Specified values for the `background-position-x` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-4/#background-position-longhands
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundPositionX(pub Vec<BgPositionComponent>);
+
+impl BackgroundPositionX {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(|i| BgPositionComponent::parse(i, false))
+            .map(BackgroundPositionX)
+    }
+}
+
+/// Specified values for the `background-position-y` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-4/#background-position-longhands
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundPositionY(pub Vec<BgPositionComponent>);
+
+impl BackgroundPositionY {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(|i| BgPositionComponent::parse(i, true))
+            .map(BackgroundPositionY)
+    }
+}
+
+/// The `background-position` shorthand, which sets both `background-position-x` and
+/// `background-position-y`.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-position
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundPosition {
+    pub x: BackgroundPositionX,
+    pub y: BackgroundPositionY,
+}
+
+impl BackgroundPosition {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let positions = input.parse_comma_separated(BgPosition::parse)?;
+        Ok(BackgroundPosition {
+            x: BackgroundPositionX(positions.iter().map(|p| p.horizontal).collect()),
+            y: BackgroundPositionY(positions.iter().map(|p| p.vertical).collect()),
+        })
+    }
+}
+
+/// A `<bg-position>`, the position of a background image within its background positioning area,
+/// or of the center of a radial gradient within its box.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#typedef-bg-position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BgPosition {
+    pub horizontal: BgPositionComponent,
+    pub vertical: BgPositionComponent,
+}
+
+impl BgPosition {
+    /// Parses one to four keywords and offsets.  The value is first split into an optional offset
+    /// following each keyword, and the keywords then say which axis each is for, in either order.
+    /// Offsets without a keyword are only allowed with at most two values, horizontal first.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let mut items = Vec::new();
+        while items.len() < 4 {
+            if let Ok(keyword) = input.try_parse(PositionKeyword::parse) {
+                items.push(PositionItem::Keyword(keyword));
+            } else if let Ok(offset) = input.try_parse(parse_length_percentage) {
+                items.push(PositionItem::Offset(offset));
+            } else {
+                break;
+            }
+        }
+        BgPosition::from_items(&items)
+            .ok_or_else(|| location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+    }
+
+    pub fn center() -> BgPosition {
+        BgPosition {
+            horizontal: BgPositionComponent::center(),
+            vertical: BgPositionComponent::center(),
+        }
+    }
+
+    fn from_items(items: &[PositionItem]) -> Option<BgPosition> {
+        use PositionItem::{Keyword, Offset};
+        let (horizontal, vertical) = match *items {
+            [Keyword(keyword)] if keyword.is_vertical() => (PositionItem::center(), items[0]),
+            [item] => (item, PositionItem::center()),
+            [Keyword(first), Keyword(second)] if first.is_vertical() || second.is_horizontal() => {
+                (items[1], items[0])
+            }
+            [first, second] => (first, second),
+            _ => {
+                // Three or four values pair every offset with the keyword before it.
+                let mut pairs = Vec::new();
+                for item in items {
+                    match (*item, pairs.last_mut()) {
+                        (Keyword(keyword), _) => pairs.push((keyword, None)),
+                        (Offset(offset), Some((keyword, slot @ None)))
+                            if *keyword != PositionKeyword::Center =>
+                        {
+                            *slot = Some(offset)
+                        }
+                        _ => return None,
+                    }
+                }
+                let (first, second) = match pairs.as_slice() {
+                    [first, second] if first.0.is_vertical() || second.0.is_horizontal() => {
+                        (*second, *first)
+                    }
+                    [first, second] => (*first, *second),
+                    _ => return None,
+                };
+                if first.0.is_vertical() || second.0.is_horizontal() {
+                    return None;
+                }
+                return Some(BgPosition {
+                    horizontal: BgPositionComponent::from_keyword(first.0, first.1),
+                    vertical: BgPositionComponent::from_keyword(second.0, second.1),
+                });
+            }
+        };
+        let component = |item: PositionItem, vertical: bool| match item {
+            Keyword(keyword) if keyword.is_vertical() != vertical && keyword.is_directional() => {
+                None
+            }
+            Keyword(keyword) => Some(BgPositionComponent::from_keyword(keyword, None)),
+            Offset(offset) => Some(BgPositionComponent {
+                from_end: false,
+                offset,
+            }),
+        };
+        Some(BgPosition {
+            horizontal: component(horizontal, false)?,
+            vertical: component(vertical, true)?,
+        })
+    }
+}
+
+/// A keyword or offset of a `<bg-position>`, before it's known which axis it's for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PositionItem {
+    Keyword(PositionKeyword),
+    Offset(LengthPercentage),
+}
+
+impl PositionItem {
+    fn center() -> PositionItem {
+        PositionItem::Keyword(PositionKeyword::Center)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PositionKeyword {
+    Left,
+    Center,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl PositionKeyword {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        try_match_ident_ignore_ascii_case! { input,
+            "left" => Ok(PositionKeyword::Left),
+            "center" => Ok(PositionKeyword::Center),
+            "right" => Ok(PositionKeyword::Right),
+            "top" => Ok(PositionKeyword::Top),
+            "bottom" => Ok(PositionKeyword::Bottom),
+        }
+    }
+
+    fn is_horizontal(self) -> bool {
+        self == PositionKeyword::Left || self == PositionKeyword::Right
+    }
+
+    fn is_vertical(self) -> bool {
+        self == PositionKeyword::Top || self == PositionKeyword::Bottom
+    }
+
+    /// Whether this keyword only makes sense in one axis, which is every keyword but `center`.
+    fn is_directional(self) -> bool {
+        self != PositionKeyword::Center
+    }
+}
+
+/// The position of something along one axis of a box, as an offset from the start or end edge
+/// of the box in that axis.  Percentages are of the space left over in the box, so `100%` from
+/// the start puts the end of what's positioned at the end of the box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BgPositionComponent {
+    /// Whether the offset is from the right or bottom edge of the box, rather than the left or top.
+    pub from_end: bool,
+    pub offset: LengthPercentage,
+}
+
+impl BgPositionComponent {
+    /// Parses a `background-position-x` value if `vertical` is false, or a
+    /// `background-position-y` value if it's true: `center`, or an offset optionally preceded by
+    /// the keyword of the edge it's from.
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+        vertical: bool,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(offset) = input.try_parse(parse_length_percentage) {
+            return Ok(BgPositionComponent {
+                from_end: false,
+                offset,
+            });
+        }
+        let location = input.current_source_location();
+        let keyword = PositionKeyword::parse(input)?;
+        if keyword.is_directional() && keyword.is_vertical() != vertical {
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        let offset = if keyword.is_directional() {
+            input.try_parse(parse_length_percentage).ok()
+        } else {
+            None
+        };
+        Ok(BgPositionComponent::from_keyword(keyword, offset))
+    }
+
+    fn start() -> BgPositionComponent {
+        BgPositionComponent {
+            from_end: false,
+            offset: LengthPercentage::Percentage(computed::Percentage(0.)),
+        }
+    }
+
+    fn center() -> BgPositionComponent {
+        BgPositionComponent {
+            from_end: false,
+            offset: LengthPercentage::Percentage(computed::Percentage(0.5)),
+        }
+    }
+
+    fn from_keyword(keyword: PositionKeyword, offset: Option<LengthPercentage>) -> Self {
+        let offset = offset.unwrap_or(LengthPercentage::Percentage(computed::Percentage(0.)));
+        match keyword {
+            PositionKeyword::Center => BgPositionComponent::center(),
+            PositionKeyword::Left | PositionKeyword::Top => BgPositionComponent {
+                from_end: false,
+                offset,
+            },
+            PositionKeyword::Right | PositionKeyword::Bottom => BgPositionComponent {
+                from_end: true,
+                offset,
+            },
+        }
+    }
+}
+
+/// Specified values for the `background-size` property, one per background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background-size
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackgroundSize(pub Vec<BgSize>);
+
+impl BackgroundSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .parse_comma_separated(BgSize::parse)
+            .map(BackgroundSize)
+    }
+}
+
+/// The size of the image of a background layer.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#typedef-bg-size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BgSize {
+    /// A width and height, either of which may be `auto` to size the image by its intrinsic size
+    /// or ratio.  Percentages are of the background positioning area.
+    Explicit {
+        width: LengthPercentageOrAuto,
+        height: LengthPercentageOrAuto,
+    },
+    /// The smallest size that keeps the intrinsic ratio of the image and covers the whole
+    /// background positioning area.
+    Cover,
+    /// The largest size that keeps the intrinsic ratio of the image and fits within the background
+    /// positioning area.
+    Contain,
+}
+
+impl BgSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(width) = input.try_parse(parse_size_component) {
+            let height = input
+                .try_parse(parse_size_component)
+                .unwrap_or(LengthPercentageOrAuto::Auto);
+            return Ok(BgSize::Explicit { width, height });
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "cover" => Ok(BgSize::Cover),
+            "contain" => Ok(BgSize::Contain),
+        }
+    }
+
+    fn auto() -> BgSize {
+        BgSize::Explicit {
+            width: LengthPercentageOrAuto::Auto,
+            height: LengthPercentageOrAuto::Auto,
+        }
+    }
+}
+
+fn parse_size_component<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<LengthPercentageOrAuto, ParseError<'i, StyleParseErrorKind<'i>>> {
+    if let Ok(length) = input.try_parse(parse_non_negative_length_percentage) {
+        return Ok(LengthPercentageOrAuto::LengthPercentage(length));
+    }
+    input.expect_ident_matching("auto")?;
+    Ok(LengthPercentageOrAuto::Auto)
+}
+
+/// The `background` shorthand, which sets every background longhand.  Each comma-separated layer
+/// may give its image, position and size, repeat style, attachment, and origin and clip boxes in
+/// any order, and the last may give the background color too.  Whatever a layer leaves out is
+/// set to its initial value.
+///
+/// TODO: `background-attachment` isn't supported, so it's parsed but has no effect.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#background
+#[derive(Clone, Debug)]
+pub struct Background {
+    pub color: BackgroundColor,
+    pub image: BackgroundImage,
+    pub position: BackgroundPosition,
+    pub size: BackgroundSize,
+    pub repeat: computed::BackgroundRepeat,
+    pub origin: computed::BackgroundOrigin,
+    pub clip: computed::BackgroundClip,
+}
+
+impl Background {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut color = None;
+        let layers = input.parse_comma_separated(|i| {
+            // Only the last layer may have a color, so a color followed by another layer is an
+            // error.
+            if color.is_some() {
+                return Err(i.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+            }
+            let (layer, layer_color) = BackgroundLayer::parse(i)?;
+            color = layer_color;
+            Ok(layer)
+        })?;
+        Ok(Background {
+            color: color.map_or_else(BackgroundColor::initial_value, BackgroundColor),
+            image: BackgroundImage(layers.iter().map(|layer| layer.image.clone()).collect()),
+            position: BackgroundPosition {
+                x: BackgroundPositionX(layers.iter().map(|l| l.position.horizontal).collect()),
+                y: BackgroundPositionY(layers.iter().map(|l| l.position.vertical).collect()),
+            },
+            size: BackgroundSize(layers.iter().map(|layer| layer.size).collect()),
+            repeat: computed::BackgroundRepeat(layers.iter().map(|layer| layer.repeat).collect()),
+            origin: computed::BackgroundOrigin(layers.iter().map(|layer| layer.origin).collect()),
+            clip: computed::BackgroundClip(layers.iter().map(|layer| layer.clip).collect()),
+        })
+    }
+}
+
+/// One layer of the `background` shorthand.
+struct BackgroundLayer {
+    image: Image,
+    position: BgPosition,
+    size: BgSize,
+    repeat: computed::BgRepeat,
+    origin: BoxArea,
+    clip: BoxArea,
+}
+
+impl BackgroundLayer {
+    /// Parses a layer, and the color given with it, if any.
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<(Self, Option<ColorUnit>), ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut image = None;
+        let mut position = None;
+        let mut size = None;
+        let mut repeat = None;
+        let mut attachment = None;
+        let mut boxes = Vec::new();
+        let mut color = None;
+        loop {
+            if image.is_none() {
+                if let Ok(value) = input.try_parse(Image::parse) {
+                    image = Some(value);
+                    continue;
+                }
+            }
+            if position.is_none() {
+                if let Ok(value) = input.try_parse(BgPosition::parse) {
+                    position = Some(value);
+                    if input.try_parse(|i| i.expect_delim('/')).is_ok() {
+                        size = Some(BgSize::parse(input)?);
+                    }
+                    continue;
+                }
+            }
+            if repeat.is_none() {
+                if let Ok(value) = input.try_parse(computed::BgRepeat::parse) {
+                    repeat = Some(value);
+                    continue;
+                }
+            }
+            if attachment.is_none() {
+                if let Ok(value) = input.try_parse(parse_attachment) {
+                    attachment = Some(value);
+                    continue;
+                }
+            }
+            if boxes.len() < 2 {
+                if let Ok(value) = input.try_parse(BoxArea::parse) {
+                    boxes.push(value);
+                    continue;
+                }
+            }
+            if color.is_none() {
+                if let Ok(value) = input.try_parse(ColorUnit::parse) {
+                    color = Some(value);
+                    continue;
+                }
+            }
+            break;
+        }
+        if image.is_none()
+            && position.is_none()
+            && repeat.is_none()
+            && attachment.is_none()
+            && boxes.is_empty()
+            && color.is_none()
+        {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        // A single box sets both the origin and the clip.
+        let (origin, clip) = match *boxes.as_slice() {
+            [origin, clip] => (origin, clip),
+            [area] => (area, area),
+            _ => (BoxArea::PaddingBox, BoxArea::BorderBox),
+        };
+        let layer = BackgroundLayer {
+            image: image.unwrap_or(Image::None),
+            position: position.unwrap_or(BgPosition {
+                horizontal: BgPositionComponent::start(),
+                vertical: BgPositionComponent::start(),
+            }),
+            size: size.unwrap_or_else(BgSize::auto),
+            repeat: repeat.unwrap_or(computed::BgRepeat {
+                horizontal: RepeatStyle::Repeat,
+                vertical: RepeatStyle::Repeat,
+            }),
+            origin,
+            clip,
+        };
+        Ok((layer, color))
+    }
+}
+
+/// Parses an `<attachment>`, which has no effect.
+fn parse_attachment<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    try_match_ident_ignore_ascii_case! { input,
+        "scroll" => Ok(()),
+        "fixed" => Ok(()),
+        "local" => Ok(()),
+    }
+}
+
+/// Parses a `<length-percentage>`, where zero doesn't need a unit.
+fn parse_length_percentage<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<LengthPercentage, ParseError<'i, StyleParseErrorKind<'i>>> {
+    if let Ok(length) = input.try_parse(LengthPercentage::parse) {
+        return Ok(length);
+    }
+    let location = input.current_source_location();
+    let token = input.next()?;
+    match *token {
+        Token::Number { value: 0., .. } => Ok(LengthPercentage::Length(NoCalcLength::Absolute(
+            AbsoluteLength::Px(0.),
+        ))),
+        _ => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+/// Parses a `<length-percentage>` that isn't negative, where zero doesn't need a unit.
+fn parse_non_negative_length_percentage<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<LengthPercentage, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let length = parse_length_percentage(input)?;
+    let negative = match length {
+        LengthPercentage::Length(NoCalcLength::Absolute(absolute)) => absolute.to_px() < 0.,
+        LengthPercentage::Percentage(percentage) => percentage.0 < 0.,
+    };
+    if negative {
+        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+    }
+    Ok(length)
+}
@@ -1,4 +1,5 @@
-use crate::style::values::specified::{ColorUnit, NoCalcLength};
+use crate::style::values::computed;
+use crate::style::values::specified::{AbsoluteLength, ColorUnit, LengthPercentage, NoCalcLength};
 use crate::style::StyleParseErrorKind;
 use cssparser::{ParseError, Parser, Token};
 
@@ -231,3 +232,102 @@ impl LineWidth {
         }
     }
 }
+
+/// Specified values of the `border-<corner>-radius` properties, such as `border-top-left-radius`:
+/// the horizontal and vertical radii of the ellipse that rounds the corner.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#border-radius
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderCornerRadius {
+    pub horizontal: LengthPercentage,
+    pub vertical: LengthPercentage,
+}
+
+impl BorderCornerRadius {
+    /// Parses `<length-percentage>{1,2}`, where a single value gives both radii, so the corner is
+    /// rounded by a quarter circle.  Negative values are invalid.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let horizontal = parse_radius(input)?;
+        let vertical = input.try_parse(parse_radius).unwrap_or(horizontal);
+        Ok(BorderCornerRadius {
+            horizontal,
+            vertical,
+        })
+    }
+}
+
+/// The `border-radius` shorthand, which sets the radii of all four corners.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#propdef-border-radius
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BorderRadius {
+    pub top_left: BorderCornerRadius,
+    pub top_right: BorderCornerRadius,
+    pub bottom_right: BorderCornerRadius,
+    pub bottom_left: BorderCornerRadius,
+}
+
+impl BorderRadius {
+    /// Parses `<length-percentage>{1,4} [ / <length-percentage>{1,4} ]?`.  The values before the
+    /// slash are the horizontal radii, and those after it the vertical radii, which are the same
+    /// as the horizontal ones if there's no slash.  Each set of radii is given in the order
+    /// top-left, top-right, bottom-right, and bottom-left, with missing values copied from the
+    /// opposite corner, the same way the `margin` shorthand fills in missing sides.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let horizontal = parse_corner_radii(input)?;
+        let vertical = if input.try_parse(|i| i.expect_delim('/')).is_ok() {
+            parse_corner_radii(input)?
+        } else {
+            horizontal
+        };
+        let corner = |i: usize| BorderCornerRadius {
+            horizontal: horizontal[i],
+            vertical: vertical[i],
+        };
+        Ok(BorderRadius {
+            top_left: corner(0),
+            top_right: corner(1),
+            bottom_right: corner(2),
+            bottom_left: corner(3),
+        })
+    }
+}
+
+/// Parses one to four radii, expanded to the radii of the top-left, top-right, bottom-right, and
+/// bottom-left corners.
+fn parse_corner_radii<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<[LengthPercentage; 4], ParseError<'i, StyleParseErrorKind<'i>>> {
+    let top_left = parse_radius(input)?;
+    let top_right = input.try_parse(parse_radius).unwrap_or(top_left);
+    let bottom_right = input.try_parse(parse_radius).unwrap_or(top_left);
+    let bottom_left = input.try_parse(parse_radius).unwrap_or(top_right);
+    Ok([top_left, top_right, bottom_right, bottom_left])
+}
+
+fn parse_radius<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<LengthPercentage, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    let radius = match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } if value >= 0. => NoCalcLength::parse_dimension(value, unit)
+            .ok()
+            .map(LengthPercentage::Length),
+        Token::Percentage { unit_value, .. } if unit_value >= 0. => Some(
+            LengthPercentage::Percentage(computed::Percentage(unit_value)),
+        ),
+        // Zero lengths don't need a unit.
+        Token::Number { value: 0., .. } => Some(LengthPercentage::Length(NoCalcLength::Absolute(
+            AbsoluteLength::Px(0.),
+        ))),
+        _ => None,
+    };
+    radius.ok_or_else(|| location.new_unexpected_token_error(token.clone()))
+}
@@ -0,0 +1,155 @@
+use crate::style::values::computed::{FlexDirection, FlexGrow, FlexShrink, FlexWrap};
+use crate::style::values::specified::{
+    AbsoluteLength, LengthPercentage, LengthPercentageOrAuto, NoCalcLength, Width,
+};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Specified values for the `flex-basis` property.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-basis-property
+#[derive(Clone, Copy, Debug)]
+pub enum FlexBasis {
+    Content,
+    Width(Width),
+}
+
+impl FlexBasis {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input
+            .try_parse(|i| i.expect_ident_matching("content"))
+            .is_ok()
+        {
+            return Ok(FlexBasis::Content);
+        }
+        Width::parse(input).map(FlexBasis::Width)
+    }
+
+    /// The `flex-basis` used by the `flex` shorthand when no basis is given, `0px`.
+    fn zero() -> Self {
+        FlexBasis::Width(Width::LengthPercentageOrAuto(
+            LengthPercentageOrAuto::LengthPercentage(LengthPercentage::Length(
+                NoCalcLength::Absolute(AbsoluteLength::Px(0.)),
+            )),
+        ))
+    }
+}
+
+/// The longhands set by the `flex` shorthand.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-property
+#[derive(Clone, Copy, Debug)]
+pub struct Flex {
+    pub grow: FlexGrow,
+    pub shrink: FlexShrink,
+    pub basis: FlexBasis,
+}
+
+impl Flex {
+    /// Parses `none | [ <'flex-grow'> <'flex-shrink'>? || <'flex-basis'> ]`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(Flex {
+                grow: FlexGrow(0.),
+                shrink: FlexShrink(0.),
+                basis: FlexBasis::Width(Width::LengthPercentageOrAuto(
+                    LengthPercentageOrAuto::Auto,
+                )),
+            });
+        }
+
+        let location = input.current_source_location();
+        let mut factors = None;
+        let mut basis = None;
+        loop {
+            if factors.is_none() {
+                if let Ok(grow) = input.try_parse(FlexGrow::parse) {
+                    let shrink = input.try_parse(FlexShrink::parse).ok();
+                    factors = Some((grow, shrink));
+                    // A unitless zero is only a valid `flex-basis` when it follows both flex
+                    // factors, since otherwise it would be ambiguous.
+                    if shrink.is_some() && basis.is_none() && input.try_parse(parse_zero).is_ok() {
+                        basis = Some(FlexBasis::zero());
+                    }
+                    continue;
+                }
+            }
+            if basis.is_none() {
+                if let Ok(parsed_basis) = input.try_parse(FlexBasis::parse) {
+                    basis = Some(parsed_basis);
+                    continue;
+                }
+            }
+            break;
+        }
+
+        if factors.is_none() && basis.is_none() {
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        // Omitted flex factors default to one, and an omitted basis to zero.
+        let (grow, shrink) = factors.unwrap_or((FlexGrow(1.), None));
+        Ok(Flex {
+            grow,
+            shrink: shrink.unwrap_or(FlexShrink(1.)),
+            basis: basis.unwrap_or_else(FlexBasis::zero),
+        })
+    }
+}
+
+fn parse_zero<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<(), ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    if input.expect_number()? == 0. {
+        Ok(())
+    } else {
+        Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+    }
+}
+
+/// The longhands set by the `flex-flow` shorthand.
+///
+/// https://drafts.csswg.org/css-flexbox-1/#flex-flow-property
+#[derive(Clone, Copy, Debug)]
+pub struct FlexFlow {
+    pub direction: FlexDirection,
+    pub wrap: FlexWrap,
+}
+
+impl FlexFlow {
+    /// Parses `<'flex-direction'> || <'flex-wrap'>`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let location = input.current_source_location();
+        let mut direction = None;
+        let mut wrap = None;
+        loop {
+            if direction.is_none() {
+                if let Ok(parsed_direction) = input.try_parse(FlexDirection::parse) {
+                    direction = Some(parsed_direction);
+                    continue;
+                }
+            }
+            if wrap.is_none() {
+                if let Ok(parsed_wrap) = input.try_parse(FlexWrap::parse) {
+                    wrap = Some(parsed_wrap);
+                    continue;
+                }
+            }
+            break;
+        }
+
+        if direction.is_none() && wrap.is_none() {
+            return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        Ok(FlexFlow {
+            direction: direction.unwrap_or_else(FlexDirection::initial_value),
+            wrap: wrap.unwrap_or_else(FlexWrap::initial_value),
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use crate::style::values::specified::LengthPercentage;
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Specified values for the `row-gap` and `column-gap` properties.
+///
+/// https://drafts.csswg.org/css-align-3/#column-row-gap
+#[derive(Clone, Copy, Debug)]
+pub enum Gap {
+    Normal,
+    LengthPercentage(LengthPercentage),
+}
+
+impl Gap {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input
+            .try_parse(|i| i.expect_ident_matching("normal"))
+            .is_ok()
+        {
+            return Ok(Gap::Normal);
+        }
+        input
+            .try_parse(|i| LengthPercentage::parse(i))
+            .map(Gap::LengthPercentage)
+    }
+}
@@ -0,0 +1,318 @@
+use crate::style::values::computed::grid::parse_custom_ident;
+use crate::style::values::computed::{GridLine, RepeatCount};
+use crate::style::values::specified::LengthPercentage;
+use crate::style::values::CSSFloat;
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, Token};
+
+/// Specified value of a single track sizing function.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-track-breadth
+#[derive(Clone, Copy, Debug)]
+pub enum TrackBreadth {
+    LengthPercentage(LengthPercentage),
+    /// A flexible length, in `fr` units.
+    Flex(CSSFloat),
+    Auto,
+    MinContent,
+    MaxContent,
+}
+
+impl TrackBreadth {
+    /// Parses `<length-percentage> | <flex> | min-content | max-content | auto`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(flex) = input.try_parse(parse_flex) {
+            return Ok(TrackBreadth::Flex(flex));
+        }
+        TrackBreadth::parse_inflexible(input)
+    }
+
+    /// Parses a track breadth other than a flexible length, as is required for the minimum of a
+    /// `minmax()`.
+    fn parse_inflexible<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse) {
+            return Ok(TrackBreadth::LengthPercentage(lp));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "auto" => Ok(TrackBreadth::Auto),
+            "min-content" => Ok(TrackBreadth::MinContent),
+            "max-content" => Ok(TrackBreadth::MaxContent),
+        }
+    }
+}
+
+/// Parses a non-negative `<flex>` value, a number with the `fr` unit.
+fn parse_flex<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<CSSFloat, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    match *input.next()? {
+        Token::Dimension {
+            value, ref unit, ..
+        } if unit.eq_ignore_ascii_case("fr") && value >= 0. => Ok(value),
+        ref token => Err(location.new_unexpected_token_error(token.clone())),
+    }
+}
+
+/// Specified value of the size of a single grid track.
+///
+/// TODO: Support `fit-content()`.
+///
+/// https://drafts.csswg.org/css-grid-1/#typedef-track-size
+#[derive(Clone, Copy, Debug)]
+pub enum TrackSize {
+    Breadth(TrackBreadth),
+    Minmax(TrackBreadth, TrackBreadth),
+}
+
+impl TrackSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let minmax = input.try_parse(|i| {
+            i.expect_function_matching("minmax")?;
+            i.parse_nested_block(|i| {
+                let min = TrackBreadth::parse_inflexible(i)?;
+                i.expect_comma()?;
+                let max = TrackBreadth::parse(i)?;
+                Ok(TrackSize::Minmax(min, max))
+            })
+        });
+        if minmax.is_ok() {
+            return minmax;
+        }
+        TrackBreadth::parse(input).map(TrackSize::Breadth)
+    }
+}
+
+/// Parses a bracketed list of line names, such as `[header-start main-start]`.
+fn parse_line_names<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Vec<String>, ParseError<'i, StyleParseErrorKind<'i>>> {
+    input.expect_square_bracket_block()?;
+    input.parse_nested_block(|i| {
+        let mut names = Vec::new();
+        while !i.is_exhausted() {
+            names.push(parse_custom_ident(i)?);
+        }
+        Ok(names)
+    })
+}
+
+/// The line names before each track and after the last, along with the tracks themselves.
+type LineNamesAndTracks<T> = (Vec<Vec<String>>, Vec<T>);
+
+/// Parses a sequence of `[ <line-names>? <track> ]+ <line-names>?`, where `parse_track` parses a
+/// single track.
+fn parse_tracks<'i, 't, T, F>(
+    input: &mut Parser<'i, 't>,
+    mut parse_track: F,
+) -> Result<LineNamesAndTracks<T>, ParseError<'i, StyleParseErrorKind<'i>>>
+where
+    F: FnMut(&mut Parser<'i, 't>) -> Result<T, ParseError<'i, StyleParseErrorKind<'i>>>,
+{
+    let location = input.current_source_location();
+    let mut line_names = Vec::new();
+    let mut tracks = Vec::new();
+    loop {
+        line_names.push(input.try_parse(parse_line_names).unwrap_or_default());
+        match input.try_parse(&mut parse_track) {
+            Ok(track) => tracks.push(track),
+            Err(_) => break,
+        }
+    }
+    if tracks.is_empty() {
+        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+    }
+    Ok((line_names, tracks))
+}
+
+/// Specified value of a `repeat()` function.
+///
+/// https://drafts.csswg.org/css-grid-1/#repeat-notation
+#[derive(Clone, Debug)]
+pub struct TrackRepeat {
+    pub count: RepeatCount,
+    /// The line names before each track, and after the last.
+    pub line_names: Vec<Vec<String>>,
+    pub track_sizes: Vec<TrackSize>,
+}
+
+impl TrackRepeat {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input.expect_function_matching("repeat")?;
+        input.parse_nested_block(|i| {
+            let location = i.current_source_location();
+            let count = if i
+                .try_parse(|i| i.expect_ident_matching("auto-fill"))
+                .is_ok()
+            {
+                RepeatCount::AutoFill
+            } else if i.try_parse(|i| i.expect_ident_matching("auto-fit")).is_ok() {
+                RepeatCount::AutoFit
+            } else {
+                match i.expect_integer()? {
+                    count if count > 0 => RepeatCount::Number(count as u32),
+                    _ => {
+                        return Err(location.new_custom_error(StyleParseErrorKind::UnspecifiedError))
+                    }
+                }
+            };
+            i.expect_comma()?;
+            let (line_names, track_sizes) = parse_tracks(i, TrackSize::parse)?;
+            Ok(TrackRepeat {
+                count,
+                line_names,
+                track_sizes,
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TrackListValue {
+    TrackSize(TrackSize),
+    Repeat(TrackRepeat),
+}
+
+impl TrackListValue {
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(repeat) = input.try_parse(TrackRepeat::parse) {
+            return Ok(TrackListValue::Repeat(repeat));
+        }
+        TrackSize::parse(input).map(TrackListValue::TrackSize)
+    }
+}
+
+/// Specified value of a track list.
+///
+/// https://drafts.csswg.org/css-grid-1/#track-sizing
+#[derive(Clone, Debug)]
+pub struct TrackList {
+    /// The line names before each value, and after the last.
+    pub line_names: Vec<Vec<String>>,
+    pub values: Vec<TrackListValue>,
+}
+
+/// Specified values for the `grid-template-rows` and `grid-template-columns` properties.
+///
+/// https://drafts.csswg.org/css-grid-1/#track-sizing
+#[derive(Clone, Debug)]
+pub enum GridTemplateComponent {
+    None,
+    TrackList(TrackList),
+}
+
+impl GridTemplateComponent {
+    /// Parses `none | <track-list> | <auto-track-list>`.
+    ///
+    /// TODO: An `<auto-track-list>` may only contain a single `auto-fill` or `auto-fit` repeat,
+    /// whose tracks must have fixed sizes, but we don't validate this.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(GridTemplateComponent::None);
+        }
+        let (line_names, values) = parse_tracks(input, TrackListValue::parse)?;
+        Ok(GridTemplateComponent::TrackList(TrackList {
+            line_names,
+            values,
+        }))
+    }
+}
+
+/// Specified values for the `grid-auto-rows` and `grid-auto-columns` properties.
+///
+/// https://drafts.csswg.org/css-grid-1/#auto-tracks
+#[derive(Clone, Debug)]
+pub struct ImplicitGridTracks(pub Vec<TrackSize>);
+
+impl ImplicitGridTracks {
+    /// Parses `<track-size>+`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut track_sizes = vec![TrackSize::parse(input)?];
+        while let Ok(track_size) = input.try_parse(TrackSize::parse) {
+            track_sizes.push(track_size);
+        }
+        Ok(ImplicitGridTracks(track_sizes))
+    }
+}
+
+/// The longhands set by the `grid-row` and `grid-column` shorthands.
+///
+/// https://drafts.csswg.org/css-grid-1/#placement-shorthands
+#[derive(Clone, Debug)]
+pub struct GridLinePair {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+impl GridLinePair {
+    /// Parses `<grid-line> [ / <grid-line> ]?`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let start = GridLine::parse(input)?;
+        let end = match input.try_parse(|i| i.expect_delim('/')) {
+            Ok(()) => GridLine::parse(input)?,
+            Err(_) => omitted_end_line(&start),
+        };
+        Ok(GridLinePair { start, end })
+    }
+}
+
+/// The longhands set by the `grid-area` shorthand.
+///
+/// https://drafts.csswg.org/css-grid-1/#propdef-grid-area
+#[derive(Clone, Debug)]
+pub struct GridArea {
+    pub row_start: GridLine,
+    pub column_start: GridLine,
+    pub row_end: GridLine,
+    pub column_end: GridLine,
+}
+
+impl GridArea {
+    /// Parses `<grid-line> [ / <grid-line> ]{0,3}`.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut lines = vec![GridLine::parse(input)?];
+        while lines.len() < 4 && input.try_parse(|i| i.expect_delim('/')).is_ok() {
+            lines.push(GridLine::parse(input)?);
+        }
+        let mut lines = lines.into_iter();
+        let row_start = lines.next().unwrap_or(GridLine::Auto);
+        let column_start = lines.next().unwrap_or_else(|| omitted_end_line(&row_start));
+        let row_end = lines.next().unwrap_or_else(|| omitted_end_line(&row_start));
+        let column_end = lines
+            .next()
+            .unwrap_or_else(|| omitted_end_line(&column_start));
+        Ok(GridArea {
+            row_start,
+            column_start,
+            row_end,
+            column_end,
+        })
+    }
+}
+
+/// The value of a grid line omitted from a placement shorthand, which copies the corresponding
+/// line if it's a name, and is otherwise `auto`.
+fn omitted_end_line(line: &GridLine) -> GridLine {
+    match line {
+        GridLine::Ident(_) => line.clone(),
+        _ => GridLine::Auto,
+    }
+}
@@ -1,22 +1,90 @@
-use crate::style::values::specified::LengthPercentageOrAuto;
+use crate::style::values::specified::{
+    IntrinsicSize, LengthPercentageOrAuto, LengthPercentageOrNone, MaxSize, Size,
+};
 use crate::style::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
 
 /// Specified values for the `height` property.
 ///
 /// https://www.w3.org/TR/css-sizing-3/#property-index
-// TODO: Need to support various other value types, such as `{min, max}-content`.
 #[derive(Clone, Copy, Debug)]
 pub enum Height {
     LengthPercentageOrAuto(LengthPercentageOrAuto),
+    IntrinsicSize(IntrinsicSize),
 }
 
 impl Height {
     pub fn parse<'i, 't>(
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
-        input
-            .try_parse(|i| LengthPercentageOrAuto::parse(i))
-            .map(Height::LengthPercentageOrAuto)
+        Size::parse(input).map(Height::from)
+    }
+}
+
+impl From<Size> for Height {
+    fn from(size: Size) -> Self {
+        match size {
+            Size::LengthPercentageOrAuto(lp_or_auto) => Height::LengthPercentageOrAuto(lp_or_auto),
+            Size::IntrinsicSize(intrinsic) => Height::IntrinsicSize(intrinsic),
+        }
+    }
+}
+
+/// Specified values for the `min-height` property.
+///
+/// `auto` is the initial value, which resolves to zero outside of flex layout, where it's the
+/// automatic minimum size of a flex item.
+///
+/// https://www.w3.org/TR/css-sizing-3/#min-size-properties
+#[derive(Clone, Copy, Debug)]
+pub enum MinHeight {
+    LengthPercentageOrAuto(LengthPercentageOrAuto),
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl MinHeight {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        Size::parse(input).map(MinHeight::from)
+    }
+}
+
+impl From<Size> for MinHeight {
+    fn from(size: Size) -> Self {
+        match size {
+            Size::LengthPercentageOrAuto(lp_or_auto) => {
+                MinHeight::LengthPercentageOrAuto(lp_or_auto)
+            }
+            Size::IntrinsicSize(intrinsic) => MinHeight::IntrinsicSize(intrinsic),
+        }
+    }
+}
+
+/// Specified values for the `max-height` property.
+///
+/// https://www.w3.org/TR/css-sizing-3/#max-size-properties
+#[derive(Clone, Copy, Debug)]
+pub enum MaxHeight {
+    LengthPercentageOrNone(LengthPercentageOrNone),
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl MaxHeight {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        MaxSize::parse(input).map(MaxHeight::from)
+    }
+}
+
+impl From<MaxSize> for MaxHeight {
+    fn from(size: MaxSize) -> Self {
+        match size {
+            MaxSize::LengthPercentageOrNone(lp_or_none) => {
+                MaxHeight::LengthPercentageOrNone(lp_or_none)
+            }
+            MaxSize::IntrinsicSize(intrinsic) => MaxHeight::IntrinsicSize(intrinsic),
+        }
     }
 }
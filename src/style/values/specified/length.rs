@@ -170,3 +170,26 @@ impl LengthPercentageOrAuto {
         }
     }
 }
+
+/// A `<length-percentage>` value, or the `none` keyword.  Used by the `max-width` and
+/// `max-height` properties, where `none` means the size is unconstrained.
+///
+/// https://www.w3.org/TR/css-sizing-3/#max-size-properties
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LengthPercentageOrNone {
+    LengthPercentage(LengthPercentage),
+    None,
+}
+
+impl LengthPercentageOrNone {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp) = input.try_parse(LengthPercentage::parse) {
+            return Ok(LengthPercentageOrNone::LengthPercentage(lp));
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "none" => Ok(LengthPercentageOrNone::None),
+        }
+    }
+}
@@ -1,19 +1,31 @@
 pub mod background;
 pub mod border;
 pub mod color;
+pub mod flex;
 pub mod font;
+pub mod gap;
+pub mod grid;
 pub mod height;
 pub mod length;
 pub mod margin;
 pub mod padding;
+pub mod position;
+pub mod shadow;
+pub mod sizing;
+pub mod table;
 pub mod width;
 
-pub use background::BackgroundColor;
+pub use background::{
+    Background, BackgroundColor, BackgroundImage, BackgroundPosition, BackgroundPositionX,
+    BackgroundPositionY, BackgroundSize,
+};
 
 pub use border::BorderBottomColor;
 pub use border::BorderBottomWidth;
+pub use border::BorderCornerRadius;
 pub use border::BorderLeftColor;
 pub use border::BorderLeftWidth;
+pub use border::BorderRadius;
 pub use border::BorderRightColor;
 pub use border::BorderRightWidth;
 pub use border::BorderTopColor;
@@ -22,14 +34,24 @@ pub use border::BorderTopWidth;
 pub use color::Color;
 pub use color::ColorUnit;
 
+pub use flex::{Flex, FlexBasis, FlexFlow};
+
 pub use font::FontSize;
 pub use font::FONT_MEDIUM_PX;
 
-pub use height::Height;
+pub use gap::Gap;
+
+pub use grid::{
+    GridArea, GridLinePair, GridTemplateComponent, ImplicitGridTracks, TrackBreadth, TrackList,
+    TrackListValue, TrackRepeat, TrackSize,
+};
+
+pub use height::{Height, MaxHeight, MinHeight};
 
 pub use length::AbsoluteLength;
 pub use length::LengthPercentage;
 pub use length::LengthPercentageOrAuto;
+pub use length::LengthPercentageOrNone;
 pub use length::NoCalcLength;
 
 pub use margin::MarginBottom;
@@ -42,4 +64,12 @@ pub use padding::PaddingLeft;
 pub use padding::PaddingRight;
 pub use padding::PaddingTop;
 
-pub use width::Width;
+pub use position::{Bottom, Left, Right, Top};
+
+pub use shadow::{BoxShadow, TextShadow};
+
+pub use sizing::{IntrinsicSize, MaxSize, Size};
+
+pub use table::BorderSpacing;
+
+pub use width::{MaxWidth, MinWidth, Width};
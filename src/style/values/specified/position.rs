@@ -0,0 +1,75 @@
+use crate::style::values::specified::LengthPercentageOrAuto;
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// Specified values for `bottom`.
+///
+/// https://www.w3.org/TR/css-position-3/#insets
+#[derive(Clone, Debug)]
+pub struct Bottom {
+    pub lp_or_auto: LengthPercentageOrAuto,
+}
+
+impl Bottom {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .try_parse(|i| LengthPercentageOrAuto::parse(i))
+            .map(|lp_or_auto| Bottom { lp_or_auto })
+    }
+}
+
+/// Specified values for `left`.
+///
+/// https://www.w3.org/TR/css-position-3/#insets
+#[derive(Clone, Debug)]
+pub struct Left {
+    pub lp_or_auto: LengthPercentageOrAuto,
+}
+
+impl Left {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .try_parse(|i| LengthPercentageOrAuto::parse(i))
+            .map(|lp_or_auto| Left { lp_or_auto })
+    }
+}
+
+/// Specified values for `right`.
+///
+/// https://www.w3.org/TR/css-position-3/#insets
+#[derive(Clone, Debug)]
+pub struct Right {
+    pub lp_or_auto: LengthPercentageOrAuto,
+}
+
+impl Right {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .try_parse(|i| LengthPercentageOrAuto::parse(i))
+            .map(|lp_or_auto| Right { lp_or_auto })
+    }
+}
+
+/// Specified values for `top`.
+///
+/// https://www.w3.org/TR/css-position-3/#insets
+#[derive(Clone, Debug)]
+pub struct Top {
+    pub lp_or_auto: LengthPercentageOrAuto,
+}
+
+impl Top {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        input
+            .try_parse(|i| LengthPercentageOrAuto::parse(i))
+            .map(|lp_or_auto| Top { lp_or_auto })
+    }
+}
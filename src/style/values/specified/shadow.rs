@@ -0,0 +1,149 @@
+use crate::style::values::specified::{AbsoluteLength, ColorUnit, NoCalcLength};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, Token};
+
+/// Specified value of `box-shadow`, with one shadow per comma-separated value.  The first shadow is
+/// painted on top of the rest, and there are none for the `none` keyword.
+///
+/// https://drafts.csswg.org/css-backgrounds-3/#box-shadow
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxShadow(pub Vec<Shadow>);
+
+impl BoxShadow {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        parse_shadows(input, true).map(BoxShadow)
+    }
+}
+
+/// Specified value of `text-shadow`, which is the same as that of `box-shadow`, except that text
+/// shadows can't be inset or spread.
+///
+/// https://drafts.csswg.org/css-text-decor-3/#text-shadow-property
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextShadow(pub Vec<Shadow>);
+
+impl TextShadow {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        parse_shadows(input, false).map(TextShadow)
+    }
+}
+
+/// A single shadow of `box-shadow` or `text-shadow`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shadow {
+    /// The color of the shadow, which is `currentColor` if `None`.
+    pub color: Option<ColorUnit>,
+    pub offset_x: NoCalcLength,
+    pub offset_y: NoCalcLength,
+    /// How far the edges of the shadow are blurred, which is never negative.
+    pub blur_radius: NoCalcLength,
+    /// How far the shadow is expanded, or contracted if negative, before it's blurred.
+    pub spread: NoCalcLength,
+    /// Whether the shadow is cast inside the padding box rather than outside the border box.
+    pub inset: bool,
+}
+
+impl Shadow {
+    /// Parses `<color>? && <length>{2} <length [0,∞]>? <length>? && inset?`, where the third length
+    /// is the blur radius and the fourth the spread.  The spread and `inset` keyword are only
+    /// allowed if `allow_inset_and_spread`.
+    fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+        allow_inset_and_spread: bool,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let mut color = None;
+        let mut lengths = None;
+        let mut inset = false;
+        loop {
+            if allow_inset_and_spread
+                && !inset
+                && input
+                    .try_parse(|i| i.expect_ident_matching("inset"))
+                    .is_ok()
+            {
+                inset = true;
+                continue;
+            }
+            if color.is_none() {
+                if let Ok(unit) = input.try_parse(ColorUnit::parse) {
+                    color = Some(unit);
+                    continue;
+                }
+            }
+            if lengths.is_none() {
+                if let Ok(parsed) = input.try_parse(|i| parse_lengths(i, allow_inset_and_spread)) {
+                    lengths = Some(parsed);
+                    continue;
+                }
+            }
+            break;
+        }
+        let (offset_x, offset_y, blur_radius, spread) = match lengths {
+            Some(lengths) => lengths,
+            None => return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError)),
+        };
+        Ok(Shadow {
+            color,
+            offset_x,
+            offset_y,
+            blur_radius,
+            spread,
+            inset,
+        })
+    }
+}
+
+type ShadowLengths = (NoCalcLength, NoCalcLength, NoCalcLength, NoCalcLength);
+
+/// Parses the horizontal and vertical offsets of a shadow, followed by its optional blur radius
+/// and, if `allow_spread`, spread, which are zero if missing.
+fn parse_lengths<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    allow_spread: bool,
+) -> Result<ShadowLengths, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let zero = NoCalcLength::Absolute(AbsoluteLength::Px(0.));
+    let offset_x = parse_length(input, false)?;
+    let offset_y = parse_length(input, false)?;
+    let blur_radius = match input.try_parse(|i| parse_length(i, true)) {
+        Ok(blur_radius) => blur_radius,
+        Err(_) => return Ok((offset_x, offset_y, zero, zero)),
+    };
+    let spread = if allow_spread {
+        input.try_parse(|i| parse_length(i, false)).unwrap_or(zero)
+    } else {
+        zero
+    };
+    Ok((offset_x, offset_y, blur_radius, spread))
+}
+
+fn parse_length<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    non_negative: bool,
+) -> Result<NoCalcLength, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    let length = match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } if !non_negative || value >= 0. => NoCalcLength::parse_dimension(value, unit).ok(),
+        // Zero lengths don't need a unit.
+        Token::Number { value: 0., .. } => Some(NoCalcLength::Absolute(AbsoluteLength::Px(0.))),
+        _ => None,
+    };
+    length.ok_or_else(|| location.new_unexpected_token_error(token.clone()))
+}
+
+/// Parses `none`, which is no shadows, or a comma-separated list of shadows.
+fn parse_shadows<'i, 't>(
+    input: &mut Parser<'i, 't>,
+    allow_inset_and_spread: bool,
+) -> Result<Vec<Shadow>, ParseError<'i, StyleParseErrorKind<'i>>> {
+    if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+        return Ok(Vec::new());
+    }
+    input.parse_comma_separated(|i| Shadow::parse(i, allow_inset_and_spread))
+}
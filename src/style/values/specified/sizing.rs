@@ -0,0 +1,77 @@
+use crate::style::values::specified::{
+    LengthPercentage, LengthPercentageOrAuto, LengthPercentageOrNone,
+};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser};
+
+/// One of the keywords for the intrinsic sizes of a box, which the sizing properties accept
+/// besides lengths and percentages.
+///
+/// https://drafts.csswg.org/css-sizing-3/#sizing-values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IntrinsicSize {
+    MinContent,
+    MaxContent,
+    /// `fit-content(<length-percentage>)`, which is the argument clamped between the min-content
+    /// and max-content sizes.
+    FitContent(LengthPercentage),
+}
+
+impl IntrinsicSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let fit_content = input.try_parse(|i| {
+            i.expect_function_matching("fit-content")?;
+            i.parse_nested_block(|i| LengthPercentage::parse(i).map(IntrinsicSize::FitContent))
+        });
+        if fit_content.is_ok() {
+            return fit_content;
+        }
+        try_match_ident_ignore_ascii_case! { input,
+            "min-content" => Ok(IntrinsicSize::MinContent),
+            "max-content" => Ok(IntrinsicSize::MaxContent),
+        }
+    }
+}
+
+/// A value accepted by `width`, `height`, `min-width`, `min-height`, and their flow-relative
+/// counterparts.
+///
+/// https://drafts.csswg.org/css-sizing-3/#sizing-values
+#[derive(Clone, Copy, Debug)]
+pub enum Size {
+    LengthPercentageOrAuto(LengthPercentageOrAuto),
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl Size {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp_or_auto) = input.try_parse(LengthPercentageOrAuto::parse) {
+            return Ok(Size::LengthPercentageOrAuto(lp_or_auto));
+        }
+        IntrinsicSize::parse(input).map(Size::IntrinsicSize)
+    }
+}
+
+/// A value accepted by `max-width`, `max-height`, and their flow-relative counterparts.
+///
+/// https://drafts.csswg.org/css-sizing-3/#sizing-values
+#[derive(Clone, Copy, Debug)]
+pub enum MaxSize {
+    LengthPercentageOrNone(LengthPercentageOrNone),
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl MaxSize {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        if let Ok(lp_or_none) = input.try_parse(LengthPercentageOrNone::parse) {
+            return Ok(MaxSize::LengthPercentageOrNone(lp_or_none));
+        }
+        IntrinsicSize::parse(input).map(MaxSize::IntrinsicSize)
+    }
+}
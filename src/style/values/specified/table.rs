@@ -0,0 +1,45 @@
+use crate::style::values::specified::{AbsoluteLength, NoCalcLength};
+use crate::style::StyleParseErrorKind;
+use cssparser::{ParseError, Parser, Token};
+
+/// Specified value of `border-spacing`.
+///
+/// https://www.w3.org/TR/CSS22/tables.html#separated-borders
+#[derive(Clone, Copy, Debug)]
+pub struct BorderSpacing {
+    pub horizontal: NoCalcLength,
+    pub vertical: NoCalcLength,
+}
+
+impl BorderSpacing {
+    /// Parses `<length> <length>?`, where a single length gives both the horizontal and vertical
+    /// spacing.  Negative lengths are invalid.
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        let horizontal = parse_non_negative_length(input)?;
+        let vertical = input
+            .try_parse(parse_non_negative_length)
+            .unwrap_or(horizontal);
+        Ok(BorderSpacing {
+            horizontal,
+            vertical,
+        })
+    }
+}
+
+fn parse_non_negative_length<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<NoCalcLength, ParseError<'i, StyleParseErrorKind<'i>>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+    let length = match *token {
+        Token::Dimension {
+            value, ref unit, ..
+        } if value >= 0. => NoCalcLength::parse_dimension(value, unit).ok(),
+        // Zero lengths don't need a unit.
+        Token::Number { value: 0., .. } => Some(NoCalcLength::Absolute(AbsoluteLength::Px(0.))),
+        _ => None,
+    };
+    length.ok_or_else(|| location.new_unexpected_token_error(token.clone()))
+}
@@ -1,22 +1,90 @@
-use crate::style::values::specified::LengthPercentageOrAuto;
+use crate::style::values::specified::{
+    IntrinsicSize, LengthPercentageOrAuto, LengthPercentageOrNone, MaxSize, Size,
+};
 use crate::style::StyleParseErrorKind;
 use cssparser::{ParseError, Parser};
 
 /// Specified values for the `width` property.
 ///
 /// https://www.w3.org/TR/css-sizing-3/#property-index
-// TODO: Need to support various other value types, such as `{min, max}-content`.
 #[derive(Clone, Copy, Debug)]
 pub enum Width {
     LengthPercentageOrAuto(LengthPercentageOrAuto),
+    IntrinsicSize(IntrinsicSize),
 }
 
 impl Width {
     pub fn parse<'i, 't>(
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
-        input
-            .try_parse(|i| LengthPercentageOrAuto::parse(i))
-            .map(Width::LengthPercentageOrAuto)
+        Size::parse(input).map(Width::from)
+    }
+}
+
+impl From<Size> for Width {
+    fn from(size: Size) -> Self {
+        match size {
+            Size::LengthPercentageOrAuto(lp_or_auto) => Width::LengthPercentageOrAuto(lp_or_auto),
+            Size::IntrinsicSize(intrinsic) => Width::IntrinsicSize(intrinsic),
+        }
+    }
+}
+
+/// Specified values for the `min-width` property.
+///
+/// `auto` is the initial value, which resolves to zero outside of flex layout, where it's the
+/// automatic minimum size of a flex item.
+///
+/// https://www.w3.org/TR/css-sizing-3/#min-size-properties
+#[derive(Clone, Copy, Debug)]
+pub enum MinWidth {
+    LengthPercentageOrAuto(LengthPercentageOrAuto),
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl MinWidth {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        Size::parse(input).map(MinWidth::from)
+    }
+}
+
+impl From<Size> for MinWidth {
+    fn from(size: Size) -> Self {
+        match size {
+            Size::LengthPercentageOrAuto(lp_or_auto) => {
+                MinWidth::LengthPercentageOrAuto(lp_or_auto)
+            }
+            Size::IntrinsicSize(intrinsic) => MinWidth::IntrinsicSize(intrinsic),
+        }
+    }
+}
+
+/// Specified values for the `max-width` property.
+///
+/// https://www.w3.org/TR/css-sizing-3/#max-size-properties
+#[derive(Clone, Copy, Debug)]
+pub enum MaxWidth {
+    LengthPercentageOrNone(LengthPercentageOrNone),
+    IntrinsicSize(IntrinsicSize),
+}
+
+impl MaxWidth {
+    pub fn parse<'i, 't>(
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i, StyleParseErrorKind<'i>>> {
+        MaxSize::parse(input).map(MaxWidth::from)
+    }
+}
+
+impl From<MaxSize> for MaxWidth {
+    fn from(size: MaxSize) -> Self {
+        match size {
+            MaxSize::LengthPercentageOrNone(lp_or_none) => {
+                MaxWidth::LengthPercentageOrNone(lp_or_none)
+            }
+            MaxSize::IntrinsicSize(intrinsic) => MaxWidth::IntrinsicSize(intrinsic),
+        }
     }
 }
@@ -1,7 +1,8 @@
 use crate::style::values::computed::length::CSSPixelLength;
 use crate::style::values::computed::length::LengthPercentage;
 use crate::style::values::computed::length::LengthPercentageOrAuto;
-use crate::style::values::used::ToPx;
+use crate::style::values::used::{ToAu, ToPx};
+use app_units::Au;
 
 impl ToPx for LengthPercentageOrAuto {
     /// Note this implementation naively treats `auto` as a zero pixel length, which correct in a
@@ -25,3 +26,22 @@ impl ToPx for LengthPercentage {
         }
     }
 }
+
+impl ToAu for LengthPercentageOrAuto {
+    /// Treats `auto` as zero, as `ToPx` does.
+    fn to_au(&self, containing_size: Au) -> Au {
+        match self {
+            LengthPercentageOrAuto::Auto => Au(0),
+            LengthPercentageOrAuto::LengthPercentage(lp) => lp.to_au(containing_size),
+        }
+    }
+}
+
+impl ToAu for LengthPercentage {
+    fn to_au(&self, containing_size: Au) -> Au {
+        match self {
+            LengthPercentage::Length(len) => Au::from(*len),
+            LengthPercentage::Percentage(percentage) => percentage.au_relative_to(containing_size),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::style::values::computed::length::CSSPixelLength;
+use app_units::Au;
 
 pub mod length;
 
@@ -10,3 +11,8 @@ pub mod length;
 pub trait ToPx {
     fn to_px(&self, containing_size: CSSPixelLength) -> CSSPixelLength;
 }
+
+/// Like `ToPx`, but resolves to app units, which is what layout works in.
+pub trait ToAu {
+    fn to_au(&self, containing_size: Au) -> Au;
+}
@@ -11,3 +11,36 @@ fn vertical_lr_block_box_only() {
         .succeeds();
     assert_snapshot!(dump_layout_cmd.stdout());
 }
+
+#[test]
+fn mixed_mode_nesting() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/directional/writing_mode/mixed-mode-nesting.html")
+        .arg("tests/websrc/directional/writing_mode/mixed-mode-nesting.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn physical_box_sides() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/directional/writing_mode/physical-box-sides.html")
+        .arg("tests/websrc/directional/writing_mode/physical-box-sides.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn flow_relative_properties() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/directional/writing_mode/flow-relative-properties.html")
+        .arg("tests/websrc/directional/writing_mode/flow-relative-properties.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
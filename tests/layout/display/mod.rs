@@ -0,0 +1,24 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn inline_block() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/display/inline-block.html")
+        .arg("tests/websrc/display/inline-block.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn flow_root() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/display/flow-root.html")
+        .arg("tests/websrc/display/flow-root.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
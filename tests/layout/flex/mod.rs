@@ -0,0 +1,68 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn row_grow_shrink() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/flex/row-grow-shrink.html")
+        .arg("tests/websrc/flex/row-grow-shrink.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn column() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/flex/column.html")
+        .arg("tests/websrc/flex/column.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn wrap() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/flex/wrap.html")
+        .arg("tests/websrc/flex/wrap.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn justify_align() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/flex/justify-align.html")
+        .arg("tests/websrc/flex/justify-align.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn order_reverse() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/flex/order-reverse.html")
+        .arg("tests/websrc/flex/order-reverse.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn inline_flex() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/flex/inline-flex.html")
+        .arg("tests/websrc/flex/inline-flex.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
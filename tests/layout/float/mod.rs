@@ -0,0 +1,68 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn left_right() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/float/left-right.html")
+        .arg("tests/websrc/float/left-right.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn clear() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/float/clear.html")
+        .arg("tests/websrc/float/clear.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn clear_margins() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/float/clear-margins.html")
+        .arg("tests/websrc/float/clear-margins.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn bfc() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/float/bfc.html")
+        .arg("tests/websrc/float/bfc.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn bfc_margins() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/float/bfc-margins.html")
+        .arg("tests/websrc/float/bfc-margins.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn inline() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/float/inline.html")
+        .arg("tests/websrc/float/inline.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
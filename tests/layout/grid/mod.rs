@@ -0,0 +1,68 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn fr_repeat() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/grid/fr-repeat.html")
+        .arg("tests/websrc/grid/fr-repeat.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn minmax_auto_rows() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/grid/minmax-auto-rows.html")
+        .arg("tests/websrc/grid/minmax-auto-rows.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn template_areas() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/grid/template-areas.html")
+        .arg("tests/websrc/grid/template-areas.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn auto_placement() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/grid/auto-placement.html")
+        .arg("tests/websrc/grid/auto-placement.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn column_flow() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/grid/column-flow.html")
+        .arg("tests/websrc/grid/column-flow.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn inline_grid() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/grid/inline-grid.html")
+        .arg("tests/websrc/grid/inline-grid.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
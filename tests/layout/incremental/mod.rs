@@ -0,0 +1,85 @@
+use crate::util::{dump_layout_cmd, CommandUnderTest};
+
+/// Runs `dump-layout` over `files`, once from scratch, and once incrementally after laying out
+/// at `relayout_from_width`, with `extra_args` added, asserting that both give the same layout.
+/// Returns the incremental run.
+fn relayout(files: &[&str], relayout_from_width: f32, extra_args: &[&str]) -> CommandUnderTest {
+    let mut fresh = dump_layout_cmd();
+    fresh.arg("--files").args(files).succeeds().no_stderr();
+    let mut incremental = dump_layout_cmd();
+    incremental
+        .arg("--files")
+        .args(files)
+        .arg("--relayout-from-width")
+        .arg(format!("{}", relayout_from_width))
+        .args(extra_args)
+        .succeeds()
+        .stdout_is(fresh.stdout());
+    incremental
+}
+
+#[test]
+fn unchanged_viewport_reuses_every_box() {
+    relayout(
+        &[
+            "tests/websrc/rainbow-divs.html",
+            "tests/websrc/rainbow-divs.css",
+        ],
+        1920.,
+        &[],
+    )
+    .stderr_is("laid out 0 of 36 boxes\n");
+}
+
+#[test]
+fn resize_reuses_boxes_with_same_containing_block_size() {
+    relayout(
+        &[
+            "tests/websrc/display/inline-block.html",
+            "tests/websrc/display/inline-block.css",
+        ],
+        700.,
+        &[],
+    )
+    .stderr_is("laid out 10 of 16 boxes\n");
+}
+
+#[test]
+fn invalidated_subtree_and_its_ancestors_are_laid_out_again() {
+    relayout(
+        &[
+            "tests/websrc/display/inline-block.html",
+            "tests/websrc/display/inline-block.css",
+        ],
+        1920.,
+        &["--invalidate", ".inner"],
+    )
+    .stderr_is("laid out 12 of 16 boxes\n");
+}
+
+#[test]
+fn positioned_offsets_are_not_applied_twice() {
+    for fixture in &["relative", "sticky", "absolute", "fixed", "static-position"] {
+        relayout(
+            &[
+                &format!("tests/websrc/position/{}.html", fixture),
+                &format!("tests/websrc/position/{}.css", fixture),
+            ],
+            1920.,
+            &[],
+        );
+    }
+}
+
+#[test]
+fn orthogonal_flows_are_laid_out_again_when_viewport_is_resized() {
+    relayout(
+        &[
+            "tests/websrc/directional/writing_mode/mixed-mode-nesting.html",
+            "tests/websrc/directional/writing_mode/mixed-mode-nesting.css",
+        ],
+        700.,
+        &[],
+    )
+    .stderr_is("laid out 17 of 20 boxes\n");
+}
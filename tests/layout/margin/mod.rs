@@ -0,0 +1,13 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn collapse() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/margin/collapse.html")
+        .arg("tests/websrc/margin/collapse.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
@@ -1,4 +1,15 @@
 pub mod directional;
+pub mod display;
+pub mod flex;
+pub mod float;
+pub mod grid;
+pub mod incremental;
+pub mod margin;
+pub mod overflow;
+pub mod position;
+pub mod replaced;
+pub mod sizing;
+pub mod table;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,24 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn scroll_container() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/overflow/scroll-container.html")
+        .arg("tests/websrc/overflow/scroll-container.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn clip() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/overflow/clip.html")
+        .arg("tests/websrc/overflow/clip.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
@@ -0,0 +1,35 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn sizing() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/replaced/sizing.html")
+        .arg("tests/websrc/replaced/sizing.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn block() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/replaced/block.html")
+        .arg("tests/websrc/replaced/block.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn min_max() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/replaced/min-max.html")
+        .arg("tests/websrc/replaced/min-max.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
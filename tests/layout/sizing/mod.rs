@@ -0,0 +1,57 @@
+use crate::util::dump_layout_cmd;
+use insta::assert_snapshot;
+
+#[test]
+fn min_max_width() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/sizing/min-max-width.html")
+        .arg("tests/websrc/sizing/min-max-width.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn min_max_height() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/sizing/min-max-height.html")
+        .arg("tests/websrc/sizing/min-max-height.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn box_sizing() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/sizing/box-sizing.html")
+        .arg("tests/websrc/sizing/box-sizing.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn flex() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/sizing/flex.html")
+        .arg("tests/websrc/sizing/flex.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
+
+#[test]
+fn intrinsic_keywords() {
+    let mut dump_layout_cmd = dump_layout_cmd();
+    dump_layout_cmd
+        .arg("--files")
+        .arg("tests/websrc/sizing/intrinsic-keywords.html")
+        .arg("tests/websrc/sizing/intrinsic-keywords.css")
+        .succeeds();
+    assert_snapshot!(dump_layout_cmd.stdout());
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::util::{dump_display_list_cmd, print_pdf_cmd, render_svg_cmd};
+    use insta::assert_snapshot;
+    use tempdir::TempDir;
+
+    #[test]
+    fn z_index() {
+        let mut dump_display_list_cmd = dump_display_list_cmd();
+        dump_display_list_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/z-index.html")
+            .arg("tests/websrc/paint/z-index.css")
+            .succeeds();
+        assert_snapshot!(dump_display_list_cmd.stdout());
+    }
+
+    #[test]
+    fn opacity_and_clips() {
+        let mut dump_display_list_cmd = dump_display_list_cmd();
+        dump_display_list_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/opacity-and-clips.html")
+            .arg("tests/websrc/paint/opacity-and-clips.css")
+            .succeeds();
+        assert_snapshot!(dump_display_list_cmd.stdout());
+    }
+
+    #[test]
+    fn svg() {
+        let mut render_svg_cmd = render_svg_cmd();
+        render_svg_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/opacity-and-clips.html")
+            .arg("tests/websrc/paint/opacity-and-clips.css")
+            .succeeds();
+        assert_snapshot!(render_svg_cmd.stdout());
+    }
+
+    #[test]
+    fn border_styles() {
+        let mut render_svg_cmd = render_svg_cmd();
+        render_svg_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/border-styles.html")
+            .arg("tests/websrc/paint/border-styles.css")
+            .succeeds();
+        assert_snapshot!(render_svg_cmd.stdout());
+    }
+
+    #[test]
+    fn border_radius() {
+        let mut dump_display_list_cmd = dump_display_list_cmd();
+        dump_display_list_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/border-radius.html")
+            .arg("tests/websrc/paint/border-radius.css")
+            .succeeds();
+        assert_snapshot!(dump_display_list_cmd.stdout());
+    }
+
+    #[test]
+    fn backgrounds() {
+        let mut dump_display_list_cmd = dump_display_list_cmd();
+        dump_display_list_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/backgrounds.html")
+            .arg("tests/websrc/paint/backgrounds.css")
+            .succeeds();
+        assert_snapshot!(dump_display_list_cmd.stdout());
+    }
+
+    #[test]
+    fn box_shadows() {
+        let mut dump_display_list_cmd = dump_display_list_cmd();
+        dump_display_list_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/box-shadows.html")
+            .arg("tests/websrc/paint/box-shadows.css")
+            .succeeds();
+        assert_snapshot!(dump_display_list_cmd.stdout());
+    }
+
+    #[test]
+    fn print_pdf() {
+        let output_dir = TempDir::new("print_pdf").unwrap();
+        let output = output_dir.path().join("pages.pdf");
+        let mut print_pdf_cmd = print_pdf_cmd(&output);
+        print_pdf_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/pages.html")
+            .arg("tests/websrc/paint/pages.css")
+            .succeeds();
+        assert_snapshot!(String::from_utf8_lossy(&std::fs::read(&output).unwrap()));
+    }
+
+    /// The glyphs shown depend on the fonts installed on the system, so only the structure of the
+    /// document is checked, along with the fonts of any glyphs that are shown.
+    #[test]
+    fn print_pdf_text() {
+        let output_dir = TempDir::new("print_pdf_text").unwrap();
+        let output = output_dir.path().join("text.pdf");
+        let mut print_pdf_cmd = print_pdf_cmd(&output);
+        print_pdf_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/pdf-text.html")
+            .arg("tests/websrc/paint/pdf-text.css")
+            .succeeds();
+        let pdf = String::from_utf8_lossy(&std::fs::read(&output).unwrap()).into_owned();
+        assert!(pdf.starts_with("%PDF-1.7\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert_eq!(pdf.matches("/Type /Page /").count(), 1);
+        assert!(pdf.contains("/MediaBox [0 0 300 225]"));
+        if pdf.contains(" Tj ET") {
+            // Every font is either embedded as a CID-keyed font with a map back to the chars of
+            // its glyphs, or is the standard Helvetica font.
+            let embedded = pdf.matches("/Subtype /Type0").count();
+            let standard = pdf.matches("/BaseFont /Helvetica ").count();
+            assert!(embedded + standard > 0);
+            assert_eq!(pdf.matches("/Encoding /Identity-H").count(), embedded);
+            assert_eq!(pdf.matches("/ToUnicode").count(), embedded);
+            assert_eq!(pdf.matches("beginbfchar").count(), embedded);
+        } else {
+            assert!(pdf.contains("/Font << >>"));
+        }
+    }
+
+    /// Opacity is applied to the boxes and image painted within an element with `opacity` as a
+    /// group, by painting them into a transparency group that's painted with the opacity once, so
+    /// the overlapping boxes don't show through one another.
+    #[test]
+    fn print_pdf_opacity_applies_to_group() {
+        let output_dir = TempDir::new("print_pdf_opacity").unwrap();
+        let output = output_dir.path().join("opacity.pdf");
+        let mut print_pdf_cmd = print_pdf_cmd(&output);
+        print_pdf_cmd
+            .arg("--files")
+            .arg("tests/websrc/paint/opacity-group.html")
+            .arg("tests/websrc/paint/opacity-group.css")
+            .succeeds();
+        let pdf = String::from_utf8_lossy(&std::fs::read(&output).unwrap()).into_owned();
+        assert!(pdf.contains("/GS0 << /Type /ExtGState /ca 0.5 /CA 0.5 >>"));
+        assert_eq!(pdf.matches("/Group << /S /Transparency >>").count(), 1);
+        // Only the group is painted with half its alpha, and the backgrounds and the image within
+        // it are painted as they are.
+        assert_eq!(pdf.matches("/GS0 gs").count(), 1);
+        assert!(pdf.contains("q /GS0 gs /Fm0 Do Q"));
+        assert!(pdf.contains("/Im0 Do"));
+    }
+}
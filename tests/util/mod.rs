@@ -3,7 +3,7 @@ use std::env;
 use std::ffi::OsStr;
 use std::fmt::Formatter;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus, Stdio};
 
 pub(crate) static LAYOUT_DUMP_INNER_WINDOW_WIDTH_PX: f32 = 1920.;
@@ -200,3 +200,37 @@ pub fn dump_layout_cmd_scaled(scale_factor: f32) -> CommandUnderTest {
 pub fn dump_layout_cmd() -> CommandUnderTest {
     dump_layout_cmd_scaled(LAYOUT_DUMP_DEFAULT_SCALE_FACTOR)
 }
+
+pub fn dump_display_list_cmd() -> CommandUnderTest {
+    let mut cmd = CommandUnderTest::new();
+    cmd.arg("dump-display-list");
+    cmd.arg("--width");
+    cmd.arg(format!("{}", LAYOUT_DUMP_INNER_WINDOW_WIDTH_PX));
+    cmd.arg("--height");
+    cmd.arg(format!("{}", LAYOUT_DUMP_INNER_WINDOW_HEIGHT_PX));
+    cmd.arg("--scale_factor");
+    cmd.arg(format!("{}", LAYOUT_DUMP_DEFAULT_SCALE_FACTOR));
+    cmd
+}
+
+pub fn render_svg_cmd() -> CommandUnderTest {
+    let mut cmd = CommandUnderTest::new();
+    cmd.arg("render-svg");
+    cmd.arg("--width");
+    cmd.arg(format!("{}", LAYOUT_DUMP_INNER_WINDOW_WIDTH_PX));
+    cmd.arg("--height");
+    cmd.arg(format!("{}", LAYOUT_DUMP_INNER_WINDOW_HEIGHT_PX));
+    cmd.arg("--scale_factor");
+    cmd.arg(format!("{}", LAYOUT_DUMP_DEFAULT_SCALE_FACTOR));
+    cmd
+}
+
+/// Prints a PDF document to `output`.  Pages are sized by `@page` rules, so no window size or scale
+/// factor is given.
+pub fn print_pdf_cmd(output: &Path) -> CommandUnderTest {
+    let mut cmd = CommandUnderTest::new();
+    cmd.arg("print-pdf");
+    cmd.arg("--output");
+    cmd.arg(output);
+    cmd
+}